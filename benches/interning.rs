@@ -0,0 +1,94 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use vrl::value::interning::{self, InterningLimits};
+use vrl::value::Value;
+
+/// Counts the allocations of the benchmark, to report the allocations the pools save.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Log events with the same keys and a few distinct short values.
+fn events() -> Vec<String> {
+    (0..1000)
+        .map(|i| {
+            format!(
+                r#"{{"timestamp": "2024-01-01T00:00:{:02}Z", "level": "{}", "service": "api", "host": {{"name": "web-{}", "region": "eu-west-1"}}, "http": {{"method": "GET", "status": {}, "path": "/items/{i}"}}, "message": "request completed"}}"#,
+                i % 60,
+                ["info", "warn", "error"][i % 3],
+                i % 4,
+                [200, 404, 500][i % 3],
+            )
+        })
+        .collect()
+}
+
+fn parse(events: &[String]) -> Vec<Value> {
+    events
+        .iter()
+        .map(|event| serde_json::from_str(event).unwrap())
+        .collect()
+}
+
+fn allocations_per_event(events: &[String]) -> f64 {
+    // The first pass fills the pools, as the events of a long running process would.
+    drop(parse(events));
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(black_box(parse(events)));
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    #[allow(clippy::cast_precision_loss)]
+    let per_event = allocations as f64 / events.len() as f64;
+    per_event
+}
+
+fn parse_json(c: &mut Criterion) {
+    let events = events();
+
+    for (name, limits) in [
+        ("pooled", InterningLimits::default()),
+        ("unpooled", InterningLimits::DISABLED),
+    ] {
+        interning::set_limits(limits);
+        interning::clear();
+        println!(
+            "vrl/interning/parse_json/{name}: {:.1} allocations per event",
+            allocations_per_event(&events)
+        );
+
+        c.bench_function(&format!("vrl/interning/parse_json/{name}"), |b| {
+            b.iter(|| parse(black_box(&events)));
+        });
+    }
+
+    interning::set_limits(InterningLimits::default());
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = parse_json
+);
+criterion_main!(benches);
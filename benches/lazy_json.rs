@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use vrl::compiler::{runtime::Runtime, LazyJsonTarget, TargetValue, TimeZone};
+use vrl::value::{Secrets, Value};
+
+/// A document with many fields the filter doesn't read.
+fn document() -> String {
+    let fields = (0..200)
+        .map(|i| format!(r#""field_{i}": {{"text": "value {i}", "numbers": [1, 2, 3]}}"#))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(r#"{{"level": "error", "host": {{"name": "web-1"}}, {fields}}}"#)
+}
+
+fn filter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vrl/lazy_json/filter");
+    let program = vrl::compiler::compile(
+        r#".level == "error" && .host.name == "web-1""#,
+        &vrl::stdlib::all(),
+    )
+    .unwrap()
+    .program;
+    let document = document();
+    let timezone = TimeZone::default();
+
+    group.bench_function("eager", |b| {
+        b.iter(|| {
+            let mut target = TargetValue {
+                value: serde_json::from_str::<Value>(&document).unwrap(),
+                metadata: Value::Object(Default::default()),
+                secrets: Secrets::new(),
+            };
+            black_box(
+                Runtime::default()
+                    .resolve(&mut target, &program, &timezone)
+                    .unwrap(),
+            )
+        });
+    });
+
+    group.bench_function("lazy", |b| {
+        b.iter(|| {
+            let mut target = LazyJsonTarget::new(&document, program.info()).unwrap();
+            black_box(
+                Runtime::default()
+                    .resolve(&mut target, &program, &timezone)
+                    .unwrap(),
+            )
+        });
+    });
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().noise_threshold(0.05);
+    targets = filter
+);
+criterion_main!(benches);
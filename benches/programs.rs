@@ -0,0 +1,27 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use vrl::test::bench::{Benchmark, BENCHMARKS};
+
+fn programs(c: &mut Criterion) {
+    let functions = vrl::stdlib::all();
+    let mut group = c.benchmark_group("vrl/programs");
+
+    for benchmark in BENCHMARKS {
+        let program = benchmark.compile(&functions).unwrap();
+        let event = benchmark.event();
+
+        group.bench_function(benchmark.name, |b| {
+            b.iter_batched(
+                || event.clone(),
+                |event| Benchmark::run(&program, event).unwrap(),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().noise_threshold(0.05);
+    targets = programs
+);
+criterion_main!(benches);
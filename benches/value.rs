@@ -0,0 +1,83 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use vrl::path::{parse_value_path, OwnedValuePath};
+use vrl::value;
+use vrl::value::Value;
+
+fn event() -> Value {
+    value!({
+        "message": "GET /index.html 200",
+        "host": { "name": "web-1", "ip": "10.0.0.1" },
+        "http": {
+            "request": { "method": "GET", "headers": [{ "name": "accept", "value": "*/*" }] },
+            "response": { "status": 200 },
+        },
+        "tags": ["web", "prod"],
+    })
+}
+
+fn paths() -> Vec<(&'static str, OwnedValuePath)> {
+    [
+        "message",
+        "host.name",
+        "http.request.headers[0].value",
+        "tags[1]",
+    ]
+    .into_iter()
+    .map(|path| (path, parse_value_path(path).unwrap()))
+    .collect()
+}
+
+fn get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vrl/value/get");
+    let event = event();
+
+    for (name, path) in paths() {
+        group.bench_function(name, |b| b.iter(|| black_box(event.get(&path))));
+    }
+}
+
+fn insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vrl/value/insert");
+    let event = event();
+
+    for (name, path) in paths() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || event.clone(),
+                |mut event| event.insert(&path, "value"),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
+    let path = parse_value_path("a.b[3].c.d").unwrap();
+    group.bench_function("new nested path", |b| {
+        b.iter_batched(
+            || event.clone(),
+            |mut event| event.insert(&path, "value"),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+fn remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vrl/value/remove");
+    let event = event();
+
+    for (name, path) in paths() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || event.clone(),
+                |mut event| event.remove(&path, true),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().noise_threshold(0.05);
+    targets = get, insert, remove
+);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 extern crate lalrpop;
 
 use std::{
+    collections::BTreeMap,
     env,
     fmt::Write as fmt_write,
     fs::{self, File},
@@ -10,6 +11,7 @@ use std::{
 
 fn main() {
     read_grok_patterns();
+    read_schema_fields();
 
     println!("cargo:rerun-if-changed=src/parser/parser.lalrpop");
     lalrpop::Configuration::new()
@@ -51,3 +53,88 @@ fn read_grok_patterns() {
     let dest_path = Path::new(&out_dir).join("patterns.rs");
     fs::write(dest_path, output).expect("'patterns.rs' wasn't generated");
 }
+
+/// Reads the ECS and OCSF field tables in `data/schemas` into the static `ECS_FIELDS` and
+/// `OCSF_CLASSES` variables, sorted by name so they can be binary searched.
+fn read_schema_fields() {
+    println!("cargo:rerun-if-changed=data/schemas");
+
+    fn entries(path: &str) -> Vec<String> {
+        let file = File::open(path).unwrap_or_else(|_| panic!("can't read '{path}'"));
+        BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .map(|line| line.trim().to_owned())
+            .filter(|line| !line.starts_with('#') && !line.is_empty())
+            .collect()
+    }
+
+    fn field(line: &str) -> (String, String) {
+        let (name, kind) = line
+            .split_once(' ')
+            .expect("field should follow the format 'name type'");
+        (name.to_owned(), kind.trim().to_owned())
+    }
+
+    fn write_fields(output: &mut String, fields: &BTreeMap<String, String>) {
+        output.push_str("&[");
+        for (name, kind) in fields {
+            write!(output, "(\"{name}\", \"{kind}\"),").expect("can't write field");
+        }
+        output.push(']');
+    }
+
+    let mut ecs = BTreeMap::new();
+    for line in entries("data/schemas/ecs.txt") {
+        let (name, kind) = field(&line);
+        assert!(
+            ecs.insert(name.clone(), kind).is_none(),
+            "duplicate ECS field '{name}'"
+        );
+    }
+
+    let mut shared = BTreeMap::new();
+    let mut classes: BTreeMap<String, (u32, BTreeMap<String, String>)> = BTreeMap::new();
+    let mut current = None;
+    for line in entries("data/schemas/ocsf.txt") {
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = match header.split_once(' ') {
+                Some((class, uid)) => {
+                    let uid = uid.parse().expect("class uid should be an integer");
+                    classes.insert(class.to_owned(), (uid, BTreeMap::new()));
+                    Some(class.to_owned())
+                }
+                None if header == "*" => None,
+                None => panic!("class header should follow the format '[class_name class_uid]'"),
+            };
+            continue;
+        }
+
+        let (name, kind) = field(&line);
+        let fields = match &current {
+            Some(class) => &mut classes.get_mut(class).expect("class exists").1,
+            None => &mut shared,
+        };
+        assert!(
+            fields.insert(name.clone(), kind).is_none(),
+            "duplicate OCSF attribute '{name}'"
+        );
+    }
+
+    let mut output = "static ECS_FIELDS: &[(&str, &str)] = ".to_string();
+    write_fields(&mut output, &ecs);
+    output.push_str(";\n\nstatic OCSF_CLASSES: &[(&str, u32, &[(&str, &str)])] = &[");
+    for (class, (uid, mut fields)) in classes {
+        for (name, kind) in &shared {
+            fields.entry(name.clone()).or_insert_with(|| kind.clone());
+        }
+        write!(output, "(\"{class}\", {uid}, ").expect("can't write class");
+        write_fields(&mut output, &fields);
+        output.push_str("),");
+    }
+    output.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR isn't defined");
+    let dest_path = Path::new(&out_dir).join("schema_fields.rs");
+    fs::write(dest_path, output).expect("'schema_fields.rs' wasn't generated");
+}
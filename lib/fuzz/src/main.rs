@@ -15,14 +15,6 @@ use vrl::value::Secrets;
 fn main() {
     fuzz!(|data: &[u8]| {
         if let Ok(src) = std::str::from_utf8(data) {
-            if src.contains('\\') {
-                // skipping known issues with invalid escapes
-                return;
-            }
-            if src.len() > 10_000 {
-                // skipping known issues with highly nested expressions
-                return;
-            }
             fuzz(src);
         }
     });
@@ -35,6 +27,7 @@ fn fuzz(src: &str) {
     let config = CompileConfig::default();
 
     if let Ok(result) = vrl::compiler::compile_with_external(src, &fns, &external, config) {
+        let program = result.program;
         let mut target = TargetValue {
             value: value!({}),
             metadata: Value::Object(BTreeMap::new()),
@@ -44,17 +37,44 @@ fn fuzz(src: &str) {
         let mut state = RuntimeState::default();
         let timezone = TimeZone::default();
         let mut ctx = Context::new(&mut target, &mut state, &timezone);
-        if let Ok(_value) = result.program.resolve(&mut ctx) {
-            let type_info = result.program.final_type_info();
-            let expected_kind = type_info.state.external.target_kind();
-            let actual_kind = Kind::from(target.value);
-            if let Err(path) = expected_kind.is_superset(&actual_kind) {
-                panic!("Value doesn't match at path: '{}'\n\nType at path = {:?}\n\nDefinition at path = {:?}",
-                    path,
-                    actual_kind.at_path(&path).debug_info(),
-                    expected_kind.at_path(&path).debug_info());
+        let value = match program.resolve(&mut ctx) {
+            Ok(value) | Err(ExpressionError::Return { value, .. }) => value,
+            Err(ExpressionError::Abort { .. }) => {
+                assert!(
+                    program.info().abortable,
+                    "Program aborted, but isn't abortable"
+                );
+                return;
             }
-            // TODO: check metadata type, result type, fallibility, abortability
-        }
+            Err(error) => {
+                assert!(
+                    program.info().fallible,
+                    "Program failed, but isn't fallible: {error}"
+                );
+                return;
+            }
+        };
+
+        let type_info = program.final_type_info();
+        check_kind(
+            "Target",
+            type_info.state.external.target_kind(),
+            &Kind::from(target.value),
+        );
+        check_kind(
+            "Metadata",
+            type_info.state.external.metadata_kind(),
+            &Kind::from(target.metadata),
+        );
+        check_kind("Result", type_info.result.kind(), &Kind::from(value));
+    }
+}
+
+fn check_kind(name: &str, expected_kind: &Kind, actual_kind: &Kind) {
+    if let Err(path) = expected_kind.is_superset(actual_kind) {
+        panic!("{name} doesn't match at path: '{}'\n\nType at path = {:?}\n\nDefinition at path = {:?}",
+            path,
+            actual_kind.at_path(&path).debug_info(),
+            expected_kind.at_path(&path).debug_info());
     }
 }
@@ -2,7 +2,7 @@ use chrono_tz::Tz;
 use clap::Parser;
 use glob::glob;
 
-use vrl::compiler::{CompileConfig, TimeZone, VrlRuntime};
+use vrl::compiler::{CompileConfig, MemoryStateStore, TimeZone, VrlRuntime};
 use vrl::test::{get_tests_from_functions, run_tests, test_dir, Test, TestConfig};
 
 #[cfg(not(target_env = "msvc"))]
@@ -36,6 +36,12 @@ pub struct Cmd {
     #[clap(short = 'z', long)]
     timezone: Option<String>,
 
+    /// Rewrite the `.json` snapshots of snapshot tests with their current
+    /// results. Can also be enabled with the `UPDATE_SNAPSHOTS` environment
+    /// variable.
+    #[clap(short, long)]
+    update_snapshots: bool,
+
     /// Should we use the VM to evaluate the VRL
     #[clap(short, long = "runtime", default_value_t)]
     runtime: VrlRuntime,
@@ -75,6 +81,7 @@ fn main() {
         verbose: cmd.verbose,
         no_diff: cmd.no_diff,
         timings: cmd.timings,
+        update_snapshots: cmd.update_snapshots || std::env::var_os("UPDATE_SNAPSHOTS").is_some(),
         runtime: cmd.runtime,
         timezone: cmd.timezone(),
     };
@@ -83,7 +90,11 @@ fn main() {
         tests,
         &cfg,
         &vrl::stdlib::all(),
-        || (CompileConfig::default(), ()),
+        || {
+            let mut config = CompileConfig::default();
+            config.set_state_store(MemoryStateStore::default());
+            (config, ())
+        },
         |_| {},
     );
 }
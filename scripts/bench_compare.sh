@@ -0,0 +1,21 @@
+#!/bin/bash
+set -euo pipefail
+
+# Compares the program and value benchmarks of the working tree against a git
+# ref, `origin/main` by default. Criterion reports the change of each benchmark
+# and whether it's significant.
+BASE_REF="${1:-origin/main}"
+BENCHES=(--features test_framework --bench programs --bench value)
+
+WORKTREE="$(mktemp -d)"
+trap 'git worktree remove --force "$WORKTREE"' EXIT
+git worktree add --detach "$WORKTREE" "$BASE_REF"
+
+# Sharing the target directory puts both runs' results in the same place.
+export CARGO_TARGET_DIR="${CARGO_TARGET_DIR:-$(pwd)/target}"
+
+echo "Benchmark $BASE_REF"
+(cd "$WORKTREE" && cargo bench "${BENCHES[@]}" -- --save-baseline base)
+
+echo "Benchmark the working tree against $BASE_REF"
+cargo bench "${BENCHES[@]}" -- --baseline base
@@ -2,25 +2,29 @@ use crate::compiler::TargetValueRef;
 use std::{
     collections::BTreeMap,
     fs::File,
-    io::{self, Read},
+    io::{self, BufRead, BufReader, Read},
     iter::IntoIterator,
+    num::NonZeroUsize,
     path::PathBuf,
+    thread,
 };
 
 use crate::compiler::runtime::Runtime;
 use crate::compiler::state::RuntimeState;
 use crate::compiler::TimeZone;
 use crate::compiler::{
-    compile_with_state, CompilationResult, CompileConfig, Function, Program, Target, TypeState,
-    VrlRuntime,
+    compile_with_state, lint, CompilationResult, CompileConfig, Function, LintLevel,
+    MemoryStateStore, Program, ProgramInfo, Target, TypeState, VrlRuntime,
 };
-use crate::diagnostic::Formatter;
+use crate::diagnostic::{DiagnosticList, Formatter};
 use crate::owned_metadata_path;
 use crate::value::Secrets;
 use crate::value::Value;
 use clap::Parser;
 
 use super::repl;
+use super::stream;
+use super::trace::Tracer;
 use super::Error;
 
 #[derive(Parser, Debug)]
@@ -44,6 +48,21 @@ pub struct Opts {
     #[arg(short = 'o', long)]
     print_object: bool,
 
+    /// The metadata of the event object(s), as a JSON object. Programs read it with metadata
+    /// paths, such as `%kafka.topic`.
+    #[arg(long)]
+    metadata: Option<String>,
+
+    /// The secrets of the event object(s), as a JSON object of strings. Programs read them with
+    /// `get_secret`.
+    #[arg(long)]
+    secrets: Option<String>,
+
+    /// Print the resulting target, the (modified) event object and its metadata, instead of the
+    /// result of the final expression.
+    #[arg(long, conflicts_with("print_object"))]
+    emit_target: bool,
+
     /// The timezone used to parse dates.
     #[arg(short = 'z', long)]
     timezone: Option<String>,
@@ -55,6 +74,75 @@ pub struct Opts {
     // Should the CLI emit warnings
     #[arg(long = "print-warnings")]
     print_warnings: bool,
+
+    /// Print compile-time artifacts of the program instead of running it. Can be repeated to
+    /// print multiple artifacts.
+    #[arg(long, value_enum)]
+    emit: Vec<Emit>,
+
+    /// Stream the input as NDJSON: events are processed in parallel as they are read, and their
+    /// results are written as NDJSON. Runtime errors are written to stderr with the line number of
+    /// the event.
+    #[arg(long, conflicts_with_all(["emit", "lint"]))]
+    stream: bool,
+
+    /// The number of threads processing events in streaming mode. Defaults to the number of CPUs.
+    #[arg(long, requires("stream"))]
+    workers: Option<NonZeroUsize>,
+
+    /// Write the results in streaming mode as soon as they are ready, instead of in input order.
+    #[arg(long, requires("stream"))]
+    unordered: bool,
+
+    /// Check the program for likely mistakes with every lint rule enabled, instead of running it.
+    /// Fails if any lint is reported.
+    #[arg(long, conflicts_with("emit"))]
+    lint: bool,
+
+    /// Print a trace of the program's execution to stderr while running it: the values assigned
+    /// to each target, the functions called with their results, and the branches taken.
+    #[arg(long, conflicts_with_all(["stream", "emit", "lint"]))]
+    trace: bool,
+}
+
+/// What is printed for each event object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Output {
+    /// The result of the final expression.
+    Result,
+
+    /// The (modified) event object.
+    Object,
+
+    /// The (modified) event object and its metadata.
+    Target,
+}
+
+impl Output {
+    pub(crate) fn select(self, result: Value, object: &Value, metadata: &Value) -> Value {
+        match self {
+            Output::Result => result,
+            Output::Object => object.clone(),
+            Output::Target => Value::from(BTreeMap::from([
+                ("value".into(), object.clone()),
+                ("metadata".into(), metadata.clone()),
+            ])),
+        }
+    }
+}
+
+/// Compile-time artifacts of a program, for debugging.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Emit {
+    /// The parsed abstract syntax tree.
+    Ast,
+
+    /// The inferred types of the event, metadata and variables after the program ran, and of its
+    /// result.
+    Types,
+
+    /// The paths read and written by the program, and whether it can fail or abort.
+    Info,
 }
 
 impl Opts {
@@ -92,6 +180,57 @@ impl Opts {
         }
     }
 
+    fn read_metadata(&self) -> Result<Value, Error> {
+        match self.metadata.as_deref() {
+            Some(json) => match serde_to_vrl(serde_json::from_str(json)?) {
+                metadata @ Value::Object(_) => Ok(metadata),
+                _ => Err(Error::Parse("metadata must be a JSON object".to_owned())),
+            },
+            None => Ok(Value::Object(BTreeMap::new())),
+        }
+    }
+
+    fn read_secrets(&self) -> Result<Secrets, Error> {
+        let mut secrets = Secrets::new();
+        if let Some(json) = self.secrets.as_deref() {
+            let values: BTreeMap<String, String> = serde_json::from_str(json)?;
+            for (key, value) in values {
+                secrets.insert(key, value);
+            }
+        }
+
+        Ok(secrets)
+    }
+
+    fn output(&self) -> Output {
+        if self.emit_target {
+            Output::Target
+        } else if self.print_object {
+            Output::Object
+        } else {
+            Output::Result
+        }
+    }
+
+    fn stream(&self, program: &Program, timezone: TimeZone) -> Result<(), Error> {
+        let options = stream::Options {
+            workers: self
+                .workers
+                .unwrap_or_else(|| thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)),
+            ordered: !self.unordered,
+            output: self.output(),
+            metadata: self.read_metadata()?,
+            secrets: self.read_secrets()?,
+            timezone,
+        };
+        let input: Box<dyn BufRead + Send> = match self.input_file.as_ref() {
+            Some(path) => Box::new(BufReader::new(File::open(path)?)),
+            None => Box::new(BufReader::new(io::stdin())),
+        };
+
+        stream::run(program, &options, input, io::stdout().lock(), io::stderr())
+    }
+
     fn should_open_repl(&self) -> bool {
         self.program.is_none() && self.program_file.is_none()
     }
@@ -123,14 +262,27 @@ fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Erro
             default_objects()
         };
 
-        repl(repl_objects, tz, opts.runtime, stdlib_functions)
+        repl(
+            repl_objects,
+            opts.read_metadata()?,
+            opts.read_secrets()?,
+            tz,
+            opts.runtime,
+            stdlib_functions,
+        )
     } else {
-        let objects = opts.read_into_objects()?;
         let source = opts.read_program()?;
+        if !opts.emit.is_empty() {
+            return emit(&source, &opts.emit);
+        }
+        if opts.lint {
+            return run_lints(&source);
+        }
 
         // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
         let mut config = CompileConfig::default();
         config.set_read_only_path(owned_metadata_path!("vector"), true);
+        config.set_state_store(MemoryStateStore::default());
 
         let state = TypeState::default();
 
@@ -138,15 +290,9 @@ fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Erro
             program,
             warnings,
             config: _,
-        } = compile_with_state(
-            &source,
-            &crate::stdlib::all(),
-            &state,
-            CompileConfig::default(),
-        )
-        .map_err(|diagnostics| {
-            Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
-        })?;
+        } = compile_with_state(&source, &crate::stdlib::all(), &state, config).map_err(
+            |diagnostics| Error::Parse(Formatter::new(&source, diagnostics).colored().to_string()),
+        )?;
 
         #[allow(clippy::print_stderr)]
         if opts.print_warnings {
@@ -154,24 +300,30 @@ fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Erro
             eprintln!("{warnings}")
         }
 
+        if opts.stream {
+            return opts.stream(&program, tz);
+        }
+
+        let objects = opts.read_into_objects()?;
+        let initial_metadata = opts.read_metadata()?;
+        let initial_secrets = opts.read_secrets()?;
+
         for mut object in objects {
-            let mut metadata = Value::Object(BTreeMap::new());
-            let mut secrets = Secrets::new();
+            let mut metadata = initial_metadata.clone();
+            let mut secrets = initial_secrets.clone();
             let mut target = TargetValueRef {
                 value: &mut object,
                 metadata: &mut metadata,
                 secrets: &mut secrets,
             };
             let state = RuntimeState::default();
-            let runtime = Runtime::new(state);
+            let mut runtime = Runtime::new(state);
+            if opts.trace {
+                runtime.set_observer(Tracer::default());
+            }
 
-            let result = execute(&mut target, &program, tz, runtime, opts.runtime).map(|v| {
-                if opts.print_object {
-                    object.to_string()
-                } else {
-                    v.to_string()
-                }
-            });
+            let result = execute(&mut target, &program, tz, runtime, opts.runtime)
+                .map(|v| opts.output().select(v, &object, &metadata).to_string());
 
             #[allow(clippy::print_stdout)]
             #[allow(clippy::print_stderr)]
@@ -185,9 +337,116 @@ fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Erro
     }
 }
 
+#[allow(clippy::print_stdout)]
+fn emit(source: &str, artifacts: &[Emit]) -> Result<(), Error> {
+    let render = |diagnostics: DiagnosticList| {
+        Error::Parse(Formatter::new(source, diagnostics).colored().to_string())
+    };
+    let compile = || {
+        compile_with_state(
+            source,
+            &crate::stdlib::all(),
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map(|result| result.program)
+        .map_err(render)
+    };
+
+    for artifact in artifacts {
+        let output = match artifact {
+            Emit::Ast => {
+                let ast = crate::parser::parse(source)
+                    .map_err(|err| render(DiagnosticList::from(vec![Box::new(err) as Box<_>])))?;
+                format!("{ast:#?}")
+            }
+            Emit::Types => type_report(&compile()?),
+            Emit::Info => info_report(compile()?.info()),
+        };
+        println!("{output}");
+    }
+
+    Ok(())
+}
+
+fn run_lints(source: &str) -> Result<(), Error> {
+    let mut config = CompileConfig::default();
+    for rule in lint::rules() {
+        config.set_lint_level(rule.name(), LintLevel::Warn);
+    }
+
+    let render =
+        |diagnostics| Error::Parse(Formatter::new(source, diagnostics).colored().to_string());
+    let CompilationResult { warnings, .. } =
+        compile_with_state(source, &crate::stdlib::all(), &TypeState::default(), config)
+            .map_err(render)?;
+
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(render(warnings))
+    }
+}
+
+fn type_report(program: &Program) -> String {
+    let info = program.final_type_info();
+    let fallibility = if info.result.is_fallible() {
+        "fallible"
+    } else {
+        "infallible"
+    };
+
+    let mut variables = info
+        .state
+        .local
+        .variable_idents()
+        .filter_map(|ident| {
+            let details = info.state.local.variable(ident)?;
+            Some(format!("{ident}: {}", details.type_def.kind()))
+        })
+        .collect::<Vec<_>>();
+    variables.sort();
+
+    let mut lines = vec![
+        format!("result: {} ({fallibility})", info.result.kind()),
+        format!("event: {}", info.state.external.target_kind()),
+        format!("metadata: {}", info.state.external.metadata_kind()),
+    ];
+    lines.extend(list("variables", variables));
+    lines.join("\n")
+}
+
+fn info_report(info: &ProgramInfo) -> String {
+    let mut lines = vec![
+        format!("fallible: {}", info.fallible),
+        format!("abortable: {}", info.abortable),
+    ];
+    lines.extend(list("reads", &info.target_queries));
+    lines.extend(list("writes", &info.target_assignments));
+    lines.extend(list("functions", &info.functions));
+    let mut secrets = info.secret_keys.clone();
+    if info.dynamic_secret_keys {
+        secrets.push("<dynamic>".to_owned());
+    }
+    lines.extend(list("secrets", secrets));
+    lines.join("\n")
+}
+
+/// Formats a titled list with one indented item per line.
+fn list<T: std::fmt::Display>(title: &str, items: impl IntoIterator<Item = T>) -> Vec<String> {
+    let mut lines = vec![format!("{title}:")];
+    lines.extend(items.into_iter().map(|item| format!("  {item}")));
+    if lines.len() == 1 {
+        lines[0].push_str(" none");
+    }
+    lines
+}
+
 #[allow(clippy::unnecessary_wraps)]
 fn repl(
     objects: Vec<Value>,
+    metadata: Value,
+    secrets: Secrets,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
     stdlib_functions: Vec<Box<dyn Function>>,
@@ -198,8 +457,8 @@ fn repl(
         .into_iter()
         .map(|value| TargetValue {
             value,
-            metadata: Value::Object(BTreeMap::new()),
-            secrets: Secrets::new(),
+            metadata: metadata.clone(),
+            secrets: secrets.clone(),
         })
         .collect();
 
@@ -220,7 +479,7 @@ fn execute(
     }
 }
 
-fn serde_to_vrl(value: serde_json::Value) -> Value {
+pub(super) fn serde_to_vrl(value: serde_json::Value) -> Value {
     use serde_json::Value as JsonValue;
 
     match value {
@@ -0,0 +1,225 @@
+use crate::compiler::runtime::Runtime;
+use crate::compiler::state::RuntimeState;
+use crate::compiler::{ExpressionError, Program, TargetValue, TimeZone};
+use crate::diagnostic::Span;
+use crate::value::Value;
+use indoc::indoc;
+use rustyline::error::ReadlineError;
+
+use super::trace::Tracer;
+
+/// Steps through a program one top-level statement at a time, keeping the
+/// target and variables from before each statement to be able to step back.
+struct Debugger<'a> {
+    source: &'a str,
+    program: &'a Program,
+
+    /// The span of each top-level statement in the source.
+    spans: Vec<Span>,
+
+    /// The index of the next statement to run.
+    next: usize,
+
+    /// The target and variables before each statement that ran.
+    history: Vec<(TargetValue, RuntimeState)>,
+}
+
+/// The outcome of running a statement.
+enum Step {
+    /// The statement resolved to a value.
+    Resolved(Value),
+
+    /// The statement returned early, ending the program.
+    Returned(Value),
+
+    /// The statement failed, which leaves the target and variables as they
+    /// were before it ran.
+    Failed(String),
+
+    /// All statements ran.
+    Done,
+}
+
+impl<'a> Debugger<'a> {
+    fn new(source: &'a str, program: &'a Program) -> Self {
+        // The program compiled, so the source parses into as many statements.
+        let spans = crate::parser::parse(source)
+            .map(|ast| ast.iter().map(|node| node.span()).collect())
+            .unwrap_or_default();
+
+        Self {
+            source,
+            program,
+            spans,
+            next: 0,
+            history: vec![],
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.next >= self.program.statement_count()
+    }
+
+    /// The position and source of the next statement, such as
+    /// `[2/3] line 1: .b = 2`.
+    fn location(&self) -> String {
+        if self.is_done() {
+            return "end of program".to_owned();
+        }
+
+        let position = format!("[{}/{}]", self.next + 1, self.program.statement_count());
+        match self.spans.get(self.next) {
+            Some(span) => {
+                let line = self.source[..span.start()].matches('\n').count() + 1;
+                let statement = &self.source[span.start()..span.end()];
+                format!("{position} line {line}: {statement}")
+            }
+            None => position,
+        }
+    }
+
+    fn step(
+        &mut self,
+        target: &mut TargetValue,
+        runtime: &mut Runtime,
+        timezone: &TimeZone,
+    ) -> Step {
+        if self.is_done() {
+            return Step::Done;
+        }
+
+        let snapshot = (target.clone(), runtime.state().clone());
+        match runtime.resolve_statement(target, self.program, self.next, timezone) {
+            Ok(value) => {
+                self.history.push(snapshot);
+                self.next += 1;
+                Step::Resolved(value)
+            }
+            Err(ExpressionError::Return { value, .. }) => {
+                self.history.push(snapshot);
+                self.next = self.program.statement_count();
+                Step::Returned(value)
+            }
+            Err(error) => {
+                let (old_target, old_state) = snapshot;
+                *target = old_target;
+                runtime.set_state(old_state);
+                Step::Failed(error.to_string())
+            }
+        }
+    }
+
+    /// Undoes the last statement that ran, returning `false` if none did.
+    fn back(&mut self, target: &mut TargetValue, runtime: &mut Runtime) -> bool {
+        let Some((old_target, old_state)) = self.history.pop() else {
+            return false;
+        };
+
+        *target = old_target;
+        runtime.set_state(old_state);
+        self.next = self.history.len();
+        true
+    }
+}
+
+/// Runs the debugger on a compiled program, reading commands with
+/// `read_line` until the user quits. The changes made by the statements that
+/// ran are kept.
+#[allow(clippy::print_stdout)]
+pub(crate) fn run(
+    source: &str,
+    program: &Program,
+    target: &mut TargetValue,
+    runtime: &mut Runtime,
+    timezone: &TimeZone,
+    mut read_line: impl FnMut(&str) -> Result<String, ReadlineError>,
+) -> Result<(), ReadlineError> {
+    let mut debugger = Debugger::new(source, program);
+    runtime.set_observer(Tracer::default());
+
+    println!("{}", debugger.location());
+    loop {
+        let line = match read_line("debug> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(err) => {
+                runtime.take_observer();
+                return Err(err);
+            }
+        };
+
+        let output = match line.trim() {
+            "" | "s" | "step" => {
+                let step = debugger.step(target, runtime, timezone);
+                describe(&step, &debugger)
+            }
+            "c" | "continue" => {
+                let mut output = vec![];
+                loop {
+                    let step = debugger.step(target, runtime, timezone);
+                    let stop = matches!(step, Step::Failed(_) | Step::Done) || debugger.is_done();
+                    output.push(describe(&step, &debugger));
+                    if stop {
+                        break;
+                    }
+                }
+                output.join("\n")
+            }
+            "b" | "back" => {
+                if debugger.back(target, runtime) {
+                    debugger.location()
+                } else {
+                    "already at the start of the program".to_owned()
+                }
+            }
+            "w" | "where" => debugger.location(),
+            "." | "event" => target.value.to_string(),
+            "%" | "metadata" => target.metadata.to_string(),
+            "v" | "vars" => variables(runtime.state()),
+            "h" | "help" => DEBUG_HELP_TEXT.trim_end().to_owned(),
+            "q" | "quit" | "exit" => break,
+            command => format!("unknown debugger command: {command}, see `help`"),
+        };
+
+        println!("{output}\n");
+    }
+
+    runtime.take_observer();
+    Ok(())
+}
+
+/// Describes the result of a statement, followed by the next statement.
+fn describe(step: &Step, debugger: &Debugger<'_>) -> String {
+    match step {
+        Step::Resolved(value) => format!("=> {value}\n{}", debugger.location()),
+        Step::Returned(value) => format!("returned {value}\n{}", debugger.location()),
+        Step::Failed(error) => format!("error: {error}\n{}", debugger.location()),
+        Step::Done => debugger.location(),
+    }
+}
+
+fn variables(state: &RuntimeState) -> String {
+    let mut variables = state
+        .variables()
+        .map(|(ident, value)| format!("{ident} = {value}"))
+        .collect::<Vec<_>>();
+    variables.sort();
+
+    if variables.is_empty() {
+        "no variables are set".to_owned()
+    } else {
+        variables.join("\n")
+    }
+}
+
+const DEBUG_HELP_TEXT: &str = indoc! {"
+    Debugger commands:
+      step      Run the next statement, also [enter] (alias: s)
+      continue  Run the remaining statements (alias: c)
+      back      Undo the last statement that ran (alias: b)
+      where     Display the next statement (alias: w)
+      event     Display the event object (alias: .)
+      metadata  Display the metadata (alias: %)
+      vars      Display the variables (alias: v)
+      quit      Leave the debugger, keeping the changes made so far (alias: q)
+"};
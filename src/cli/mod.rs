@@ -13,7 +13,10 @@
 )]
 
 pub mod cmd;
+mod debug;
 mod repl;
+mod stream;
+mod trace;
 
 use crate::compiler::runtime::Terminate;
 pub use cmd::{cmd, Opts};
@@ -6,9 +6,12 @@ use std::rc::Rc;
 use crate::compiler::runtime::Runtime;
 use crate::compiler::state::{RuntimeState, TypeState};
 use crate::compiler::TimeZone;
-use crate::compiler::{compile_with_state, CompileConfig, Function, Program, Target, VrlRuntime};
+use crate::compiler::{
+    compile_with_state, CompileConfig, Function, MemoryStateStore, Program, Target, VrlRuntime,
+};
 use crate::diagnostic::Formatter;
 use crate::owned_metadata_path;
+use crate::parser::Error as ParseError;
 use crate::value::Secrets;
 use crate::value::Value;
 use indoc::indoc;
@@ -16,30 +19,38 @@ use once_cell::sync::Lazy;
 use prettytable::{format, Cell, Row, Table};
 use regex::Regex;
 use rustyline::{
-    completion::Completer,
+    completion::{Completer, Pair},
     error::ReadlineError,
     highlight::{CmdKind, Highlighter, MatchingBracketHighlighter},
-    hint::{Hinter, HistoryHinter},
+    hint::{Hint, Hinter, HistoryHinter},
     history::MemHistory,
     validate::{self, ValidationResult, Validator},
     Context, Editor, Helper,
 };
 
+use super::debug;
+
 // Create a list of all possible error values for potential docs lookup
 static ERRORS: Lazy<Vec<String>> = Lazy::new(|| {
     [
         100, 101, 102, 103, 104, 105, 106, 107, 108, 110, 203, 204, 205, 206, 207, 208, 209, 300,
         301, 302, 303, 304, 305, 306, 307, 308, 309, 310, 311, 312, 313, 314, 400, 401, 402, 403,
-        601, 620, 630, 640, 650, 651, 652, 660, 701,
+        601, 620, 630, 640, 650, 651, 652, 653, 660, 661, 701,
     ]
     .iter()
     .map(std::string::ToString::to_string)
     .collect()
 });
 
+/// The state store shared by all programs of the session, so that functions
+/// such as `throttle` keep counting across inputs.
+static STATE_STORE: Lazy<MemoryStateStore> = Lazy::new(MemoryStateStore::default);
+
 const DOCS_URL: &str = "https://vector.dev/docs/reference/vrl";
 const ERRORS_URL_ROOT: &str = "https://errors.vrl.dev";
 const RESERVED_TERMS: &[&str] = &[
+    ":type",
+    ":debug",
     "next",
     "prev",
     "exit",
@@ -66,7 +77,9 @@ pub(crate) fn run(
 
     let mut rt = Runtime::new(RuntimeState::default());
     let mut rl = Editor::<Repl, MemHistory>::new()?;
-    rl.set_helper(Some(Repl::new(stdlib_functions.clone())));
+    let mut helper = Repl::new(stdlib_functions.clone());
+    helper.update(&objects[index], &state);
+    rl.set_helper(Some(helper));
 
     #[allow(clippy::print_stdout)]
     {
@@ -86,6 +99,36 @@ pub(crate) fn run(
             Ok(line) if error_docs_regex.is_match(line) => show_error_docs(line, &error_docs_regex),
             // Capture "help docs <func_name>"
             Ok(line) if func_docs_regex.is_match(line) => show_func_docs(line, &func_docs_regex),
+            // Capture ":type <expression>"
+            Ok(line) if line.starts_with(":type ") => {
+                rl.add_history_entry(line)?;
+                show_type(&line[":type ".len()..], &state, &stdlib_functions);
+            }
+            // Capture ":debug <program>"
+            Ok(line) if line.starts_with(":debug ") => {
+                rl.add_history_entry(line)?;
+
+                let source = &line[":debug ".len()..];
+                match compile(source, &state, &stdlib_functions) {
+                    Ok(program) => {
+                        state = program.final_type_info().state;
+                        debug::run(
+                            source,
+                            &program,
+                            objects.get_mut(index).expect("object should exist"),
+                            &mut rt,
+                            &timezone,
+                            |prompt| rl.readline(prompt),
+                        )?;
+
+                        if let Some(helper) = rl.helper_mut() {
+                            helper.update(&objects[index], &state);
+                        }
+                    }
+                    #[allow(clippy::print_stdout)]
+                    Err(err) => println!("{err}\n"),
+                }
+            }
             Ok(line) => {
                 rl.add_history_entry(line)?;
 
@@ -138,6 +181,10 @@ pub(crate) fn run(
                     Err(v) => v.to_string(),
                 };
 
+                if let Some(helper) = rl.helper_mut() {
+                    helper.update(&objects[index], &state);
+                }
+
                 #[allow(clippy::print_stdout)]
                 {
                     println!("{string}\n");
@@ -165,20 +212,48 @@ fn resolve(
     vrl_runtime: VrlRuntime,
     stdlib_functions: &[Box<dyn Function>],
 ) -> Result<Value, String> {
+    let program = compile(program, state, stdlib_functions)?;
+
+    *state = program.final_type_info().state;
+    execute(runtime, &program, target, timezone, vrl_runtime)
+}
+
+fn compile(
+    program: &str,
+    state: &TypeState,
+    stdlib_functions: &[Box<dyn Function>],
+) -> Result<Program, String> {
     let mut config = CompileConfig::default();
     // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
     config.set_read_only_path(owned_metadata_path!("vector"), true);
     config.disable_unused_expression_check();
+    config.set_state_store(STATE_STORE.clone());
+
+    compile_with_state(program, stdlib_functions, state, config)
+        .map(|result| result.program)
+        .map_err(|diagnostics| Formatter::new(program, diagnostics).colored().to_string())
+}
+
+/// Prints the inferred type of an expression, without running it or changing the session state.
+fn show_type(expression: &str, state: &TypeState, stdlib_functions: &[Box<dyn Function>]) {
+    let output = match compile(expression, state, stdlib_functions) {
+        Ok(program) => {
+            let result = program.final_type_info().result;
+            let fallibility = if result.is_fallible() {
+                "fallible"
+            } else {
+                "infallible"
+            };
 
-    let program = match compile_with_state(program, stdlib_functions, state, config) {
-        Ok(result) => result.program,
-        Err(diagnostics) => {
-            return Err(Formatter::new(program, diagnostics).colored().to_string());
+            format!("{} ({fallibility})", result.kind())
         }
+        Err(err) => err,
     };
 
-    *state = program.final_type_info().state;
-    execute(runtime, &program, target, timezone, vrl_runtime)
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{output}\n");
+    }
 }
 
 fn execute(
@@ -201,6 +276,10 @@ struct Repl {
     colored_prompt: String,
     hints: Vec<&'static str>,
     stdlib_functions: Rc<Vec<Box<dyn Function>>>,
+    /// The current event object, metadata and variables, used to complete paths and variables.
+    value: Value,
+    metadata: Value,
+    variables: Vec<String>,
 }
 
 impl Repl {
@@ -211,8 +290,48 @@ impl Repl {
             colored_prompt: "$ ".to_owned(),
             hints: initial_hints(&stdlib_functions),
             stdlib_functions,
+            value: Value::Null,
+            metadata: Value::Object(BTreeMap::new()),
+            variables: vec![],
         }
     }
+
+    /// Updates the completion candidates after a program ran.
+    fn update(&mut self, target: &TargetValue, state: &TypeState) {
+        self.value = target.value.clone();
+        self.metadata = target.metadata.clone();
+        self.variables = state
+            .local
+            .variable_idents()
+            .map(ToString::to_string)
+            .collect();
+    }
+
+    /// The parameters of the function called at the end of `line`, such as `upcase(`.
+    fn signature(&self, line: &str) -> Option<String> {
+        let line = line.strip_suffix('(')?;
+        let line = line.strip_suffix('!').unwrap_or(line);
+        let ident = &line[line.trim_end_matches(is_ident_char).len()..];
+        let function = self
+            .stdlib_functions
+            .iter()
+            .find(|function| function.identifier() == ident)?;
+
+        let parameters = function
+            .parameters()
+            .iter()
+            .map(|parameter| {
+                let parameter_signature = format!("{}: {}", parameter.keyword, parameter.kind());
+                if parameter.required {
+                    parameter_signature
+                } else {
+                    format!("[{parameter_signature}]")
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Some(format!("{})", parameters.join(", ")))
+    }
 }
 
 fn initial_hints(funcs: &[Box<dyn Function>]) -> Vec<&'static str> {
@@ -223,38 +342,143 @@ fn initial_hints(funcs: &[Box<dyn Function>]) -> Vec<&'static str> {
         .collect()
 }
 
+fn is_ident_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn is_path_char(ch: char) -> bool {
+    is_ident_char(ch) || matches!(ch, '.' | '%' | '"')
+}
+
+/// Completes a path into `value`, where `path` is the path typed so far without its prefix, such
+/// as `foo.ba` for `.foo.ba`.
+fn path_candidates(value: &Value, prefix: &str, path: &str) -> Vec<Pair> {
+    let (parent, partial) = path.rsplit_once('.').unwrap_or(("", path));
+
+    let mut value = value;
+    for segment in parent.split('.').filter(|segment| !segment.is_empty()) {
+        match value
+            .as_object()
+            .and_then(|object| object.get(segment.trim_matches('"')))
+        {
+            Some(field) => value = field,
+            None => return vec![],
+        }
+    }
+
+    let Some(object) = value.as_object() else {
+        return vec![];
+    };
+
+    let parent = if parent.is_empty() {
+        prefix.to_owned()
+    } else {
+        format!("{prefix}{parent}.")
+    };
+
+    object
+        .keys()
+        .filter(|key| key.starts_with(partial.trim_start_matches('"')))
+        .map(|key| {
+            let key = key.as_str();
+            let segment = if key.chars().all(is_ident_char) {
+                key.to_owned()
+            } else {
+                format!("{key:?}")
+            };
+
+            Pair {
+                display: key.to_owned(),
+                replacement: format!("{parent}{segment}"),
+            }
+        })
+        .collect()
+}
+
 impl Helper for Repl {}
 impl Completer for Repl {
-    type Candidate = String;
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos].trim_end_matches(is_path_char).len();
+        let word = &line[start..pos];
+
+        let candidates = if let Some(path) = word.strip_prefix('.') {
+            path_candidates(&self.value, ".", path)
+        } else if let Some(path) = word.strip_prefix('%') {
+            path_candidates(&self.metadata, "%", path)
+        } else if word.is_empty() || !word.chars().all(is_ident_char) {
+            vec![]
+        } else {
+            let functions = self.stdlib_functions.iter().map(|function| Pair {
+                display: function.identifier().to_owned(),
+                replacement: format!("{}(", function.identifier()),
+            });
+            let variables = self.variables.iter().map(|variable| Pair {
+                display: variable.clone(),
+                replacement: variable.clone(),
+            });
+
+            variables
+                .chain(functions)
+                .filter(|candidate| candidate.display.starts_with(word))
+                .collect()
+        };
+
+        Ok((start, candidates))
+    }
+}
+
+/// A hint shown after the cursor.
+enum ReplHint {
+    /// Text that can be inserted to complete the line.
+    Completion(String),
+
+    /// The parameters of the function being called, which are only displayed.
+    Signature(String),
+}
+
+impl Hint for ReplHint {
+    fn display(&self) -> &str {
+        match self {
+            ReplHint::Completion(hint) | ReplHint::Signature(hint) => hint,
+        }
+    }
+
+    fn completion(&self) -> Option<&str> {
+        match self {
+            ReplHint::Completion(hint) => Some(hint),
+            ReplHint::Signature(_) => None,
+        }
+    }
 }
 
 impl Hinter for Repl {
-    type Hint = String;
+    type Hint = ReplHint;
 
-    fn hint(&self, line: &str, pos: usize, ctx: &Context<'_>) -> Option<String> {
+    fn hint(&self, line: &str, pos: usize, ctx: &Context<'_>) -> Option<ReplHint> {
         if pos < line.len() {
             return None;
         }
 
-        let mut hints: Vec<String> = Vec::new();
-
-        // Add all function names to the hints
-        let mut func_names = crate::stdlib::all()
-            .iter()
-            .map(|f| f.identifier().into())
-            .collect::<Vec<String>>();
-
-        hints.append(&mut func_names);
+        if let Some(signature) = self.signature(line) {
+            return Some(ReplHint::Signature(signature));
+        }
 
         // Check history first
         if let Some(hist) = self.history_hinter.hint(line, pos, ctx) {
-            return Some(hist);
+            return Some(ReplHint::Completion(hist));
         }
 
         // Then check the other built-in hints
         self.hints.iter().find_map(|hint| {
             if pos > 0 && hint.starts_with(&line[..pos]) {
-                Some(String::from(&hint[pos..]))
+                Some(ReplHint::Completion(String::from(&hint[pos..])))
             } else {
                 None
             }
@@ -293,40 +517,23 @@ impl Validator for Repl {
         &self,
         ctx: &mut validate::ValidationContext,
     ) -> rustyline::Result<ValidationResult> {
-        let timezone = TimeZone::default();
-        let mut state = TypeState::default();
-        let mut rt = Runtime::new(RuntimeState::default());
-        let mut target = TargetValue {
-            value: Value::Null,
-            metadata: Value::Object(BTreeMap::new()),
-            secrets: Secrets::new(),
-        };
-
-        let result = resolve(
-            &mut target,
-            &mut rt,
-            ctx.input(),
-            &mut state,
-            timezone,
-            VrlRuntime::Ast,
-            &self.stdlib_functions,
+        // Keep reading lines while a block, string or expression is left open at the end of the
+        // input.
+        let incomplete = matches!(
+            crate::parser::parse(ctx.input()),
+            Err(ParseError::UnclosedDelimiter { .. }
+                | ParseError::StringLiteral { .. }
+                | ParseError::ParseError {
+                    source: lalrpop_util::ParseError::UnrecognizedEof { .. },
+                    ..
+                })
         );
 
-        let result = match result {
-            Err(error) => {
-                // TODO: Ideally we'd used typed errors for this, but
-                // that requires some more work to the VRL compiler.
-                if error.contains("syntax error") && error.contains("unexpected end of program") {
-                    ValidationResult::Incomplete
-                } else {
-                    ValidationResult::Valid(None)
-                }
-            }
-
-            Ok(..) => ValidationResult::Valid(None),
-        };
-
-        Ok(result)
+        if incomplete {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
     }
 
     fn validate_while_typing(&self) -> bool {
@@ -422,6 +629,9 @@ const HELP_TEXT: &str = indoc! {r#"
       help docs          Navigate to the VRL docs on the Vector website
       help docs <func>   Navigate to the VRL docs for the specified function
       help error <code>  Navigate to the docs for a specific error code
+      :type <expr>       Display the inferred type of an expression without running it
+      :debug <program>   Step through a program one statement at a time, inspecting the event,
+                         metadata and variables in between
       next               Load the next object or create a new one
       prev               Load the previous object
       exit               Terminate the program
@@ -0,0 +1,193 @@
+//! Streaming mode of the CLI.
+//!
+//! Events are read as NDJSON one line at a time, processed by a pool of worker threads that each
+//! reuse their own [`Runtime`], and the results are written as NDJSON. Each result is a line of
+//! output, while invalid input and runtime errors are written to stderr, prefixed with the line
+//! number of the event.
+//!
+//! The number of events in flight is bounded, so reading the input stalls until the output has
+//! caught up. Results are written in input order, unless the stream is unordered.
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::num::NonZeroUsize;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::compiler::runtime::Runtime;
+use crate::compiler::state::RuntimeState;
+use crate::compiler::{Program, TargetValueRef, TimeZone};
+use crate::value::{Secrets, Value};
+
+use super::cmd::{serde_to_vrl, Output};
+use super::Error;
+
+/// The number of events in flight for each worker.
+const EVENTS_PER_WORKER: usize = 64;
+
+pub(crate) struct Options {
+    pub(crate) workers: NonZeroUsize,
+    pub(crate) ordered: bool,
+    pub(crate) output: Output,
+    pub(crate) metadata: Value,
+    pub(crate) secrets: Secrets,
+    pub(crate) timezone: TimeZone,
+}
+
+/// An event, numbered in input order, along with the number of its input line.
+type Event = (usize, usize, String);
+
+/// The output of a single event, along with its number and the number of its input line.
+type Output = (usize, usize, Result<String, String>);
+
+pub(crate) fn run(
+    program: &Program,
+    options: &Options,
+    input: impl BufRead + Send,
+    mut stdout: impl Write,
+    mut stderr: impl Write,
+) -> Result<(), Error> {
+    let capacity = options.workers.get() * EVENTS_PER_WORKER;
+
+    // Every event in flight holds a permit, which is only returned once its output is written.
+    let (permit_tx, permit_rx) = sync_channel(capacity);
+    for _ in 0..capacity {
+        permit_tx.send(()).expect("receiver is alive");
+    }
+
+    let (event_tx, event_rx) = sync_channel::<Event>(capacity);
+    let event_rx = Arc::new(Mutex::new(event_rx));
+    let (output_tx, output_rx) = sync_channel::<Output>(capacity);
+
+    thread::scope(|scope| {
+        let reader = scope.spawn(move || -> Result<(), Error> {
+            let lines = input
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()));
+
+            for (number, (index, line)) in lines.enumerate() {
+                let line = line?;
+                if permit_rx.recv().is_err() || event_tx.send((number, index + 1, line)).is_err() {
+                    break;
+                }
+            }
+
+            Ok(())
+        });
+
+        for _ in 0..options.workers.get() {
+            let event_rx = Arc::clone(&event_rx);
+            let output_tx = output_tx.clone();
+            scope.spawn(move || work(program, options, &event_rx, &output_tx));
+        }
+        drop(event_rx);
+        drop(output_tx);
+
+        let written = write(
+            options.ordered,
+            &output_rx,
+            &mut stdout,
+            &mut stderr,
+            || {
+                // The reader may already be done, in which case the permit isn't needed.
+                let _ = permit_tx.send(());
+            },
+        );
+
+        // Unblock the reader if writing failed.
+        drop(output_rx);
+        drop(permit_tx);
+
+        let read = reader.join().expect("reader doesn't panic");
+        written.and(read)
+    })
+}
+
+fn work(
+    program: &Program,
+    options: &Options,
+    events: &Mutex<Receiver<Event>>,
+    outputs: &SyncSender<Output>,
+) {
+    let mut runtime = Runtime::new(RuntimeState::default());
+
+    loop {
+        // The lock is released before the event is processed.
+        let next = events.lock().expect("lock isn't poisoned").recv();
+        let Ok((number, line_number, line)) = next else {
+            return;
+        };
+
+        let output = process(program, options, &mut runtime, &line);
+        runtime.clear();
+
+        if outputs.send((number, line_number, output)).is_err() {
+            return;
+        }
+    }
+}
+
+fn process(
+    program: &Program,
+    options: &Options,
+    runtime: &mut Runtime,
+    line: &str,
+) -> Result<String, String> {
+    let mut object = serde_json::from_str(line)
+        .map(serde_to_vrl)
+        .map_err(|err| format!("input error: {err}"))?;
+    let mut metadata = options.metadata.clone();
+    let mut secrets = options.secrets.clone();
+    let mut target = TargetValueRef {
+        value: &mut object,
+        metadata: &mut metadata,
+        secrets: &mut secrets,
+    };
+
+    let result = runtime
+        .resolve(&mut target, program, &options.timezone)
+        .map_err(|err| err.to_string())?;
+    let output = options.output.select(result, &object, &metadata);
+
+    serde_json::to_string(&output).map_err(|err| format!("output error: {err}"))
+}
+
+fn write(
+    ordered: bool,
+    outputs: &Receiver<Output>,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    mut release: impl FnMut(),
+) -> Result<(), Error> {
+    let mut write_one = |line_number: usize, output: Result<String, String>| -> Result<(), Error> {
+        match output {
+            Ok(json) => writeln!(stdout, "{json}")?,
+            Err(err) => writeln!(stderr, "line {line_number}: {err}")?,
+        }
+        release();
+        Ok(())
+    };
+
+    if !ordered {
+        for (_, line_number, output) in outputs {
+            write_one(line_number, output)?;
+        }
+        return Ok(());
+    }
+
+    // Outputs that are ready before the output of an earlier event, by event number.
+    let mut pending = BTreeMap::new();
+    let mut next = 0;
+
+    for (number, line_number, output) in outputs {
+        pending.insert(number, (line_number, output));
+
+        while let Some((line_number, output)) = pending.remove(&next) {
+            write_one(line_number, output)?;
+            next += 1;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::compiler::{Resolved, RuntimeObserver};
+use crate::diagnostic::Span;
+use crate::value::Value;
+
+/// Prints a readable execution trace of a program to stderr, indenting the
+/// events that happen within function calls.
+#[derive(Debug, Default)]
+pub(crate) struct Tracer {
+    depth: usize,
+}
+
+impl Tracer {
+    #[allow(clippy::print_stderr)]
+    fn print(&self, line: &str) {
+        eprintln!("{:indent$}{line}", "", indent = self.depth * 2);
+    }
+}
+
+impl RuntimeObserver for Tracer {
+    fn on_assignment(&mut self, target: &str, old: Option<&Value>, new: &Value) {
+        let old = old.map_or_else(|| "none".to_owned(), ToString::to_string);
+        self.print(&format!("{target}: {old} -> {new}"));
+    }
+
+    fn on_function_enter(&mut self, function: &str, span: Span) {
+        self.print(&format!("{function}() at {}:{}", span.start(), span.end()));
+        self.depth += 1;
+    }
+
+    fn on_function_exit(&mut self, function: &str, _span: Span, result: &Resolved) {
+        self.depth = self.depth.saturating_sub(1);
+        match result {
+            Ok(value) => self.print(&format!("{function}() returned {value}")),
+            Err(error) => self.print(&format!("{function}() failed: {error}")),
+        }
+    }
+
+    fn on_branch(&mut self, condition: &str, taken: bool) {
+        let branch = if taken { "if" } else { "else" };
+        self.print(&format!("if {condition}: taking the {branch} branch"));
+    }
+}
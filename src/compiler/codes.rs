@@ -1,2 +1,9 @@
 // TODO Gather all codes here. Ensure they are unique and publicly documented.
 pub const WARNING_UNUSED_CODE: usize = 900;
+pub const WARNING_STATIC_PREDICATE_CODE: usize = 901;
+pub const WARNING_REDUNDANT_NULL_COALESCE_CODE: usize = 902;
+pub const WARNING_NULL_COMPARISON_CODE: usize = 903;
+pub const WARNING_SHADOWED_VARIABLE_CODE: usize = 904;
+pub const WARNING_ARRAY_PADDING_CODE: usize = 905;
+pub const WARNING_NARROWED_ABORT_CODE: usize = 906;
+pub const WARNING_FAILING_CONSTANT_CALL_CODE: usize = 907;
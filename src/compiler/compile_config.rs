@@ -1,7 +1,14 @@
+use super::env_var_policy::EnvVarPolicy;
+use super::lint::{LintLevel, LintRule, UnusedResult};
+use super::path_pattern::PathPattern;
+use super::state_store::StateStore;
+use super::tenant_policy::TenantPolicy;
+use super::write_limits::WriteLimits;
 use crate::path::OwnedTargetPath;
 use std::{
     any::{Any, TypeId},
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
 };
 
 type AnyMap = HashMap<TypeId, Box<dyn Any>>;
@@ -10,7 +17,16 @@ pub struct CompileConfig {
     /// Custom context injected by the external environment
     custom: AnyMap,
     read_only_paths: BTreeSet<ReadOnlyPath>,
-    check_unused_expressions: bool,
+    /// Whether paths that aren't writable are read only.
+    read_only_by_default: bool,
+    writable_paths: BTreeSet<ReadOnlyPath>,
+    lint_levels: BTreeMap<String, LintLevel>,
+    lint_rules: Vec<Box<dyn LintRule>>,
+    tenant_policy: Option<TenantPolicy>,
+    write_limits: WriteLimits,
+    raise_target_insert_errors: bool,
+    env_var_policy: EnvVarPolicy,
+    state_store: Option<Arc<dyn StateStore>>,
 }
 
 impl Default for CompileConfig {
@@ -18,7 +34,15 @@ impl Default for CompileConfig {
         CompileConfig {
             custom: AnyMap::default(),
             read_only_paths: BTreeSet::default(),
-            check_unused_expressions: true,
+            read_only_by_default: false,
+            writable_paths: BTreeSet::default(),
+            lint_levels: BTreeMap::default(),
+            lint_rules: vec![],
+            tenant_policy: None,
+            write_limits: WriteLimits::default(),
+            raise_target_insert_errors: false,
+            env_var_policy: EnvVarPolicy::default(),
+            state_store: None,
         }
     }
 }
@@ -57,46 +81,208 @@ impl CompileConfig {
 
     #[must_use]
     pub fn is_read_only_path(&self, path: &OwnedTargetPath) -> bool {
+        self.read_only_reason(path).is_some()
+    }
+
+    /// Returns why the given path is read only, or `None` if it can be
+    /// modified.
+    #[must_use]
+    pub fn read_only_reason(&self, path: &OwnedTargetPath) -> Option<String> {
         for read_only_path in &self.read_only_paths {
+            if read_only_path.contains(path) {
+                let verb = if read_only_path.recursive {
+                    "are"
+                } else {
+                    "is"
+                };
+                return Some(format!("{read_only_path} {verb} read-only"));
+            }
+
             // any paths that are a parent of read-only paths also can't be modified
-            if read_only_path.path.can_start_with(path) {
-                return true;
+            if read_only_path.pattern.has_prefix(path) {
+                return Some(format!(
+                    "it contains `{}`, which is read-only",
+                    read_only_path.pattern
+                ));
             }
+        }
 
-            if read_only_path.recursive {
-                if path.can_start_with(&read_only_path.path) {
-                    return true;
-                }
-            } else if path == &read_only_path.path {
-                return true;
+        if self.read_only_by_default
+            && !self
+                .writable_paths
+                .iter()
+                .any(|writable_path| writable_path.contains(path))
+        {
+            if self.writable_paths.is_empty() {
+                return Some("all paths are read-only".to_owned());
             }
+
+            let writable_paths = self
+                .writable_paths
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            return Some(format!(
+                "only {} can be modified",
+                writable_paths.join(", ")
+            ));
         }
-        false
+
+        None
     }
 
     /// Adds a path that is considered read only. Assignments to any paths that match
     /// will fail at compile time.
     pub fn set_read_only_path(&mut self, path: OwnedTargetPath, recursive: bool) {
-        self.read_only_paths
-            .insert(ReadOnlyPath { path, recursive });
+        self.set_read_only_pattern(path, recursive);
+    }
+
+    /// Adds a path pattern, such as `.kubernetes.*.labels`, that is considered
+    /// read only. Assignments to any paths that match, or to a parent of a
+    /// path that matches, will fail at compile time.
+    pub fn set_read_only_pattern(&mut self, pattern: impl Into<PathPattern>, recursive: bool) {
+        self.read_only_paths.insert(ReadOnlyPath {
+            pattern: pattern.into(),
+            recursive,
+        });
+    }
+
+    /// Makes every path read only, except the paths added with
+    /// [`CompileConfig::set_writable_pattern`].
+    pub fn set_read_only_by_default(&mut self, read_only: bool) {
+        self.read_only_by_default = read_only;
+    }
+
+    /// Adds a path pattern that can be modified when paths are read only by
+    /// default. Read-only paths can't be modified, even if they are writable.
+    pub fn set_writable_pattern(&mut self, pattern: impl Into<PathPattern>, recursive: bool) {
+        self.writable_paths.insert(ReadOnlyPath {
+            pattern: pattern.into(),
+            recursive,
+        });
     }
 
     #[must_use]
     pub fn unused_expression_check_enabled(&self) -> bool {
-        self.check_unused_expressions
+        self.lint_level(&UnusedResult) != LintLevel::Allow
     }
 
     pub fn disable_unused_expression_check(&mut self) {
-        self.check_unused_expressions = false;
+        self.set_lint_level(UnusedResult.name(), LintLevel::Allow);
+    }
+
+    /// Sets the level of the lint rule with the given name, overriding its default level.
+    pub fn set_lint_level(&mut self, rule: impl Into<String>, level: LintLevel) {
+        self.lint_levels.insert(rule.into(), level);
+    }
+
+    /// Get the configured level of a lint rule, or its default level if it isn't configured.
+    #[must_use]
+    pub fn lint_level(&self, rule: &dyn LintRule) -> LintLevel {
+        self.lint_levels
+            .get(rule.name())
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    /// Adds a custom lint rule, checked along with the built-in rules.
+    pub fn add_lint_rule(&mut self, rule: impl LintRule + 'static) {
+        self.lint_rules.push(Box::new(rule));
+    }
+
+    #[must_use]
+    pub fn lint_rules(&self) -> &[Box<dyn LintRule>] {
+        &self.lint_rules
+    }
+
+    /// Applies a tenant isolation policy. The read-only paths of the policy are
+    /// added to the read-only paths of this config, and its function and resource
+    /// restrictions are checked at compile time.
+    pub fn set_tenant_policy(&mut self, policy: TenantPolicy) {
+        for (path, recursive) in policy.read_only_paths() {
+            self.set_read_only_path(path.clone(), *recursive);
+        }
+        self.tenant_policy = Some(policy);
+    }
+
+    #[must_use]
+    pub fn tenant_policy(&self) -> Option<&TenantPolicy> {
+        self.tenant_policy.as_ref()
+    }
+
+    /// Limits the fields that compiled programs may insert into the external
+    /// target each time they run. Exceeding a limit is a runtime error.
+    pub fn set_write_limits(&mut self, limits: WriteLimits) {
+        self.write_limits = limits;
+    }
+
+    #[must_use]
+    pub fn write_limits(&self) -> WriteLimits {
+        self.write_limits
+    }
+
+    /// Whether errors returned by [`Target::target_insert`](super::Target::target_insert)
+    /// fail the assignment with a runtime error. By default they are ignored.
+    pub fn set_raise_target_insert_errors(&mut self, raise: bool) {
+        self.raise_target_insert_errors = raise;
+    }
+
+    #[must_use]
+    pub fn raise_target_insert_errors(&self) -> bool {
+        self.raise_target_insert_errors
+    }
+
+    /// Restricts the environment variables that compiled programs can read.
+    pub fn set_env_var_policy(&mut self, policy: EnvVarPolicy) {
+        self.env_var_policy = policy;
+    }
+
+    #[must_use]
+    pub fn env_var_policy(&self) -> &EnvVarPolicy {
+        &self.env_var_policy
+    }
+
+    /// Sets the store of the functions keeping state across the runs of a
+    /// program, such as `throttle`. Programs calling these functions fail to
+    /// compile without a store.
+    pub fn set_state_store(&mut self, store: impl StateStore + 'static) {
+        self.state_store = Some(Arc::new(store));
+    }
+
+    #[must_use]
+    pub fn state_store(&self) -> Option<&Arc<dyn StateStore>> {
+        self.state_store.as_ref()
     }
 }
 
+/// A read-only or writable path pattern.
 #[derive(Debug, Clone, Ord, Eq, PartialEq, PartialOrd)]
 struct ReadOnlyPath {
-    path: OwnedTargetPath,
+    pattern: PathPattern,
     recursive: bool,
 }
 
+impl ReadOnlyPath {
+    /// Returns whether the rule applies to the given path.
+    fn contains(&self, path: &OwnedTargetPath) -> bool {
+        if self.recursive {
+            self.pattern.is_prefix_of(path)
+        } else {
+            self.pattern.matches(path)
+        }
+    }
+}
+
+impl std::fmt::Display for ReadOnlyPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.pattern)?;
+        if self.recursive {
+            f.write_str(" and the paths in it")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
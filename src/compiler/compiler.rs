@@ -1,13 +1,18 @@
+use std::collections::BTreeSet;
+
 use crate::compiler::expression::function_call::FunctionCallError;
 use crate::compiler::expression::ExpressionError;
 use crate::compiler::{
     expression::{
-        assignment, function_call, literal, predicate, query, Abort, Array, Assignment, Block,
-        Container, Expr, Expression, FunctionArgument, FunctionCall, Group, IfStatement, Literal,
-        Noop, Not, Object, Op, Predicate, Query, Return, Target, Unary, Variable,
+        assignment, function_call, literal, predicate, query, r#loop, Abort, Array, Assignment,
+        BitNot, Block, Break, Container, Expr, Expression, FunctionArgument, FunctionCall, Group,
+        IfStatement, Literal, Loop, Noop, Not, Object, Op, Predicate, Query, Return, Target, Unary,
+        Variable,
     },
+    on_error::{self, OnError},
     parser::ast::RootExpr,
     program::ProgramInfo,
+    tenant_policy::{SECRET_READ_FUNCTIONS, SECRET_WRITE_FUNCTIONS},
     CompileConfig, DeprecationWarning, Function, Program, TypeDef,
 };
 use crate::diagnostic::{DiagnosticList, DiagnosticMessage, Note};
@@ -38,6 +43,9 @@ pub struct Compiler<'a> {
     abortable: bool,
     external_queries: Vec<OwnedTargetPath>,
     external_assignments: Vec<OwnedTargetPath>,
+    functions: BTreeSet<&'static str>,
+    secret_keys: BTreeSet<String>,
+    dynamic_secret_keys: bool,
 
     /// A list of variables that are missing, because the rhs expression of the
     /// assignment failed to compile.
@@ -56,6 +64,18 @@ pub struct Compiler<'a> {
     // the error from the LHS)
     fallible_expression_error: Option<CompilerError>,
 
+    /// The number of function calls compiled so far, checked against the limits
+    /// of the tenant policy, if any.
+    function_calls: usize,
+
+    /// The targets whose type is narrowed by the conditions of the enclosing
+    /// `if` statements.
+    narrowed_targets: Vec<assignment::Target>,
+
+    /// Whether the compiler only computes the types of a loop block, in which
+    /// case nested loop blocks are compiled once.
+    probing_loop: bool,
+
     config: CompileConfig,
 }
 
@@ -84,27 +104,37 @@ impl CompilerError {
 }
 
 impl<'a> Compiler<'a> {
-    pub fn compile(
-        fns: &'a [Box<dyn Function>],
-        ast: crate::parser::Program,
-        state: &TypeState,
-        config: CompileConfig,
-    ) -> Result<CompilationResult, DiagnosticList> {
-        let initial_state = state.clone();
-        let mut state = state.clone();
-
-        let mut compiler = Self {
+    fn new(fns: &'a [Box<dyn Function>], config: CompileConfig) -> Self {
+        Self {
             fns,
             diagnostics: vec![],
             fallible: false,
             abortable: false,
             external_queries: vec![],
             external_assignments: vec![],
+            functions: BTreeSet::new(),
+            secret_keys: BTreeSet::new(),
+            dynamic_secret_keys: false,
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
+            function_calls: 0,
+            narrowed_targets: vec![],
+            probing_loop: false,
             config,
-        };
-        let expressions = compiler.compile_root_exprs(ast, &mut state);
+        }
+    }
+
+    pub fn compile(
+        fns: &'a [Box<dyn Function>],
+        ast: crate::parser::Program,
+        state: &TypeState,
+        config: CompileConfig,
+    ) -> Result<CompilationResult, DiagnosticList> {
+        let initial_state = state.clone();
+        let mut state = state.clone();
+
+        let mut compiler = Self::new(fns, config);
+        let (expressions, on_error) = compiler.compile_root_exprs(ast, &mut state);
 
         let (errors, warnings): (Vec<_>, Vec<_>) =
             compiler.diagnostics.into_iter().partition(|diagnostic| {
@@ -121,13 +151,19 @@ impl<'a> Compiler<'a> {
         let result = CompilationResult {
             program: Program {
                 expressions: Block::new_inline(expressions),
+                on_error,
                 info: ProgramInfo {
                     fallible: compiler.fallible,
                     abortable: compiler.abortable,
                     target_queries: compiler.external_queries,
                     target_assignments: compiler.external_assignments,
+                    functions: compiler.functions.into_iter().collect(),
+                    secret_keys: compiler.secret_keys.into_iter().collect(),
+                    dynamic_secret_keys: compiler.dynamic_secret_keys,
                 },
                 initial_state,
+                write_limits: compiler.config.write_limits(),
+                raise_target_insert_errors: compiler.config.raise_target_insert_errors(),
             },
             warnings: warnings.into(),
             config: compiler.config,
@@ -150,8 +186,8 @@ impl<'a> Compiler<'a> {
 
     fn compile_expr(&mut self, node: Node<ast::Expr>, state: &mut TypeState) -> Option<Expr> {
         use ast::Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Return,
-            Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Op,
+            Query, Return, Unary, Variable,
         };
         let original_state = state.clone();
 
@@ -169,6 +205,12 @@ impl<'a> Compiler<'a> {
             Unary(node) => self.compile_unary(node, state).map(Into::into),
             Abort(node) => self.compile_abort(node, state).map(Into::into),
             Return(node) => self.compile_return(node, state).map(Into::into),
+            Loop(node) => self.compile_loop(node, state).map(Into::into),
+            Break(node) => {
+                self.diagnostics
+                    .push(Box::new(r#loop::Error::misplaced_break(node.span())));
+                None
+            }
         }?;
 
         // If the previously compiled expression is fallible, _and_ we are
@@ -252,10 +294,24 @@ impl<'a> Compiler<'a> {
         &mut self,
         nodes: impl IntoIterator<Item = Node<ast::RootExpr>>,
         state: &mut TypeState,
-    ) -> Vec<Expr> {
+    ) -> (Vec<Expr>, Option<OnError>) {
+        let nodes: Vec<_> = nodes.into_iter().collect();
         let mut node_exprs = vec![];
 
+        // The error handler can run after any of the root expressions, so it
+        // is compiled against the union of the target types between them. Local
+        // variables assigned by the program may be unset when the error occurs,
+        // so only the ones defined before the program runs are in scope.
+        let has_handler = nodes
+            .iter()
+            .any(|node| matches!(node.inner(), RootExpr::OnError(_)));
+        let initial_state = state.clone();
+        let mut error_state = has_handler.then(|| state.clone());
+        let mut handler: Option<Node<ast::Block>> = None;
+
         for root_expr in nodes {
+            let span = root_expr.span();
+
             match root_expr.into_inner() {
                 RootExpr::Expr(node_expr) => {
                     self.fallible_expression_error = None;
@@ -267,7 +323,15 @@ impl<'a> Compiler<'a> {
 
                         node_exprs.push(expr);
                     }
+
+                    error_state = error_state.map(|error_state| error_state.merge(state.clone()));
                 }
+                RootExpr::OnError(block) => match &handler {
+                    Some(first) => self
+                        .diagnostics
+                        .push(Box::new(on_error::Error::duplicate(span, first.span()))),
+                    None => handler = Some(block),
+                },
                 RootExpr::Error(err) => self.handle_parser_error(err),
             }
         }
@@ -275,7 +339,27 @@ impl<'a> Compiler<'a> {
         if node_exprs.is_empty() {
             node_exprs.push(Expr::Noop(Noop));
         }
-        node_exprs
+
+        let on_error = handler.zip(error_state).and_then(|(block, error_state)| {
+            let state = OnError::type_state(TypeState {
+                local: initial_state.local,
+                external: error_state.external,
+            });
+            self.compile_on_error(block, state)
+        });
+
+        (node_exprs, on_error)
+    }
+
+    fn compile_on_error(&mut self, node: Node<ast::Block>, state: TypeState) -> Option<OnError> {
+        self.fallible_expression_error = None;
+
+        let block = self.compile_block(node, &mut state.clone())?;
+        if let Some(error) = self.fallible_expression_error.take() {
+            self.diagnostics.push(error.into_diagnostic_boxed());
+        }
+
+        Some(OnError::new(state, block))
     }
 
     fn compile_block(&mut self, node: Node<ast::Block>, state: &mut TypeState) -> Option<Block> {
@@ -334,19 +418,33 @@ impl<'a> Compiler<'a> {
         } = node.into_inner();
 
         let original_state = state.clone();
+        let predicate_span = predicate.span();
 
         let predicate = self
             .compile_predicate(predicate, state)?
             .map_err(|err| self.diagnostics.push(Box::new(err)))
             .ok()?;
 
+        if let Some(warning) = predicate.static_outcome(predicate_span, state) {
+            self.diagnostics.push(Box::new(warning));
+        }
+
         let after_predicate_state = state.clone();
 
-        let if_block = self.compile_block(if_node, state)?;
+        let narrowed_targets = self.narrowed_targets.len();
+        let narrowed = predicate.narrow_type_state(state, true);
+        self.narrowed_targets.extend(narrowed);
+        let if_block = self.compile_block(if_node, state);
+        self.narrowed_targets.truncate(narrowed_targets);
+        let if_block = if_block?;
 
         let else_block = if let Some(else_node) = else_node {
             *state = after_predicate_state;
-            Some(self.compile_block(else_node, state)?)
+            let narrowed = predicate.narrow_type_state(state, false);
+            self.narrowed_targets.extend(narrowed);
+            let else_block = self.compile_block(else_node, state);
+            self.narrowed_targets.truncate(narrowed_targets);
+            Some(else_block?)
         } else {
             None
         };
@@ -469,6 +567,7 @@ impl<'a> Compiler<'a> {
 
         let original_state = state.clone();
 
+        let assignment_span = node.span();
         let assignment = node.into_inner();
 
         let node = match assignment {
@@ -546,6 +645,14 @@ impl<'a> Compiler<'a> {
         .map_err(|err| self.diagnostics.push(Box::new(err)))
         .ok()?;
 
+        if assignment.may_fail(&self.config) {
+            self.fallible = true;
+        }
+
+        for warning in assignment.padding_warnings(&self.config, assignment_span) {
+            self.diagnostics.push(Box::new(warning));
+        }
+
         // Track any potential external target assignments within the program.
         //
         // This data is exposed to the caller of the compiler, to allow any
@@ -634,6 +741,31 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    /// Records the function called, and the key of the secret it accesses, if
+    /// any, in the program info.
+    fn record_function_call(
+        &mut self,
+        func: &FunctionCall,
+        args: &ArgumentList,
+        state: &TypeState,
+    ) {
+        self.functions.insert(func.ident);
+
+        if !SECRET_READ_FUNCTIONS.contains(&func.ident)
+            && !SECRET_WRITE_FUNCTIONS.contains(&func.ident)
+        {
+            return;
+        }
+
+        match args.optional_literal("key", state) {
+            Ok(Some(Value::Bytes(key))) => {
+                self.secret_keys
+                    .insert(String::from_utf8_lossy(&key).into_owned());
+            }
+            _ => self.dynamic_secret_keys = true,
+        }
+    }
+
     fn compile_function_call(
         &mut self,
         node: Node<ast::FunctionCall>,
@@ -647,6 +779,33 @@ impl<'a> Compiler<'a> {
             closure,
         } = node.into_inner();
 
+        if let Some(policy) = self.config.tenant_policy() {
+            let ident_span = ident.span();
+            if let Err(reason) = policy.check_function(ident.as_deref()) {
+                self.diagnostics
+                    .push(Box::new(FunctionCallError::Disallowed {
+                        ident_span,
+                        reason,
+                    }));
+                return None;
+            }
+
+            self.function_calls += 1;
+            if let Some(max) = policy.limits().max_function_calls {
+                if self.function_calls > max {
+                    // Only the first call over the limit is reported.
+                    if self.function_calls == max + 1 {
+                        self.diagnostics
+                            .push(Box::new(FunctionCallError::TooManyCalls {
+                                ident_span,
+                                max,
+                            }));
+                    }
+                    return None;
+                }
+            }
+        }
+
         let original_state = state.clone();
         // TODO: Remove this (hacky) code once dynamic path syntax lands.
         //
@@ -669,6 +828,14 @@ impl<'a> Compiler<'a> {
             self.fallible = true;
         }
 
+        let narrowed_arguments = arguments.iter().any(|argument| {
+            predicate::queries_narrowed_target(
+                argument.inner().expr(),
+                &self.narrowed_targets,
+                state,
+            )
+        });
+
         let (closure_variables, closure_block) = match closure {
             Some(closure) => {
                 let span = closure.span();
@@ -721,6 +888,7 @@ impl<'a> Compiler<'a> {
             let arg_list = builder.get_arg_list().clone();
 
             builder
+                .narrowed_arguments(narrowed_arguments)
                 .compile(
                     &state_before_function,
                     state,
@@ -734,12 +902,16 @@ impl<'a> Compiler<'a> {
                     if let Some(e) = result.error {
                         self.fallible_expression_error = Some(CompilerError::FunctionCallError(e));
                     }
+                    if let Some(warning) = result.warning {
+                        self.diagnostics.push(Box::new(warning));
+                    }
                     (arg_list, result.function_call)
                 })
         });
 
         if let Some((args, function)) = &function_info {
             self.check_function_deprecations(function, args);
+            self.record_function_call(function, args, &original_state);
             // Update the final state using the function expression to make sure it's accurate.
             *state = function.type_info(&original_state).state;
         }
@@ -783,10 +955,11 @@ impl<'a> Compiler<'a> {
     }
 
     fn compile_unary(&mut self, node: Node<ast::Unary>, state: &mut TypeState) -> Option<Unary> {
-        use ast::Unary::Not;
+        use ast::Unary::{BitNot, Not};
 
         let variant = match node.into_inner() {
             Not(node) => self.compile_not(node, state)?.into(),
+            BitNot(node) => self.compile_bit_not(node, state)?.into(),
         };
 
         Some(Unary::new(variant))
@@ -802,6 +975,20 @@ impl<'a> Compiler<'a> {
             .ok()
     }
 
+    fn compile_bit_not(
+        &mut self,
+        node: Node<ast::BitNot>,
+        state: &mut TypeState,
+    ) -> Option<BitNot> {
+        let (tilde, expr) = node.into_inner().take();
+
+        let node = Node::new(expr.span(), self.compile_expr(*expr, state)?);
+
+        BitNot::new(node, tilde.span(), state)
+            .map_err(|err| self.diagnostics.push(Box::new(err)))
+            .ok()
+    }
+
     fn compile_abort(&mut self, node: Node<ast::Abort>, state: &mut TypeState) -> Option<Abort> {
         self.abortable = true;
         let (span, abort) = node.take();
@@ -828,6 +1015,111 @@ impl<'a> Compiler<'a> {
             .ok()
     }
 
+    fn compile_loop(&mut self, node: Node<ast::Loop>, state: &mut TypeState) -> Option<Loop> {
+        let ast::Loop {
+            keyword,
+            max,
+            block,
+        } = node.into_inner();
+
+        if keyword.as_deref() != "max" {
+            let (span, keyword) = keyword.take();
+            self.diagnostics
+                .push(Box::new(r#loop::Error::unknown_keyword(
+                    keyword.into_inner(),
+                    span,
+                )));
+            return None;
+        }
+
+        let max_span = max.span();
+        let max = self
+            .compile_expr(*max, state)?
+            .resolve_constant(state)
+            .and_then(|value| value.as_integer())
+            .and_then(|max| usize::try_from(max).ok())
+            .filter(|max| *max <= Loop::MAX_ITERATIONS);
+        let Some(max) = max else {
+            self.diagnostics
+                .push(Box::new(r#loop::Error::invalid_bound(max_span)));
+            return None;
+        };
+
+        let original_state = state.clone();
+
+        // Variables of the enclosing scope can change type from one iteration
+        // to the next, so the block is compiled with the state at the start of
+        // any iteration. A first, discarded, compilation of the block gives the
+        // expressions to compute that state from.
+        //
+        // The types of the expressions of that first compilation are computed
+        // for every iteration of the loops they are nested in, so its nested
+        // loop blocks are only compiled once, instead of twice per level of
+        // nesting.
+        let iteration_state = if self.probing_loop {
+            original_state.clone()
+        } else {
+            let config = std::mem::take(&mut self.config);
+            let mut probe = Self::new(self.fns, config);
+            probe.probing_loop = true;
+            let iteration_state = probe
+                .compile_loop_block(block.clone(), &mut original_state.clone())
+                .map_or_else(
+                    || original_state.clone(),
+                    |block| Loop::new(max, block).type_info(&original_state).state,
+                );
+            self.config = probe.config;
+            iteration_state
+        };
+
+        let block = self.compile_loop_block(block, &mut iteration_state.clone())?;
+        let r#loop = Loop::new(max, block);
+
+        *state = original_state;
+        r#loop.apply_type_info(state);
+        Some(r#loop)
+    }
+
+    fn compile_loop_block(
+        &mut self,
+        node: Node<ast::Block>,
+        state: &mut TypeState,
+    ) -> Option<Block> {
+        let original_state = state.clone();
+
+        // A `break` statement is only allowed as a statement of the loop
+        // block itself, anywhere else it is rejected by `compile_expr`.
+        let mut exprs = vec![];
+        for node in node.into_inner() {
+            let (span, expr) = node.take();
+            let expr = match expr {
+                ast::Expr::Break(node) => self.compile_break(node, state).map(Into::into),
+                expr => self.compile_expr(Node::new(span, expr), state),
+            };
+            exprs.push(expr?);
+        }
+        let block = Block::new_scoped(exprs);
+
+        *state = original_state;
+        block.apply_type_info(state);
+        Some(block)
+    }
+
+    fn compile_break(&mut self, node: Node<ast::Break>, state: &mut TypeState) -> Option<Break> {
+        let (span, r#break) = node.take();
+
+        let condition = match r#break.condition {
+            Some(node) => Some(
+                self.compile_predicate(node, state)?
+                    .map_err(|err| self.diagnostics.push(Box::new(err)))
+                    .ok()?,
+            ),
+            None => None,
+        };
+
+        Some(Break::new(span, condition))
+    }
+
     fn handle_parser_error(&mut self, error: crate::parser::Error) {
         self.diagnostics.push(Box::new(error));
     }
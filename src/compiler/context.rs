@@ -1,11 +1,14 @@
 use super::TimeZone;
 
-use super::{state::RuntimeState, Target};
+use super::{state::RuntimeState, RuntimeObserver, Target};
 
 pub struct Context<'a> {
     target: &'a mut dyn Target,
     state: &'a mut RuntimeState,
     timezone: &'a TimeZone,
+    max_array_padding: Option<usize>,
+    raise_insert_errors: bool,
+    observer: Option<&'a mut dyn RuntimeObserver>,
 }
 
 impl<'a> Context<'a> {
@@ -19,6 +22,9 @@ impl<'a> Context<'a> {
             target,
             state,
             timezone,
+            max_array_padding: None,
+            raise_insert_errors: false,
+            observer: None,
         }
     }
 
@@ -49,4 +55,48 @@ impl<'a> Context<'a> {
     pub fn timezone(&self) -> &TimeZone {
         self.timezone
     }
+
+    /// Get the maximum number of `null` elements that an assignment may pad
+    /// an array with, if any.
+    #[must_use]
+    pub fn max_array_padding(&self) -> Option<usize> {
+        self.max_array_padding
+    }
+
+    /// Limits the number of `null` elements that an assignment may pad an
+    /// array with, when inserting at an index past the end of the array.
+    pub fn set_max_array_padding(&mut self, limit: Option<usize>) {
+        self.max_array_padding = limit;
+    }
+
+    /// Returns `true` if errors returned by [`Target::target_insert`] fail
+    /// the assignment.
+    #[must_use]
+    pub fn raise_insert_errors(&self) -> bool {
+        self.raise_insert_errors
+    }
+
+    /// Whether errors returned by [`Target::target_insert`] fail the
+    /// assignment. By default they are ignored.
+    pub fn set_raise_insert_errors(&mut self, raise: bool) {
+        self.raise_insert_errors = raise;
+    }
+
+    /// Registers an observer that is notified as the program runs.
+    pub fn set_observer(&mut self, observer: &'a mut dyn RuntimeObserver) {
+        self.observer = Some(observer);
+    }
+
+    /// Returns `true` if an observer is registered.
+    #[must_use]
+    pub fn is_observed(&self) -> bool {
+        self.observer.is_some()
+    }
+
+    /// Notifies the registered observer, if any.
+    pub fn observe(&mut self, notify: impl FnOnce(&mut dyn RuntimeObserver)) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            notify(observer);
+        }
+    }
 }
@@ -0,0 +1,79 @@
+use std::collections::BTreeSet;
+
+/// The environment variables that programs can read with the `get_env_var`
+/// and `env_vars` functions.
+///
+/// A name ending with `*` matches every variable starting with the rest of
+/// it, so `APP_*` matches `APP_NAME` and `APP_PORT`. Denied names take
+/// precedence over allowed names.
+///
+/// The default policy allows every variable. Reading a disallowed variable
+/// whose name is a literal is a compile-time error, otherwise it is a runtime
+/// error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvVarPolicy {
+    allowed: Option<BTreeSet<String>>,
+    denied: BTreeSet<String>,
+}
+
+impl EnvVarPolicy {
+    /// Only allows reading the given variables.
+    #[must_use]
+    pub fn with_allowed<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Disallows reading the given variables.
+    #[must_use]
+    pub fn with_denied<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.denied.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns whether the variable with the given name can be read.
+    #[must_use]
+    pub fn is_allowed(&self, name: &str) -> bool {
+        let matches = |pattern: &String| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => pattern == name,
+        };
+
+        !self.denied.iter().any(matches)
+            && self
+                .allowed
+                .as_ref()
+                .map_or(true, |allowed| allowed.iter().any(matches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_everything_by_default() {
+        assert!(EnvVarPolicy::default().is_allowed("HOME"));
+    }
+
+    #[test]
+    fn allowed_and_denied() {
+        let policy = EnvVarPolicy::default()
+            .with_allowed(["HOME", "APP_*"])
+            .with_denied(["APP_SECRET"]);
+
+        assert!(policy.is_allowed("HOME"));
+        assert!(policy.is_allowed("APP_NAME"));
+        assert!(!policy.is_allowed("APP_SECRET"));
+        assert!(!policy.is_allowed("PATH"));
+        assert!(!policy.is_allowed("HOMEDIR"));
+    }
+}
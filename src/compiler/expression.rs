@@ -5,6 +5,7 @@ use dyn_clone::{clone_trait_object, DynClone};
 pub use abort::Abort;
 pub use array::Array;
 pub use assignment::Assignment;
+pub use bit_not::BitNot;
 pub use block::Block;
 pub use container::{Container, Variant};
 pub use function::FunctionExpression;
@@ -19,6 +20,7 @@ pub use object::Object;
 pub use op::Op;
 pub use predicate::Predicate;
 pub use query::{Query, Target};
+pub use r#loop::{Break, Loop};
 pub use r#return::Return;
 pub use unary::Unary;
 pub use variable::Variable;
@@ -26,11 +28,12 @@ pub use variable::Variable;
 use crate::value::Value;
 
 use super::state::{TypeInfo, TypeState};
-use super::{Context, TypeDef};
+use super::{Context, IntegerRange, TypeDef};
 pub use super::{ExpressionError, Resolved};
 
 mod abort;
 mod array;
+mod bit_not;
 mod block;
 mod function_argument;
 mod group;
@@ -49,6 +52,7 @@ pub(crate) mod container;
 pub(crate) mod function;
 pub(crate) mod function_call;
 pub(crate) mod literal;
+pub(crate) mod r#loop;
 pub(crate) mod predicate;
 pub mod query;
 
@@ -68,6 +72,18 @@ pub trait Expression: Send + Sync + fmt::Debug + DynClone {
         None
     }
 
+    /// Resolve an expression to a lazily evaluated [`IntegerRange`], if
+    /// possible.
+    ///
+    /// This allows iterating functions such as `for_each` to consume the
+    /// result of `range` without materializing it as an array.
+    ///
+    /// This returns `None` for expressions that can only resolve to a
+    /// [`Value`].
+    fn resolve_range(&self, _ctx: &mut Context) -> Option<Result<IntegerRange, ExpressionError>> {
+        None
+    }
+
     /// Resolve an expression to its [`TypeDef`] type definition.
     /// This must be called with the _initial_ `TypeState`.
     ///
@@ -118,14 +134,16 @@ pub enum Expr {
     Unary(Unary),
     Abort(Abort),
     Return(Return),
+    Loop(Loop),
+    Break(Break),
 }
 
 impl Expr {
     pub fn as_str(&self) -> &str {
         use container::Variant::{Array, Block, Group, Object};
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Noop,
+            Op, Query, Return, Unary, Variable,
         };
 
         match self {
@@ -146,6 +164,8 @@ impl Expr {
             Unary(..) => "unary operation",
             Abort(..) => "abort operation",
             Return(..) => "return",
+            Loop(..) => "loop",
+            Break(..) => "break",
         }
     }
 
@@ -184,8 +204,8 @@ impl Expr {
 impl Expression for Expr {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Noop,
+            Op, Query, Return, Unary, Variable,
         };
 
         match self {
@@ -201,13 +221,15 @@ impl Expression for Expr {
             Unary(v) => v.resolve(ctx),
             Abort(v) => v.resolve(ctx),
             Return(v) => v.resolve(ctx),
+            Loop(v) => v.resolve(ctx),
+            Break(v) => v.resolve(ctx),
         }
     }
 
     fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Noop,
+            Op, Query, Return, Unary, Variable,
         };
 
         match self {
@@ -223,13 +245,22 @@ impl Expression for Expr {
             Unary(v) => Expression::resolve_constant(v, state),
             Abort(v) => Expression::resolve_constant(v, state),
             Return(v) => Expression::resolve_constant(v, state),
+            Loop(v) => Expression::resolve_constant(v, state),
+            Break(v) => Expression::resolve_constant(v, state),
+        }
+    }
+
+    fn resolve_range(&self, ctx: &mut Context) -> Option<Result<IntegerRange, ExpressionError>> {
+        match self {
+            Expr::FunctionCall(v) => v.resolve_range(ctx),
+            _ => None,
         }
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Noop,
+            Op, Query, Return, Unary, Variable,
         };
 
         match self {
@@ -245,6 +276,8 @@ impl Expression for Expr {
             Unary(v) => v.type_info(state),
             Abort(v) => v.type_info(state),
             Return(v) => v.type_info(state),
+            Loop(v) => v.type_info(state),
+            Break(v) => v.type_info(state),
         }
     }
 }
@@ -252,8 +285,8 @@ impl Expression for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Noop,
+            Op, Query, Return, Unary, Variable,
         };
 
         match self {
@@ -269,6 +302,8 @@ impl fmt::Display for Expr {
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
             Return(v) => v.fmt(f),
+            Loop(v) => v.fmt(f),
+            Break(v) => v.fmt(f),
         }
     }
 }
@@ -347,6 +382,18 @@ impl From<Return> for Expr {
     }
 }
 
+impl From<Loop> for Expr {
+    fn from(r#loop: Loop) -> Self {
+        Expr::Loop(r#loop)
+    }
+}
+
+impl From<Break> for Expr {
+    fn from(r#break: Break) -> Self {
+        Expr::Break(r#break)
+    }
+}
+
 impl From<Value> for Expr {
     fn from(value: Value) -> Self {
         use std::collections::BTreeMap;
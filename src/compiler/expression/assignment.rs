@@ -1,5 +1,6 @@
 use std::{convert::TryFrom, fmt};
 
+use crate::compiler::codes::WARNING_ARRAY_PADDING_CODE;
 use crate::compiler::expression::function_call::FunctionCallError::InvalidArgumentKind;
 use crate::compiler::expression::function_call::InvalidArgumentErrorContext;
 use crate::compiler::{
@@ -9,12 +10,12 @@ use crate::compiler::{
         ast::{self, Ident},
         Node,
     },
-    state::{TypeInfo, TypeState},
+    state::{ErrorBinding, TypeInfo, TypeState},
     type_def::Details,
     value::kind::DefaultValue,
-    CompileConfig, Context, Expression, Span, TypeDef,
+    CompileConfig, Context, Expression, ExpressionError, Span, TypeDef,
 };
-use crate::diagnostic::{DiagnosticMessage, Label, Note};
+use crate::diagnostic::{DiagnosticMessage, Label, Note, Severity};
 use crate::path::{OwnedSegment, OwnedTargetPath};
 use crate::path::{OwnedValuePath, PathPrefix};
 use crate::value::{Kind, Value};
@@ -167,6 +168,35 @@ impl Assignment {
         Ok(Self { variant })
     }
 
+    /// Returns `true` if the assignment can fail at runtime, because of the
+    /// [`WriteLimits`](crate::compiler::WriteLimits) or because target insert
+    /// errors are raised. This is independent of the type of the assigned
+    /// expression, and is reported by [`ProgramInfo::fallible`](crate::compiler::ProgramInfo::fallible).
+    pub(crate) fn may_fail(&self, config: &CompileConfig) -> bool {
+        self.targets().iter().any(|target| target.may_fail(config))
+    }
+
+    /// Warns about the indexes past the `max_array_padding` limit, that fail to
+    /// insert into short arrays.
+    pub(crate) fn padding_warnings(&self, config: &CompileConfig, span: Span) -> Vec<Warning> {
+        let Some(limit) = config.write_limits().max_array_padding else {
+            return vec![];
+        };
+
+        self.targets()
+            .into_iter()
+            .filter_map(|target| {
+                let index = target.index_past_padding_limit(limit)?;
+                Some(Warning::ArrayPadding {
+                    target: target.to_string(),
+                    index,
+                    limit,
+                    span,
+                })
+            })
+            .collect()
+    }
+
     /// Get a list of targets for this assignment.
     ///
     /// For regular assignments, this contains a single target, for infallible
@@ -193,17 +223,14 @@ fn verify_mutable(
     assignment_span: Span,
 ) -> Result<(), Error> {
     match target {
-        Target::External(target_path) => {
-            if config.is_read_only_path(target_path) {
-                Err(Error {
-                    variant: ErrorVariant::ReadOnly,
-                    expr_span,
-                    assignment_span,
-                })
-            } else {
-                Ok(())
-            }
-        }
+        Target::External(target_path) => match config.read_only_reason(target_path) {
+            Some(reason) => Err(Error {
+                variant: ErrorVariant::ReadOnly(reason),
+                expr_span,
+                assignment_span,
+            }),
+            None => Ok(()),
+        },
         Target::Internal(_, _) | Target::Noop => Ok(()),
     }
 }
@@ -349,7 +376,12 @@ pub(crate) enum Target {
 }
 
 impl Target {
-    fn insert_type_def(&self, state: &mut TypeState, new_type_def: TypeDef, value: Option<Value>) {
+    pub(crate) fn insert_type_def(
+        &self,
+        state: &mut TypeState,
+        new_type_def: TypeDef,
+        value: Option<Value>,
+    ) {
         match self {
             Self::Noop => {}
             Self::Internal(ident, path) => {
@@ -387,9 +419,33 @@ impl Target {
         }
     }
 
-    fn insert(&self, value: Value, ctx: &mut Context) {
+    fn insert(&self, value: Value, ctx: &mut Context) -> Result<(), ExpressionError> {
+        if !ctx.is_observed() || matches!(self, Self::Noop) {
+            return self.write(value, ctx);
+        }
+
+        let old = self.value(ctx);
+        let new = value.clone();
+        self.write(value, ctx)?;
+
+        let target = self.to_string();
+        ctx.observe(|observer| observer.on_assignment(&target, old.as_ref(), &new));
+        Ok(())
+    }
+
+    fn write(&self, value: Value, ctx: &mut Context) -> Result<(), ExpressionError> {
         use Target::{External, Internal, Noop};
 
+        if let Some(limit) = ctx.max_array_padding() {
+            let padding = self.insert_padding(ctx);
+            if padding > limit {
+                return Err(format!(
+                    r#"assigning to {self} pads an array with {padding} null elements, exceeding the limit of {limit}; use "array_fill" to pad arrays intentionally"#
+                )
+                .into());
+            }
+        }
+
         match self {
             Noop => {}
             Internal(ident, path) => {
@@ -397,7 +453,10 @@ impl Target {
                 // without any path appended and return early.
                 let path = match path.is_root() {
                     false => path,
-                    true => return ctx.state_mut().insert_variable(ident.clone(), value),
+                    true => {
+                        ctx.state_mut().insert_variable(ident.clone(), value);
+                        return Ok(());
+                    }
                 };
 
                 // Update existing variable using the provided path, or create a
@@ -413,7 +472,86 @@ impl Target {
             }
 
             External(path) => {
-                drop(ctx.target_mut().target_insert(path, value));
+                let result = ctx.target_mut().target_insert(path, value);
+                if ctx.raise_insert_errors() {
+                    result?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if inserting into the target can fail at runtime.
+    fn may_fail(&self, config: &CompileConfig) -> bool {
+        let limits = config.write_limits();
+
+        let pads = limits.max_array_padding.is_some()
+            && self
+                .path()
+                .segments
+                .iter()
+                .any(|segment| matches!(segment, OwnedSegment::Index(_)));
+
+        match self {
+            Self::Noop => false,
+            Self::Internal(..) => pads,
+            Self::External(_) => {
+                pads || config.raise_target_insert_errors()
+                    || limits.max_inserted_fields.is_some()
+                    || limits.max_depth.is_some()
+            }
+        }
+    }
+
+    /// The largest index of the path past the padding `limit`. Inserting at
+    /// this index fails unless the array already holds more than `index - limit`
+    /// elements.
+    fn index_past_padding_limit(&self, limit: usize) -> Option<usize> {
+        self.path()
+            .segments
+            .iter()
+            .filter_map(|segment| match segment {
+                OwnedSegment::Index(index) => usize::try_from(*index).ok(),
+                OwnedSegment::Field(_) => None,
+            })
+            .filter(|&index| index > limit)
+            .max()
+    }
+
+    /// The current value of the target, if any.
+    fn value(&self, ctx: &Context) -> Option<Value> {
+        use Target::{External, Internal, Noop};
+
+        match self {
+            Noop => None,
+            Internal(ident, path) => ctx
+                .state()
+                .variable(ident)
+                .and_then(|value| value.get(path))
+                .cloned(),
+            External(path) => ctx.target().target_get(path).ok().flatten().cloned(),
+        }
+    }
+
+    /// The number of `null` elements that inserting into the target pads an
+    /// array with.
+    fn insert_padding(&self, ctx: &Context) -> usize {
+        use Target::{External, Internal, Noop};
+
+        match self {
+            Noop => 0,
+            Internal(ident, path) => ctx
+                .state()
+                .variable(ident)
+                .unwrap_or(&Value::Null)
+                .insert_padding(path),
+            External(path) => {
+                let root = OwnedTargetPath::root(path.prefix);
+                match ctx.target().target_get(&root) {
+                    Ok(Some(value)) => value.insert_padding(&path.path),
+                    _ => Value::Null.insert_padding(&path.path),
+                }
             }
         }
     }
@@ -435,7 +573,7 @@ impl fmt::Display for Target {
             Noop => f.write_str("_"),
             Internal(ident, path) if path.is_root() => ident.fmt(f),
             Internal(ident, path) => write!(f, "{ident}{path}"),
-            External(path) => write!(f, "{path}"),
+            External(path) => f.write_str(&path.to_string_lossless()),
         }
     }
 }
@@ -528,7 +666,7 @@ where
         let value = match self {
             Single { target, expr } => {
                 let value = expr.resolve(ctx)?;
-                target.insert(value.clone(), ctx);
+                target.insert(value.clone(), ctx)?;
                 value
             }
             Infallible {
@@ -538,14 +676,14 @@ where
                 default,
             } => match expr.resolve(ctx) {
                 Ok(value) => {
-                    ok.insert(value.clone(), ctx);
-                    err.insert(Value::Null, ctx);
+                    ok.insert(value.clone(), ctx)?;
+                    err.insert(Value::Null, ctx)?;
                     value
                 }
                 Err(error) => {
-                    ok.insert(default.clone(), ctx);
+                    ok.insert(default.clone(), ctx)?;
                     let value = Value::from(error.to_string());
-                    err.insert(value.clone(), ctx);
+                    err.insert(value.clone(), ctx)?;
                     value
                 }
             },
@@ -579,12 +717,37 @@ where
                     .infallible();
 
                 let const_value = expr.resolve_constant(&state);
-                ok.insert_type_def(&mut state, ok_type, const_value);
+                ok.insert_type_def(&mut state, ok_type, const_value.clone());
 
                 // The "err" type is either the error message "bytes" or "null" (not undefined).
                 let err_type = TypeDef::from(Kind::bytes().or_null());
                 err.insert_type_def(&mut state, err_type, None);
 
+                // Remember what "ok" holds depending on "err", so that checking
+                // "err" can narrow the type of "ok" (e.g. `if err == null { ... }`).
+                if let Target::Internal(err, path) = err {
+                    let ok = match ok {
+                        Target::Internal(ok, path) if path.is_root() => Some(ok.clone()),
+                        _ => None,
+                    };
+
+                    if path.is_root() && ok.as_ref() != Some(err) {
+                        let binding = ErrorBinding {
+                            ok,
+                            success: Details {
+                                type_def: expr_result.clone().infallible(),
+                                value: const_value,
+                            },
+                            failure: Details {
+                                type_def: TypeDef::from(default.kind()),
+                                value: Some(default.clone()),
+                            },
+                        };
+
+                        state.local.insert_error_binding(err.clone(), binding);
+                    }
+                }
+
                 // Return type of the assignment expression itself is either the "expr" type or "bytes (the error message).
                 let assignment_result = expr_result.infallible().impure().or_bytes();
 
@@ -641,7 +804,7 @@ pub(crate) enum ErrorVariant {
     InvalidTarget(Span),
 
     #[error("mutation of read-only value")]
-    ReadOnly,
+    ReadOnly(String),
 
     #[error("parent path segment rejects this mutation")]
     InvalidParentPathSegment {
@@ -679,7 +842,7 @@ impl DiagnosticMessage for Error {
             InfallibleAssignment(..) => 104,
             InvalidTarget(..) => 641,
             InvalidParentPathSegment { .. } => 642,
-            ReadOnly => 315,
+            ReadOnly(..) => 315,
         }
     }
 
@@ -748,10 +911,10 @@ impl DiagnosticMessage for Error {
                 Label::primary("invalid assignment target", span),
                 Label::context("use one of variable or path", span),
             ],
-            ReadOnly => vec![Label::primary(
-                "mutation of read-only value",
-                self.assignment_span,
-            )],
+            ReadOnly(reason) => vec![
+                Label::primary("mutation of read-only value", self.assignment_span),
+                Label::context(reason, self.assignment_span),
+            ],
             InvalidParentPathSegment {
                 variant,
                 parent_kind,
@@ -818,6 +981,60 @@ impl DiagnosticMessage for Error {
     }
 }
 
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Warning {
+    #[error("array padded past the limit")]
+    ArrayPadding {
+        target: String,
+        index: usize,
+        limit: usize,
+        span: Span,
+    },
+}
+
+impl DiagnosticMessage for Warning {
+    fn code(&self) -> usize {
+        match self {
+            Warning::ArrayPadding { .. } => WARNING_ARRAY_PADDING_CODE,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Warning::ArrayPadding {
+                target,
+                index,
+                limit,
+                span,
+            } => vec![
+                Label::primary(
+                    format!("assigning to {target} pads an array with up to {index} null elements, exceeding the limit of {limit}"),
+                    span,
+                ),
+                Label::context(
+                    format!(
+                        "this fails at runtime unless the array holds at least {} elements",
+                        index - limit
+                    ),
+                    span,
+                ),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::Hint(
+            r#"use "array_fill" to pad arrays intentionally"#.to_owned(),
+        )]
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::compiler::state::{ExternalEnv, LocalEnv};
@@ -0,0 +1,130 @@
+use std::fmt;
+
+use crate::compiler::state::{TypeInfo, TypeState};
+use crate::compiler::{
+    expression::{Expr, Resolved},
+    parser::Node,
+    value::{Kind, VrlValueConvert},
+    Context, Expression, Span, TypeDef,
+};
+use crate::diagnostic::{DiagnosticMessage, Label, Note, Urls};
+
+pub(crate) type Result = std::result::Result<BitNot, Error>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitNot {
+    inner: Box<Expr>,
+}
+
+impl BitNot {
+    pub fn new(node: Node<Expr>, tilde_span: Span, state: &TypeState) -> Result {
+        let (expr_span, expr) = node.take();
+        let type_def = expr.type_info(state).result;
+
+        if !type_def.is_integer() {
+            return Err(Error {
+                variant: ErrorVariant::NonInteger(type_def.into()),
+                tilde_span,
+                expr_span,
+            });
+        }
+
+        Ok(Self {
+            inner: Box::new(expr),
+        })
+    }
+}
+
+impl Expression for BitNot {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        Ok((!self.inner.resolve(ctx)?.try_integer()?).into())
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        let mut state = state.clone();
+        let result = self.inner.apply_type_info(&mut state);
+        TypeInfo::new(
+            state,
+            TypeDef::integer()
+                .maybe_fallible(result.is_fallible())
+                .with_returns(result.returns().clone()),
+        )
+    }
+}
+
+impl fmt::Display for BitNot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "~{}", self.inner)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Error {
+    pub(crate) variant: ErrorVariant,
+
+    tilde_span: Span,
+    expr_span: Span,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum ErrorVariant {
+    #[error("non-integer bitwise negation")]
+    NonInteger(Kind),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.variant)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.variant)
+    }
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use ErrorVariant::NonInteger;
+
+        match &self.variant {
+            NonInteger(..) => 661,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use ErrorVariant::NonInteger;
+
+        match &self.variant {
+            NonInteger(kind) => vec![
+                Label::primary(
+                    "bitwise negation only works on integer values",
+                    self.tilde_span,
+                ),
+                Label::context(
+                    format!("this expression resolves to {kind}"),
+                    self.expr_span,
+                ),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use ErrorVariant::NonInteger;
+
+        match &self.variant {
+            NonInteger(..) => {
+                vec![
+                    Note::CoerceValue,
+                    Note::SeeDocs(
+                        "type coercion".to_owned(),
+                        Urls::func_docs("#coerce-functions"),
+                    ),
+                ]
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::compiler::state::{TypeInfo, TypeState};
-use crate::compiler::{Context, Expression, Resolved, TypeDef};
+use crate::compiler::{Context, Expression, ExpressionError, IntegerRange, Resolved, TypeDef};
 use crate::value::Value;
 use dyn_clone::DynClone;
 use std::fmt;
@@ -27,6 +27,14 @@ pub trait FunctionExpression: Send + Sync + fmt::Debug + DynClone + Clone + 'sta
         None
     }
 
+    /// Resolves the function to a lazily evaluated [`IntegerRange`], for
+    /// functions that return a range of integers.
+    ///
+    /// See [`Expression::resolve_range`].
+    fn resolve_range(&self, _ctx: &mut Context) -> Option<Result<IntegerRange, ExpressionError>> {
+        None
+    }
+
     /// Converts this function to a normal `Expression`.
     fn as_expr(&self) -> Box<dyn Expression> {
         Box::new(FunctionExpressionAdapter {
@@ -45,6 +53,14 @@ impl<T: FunctionExpression + Debug + Clone> Expression for FunctionExpressionAda
         self.inner.resolve(ctx)
     }
 
+    fn resolve_constant(&self, _state: &TypeState) -> Option<Value> {
+        self.inner.as_value()
+    }
+
+    fn resolve_range(&self, ctx: &mut Context) -> Option<Result<IntegerRange, ExpressionError>> {
+        self.inner.resolve_range(ctx)
+    }
+
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         let result = self.inner.type_def(state);
         TypeInfo::new(state, result)
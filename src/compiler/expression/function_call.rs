@@ -1,19 +1,23 @@
 use std::{fmt, sync::Arc};
 
-use crate::compiler::state::{TypeInfo, TypeState};
+use crate::compiler::codes::{WARNING_FAILING_CONSTANT_CALL_CODE, WARNING_NARROWED_ABORT_CODE};
+use crate::compiler::state::{RuntimeState, TypeInfo, TypeState};
 use crate::compiler::{
-    expression::{levenstein, ExpressionError, FunctionArgument},
+    expression::{levenstein, Expr, ExpressionError, FunctionArgument},
     function::{
+        self,
         closure::{self, VariableKind},
-        ArgumentList, Example, FunctionClosure, FunctionCompileContext, Parameter,
+        ArgumentList, Example, FunctionClosure, FunctionCompileContext, Parameter, TypeGuard,
     },
     parser::{Ident, Node},
     state::LocalEnv,
     type_def::Details,
     value::Kind,
-    CompileConfig, Context, Expression, Function, Resolved, Span, TypeDef,
+    CompileConfig, Context, Expression, Function, IntegerRange, Resolved, Span, TargetValue,
+    TimeZone, TypeDef,
 };
-use crate::diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use crate::diagnostic::{DiagnosticMessage, Label, Note, Severity, Urls};
+use crate::value::{ObjectMap, Secrets, Value};
 
 use super::Block;
 
@@ -27,11 +31,13 @@ pub(crate) struct Builder<'a> {
     closure: Option<(Vec<Ident>, closure::Input)>,
     list: ArgumentList,
     function: &'a dyn Function,
+    narrowed_arguments: bool,
 }
 
 pub(crate) struct CallCompilationResult {
     pub(crate) function_call: FunctionCall,
     pub(crate) error: Option<FunctionCallError>,
+    pub(crate) warning: Option<Warning>,
 }
 
 impl<'a> Builder<'a> {
@@ -53,14 +59,20 @@ impl<'a> Builder<'a> {
         let (ident_span, ident) = ident.take();
 
         // Check if function exists.
-        let Some((function_id, function)) = funcs
+        let (namespace, name) = match ident.as_ref().split_once("::") {
+            Some((namespace, name)) => (Some(namespace), name),
+            None => (None, ident.as_ref()),
+        };
+
+        let mut candidates = funcs
             .iter()
             .enumerate()
-            .find(|(_pos, f)| f.identifier() == ident.as_ref())
-        else {
+            .filter(|(_pos, f)| f.namespace() == namespace && f.identifier() == name);
+
+        let Some((function_id, function)) = candidates.next() else {
             let idents = funcs
                 .iter()
-                .map(|func| func.identifier())
+                .map(|func| function::qualified_identifier(func.as_ref()))
                 .collect::<Vec<_>>();
 
             return Err(FunctionCallError::Undefined {
@@ -70,6 +82,14 @@ impl<'a> Builder<'a> {
             });
         };
 
+        // Unqualified identifiers registered more than once are ambiguous.
+        if candidates.next().is_some() {
+            return Err(FunctionCallError::Ambiguous {
+                ident_span,
+                ident: ident.clone(),
+            });
+        }
+
         // Check function arity.
         if arguments.len() > function.parameters().len() {
             let arguments_span = {
@@ -142,6 +162,7 @@ impl<'a> Builder<'a> {
                         got: expr_kind.clone(),
                         argument,
                         argument_span,
+                        unverified_arguments: Vec::new(),
                     },
                 ));
             } else if param_kind.is_superset(expr_kind).is_err() {
@@ -155,7 +176,8 @@ impl<'a> Builder<'a> {
                 });
             }
 
-            list.insert(parameter.keyword, argument.into_inner());
+            let expr_span = argument.expr_span();
+            list.insert(parameter.keyword, argument.into_inner(), expr_span);
         }
 
         // Check missing required arguments.
@@ -193,9 +215,17 @@ impl<'a> Builder<'a> {
             closure,
             list,
             function: function.as_ref(),
+            narrowed_arguments: false,
         })
     }
 
+    /// Sets whether the type of an argument was narrowed by the condition of
+    /// an enclosing `if` statement.
+    pub(crate) fn narrowed_arguments(mut self, narrowed: bool) -> Self {
+        self.narrowed_arguments = narrowed;
+        self
+    }
+
     fn check_closure(
         function: &dyn Function,
         closure_variables: Option<Node<Vec<Node<Ident>>>>,
@@ -217,7 +247,7 @@ impl<'a> Builder<'a> {
             }
 
             // Error if closure is missing from function that expects one.
-            (Some(definition), None) => {
+            (Some(definition), None) if !definition.is_optional => {
                 let example = definition.inputs.first().map(|input| input.example);
 
                 return Err(FunctionCallError::MissingClosure { call_span, example });
@@ -413,15 +443,34 @@ impl<'a> Builder<'a> {
 
         let mut compile_ctx = FunctionCompileContext::new(self.call_span, temp_config);
 
+        // Variables only resolve to their constant value at compile time, so
+        // the call of a pure function whose arguments are all constant is
+        // compiled with the values as literal arguments, and evaluated once.
+        let constant_arguments = if self.function.pure()
+            && closure.is_none()
+            && self.arguments_with_unknown_type_validity.is_empty()
+        {
+            self.constant_arguments(state_before_function_args)
+        } else {
+            None
+        };
+        let folding = constant_arguments.is_some();
+
         let expr = self
             .function
             .compile(
                 state_before_function_args,
                 &mut compile_ctx,
-                self.list.clone(),
+                constant_arguments.unwrap_or_else(|| self.list.clone()),
             )
             .map_err(|error| FunctionCallError::Compilation { call_span, error })?;
 
+        let (expr, mut warning) = if folding {
+            self.fold(expr, state_before_function_args)
+        } else {
+            (expr, None)
+        };
+
         // Re-insert the external context into the compiler state.
         *config = compile_ctx.into_config();
 
@@ -435,9 +484,20 @@ impl<'a> Builder<'a> {
                 .result
                 .is_fallible()
         {
-            return Err(FunctionCallError::AbortInfallible {
+            let abort_span = Span::new(ident_span.end(), ident_span.end() + 1);
+
+            // The call may only be infallible because an `if` condition checks
+            // the type of its arguments, in which case the abort was needed
+            // before types were narrowed, and is only reported with a warning.
+            if !self.narrowed_arguments {
+                return Err(FunctionCallError::AbortInfallible {
+                    ident_span,
+                    abort_span,
+                });
+            }
+            warning = Some(Warning::NarrowedAbort {
                 ident_span,
-                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+                abort_span,
             });
         }
 
@@ -468,6 +528,20 @@ impl<'a> Builder<'a> {
                         argument_span: argument
                             .keyword_span()
                             .unwrap_or_else(|| argument.expr_span()),
+                        unverified_arguments: self
+                            .arguments_with_unknown_type_validity
+                            .iter()
+                            .map(|(parameter, argument)| UnverifiedArgument {
+                                keyword: parameter.keyword,
+                                expected: parameter.kind(),
+                                got: argument
+                                    .expr()
+                                    .type_info(state_before_function_args)
+                                    .result
+                                    .into(),
+                                argument: argument.inner().to_string(),
+                            })
+                            .collect(),
                     },
                 ));
             }
@@ -482,13 +556,71 @@ impl<'a> Builder<'a> {
                 closure,
                 span: call_span,
                 ident: self.function.identifier(),
+                type_guard: self.function.type_guard(),
                 function_id: self.function_id,
                 arguments: self.arguments.clone(),
             },
             error: invalid_argument_error,
+            warning,
         })
     }
 
+    /// Returns the arguments of the call as literals, if they are all
+    /// constant.
+    fn constant_arguments(&self, state: &TypeState) -> Option<ArgumentList> {
+        let mut list = self.list.clone();
+        for argument in list.arguments.values_mut() {
+            *argument = Expr::from(argument.resolve_constant(state)?);
+        }
+        Some(list)
+    }
+
+    /// Evaluates the call of a pure function compiled with constant
+    /// arguments.
+    ///
+    /// The folded call keeps the fallibility of the function, so that the
+    /// program type-checks the same whether the call is folded or not. A call
+    /// that fails is left to fail at runtime, as it did before being folded,
+    /// and is reported with a warning, since it fails on every run.
+    ///
+    /// Pure functions don't depend on the target or the timezone, so the call
+    /// is evaluated against an empty target and the default timezone.
+    fn fold(
+        &self,
+        expr: Box<dyn Expression>,
+        state: &TypeState,
+    ) -> (Box<dyn Expression>, Option<Warning>) {
+        // The function already evaluated the call itself.
+        if expr.resolve_constant(state).is_some() {
+            return (expr, None);
+        }
+
+        let mut target = TargetValue {
+            value: Value::Null,
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut runtime_state = RuntimeState::default();
+        let timezone = TimeZone::default();
+        let mut runtime_ctx = Context::new(&mut target, &mut runtime_state, &timezone);
+
+        match expr.resolve(&mut runtime_ctx) {
+            Ok(value) => {
+                let type_def = TypeDef::from(value.kind())
+                    .maybe_fallible(expr.type_info(state).result.is_fallible());
+
+                (Box::new(FoldedCall { value, type_def }), None)
+            }
+            Err(error) => {
+                let warning = Warning::ConstantCallFails {
+                    call_span: self.call_span,
+                    error: error.to_string(),
+                };
+                (expr, Some(warning))
+            }
+        }
+    }
+
     fn compile_closure(
         &mut self,
         closure_block: Option<Node<(Block, TypeDef)>>,
@@ -561,10 +693,13 @@ pub struct FunctionCall {
     // used for equality check
     pub(crate) ident: &'static str,
 
+    // used to narrow the type of the first argument in `if` branches
+    pub(crate) type_guard: Option<TypeGuard>,
+
     // May be used by the LLVM runtime. If not, it should be removed
     #[allow(dead_code)]
     function_id: usize,
-    arguments: Arc<Vec<Node<FunctionArgument>>>,
+    pub(crate) arguments: Arc<Vec<Node<FunctionArgument>>>,
 }
 
 impl FunctionCall {
@@ -636,12 +771,12 @@ impl FunctionCall {
             .map(|arg| format!("{:?}", arg.inner()))
             .collect::<Vec<_>>()
     }
-}
 
-impl Expression for FunctionCall {
-    fn resolve(&self, ctx: &mut Context) -> Resolved {
-        self.expr.resolve(ctx).map_err(|err| match err {
+    /// Attributes an error of the function to this call.
+    fn map_error(&self, err: ExpressionError) -> ExpressionError {
+        match err {
             ExpressionError::Abort { .. }
+            | ExpressionError::Break { .. }
             | ExpressionError::Fallible { .. }
             | ExpressionError::Missing { .. } => {
                 // propagate the error
@@ -674,7 +809,37 @@ impl Expression for FunctionCall {
                     notes,
                 }
             }
-        })
+        }
+    }
+}
+
+impl Expression for FunctionCall {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        ctx.observe(|observer| observer.on_function_enter(self.ident, self.span));
+
+        let result = self.expr.resolve(ctx).map_err(|err| self.map_error(err));
+
+        ctx.observe(|observer| observer.on_function_exit(self.ident, self.span, &result));
+        result
+    }
+
+    fn resolve_range(&self, ctx: &mut Context) -> Option<Result<IntegerRange, ExpressionError>> {
+        ctx.observe(|observer| observer.on_function_enter(self.ident, self.span));
+
+        let result = self
+            .expr
+            .resolve_range(ctx)?
+            .map_err(|err| self.map_error(err));
+
+        ctx.observe(|observer| {
+            let result = result.clone().map(IntegerRange::into_value);
+            observer.on_function_exit(self.ident, self.span, &result);
+        });
+        Some(result)
+    }
+
+    fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
+        self.expr.resolve_constant(state)
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
@@ -774,6 +939,23 @@ impl Expression for FunctionCall {
             expr_result = expr_result.infallible();
         }
 
+        // The closure can change the variables and the event it can access,
+        // whose values are then no longer known at compile-time.
+        if let Some(closure) = &self.closure {
+            let closure_state = closure.block.type_info(&state).state;
+
+            for (ident, details) in &mut state.local.bindings {
+                if closure_state.local.variable(ident) != Some(details) {
+                    details.value = None;
+                }
+            }
+            if closure_state.external.target() != state.external.target() {
+                let mut target = state.external.target().clone();
+                target.value = None;
+                state.external.update_target(target);
+            }
+        }
+
         TypeInfo::new(state, expr_result)
     }
 }
@@ -818,6 +1000,27 @@ impl fmt::Debug for FunctionCall {
     }
 }
 
+/// A call of a pure function evaluated at compile time.
+#[derive(Debug, Clone)]
+struct FoldedCall {
+    value: Value,
+    type_def: TypeDef,
+}
+
+impl Expression for FoldedCall {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        Ok(self.value.clone())
+    }
+
+    fn resolve_constant(&self, _: &TypeState) -> Option<Value> {
+        Some(self.value.clone())
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        TypeInfo::new(state, self.type_def.clone())
+    }
+}
+
 impl PartialEq for FunctionCall {
     fn eq(&self, other: &Self) -> bool {
         self.ident == other.ident
@@ -834,6 +1037,48 @@ pub(crate) struct InvalidArgumentErrorContext {
     pub(crate) got: Kind,
     pub(crate) argument: FunctionArgument,
     pub(crate) argument_span: Span,
+    /// Every argument that makes the function call fallible.
+    pub(crate) unverified_arguments: Vec<UnverifiedArgument>,
+}
+
+/// An argument whose type only partially matches the parameter it's passed
+/// to, which makes the function call fallible.
+#[derive(Debug, Clone)]
+pub(crate) struct UnverifiedArgument {
+    pub(crate) keyword: &'static str,
+    pub(crate) expected: Kind,
+    pub(crate) got: Kind,
+    pub(crate) argument: String,
+}
+
+impl UnverifiedArgument {
+    /// Explains which of the types the argument resolves to aren't accepted by
+    /// the parameter.
+    fn notes(&self) -> Vec<Note> {
+        let Self {
+            keyword,
+            expected,
+            got,
+            argument,
+        } = self;
+
+        let mut notes = vec![
+            Note::Basic(format!(
+                r#"  the parameter "{keyword}" expects {}"#,
+                describe_kind(expected)
+            )),
+            Note::Basic(format!("  {argument} resolves to {}", describe_kind(got))),
+        ];
+
+        let rejected = got.without(expected);
+        if !rejected.is_never() {
+            notes.push(Note::Basic(format!(
+                r#"  {argument} can be {rejected}, which "{keyword}" doesn't accept"#
+            )));
+        }
+
+        notes
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -843,9 +1088,12 @@ pub(crate) enum FunctionCallError {
     Undefined {
         ident_span: Span,
         ident: Ident,
-        idents: Vec<&'static str>,
+        idents: Vec<String>,
     },
 
+    #[error("ambiguous function call")]
+    Ambiguous { ident_span: Span, ident: Ident },
+
     #[error("wrong number of function arguments")]
     WrongNumberOfArgs { arguments_span: Span, max: usize },
 
@@ -902,18 +1150,29 @@ pub(crate) enum FunctionCallError {
         found_kind: Kind,
         expected_kind: Kind,
     },
+
+    #[error("function call not allowed")]
+    Disallowed {
+        ident_span: Span,
+        reason: &'static str,
+    },
+
+    #[error("too many function calls")]
+    TooManyCalls { ident_span: Span, max: usize },
 }
 
 impl DiagnosticMessage for FunctionCallError {
     fn code(&self) -> usize {
         use FunctionCallError::{
-            AbortInfallible, ClosureArityMismatch, ClosureParameterTypeMismatch, Compilation,
-            FallibleArgument, InvalidArgumentKind, MissingArgument, MissingClosure,
-            ReturnTypeMismatch, Undefined, UnexpectedClosure, UnknownKeyword, WrongNumberOfArgs,
+            AbortInfallible, Ambiguous, ClosureArityMismatch, ClosureParameterTypeMismatch,
+            Compilation, Disallowed, FallibleArgument, InvalidArgumentKind, MissingArgument,
+            MissingClosure, ReturnTypeMismatch, TooManyCalls, Undefined, UnexpectedClosure,
+            UnknownKeyword, WrongNumberOfArgs,
         };
 
         match self {
             Undefined { .. } => 105,
+            Ambiguous { .. } => 114,
             WrongNumberOfArgs { .. } => 106,
             UnknownKeyword { .. } => 108,
             Compilation { .. } => 610,
@@ -926,14 +1185,17 @@ impl DiagnosticMessage for FunctionCallError {
             ClosureArityMismatch { .. } => 120,
             ClosureParameterTypeMismatch { .. } => 121,
             ReturnTypeMismatch { .. } => 122,
+            Disallowed { .. } => 112,
+            TooManyCalls { .. } => 113,
         }
     }
 
     fn labels(&self) -> Vec<Label> {
         use FunctionCallError::{
-            AbortInfallible, ClosureArityMismatch, ClosureParameterTypeMismatch, Compilation,
-            FallibleArgument, InvalidArgumentKind, MissingArgument, MissingClosure,
-            ReturnTypeMismatch, Undefined, UnexpectedClosure, UnknownKeyword, WrongNumberOfArgs,
+            AbortInfallible, Ambiguous, ClosureArityMismatch, ClosureParameterTypeMismatch,
+            Compilation, Disallowed, FallibleArgument, InvalidArgumentKind, MissingArgument,
+            MissingClosure, ReturnTypeMismatch, TooManyCalls, Undefined, UnexpectedClosure,
+            UnknownKeyword, WrongNumberOfArgs,
         };
 
         match self {
@@ -943,6 +1205,19 @@ impl DiagnosticMessage for FunctionCallError {
                 idents,
             } => {
                 let mut vec = vec![Label::primary("undefined function", ident_span)];
+
+                // Prefer pointing at a namespaced function with the exact same
+                // name over a fuzzy match.
+                let suffix = format!("::{ident}");
+                if let Some(qualified) = idents.iter().find(|id| id.ends_with(&suffix)) {
+                    vec.push(Label::context(
+                        format!(r#"did you mean "{qualified}"?"#),
+                        ident_span,
+                    ));
+
+                    return vec;
+                }
+
                 let ident_chars = ident.as_ref().chars().collect::<Vec<_>>();
 
                 if let Some((idx, _)) = idents
@@ -955,7 +1230,7 @@ impl DiagnosticMessage for FunctionCallError {
                     .min_by_key(|(_, score)| *score)
                 {
                     {
-                        let guessed: &str = idents[idx];
+                        let guessed: &str = &idents[idx];
                         vec.push(Label::context(
                             format!(r#"did you mean "{guessed}"?"#),
                             ident_span,
@@ -966,6 +1241,14 @@ impl DiagnosticMessage for FunctionCallError {
                 vec
             }
 
+            Ambiguous { ident_span, ident } => vec![
+                Label::primary("function is registered more than once", ident_span),
+                Label::context(
+                    format!(r#"register custom functions under a namespace, e.g. "myco::{ident}""#),
+                    ident_span,
+                ),
+            ],
+
             WrongNumberOfArgs {
                 arguments_span,
                 max,
@@ -1004,7 +1287,10 @@ impl DiagnosticMessage for FunctionCallError {
                 .labels()
                 .into_iter()
                 .map(|mut label| {
-                    label.span = *call_span;
+                    // labels without a span of their own point at the function call
+                    if label.span == Span::default() {
+                        label.span = *call_span;
+                    }
                     label
                 })
                 .collect(),
@@ -1037,27 +1323,19 @@ impl DiagnosticMessage for FunctionCallError {
                 let expected = context.parameter.kind();
                 let expr_span = context.argument.span();
 
-                // TODO: extract this out into a helper
-                let kind_str = |kind: &Kind| {
-                    if kind.is_any() {
-                        kind.to_string()
-                    } else if kind.is_exact() {
-                        format!("the exact type {kind}")
-                    } else {
-                        format!("one of {kind}")
-                    }
-                };
-
                 vec![
                     Label::primary(
-                        format!("this expression resolves to {}", kind_str(&context.got)),
+                        format!(
+                            "this expression resolves to {}",
+                            describe_kind(&context.got)
+                        ),
                         expr_span,
                     ),
                     Label::context(
                         format!(
                             r#"but the parameter "{}" expects {}"#,
                             keyword,
-                            kind_str(&expected)
+                            describe_kind(&expected)
                         ),
                         context.argument_span,
                     ),
@@ -1094,13 +1372,18 @@ impl DiagnosticMessage for FunctionCallError {
                 Label::primary("block returns invalid value type", block_span),
                 Label::context(format!("expected: {expected_kind}"), block_span),
                 Label::context(format!("received: {found_kind}"), block_span)],
+            Disallowed { ident_span, reason } => vec![Label::primary(*reason, ident_span)],
+            TooManyCalls { ident_span, max } => vec![
+                Label::primary("function call limit exceeded", ident_span),
+                Label::context(format!("the tenant policy allows at most {max} function calls"), ident_span),
+            ],
         }
     }
 
     fn notes(&self) -> Vec<Note> {
         use FunctionCallError::{
             AbortInfallible, Compilation, FallibleArgument, InvalidArgumentKind, MissingClosure,
-            WrongNumberOfArgs,
+            ReturnTypeMismatch, WrongNumberOfArgs,
         };
 
         match self {
@@ -1160,10 +1443,18 @@ impl DiagnosticMessage for FunctionCallError {
 
                 let abort = if context.abort_on_error { "!" } else { "" };
 
-                let mut notes = vec![];
-
                 let call = format!("{}{abort}({args})", context.function_ident);
 
+                let mut notes = Vec::new();
+                if !context.unverified_arguments.is_empty() {
+                    notes.push(Note::Basic(format!("why can {call} fail?")));
+                    for argument in &context.unverified_arguments {
+                        notes.append(&mut argument.notes());
+                    }
+                }
+
+                notes.append(&mut nested_kind_mismatch_notes(kind, &context.got));
+
                 notes.append(&mut Note::solution(
                     "ensuring an appropriate type at runtime",
                     vec![format!("{argument} = {guard}"), call.clone()],
@@ -1183,6 +1474,12 @@ impl DiagnosticMessage for FunctionCallError {
 
             Compilation { error, .. } => error.notes(),
 
+            ReturnTypeMismatch {
+                found_kind,
+                expected_kind,
+                ..
+            } => nested_kind_mismatch_notes(expected_kind, found_kind),
+
             MissingClosure { example, .. } if example.is_some() => {
                 let code = example.unwrap().source.to_owned();
                 vec![Note::Example(code)]
@@ -1193,8 +1490,78 @@ impl DiagnosticMessage for FunctionCallError {
     }
 }
 
+fn describe_kind(kind: &Kind) -> String {
+    if kind.is_any() {
+        kind.to_string()
+    } else if kind.is_exact() {
+        format!("the exact type {kind}")
+    } else {
+        format!("one of {kind}")
+    }
+}
+
+/// Describes where `found` diverges from `expected` inside objects and arrays.
+/// Mismatches of the values themselves are already covered by the labels.
+fn nested_kind_mismatch_notes(expected: &Kind, found: &Kind) -> Vec<Note> {
+    expected
+        .diff(found)
+        .into_iter()
+        .filter(|difference| !difference.starts_with(".:"))
+        .map(|difference| Note::Basic(format!("type mismatch at {difference}")))
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Warning {
+    #[error("function call error: {error}")]
+    ConstantCallFails { call_span: Span, error: String },
+
+    #[error("unnecessary abort of infallible function")]
+    NarrowedAbort { ident_span: Span, abort_span: Span },
+}
+
+impl DiagnosticMessage for Warning {
+    fn code(&self) -> usize {
+        match self {
+            Warning::ConstantCallFails { .. } => WARNING_FAILING_CONSTANT_CALL_CODE,
+            Warning::NarrowedAbort { .. } => WARNING_NARROWED_ABORT_CODE,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Warning::ConstantCallFails { call_span, error } => vec![
+                Label::primary(error, call_span),
+                Label::context(
+                    "the arguments are constant, so the call fails on every run",
+                    call_span,
+                ),
+            ],
+            Warning::NarrowedAbort {
+                ident_span,
+                abort_span,
+            } => vec![
+                Label::primary(
+                    "this function can't fail, as the type of its arguments is checked",
+                    ident_span,
+                ),
+                Label::context("remove this abort-instruction", abort_span),
+            ],
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use crate::compiler::{value::kind, FunctionExpression};
 
     use super::*;
@@ -1382,4 +1749,219 @@ mod tests {
 
         assert_eq!(Ok(expected), params);
     }
+
+    #[test]
+    fn return_type_mismatch_notes_nested_differences() {
+        let error = FunctionCallError::ReturnTypeMismatch {
+            block_span: Span::default(),
+            expected_kind: Kind::object(BTreeMap::from([("foo".into(), Kind::bytes())])),
+            found_kind: Kind::object(BTreeMap::from([("foo".into(), Kind::integer())])),
+        };
+
+        assert_eq!(
+            error.notes(),
+            vec![Note::Basic(
+                "type mismatch at .foo: expected string, found integer".to_owned()
+            )]
+        );
+
+        let error = FunctionCallError::ReturnTypeMismatch {
+            block_span: Span::default(),
+            expected_kind: Kind::boolean(),
+            found_kind: Kind::integer(),
+        };
+
+        assert!(error.notes().is_empty());
+    }
+
+    fn compile_err(source: &str, fns: &[Box<dyn Function>]) -> crate::diagnostic::DiagnosticList {
+        match crate::compiler::compile(source, fns) {
+            Ok(_) => panic!("expected the program to fail to compile"),
+            Err(diagnostics) => diagnostics,
+        }
+    }
+
+    #[test]
+    fn invalid_argument_kind_explains_every_unverified_argument() {
+        let source = r#"x = "foo"; if contains(.message, x, case_sensitive: .strict) { true }"#;
+        let diagnostics = compile_err(source, &crate::stdlib::all());
+
+        let notes = diagnostics[0]
+            .notes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        let explanation = [
+            r#"why can contains(.message, x, .strict) fail?"#,
+            r#"  the parameter "value" expects the exact type string"#,
+            "  .message resolves to any",
+            r#"  .message can be integer, float, boolean, timestamp, regex, null, undefined, array or object, which "value" doesn't accept"#,
+            r#"  the parameter "case_sensitive" expects the exact type boolean"#,
+            "  .strict resolves to any",
+            r#"  .strict can be string, integer, float, timestamp, regex, null, undefined, array or object, which "case_sensitive" doesn't accept"#,
+        ];
+        assert_eq!(notes[..explanation.len()], explanation, "{notes:#?}");
+    }
+
+    #[test]
+    fn namespaced_functions_require_qualified_name() {
+        let mut fns = crate::stdlib::all();
+        fns.extend(function::namespaced("myco", vec![Box::new(TestFn)]));
+
+        assert!(crate::compiler::compile("myco::test(1)", &fns).is_ok());
+
+        let diagnostics = compile_err("test(1)", &fns);
+
+        assert_eq!(diagnostics[0].code, 105);
+        assert_eq!(
+            diagnostics[0].labels[1].message,
+            r#"did you mean "myco::test"?"#
+        );
+    }
+
+    #[test]
+    fn duplicate_unqualified_functions_are_ambiguous() {
+        let fns: Vec<Box<dyn Function>> = vec![Box::new(TestFn), Box::new(TestFn)];
+
+        let diagnostics = compile_err("test(1)", &fns);
+
+        assert_eq!(diagnostics[0].code, 114);
+    }
+
+    /// Counts the calls it compiles and resolves, to tell folded calls apart.
+    #[derive(Debug)]
+    struct CountingFn {
+        compiles: Arc<AtomicUsize>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[derive(Clone, Debug)]
+    struct CountingFnExpr {
+        value: Box<dyn Expression>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl FunctionExpression for CountingFnExpr {
+        fn resolve(&self, ctx: &mut Context) -> Resolved {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.value.resolve(ctx)
+        }
+
+        fn type_def(&self, _state: &TypeState) -> TypeDef {
+            TypeDef::any().infallible()
+        }
+    }
+
+    impl Function for CountingFn {
+        fn identifier(&self) -> &'static str {
+            "counting"
+        }
+
+        fn examples(&self) -> &'static [crate::compiler::function::Example] {
+            &[]
+        }
+
+        fn parameters(&self) -> &'static [Parameter] {
+            &[Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            }]
+        }
+
+        fn pure(&self) -> bool {
+            true
+        }
+
+        fn compile(
+            &self,
+            _state: &TypeState,
+            _ctx: &mut FunctionCompileContext,
+            arguments: ArgumentList,
+        ) -> crate::compiler::function::Compiled {
+            self.compiles.fetch_add(1, Ordering::Relaxed);
+            let value = arguments.required("value");
+            let calls = Arc::clone(&self.calls);
+            Ok(CountingFnExpr { value, calls }.as_expr())
+        }
+    }
+
+    #[test]
+    fn pure_calls_with_constant_arguments_are_folded() {
+        use crate::compiler::runtime::Runtime;
+
+        let compiles = Arc::new(AtomicUsize::new(0));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fns: Vec<Box<dyn Function>> = vec![Box::new(CountingFn {
+            compiles: Arc::clone(&compiles),
+            calls: Arc::clone(&calls),
+        })];
+        let program = crate::compiler::compile("x = 2; [counting(x), counting(.y)]", &fns)
+            .unwrap()
+            .program;
+        assert_eq!(compiles.load(Ordering::Relaxed), 2);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let mut target = TargetValue {
+            value: crate::value!({"y": 3}),
+            metadata: crate::value!({}),
+            secrets: Secrets::new(),
+        };
+        for _ in 0..2 {
+            let result = Runtime::default()
+                .resolve(&mut target, &program, &TimeZone::default())
+                .unwrap();
+            assert_eq!(result, crate::value!([2, 3]));
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn failing_constant_calls_are_reported_and_fail_at_runtime() {
+        use crate::compiler::runtime::Runtime;
+
+        let result =
+            crate::compiler::compile(r#"to_syslog_severity!("bogus")"#, &crate::stdlib::all())
+                .unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, WARNING_FAILING_CONSTANT_CALL_CODE);
+        assert!(
+            result.warnings[0].message.contains("syslog"),
+            "{}",
+            result.warnings[0].message
+        );
+
+        let mut target = TargetValue {
+            value: crate::value!({}),
+            metadata: crate::value!({}),
+            secrets: Secrets::new(),
+        };
+        let error = Runtime::default()
+            .resolve(&mut target, &result.program, &TimeZone::default())
+            .unwrap_err();
+        assert!(error.to_string().contains("syslog"), "{error}");
+    }
+
+    #[test]
+    fn variables_changed_by_closures_are_not_folded() {
+        use crate::compiler::runtime::Runtime;
+
+        let program = crate::compiler::compile(
+            r#"keys = []; for_each({"a": 1}) -> |key, _value| { keys = push(keys, key) }; join!(keys, ",")"#,
+            &crate::stdlib::all(),
+        )
+        .unwrap()
+        .program;
+
+        let mut target = TargetValue {
+            value: crate::value!({}),
+            metadata: crate::value!({}),
+            secrets: Secrets::new(),
+        };
+        let value = Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap();
+        assert_eq!(value, crate::value!("a"));
+    }
 }
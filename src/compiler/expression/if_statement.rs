@@ -19,6 +19,7 @@ pub struct IfStatement {
 impl Expression for IfStatement {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let predicate = self.predicate.resolve(ctx)?.try_boolean()?;
+        ctx.observe(|observer| observer.on_branch(&self.predicate.to_string(), predicate));
 
         match predicate {
             true => self.if_block.resolve(ctx),
@@ -33,7 +34,11 @@ impl Expression for IfStatement {
         let mut state = state.clone();
         let predicate_info = self.predicate.apply_type_info(&mut state);
 
-        let if_info = self.if_block.type_info(&state);
+        let mut if_state = state.clone();
+        self.predicate.narrow_type_state(&mut if_state, true);
+        self.predicate.narrow_type_state(&mut state, false);
+
+        let if_info = self.if_block.type_info(&if_state);
 
         if let Some(else_block) = &self.else_block {
             let else_info = else_block.type_info(&state);
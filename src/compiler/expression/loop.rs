@@ -0,0 +1,451 @@
+use std::fmt;
+
+use crate::compiler::{
+    expression::{Block, Expr, Predicate, Resolved},
+    state::{TypeInfo, TypeState},
+    type_def::Details,
+    value::VrlValueConvert,
+    Context, Expression, Span, TypeDef,
+};
+use crate::diagnostic::{DiagnosticMessage, Label, Note};
+use crate::value::{Kind, Value};
+
+use super::ExpressionError;
+
+/// A loop running its block at most `max` times, or until a `break`
+/// statement of the block exits it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loop {
+    max: usize,
+    block: Block,
+}
+
+impl Loop {
+    /// The highest iteration bound a loop can have.
+    pub const MAX_ITERATIONS: usize = 1_000;
+
+    /// The number of iterations after which the types that still change from
+    /// one iteration to the next are widened, so that computing the types of
+    /// the loop takes a bounded number of iterations. The arrays that grow are
+    /// first widened to arrays of any length, and the types that still change
+    /// after as many iterations again are widened to any type.
+    const WIDENING_ITERATIONS: usize = 8;
+
+    #[must_use]
+    pub fn new(max: usize, block: Block) -> Self {
+        Self { max, block }
+    }
+}
+
+impl Expression for Loop {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        for _ in 0..self.max {
+            match self.block.resolve(ctx) {
+                Ok(_) => {}
+                Err(ExpressionError::Break { .. }) => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        let parent_locals = &state.local;
+        let scoped = |state: TypeState| TypeState {
+            local: parent_locals.clone().apply_child_scope(state.local),
+            external: state.external,
+        };
+
+        // The loop exits either at a `break` statement, or at the end of the
+        // last iteration.
+        let mut entry = state.clone();
+        let mut exit = state.clone();
+        let mut fallible = false;
+        let mut returns = Kind::never();
+
+        for iteration in 0..self.max {
+            let mut state = entry.clone();
+
+            for expr in self.block.exprs() {
+                let result = expr.apply_type_info(&mut state);
+
+                fallible |= result.is_fallible();
+                returns.merge_keep(result.returns().clone(), false);
+
+                if matches!(expr, Expr::Break(_)) {
+                    exit = exit.merge(scoped(state.clone()));
+                }
+            }
+
+            let next = entry.clone().merge(scoped(state));
+            if next == entry {
+                break;
+            }
+            entry = if iteration < Self::WIDENING_ITERATIONS {
+                next
+            } else {
+                widen(&entry, next, iteration >= 2 * Self::WIDENING_ITERATIONS)
+            };
+        }
+
+        TypeInfo::new(
+            exit.merge(entry),
+            TypeDef::null()
+                .maybe_fallible(fallible)
+                .with_returns(returns),
+        )
+    }
+}
+
+/// Widens the variables and external values whose type changed, by forgetting
+/// the indices of the arrays that grew, or to any type if `to_any` is set.
+fn widen(previous: &TypeState, mut next: TypeState, to_any: bool) -> TypeState {
+    let widen_details = |previous: Option<&Details>, details: &mut Details| {
+        if previous == Some(details) {
+            return;
+        }
+        details.value = None;
+        match previous {
+            Some(previous) if !to_any => {
+                widen_kind(previous.type_def.kind(), details.type_def.kind_mut());
+            }
+            _ => details.type_def = TypeDef::any(),
+        }
+    };
+
+    for (ident, details) in &mut next.local.bindings {
+        widen_details(previous.local.variable(ident), details);
+    }
+
+    let mut target = next.external.target().clone();
+    widen_details(Some(previous.external.target()), &mut target);
+    next.external.update_target(target);
+
+    if next.external.metadata_kind() != previous.external.metadata_kind() {
+        let mut metadata = Kind::any();
+        if !to_any {
+            metadata = next.external.metadata_kind().clone();
+            widen_kind(previous.external.metadata_kind(), &mut metadata);
+        }
+        next.external.update_metadata(metadata);
+    }
+
+    next
+}
+
+/// Forgets the indices of the arrays that aren't the same length as in the
+/// previous iteration.
+fn widen_kind(previous: &Kind, next: &mut Kind) {
+    if previous == next {
+        return;
+    }
+
+    if let Some(array) = next.as_array_mut() {
+        match previous.as_array() {
+            Some(previous) if previous.known().keys().eq(array.known().keys()) => {
+                for (index, kind) in array.known_mut() {
+                    widen_kind(&previous.known()[index], kind);
+                }
+            }
+            _ => array.anonymize(),
+        }
+    }
+
+    if let (Some(object), Some(previous)) = (next.as_object_mut(), previous.as_object()) {
+        for (field, kind) in object.known_mut() {
+            if let Some(previous) = previous.known().get(field) {
+                widen_kind(previous, kind);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "loop max: {} {}", self.max, self.block)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Break {
+    span: Span,
+    condition: Option<Predicate>,
+}
+
+impl Break {
+    #[must_use]
+    pub fn new(span: Span, condition: Option<Predicate>) -> Self {
+        Self { span, condition }
+    }
+}
+
+impl Expression for Break {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        if let Some(condition) = &self.condition {
+            if !condition.resolve(ctx)?.try_boolean()? {
+                return Ok(Value::Null);
+            }
+        }
+
+        Err(ExpressionError::Break { span: self.span })
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        match &self.condition {
+            Some(condition) => {
+                let mut state = state.clone();
+                let result = condition.apply_type_info(&mut state);
+
+                TypeInfo::new(
+                    state,
+                    TypeDef::null()
+                        .maybe_fallible(result.is_fallible())
+                        .with_returns(result.returns().clone()),
+                )
+            }
+            None => TypeInfo::new(state, TypeDef::never()),
+        }
+    }
+}
+
+impl fmt::Display for Break {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.condition {
+            Some(condition) => write!(f, "break if {condition}"),
+            None => f.write_str("break"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub(crate) struct Error {
+    variant: ErrorVariant,
+    span: Span,
+}
+
+impl Error {
+    pub(crate) fn unknown_keyword(keyword: String, span: Span) -> Self {
+        Self {
+            variant: ErrorVariant::UnknownKeyword(keyword),
+            span,
+        }
+    }
+
+    pub(crate) fn invalid_bound(span: Span) -> Self {
+        Self {
+            variant: ErrorVariant::InvalidBound,
+            span,
+        }
+    }
+
+    pub(crate) fn misplaced_break(span: Span) -> Self {
+        Self {
+            variant: ErrorVariant::MisplacedBreak,
+            span,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum ErrorVariant {
+    #[error("unknown loop argument")]
+    UnknownKeyword(String),
+
+    #[error("invalid loop bound")]
+    InvalidBound,
+
+    #[error("misplaced break statement")]
+    MisplacedBreak,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.variant)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.variant)
+    }
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use ErrorVariant::{InvalidBound, MisplacedBreak, UnknownKeyword};
+
+        match self.variant {
+            UnknownKeyword(..) => 633,
+            InvalidBound => 634,
+            MisplacedBreak => 635,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use ErrorVariant::{InvalidBound, MisplacedBreak, UnknownKeyword};
+
+        match &self.variant {
+            UnknownKeyword(keyword) => vec![
+                Label::primary(format!(r#"unknown argument "{keyword}""#), self.span),
+                Label::context(
+                    r#"the iteration bound of a loop is set with "max""#,
+                    self.span,
+                ),
+            ],
+            InvalidBound => vec![
+                Label::primary("the iteration bound is invalid", self.span),
+                Label::context(
+                    format!(
+                        "the bound must be a constant integer between 0 and {}",
+                        Loop::MAX_ITERATIONS
+                    ),
+                    self.span,
+                ),
+            ],
+            MisplacedBreak => vec![
+                Label::primary("break is not a statement of a loop block", self.span),
+                Label::context(
+                    r#"use "break if <condition>" in the loop block to exit conditionally"#,
+                    self.span,
+                ),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeErrorDocs]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::{compile, runtime::Runtime, TimeZone};
+    use crate::parser::ast::Ident;
+    use crate::stdlib;
+    use crate::value;
+    use crate::value::kind::Collection;
+    use crate::value::{Kind, Value};
+
+    fn resolve(source: &str) -> Value {
+        let program = compile(source, &stdlib::all()).unwrap().program;
+        let mut target = value!({});
+
+        Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap()
+    }
+
+    fn error_codes(source: &str) -> Vec<usize> {
+        match compile(source, &stdlib::all()) {
+            Ok(_) => panic!("expected compilation to fail"),
+            Err(diagnostics) => diagnostics.iter().map(|d| d.code).collect(),
+        }
+    }
+
+    #[test]
+    fn runs_until_break_or_bound() {
+        assert_eq!(
+            resolve("i = 0; loop max: 10 { i = i + 1; break if i == 3 }; i"),
+            value!(3)
+        );
+        assert_eq!(resolve("i = 0; loop max: 5 { i = i + 1 }; i"), value!(5));
+        assert_eq!(resolve("i = 0; loop max: 0 { i = i + 1 }; i"), value!(0));
+        assert_eq!(
+            resolve("i = 0; loop max: 5 { break; i = i + 1 }; i"),
+            value!(0)
+        );
+    }
+
+    #[test]
+    fn merges_types_across_iterations() {
+        let program = compile(r#"x = 0; loop max: 3 { x = "x" }"#, &stdlib::all())
+            .unwrap()
+            .program;
+        let x = program
+            .final_type_info()
+            .state
+            .local
+            .variable(&Ident::new("x"))
+            .unwrap()
+            .type_def
+            .kind()
+            .clone();
+
+        assert_eq!(x, Kind::integer().or_bytes());
+
+        // `x` is only an integer on the first iteration.
+        assert_eq!(
+            error_codes(r#"x = 0; loop max: 3 { y = x + 1; x = "x"; .y = y }"#),
+            vec![103]
+        );
+    }
+
+    #[test]
+    fn merges_types_across_iterations_of_nested_loops() {
+        // `x` is only an integer on the first iteration of the outer loop.
+        assert_eq!(
+            error_codes(r#"x = 0; loop max: 3 { loop max: 3 { y = x + 1; .y = y }; x = "x" }"#),
+            vec![103]
+        );
+
+        let source = format!(
+            "i = 0; {}i = i + 1{}",
+            "loop max: 2 { ".repeat(32),
+            " }".repeat(32)
+        );
+        assert!(compile(&source, &stdlib::all()).is_ok());
+    }
+
+    #[test]
+    fn widens_types_changing_on_every_iteration() {
+        let program = compile("x = 0; loop max: 1000 { x = [x] }", &stdlib::all())
+            .unwrap()
+            .program;
+        let x = program
+            .final_type_info()
+            .state
+            .local
+            .variable(&Ident::new("x"))
+            .unwrap()
+            .type_def
+            .kind()
+            .clone();
+
+        assert!(x.is_any());
+
+        let program = compile("x = []; loop max: 1000 { x = push(x, 1) }", &stdlib::all())
+            .unwrap()
+            .program;
+        let x = program
+            .final_type_info()
+            .state
+            .local
+            .variable(&Ident::new("x"))
+            .unwrap()
+            .type_def
+            .kind()
+            .clone();
+
+        assert_eq!(x, Kind::array(Collection::from_unknown(Kind::integer())));
+    }
+
+    #[test]
+    fn rejects_invalid_loops() {
+        assert_eq!(error_codes("loop count: 3 { null }"), vec![633]);
+        assert_eq!(error_codes("loop max: .count { null }"), vec![634]);
+        assert_eq!(error_codes("loop max: 1001 { null }"), vec![634]);
+        assert_eq!(error_codes("loop max: -1 { null }"), vec![634]);
+
+        assert_eq!(error_codes("break"), vec![635]);
+        assert_eq!(error_codes("loop max: 3 { if true { break } }"), vec![635]);
+        assert_eq!(
+            error_codes("loop max: 3 { for_each([1]) -> |_index, _value| { break } }"),
+            vec![635]
+        );
+    }
+}
@@ -24,7 +24,7 @@ impl Op {
         rhs: Node<Expr>,
         state: &TypeState,
     ) -> Result<Self, Error> {
-        use ast::Opcode::{Eq, Ge, Gt, Le, Lt, Ne};
+        use ast::Opcode::{BitAnd, BitXor, Eq, Ge, Gt, Le, Lt, Merge, Ne, Shl, Shr};
 
         let mut state = state.clone();
 
@@ -54,7 +54,35 @@ impl Op {
             }
         }
 
-        if let ast::Opcode::Merge = opcode {
+        // `|` is a bitwise OR when the lhs is an integer, and a merge otherwise.
+        // Unless either operand is an object, an operand that may be an integer
+        // hints at a bitwise OR, which is reported as such.
+        let bitwise = match opcode {
+            BitAnd | BitXor | Shl | Shr => true,
+            Merge => {
+                lhs_type_def.is_integer()
+                    || !(lhs_type_def.is_object() || rhs_type_def.is_object())
+                        && (lhs_type_def.contains_integer() || rhs_type_def.contains_integer())
+            }
+            _ => false,
+        };
+
+        if bitwise {
+            if !(lhs_type_def.is_integer() && rhs_type_def.is_integer()) {
+                return Err(Error::BitwiseNonIntegers {
+                    lhs_span: if lhs_type_def.is_integer() {
+                        None
+                    } else {
+                        Some(lhs_span)
+                    },
+                    rhs_span: if rhs_type_def.is_integer() {
+                        None
+                    } else {
+                        Some(rhs_span)
+                    },
+                });
+            }
+        } else if let Merge = opcode {
             if !(lhs_type_def.is_object() && rhs_type_def.is_object()) {
                 return Err(Error::MergeNonObjects {
                     lhs_span: if lhs_type_def.is_object() {
@@ -82,7 +110,10 @@ impl Op {
 impl Expression for Op {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         use crate::value::Value::{Boolean, Null};
-        use ast::Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Sub};
+        use ast::Opcode::{
+            Add, And, BitAnd, BitXor, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Shl, Shr,
+            Sub,
+        };
 
         match self.opcode {
             Err => return self.lhs.resolve(ctx).or_else(|_| self.rhs.resolve(ctx)),
@@ -117,7 +148,12 @@ impl Expression for Op {
             Ge => lhs.try_ge(rhs),
             Lt => lhs.try_lt(rhs),
             Le => lhs.try_le(rhs),
+            Merge if lhs.is_integer() => lhs.try_bit_or(rhs),
             Merge => lhs.try_merge(rhs),
+            BitAnd => lhs.try_bit_and(rhs),
+            BitXor => lhs.try_bit_xor(rhs),
+            Shl => lhs.try_shl(rhs),
+            Shr => lhs.try_shr(rhs),
             And | Or | Err => unreachable!(),
         }
         .map_err(Into::into)
@@ -125,7 +161,10 @@ impl Expression for Op {
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         use crate::value::Kind as K;
-        use ast::Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Sub};
+        use ast::Opcode::{
+            Add, And, BitAnd, BitXor, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Shl, Shr,
+            Sub,
+        };
         let original_state = state.clone();
 
         let mut state = state.clone();
@@ -176,7 +215,35 @@ impl Expression for Op {
             }
 
             // ... | ...
-            Merge => lhs_def.merge_overwrite(self.rhs.apply_type_info(&mut state)),
+            Merge if !lhs_def.is_integer() => {
+                lhs_def.merge_overwrite(self.rhs.apply_type_info(&mut state))
+            }
+
+            // 1 | ...
+            // ... & ...
+            // ... ^ ...
+            // ... << ...
+            // ... >> ...
+            Merge | BitAnd | BitXor | Shl | Shr => {
+                let rhs_def = self.rhs.apply_type_info(&mut state);
+                let integers = lhs_def.is_integer() && rhs_def.is_integer();
+
+                // Shifting is only infallible if the rhs is a literal integer in range.
+                let in_range = match self.opcode {
+                    Shl | Shr => matches!(
+                        self.rhs.resolve_constant(&state),
+                        Some(Value::Integer(v)) if (0..i64::from(i64::BITS)).contains(&v)
+                    ),
+                    _ => true,
+                };
+
+                let type_def = lhs_def.union(rhs_def).with_kind(K::integer());
+                if integers && in_range {
+                    type_def
+                } else {
+                    type_def.fallible()
+                }
+            }
 
             And => {
                 if lhs_def.is_null() || lhs_value == Some(Value::Boolean(false)) {
@@ -339,18 +406,27 @@ pub enum Error {
         rhs_span: Option<Span>,
     },
 
+    #[error("bitwise operations only work on integers")]
+    BitwiseNonIntegers {
+        lhs_span: Option<Span>,
+        rhs_span: Option<Span>,
+    },
+
     #[error("fallible operation")]
     Expr(#[from] expression::ExpressionError),
 }
 
 impl DiagnosticMessage for Error {
     fn code(&self) -> usize {
-        use Error::{ChainedComparison, Expr, MergeNonObjects, UnnecessaryCoalesce};
+        use Error::{
+            BitwiseNonIntegers, ChainedComparison, Expr, MergeNonObjects, UnnecessaryCoalesce,
+        };
 
         match self {
             ChainedComparison { .. } => 650,
             UnnecessaryCoalesce { .. } => 651,
             MergeNonObjects { .. } => 652,
+            BitwiseNonIntegers { .. } => 653,
             Expr(err) => err.code(),
         }
     }
@@ -365,7 +441,9 @@ impl DiagnosticMessage for Error {
     }
 
     fn labels(&self) -> Vec<Label> {
-        use Error::{ChainedComparison, Expr, MergeNonObjects, UnnecessaryCoalesce};
+        use Error::{
+            BitwiseNonIntegers, ChainedComparison, Expr, MergeNonObjects, UnnecessaryCoalesce,
+        };
 
         match self {
             ChainedComparison { span } => vec![Label::primary("", span)],
@@ -395,6 +473,11 @@ impl DiagnosticMessage for Error {
 
                 labels
             }
+            BitwiseNonIntegers { lhs_span, rhs_span } => [lhs_span, rhs_span]
+                .into_iter()
+                .flatten()
+                .map(|span| Label::primary("this expression must resolve to an integer", span))
+                .collect(),
             Expr(err) => err.labels(),
         }
     }
@@ -424,7 +507,10 @@ mod tests {
 
     use ast::{
         Ident,
-        Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Mul, Ne, Or, Sub},
+        Opcode::{
+            Add, And, BitAnd, BitXor, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Shl, Shr,
+            Sub,
+        },
     };
 
     use crate::compiler::expression::{Block, IfStatement, Literal, Predicate, Variable};
@@ -622,6 +708,46 @@ mod tests {
             want: TypeDef::float().fallible(),
         }
 
+        bit_or_integer {
+            expr: |_| op(Merge, 6, 3),
+            want: TypeDef::integer().infallible(),
+        }
+
+        bit_and_integer {
+            expr: |_| op(BitAnd, 6, 3),
+            want: TypeDef::integer().infallible(),
+        }
+
+        bit_xor_integer {
+            expr: |_| op(BitXor, 6, 3),
+            want: TypeDef::integer().infallible(),
+        }
+
+        bit_and_other {
+            expr: |_| op(BitAnd, 6, "foo"),
+            want: TypeDef::integer().fallible(),
+        }
+
+        shift_left_literal {
+            expr: |_| op(Shl, 1, 63),
+            want: TypeDef::integer().infallible(),
+        }
+
+        shift_right_literal {
+            expr: |_| op(Shr, -8, 2),
+            want: TypeDef::integer().infallible(),
+        }
+
+        shift_out_of_range_literal {
+            expr: |_| op(Shl, 1, 64),
+            want: TypeDef::integer().fallible(),
+        }
+
+        shift_negative_literal {
+            expr: |_| op(Shr, 1, -1),
+            want: TypeDef::integer().fallible(),
+        }
+
         and_null {
             expr: |_| op(And, (), ()),
             want: TypeDef::boolean().infallible(),
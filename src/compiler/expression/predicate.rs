@@ -1,15 +1,24 @@
 use std::fmt;
 
-use crate::diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use crate::diagnostic::{DiagnosticMessage, Label, Note, Severity, Urls};
 
-use crate::compiler::expression::Block;
+use crate::compiler::expression::{
+    assignment::Target, container, query, unary, Block, Container, FunctionCall, Literal, Unary,
+};
 use crate::compiler::{
+    codes::WARNING_STATIC_PREDICATE_CODE,
     expression::{Expr, Resolved},
-    parser::Node,
+    parser::{
+        ast::{Ident, Opcode},
+        Node,
+    },
     state::{TypeInfo, TypeState},
+    type_def::Details,
     value::Kind,
-    Context, Expression, Span,
+    Context, Expression, Span, TypeDef,
 };
+use crate::path::{OwnedTargetPath, OwnedValuePath};
+use crate::value::Value;
 
 pub(crate) type Result = std::result::Result<Predicate, Error>;
 
@@ -52,6 +61,232 @@ impl Predicate {
             inner: Block::new_inline(inner),
         }
     }
+
+    /// Narrows the type of the values checked by the predicate, for the branch
+    /// taken when the predicate resolves to `outcome`.
+    ///
+    /// Given `if is_string(.message) { ... } else { ... }`, `.message` is known
+    /// to be a string in the "if" block, and known not to be one in the "else"
+    /// block.
+    ///
+    /// Returns the targets whose type was narrowed.
+    pub(crate) fn narrow_type_state(&self, state: &mut TypeState, outcome: bool) -> Vec<Target> {
+        let mut narrowed = vec![];
+        if let Some(expr) = self.inner.exprs().last() {
+            narrow(expr, state, outcome, &mut narrowed);
+        }
+        narrowed
+    }
+
+    /// Returns a warning if the types checked by the predicate prove it always
+    /// resolves to the same value, meaning one of the branches is never taken.
+    ///
+    /// Given `if is_string("foo") { ... }`, the predicate is always `true`.
+    pub(crate) fn static_outcome(&self, span: Span, state: &TypeState) -> Option<Warning> {
+        let (outcome, reason) = static_outcome(self.inner.exprs().last()?, state)?;
+
+        Some(Warning::StaticPredicate {
+            outcome,
+            reason,
+            span,
+        })
+    }
+}
+
+fn static_outcome(expr: &Expr, state: &TypeState) -> Option<(bool, String)> {
+    match expr {
+        Expr::Unary(Unary {
+            variant: unary::Variant::Not(not),
+        }) => static_outcome(&not.inner, state).map(|(outcome, reason)| (!outcome, reason)),
+        Expr::Container(Container {
+            variant: container::Variant::Group(group),
+        }) => static_outcome(&group.inner, state),
+        Expr::FunctionCall(call) => {
+            let guard = &call.type_guard.as_ref()?.kind;
+            let expr = call.arguments.first()?.inner().expr();
+            let kind = expr.type_info(state).result.kind().clone();
+
+            // A "never" kind means the predicate is never evaluated at all.
+            if kind.is_never() {
+                return None;
+            }
+
+            let outcome = if filter_kind(&kind, guard, false).is_never() {
+                true
+            } else if filter_kind(&kind, guard, true).is_never() {
+                false
+            } else {
+                return None;
+            };
+
+            Some((outcome, format!("`{expr}` always resolves to {kind}")))
+        }
+        _ => None,
+    }
+}
+
+fn narrow(expr: &Expr, state: &mut TypeState, outcome: bool, narrowed: &mut Vec<Target>) {
+    match expr {
+        Expr::Unary(Unary {
+            variant: unary::Variant::Not(not),
+        }) => narrow(&not.inner, state, !outcome, narrowed),
+        Expr::Container(Container {
+            variant: container::Variant::Group(group),
+        }) => narrow(&group.inner, state, outcome, narrowed),
+        // Both sides of `a && b` hold if it is true, and neither side of
+        // `a || b` holds if it is false.
+        Expr::Op(op)
+            if matches!(
+                (op.opcode, outcome),
+                (Opcode::And, true) | (Opcode::Or, false)
+            ) =>
+        {
+            narrow(&op.lhs, state, outcome, narrowed);
+            narrow(&op.rhs, state, outcome, narrowed);
+        }
+        // `err == null` and `err != null`
+        Expr::Op(op) if matches!(op.opcode, Opcode::Eq | Opcode::Ne) => {
+            let variable = match (&*op.lhs, &*op.rhs) {
+                (Expr::Variable(variable), Expr::Literal(Literal::Null))
+                | (Expr::Literal(Literal::Null), Expr::Variable(variable)) => variable,
+                _ => return,
+            };
+
+            let is_null = outcome == (op.opcode == Opcode::Eq);
+            narrow_error(variable.ident(), state, is_null, narrowed);
+        }
+        Expr::FunctionCall(call) => narrow_function_call(call, state, outcome, narrowed),
+        _ => {}
+    }
+}
+
+/// Narrows both variables of an infallible assignment (`ok, err = ...`),
+/// once `err` is known to be null or not.
+fn narrow_error(err: &Ident, state: &mut TypeState, is_null: bool, narrowed: &mut Vec<Target>) {
+    let Some(binding) = state.local.error_binding(err).cloned() else {
+        return;
+    };
+
+    let err_type_def = if is_null {
+        TypeDef::null()
+    } else {
+        TypeDef::bytes()
+    };
+
+    state.local.insert_variable(
+        err.clone(),
+        Details {
+            type_def: err_type_def,
+            value: None,
+        },
+    );
+    narrowed.push(Target::Internal(err.clone(), OwnedValuePath::root()));
+
+    if let Some(ok) = &binding.ok {
+        let details = if is_null {
+            binding.success.clone()
+        } else {
+            binding.failure.clone()
+        };
+
+        state.local.insert_variable(ok.clone(), details);
+        narrowed.push(Target::Internal(ok.clone(), OwnedValuePath::root()));
+    }
+
+    // Narrowing doesn't change either variable, so a later check of `err`
+    // still narrows the "ok" variable once the branches are merged.
+    state.local.insert_error_binding(err.clone(), binding);
+}
+
+fn narrow_function_call(
+    call: &FunctionCall,
+    state: &mut TypeState,
+    outcome: bool,
+    narrowed: &mut Vec<Target>,
+) {
+    let Some(guard) = &call.type_guard else {
+        return;
+    };
+
+    if !outcome && !guard.negatable {
+        return;
+    }
+
+    let Some(argument) = call.arguments.first() else {
+        return;
+    };
+
+    let expr = argument.inner().expr();
+    if let Expr::Variable(variable) = expr {
+        // The error variable of an infallible assignment is either `null` or
+        // a string.
+        let err_kind = filter_kind(&Kind::null().or_bytes(), &guard.kind, outcome);
+        if err_kind.is_null() || err_kind.is_bytes() {
+            narrow_error(variable.ident(), state, err_kind.is_null(), narrowed);
+        }
+    }
+
+    let Some((target, value)) = narrowing_target(expr, state) else {
+        return;
+    };
+
+    let kind = expr.type_info(state).result.kind().clone();
+    let narrowed_kind = filter_kind(&kind, &guard.kind, outcome);
+
+    // A "never" kind means the branch can't be taken, there's nothing to gain
+    // from narrowing it.
+    if narrowed_kind != kind && !narrowed_kind.is_never() {
+        target.insert_type_def(state, narrowed_kind.into(), value);
+        narrowed.push(target);
+    }
+}
+
+/// Whether the expression queries one of the given narrowed targets.
+pub(crate) fn queries_narrowed_target(expr: &Expr, narrowed: &[Target], state: &TypeState) -> bool {
+    narrowing_target(expr, state).is_some_and(|(target, _)| narrowed.contains(&target))
+}
+
+/// Returns the assignment target matching a queried path, along with its
+/// current constant value, if any.
+fn narrowing_target(expr: &Expr, state: &TypeState) -> Option<(Target, Option<Value>)> {
+    let variable_value = |ident| {
+        state
+            .local
+            .variable(ident)
+            .and_then(|details| details.value.clone())
+    };
+
+    match expr {
+        Expr::Variable(variable) => Some((
+            Target::Internal(variable.ident().clone(), OwnedValuePath::root()),
+            variable_value(variable.ident()),
+        )),
+        Expr::Query(query) => match query.target() {
+            query::Target::Internal(variable) => Some((
+                Target::Internal(variable.ident().clone(), query.path().clone()),
+                variable_value(variable.ident()),
+            )),
+            query::Target::External(prefix) => Some((
+                Target::External(OwnedTargetPath {
+                    prefix: *prefix,
+                    path: query.path().clone(),
+                }),
+                state.external.target().value.clone(),
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Keeps the states of `kind` that are part of `guard` if `keep` is true, or
+/// removes them otherwise.
+fn filter_kind(kind: &Kind, guard: &Kind, keep: bool) -> Kind {
+    if keep {
+        kind.without(&Kind::any().without(guard))
+    } else {
+        kind.without(guard)
+    }
 }
 
 impl Expression for Predicate {
@@ -156,3 +391,199 @@ impl DiagnosticMessage for Error {
         }
     }
 }
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Warning {
+    #[error("predicate always resolves to `{outcome}`")]
+    StaticPredicate {
+        outcome: bool,
+        reason: String,
+        span: Span,
+    },
+}
+
+impl DiagnosticMessage for Warning {
+    fn code(&self) -> usize {
+        match self {
+            Warning::StaticPredicate { .. } => WARNING_STATIC_PREDICATE_CODE,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Warning::StaticPredicate {
+                outcome,
+                reason,
+                span,
+            } => vec![
+                Label::primary(format!("this predicate is always `{outcome}`"), span),
+                Label::context(reason, span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        match self {
+            Warning::StaticPredicate { outcome, .. } => {
+                let hint = if *outcome {
+                    "the \"if\" block always runs, consider removing the condition"
+                } else {
+                    "the \"if\" block never runs, consider removing it"
+                };
+
+                vec![Note::Hint(hint.to_owned())]
+            }
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::codes::{WARNING_NARROWED_ABORT_CODE, WARNING_STATIC_PREDICATE_CODE};
+    use crate::compiler::compile;
+    use crate::compiler::prelude::*;
+    use crate::stdlib;
+
+    fn compiles(source: &str) -> bool {
+        compile(source, &stdlib::all()).is_ok()
+    }
+
+    fn static_predicate_warnings(source: &str) -> Vec<String> {
+        compile(source, &stdlib::all())
+            .unwrap()
+            .warnings
+            .into_iter()
+            .filter(|warning| warning.code == WARNING_STATIC_PREDICATE_CODE)
+            .map(|warning| warning.message)
+            .collect()
+    }
+
+    #[test]
+    fn error_check_narrows_ok_variable() {
+        assert!(compiles(
+            "parsed, err = slice(.items, 1); if err == null { length(parsed) }"
+        ));
+        assert!(compiles(
+            "parsed, err = slice(.items, 1); if null != err { err + \"!\" } else { length(parsed) }"
+        ));
+        assert!(compiles(
+            "parsed, err = slice(.items, 1); if !is_null(err) { upcase(err) }"
+        ));
+        assert!(compiles(
+            "parsed, err = slice(.items, 1); if is_string(err) { upcase(err) } else { length(parsed) }"
+        ));
+        assert!(!compiles(
+            "parsed, err = slice(.items, 1); if err != null { length(parsed) }"
+        ));
+        assert!(compiles(
+            "parsed, err = slice(.items, 1); if err != null { log(err) }; if err == null { length(parsed) }"
+        ));
+    }
+
+    /// A host function returning whether the value is a valid port number.
+    #[derive(Debug)]
+    struct IsPort;
+
+    impl Function for IsPort {
+        fn identifier(&self) -> &'static str {
+            "is_port"
+        }
+
+        fn parameters(&self) -> &'static [Parameter] {
+            stdlib::IsInteger.parameters()
+        }
+
+        fn examples(&self) -> &'static [Example] {
+            &[]
+        }
+
+        fn compile(
+            &self,
+            state: &TypeState,
+            ctx: &mut FunctionCompileContext,
+            arguments: ArgumentList,
+        ) -> Compiled {
+            stdlib::IsInteger.compile(state, ctx, arguments)
+        }
+
+        fn type_guard(&self) -> Option<TypeGuard> {
+            Some(TypeGuard {
+                kind: Kind::integer(),
+                negatable: false,
+            })
+        }
+    }
+
+    #[test]
+    fn functions_narrow_with_their_type_guard() {
+        let compiles = |source| {
+            let mut functions = stdlib::all();
+            functions.push(Box::new(IsPort));
+            compile(source, &functions).is_ok()
+        };
+
+        assert!(compiles("if is_port(.port) { .port = .port + 1 }"));
+        assert!(!compiles(
+            "if is_port(.port) { .port = 0 } else { .port = .port + 1 }"
+        ));
+    }
+
+    #[test]
+    fn abort_of_narrowed_call_is_a_warning() {
+        let warnings = compile(
+            "if is_integer(.code) { .code = int!(.code) }",
+            &stdlib::all(),
+        )
+        .unwrap()
+        .warnings;
+        assert_eq!(warnings[0].code, WARNING_NARROWED_ABORT_CODE);
+
+        assert!(compiles(
+            "parsed, err = parse_int(.code); if err == null { .code = int!(parsed) }"
+        ));
+        assert!(!compiles("if is_integer(.code) { .code = int!(1) }"));
+    }
+
+    #[test]
+    fn reassignment_discards_error_check() {
+        assert!(!compiles(
+            "parsed, err = slice(.items, 1); parsed = .items; if err == null { length(parsed) }"
+        ));
+        assert!(!compiles(
+            "parsed, err = slice(.items, 1); err = .error; if err == null { length(parsed) }"
+        ));
+        assert!(!compiles(
+            "parsed, err = slice(.items, 1); if true { parsed = null }; if err == null { length(parsed) }"
+        ));
+    }
+
+    #[test]
+    fn warns_on_static_predicate() {
+        assert_eq!(
+            static_predicate_warnings(r#"if is_string("foo") { .a = 1 }"#),
+            vec!["predicate always resolves to `true`"]
+        );
+        assert_eq!(
+            static_predicate_warnings(r#"if !is_integer("foo") { .a = 1 }"#),
+            vec!["predicate always resolves to `true`"]
+        );
+        assert_eq!(
+            static_predicate_warnings(r#"x = 1; if (is_null(x)) { .a = 1 }"#),
+            vec!["predicate always resolves to `false`"]
+        );
+    }
+
+    #[test]
+    fn no_warning_on_dynamic_predicate() {
+        assert!(static_predicate_warnings("if is_string(.foo) { .a = 1 }").is_empty());
+        assert!(static_predicate_warnings("if exists(.foo) { .a = 1 }").is_empty());
+        assert!(static_predicate_warnings("if !exists(.foo) { .a = 1 }").is_empty());
+        assert!(static_predicate_warnings("if true { .a = 1 }").is_empty());
+    }
+}
@@ -1,14 +1,14 @@
 use std::fmt;
 
 use crate::compiler::{
-    expression::{Not, Resolved},
+    expression::{BitNot, Not, Resolved},
     state::{TypeInfo, TypeState},
     Context, Expression,
 };
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Unary {
-    variant: Variant,
+    pub(crate) variant: Variant,
 }
 
 impl Unary {
@@ -21,24 +21,27 @@ impl Unary {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Variant {
     Not(Not),
+    BitNot(BitNot),
 }
 
 impl Expression for Unary {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        use Variant::Not;
+        use Variant::{BitNot, Not};
 
         match &self.variant {
             Not(v) => v.resolve(ctx),
+            BitNot(v) => v.resolve(ctx),
         }
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
-        use Variant::Not;
+        use Variant::{BitNot, Not};
 
         let mut state = state.clone();
 
         let result = match &self.variant {
             Not(v) => v.apply_type_info(&mut state),
+            BitNot(v) => v.apply_type_info(&mut state),
         };
         TypeInfo::new(state, result)
     }
@@ -46,10 +49,11 @@ impl Expression for Unary {
 
 impl fmt::Display for Unary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Variant::Not;
+        use Variant::{BitNot, Not};
 
         match &self.variant {
             Not(v) => v.fmt(f),
+            BitNot(v) => v.fmt(f),
         }
     }
 }
@@ -59,3 +63,9 @@ impl From<Not> for Variant {
         Variant::Not(not)
     }
 }
+
+impl From<BitNot> for Variant {
+    fn from(bit_not: BitNot) -> Self {
+        Variant::BitNot(bit_not)
+    }
+}
@@ -1,7 +1,7 @@
-use ExpressionError::{Abort, Error, Fallible, Missing, Return};
+use ExpressionError::{Abort, Break, Error, Fallible, Missing, Return};
 
 use crate::diagnostic::{Diagnostic, DiagnosticMessage, Label, Note, Severity, Span};
-use crate::value::Value;
+use crate::value::{SecretError, Value};
 
 pub type Resolved = Result<Value, ExpressionError>;
 
@@ -15,6 +15,9 @@ pub enum ExpressionError {
         span: Span,
         value: Value,
     },
+    Break {
+        span: Span,
+    },
     Error {
         message: String,
         labels: Vec<Label>,
@@ -60,6 +63,7 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             Abort { .. } => 0,
             Return { .. } => 0,
+            Break { .. } => 0,
             Error { .. } => 0,
             Fallible { .. } => 100,
             Missing { .. } => 900,
@@ -70,6 +74,7 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             Abort { message, .. } => message.clone().unwrap_or_else(|| "aborted".to_owned()),
             Return { .. } => "return".to_string(),
+            Break { .. } => "break".to_string(),
             Error { message, .. } => message.clone(),
             Fallible { .. } => "unhandled error".to_string(),
             Missing { .. } => "expression type unavailable".to_string(),
@@ -82,6 +87,7 @@ impl DiagnosticMessage for ExpressionError {
                 vec![Label::primary("aborted", span)]
             }
             Return { .. } => Vec::new(),
+            Break { .. } => Vec::new(),
             Error { labels, .. } => labels.clone(),
             Fallible { span } => vec![
                 Label::primary("expression can result in runtime error", span),
@@ -101,6 +107,7 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             Abort { .. } => vec![],
             Return { .. } => vec![],
+            Break { .. } => vec![],
             Error { notes, .. } => notes.clone(),
             Fallible { .. } => vec![Note::SeeErrorDocs],
             Missing { .. } => vec![],
@@ -123,3 +130,9 @@ impl From<&str> for ExpressionError {
         message.to_owned().into()
     }
 }
+
+impl From<SecretError> for ExpressionError {
+    fn from(err: SecretError) -> Self {
+        err.to_string().into()
+    }
+}
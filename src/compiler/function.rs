@@ -1,4 +1,5 @@
 pub mod closure;
+pub mod signature;
 
 use crate::diagnostic::{DiagnosticMessage, Label, Note};
 use crate::parser::ast::Ident;
@@ -7,13 +8,15 @@ use crate::value::{kind::Collection, KeyString, Value};
 use std::{
     collections::{BTreeMap, HashMap},
     fmt,
+    sync::Arc,
 };
 
+use self::signature::Signature;
 use super::{
     expression::{container::Variant, Block, Container, Expr, Expression},
     state::TypeState,
     value::{kind, Kind},
-    CompileConfig, Span, TypeDef,
+    CompileConfig, EnvVarPolicy, Span, StateStore, TypeDef,
 };
 
 pub type Compiled = Result<Box<dyn Expression>, Box<dyn DiagnosticMessage>>;
@@ -24,6 +27,15 @@ pub trait Function: Send + Sync + fmt::Debug {
     /// The identifier by which the function can be called.
     fn identifier(&self) -> &'static str;
 
+    /// The namespace the function is registered under, if any.
+    ///
+    /// Namespaced functions can only be called using their qualified name
+    /// (e.g. `myco::lookup(...)`), which keeps host-provided functions from
+    /// colliding with current or future stdlib functions. See [`namespaced`].
+    fn namespace(&self) -> Option<&'static str> {
+        None
+    }
+
     /// A brief single-line description explaining what this function does.
     fn summary(&self) -> &'static str {
         "TODO"
@@ -68,6 +80,162 @@ pub trait Function: Send + Sync + fmt::Debug {
     fn closure(&self) -> Option<closure::Definition> {
         None
     }
+
+    /// Whether the result of the function only depends on its arguments.
+    ///
+    /// A pure function returns the same value for the same arguments, doesn't
+    /// read the event, the runtime state, the timezone or the environment, and
+    /// has no side effects. Calls of pure functions whose arguments are all
+    /// constant are evaluated when the program is compiled, against an empty
+    /// target and the default timezone, so a function depending on either
+    /// must not be pure.
+    ///
+    /// This returns `false` by default.
+    fn pure(&self) -> bool {
+        false
+    }
+
+    /// What the function tells about the type of its first argument when it
+    /// returns a boolean, such as `is_string`. Inside `if` branches guarded by
+    /// a call of the function, the type of the argument is narrowed
+    /// accordingly.
+    ///
+    /// This returns `None` by default, meaning the call doesn't narrow any
+    /// type.
+    fn type_guard(&self) -> Option<TypeGuard> {
+        None
+    }
+
+    /// A machine-readable description of the function, for documentation
+    /// and editor tooling.
+    ///
+    /// The default implementation is derived from the other trait methods.
+    /// Override it to add information that can't be derived, such as
+    /// parameter defaults or the return type.
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+    }
+}
+
+/// The type of the first argument of a predicate function, depending on
+/// whether it returns `true` or `false`. See [`Function::type_guard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeGuard {
+    /// The kind the argument is known to have when the function returns
+    /// `true`.
+    pub kind: Kind,
+
+    /// Whether the argument is known _not_ to be of `kind` when the function
+    /// returns `false`.
+    pub negatable: bool,
+}
+
+impl TypeGuard {
+    /// The argument is of the given kind if and only if the function returns
+    /// `true`.
+    #[must_use]
+    pub fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            negatable: true,
+        }
+    }
+}
+
+/// Returns the name by which the function is called in VRL source, including
+/// its namespace, if any.
+#[must_use]
+pub fn qualified_identifier(function: &dyn Function) -> String {
+    match function.namespace() {
+        Some(namespace) => format!("{namespace}::{}", function.identifier()),
+        None => function.identifier().to_owned(),
+    }
+}
+
+/// Registers the given functions under `namespace`.
+///
+/// The returned functions can be appended to the stdlib function list; they
+/// are only callable using their qualified name (`namespace::identifier`).
+#[must_use]
+pub fn namespaced(
+    namespace: &'static str,
+    functions: Vec<Box<dyn Function>>,
+) -> Vec<Box<dyn Function>> {
+    functions
+        .into_iter()
+        .map(|function| Box::new(Namespaced::new(namespace, function)) as _)
+        .collect()
+}
+
+/// A [`Function`] registered under a custom namespace.
+#[derive(Debug)]
+pub struct Namespaced {
+    namespace: &'static str,
+    function: Box<dyn Function>,
+}
+
+impl Namespaced {
+    #[must_use]
+    pub fn new(namespace: &'static str, function: Box<dyn Function>) -> Self {
+        Self {
+            namespace,
+            function,
+        }
+    }
+}
+
+impl Function for Namespaced {
+    fn identifier(&self) -> &'static str {
+        self.function.identifier()
+    }
+
+    fn namespace(&self) -> Option<&'static str> {
+        Some(self.namespace)
+    }
+
+    fn summary(&self) -> &'static str {
+        self.function.summary()
+    }
+
+    fn usage(&self) -> &'static str {
+        self.function.usage()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        self.function.examples()
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        self.function.compile(state, ctx, arguments)
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.function.parameters()
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        self.function.closure()
+    }
+
+    fn pure(&self) -> bool {
+        self.function.pure()
+    }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        self.function.type_guard()
+    }
+
+    fn signature(&self) -> Signature {
+        let mut signature = self.function.signature();
+        signature.identifier = qualified_identifier(self);
+        signature.namespace = Some(self.namespace);
+        signature
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -112,6 +280,26 @@ impl FunctionCompileContext {
         self.config.is_read_only_path(path)
     }
 
+    /// Returns why the given path is read only, or `None` if it can be
+    /// modified.
+    #[must_use]
+    pub fn read_only_reason(&self, path: &OwnedTargetPath) -> Option<String> {
+        self.config.read_only_reason(path)
+    }
+
+    /// The environment variables that the program can read.
+    #[must_use]
+    pub fn env_var_policy(&self) -> &EnvVarPolicy {
+        self.config.env_var_policy()
+    }
+
+    /// The store of the functions keeping state across the runs of the
+    /// program, if the host provides one.
+    #[must_use]
+    pub fn state_store(&self) -> Option<&Arc<dyn StateStore>> {
+        self.config.state_store()
+    }
+
     /// Consume the `FunctionCompileContext`, returning the (potentially mutated) `AnyMap`.
     #[must_use]
     pub fn into_config(self) -> CompileConfig {
@@ -200,6 +388,9 @@ impl Parameter {
 pub struct ArgumentList {
     pub(crate) arguments: HashMap<&'static str, Expr>,
 
+    /// The source spans of the arguments, when compiled from source.
+    spans: HashMap<&'static str, Span>,
+
     /// A closure argument differs from regular arguments, in that it isn't an
     /// expression by itself, and it also isn't tied to a parameter string in
     /// the function call.
@@ -382,8 +573,19 @@ impl ArgumentList {
         self.arguments.keys().copied().collect::<Vec<_>>()
     }
 
-    pub(crate) fn insert(&mut self, k: &'static str, v: Expr) {
+    pub(crate) fn insert(&mut self, k: &'static str, v: Expr, span: Span) {
         self.arguments.insert(k, v);
+        self.spans.insert(k, span);
+    }
+
+    /// Returns the source span of an argument expression, if the argument was
+    /// given and the function call was compiled from source.
+    ///
+    /// Labels of a compilation error that use this span point at the argument,
+    /// instead of at the whole function call.
+    #[must_use]
+    pub fn span(&self, keyword: &'static str) -> Option<Span> {
+        self.spans.get(keyword).copied()
     }
 
     pub(crate) fn set_closure(&mut self, closure: FunctionClosure) {
@@ -417,6 +619,7 @@ mod test_impls {
                     .into_iter()
                     .map(|(k, v)| (k, v.into()))
                     .collect::<HashMap<_, _>>(),
+                spans: HashMap::new(),
                 closure: None,
             }
         }
@@ -493,13 +696,16 @@ pub enum Error {
 
     #[error("mutation of read-only value")]
     ReadOnlyMutation { context: String },
+
+    #[error("missing host context")]
+    MissingContext { context: &'static str },
 }
 
 impl crate::diagnostic::DiagnosticMessage for Error {
     fn code(&self) -> usize {
         use Error::{
             ExpectedFunctionClosure, ExpectedStaticExpression, InvalidArgument, InvalidEnumVariant,
-            ReadOnlyMutation, UnexpectedExpression,
+            MissingContext, ReadOnlyMutation, UnexpectedExpression,
         };
 
         match self {
@@ -509,13 +715,14 @@ impl crate::diagnostic::DiagnosticMessage for Error {
             InvalidArgument { .. } => 403,
             ExpectedFunctionClosure => 420,
             ReadOnlyMutation { .. } => 315,
+            MissingContext { .. } => 404,
         }
     }
 
     fn labels(&self) -> Vec<Label> {
         use Error::{
             ExpectedFunctionClosure, ExpectedStaticExpression, InvalidArgument, InvalidEnumVariant,
-            ReadOnlyMutation, UnexpectedExpression,
+            MissingContext, ReadOnlyMutation, UnexpectedExpression,
         };
 
         match self {
@@ -578,6 +785,16 @@ impl crate::diagnostic::DiagnosticMessage for Error {
                 Label::primary("mutation of read-only value", Span::default()),
                 Label::context(context, Span::default()),
             ],
+            MissingContext { context } => vec![
+                Label::primary(
+                    format!("this function requires a {context}"),
+                    Span::default(),
+                ),
+                Label::context(
+                    format!("the {context} is not provided by this environment"),
+                    Span::default(),
+                ),
+            ],
         }
     }
 
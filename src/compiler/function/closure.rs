@@ -28,6 +28,12 @@ pub struct Definition {
     /// collection elements to determine the eventual type definition of the
     /// closure variable(s) (see `Variable`).
     pub is_iterator: bool,
+
+    /// Defines whether the function can be called without a closure.
+    ///
+    /// If this is `false`, the compiler rejects calls to the function that
+    /// don't provide a closure.
+    pub is_optional: bool,
 }
 
 /// One input variant for a function-closure.
@@ -218,6 +224,30 @@ where
         Ok(value)
     }
 
+    /// Run the closure to completion, given the provided values for the
+    /// closure variables in order, and the runtime context.
+    ///
+    /// The provided values are *NOT* mutated during the run.
+    pub fn run_values(
+        &self,
+        ctx: &mut Context,
+        values: &[&Value],
+    ) -> Result<Value, ExpressionError> {
+        let old_values = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| insert(ctx.state_mut(), self.ident(index), (*value).clone()))
+            .collect::<Vec<_>>();
+
+        let value = (self.runner)(ctx)?;
+
+        for (index, old_value) in old_values.into_iter().enumerate() {
+            cleanup(ctx.state_mut(), self.ident(index), old_value);
+        }
+
+        Ok(value)
+    }
+
     /// Run the closure to completion, given the provided key, and the runtime
     /// context.
     ///
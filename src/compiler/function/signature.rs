@@ -0,0 +1,248 @@
+//! Machine-readable function signatures.
+//!
+//! A [`Signature`] describes everything a caller needs to know about
+//! a function — its parameters, return type, closure and examples — so that
+//! documentation sites and editor integrations don't have to parse the source
+//! of the function implementations.
+
+use serde::Serialize;
+
+use crate::compiler::value::kind;
+
+use super::{closure, Function};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Signature {
+    /// The name by which the function is called, including its namespace.
+    pub identifier: String,
+    pub namespace: Option<&'static str>,
+    pub summary: &'static str,
+    pub usage: &'static str,
+    pub category: Option<&'static str>,
+    pub parameters: Vec<ParameterSignature>,
+    pub returns: Option<ReturnSignature>,
+    pub closure: Option<ClosureSignature>,
+    pub examples: Vec<ExampleSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParameterSignature {
+    pub keyword: &'static str,
+    pub kinds: Vec<&'static str>,
+    pub required: bool,
+
+    /// The value used when the argument is omitted, as VRL source.
+    pub default: Option<&'static str>,
+
+    /// The only values the parameter accepts, if it is an enumeration.
+    pub enum_variants: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReturnSignature {
+    pub kinds: Vec<&'static str>,
+    pub fallible: bool,
+
+    /// Human-readable rules refining the return type, e.g. "returns `null`
+    /// if `value` is empty".
+    pub rules: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClosureSignature {
+    pub is_iterator: bool,
+    pub is_optional: bool,
+    pub inputs: Vec<ClosureInputSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClosureInputSignature {
+    pub parameter_keyword: &'static str,
+    pub kind: String,
+    pub variables: Vec<String>,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExampleSignature {
+    pub title: &'static str,
+    pub source: &'static str,
+    pub result: Option<&'static str>,
+    pub error: Option<&'static str>,
+}
+
+impl Signature {
+    /// Build the signature from the metadata exposed by the [`Function`]
+    /// trait.
+    ///
+    /// Functions can refine the returned signature using the builder methods
+    /// below when overriding [`Function::signature`].
+    #[must_use]
+    pub fn new<F: Function + ?Sized>(function: &F) -> Self {
+        Self {
+            identifier: match function.namespace() {
+                Some(namespace) => format!("{namespace}::{}", function.identifier()),
+                None => function.identifier().to_owned(),
+            },
+            namespace: function.namespace(),
+            summary: function.summary(),
+            usage: function.usage(),
+            category: None,
+            parameters: function
+                .parameters()
+                .iter()
+                .map(|parameter| ParameterSignature {
+                    keyword: parameter.keyword,
+                    kinds: kind_names(parameter.kind),
+                    required: parameter.required,
+                    default: None,
+                    enum_variants: vec![],
+                })
+                .collect(),
+            returns: None,
+            closure: function.closure().map(closure_signature),
+            examples: function
+                .examples()
+                .iter()
+                .map(|example| ExampleSignature {
+                    title: example.title,
+                    source: example.source,
+                    result: example.result.ok(),
+                    error: example.result.err(),
+                })
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn category(mut self, category: &'static str) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    #[must_use]
+    pub fn returns(mut self, kind: u16, fallible: bool) -> Self {
+        let rules = self.returns.take().map(|r| r.rules).unwrap_or_default();
+        self.returns = Some(ReturnSignature {
+            kinds: kind_names(kind),
+            fallible,
+            rules,
+        });
+        self
+    }
+
+    /// Add a rule refining the return type. Has no effect unless
+    /// [`Signature::returns`] was called first.
+    #[must_use]
+    pub fn return_rule(mut self, rule: &'static str) -> Self {
+        if let Some(returns) = &mut self.returns {
+            returns.rules.push(rule);
+        }
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the function has no parameter named `keyword`.
+    #[must_use]
+    pub fn parameter_default(mut self, keyword: &'static str, default: &'static str) -> Self {
+        self.parameter_mut(keyword).default = Some(default);
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the function has no parameter named `keyword`.
+    #[must_use]
+    pub fn parameter_variants(mut self, keyword: &'static str, variants: &[&'static str]) -> Self {
+        self.parameter_mut(keyword).enum_variants = variants.to_vec();
+        self
+    }
+
+    fn parameter_mut(&mut self, keyword: &'static str) -> &mut ParameterSignature {
+        self.parameters
+            .iter_mut()
+            .find(|parameter| parameter.keyword == keyword)
+            .unwrap_or_else(|| panic!("unknown parameter {keyword:?}"))
+    }
+}
+
+fn closure_signature(definition: closure::Definition) -> ClosureSignature {
+    ClosureSignature {
+        is_iterator: definition.is_iterator,
+        is_optional: definition.is_optional,
+        inputs: definition
+            .inputs
+            .into_iter()
+            .map(|input| ClosureInputSignature {
+                parameter_keyword: input.parameter_keyword,
+                kind: input.kind.to_string(),
+                variables: input
+                    .variables
+                    .into_iter()
+                    .map(|variable| match variable.kind {
+                        closure::VariableKind::Exact(kind) => kind.to_string(),
+                        closure::VariableKind::Target => "target".to_owned(),
+                        closure::VariableKind::TargetInnerValue => "target value".to_owned(),
+                        closure::VariableKind::TargetInnerKey => "target key".to_owned(),
+                    })
+                    .collect(),
+                output: input.output.into_kind().to_string(),
+            })
+            .collect(),
+    }
+}
+
+fn kind_names(n: u16) -> Vec<&'static str> {
+    if n == kind::ANY {
+        return vec!["any"];
+    }
+
+    [
+        (kind::BYTES, "string"),
+        (kind::INTEGER, "integer"),
+        (kind::FLOAT, "float"),
+        (kind::BOOLEAN, "boolean"),
+        (kind::OBJECT, "object"),
+        (kind::ARRAY, "array"),
+        (kind::TIMESTAMP, "timestamp"),
+        (kind::REGEX, "regex"),
+        (kind::NULL, "null"),
+        (kind::UNDEFINED, "undefined"),
+    ]
+    .into_iter()
+    .filter(|(bit, _)| n & bit == *bit)
+    .map(|(_, name)| name)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::function::Namespaced;
+    use crate::stdlib::{EncodeBase64, ParseJson};
+
+    #[test]
+    fn signature_from_parameters() {
+        let signature = EncodeBase64.signature();
+
+        assert_eq!(signature.identifier, "encode_base64");
+        assert_eq!(signature.parameters[0].kinds, vec!["string"]);
+        assert!(signature.parameters[0].required);
+        assert_eq!(signature.parameters[1].default, Some("true"));
+        assert_eq!(
+            signature.parameters[2].enum_variants,
+            vec!["standard", "url_safe"]
+        );
+        assert_eq!(signature.examples.len(), 1);
+    }
+
+    #[test]
+    fn signature_includes_namespace() {
+        let signature = Namespaced::new("myco", Box::new(ParseJson)).signature();
+
+        assert_eq!(signature.identifier, "myco::parse_json");
+        assert_eq!(signature.namespace, Some("myco"));
+        assert_eq!(signature.category, Some("Parse"));
+    }
+}
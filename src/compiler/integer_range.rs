@@ -0,0 +1,132 @@
+use crate::value::Value;
+
+/// A sequence of integers from `start` (inclusive) to `end` (exclusive),
+/// incremented by `step`, as returned by the `range` function.
+///
+/// The integers are only computed when iterated over, which allows iterating
+/// functions to consume a range without materializing it as an array. See
+/// [`Expression::resolve_range`](super::Expression::resolve_range).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegerRange {
+    next: i64,
+    end: i64,
+    step: i64,
+}
+
+impl IntegerRange {
+    /// The maximum number of integers in a range.
+    pub const MAX_LENGTH: usize = 10_000;
+
+    /// # Errors
+    ///
+    /// Returns an error if `step` is zero, or if the range has more than
+    /// [`IntegerRange::MAX_LENGTH`] integers.
+    pub fn new(start: i64, end: i64, step: i64) -> Result<Self, &'static str> {
+        if step == 0 {
+            return Err(r#""step" must not be zero"#);
+        }
+
+        let range = Self {
+            next: start,
+            end,
+            step,
+        };
+
+        if range.len() > Self::MAX_LENGTH {
+            return Err("range exceeds the maximum length of 10000 integers");
+        }
+
+        Ok(range)
+    }
+
+    /// The number of remaining integers in the range.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let (start, end, step) = (
+            i128::from(self.next),
+            i128::from(self.end),
+            i128::from(self.step),
+        );
+
+        let distance = if step > 0 { end - start } else { start - end };
+        if distance <= 0 {
+            return 0;
+        }
+
+        let len = (distance - 1) / step.abs() + 1;
+        usize::try_from(len).unwrap_or(usize::MAX)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collects the remaining integers into an array.
+    #[must_use]
+    pub fn into_value(self) -> Value {
+        Value::Array(self.map(Value::from).collect())
+    }
+}
+
+impl Iterator for IntegerRange {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let exhausted = if self.step > 0 {
+            self.next >= self.end
+        } else {
+            self.next <= self.end
+        };
+        if exhausted {
+            return None;
+        }
+
+        let next = self.next;
+        self.next = next.saturating_add(self.step);
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IntegerRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterates() {
+        let collect = |start, end, step| {
+            IntegerRange::new(start, end, step)
+                .unwrap()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(collect(0, 5, 1), vec![0, 1, 2, 3, 4]);
+        assert_eq!(collect(0, 5, 2), vec![0, 2, 4]);
+        assert_eq!(collect(5, 0, -2), vec![5, 3, 1]);
+        assert_eq!(collect(5, 0, 1), Vec::<i64>::new());
+        assert_eq!(collect(i64::MAX - 1, i64::MAX, 5), vec![i64::MAX - 1]);
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(IntegerRange::new(0, 10, 3).unwrap().len(), 4);
+        assert_eq!(IntegerRange::new(-3, 3, 1).unwrap().len(), 6);
+        assert_eq!(IntegerRange::new(3, -3, -4).unwrap().len(), 2);
+        assert!(IntegerRange::new(1, 1, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        assert!(IntegerRange::new(0, 10, 0).is_err());
+        assert!(IntegerRange::new(0, 10_001, 1).is_err());
+        assert!(IntegerRange::new(i64::MIN, i64::MAX, 1).is_err());
+        assert!(IntegerRange::new(0, 10_001, 2).is_ok());
+    }
+}
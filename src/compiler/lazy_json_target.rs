@@ -0,0 +1,386 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::value::RawValue;
+
+use crate::path::{OwnedSegment, OwnedTargetPath, OwnedValuePath, PathPrefix};
+use crate::value::{ObjectMap, SecretError, Secrets, Value};
+
+use super::{ProgramInfo, SecretTarget, Target};
+
+/// A [`Target`] over a JSON document, which only converts the parts of the
+/// document a program reads or writes into values.
+///
+/// The parts are the paths the program queries and assigns, as reported by
+/// [`ProgramInfo`], so the target must only run the program it was created
+/// for. This makes filtering large documents on a few fields cheaper than
+/// converting the whole document, which [`LazyJsonTarget::into_value`] only
+/// does when the modified event is needed.
+#[derive(Debug)]
+pub struct LazyJsonTarget<'a> {
+    json: &'a RawValue,
+    paths: Vec<OwnedValuePath>,
+    value: Value,
+    metadata: Value,
+    secrets: Secrets,
+}
+
+impl<'a> LazyJsonTarget<'a> {
+    /// Creates a target over the JSON document, for the program with the
+    /// given info, with empty metadata and secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document isn't valid JSON.
+    pub fn new(json: &'a str, info: &ProgramInfo) -> Result<Self, serde_json::Error> {
+        let json: &RawValue = serde_json::from_str(json)?;
+
+        // Paths indexing arrays from the end depend on the length of the
+        // array, so the whole array is converted instead.
+        let mut candidates = info
+            .target_queries
+            .iter()
+            .chain(&info.target_assignments)
+            .filter(|path| path.prefix == PathPrefix::Event)
+            .map(|path| {
+                let end = path
+                    .path
+                    .segments
+                    .iter()
+                    .position(|segment| matches!(segment, OwnedSegment::Index(index) if *index < 0))
+                    .unwrap_or(path.path.segments.len());
+                OwnedValuePath {
+                    segments: path.path.segments[..end].to_vec(),
+                }
+            })
+            .collect::<Vec<_>>();
+        candidates.sort();
+
+        // Converting a path converts everything below it.
+        let mut paths: Vec<OwnedValuePath> = Vec::new();
+        for path in candidates {
+            if !paths
+                .last()
+                .is_some_and(|parent| path.segments.starts_with(&parent.segments))
+            {
+                paths.push(path);
+            }
+        }
+
+        let mut value = Value::Object(ObjectMap::new());
+        for path in &paths {
+            if let Some(raw) = lookup(json, path)? {
+                value.insert(path, serde_json::from_str::<Value>(raw.get())?);
+            }
+        }
+
+        Ok(Self {
+            json,
+            paths,
+            value,
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::new(),
+        })
+    }
+
+    /// Sets the metadata of the event.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets the secrets of the event.
+    #[must_use]
+    pub fn with_secrets(mut self, secrets: Secrets) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Returns the parts of the event converted so far, as modified by the
+    /// program.
+    #[must_use]
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Converts the whole document into the event, as modified by the
+    /// program.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document holds a value that can't be
+    /// converted, such as a number out of range.
+    pub fn into_value(mut self) -> Result<Value, serde_json::Error> {
+        if self.paths.iter().any(OwnedValuePath::is_root) {
+            return Ok(self.value);
+        }
+
+        let mut event: Value = serde_json::from_str(self.json.get())?;
+        for path in &self.paths {
+            match self.value.get_mut(path) {
+                Some(value) => {
+                    event.insert(path, std::mem::replace(value, Value::Null));
+                }
+                None => {
+                    event.remove(path, false);
+                }
+            }
+        }
+
+        Ok(event)
+    }
+}
+
+/// Finds the raw value at the path, without converting the values around it.
+fn lookup<'a>(
+    json: &'a RawValue,
+    path: &OwnedValuePath,
+) -> Result<Option<&'a RawValue>, serde_json::Error> {
+    let mut current = json;
+    for segment in &path.segments {
+        let mut deserializer = serde_json::Deserializer::from_str(current.get());
+        match deserializer.deserialize_any(Child { segment })? {
+            Some(child) => current = child,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(current))
+}
+
+/// Finds the field or element of an object or array matching the segment.
+struct Child<'s> {
+    segment: &'s OwnedSegment,
+}
+
+impl<'de> Visitor<'de> for Child<'_> {
+    type Value = Option<&'de RawValue>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut child = None;
+        match self.segment {
+            OwnedSegment::Field(field) => {
+                // As when converting the whole object, the last of duplicate
+                // fields wins.
+                while let Some(matches) = map.next_key_seed(Key(field.as_str()))? {
+                    if matches {
+                        child = Some(map.next_value()?);
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            OwnedSegment::Index(_) => {
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            }
+        }
+
+        Ok(child)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut child = None;
+        let mut index = 0;
+        match self.segment {
+            OwnedSegment::Index(wanted) => {
+                while let Some(element) = seq.next_element::<&RawValue>()? {
+                    if usize::try_from(*wanted) == Ok(index) {
+                        child = Some(element);
+                    }
+                    index += 1;
+                }
+            }
+            OwnedSegment::Field(_) => while seq.next_element::<IgnoredAny>()?.is_some() {},
+        }
+
+        Ok(child)
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+}
+
+/// Compares an object key with a field, without allocating unescaped keys.
+struct Key<'s>(&'s str);
+
+impl<'de> DeserializeSeed<'de> for Key<'_> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for Key<'_> {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an object key")
+    }
+
+    fn visit_str<E: de::Error>(self, key: &str) -> Result<bool, E> {
+        Ok(key == self.0)
+    }
+}
+
+impl Target for LazyJsonTarget<'_> {
+    fn target_insert(&mut self, target_path: &OwnedTargetPath, value: Value) -> Result<(), String> {
+        match target_path.prefix {
+            PathPrefix::Event => self.value.insert(&target_path.path, value),
+            PathPrefix::Metadata => self.metadata.insert(&target_path.path, value),
+        };
+        Ok(())
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        let value = match target_path.prefix {
+            PathPrefix::Event => self.value.get(&target_path.path),
+            PathPrefix::Metadata => self.metadata.get(&target_path.path),
+        };
+        Ok(value)
+    }
+
+    fn target_get_mut(
+        &mut self,
+        target_path: &OwnedTargetPath,
+    ) -> Result<Option<&mut Value>, String> {
+        let value = match target_path.prefix {
+            PathPrefix::Event => self.value.get_mut(&target_path.path),
+            PathPrefix::Metadata => self.metadata.get_mut(&target_path.path),
+        };
+        Ok(value)
+    }
+
+    fn target_remove(
+        &mut self,
+        target_path: &OwnedTargetPath,
+        compact: bool,
+    ) -> Result<Option<Value>, String> {
+        let prev_value = match target_path.prefix {
+            PathPrefix::Event => self.value.remove(&target_path.path, compact),
+            PathPrefix::Metadata => self.metadata.remove(&target_path.path, compact),
+        };
+        Ok(prev_value)
+    }
+}
+
+impl SecretTarget for LazyJsonTarget<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.secrets.get_secret(key)
+    }
+
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        self.secrets.read(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.secrets.insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.secrets.remove_secret(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile, runtime::Runtime, Program, TimeZone};
+    use crate::{owned_value_path, stdlib, value};
+
+    const EVENT: &str = r#"{
+        "message": "GET /index.html 200",
+        "host": {"name": "web-1", "ip": "10.0.0.1"},
+        "http": {"status": 200, "headers": [{"name": "accept"}, {"name": "host"}]},
+        "tags": ["web", "prod"],
+        "escaped\"key": true
+    }"#;
+
+    fn program(source: &str) -> Program {
+        compile(source, &stdlib::all()).unwrap().program
+    }
+
+    fn run(program: &Program, target: &mut LazyJsonTarget<'_>) -> Value {
+        Runtime::default()
+            .resolve(target, program, &TimeZone::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_only_used_paths() {
+        let program = program(r#".http.status == 200 && .tags[-1] == "prod""#);
+        let mut target = LazyJsonTarget::new(EVENT, program.info()).unwrap();
+
+        assert_eq!(run(&program, &mut target), value!(true));
+        assert_eq!(
+            target.value(),
+            &value!({"http": {"status": 200}, "tags": ["web", "prod"]})
+        );
+    }
+
+    #[test]
+    fn finds_nested_and_escaped_paths() {
+        let program = program(r#"[.http.headers[1].name, ."escaped\"key", .missing.field]"#);
+        let mut target = LazyJsonTarget::new(EVENT, program.info()).unwrap();
+
+        assert_eq!(run(&program, &mut target), value!(["host", true, null]));
+    }
+
+    #[test]
+    fn applies_changes_to_the_document() {
+        let program = program(
+            "
+                .host.name = upcase!(.host.name)
+                del(.http.headers[0])
+                .new = 1
+            ",
+        );
+        let mut target = LazyJsonTarget::new(EVENT, program.info()).unwrap();
+        run(&program, &mut target);
+
+        let mut expected: Value = serde_json::from_str(EVENT).unwrap();
+        expected.insert(&owned_value_path!("host", "name"), "WEB-1");
+        expected.remove(&owned_value_path!("http", "headers", 0), false);
+        expected.insert(&owned_value_path!("new"), 1);
+        assert_eq!(target.into_value().unwrap(), expected);
+    }
+
+    #[test]
+    fn converts_everything_for_the_root() {
+        let program = program(r#". = {"replaced": keys(.)}"#);
+        let mut target = LazyJsonTarget::new(EVENT, program.info()).unwrap();
+        run(&program, &mut target);
+
+        assert_eq!(
+            target.into_value().unwrap(),
+            value!({"replaced": ["escaped\"key", "host", "http", "message", "tags"]})
+        );
+    }
+}
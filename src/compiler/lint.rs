@@ -0,0 +1,570 @@
+//! # Lints
+//!
+//! Lint rules check the AST of a program for code that compiles, but is likely to be a mistake.
+//! Each rule has a [`LintLevel`] that decides whether its findings are ignored, reported as
+//! warnings, or fail the compilation. The level of a rule is configured by name using
+//! [`CompileConfig::set_lint_level`], and custom rules are added with
+//! [`CompileConfig::add_lint_rule`].
+//!
+//! Only the `unused_result` rule is enabled by default, the other built-in rules are opt-in.
+//!
+//! [`CompileConfig::set_lint_level`]: super::CompileConfig::set_lint_level
+//! [`CompileConfig::add_lint_rule`]: super::CompileConfig::add_lint_rule
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::compiler::codes::{
+    WARNING_NULL_COMPARISON_CODE, WARNING_REDUNDANT_NULL_COALESCE_CODE,
+    WARNING_SHADOWED_VARIABLE_CODE,
+};
+use crate::compiler::unused_expression_checker::check_for_unused_results;
+use crate::compiler::{CompileConfig, TypeState};
+use crate::diagnostic::{Diagnostic, DiagnosticList, Label, Note, Severity, Span};
+use crate::parser::ast::{
+    Assignment, AssignmentTarget, Block, Container, Expr, FunctionCall, Ident, IfStatement, Node,
+    Opcode, Predicate, QueryTarget, RootExpr, Unary,
+};
+use crate::parser::{Literal, Program};
+
+/// How the findings of a lint rule are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    /// The rule isn't checked.
+    Allow,
+    /// Findings are reported as warnings.
+    Warn,
+    /// Findings are reported as errors, and the program fails to compile.
+    Deny,
+}
+
+impl FromStr for LintLevel {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            _ => Err("lint level must be one of allow, warn or deny."),
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => f.write_str("allow"),
+            Self::Warn => f.write_str("warn"),
+            Self::Deny => f.write_str("deny"),
+        }
+    }
+}
+
+/// A check run against the AST of a program.
+pub trait LintRule {
+    /// The name used to configure the level of the rule, such as `unused_result`.
+    fn name(&self) -> &'static str;
+
+    /// The level of the rule if it isn't configured.
+    fn default_level(&self) -> LintLevel {
+        LintLevel::Allow
+    }
+
+    /// Returns the findings of the rule for the given program. The severity of the returned
+    /// diagnostics is overridden by the level of the rule.
+    ///
+    /// The type state is the one the program is compiled with.
+    fn check(&self, ast: &Program, state: &TypeState) -> DiagnosticList;
+}
+
+/// The built-in lint rules.
+#[must_use]
+pub fn rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(UnusedResult),
+        Box::new(RedundantNullCoalesce),
+        Box::new(NullComparison),
+        Box::new(ShadowedVariable),
+    ]
+}
+
+/// Runs the built-in and custom lint rules of the config against the program.
+pub(crate) fn check(ast: &Program, state: &TypeState, config: &CompileConfig) -> DiagnosticList {
+    let mut diagnostics = DiagnosticList::default();
+
+    for rule in rules().iter().chain(config.lint_rules()) {
+        let severity = match config.lint_level(rule.as_ref()) {
+            LintLevel::Allow => continue,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+
+        diagnostics.extend(rule.check(ast, state).into_iter().map(|mut diagnostic| {
+            diagnostic.severity = severity;
+            diagnostic
+        }));
+    }
+
+    diagnostics
+}
+
+// -----------------------------------------------------------------------------
+// rules
+// -----------------------------------------------------------------------------
+
+/// Reports expressions whose result is never used, and variables that are never read.
+pub struct UnusedResult;
+
+impl LintRule for UnusedResult {
+    fn name(&self) -> &'static str {
+        "unused_result"
+    }
+
+    fn default_level(&self) -> LintLevel {
+        LintLevel::Warn
+    }
+
+    fn check(&self, ast: &Program, _: &TypeState) -> DiagnosticList {
+        check_for_unused_results(ast)
+    }
+}
+
+/// Reports `expr ?? null`, which replaces the error of `expr` with a `null` value that can't be
+/// told apart from a `null` result.
+pub struct RedundantNullCoalesce;
+
+impl LintRule for RedundantNullCoalesce {
+    fn name(&self) -> &'static str {
+        "redundant_null_coalesce"
+    }
+
+    fn check(&self, ast: &Program, _: &TypeState) -> DiagnosticList {
+        let mut diagnostics = DiagnosticList::default();
+
+        walk(ast, &mut |node| {
+            let Expr::Op(op) = node.inner() else {
+                return;
+            };
+
+            let op = op.inner();
+            if *op.1.inner() == Opcode::Err && is_null(&op.2) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        WARNING_REDUNDANT_NULL_COALESCE_CODE,
+                        "error coalesced to `null`",
+                        vec![Label::primary(
+                            "the error is replaced with `null`",
+                            node.span(),
+                        )],
+                        vec![Note::Hint(format!(
+                            "use an infallible assignment to handle the error, such as `value, err = {}`",
+                            op.0
+                        ))],
+                    ),
+                );
+            }
+        });
+
+        diagnostics
+    }
+}
+
+/// Reports comparisons of a path to `null`, which are also true if the path doesn't exist.
+pub struct NullComparison;
+
+impl LintRule for NullComparison {
+    fn name(&self) -> &'static str {
+        "null_comparison"
+    }
+
+    fn check(&self, ast: &Program, _: &TypeState) -> DiagnosticList {
+        let mut diagnostics = DiagnosticList::default();
+
+        walk(ast, &mut |node| {
+            let Expr::Op(op) = node.inner() else {
+                return;
+            };
+
+            let op = op.inner();
+            let outcome = match op.1.inner() {
+                Opcode::Eq => true,
+                Opcode::Ne => false,
+                _ => return,
+            };
+
+            let query = match (op.0.inner(), op.2.inner()) {
+                (Expr::Query(query), _) if is_null(&op.2) => query,
+                (_, Expr::Query(query)) if is_null(&op.0) => query,
+                _ => return,
+            };
+
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                WARNING_NULL_COMPARISON_CODE,
+                "comparison of a path to `null`",
+                vec![Label::primary(
+                    format!("this is also `{outcome}` if `{query}` doesn't exist"),
+                    node.span(),
+                )],
+                vec![Note::Hint(format!(
+                    "use `exists({query})` to check whether the path exists"
+                ))],
+            ));
+        });
+
+        diagnostics
+    }
+}
+
+/// Reports closure parameters that shadow a variable, making the variable inaccessible inside
+/// the closure.
+pub struct ShadowedVariable;
+
+impl LintRule for ShadowedVariable {
+    fn name(&self) -> &'static str {
+        "shadowed_variable"
+    }
+
+    fn check(&self, ast: &Program, _: &TypeState) -> DiagnosticList {
+        let mut visitor = ShadowVisitor {
+            scopes: vec![BTreeMap::new()],
+            diagnostics: DiagnosticList::default(),
+        };
+
+        for expr in root_exprs(ast) {
+            visitor.visit(expr);
+        }
+
+        visitor.diagnostics
+    }
+}
+
+struct ShadowVisitor {
+    /// The variables in scope and where they are first assigned, innermost scope last.
+    scopes: Vec<BTreeMap<Ident, Span>>,
+    diagnostics: DiagnosticList,
+}
+
+impl ShadowVisitor {
+    fn find(&self, ident: &Ident) -> Option<Span> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(ident).copied())
+    }
+
+    fn declare(&mut self, ident: &Ident, span: Span) {
+        if self.find(ident).is_none() {
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.insert(ident.clone(), span);
+            }
+        }
+    }
+
+    fn visit_block(&mut self, block: &Block, params: BTreeMap<Ident, Span>) {
+        self.scopes.push(params);
+        for expr in &block.0 {
+            self.visit(expr);
+        }
+        self.scopes.pop();
+    }
+
+    fn visit(&mut self, node: &Node<Expr>) {
+        match node.inner() {
+            Expr::Assignment(assignment) => {
+                let (targets, expr) = match assignment.inner() {
+                    Assignment::Single { target, expr, .. } => (vec![target], expr),
+                    Assignment::Infallible { ok, err, expr, .. } => (vec![ok, err], expr),
+                };
+
+                self.visit(expr);
+                for target in targets {
+                    if let AssignmentTarget::Internal(ident, _) = target.inner() {
+                        self.declare(ident, target.span());
+                    }
+                }
+            }
+            Expr::Container(container) => match container.inner() {
+                Container::Block(block) => self.visit_block(block.inner(), BTreeMap::new()),
+                _ => self.visit_children(node),
+            },
+            Expr::IfStatement(statement) => {
+                let IfStatement {
+                    predicate,
+                    if_node,
+                    else_node,
+                } = statement.inner();
+
+                for expr in predicate_exprs(predicate.inner()) {
+                    self.visit(expr);
+                }
+                self.visit_block(if_node.inner(), BTreeMap::new());
+                if let Some(else_node) = else_node {
+                    self.visit_block(else_node.inner(), BTreeMap::new());
+                }
+            }
+            Expr::FunctionCall(call) => {
+                let call = call.inner();
+                for argument in &call.arguments {
+                    self.visit(&argument.inner().expr);
+                }
+
+                let Some(closure) = &call.closure else {
+                    return;
+                };
+
+                let mut params = BTreeMap::new();
+                for param in &closure.inner().variables {
+                    let ident = param.inner();
+                    if let Some(span) = self.find(ident) {
+                        self.diagnostics.push(Diagnostic::new(
+                            Severity::Warning,
+                            WARNING_SHADOWED_VARIABLE_CODE,
+                            format!("closure parameter `{ident}` shadows a variable"),
+                            vec![
+                                Label::primary(
+                                    format!("this shadows the variable `{ident}`"),
+                                    param.span(),
+                                ),
+                                Label::context(format!("`{ident}` is assigned here"), span),
+                            ],
+                            vec![Note::Hint(format!(
+                                "rename the parameter to read `{ident}` inside the closure"
+                            ))],
+                        ));
+                    }
+                    params.insert(ident.clone(), param.span());
+                }
+
+                self.visit_block(closure.inner().block.inner(), params);
+            }
+            _ => self.visit_children(node),
+        }
+    }
+
+    fn visit_children(&mut self, node: &Node<Expr>) {
+        for child in children(node.inner()) {
+            self.visit(child);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// traversal
+// -----------------------------------------------------------------------------
+
+fn is_null(node: &Node<Expr>) -> bool {
+    matches!(node.inner(), Expr::Literal(literal) if *literal.inner() == Literal::Null)
+}
+
+fn root_exprs(ast: &Program) -> impl Iterator<Item = &Node<Expr>> {
+    ast.0.iter().flat_map(|root| match root.inner() {
+        RootExpr::Expr(expr) => vec![expr],
+        RootExpr::OnError(block) => block.inner().0.iter().collect(),
+        RootExpr::Error(_) => vec![],
+    })
+}
+
+/// Calls `f` for every expression of the program, parents before their children.
+fn walk<'a>(ast: &'a Program, f: &mut impl FnMut(&'a Node<Expr>)) {
+    fn visit<'a>(node: &'a Node<Expr>, f: &mut impl FnMut(&'a Node<Expr>)) {
+        f(node);
+        for child in children(node.inner()) {
+            visit(child, f);
+        }
+    }
+
+    for expr in root_exprs(ast) {
+        visit(expr, f);
+    }
+}
+
+fn predicate_exprs(predicate: &Predicate) -> Vec<&Node<Expr>> {
+    match predicate {
+        Predicate::One(expr) => vec![&**expr],
+        Predicate::Many(exprs) => exprs.iter().collect(),
+    }
+}
+
+fn container_children(container: &Container) -> Vec<&Node<Expr>> {
+    match container {
+        Container::Group(group) => vec![&group.inner().0],
+        Container::Block(block) => block.inner().0.iter().collect(),
+        Container::Array(array) => array.inner().0.iter().collect(),
+        Container::Object(object) => object.inner().0.values().collect(),
+    }
+}
+
+fn call_children(call: &FunctionCall) -> Vec<&Node<Expr>> {
+    let mut children: Vec<_> = call
+        .arguments
+        .iter()
+        .map(|argument| &argument.inner().expr)
+        .collect();
+
+    if let Some(closure) = &call.closure {
+        children.extend(&closure.inner().block.inner().0);
+    }
+
+    children
+}
+
+/// The expressions directly nested in the given expression.
+fn children(expr: &Expr) -> Vec<&Node<Expr>> {
+    match expr {
+        Expr::Literal(_) | Expr::Variable(_) => vec![],
+        Expr::Container(container) => container_children(container.inner()),
+        Expr::IfStatement(statement) => {
+            let IfStatement {
+                predicate,
+                if_node,
+                else_node,
+            } = statement.inner();
+
+            let mut children = predicate_exprs(predicate.inner());
+            children.extend(&if_node.inner().0);
+            if let Some(else_node) = else_node {
+                children.extend(&else_node.inner().0);
+            }
+            children
+        }
+        Expr::Op(op) => vec![&*op.inner().0, &*op.inner().2],
+        Expr::Assignment(assignment) => match assignment.inner() {
+            Assignment::Single { expr, .. } | Assignment::Infallible { expr, .. } => {
+                vec![&**expr]
+            }
+        },
+        Expr::Query(query) => match query.inner().target.inner() {
+            QueryTarget::FunctionCall(call) => call_children(call),
+            QueryTarget::Container(container) => container_children(container),
+            QueryTarget::Internal(_) | QueryTarget::External(_) => vec![],
+        },
+        Expr::FunctionCall(call) => call_children(call.inner()),
+        Expr::Unary(unary) => match unary.inner() {
+            Unary::Not(not) => vec![&*not.inner().1],
+            Unary::BitNot(not) => vec![&*not.inner().1],
+        },
+        Expr::Abort(abort) => abort
+            .inner()
+            .message
+            .iter()
+            .map(|message| &**message)
+            .collect(),
+        Expr::Return(ret) => vec![&*ret.inner().expr],
+        Expr::Loop(r#loop) => {
+            let mut children = vec![&*r#loop.inner().max];
+            children.extend(&r#loop.inner().block.inner().0);
+            children
+        }
+        Expr::Break(r#break) => r#break
+            .inner()
+            .condition
+            .iter()
+            .flat_map(|condition| predicate_exprs(condition.inner()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile_with_state;
+    use crate::stdlib;
+
+    fn lint(source: &str, rule: &str, level: LintLevel) -> crate::compiler::Result {
+        let mut config = CompileConfig::default();
+        config.set_lint_level(rule, level);
+
+        compile_with_state(source, &stdlib::all(), &TypeState::default(), config)
+    }
+
+    fn warnings(source: &str, rule: &str, code: usize) -> Vec<String> {
+        lint(source, rule, LintLevel::Warn)
+            .unwrap()
+            .warnings
+            .into_iter()
+            .filter(|warning| warning.code == code)
+            .map(|warning| warning.message)
+            .collect()
+    }
+
+    #[test]
+    fn opt_in_rules_are_allowed_by_default() {
+        let result = crate::compiler::compile(
+            r#".a = parse_json(.b) ?? null; .c = .d == null; map_values(.) -> |value| { value }"#,
+            &stdlib::all(),
+        )
+        .unwrap();
+
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn unused_result_can_be_allowed() {
+        let result = lint(r#"x = 1; .a = 2"#, "unused_result", LintLevel::Allow).unwrap();
+
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn denied_rule_fails_compilation() {
+        let diagnostics = match lint(".a = .b == null", "null_comparison", LintLevel::Deny) {
+            Ok(_) => panic!("expected the program to fail to compile"),
+            Err(diagnostics) => diagnostics,
+        };
+
+        assert_eq!(diagnostics.errors().len(), 1);
+        assert_eq!(diagnostics.errors()[0].code, WARNING_NULL_COMPARISON_CODE);
+    }
+
+    #[test]
+    fn redundant_null_coalesce() {
+        assert_eq!(
+            warnings(
+                ".a = parse_json(.b) ?? null; .c = parse_json(.d) ?? {}",
+                "redundant_null_coalesce",
+                WARNING_REDUNDANT_NULL_COALESCE_CODE,
+            ),
+            vec!["error coalesced to `null`"]
+        );
+    }
+
+    #[test]
+    fn null_comparison() {
+        assert_eq!(
+            warnings(
+                ".a = .b == null; .c = null != .d; .e = .f == 1",
+                "null_comparison",
+                WARNING_NULL_COMPARISON_CODE,
+            ),
+            vec![
+                "comparison of a path to `null`",
+                "comparison of a path to `null`"
+            ]
+        );
+    }
+
+    #[test]
+    fn shadowed_variable() {
+        assert_eq!(
+            warnings(
+                "value = 1; . = map_values(.) -> |value| { value }; .a = value",
+                "shadowed_variable",
+                WARNING_SHADOWED_VARIABLE_CODE,
+            ),
+            vec!["closure parameter `value` shadows a variable"]
+        );
+    }
+
+    #[test]
+    fn block_variables_are_not_shadowed() {
+        assert!(warnings(
+            "if .a == 1 { value = 1; .b = value }; . = map_values(.) -> |value| { value }",
+            "shadowed_variable",
+            WARNING_SHADOWED_VARIABLE_CODE,
+        )
+        .is_empty());
+    }
+}
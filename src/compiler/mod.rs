@@ -34,14 +34,15 @@ use std::{fmt::Display, str::FromStr};
 pub use paste::paste;
 use serde::{Deserialize, Serialize};
 
-use crate::compiler::unused_expression_checker::check_for_unused_results;
 pub use compiler::{CompilationResult, Compiler};
 pub use context::Context;
 pub use datetime::TimeZone;
 pub use expression::{Expression, FunctionExpression};
 pub use expression_error::{ExpressionError, Resolved};
 pub use function::{Function, Parameter};
-pub use program::{Program, ProgramInfo};
+pub use integer_range::IntegerRange;
+pub use observer::RuntimeObserver;
+pub use program::{CollectedError, Program, ProgramInfo};
 pub use state::{TypeInfo, TypeState};
 pub use target::{SecretTarget, Target, TargetValue, TargetValueRef};
 pub use type_def::TypeDef;
@@ -51,7 +52,15 @@ pub(crate) use crate::diagnostic::Span;
 use crate::parser::parse;
 
 pub use self::compile_config::CompileConfig;
+pub use self::path_pattern::PathPattern;
 pub use self::deprecation_warning::DeprecationWarning;
+pub use self::env_var_policy::EnvVarPolicy;
+pub use self::lazy_json_target::LazyJsonTarget;
+pub use self::lint::{LintLevel, LintRule};
+pub use self::serde_target::SerdeTarget;
+pub use self::state_store::{MemoryStateStore, StateStore};
+pub use self::tenant_policy::{ResourceLimits, SecretAccess, TenantPolicy};
+pub use self::write_limits::{WriteLimitError, WriteLimits};
 
 #[allow(clippy::module_inception)]
 mod compiler;
@@ -60,15 +69,26 @@ mod compile_config;
 mod context;
 mod datetime;
 mod deprecation_warning;
+mod env_var_policy;
 mod expression_error;
+mod integer_range;
+mod lazy_json_target;
+mod observer;
+mod on_error;
+mod path_pattern;
 mod program;
+mod serde_target;
+mod state_store;
 mod target;
+mod tenant_policy;
 mod test_util;
+mod write_limits;
 
 pub mod codes;
 pub mod conversion;
 pub mod expression;
 pub mod function;
+pub mod lint;
 pub mod prelude;
 pub mod runtime;
 pub mod state;
@@ -106,23 +126,34 @@ pub fn compile_with_state(
     state: &TypeState,
     config: CompileConfig,
 ) -> Result {
+    if let Some(max) = config
+        .tenant_policy()
+        .and_then(|policy| policy.limits().max_source_length)
+    {
+        if source.len() > max {
+            let error = ExpressionError::from(format!(
+                "program source is {} bytes, which exceeds the tenant limit of {max} bytes",
+                source.len()
+            ));
+            return Err(DiagnosticList::from(vec![Box::new(error) as Box<_>]));
+        }
+    }
+
     let ast = parse(source)
         .map_err(|err| crate::diagnostic::DiagnosticList::from(vec![Box::new(err) as Box<_>]))?;
 
-    let unused_expression_check_enabled = config.unused_expression_check_enabled();
-    let result = Compiler::compile(fns, ast.clone(), state, config);
+    // Lints are only reported for programs that compile.
+    let lint_diagnostics = lint::check(&ast, state, &config);
+    let mut result = Compiler::compile(fns, ast, state, config)?;
 
-    if unused_expression_check_enabled {
-        let unused_warnings = check_for_unused_results(&ast);
-        if !unused_warnings.is_empty() {
-            return result.map(|mut compilation_result| {
-                compilation_result.warnings.extend(unused_warnings);
-                compilation_result
-            });
-        }
+    if lint_diagnostics.is_err() {
+        let mut diagnostics = result.warnings;
+        diagnostics.extend(lint_diagnostics);
+        return Err(diagnostics);
     }
 
-    result
+    result.warnings.extend(lint_diagnostics);
+    Ok(result)
 }
 
 /// Available VRL runtimes.
@@ -0,0 +1,26 @@
+use crate::diagnostic::Span;
+use crate::value::Value;
+
+use super::Resolved;
+
+/// Receives callbacks as a [`Program`](super::Program) runs, for example to
+/// trace which statements modify which fields.
+///
+/// Observers are registered on the [`Runtime`](super::runtime::Runtime). All
+/// callbacks do nothing by default, so an observer only implements the ones it
+/// needs. Programs run without an observer don't pay for any of them.
+pub trait RuntimeObserver {
+    /// Called after a value is assigned to `target`, such as `.foo`,
+    /// `%foo` or `foo.bar`, with the value it had before, if any.
+    fn on_assignment(&mut self, _target: &str, _old: Option<&Value>, _new: &Value) {}
+
+    /// Called before `function` is called at `span`.
+    fn on_function_enter(&mut self, _function: &str, _span: Span) {}
+
+    /// Called after `function` returns at `span`.
+    fn on_function_exit(&mut self, _function: &str, _span: Span, _result: &Resolved) {}
+
+    /// Called after the condition of an `if` statement is evaluated, with
+    /// whether the `if` block is taken.
+    fn on_branch(&mut self, _condition: &str, _taken: bool) {}
+}
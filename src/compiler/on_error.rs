@@ -0,0 +1,114 @@
+use std::fmt;
+
+use crate::compiler::{
+    expression::Block,
+    state::{TypeInfo, TypeState},
+    type_def::Details,
+    Context, Expression, Resolved, Span, TypeDef,
+};
+use crate::diagnostic::{DiagnosticMessage, Label, Note};
+use crate::parser::ast::Ident;
+use crate::value::Value;
+
+/// The variable holding the error message inside the handler.
+const ERROR_VARIABLE: &str = "err";
+
+/// The program-level `on_error { ... }` handler, run when the program fails
+/// with an uncaught runtime error.
+#[derive(Debug, Clone)]
+pub(crate) struct OnError {
+    /// The type state the handler was compiled with.
+    state: TypeState,
+    block: Block,
+}
+
+impl OnError {
+    pub(crate) fn new(state: TypeState, block: Block) -> Self {
+        Self { state, block }
+    }
+
+    /// The type state the handler runs with, given the state at the point
+    /// where the error can occur.
+    pub(crate) fn type_state(mut state: TypeState) -> TypeState {
+        state.local.insert_variable(
+            Ident::new(ERROR_VARIABLE),
+            Details {
+                type_def: TypeDef::bytes(),
+                value: None,
+            },
+        );
+        state
+    }
+
+    pub(crate) fn resolve(&self, message: String, ctx: &mut Context) -> Resolved {
+        ctx.state_mut()
+            .insert_variable(Ident::new(ERROR_VARIABLE), Value::from(message));
+        self.block.resolve(ctx)
+    }
+
+    pub(crate) fn type_info(&self) -> TypeInfo {
+        self.block.type_info(&self.state)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub(crate) struct Error {
+    variant: ErrorVariant,
+    span: Span,
+    first_span: Span,
+}
+
+impl Error {
+    pub(crate) fn duplicate(span: Span, first_span: Span) -> Self {
+        Self {
+            variant: ErrorVariant::Duplicate,
+            span,
+            first_span,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum ErrorVariant {
+    #[error("duplicate error handler")]
+    Duplicate,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.variant)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.variant)
+    }
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use ErrorVariant::Duplicate;
+
+        match self.variant {
+            Duplicate => 632,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        match &self.variant {
+            ErrorVariant::Duplicate => vec![
+                Label::primary("a program can only have one error handler", self.span),
+                Label::context("first error handler defined here", self.first_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        match self.variant {
+            ErrorVariant::Duplicate => vec![Note::SeeErrorDocs],
+        }
+    }
+}
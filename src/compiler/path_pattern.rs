@@ -0,0 +1,234 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::path::{OwnedSegment, OwnedTargetPath, PathPrefix};
+
+/// A target path in which `*` matches any single field or index, such as
+/// `.kubernetes.*.labels` or `%headers[*]`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PathPattern {
+    prefix: PathPrefix,
+    segments: Vec<PatternSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PatternSegment {
+    Field(String),
+    Index(isize),
+    Wildcard,
+}
+
+impl PatternSegment {
+    fn matches(&self, segment: &OwnedSegment) -> bool {
+        match (self, segment) {
+            (PatternSegment::Wildcard, _) => true,
+            (PatternSegment::Field(field), OwnedSegment::Field(key)) => field.as_str() == &**key,
+            (PatternSegment::Index(index), OwnedSegment::Index(other)) => index == other,
+            _ => false,
+        }
+    }
+}
+
+impl PathPattern {
+    /// Returns whether the path matches the pattern exactly.
+    #[must_use]
+    pub fn matches(&self, path: &OwnedTargetPath) -> bool {
+        path.path.segments.len() == self.segments.len() && self.is_prefix_of(path)
+    }
+
+    /// Returns whether the path matches the pattern, or is nested in a path
+    /// that does.
+    #[must_use]
+    pub fn is_prefix_of(&self, path: &OwnedTargetPath) -> bool {
+        self.prefix == path.prefix
+            && path.path.segments.len() >= self.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&path.path.segments)
+                .all(|(pattern, segment)| pattern.matches(segment))
+    }
+
+    /// Returns whether the path matches the pattern, or contains a path that
+    /// does.
+    #[must_use]
+    pub fn has_prefix(&self, path: &OwnedTargetPath) -> bool {
+        self.prefix == path.prefix
+            && path.path.segments.len() <= self.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&path.path.segments)
+                .all(|(pattern, segment)| pattern.matches(segment))
+    }
+}
+
+impl From<OwnedTargetPath> for PathPattern {
+    fn from(path: OwnedTargetPath) -> Self {
+        let segments = path
+            .path
+            .segments
+            .into_iter()
+            .map(|segment| match segment {
+                OwnedSegment::Field(field) => PatternSegment::Field(field.into()),
+                OwnedSegment::Index(index) => PatternSegment::Index(index),
+            })
+            .collect();
+
+        Self {
+            prefix: path.prefix,
+            segments,
+        }
+    }
+}
+
+impl FromStr for PathPattern {
+    type Err = String;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let error = |reason: &str| format!("invalid path pattern `{pattern}`: {reason}");
+
+        let mut chars = pattern.chars().peekable();
+        let prefix = match chars.next() {
+            Some('.') => PathPrefix::Event,
+            Some('%') => PathPrefix::Metadata,
+            _ => return Err(error("must start with `.` or `%`")),
+        };
+
+        let mut segments = vec![];
+        // The prefix acts as the separator of the first field.
+        let mut after_separator = true;
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '.' if !after_separator => after_separator = true,
+                '[' => {
+                    let index: String = chars.by_ref().take_while(|ch| *ch != ']').collect();
+                    let segment = match index.as_str() {
+                        "*" => PatternSegment::Wildcard,
+                        index => PatternSegment::Index(
+                            index
+                                .parse()
+                                .map_err(|_| error(&format!("invalid index `{index}`")))?,
+                        ),
+                    };
+                    segments.push(segment);
+                    after_separator = false;
+                }
+                '*' if after_separator => {
+                    segments.push(PatternSegment::Wildcard);
+                    after_separator = false;
+                }
+                '"' if after_separator => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some('\\') => field.extend(chars.next()),
+                            Some(ch) => field.push(ch),
+                            None => return Err(error("unterminated quoted field")),
+                        }
+                    }
+                    segments.push(PatternSegment::Field(field));
+                    after_separator = false;
+                }
+                ch if after_separator && is_field_char(ch) => {
+                    let mut field = String::from(ch);
+                    while let Some(ch) = chars.next_if(|ch| is_field_char(*ch)) {
+                        field.push(ch);
+                    }
+                    segments.push(PatternSegment::Field(field));
+                    after_separator = false;
+                }
+                ch => return Err(error(&format!("unexpected character `{ch}`"))),
+            }
+        }
+
+        if after_separator && !segments.is_empty() {
+            return Err(error("ends with a separator"));
+        }
+
+        Ok(Self { prefix, segments })
+    }
+}
+
+fn is_field_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '@')
+}
+
+impl fmt::Display for PathPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.prefix {
+            PathPrefix::Event => f.write_str(".")?,
+            PathPrefix::Metadata => f.write_str("%")?,
+        }
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "." };
+            match segment {
+                PatternSegment::Field(field) if field.chars().all(is_field_char) => {
+                    write!(f, "{separator}{field}")?;
+                }
+                PatternSegment::Field(field) => write!(f, "{separator}{field:?}")?,
+                PatternSegment::Index(index) => write!(f, "[{index}]")?,
+                PatternSegment::Wildcard => write!(f, "{separator}*")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{owned_event_path, owned_metadata_path};
+
+    fn pattern(pattern: &str) -> PathPattern {
+        pattern.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays_patterns() {
+        for source in [
+            ".",
+            "%",
+            ".foo",
+            ".kubernetes.*.labels",
+            r#".foo."bar baz"[0]"#,
+            "%headers.*[0]",
+        ] {
+            assert_eq!(pattern(source).to_string(), source);
+        }
+
+        // A wildcard matches both fields and indices, so it is always
+        // displayed as a field.
+        assert_eq!(pattern("%headers[*].*").to_string(), "%headers.*.*");
+
+        for source in [
+            "foo",
+            ".foo.",
+            ".foo..bar",
+            ".foo[x]",
+            r#"."foo"#,
+            ".foo-bar",
+        ] {
+            assert!(source.parse::<PathPattern>().is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn wildcards_match_any_segment() {
+        let labels = pattern(".kubernetes.*.labels");
+
+        assert!(labels.matches(&owned_event_path!("kubernetes", "pod", "labels")));
+        assert!(!labels.matches(&owned_event_path!("kubernetes", "pod")));
+        assert!(!labels.matches(&owned_metadata_path!("kubernetes", "pod", "labels")));
+
+        assert!(labels.is_prefix_of(&owned_event_path!("kubernetes", "pod", "labels", "app")));
+        assert!(labels.has_prefix(&owned_event_path!("kubernetes")));
+        assert!(!labels.has_prefix(&owned_event_path!("message")));
+
+        assert!(pattern(".tags[*]").matches(&owned_event_path!("tags", 1)));
+    }
+}
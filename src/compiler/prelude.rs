@@ -18,13 +18,14 @@ pub use super::Resolved;
 pub use super::{
     expression,
     function::{
-        self, closure, ArgumentList, Compiled, Example, FunctionClosure, FunctionCompileContext,
+        self, closure, signature::Signature, ArgumentList, Compiled, Example, FunctionClosure,
+        FunctionCompileContext, TypeGuard,
     },
     state::{self, TypeInfo, TypeState},
     type_def,
     value::{kind, ValueError, VrlValueArithmetic, VrlValueConvert},
-    Context, Expression, ExpressionError, Function, FunctionExpression, Parameter, TimeZone,
-    TypeDef,
+    Context, Expression, ExpressionError, Function, FunctionExpression, IntegerRange, Parameter,
+    TimeZone, TypeDef,
 };
 
 pub type ExpressionResult<T> = Result<T, ExpressionError>;
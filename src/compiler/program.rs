@@ -1,14 +1,20 @@
+use crate::diagnostic::{DiagnosticMessage, Span};
 use crate::path::OwnedTargetPath;
+use crate::value::{ObjectMap, Value};
 
+use super::on_error::OnError;
 use super::state::{TypeInfo, TypeState};
-use super::{expression::Block, Context, Expression, Resolved};
+use super::{expression::Block, Context, Expression, ExpressionError, Resolved, WriteLimits};
 
 #[derive(Debug, Clone)]
 pub struct Program {
     /// The initial state that the program was compiled with.
     pub(crate) initial_state: TypeState,
     pub(crate) expressions: Block,
+    pub(crate) on_error: Option<OnError>,
     pub(crate) info: ProgramInfo,
+    pub(crate) write_limits: WriteLimits,
+    pub(crate) raise_target_insert_errors: bool,
 }
 
 impl Program {
@@ -21,7 +27,24 @@ impl Program {
     /// Retrieves the state of the type system after the program runs.
     #[must_use]
     pub fn final_type_info(&self) -> TypeInfo {
-        self.expressions.type_info(&self.initial_state)
+        let info = self.expressions.type_info(&self.initial_state);
+
+        match &self.on_error {
+            Some(on_error) => {
+                let handled = on_error.type_info();
+                TypeInfo::new(
+                    info.state.merge(handled.state),
+                    info.result.union(handled.result),
+                )
+            }
+            None => info,
+        }
+    }
+
+    /// Returns whether the program has an `on_error` handler.
+    #[must_use]
+    pub fn has_error_handler(&self) -> bool {
+        self.on_error.is_some()
     }
 
     /// Get detailed information about the program, as collected by the VRL
@@ -31,13 +54,125 @@ impl Program {
         &self.info
     }
 
+    /// Get the limits on the fields the program may insert into the external
+    /// [`Target`], enforced by the [`Runtime`](super::runtime::Runtime).
+    #[must_use]
+    pub fn write_limits(&self) -> WriteLimits {
+        self.write_limits
+    }
+
+    /// Whether errors returned by [`Target::target_insert`](super::Target::target_insert)
+    /// fail the program, see [`CompileConfig::set_raise_target_insert_errors`](super::CompileConfig::set_raise_target_insert_errors).
+    #[must_use]
+    pub fn raise_target_insert_errors(&self) -> bool {
+        self.raise_target_insert_errors
+    }
+
     /// Resolve the program to its final [`Value`].
     ///
+    /// If the program has an `on_error` handler, a runtime error runs the
+    /// handler instead, and the program resolves to the value of the handler.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the program resulted in a runtime error.
+    /// Returns an error if the program resulted in a runtime error that
+    /// wasn't handled, or if it was aborted.
     pub fn resolve(&self, ctx: &mut Context) -> Resolved {
-        self.expressions.resolve(ctx)
+        let result = self.expressions.resolve(ctx);
+
+        match (&self.on_error, result) {
+            (Some(on_error), Err(ExpressionError::Error { message, .. })) => {
+                on_error.resolve(message, ctx)
+            }
+            (_, result) => result,
+        }
+    }
+
+    /// The number of top-level statements of the program.
+    #[must_use]
+    pub fn statement_count(&self) -> usize {
+        self.expressions.exprs().len()
+    }
+
+    /// Resolve only the top-level statement at `index`, which allows running
+    /// the program one statement at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statement resulted in a runtime error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds of
+    /// [`Program::statement_count`].
+    pub fn resolve_statement(&self, index: usize, ctx: &mut Context) -> Resolved {
+        self.expressions.exprs()[index].resolve(ctx)
+    }
+
+    /// Resolve the program to its final [`Value`], continuing with the next
+    /// top-level statement when a statement fails with a runtime error.
+    ///
+    /// The errors are added to `errors`, and the effects of the statements
+    /// that didn't fail are kept. If the last statement fails, the program
+    /// resolves to `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program was aborted, all other errors are
+    /// collected.
+    pub fn resolve_collecting_errors(
+        &self,
+        ctx: &mut Context,
+        errors: &mut Vec<CollectedError>,
+    ) -> Resolved {
+        let mut value = Value::Null;
+        for expr in self.expressions.exprs() {
+            value = match expr.resolve(ctx) {
+                Ok(value) => value,
+                Err(error @ ExpressionError::Error { .. }) => {
+                    errors.push(CollectedError::from(error));
+                    Value::Null
+                }
+                Err(error) => return Err(error),
+            };
+        }
+        Ok(value)
+    }
+}
+
+/// A runtime error of a top-level statement, collected by
+/// [`Program::resolve_collecting_errors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectedError {
+    pub message: String,
+
+    /// The span of the expression that raised the error, if known.
+    pub span: Option<Span>,
+}
+
+impl From<ExpressionError> for CollectedError {
+    fn from(error: ExpressionError) -> Self {
+        let span = error
+            .labels()
+            .into_iter()
+            .find(|label| label.primary)
+            .map(|label| label.span);
+
+        Self {
+            message: error.message(),
+            span,
+        }
+    }
+}
+
+impl From<CollectedError> for Value {
+    fn from(error: CollectedError) -> Self {
+        let mut map = ObjectMap::from([("message".into(), error.message.into())]);
+        if let Some(span) = error.span {
+            map.insert("start".into(), span.start().into());
+            map.insert("end".into(), span.end().into());
+        }
+        map.into()
     }
 }
 
@@ -46,7 +181,9 @@ pub struct ProgramInfo {
     /// Returns whether the compiled program can fail at runtime.
     ///
     /// A program can only fail at runtime if the fallible-function-call
-    /// (`foo!()`) is used within the source.
+    /// (`foo!()`) is used within the source, or if it assigns to the external
+    /// [`Target`] while [`WriteLimits`] are set or target insert errors are
+    /// raised.
     pub fallible: bool,
 
     /// Returns whether the compiled program can be aborted at runtime.
@@ -61,4 +198,128 @@ pub struct ProgramInfo {
     /// A list of possible assignments made to the external [`Target`] at
     /// runtime.
     pub target_assignments: Vec<OwnedTargetPath>,
+
+    /// The identifiers of the functions called by the program, sorted and
+    /// without duplicates.
+    pub functions: Vec<&'static str>,
+
+    /// The keys of the secrets read or modified by the program, sorted and
+    /// without duplicates.
+    ///
+    /// Only keys passed as a literal are known, see
+    /// [`ProgramInfo::dynamic_secret_keys`].
+    pub secret_keys: Vec<String>,
+
+    /// Returns whether a secret function is called with a key that is only
+    /// known at runtime, in which case any secret can be accessed.
+    pub dynamic_secret_keys: bool,
+}
+
+impl ProgramInfo {
+    /// Returns whether the program can read the given path of the external
+    /// [`Target`], or a path nested in it or containing it.
+    #[must_use]
+    pub fn may_read(&self, path: &OwnedTargetPath) -> bool {
+        self.target_queries
+            .iter()
+            .any(|query| overlaps(query, path))
+    }
+
+    /// Returns whether the program can assign the given path of the external
+    /// [`Target`], or a path nested in it or containing it.
+    #[must_use]
+    pub fn may_write(&self, path: &OwnedTargetPath) -> bool {
+        self.target_assignments
+            .iter()
+            .any(|assignment| overlaps(assignment, path))
+    }
+
+    /// Returns whether the program calls the given function.
+    #[must_use]
+    pub fn calls(&self, function: &str) -> bool {
+        self.functions.contains(&function)
+    }
+
+    /// Returns whether the program can read or modify the secret with the
+    /// given key.
+    #[must_use]
+    pub fn may_access_secret(&self, key: &str) -> bool {
+        self.dynamic_secret_keys || self.secret_keys.iter().any(|secret| secret == key)
+    }
+}
+
+fn overlaps(lhs: &OwnedTargetPath, rhs: &OwnedTargetPath) -> bool {
+    lhs.can_start_with(rhs) || rhs.can_start_with(lhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::compile;
+    use crate::parser::ast::Ident;
+    use crate::path::OwnedTargetPath;
+    use crate::{owned_event_path, owned_metadata_path, stdlib};
+
+    #[test]
+    fn info_records_paths_and_functions() {
+        let program = compile(
+            r#".a.b = upcase(string!(.c)); %d = downcase(.e) ?? "e"; .f = upcase("f")"#,
+            &stdlib::all(),
+        )
+        .unwrap()
+        .program;
+        let info = program.info();
+
+        assert_eq!(info.functions, vec!["downcase", "string", "upcase"]);
+        assert!(info.calls("upcase"));
+        assert!(!info.calls("get_secret"));
+
+        assert!(info.may_read(&owned_event_path!("c")));
+        assert!(info.may_read(&OwnedTargetPath::event_root()));
+        assert!(!info.may_read(&owned_event_path!("a")));
+
+        assert!(info.may_write(&owned_event_path!("a")));
+        assert!(info.may_write(&owned_event_path!("a", "b", "c")));
+        assert!(info.may_write(&owned_metadata_path!("d")));
+        assert!(!info.may_write(&owned_event_path!("d")));
+
+        assert!(info.secret_keys.is_empty());
+        assert!(!info.may_access_secret("key"));
+    }
+
+    fn error_codes(source: &str) -> Vec<usize> {
+        match compile(source, &stdlib::all()) {
+            Ok(_) => panic!("expected compilation to fail"),
+            Err(diagnostics) => diagnostics.iter().map(|d| d.code).collect(),
+        }
+    }
+
+    #[test]
+    fn on_error_handler() {
+        let program = compile(
+            ".a = to_int!(.a); on_error { .error = err; null }",
+            &stdlib::all(),
+        )
+        .unwrap()
+        .program;
+
+        assert!(program.has_error_handler());
+        assert!(program
+            .final_type_info()
+            .state
+            .local
+            .variable(&Ident::new("err"))
+            .is_some());
+
+        // Handlers can only be defined once.
+        assert_eq!(
+            error_codes("on_error { .a = 1 }; on_error { .b = 2 }"),
+            vec![632]
+        );
+
+        // Errors within the handler must be handled.
+        assert_eq!(error_codes("on_error { to_int(.a) }"), vec![100]);
+
+        // Variables assigned by the program may not be set when the handler runs.
+        assert_eq!(error_codes("x = 1; on_error { .x = x }"), vec![701]);
+    }
 }
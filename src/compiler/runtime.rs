@@ -3,15 +3,26 @@ use std::{error::Error, fmt};
 use crate::path::OwnedTargetPath;
 use crate::value::Value;
 
-use super::ExpressionError;
+use super::write_limits::LimitedTarget;
 use super::TimeZone;
-use super::{state, Context, Program, Target};
+use super::{state, Context, Program, RuntimeObserver, Target};
+use super::{ExpressionError, Resolved};
 
 pub type RuntimeResult = Result<Value, Terminate>;
 
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct Runtime {
     state: state::RuntimeState,
+    observer: Option<Box<dyn RuntimeObserver + Send>>,
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Runtime")
+            .field("state", &self.state)
+            .field("observed", &self.observer.is_some())
+            .finish()
+    }
 }
 
 /// The error raised if the runtime is terminated.
@@ -56,7 +67,21 @@ impl Error for Terminate {
 impl Runtime {
     #[must_use]
     pub fn new(state: state::RuntimeState) -> Self {
-        Self { state }
+        Self {
+            state,
+            observer: None,
+        }
+    }
+
+    /// Registers an observer that is notified as programs run, replacing any
+    /// previously registered one.
+    pub fn set_observer(&mut self, observer: impl RuntimeObserver + Send + 'static) {
+        self.observer = Some(Box::new(observer));
+    }
+
+    /// Removes the registered observer, if any.
+    pub fn take_observer(&mut self) -> Option<Box<dyn RuntimeObserver + Send>> {
+        self.observer.take()
     }
 
     #[must_use]
@@ -68,6 +93,18 @@ impl Runtime {
         self.state.clear();
     }
 
+    /// The variables set by the programs resolved so far.
+    #[must_use]
+    pub fn state(&self) -> &state::RuntimeState {
+        &self.state
+    }
+
+    /// Replaces the variables, for example to restore a previous
+    /// [`Runtime::state`].
+    pub fn set_state(&mut self, state: state::RuntimeState) {
+        self.state = state;
+    }
+
     /// Given the provided [`Target`], resolve the provided [`Program`] to
     /// completion.
     pub fn resolve(
@@ -76,31 +113,338 @@ impl Runtime {
         program: &Program,
         timezone: &TimeZone,
     ) -> RuntimeResult {
-        // Validate that the path is a value.
-        match target.target_get(&OwnedTargetPath::event_root()) {
-            Ok(Some(_)) => {}
-            Ok(None) => {
-                return Err(Terminate::Error(
-                    "expected target object, got nothing".to_owned().into(),
-                ))
-            }
-            Err(err) => {
-                return Err(Terminate::Error(
-                    format!("error querying target object: {err}").into(),
-                ))
-            }
+        validate_target(target)?;
+
+        into_runtime_result(
+            self.with_context(target, program, *timezone, |ctx| program.resolve(ctx)),
+        )
+    }
+
+    /// Given the provided [`Target`], resolve only the top-level statement at
+    /// `index` of the provided [`Program`].
+    ///
+    /// Unlike [`Runtime::resolve`], the error isn't converted to a
+    /// [`Terminate`], so that callers stepping through a program can tell an
+    /// early `return` apart from a failure.
+    ///
+    /// See [`Program::resolve_statement`].
+    pub fn resolve_statement(
+        &mut self,
+        target: &mut dyn Target,
+        program: &Program,
+        index: usize,
+        timezone: &TimeZone,
+    ) -> Resolved {
+        validate_target(target).map_err(Terminate::get_expression_error)?;
+
+        self.with_context(target, program, *timezone, |ctx| {
+            program.resolve_statement(index, ctx)
+        })
+    }
+
+    /// Given the provided [`Target`], resolve the provided [`Program`] to
+    /// completion, collecting the runtime errors of failed top-level statements
+    /// instead of terminating.
+    ///
+    /// If any statement failed, the errors are inserted into the target at
+    /// `errors_path` as an array of objects with the error `message` and the
+    /// `start` and `end` of its span in the source.
+    ///
+    /// See [`Program::resolve_collecting_errors`].
+    pub fn resolve_collecting_errors(
+        &mut self,
+        target: &mut dyn Target,
+        program: &Program,
+        timezone: &TimeZone,
+        errors_path: &OwnedTargetPath,
+    ) -> RuntimeResult {
+        validate_target(target)?;
+
+        let mut errors = Vec::new();
+        let result = self.with_context(target, program, *timezone, |ctx| {
+            program.resolve_collecting_errors(ctx, &mut errors)
+        });
+
+        if !errors.is_empty() {
+            let errors = errors.into_iter().map(Value::from).collect::<Vec<_>>();
+            target
+                .target_insert(errors_path, Value::Array(errors))
+                .map_err(|err| {
+                    Terminate::Error(format!("error inserting collected errors: {err}").into())
+                })?;
+        }
+
+        into_runtime_result(result)
+    }
+
+    /// Runs `run` with the context of the program, which enforces its
+    /// [`WriteLimits`](super::WriteLimits) on the target.
+    fn with_context<T>(
+        &mut self,
+        target: &mut dyn Target,
+        program: &Program,
+        timezone: TimeZone,
+        run: impl FnOnce(&mut Context) -> T,
+    ) -> T {
+        let limits = program.write_limits();
+        let mut limited_target;
+        let (target, raise_insert_errors): (&mut dyn Target, _) = if limits.limits_insertions() {
+            limited_target = LimitedTarget::new(target, program);
+            // The limited target already ignores the errors the program doesn't raise.
+            (&mut limited_target, true)
+        } else {
+            (target, program.raise_target_insert_errors())
+        };
+
+        let mut ctx = Context::new(target, &mut self.state, &timezone);
+        ctx.set_max_array_padding(limits.max_array_padding);
+        ctx.set_raise_insert_errors(raise_insert_errors);
+        if let Some(observer) = self.observer.as_deref_mut() {
+            ctx.set_observer(observer);
+        }
+
+        run(&mut ctx)
+    }
+}
+
+/// Validate that the path is a value.
+fn validate_target(target: &dyn Target) -> Result<(), Terminate> {
+    match target.target_get(&OwnedTargetPath::event_root()) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(Terminate::Error(
+            "expected target object, got nothing".to_owned().into(),
+        )),
+        Err(err) => Err(Terminate::Error(
+            format!("error querying target object: {err}").into(),
+        )),
+    }
+}
+
+fn into_runtime_result(result: Resolved) -> RuntimeResult {
+    match result {
+        Ok(value) | Err(ExpressionError::Return { value, .. }) => Ok(value),
+        Err(
+            err @ (ExpressionError::Abort { .. }
+            | ExpressionError::Break { .. }
+            | ExpressionError::Fallible { .. }
+            | ExpressionError::Missing { .. }),
+        ) => Err(Terminate::Abort(err)),
+        Err(err @ ExpressionError::Error { .. }) => Err(Terminate::Error(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile, TargetValue};
+    use crate::owned_value_path;
+    use crate::value;
+    use crate::value::Secrets;
+
+    fn resolve_collecting_errors(source: &str) -> (RuntimeResult, Value) {
+        let program = compile(source, &crate::stdlib::all())
+            .expect("compiles")
+            .program;
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
         };
 
-        let mut ctx = Context::new(target, &mut self.state, timezone);
+        let result = Runtime::default().resolve_collecting_errors(
+            &mut target,
+            &program,
+            &TimeZone::default(),
+            &OwnedTargetPath::metadata(owned_value_path!("errors")),
+        );
+        (result, target.metadata)
+    }
+
+    #[test]
+    fn collects_errors_and_continues() {
+        let (result, metadata) =
+            resolve_collecting_errors(r#".a = 1; .b = parse_json!("{"); .c = 3"#);
+
+        assert_eq!(result, Ok(value!(3)));
+        assert_eq!(
+            metadata,
+            value!({
+                "errors": [{
+                    "message": r#"function call error for "parse_json" at (13:29): unable to parse json: EOF while parsing an object at line 1 column 1"#,
+                    "start": 13,
+                    "end": 29,
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn no_errors() {
+        let (result, metadata) = resolve_collecting_errors(".a = 1");
+
+        assert_eq!(result, Ok(value!(1)));
+        assert_eq!(metadata, value!({}));
+    }
+
+    #[test]
+    fn abort_terminates() {
+        let (result, metadata) = resolve_collecting_errors(r#"parse_json!("{"); abort"#);
+
+        assert!(matches!(result, Err(Terminate::Abort(_))));
+        assert_eq!(
+            metadata
+                .as_object()
+                .map(|metadata| metadata.contains_key("errors")),
+            Some(true)
+        );
+    }
+
+    fn resolve(source: &str) -> (RuntimeResult, Value) {
+        let program = compile(source, &crate::stdlib::all())
+            .expect("compiles")
+            .program;
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+
+        let result = Runtime::default().resolve(&mut target, &program, &TimeZone::default());
+        (result, target.value)
+    }
+
+    #[test]
+    fn on_error_handles_runtime_errors() {
+        let (result, target) = resolve(
+            r#".a = 1; .b = parse_json!("{"); .c = 3; on_error { .error = err; .status = "degraded" }"#,
+        );
+
+        assert_eq!(result, Ok(value!("degraded")));
+        assert_eq!(
+            target,
+            value!({
+                "a": 1,
+                "error": r#"function call error for "parse_json" at (13:29): unable to parse json: EOF while parsing an object at line 1 column 1"#,
+                "status": "degraded",
+            })
+        );
+    }
 
-        match program.resolve(&mut ctx) {
-            Ok(value) | Err(ExpressionError::Return { value, .. }) => Ok(value),
-            Err(
-                err @ (ExpressionError::Abort { .. }
-                | ExpressionError::Fallible { .. }
-                | ExpressionError::Missing { .. }),
-            ) => Err(Terminate::Abort(err)),
-            Err(err @ ExpressionError::Error { .. }) => Err(Terminate::Error(err)),
+    #[test]
+    fn on_error_not_run_without_errors() {
+        let (result, target) = resolve(r#"on_error { .status = "degraded" }; .a = 1"#);
+
+        assert_eq!(result, Ok(value!(1)));
+        assert_eq!(target, value!({ "a": 1 }));
+    }
+
+    #[test]
+    fn on_error_not_run_on_abort() {
+        let (result, target) = resolve(r#".a = 1; abort; on_error { .status = "degraded" }"#);
+
+        assert!(matches!(result, Err(Terminate::Abort(_))));
+        assert_eq!(target, value!({ "a": 1 }));
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl RuntimeObserver for Recorder {
+        fn on_assignment(&mut self, target: &str, old: Option<&Value>, new: &Value) {
+            let old = old.map_or_else(|| "none".to_owned(), ToString::to_string);
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{target}: {old} -> {new}"));
+        }
+
+        fn on_function_enter(&mut self, function: &str, _span: crate::diagnostic::Span) {
+            self.0.lock().unwrap().push(format!("enter {function}"));
+        }
+
+        fn on_function_exit(
+            &mut self,
+            function: &str,
+            _span: crate::diagnostic::Span,
+            result: &Resolved,
+        ) {
+            let result = result.as_ref().map(ToString::to_string).unwrap_or_default();
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("exit {function}: {result}"));
+        }
+
+        fn on_branch(&mut self, condition: &str, taken: bool) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("if {condition}: {taken}"));
         }
     }
+
+    #[test]
+    fn observer_is_notified() {
+        let program = compile(
+            r#".a = 1; if .a == 1 { .a = upcase("x") } else { .b = 2 }"#,
+            &crate::stdlib::all(),
+        )
+        .expect("compiles")
+        .program;
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        let recorder = Recorder::default();
+        let mut runtime = Runtime::default();
+        runtime.set_observer(recorder.clone());
+
+        runtime
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap();
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                ".a: none -> 1",
+                "if .a == 1: true",
+                "enter upcase",
+                r#"exit upcase: "X""#,
+                r#".a: 1 -> "X""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_statement_by_statement() {
+        let program = compile(".a = 1; b = .a + 1; return b", &crate::stdlib::all())
+            .expect("compiles")
+            .program;
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        let mut runtime = Runtime::default();
+        let timezone = TimeZone::default();
+
+        assert_eq!(program.statement_count(), 3);
+        assert_eq!(
+            runtime.resolve_statement(&mut target, &program, 0, &timezone),
+            Ok(value!(1))
+        );
+        assert_eq!(target.value, value!({ "a": 1 }));
+
+        assert_eq!(
+            runtime.resolve_statement(&mut target, &program, 1, &timezone),
+            Ok(value!(2))
+        );
+        assert_eq!(runtime.state().variables().count(), 1);
+
+        assert!(matches!(
+            runtime.resolve_statement(&mut target, &program, 2, &timezone),
+            Err(ExpressionError::Return { .. })
+        ));
+    }
 }
@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::path::OwnedTargetPath;
+use crate::value::{ObjectMap, SecretError, Secrets, Value};
+
+use super::{SecretTarget, Target, TargetValue};
+
+/// A [`Target`] over a host type implementing [`Serialize`] and
+/// [`DeserializeOwned`], such as an event struct, which doesn't need a manual
+/// [`Target`] implementation.
+///
+/// The event is converted into a [`Value`] once when the target is created,
+/// and back with [`SerdeTarget::into_inner`], so that the paths read and
+/// written by a program don't convert the event on every access. Fields the
+/// program adds that the type doesn't declare are dropped on the way back,
+/// unless the type collects them, such as with `#[serde(flatten)]`.
+pub struct SerdeTarget<T> {
+    target: TargetValue,
+    event: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for SerdeTarget<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerdeTarget")
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> SerdeTarget<T> {
+    /// Creates a target over the serialized event, with empty metadata and
+    /// secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event can't be serialized.
+    pub fn new(event: &T) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            target: TargetValue {
+                value: serde_json::to_value(event)?.into(),
+                metadata: Value::Object(ObjectMap::new()),
+                secrets: Secrets::new(),
+            },
+            event: PhantomData,
+        })
+    }
+
+    /// Sets the metadata of the event.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: Value) -> Self {
+        self.target.metadata = metadata;
+        self
+    }
+
+    /// Sets the secrets of the event.
+    #[must_use]
+    pub fn with_secrets(mut self, secrets: Secrets) -> Self {
+        self.target.secrets = secrets;
+        self
+    }
+
+    /// Returns the event, as modified by the programs run over the target.
+    #[must_use]
+    pub fn value(&self) -> &Value {
+        &self.target.value
+    }
+
+    /// Converts the target back into the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value no longer deserializes into the event,
+    /// such as when a program assigned a field a value of another type.
+    pub fn into_inner(self) -> Result<T, serde_json::Error> {
+        self.into_parts().map(|(event, _, _)| event)
+    }
+
+    /// Converts the target back into the event, its metadata and its secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value no longer deserializes into the event.
+    pub fn into_parts(self) -> Result<(T, Value, Secrets), serde_json::Error> {
+        let TargetValue {
+            value,
+            metadata,
+            secrets,
+        } = self.target;
+        let event = serde_json::from_value(serde_json::to_value(value)?)?;
+
+        Ok((event, metadata, secrets))
+    }
+}
+
+impl<T> Target for SerdeTarget<T> {
+    fn target_insert(&mut self, path: &OwnedTargetPath, value: Value) -> Result<(), String> {
+        self.target.target_insert(path, value)
+    }
+
+    fn target_get(&self, path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        self.target.target_get(path)
+    }
+
+    fn target_get_mut(&mut self, path: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+        self.target.target_get_mut(path)
+    }
+
+    fn target_remove(
+        &mut self,
+        path: &OwnedTargetPath,
+        compact: bool,
+    ) -> Result<Option<Value>, String> {
+        self.target.target_remove(path, compact)
+    }
+}
+
+impl<T> SecretTarget for SerdeTarget<T> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.target.get_secret(key)
+    }
+
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        self.target.read_secret(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.target.insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.target.remove_secret(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::compiler::{compile, runtime::Runtime, TimeZone};
+    use crate::{stdlib, value};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        message: String,
+        host: Host,
+        tags: Vec<String>,
+        #[serde(flatten)]
+        extra: BTreeMap<String, serde_json::Value>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Host {
+        name: String,
+        port: u16,
+    }
+
+    fn run(source: &str, target: &mut SerdeTarget<Event>) -> Result<Value, String> {
+        let program = compile(source, &stdlib::all()).unwrap().program;
+        Runtime::default()
+            .resolve(target, &program, &TimeZone::default())
+            .map_err(|err| err.to_string())
+    }
+
+    fn event() -> Event {
+        Event {
+            message: "hello".to_owned(),
+            host: Host {
+                name: "web-1".to_owned(),
+                port: 80,
+            },
+            tags: vec!["a".to_owned()],
+            extra: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn maps_paths_to_fields() {
+        let mut target = SerdeTarget::new(&event())
+            .unwrap()
+            .with_metadata(value!({"source": "test"}));
+
+        let result = run(
+            r#"
+                .message = upcase!(.message)
+                .host.port = int!(.host.port) + 1
+                .tags = push!(.tags, %source)
+                .region = "eu"
+                .host.name
+            "#,
+            &mut target,
+        );
+        assert_eq!(result, Ok(value!("web-1")));
+
+        let (event, metadata, _) = target.into_parts().unwrap();
+        assert_eq!(
+            event,
+            Event {
+                message: "HELLO".to_owned(),
+                host: Host {
+                    name: "web-1".to_owned(),
+                    port: 81,
+                },
+                tags: vec!["a".to_owned(), "test".to_owned()],
+                extra: BTreeMap::from([("region".to_owned(), serde_json::json!("eu"))]),
+            }
+        );
+        assert_eq!(metadata, value!({"source": "test"}));
+    }
+
+    #[test]
+    fn rejects_mistyped_fields() {
+        let mut target = SerdeTarget::new(&event()).unwrap();
+        run(".host.port = -1", &mut target).unwrap();
+
+        assert!(target.into_inner().is_err());
+    }
+}
@@ -32,7 +32,7 @@ impl From<&TypeState> for TypeState {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TypeState {
     pub local: LocalEnv,
     pub external: ExternalEnv,
@@ -51,6 +51,23 @@ impl TypeState {
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct LocalEnv {
     pub(crate) bindings: HashMap<Ident, Details>,
+
+    /// The "err" variables of infallible assignments (`ok, err = ...`), for
+    /// as long as neither of the assigned variables changes.
+    error_bindings: HashMap<Ident, ErrorBinding>,
+}
+
+/// Ties the "err" variable of an infallible assignment to its "ok" variable.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ErrorBinding {
+    /// The "ok" variable, if it is a local variable.
+    pub(crate) ok: Option<Ident>,
+
+    /// The "ok" variable when the expression succeeded.
+    pub(crate) success: Details,
+
+    /// The "ok" variable when the expression failed.
+    pub(crate) failure: Details,
 }
 
 impl LocalEnv {
@@ -63,13 +80,32 @@ impl LocalEnv {
     }
 
     pub(crate) fn insert_variable(&mut self, ident: Ident, details: Details) {
+        self.remove_error_bindings(&ident);
         self.bindings.insert(ident, details);
     }
 
     pub(crate) fn remove_variable(&mut self, ident: &Ident) -> Option<Details> {
+        self.remove_error_bindings(ident);
         self.bindings.remove(ident)
     }
 
+    pub(crate) fn error_binding(&self, err: &Ident) -> Option<&ErrorBinding> {
+        self.error_bindings.get(err)
+    }
+
+    /// Records that `err` holds the error of an infallible assignment. This
+    /// must be called _after_ both of the assigned variables are inserted.
+    pub(crate) fn insert_error_binding(&mut self, err: Ident, binding: ErrorBinding) {
+        self.error_bindings.insert(err, binding);
+    }
+
+    /// Changing either variable of an infallible assignment means the "err"
+    /// variable no longer says anything about the "ok" variable.
+    fn remove_error_bindings(&mut self, ident: &Ident) {
+        self.error_bindings
+            .retain(|err, binding| err != ident && binding.ok.as_ref() != Some(ident));
+    }
+
     /// Any state the child scope modified that was part of the parent is copied to the parent scope
     pub(crate) fn apply_child_scope(mut self, child: Self) -> Self {
         for (ident, child_details) in child.bindings {
@@ -78,6 +114,18 @@ impl LocalEnv {
             }
         }
 
+        self.error_bindings = child
+            .error_bindings
+            .into_iter()
+            .filter(|(err, binding)| {
+                self.bindings.contains_key(err)
+                    && binding
+                        .ok
+                        .as_ref()
+                        .map_or(true, |ok| self.bindings.contains_key(ok))
+            })
+            .collect();
+
         self
     }
 
@@ -85,6 +133,9 @@ impl LocalEnv {
     /// where different `LocalEnv`'s can be created, and the result is decided at runtime.
     /// The compile-time type must be the union of the options.
     pub(crate) fn merge(mut self, other: Self) -> Self {
+        self.error_bindings
+            .retain(|err, binding| other.error_bindings.get(err) == Some(binding));
+
         for (ident, other_details) in other.bindings {
             if let Some(self_details) = self.bindings.get_mut(&ident) {
                 *self_details = self_details.clone().merge(other_details);
@@ -97,7 +148,7 @@ impl LocalEnv {
 }
 
 /// A lexical scope within the program.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExternalEnv {
     /// The external target of the program.
     target: Details,
@@ -166,7 +217,7 @@ impl ExternalEnv {
 }
 
 /// The state used at runtime to track changes as they happen.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct RuntimeState {
     /// The [`Value`] stored in each variable.
     variables: HashMap<Ident, Value>,
@@ -191,6 +242,11 @@ impl RuntimeState {
         self.variables.get_mut(ident)
     }
 
+    /// The variables that are set, in no particular order.
+    pub fn variables(&self) -> impl Iterator<Item = (&Ident, &Value)> {
+        self.variables.iter()
+    }
+
     pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) {
         self.variables.insert(ident, value);
     }
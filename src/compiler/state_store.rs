@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Counters, sums and sets kept across the runs of a program, for functions
+/// such as `throttle` that need to remember previous events. The store is
+/// provided by the host environment, see [`CompileConfig::set_state_store`].
+///
+/// Every entry expires after the time to live it was created with, after
+/// which it starts over empty. Each key holds a single kind of entry.
+///
+/// [`CompileConfig::set_state_store`]: super::CompileConfig::set_state_store
+pub trait StateStore: Send + Sync + fmt::Debug {
+    /// Returns the value of the counter, or zero if it doesn't exist or has
+    /// expired.
+    fn get(&self, key: &str) -> i64;
+
+    /// Increments the counter and returns its new value. A counter that
+    /// doesn't exist or has expired is created with a value of one, and
+    /// expires after `ttl`.
+    fn incr(&self, key: &str, ttl: Duration) -> i64;
+
+    /// Adds `amount` to the sum and returns its new value. A sum that doesn't
+    /// exist or has expired is created with a value of `amount`, and expires
+    /// after `ttl`.
+    fn add(&self, key: &str, amount: f64, ttl: Duration) -> f64;
+
+    /// Inserts `member` in the set and returns the number of members of the
+    /// set. A set that doesn't exist or has expired is created with `member`
+    /// as its only member, and expires after `ttl`.
+    fn insert(&self, key: &str, member: &str, ttl: Duration) -> usize;
+}
+
+/// A [`StateStore`] keeping its entries in memory, for a single process.
+///
+/// Expired entries are removed whenever the number of entries has doubled
+/// since they were last removed, so the store only grows with the number of
+/// live keys. Clones of the store share the same entries.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStateStore {
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    entries: HashMap<String, Entry>,
+
+    /// The number of entries at which the expired ones are removed.
+    purge_at: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    state: State,
+    expires_at: Instant,
+}
+
+#[derive(Debug)]
+enum State {
+    Counter(i64),
+    Sum(f64),
+    Set(HashSet<String>),
+}
+
+impl Entries {
+    /// Entries never expire later than this, however long their time to
+    /// live, so their expiration time can't overflow.
+    const MAX_TTL: Duration = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+
+    /// The fewest entries at which the expired ones are removed.
+    const MIN_PURGE_AT: usize = 1024;
+
+    fn purge_expired(&mut self, now: Instant) {
+        self.entries.retain(|_, entry| entry.expires_at > now);
+        self.purge_at = (self.entries.len() * 2).max(Self::MIN_PURGE_AT);
+    }
+}
+
+impl MemoryStateStore {
+    /// Removes the expired entries right away, instead of waiting for the
+    /// store to grow.
+    pub fn purge_expired(&self) {
+        self.entries
+            .lock()
+            .expect("lock poisoned")
+            .purge_expired(Instant::now());
+    }
+
+    /// Applies `update` to the state of the entry, after starting the entry
+    /// over if it doesn't exist, has expired or holds another kind of state.
+    fn update<T>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        empty: State,
+        update: impl FnOnce(&mut State) -> Option<T>,
+    ) -> T {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("lock poisoned");
+        if entries.entries.len() >= entries.purge_at && !entries.entries.contains_key(key) {
+            entries.purge_expired(now);
+        }
+
+        let entry = entries.entries.entry(key.to_owned()).or_insert(Entry {
+            state: State::Counter(0),
+            expires_at: now,
+        });
+
+        if entry.expires_at <= now
+            || std::mem::discriminant(&entry.state) != std::mem::discriminant(&empty)
+        {
+            entry.state = empty;
+            entry.expires_at = now + ttl.min(Entries::MAX_TTL);
+        }
+
+        update(&mut entry.state).expect("state of the same kind")
+    }
+}
+
+impl StateStore for MemoryStateStore {
+    fn get(&self, key: &str) -> i64 {
+        match self.entries.lock().expect("lock poisoned").entries.get(key) {
+            Some(Entry {
+                state: State::Counter(value),
+                expires_at,
+            }) if *expires_at > Instant::now() => *value,
+            _ => 0,
+        }
+    }
+
+    fn incr(&self, key: &str, ttl: Duration) -> i64 {
+        self.update(key, ttl, State::Counter(0), |state| match state {
+            State::Counter(value) => {
+                *value = value.saturating_add(1);
+                Some(*value)
+            }
+            _ => None,
+        })
+    }
+
+    fn add(&self, key: &str, amount: f64, ttl: Duration) -> f64 {
+        self.update(key, ttl, State::Sum(0.0), |state| match state {
+            State::Sum(sum) => {
+                *sum += amount;
+                Some(*sum)
+            }
+            _ => None,
+        })
+    }
+
+    fn insert(&self, key: &str, member: &str, ttl: Duration) -> usize {
+        self.update(key, ttl, State::Set(HashSet::new()), |state| match state {
+            State::Set(members) => {
+                if !members.contains(member) {
+                    members.insert(member.to_owned());
+                }
+                Some(members.len())
+            }
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_until_expired() {
+        let store = MemoryStateStore::default();
+        let minute = Duration::from_secs(60);
+
+        assert_eq!(store.get("a"), 0);
+        assert_eq!(store.incr("a", minute), 1);
+        assert_eq!(store.incr("a", minute), 2);
+        assert_eq!(store.incr("b", minute), 1);
+        assert_eq!(store.get("a"), 2);
+
+        assert_eq!(store.incr("c", Duration::ZERO), 1);
+        assert_eq!(store.get("c"), 0);
+        assert_eq!(store.incr("c", Duration::ZERO), 1);
+
+        store.purge_expired();
+        assert_eq!(store.entries.lock().unwrap().entries.len(), 2);
+
+        // Clones share their counters.
+        assert_eq!(store.clone().incr("a", minute), 3);
+    }
+
+    #[test]
+    fn aggregates_sums_and_sets() {
+        let store = MemoryStateStore::default();
+        let minute = Duration::from_secs(60);
+
+        assert!((store.add("sum", 1.5, minute) - 1.5).abs() < f64::EPSILON);
+        assert!((store.add("sum", 2.0, minute) - 3.5).abs() < f64::EPSILON);
+
+        assert_eq!(store.insert("set", "a", minute), 1);
+        assert_eq!(store.insert("set", "b", minute), 2);
+        assert_eq!(store.insert("set", "a", minute), 2);
+        assert_eq!(store.insert("expired", "a", Duration::ZERO), 1);
+        assert_eq!(store.insert("expired", "b", Duration::ZERO), 1);
+
+        // An entry of another kind starts over.
+        assert_eq!(store.incr("set", minute), 1);
+        assert_eq!(store.insert("set", "a", minute), 1);
+    }
+
+    #[test]
+    fn purges_expired_entries_as_it_grows() {
+        let store = MemoryStateStore::default();
+
+        for key in 0..10 * Entries::MIN_PURGE_AT {
+            store.incr(&key.to_string(), Duration::ZERO);
+        }
+        assert!(store.entries.lock().unwrap().entries.len() <= Entries::MIN_PURGE_AT);
+
+        assert_eq!(store.incr("a", Duration::MAX), 1);
+        assert_eq!(store.incr("a", Duration::MAX), 2);
+    }
+}
@@ -1,8 +1,9 @@
+use std::borrow::Cow;
 use std::convert::AsRef;
 
 use crate::path::OwnedTargetPath;
 use crate::path::PathPrefix;
-use crate::value::{Secrets, Value};
+use crate::value::{SecretError, Secrets, Value};
 
 /// Any target object you want to remap using VRL has to implement this trait.
 pub trait Target: std::fmt::Debug + SecretTarget {
@@ -59,6 +60,22 @@ pub trait Target: std::fmt::Debug + SecretTarget {
 pub trait SecretTarget {
     fn get_secret(&self, key: &str) -> Option<&str>;
 
+    /// Reads the value of a secret, which doesn't need to be UTF-8, unsealing
+    /// it if needed.
+    ///
+    /// Functions reading secrets should prefer this method, which fails for
+    /// secrets that can't be read instead of treating them as missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret has expired, or if it is sealed and
+    /// can't be unsealed.
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        Ok(self
+            .get_secret(key)
+            .map(|value| Cow::Borrowed(value.as_bytes())))
+    }
+
     fn insert_secret(&mut self, key: &str, value: &str);
 
     fn remove_secret(&mut self, key: &str);
@@ -117,6 +134,10 @@ impl SecretTarget for TargetValueRef<'_> {
         self.secrets.get_secret(key)
     }
 
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        self.secrets.read(key)
+    }
+
     fn insert_secret(&mut self, key: &str, value: &str) {
         self.secrets.insert_secret(key, value);
     }
@@ -179,6 +200,10 @@ impl SecretTarget for TargetValue {
         self.secrets.get_secret(key)
     }
 
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        self.secrets.read(key)
+    }
+
     fn insert_secret(&mut self, key: &str, value: &str) {
         self.secrets.insert_secret(key, value);
     }
@@ -193,6 +218,10 @@ impl SecretTarget for Secrets {
         self.get(key).map(AsRef::as_ref)
     }
 
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        self.read(key)
+    }
+
     fn insert_secret(&mut self, key: &str, value: &str) {
         self.insert(key, value);
     }
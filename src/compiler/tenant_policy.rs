@@ -0,0 +1,263 @@
+use std::collections::BTreeSet;
+
+use crate::path::OwnedTargetPath;
+
+/// Functions that read secrets.
+pub(crate) const SECRET_READ_FUNCTIONS: &[&str] = &["get_secret"];
+
+/// Functions that modify secrets.
+pub(crate) const SECRET_WRITE_FUNCTIONS: &[&str] = &["set_secret", "remove_secret"];
+
+/// Functions that read the process environment.
+const ENV_FUNCTIONS: &[&str] = &["get_env_var", "env_vars"];
+
+/// The level of access a program has to the secrets of its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SecretAccess {
+    /// Secret functions can't be called.
+    None,
+    /// Secrets can be read, but not modified.
+    ReadOnly,
+    /// Secrets can be read and modified.
+    #[default]
+    ReadWrite,
+}
+
+/// Limits on the size of a compiled program. A limit of `None` means unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceLimits {
+    /// The maximum length of the program source, in bytes.
+    pub max_source_length: Option<usize>,
+
+    /// The maximum number of function calls in the program.
+    pub max_function_calls: Option<usize>,
+}
+
+/// Isolation settings for programs compiled on behalf of a tenant.
+///
+/// A policy bundles the function allow- and denylists, resource limits,
+/// read-only paths and environment and secret access rules into one value that
+/// can be built once and attached to the [`CompileConfig`] of every program of
+/// that tenant, using [`CompileConfig::set_tenant_policy`].
+///
+/// The default policy doesn't restrict anything.
+///
+/// [`CompileConfig`]: super::CompileConfig
+/// [`CompileConfig::set_tenant_policy`]: super::CompileConfig::set_tenant_policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantPolicy {
+    allowed_functions: Option<BTreeSet<String>>,
+    denied_functions: BTreeSet<String>,
+    read_only_paths: BTreeSet<(OwnedTargetPath, bool)>,
+    env_access: bool,
+    secret_access: SecretAccess,
+    limits: ResourceLimits,
+}
+
+impl Default for TenantPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_functions: None,
+            denied_functions: BTreeSet::new(),
+            read_only_paths: BTreeSet::new(),
+            env_access: true,
+            secret_access: SecretAccess::default(),
+            limits: ResourceLimits::default(),
+        }
+    }
+}
+
+impl TenantPolicy {
+    /// Only allows calls to the given functions. Other restrictions of the
+    /// policy still apply to the allowed functions.
+    #[must_use]
+    pub fn with_allowed_functions<I, S>(mut self, functions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_functions = Some(functions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Disallows calls to the given functions.
+    #[must_use]
+    pub fn with_denied_functions<I, S>(mut self, functions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.denied_functions
+            .extend(functions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Marks a path as read only, see [`CompileConfig::set_read_only_path`].
+    ///
+    /// [`CompileConfig::set_read_only_path`]: super::CompileConfig::set_read_only_path
+    #[must_use]
+    pub fn with_read_only_path(mut self, path: OwnedTargetPath, recursive: bool) -> Self {
+        self.read_only_paths.insert((path, recursive));
+        self
+    }
+
+    /// Sets whether programs can read environment variables.
+    #[must_use]
+    pub fn with_env_access(mut self, allowed: bool) -> Self {
+        self.env_access = allowed;
+        self
+    }
+
+    /// Sets the level of access programs have to secrets.
+    #[must_use]
+    pub fn with_secret_access(mut self, access: SecretAccess) -> Self {
+        self.secret_access = access;
+        self
+    }
+
+    /// Sets the resource limits of compiled programs.
+    #[must_use]
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    #[must_use]
+    pub fn limits(&self) -> ResourceLimits {
+        self.limits
+    }
+
+    pub(crate) fn read_only_paths(&self) -> impl Iterator<Item = &(OwnedTargetPath, bool)> {
+        self.read_only_paths.iter()
+    }
+
+    /// Checks whether a call to `function` is allowed, returning the reason if
+    /// it isn't.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the rule that disallows the call.
+    pub fn check_function(&self, function: &str) -> Result<(), &'static str> {
+        if self.denied_functions.contains(function) {
+            return Err("function is denied by the tenant policy");
+        }
+        if self
+            .allowed_functions
+            .as_ref()
+            .is_some_and(|allowed| !allowed.contains(function))
+        {
+            return Err("function is not in the tenant's allowed functions");
+        }
+        if !self.env_access && ENV_FUNCTIONS.contains(&function) {
+            return Err("the tenant policy doesn't allow environment variable access");
+        }
+        match self.secret_access {
+            SecretAccess::None
+                if SECRET_READ_FUNCTIONS.contains(&function)
+                    || SECRET_WRITE_FUNCTIONS.contains(&function) =>
+            {
+                Err("the tenant policy doesn't allow secret access")
+            }
+            SecretAccess::ReadOnly if SECRET_WRITE_FUNCTIONS.contains(&function) => {
+                Err("the tenant policy only allows reading secrets")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile_with_external, state::ExternalEnv, CompileConfig};
+
+    fn compile_errors(source: &str, policy: TenantPolicy) -> Vec<(usize, String)> {
+        let mut config = CompileConfig::default();
+        config.set_tenant_policy(policy);
+
+        match compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &ExternalEnv::default(),
+            config,
+        ) {
+            Ok(_) => vec![],
+            Err(diagnostics) => diagnostics
+                .errors()
+                .into_iter()
+                .map(|diagnostic| (diagnostic.code, diagnostic.message().to_owned()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn default_allows_everything() {
+        let policy = TenantPolicy::default();
+        for function in ["upcase", "get_env_var", "get_secret", "set_secret"] {
+            assert_eq!(policy.check_function(function), Ok(()));
+        }
+    }
+
+    #[test]
+    fn allow_and_deny_lists() {
+        let policy = TenantPolicy::default()
+            .with_allowed_functions(["upcase", "downcase"])
+            .with_denied_functions(["downcase"]);
+
+        assert_eq!(policy.check_function("upcase"), Ok(()));
+        assert!(policy.check_function("downcase").is_err());
+        assert!(policy.check_function("parse_json").is_err());
+    }
+
+    #[test]
+    fn env_and_secret_access() {
+        let policy = TenantPolicy::default()
+            .with_env_access(false)
+            .with_secret_access(SecretAccess::ReadOnly);
+
+        assert!(policy.check_function("get_env_var").is_err());
+        assert_eq!(policy.check_function("get_secret"), Ok(()));
+        assert!(policy.check_function("set_secret").is_err());
+        assert!(policy.check_function("remove_secret").is_err());
+
+        let policy = policy.with_secret_access(SecretAccess::None);
+        assert!(policy.check_function("get_secret").is_err());
+    }
+
+    #[test]
+    fn compile_rejects_disallowed_functions() {
+        let policy = TenantPolicy::default().with_allowed_functions(["upcase"]);
+
+        assert!(compile_errors(r#".a = upcase("a")"#, policy.clone()).is_empty());
+        let errors = compile_errors(r#".a = downcase("a")"#, policy);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, 112);
+    }
+
+    #[test]
+    fn compile_enforces_limits() {
+        let policy = TenantPolicy::default().with_limits(ResourceLimits {
+            max_source_length: None,
+            max_function_calls: Some(1),
+        });
+        let errors = compile_errors(r#".a = upcase(downcase(upcase("a")))"#, policy);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, 113);
+
+        let policy = TenantPolicy::default().with_limits(ResourceLimits {
+            max_source_length: Some(4),
+            max_function_calls: None,
+        });
+        let errors = compile_errors(".a = 1", policy);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].1.contains("exceeds the tenant limit of 4 bytes"));
+    }
+
+    #[test]
+    fn compile_applies_read_only_paths() {
+        let policy =
+            TenantPolicy::default().with_read_only_path(OwnedTargetPath::event_root(), true);
+
+        assert!(!compile_errors(".a = 1", policy).is_empty());
+    }
+}
@@ -14,20 +14,31 @@
 /// - **Detection**: Identifies and reports expressions that do not contribute to assignments,
 ///   affect external events, or influence the outcome of function calls.
 /// - **Ignored Variables**: Variable names prefixed with '_' are ignored.
+/// - **Scopes**: Variables are tracked in a stack of scopes that mirrors the compiler. A variable first
+///   assigned inside a block goes out of scope at the end of it, and closure parameters shadow any
+///   variable of the same name for the duration of the closure.
+/// - **Reassignments**: A value that is overwritten before it is read is reported, unless either
+///   assignment only happens conditionally (in an `if` branch, on the right-hand side of `&&`, `||`
+///   or `??`, or in a closure). A variable assigned from its own value, as in `x = x + 1`, counts
+///   as used, as its value is updated rather than replaced. Literals are not reported when
+///   overwritten, as they are commonly assigned to set the type or a default value of a variable,
+///   and neither are the assignments in the argument of `type_def`, which is never evaluated.
 ///
 /// ## Caveats
-/// - **Closures**: Closure support is minimal. For now, we are only ensuring that there are no false positives.
-/// - **Variable Shadowing**: Variable shadowing is not supported. Unused variables will not be detected in this case.
+/// - **Closures**: Closures are assumed to run any number of times, so a variable that is read in a
+///   closure is never reported as overwritten by an assignment in that same closure. The same goes
+///   for loop blocks. Closure parameters are only reported if they are neither read nor assigned.
 use crate::compiler::codes::WARNING_UNUSED_CODE;
 use crate::compiler::parser::{Ident, Node};
 use crate::diagnostic::{Diagnostic, DiagnosticList, Label, Note, Severity};
 use crate::parser::ast::{
-    Array, Assignment, AssignmentOp, AssignmentTarget, Block, Container, Expr, FunctionCall,
-    IfStatement, Object, Predicate, QueryTarget, Return, RootExpr, Unary,
+    Array, Assignment, AssignmentOp, AssignmentTarget, Block, Break, Container, Expr, FunctionCall,
+    FunctionClosure, IfStatement, Loop, Object, Opcode, Predicate, QueryTarget, Return, RootExpr,
+    Unary,
 };
 use crate::parser::template_string::StringSegment;
 use crate::parser::{Literal, Program, Span};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use tracing::warn;
 
 const SIDE_EFFECT_FUNCTIONS: [&str; 5] =
@@ -47,6 +58,12 @@ pub struct AstVisitor<'a> {
 struct IdentState {
     span: Span,
     pending_usage: bool,
+    /// The conditional branch in which the variable was last assigned.
+    branch: usize,
+    /// Whether the variable was last assigned a literal.
+    literal: bool,
+    /// Whether the variable is a closure parameter.
+    parameter: bool,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -54,7 +71,15 @@ struct VisitorState {
     level: usize,
     expecting_result: HashMap<usize, bool>,
     within_block_expression: HashMap<usize, bool>,
-    ident_to_state: BTreeMap<Ident, IdentState>,
+    /// The variables in scope, innermost scope last.
+    scopes: Vec<BTreeMap<Ident, IdentState>>,
+    /// The conditional branches being visited, innermost last. The root of the
+    /// program is branch `0`.
+    branches: Vec<usize>,
+    branch_count: usize,
+    /// The variables read by each closure, loop block or assigned expression being visited,
+    /// innermost last.
+    reads: Vec<BTreeSet<Ident>>,
     diagnostics: DiagnosticList,
 }
 
@@ -99,28 +124,135 @@ impl VisitorState {
         self.expecting_result.insert(self.level, false);
     }
 
+    fn current_branch(&self) -> usize {
+        self.branches.last().copied().unwrap_or_default()
+    }
+
+    fn enter_branch(&mut self) {
+        self.branch_count += 1;
+        self.branches.push(self.branch_count);
+    }
+
+    /// Once a branch is exited, the assignments made in it are treated as if
+    /// they happened in the enclosing branch. Any later unconditional
+    /// assignment overwrites them, whether or not the branch was taken.
+    fn exit_branch(&mut self) {
+        let branch = self.branches.pop().unwrap_or_default();
+        let parent = self.current_branch();
+        for state in self.scopes.iter_mut().flat_map(BTreeMap::values_mut) {
+            if state.branch == branch {
+                state.branch = parent;
+            }
+        }
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(BTreeMap::new());
+    }
+
+    /// Variables go out of scope at the end of the block they were first
+    /// assigned in, so any that are still pending usage are unused.
+    fn exit_scope(&mut self) {
+        for (ident, state) in self.scopes.pop().unwrap_or_default() {
+            if state.pending_usage {
+                self.append_diagnostic(format!("unused variable `{ident}`"), &state.span);
+            }
+        }
+    }
+
+    fn find_identifier(&mut self, ident: &Ident) -> Option<&mut IdentState> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(ident))
+    }
+
+    fn is_ignored(ident: &Ident) -> bool {
+        ident.is_empty() || ident.starts_with('_')
+    }
+
+    /// Declares a new variable in the innermost scope, shadowing any variable
+    /// with the same name in an outer scope.
+    fn declare_identifier(&mut self, ident: &Ident, span: &Span, literal: bool) {
+        if Self::is_ignored(ident) {
+            return;
+        }
+
+        let state = IdentState {
+            span: *span,
+            pending_usage: true,
+            branch: self.current_branch(),
+            literal,
+            parameter: false,
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(ident.clone(), state);
+        }
+    }
+
+    fn declare_parameter(&mut self, ident: &Ident, span: &Span) {
+        self.declare_identifier(ident, span, false);
+        if let Some(state) = self
+            .scopes
+            .last_mut()
+            .and_then(|scope| scope.get_mut(ident))
+        {
+            state.parameter = true;
+        }
+    }
+
+    /// Assigns a new value to a variable, reporting the previous value if it
+    /// was never read and is certainly overwritten.
+    fn mark_identifier_assigned(&mut self, ident: &Ident, span: &Span, literal: bool) {
+        if Self::is_ignored(ident) {
+            return;
+        }
+
+        let branch = self.current_branch();
+        let Some(state) = self.find_identifier(ident) else {
+            self.declare_identifier(ident, span, literal);
+            return;
+        };
+        // An assigned closure parameter counts as used.
+        if state.parameter {
+            state.pending_usage = false;
+            return;
+        }
+        let overwritten =
+            (state.pending_usage && !state.literal && state.branch == branch).then_some(state.span);
+        *state = IdentState {
+            span: *span,
+            pending_usage: true,
+            branch,
+            literal,
+            parameter: false,
+        };
+
+        if let Some(span) = overwritten {
+            self.append_diagnostic(format!("unused variable `{ident}`"), &span);
+        }
+    }
+
     fn mark_identifier_pending_usage(&mut self, ident: &Ident, span: &Span) {
-        if ident.is_empty() || ident.starts_with('_') {
+        if Self::is_ignored(ident) {
             return;
         }
 
-        self.ident_to_state
-            .entry(ident.clone())
-            .and_modify(|state| {
-                state.pending_usage = true;
-            })
-            .or_insert(IdentState {
-                span: *span,
-                pending_usage: true,
-            });
+        match self.find_identifier(ident) {
+            Some(state) => state.pending_usage = true,
+            None => self.declare_identifier(ident, span, false),
+        }
     }
 
     fn mark_identifier_used(&mut self, ident: &Ident) {
-        if ident.is_empty() || ident.starts_with('_') {
+        if Self::is_ignored(ident) {
             return;
         }
 
-        if let Some(entry) = self.ident_to_state.get_mut(ident) {
+        for reads in &mut self.reads {
+            reads.insert(ident.clone());
+        }
+        if let Some(entry) = self.find_identifier(ident) {
             entry.pending_usage = false;
         } else {
             warn!("unexpected identifier `{}` reported as used", ident);
@@ -152,16 +284,34 @@ impl VisitorState {
             )],
         })
     }
+}
 
-    fn extend_diagnostics_for_unused_variables(&mut self) {
-        for (ident, state) in self.ident_to_state.clone() {
-            if state.pending_usage {
-                self.append_diagnostic(format!("unused variable `{ident}`"), &state.span);
-            }
+/// Keeps the values assigned in the current branch from being reported as
+/// overwritten, if they are read by the closure or loop block being exited.
+fn release_repeated_reads(state: &mut VisitorState) {
+    let reads = state.reads.pop().unwrap_or_default();
+    let branch = state.current_branch();
+    for (ident, ident_state) in state.scopes.iter_mut().flat_map(BTreeMap::iter_mut) {
+        if ident_state.branch == branch && reads.contains(ident) {
+            ident_state.pending_usage = false;
         }
     }
 }
 
+/// Whether the expression is a literal, or an array or object of literals.
+fn is_literal(node: &Node<Expr>) -> bool {
+    match node.inner() {
+        Expr::Literal(_) => true,
+        Expr::Container(container) => match &container.node {
+            Container::Group(group) => is_literal(&group.node.0),
+            Container::Block(_) => false,
+            Container::Array(array) => array.0.iter().all(is_literal),
+            Container::Object(object) => object.0.values().all(is_literal),
+        },
+        _ => false,
+    }
+}
+
 fn scoped_visit(state: &mut VisitorState, f: impl FnOnce(&mut VisitorState)) {
     state.increase_level();
     state.mark_level_as_expecting_result();
@@ -197,14 +347,26 @@ impl AstVisitor<'_> {
             }
             Expr::Op(op) => {
                 self.visit_node(&op.0, state);
+
+                // The right-hand side of these operators isn't always evaluated.
+                let conditional = matches!(op.1.node, Opcode::And | Opcode::Or | Opcode::Err);
+                if conditional {
+                    state.enter_branch();
+                }
                 scoped_visit(state, |state| {
                     self.visit_node(&op.2, state);
                 });
+                if conditional {
+                    state.exit_branch();
+                }
             }
             Expr::Unary(unary) => match &unary.node {
                 Unary::Not(not) => {
                     self.visit_node(&not.1, state);
                 }
+                Unary::BitNot(bit_not) => {
+                    self.visit_node(&bit_not.1, state);
+                }
             },
             Expr::Assignment(assignment) => {
                 self.visit_assignment(assignment, state);
@@ -229,6 +391,8 @@ impl AstVisitor<'_> {
             }
             Expr::Abort(_) => {}
             Expr::Return(r#return) => self.visit_return(r#return, state),
+            Expr::Loop(r#loop) => self.visit_loop(r#loop, state),
+            Expr::Break(r#break) => self.visit_break(r#break, state),
         }
     }
 
@@ -253,6 +417,7 @@ impl AstVisitor<'_> {
             return;
         }
         state.enter_block();
+        state.enter_scope();
 
         for (i, expr) in block_expressions.iter().enumerate() {
             if i == block_expressions.len() - 1 {
@@ -260,6 +425,8 @@ impl AstVisitor<'_> {
             }
             self.visit_node(expr, state);
         }
+
+        state.exit_scope();
     }
 
     fn visit_object(&self, object: &Node<Object>, state: &mut VisitorState) {
@@ -283,14 +450,18 @@ impl AstVisitor<'_> {
             }
         }
 
+        state.enter_branch();
         scoped_visit(state, |state| {
             self.visit_block(&if_statement.if_node, state);
         });
+        state.exit_branch();
 
         if let Some(else_block) = &if_statement.else_node {
+            state.enter_branch();
             scoped_visit(state, |state| {
                 self.visit_block(else_block, state);
             });
+            state.exit_branch();
         }
     }
 
@@ -299,6 +470,22 @@ impl AstVisitor<'_> {
         let level = state.level;
         state.expecting_result.insert(level, true);
 
+        // Visit the assignment right hand side first, it is evaluated before
+        // the targets are assigned.
+        state.reads.push(BTreeSet::new());
+        match &assignment.node {
+            Assignment::Single { expr, .. } => {
+                self.visit_node(expr, state);
+            }
+            Assignment::Infallible { expr, .. } => {
+                self.visit_node(expr, state);
+            }
+        }
+        let reads = state.reads.pop().unwrap_or_default();
+
+        let literal =
+            matches!(&assignment.node, Assignment::Single { expr, .. } if is_literal(expr));
+
         // All targets needs to be used later.
         let (op, targets) = match &assignment.node {
             Assignment::Single { target, op, .. } => (op, vec![target]),
@@ -312,9 +499,15 @@ impl AstVisitor<'_> {
                 }
                 AssignmentTarget::Internal(ident, path) => {
                     if *op == AssignmentOp::Assign && path.is_none() {
-                        state.mark_identifier_pending_usage(ident, &target.span);
+                        state.mark_identifier_assigned(ident, &target.span, literal);
+                        // The value of a variable assigned from its own value is
+                        // updated rather than replaced, as for a counter.
+                        if reads.contains(ident) {
+                            state.mark_identifier_used(ident);
+                        }
                     } else if *op == AssignmentOp::Merge {
-                        // The following example: `x |= {}` falls under shadowing and is not handled.
+                        // The following example: `x |= {}` reads `x`, but the merged result
+                        // isn't required to be used.
                         state.mark_identifier_used(ident);
                     }
                 }
@@ -322,15 +515,6 @@ impl AstVisitor<'_> {
             }
         }
 
-        // Visit the assignment right hand side.
-        match &assignment.node {
-            Assignment::Single { expr, .. } => {
-                self.visit_node(expr, state);
-            }
-            Assignment::Infallible { expr, .. } => {
-                self.visit_node(expr, state);
-            }
-        }
         state.expecting_result.insert(level, false);
         state.decrease_level();
     }
@@ -341,6 +525,12 @@ impl AstVisitor<'_> {
         span: &Span,
         state: &mut VisitorState,
     ) {
+        // `type_def` doesn't evaluate its argument, so the assignments in it
+        // don't overwrite any value.
+        let probe = function_call.ident.0 == "type_def";
+        if probe {
+            state.enter_branch();
+        }
         for argument in &function_call.arguments {
             state.increase_level();
             state.mark_level_as_expecting_result();
@@ -348,6 +538,9 @@ impl AstVisitor<'_> {
             state.mark_level_as_not_expecting_result();
             state.decrease_level();
         }
+        if probe {
+            state.exit_branch();
+        }
 
         // This function call might be part of fallible block.
         if !function_call.abort_on_error && state.is_within_block() {
@@ -356,12 +549,7 @@ impl AstVisitor<'_> {
 
         if !SIDE_EFFECT_FUNCTIONS.contains(&function_call.ident.0.as_str()) {
             if let Some(closure) = &function_call.closure {
-                for variable in &closure.variables {
-                    state.mark_identifier_pending_usage(&variable.node, &variable.span);
-                }
-                state.mark_level_as_expecting_result();
-                self.visit_block(&closure.block, state);
-                state.mark_level_as_not_expecting_result();
+                self.visit_closure(closure, state);
             } else if state.is_unused() {
                 state.append_diagnostic(
                     format!("unused result for function call `{function_call}`"),
@@ -375,6 +563,56 @@ impl AstVisitor<'_> {
         }
     }
 
+    fn visit_closure(&self, closure: &Node<FunctionClosure>, state: &mut VisitorState) {
+        // A closure might run any number of times, including never.
+        state.enter_branch();
+        state.reads.push(BTreeSet::new());
+        state.enter_scope();
+
+        for variable in &closure.variables {
+            state.declare_parameter(&variable.node, &variable.span);
+        }
+        state.mark_level_as_expecting_result();
+        self.visit_block(&closure.block, state);
+        state.mark_level_as_not_expecting_result();
+
+        state.exit_scope();
+
+        // A value assigned in one run of the closure can be read by the next one.
+        release_repeated_reads(state);
+        state.exit_branch();
+    }
+
+    fn visit_loop(&self, r#loop: &Node<Loop>, state: &mut VisitorState) {
+        scoped_visit(state, |state| {
+            self.visit_node(&r#loop.max, state);
+        });
+
+        // A loop block might run any number of times up to its bound, including never.
+        state.enter_branch();
+        state.reads.push(BTreeSet::new());
+        self.visit_block(&r#loop.block, state);
+
+        // A value assigned in one iteration can be read by the next one.
+        release_repeated_reads(state);
+        state.exit_branch();
+    }
+
+    fn visit_break(&self, r#break: &Node<Break>, state: &mut VisitorState) {
+        let Some(condition) = &r#break.condition else {
+            return;
+        };
+
+        scoped_visit(state, |state| match &condition.node {
+            Predicate::One(expr) => self.visit_node(expr, state),
+            Predicate::Many(exprs) => {
+                for expr in exprs {
+                    self.visit_node(expr, state);
+                }
+            }
+        });
+    }
+
     fn visit_return(&self, r#return: &Node<Return>, state: &mut VisitorState) {
         state.increase_level();
         let level = state.level;
@@ -393,6 +631,7 @@ impl AstVisitor<'_> {
     fn check_for_unused_results(&self) -> DiagnosticList {
         let mut unused_warnings = DiagnosticList::default();
         let mut state = VisitorState::default();
+        state.enter_scope();
         let root_expressions = &self.ast.0;
         for (i, root_node) in root_expressions.iter().enumerate() {
             let is_last = i == root_expressions.len() - 1;
@@ -402,6 +641,7 @@ impl AstVisitor<'_> {
             }
             match root_node.inner() {
                 RootExpr::Expr(node) => self.visit_node(node, &mut state),
+                RootExpr::OnError(block) => self.visit_block(block, &mut state),
                 RootExpr::Error(_) => {}
             }
             if is_last {
@@ -409,7 +649,10 @@ impl AstVisitor<'_> {
                 state.mark_level_as_not_expecting_result();
             }
         }
-        state.extend_diagnostics_for_unused_variables();
+        state.exit_scope();
+        state
+            .diagnostics
+            .sort_by_key(|diagnostic| diagnostic.labels.first().map(|label| label.span.start()));
         unused_warnings.extend(state.diagnostics);
         unused_warnings
     }
@@ -617,8 +860,24 @@ mod test {
             for_each({ "a": 1, "b": 2 }) -> |_key, value| { count = count + value };
             count
         "#};
-        // Note that the `value` outside of the closure block is unused but not detected.
-        unused_test(source, vec![]);
+        unused_test(source, vec!["unused variable `value`".to_string()]);
+    }
+
+    #[test]
+    fn loop_reads_previous_iteration() {
+        let source = indoc! {r#"
+            previous = 0
+            current = 1
+            loop max: 10 {
+              next = previous + current
+              previous = current
+              current = next
+              break if current > 20
+              "done"
+            }
+            current
+        "#};
+        unused_test(source, vec![r#"unused literal `"done"`"#.to_string()]);
     }
 
     #[test]
@@ -633,7 +892,6 @@ mod test {
             }
             matched
         "};
-        // Note that the `value` outside of the closure block is unused but not detected.
         unused_test(source, vec![]);
     }
 
@@ -649,16 +907,15 @@ mod test {
     }
 
     #[test]
-    fn unused_shadow_variable_not_detected() {
-        // TODO: Support variable shadowing. A potential solution is to introduce the following type:
-        // type IdentState = HashMap<usize, (bool, Span)>;
+    fn unused_overwritten_variable() {
+        // `x = .a`, `x = .b` and `x = .c` are all overwritten before they are read.
         let source = indoc! {"
-            x = 1
-            x = 2
+            x = .a
+            x = .b
             {
                 x = {
                     x = {
-                        x = 3
+                        x = .c
                         4
                     }
                     x
@@ -666,9 +923,172 @@ mod test {
                 x
             }
         "};
+        let expected_warnings = vec!["unused variable `x`".to_string(); 3];
+        unused_test(source, expected_warnings);
+    }
+
+    #[test]
+    fn overwritten_variable_read_in_between() {
+        let source = indoc! {"
+            x = 1
+            x = x + 1
+            .a = x
+        "};
         unused_test(source, vec![]);
     }
 
+    #[test]
+    fn conditionally_overwritten_variable() {
+        let source = indoc! {"
+            x = 1
+            if random_bool() { x = 2 } else { x = 3 }
+            .a = x
+
+            y = 1
+            .b = random_bool() || { y = 2; true }
+            .c = y
+        "};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn variable_overwritten_after_branch() {
+        let source = indoc! {"
+            x = .a
+            if random_bool() { x = .b }
+            x = .c
+            .d = x
+        "};
+        unused_test(source, vec!["unused variable `x`".to_string()]);
+    }
+
+    #[test]
+    fn overwritten_literals() {
+        let source = indoc! {r#"
+            x = 0
+            x = [.a]
+            .b = x
+
+            y = ["foo", { "bar": null }]
+            y = .c
+            .d = y
+        "#};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn self_referencing_assignment() {
+        let source = indoc! {"
+            x = 1
+            .a = x
+            x = x + 1
+        "};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn assignment_in_type_def() {
+        let source = indoc! {"
+            x = .a
+            .b = type_def({ x = 1; x })
+            .c = x
+        "};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn unused_variable_in_block_scope() {
+        let source = indoc! {"
+            x = 1
+            .a = {
+                y = 2
+                x + 1
+            }
+            .b = x
+        "};
+        unused_test(source, vec!["unused variable `y`".to_string()]);
+    }
+
+    #[test]
+    fn unused_variable_in_if_branch() {
+        let source = indoc! {"
+            if random_bool() {
+                y = 1
+                .a = 2
+            }
+        "};
+        unused_test(source, vec!["unused variable `y`".to_string()]);
+    }
+
+    #[test]
+    fn unused_closure_parameter() {
+        let source = indoc! {"
+            for_each([1, 2]) -> |index, _value| {
+                .a = 1
+            }
+        "};
+        unused_test(source, vec!["unused variable `index`".to_string()]);
+    }
+
+    #[test]
+    fn assigned_closure_parameter() {
+        let source = indoc! {"
+            for_each([1, 2]) -> |index, _value| {
+                index = 0
+            }
+        "};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn closure_parameter_does_not_use_outer_variable() {
+        let source = indoc! {"
+            value = 1
+            .a = value
+            value = 2
+            for_each([1, 2]) -> |_index, value| {
+                .b = value
+            }
+        "};
+        unused_test(source, vec!["unused variable `value`".to_string()]);
+    }
+
+    #[test]
+    fn nested_closures_shadow_parameters() {
+        let source = indoc! {r#"
+            .a = map_values({ "a": 1 }) -> |value| {
+                map_values({ "b": value }) -> |value| {
+                    value
+                }
+            }
+        "#};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn variable_read_by_next_closure_run() {
+        let source = indoc! {"
+            previous = null
+            for_each([1, 2]) -> |_index, value| {
+                .a = previous
+                previous = value
+            }
+        "};
+        unused_test(source, vec![]);
+    }
+
+    #[test]
+    fn variable_overwritten_within_closure_run() {
+        let source = indoc! {"
+            for_each([1, 2]) -> |_index, value| {
+                x = value
+                x = 1
+                .a = x
+            }
+        "};
+        unused_test(source, vec!["unused variable `x`".to_string()]);
+    }
+
     #[test]
     fn undetected_merge_assignment() {
         // `x` is not used after the merging operation. This case is not detected.
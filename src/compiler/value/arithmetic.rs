@@ -54,6 +54,23 @@ pub trait VrlValueArithmetic: Sized {
 
     fn try_merge(self, rhs: Self) -> Result<Self, ValueError>;
 
+    /// Similar to [`std::ops::BitAnd`], but fallible (e.g. `TryBitAnd`).
+    fn try_bit_and(self, rhs: Self) -> Result<Self, ValueError>;
+
+    /// Similar to [`std::ops::BitOr`], but fallible (e.g. `TryBitOr`).
+    fn try_bit_or(self, rhs: Self) -> Result<Self, ValueError>;
+
+    /// Similar to [`std::ops::BitXor`], but fallible (e.g. `TryBitXor`).
+    fn try_bit_xor(self, rhs: Self) -> Result<Self, ValueError>;
+
+    /// Similar to [`std::ops::Shl`], but fallible (e.g. `TryShl`). Bits shifted
+    /// past the most significant bit are discarded.
+    fn try_shl(self, rhs: Self) -> Result<Self, ValueError>;
+
+    /// Similar to [`std::ops::Shr`], but fallible (e.g. `TryShr`). The shift is
+    /// arithmetic, so the sign of the lhs is preserved.
+    fn try_shr(self, rhs: Self) -> Result<Self, ValueError>;
+
     /// Similar to [`std::cmp::Eq`], but does a lossless comparison for integers
     /// and floats.
     fn eq_lossy(&self, rhs: &Self) -> bool;
@@ -322,6 +339,49 @@ impl VrlValueArithmetic for Value {
         Ok(value)
     }
 
+    fn try_bit_and(self, rhs: Self) -> Result<Self, ValueError> {
+        match (&self, &rhs) {
+            (Value::Integer(lhv), Value::Integer(rhv)) => Ok((lhv & rhv).into()),
+            _ => Err(ValueError::BitAnd(self.kind(), rhs.kind())),
+        }
+    }
+
+    fn try_bit_or(self, rhs: Self) -> Result<Self, ValueError> {
+        match (&self, &rhs) {
+            (Value::Integer(lhv), Value::Integer(rhv)) => Ok((lhv | rhv).into()),
+            _ => Err(ValueError::BitOr(self.kind(), rhs.kind())),
+        }
+    }
+
+    fn try_bit_xor(self, rhs: Self) -> Result<Self, ValueError> {
+        match (&self, &rhs) {
+            (Value::Integer(lhv), Value::Integer(rhv)) => Ok((lhv ^ rhv).into()),
+            _ => Err(ValueError::BitXor(self.kind(), rhs.kind())),
+        }
+    }
+
+    fn try_shl(self, rhs: Self) -> Result<Self, ValueError> {
+        match (&self, &rhs) {
+            (Value::Integer(lhv), Value::Integer(rhv)) => u32::try_from(*rhv)
+                .ok()
+                .and_then(|amount| lhv.checked_shl(amount))
+                .map(Into::into)
+                .ok_or(ValueError::ShiftOutOfRange(*rhv)),
+            _ => Err(ValueError::Shift(self.kind(), rhs.kind())),
+        }
+    }
+
+    fn try_shr(self, rhs: Self) -> Result<Self, ValueError> {
+        match (&self, &rhs) {
+            (Value::Integer(lhv), Value::Integer(rhv)) => u32::try_from(*rhv)
+                .ok()
+                .and_then(|amount| lhv.checked_shr(amount))
+                .map(Into::into)
+                .ok_or(ValueError::ShiftOutOfRange(*rhv)),
+            _ => Err(ValueError::Shift(self.kind(), rhs.kind())),
+        }
+    }
+
     /// Similar to [`std::cmp::Eq`], but does a lossless comparison for integers
     /// and floats.
     fn eq_lossy(&self, rhs: &Self) -> bool {
@@ -58,13 +58,28 @@ pub enum ValueError {
 
     #[error("can't convert out of range {0}")]
     OutOfRange(Kind),
+
+    #[error("can't apply a bitwise AND to types {0} and {1}")]
+    BitAnd(Kind, Kind),
+
+    #[error("can't apply a bitwise OR to types {0} and {1}")]
+    BitOr(Kind, Kind),
+
+    #[error("can't apply a bitwise XOR to types {0} and {1}")]
+    BitXor(Kind, Kind),
+
+    #[error("can't shift type {0} by {1}")]
+    Shift(Kind, Kind),
+
+    #[error("can't shift by {0} bits, the shift amount must be between 0 and 63")]
+    ShiftOutOfRange(i64),
 }
 
 impl DiagnosticMessage for ValueError {
     fn code(&self) -> usize {
         use ValueError::{
-            Add, And, Coerce, Div, DivideByZero, Expected, Ge, Gt, Le, Lt, Merge, Mul, NanFloat,
-            Or, Rem, Sub,
+            Add, And, BitAnd, BitOr, BitXor, Coerce, Div, DivideByZero, Expected, Ge, Gt, Le, Lt,
+            Merge, Mul, NanFloat, Or, Rem, Shift, ShiftOutOfRange, Sub,
         };
 
         match self {
@@ -85,6 +100,11 @@ impl DiagnosticMessage for ValueError {
             Le(..) => 314,
             Merge(..) => 315,
             OutOfRange(..) => 316,
+            BitAnd(..) => 317,
+            BitOr(..) => 318,
+            BitXor(..) => 319,
+            Shift(..) => 320,
+            ShiftOutOfRange(..) => 321,
         }
     }
 }
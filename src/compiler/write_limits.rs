@@ -0,0 +1,399 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::path::OwnedTargetPath;
+use crate::value::{SecretError, Value};
+
+use super::{Program, SecretTarget, Target};
+
+/// Limits on the values a program may insert each time it runs. A limit of
+/// `None` means unlimited.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WriteLimits {
+    /// The maximum number of new fields, including array elements, that the
+    /// program may insert.
+    pub max_inserted_fields: Option<usize>,
+
+    /// The maximum nesting depth of the fields that the program may insert,
+    /// where `.foo` has a depth of 1 and `.foo.bar` a depth of 2.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of `null` elements that an assignment may pad an
+    /// array with, when inserting at an index past the end of the array. This
+    /// applies to variables as well as the external [`Target`].
+    pub max_array_padding: Option<usize>,
+}
+
+impl WriteLimits {
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        self.max_inserted_fields.is_none()
+            && self.max_depth.is_none()
+            && self.max_array_padding.is_none()
+    }
+
+    /// Returns `true` if the insertions into the external [`Target`] must go
+    /// through a [`LimitedTarget`]. The array padding is limited by the
+    /// assignments themselves.
+    pub(crate) fn limits_insertions(&self) -> bool {
+        self.max_inserted_fields.is_some() || self.max_depth.is_some()
+    }
+}
+
+/// The runtime error raised when an insertion into the external [`Target`]
+/// exceeds the [`WriteLimits`] of the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteLimitError {
+    TooManyFields {
+        path: OwnedTargetPath,
+        limit: usize,
+    },
+    TooDeep {
+        path: OwnedTargetPath,
+        depth: usize,
+        limit: usize,
+    },
+}
+
+impl fmt::Display for WriteLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyFields { path, limit } => write!(
+                f,
+                "inserting into {} exceeds the limit of {limit} inserted fields",
+                path.to_string_lossless()
+            ),
+            Self::TooDeep { path, depth, limit } => write!(
+                f,
+                "inserting into {} creates fields nested {depth} levels deep, exceeding the limit of {limit}",
+                path.to_string_lossless()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WriteLimitError {}
+
+/// A [`Target`] that enforces the [`WriteLimits`] of a program on the
+/// insertions into the wrapped target.
+///
+/// Errors of the wrapped target are ignored, unless the program raises them.
+#[derive(Debug)]
+pub(crate) struct LimitedTarget<'a> {
+    target: &'a mut dyn Target,
+    limits: WriteLimits,
+    raise_insert_errors: bool,
+    inserted_fields: usize,
+}
+
+impl<'a> LimitedTarget<'a> {
+    pub(crate) fn new(target: &'a mut dyn Target, program: &Program) -> Self {
+        Self {
+            target,
+            limits: program.write_limits(),
+            raise_insert_errors: program.raise_target_insert_errors(),
+            inserted_fields: 0,
+        }
+    }
+
+    fn check_insert(
+        &mut self,
+        path: &OwnedTargetPath,
+        value: &Value,
+    ) -> Result<(), WriteLimitError> {
+        if let Some(limit) = self.limits.max_depth {
+            let depth = path.path.segments.len() + depth(value);
+            if depth > limit {
+                return Err(WriteLimitError::TooDeep {
+                    path: path.clone(),
+                    depth,
+                    limit,
+                });
+            }
+        }
+
+        if let Some(limit) = self.limits.max_inserted_fields {
+            let inserted_fields = self.inserted_fields + self.new_fields(path, value);
+            if inserted_fields > limit {
+                return Err(WriteLimitError::TooManyFields {
+                    path: path.clone(),
+                    limit,
+                });
+            }
+            self.inserted_fields = inserted_fields;
+        }
+
+        Ok(())
+    }
+
+    /// The number of fields that don't exist yet and are created by inserting
+    /// the value at the given path.
+    fn new_fields(&self, path: &OwnedTargetPath, value: &Value) -> usize {
+        let segments = &path.path.segments;
+        let Ok(Some(mut existing)) = self.target.target_get(&OwnedTargetPath::root(path.prefix))
+        else {
+            return segments.len() + count_fields(value);
+        };
+
+        for (depth, segment) in segments.iter().enumerate() {
+            match existing.get(std::slice::from_ref(segment)) {
+                Some(child) => existing = child,
+                // The parents of the path that don't exist yet are created as well.
+                None => return segments.len() - depth + count_fields(value),
+            }
+        }
+
+        count_fields(value).saturating_sub(count_fields(existing))
+    }
+}
+
+/// The number of fields and array elements nested in the value.
+fn count_fields(value: &Value) -> usize {
+    match value {
+        Value::Object(object) => object.values().map(|value| 1 + count_fields(value)).sum(),
+        Value::Array(array) => array.iter().map(|value| 1 + count_fields(value)).sum(),
+        _ => 0,
+    }
+}
+
+/// The nesting depth of the fields and array elements in the value.
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Object(object) => object.values().map(|value| 1 + depth(value)).max(),
+        Value::Array(array) => array.iter().map(|value| 1 + depth(value)).max(),
+        _ => None,
+    }
+    .unwrap_or_default()
+}
+
+impl Target for LimitedTarget<'_> {
+    fn target_insert(&mut self, path: &OwnedTargetPath, value: Value) -> Result<(), String> {
+        self.check_insert(path, &value)
+            .map_err(|err| err.to_string())?;
+
+        let result = self.target.target_insert(path, value);
+        if self.raise_insert_errors {
+            return result;
+        }
+        drop(result);
+        Ok(())
+    }
+
+    fn target_get(&self, path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        self.target.target_get(path)
+    }
+
+    fn target_get_mut(&mut self, path: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+        self.target.target_get_mut(path)
+    }
+
+    fn target_remove(
+        &mut self,
+        path: &OwnedTargetPath,
+        compact: bool,
+    ) -> Result<Option<Value>, String> {
+        self.target.target_remove(path, compact)
+    }
+}
+
+impl SecretTarget for LimitedTarget<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.target.get_secret(key)
+    }
+
+    fn read_secret(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        self.target.read_secret(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.target.insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.target.remove_secret(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::codes::WARNING_ARRAY_PADDING_CODE;
+    use crate::compiler::{compile_with_state, runtime::Runtime};
+    use crate::compiler::{CompileConfig, TargetValue, TimeZone, TypeState};
+    use crate::value::Secrets;
+    use crate::{stdlib, value};
+
+    fn compile(source: &str, config: CompileConfig) -> Program {
+        compile_with_state(source, &stdlib::all(), &TypeState::default(), config)
+            .unwrap()
+            .program
+    }
+
+    fn run(source: &str, limits: WriteLimits) -> Result<Value, String> {
+        let mut config = CompileConfig::default();
+        config.set_write_limits(limits);
+        let program = compile(source, config);
+
+        let mut target = TargetValue {
+            value: value!({"existing": {"a": 1}}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .map(|_| target.value)
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn limits_inserted_fields() {
+        let limits = WriteLimits {
+            max_inserted_fields: Some(3),
+            ..WriteLimits::default()
+        };
+
+        assert!(run(".a.b = 1; .c = 2; .existing.a = 3", limits).is_ok());
+        assert!(run(r#".a = {"b": 1, "c": 2}"#, limits).is_ok());
+        assert!(run(".existing.b.c.d = 1", limits).is_ok());
+        assert!(run(".existing.b.c.d.e = 1", limits).is_err());
+
+        let err = run(".a = 1; .b = [1, 2]", limits).unwrap_err();
+        assert!(
+            err.contains("inserting into .b exceeds the limit of 3"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn limits_depth() {
+        let limits = WriteLimits {
+            max_depth: Some(2),
+            ..WriteLimits::default()
+        };
+
+        assert!(run(r#".a.b = 1; .c = {"d": {}}"#, limits).is_ok());
+
+        let err = run(r#".a = {"b": {"c": 1}}"#, limits).unwrap_err();
+        assert!(
+            err.contains("inserting into .a creates fields nested 3 levels deep"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn limited_assignments_are_fallible() {
+        let limits = WriteLimits {
+            max_depth: Some(2),
+            ..WriteLimits::default()
+        };
+        let mut config = CompileConfig::default();
+        config.set_write_limits(limits);
+
+        assert!(compile(".a = 1", config).info().fallible);
+        assert!(!compile(".a = 1", CompileConfig::default()).info().fallible);
+        assert!(
+            !compile("a = 1; a", CompileConfig::default())
+                .info()
+                .fallible
+        );
+    }
+
+    #[test]
+    fn limits_array_padding() {
+        let limits = WriteLimits {
+            max_array_padding: Some(2),
+            ..WriteLimits::default()
+        };
+
+        assert_eq!(
+            run(".a[2] = 1; .existing.b[1] = 2", limits),
+            Ok(value!({"a": [null, null, 1], "existing": {"a": 1, "b": [null, 2]}}))
+        );
+
+        let err = run(".a[5] = 1", limits).unwrap_err();
+        assert!(
+            err.contains("assigning to .a[5] pads an array with 5 null elements"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn padding_assignments_are_fallible() {
+        let compile = |source: &str| {
+            let mut config = CompileConfig::default();
+            config.set_write_limits(WriteLimits {
+                max_array_padding: Some(1000),
+                ..WriteLimits::default()
+            });
+            compile_with_state(source, &stdlib::all(), &TypeState::default(), config).unwrap()
+        };
+
+        assert!(compile(".a[1] = 1").program.info().fallible);
+        assert!(compile("a = []; a[1] = 1; a").program.info().fallible);
+        assert!(!compile(".a.b = 1").program.info().fallible);
+
+        let codes = |source: &str| {
+            compile(source)
+                .warnings
+                .into_iter()
+                .map(|warning| warning.code)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes(".a[1000000] = 1"), [WARNING_ARRAY_PADDING_CODE]);
+        assert!(codes(".a[1000] = 1").is_empty());
+    }
+
+    /// A target rejecting every insertion.
+    #[derive(Debug)]
+    struct ReadOnlyTarget(Value);
+
+    impl Target for ReadOnlyTarget {
+        fn target_insert(&mut self, _: &OwnedTargetPath, _: Value) -> Result<(), String> {
+            Err("the target is read only".to_owned())
+        }
+
+        fn target_get(&self, path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+            Ok(self.0.get(&path.path))
+        }
+
+        fn target_get_mut(&mut self, path: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+            Ok(self.0.get_mut(&path.path))
+        }
+
+        fn target_remove(&mut self, _: &OwnedTargetPath, _: bool) -> Result<Option<Value>, String> {
+            Err("the target is read only".to_owned())
+        }
+    }
+
+    impl SecretTarget for ReadOnlyTarget {
+        fn get_secret(&self, _: &str) -> Option<&str> {
+            None
+        }
+
+        fn insert_secret(&mut self, _: &str, _: &str) {}
+
+        fn remove_secret(&mut self, _: &str) {}
+    }
+
+    #[test]
+    fn target_insert_errors_are_raised_if_enabled() {
+        let resolve = |program: &Program| {
+            Runtime::default().resolve(
+                &mut ReadOnlyTarget(value!({})),
+                program,
+                &TimeZone::default(),
+            )
+        };
+
+        let program = compile(".a = 1", CompileConfig::default());
+        assert!(!program.info().fallible);
+        assert_eq!(resolve(&program), Ok(value!(1)));
+
+        let mut config = CompileConfig::default();
+        config.set_raise_target_insert_errors(true);
+        let program = compile(".a = 1", config);
+        assert!(program.info().fallible);
+        let err = resolve(&program).unwrap_err().to_string();
+        assert!(err.contains("the target is read only"), "{err}");
+    }
+}
@@ -38,6 +38,13 @@ pub trait Filter<V: Debug + Send + Sync + Clone + 'static>: DynClone {
     fn wildcard(&self, field: Field, wildcard: &str)
         -> Result<Box<dyn Matcher<V>>, PathParseError>;
 
+    /// Determine whether a field value is an IP address within any of the `cidrs` blocks.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    fn cidr(&self, field: Field, cidrs: &[String]) -> Result<Box<dyn Matcher<V>>, PathParseError>;
+
     /// Compare a field value against `comparison_value`, using one of the `comparator` operators.
     ///
     /// # Errors
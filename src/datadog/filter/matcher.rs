@@ -159,6 +159,15 @@ where
 
             Ok(any(matchers?))
         }
+        QueryNode::AttributeCidr { attr, cidrs } => {
+            let matchers: Result<Vec<_>, _> = filter
+                .build_fields(attr)
+                .into_iter()
+                .map(|field| filter.cidr(field, cidrs))
+                .collect();
+
+            Ok(any(matchers?))
+        }
         QueryNode::AttributeComparison {
             attr,
             comparator,
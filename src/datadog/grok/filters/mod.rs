@@ -1,2 +1,4 @@
 pub mod array;
 pub mod keyvalue;
+pub mod url;
+pub mod useragent;
@@ -0,0 +1,29 @@
+use crate::parsing::query_string::parse_query_string;
+use crate::value::{ObjectMap, Value};
+use ::url::Url;
+
+/// Splits a URL into the members Datadog reports as `url_details`: `scheme`, `host`,
+/// `path`, and `port` and `queryString` when present. Returns `None` for values
+/// that aren't absolute URLs.
+pub fn parse(url: &str) -> Option<Value> {
+    let url = Url::parse(url).ok()?;
+
+    let mut details = ObjectMap::from([
+        ("scheme".into(), url.scheme().into()),
+        ("path".into(), url.path().into()),
+    ]);
+    if let Some(host) = url.host_str() {
+        details.insert("host".into(), host.into());
+    }
+    if let Some(port) = url.port() {
+        details.insert("port".into(), port.into());
+    }
+    if let Some(query) = url.query().filter(|query| !query.is_empty()) {
+        details.insert(
+            "queryString".into(),
+            parse_query_string(&query.to_owned().into(), true).ok()?,
+        );
+    }
+
+    Some(details.into())
+}
@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use crate::value::{ObjectMap, Value};
+use once_cell::sync::Lazy;
+use uaparser::{Parser, UserAgentParser};
+
+static UA_PARSER: Lazy<UserAgentParser> = Lazy::new(|| {
+    let regexes = include_bytes!("./../../../../data/user_agent_regexes.yaml");
+    UserAgentParser::from_bytes(regexes).expect("Regex file is not valid.")
+});
+
+/// Operating systems that only run on phones and tablets.
+const MOBILE_OS_FAMILIES: &[&str] = &[
+    "Android",
+    "BlackBerry OS",
+    "Firefox OS",
+    "iOS",
+    "KaiOS",
+    "Symbian OS",
+    "Windows Phone",
+];
+
+/// Parses a user agent into the `browser`, `device` and `os` objects Datadog reports.
+/// Families are `Other` when unknown, and unknown versions are left out.
+pub fn parse(user_agent: &str) -> Value {
+    let ua = UA_PARSER.parse(user_agent);
+
+    let category = device_category(user_agent, &ua.device.family, &ua.os.family);
+
+    ObjectMap::from([
+        (
+            "browser".into(),
+            versioned(
+                ua.user_agent.family,
+                [
+                    ("major", ua.user_agent.major),
+                    ("minor", ua.user_agent.minor),
+                    ("patch", ua.user_agent.patch),
+                ],
+            ),
+        ),
+        (
+            "device".into(),
+            ObjectMap::from([
+                ("family".into(), ua.device.family.into_owned().into()),
+                ("category".into(), category.into()),
+            ])
+            .into(),
+        ),
+        (
+            "os".into(),
+            versioned(
+                ua.os.family,
+                [
+                    ("major", ua.os.major),
+                    ("minor", ua.os.minor),
+                    ("patch", ua.os.patch),
+                ],
+            ),
+        ),
+    ])
+    .into()
+}
+
+fn versioned<'a>(
+    family: Cow<'a, str>,
+    version: impl IntoIterator<Item = (&'static str, Option<Cow<'a, str>>)>,
+) -> Value {
+    let mut map = ObjectMap::from([("family".into(), family.into_owned().into())]);
+    for (name, part) in version {
+        if let Some(part) = part {
+            map.insert(name.into(), part.into_owned().into());
+        }
+    }
+    map.into()
+}
+
+/// Classifies the device as `Bot`, `Tablet`, `Mobile`, `Desktop` or `Other`.
+fn device_category(user_agent: &str, device_family: &str, os_family: &str) -> &'static str {
+    if device_family == "Spider" {
+        "Bot"
+    } else if device_family.contains("iPad")
+        || device_family.contains("Tablet")
+        || user_agent.contains("Tablet")
+        // Android tablets don't advertise themselves as mobile
+        || (os_family == "Android" && !user_agent.contains("Mobile"))
+    {
+        "Tablet"
+    } else if MOBILE_OS_FAMILIES.contains(&os_family) {
+        "Mobile"
+    } else if os_family != "Other" {
+        "Desktop"
+    } else {
+        "Other"
+    }
+}
@@ -10,7 +10,7 @@ use percent_encoding::percent_decode;
 
 use super::{
     ast::{Function, FunctionArgument},
-    filters::{array, keyvalue, keyvalue::KeyValueFilter},
+    filters::{array, keyvalue, keyvalue::KeyValueFilter, url, useragent},
     matchers::date::{apply_date_filter, DateFilter},
     parse_grok::InternalError,
     parse_grok_rules::Error as GrokStaticError,
@@ -35,6 +35,9 @@ pub enum GrokFilter {
     Boolean,
     Decodeuricomponent,
     Xml,
+    Url,
+    // percent-decodes the user agent first when set
+    UserAgent(bool),
     Array(
         Option<(String, String)>,
         Option<String>,
@@ -61,6 +64,8 @@ impl fmt::Display for GrokFilter {
             GrokFilter::Boolean => f.pad("Boolean"),
             GrokFilter::Decodeuricomponent => f.pad("DecodeUriComponent"),
             GrokFilter::Xml => f.pad("Xml"),
+            GrokFilter::Url => f.pad("Url"),
+            GrokFilter::UserAgent(..) => f.pad("UserAgent(..)"),
             GrokFilter::Array(..) => f.pad("Array(..)"),
             GrokFilter::KeyValue(..) => f.pad("KeyValue(..)"),
         }
@@ -97,11 +102,19 @@ impl TryFrom<&Function> for GrokFilter {
             "decodeuricomponent" => Ok(GrokFilter::Decodeuricomponent),
             "boolean" => Ok(GrokFilter::Boolean),
             "xml" => Ok(GrokFilter::Xml),
+            "url" => Ok(GrokFilter::Url),
+            "useragent" => match f.args.as_deref() {
+                None | Some([]) => Ok(GrokFilter::UserAgent(false)),
+                Some([FunctionArgument::Arg(Value::Boolean(decode))]) => {
+                    Ok(GrokFilter::UserAgent(*decode))
+                }
+                _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+            },
             "nullIf" => f
                 .args
                 .as_ref()
                 .and_then(|args| {
-                    if let FunctionArgument::Arg(Value::Bytes(null_value)) = &args[0] {
+                    if let Some(FunctionArgument::Arg(Value::Bytes(null_value))) = args.first() {
                         Some(GrokFilter::NullIf(
                             String::from_utf8_lossy(null_value).to_string(),
                         ))
@@ -134,31 +147,27 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, Interna
             )),
         },
         GrokFilter::IntegerExt => match value {
-            Value::Bytes(v) => Ok(String::from_utf8_lossy(v)
+            Value::Bytes(v) => String::from_utf8_lossy(v)
                 .parse::<f64>()
-                .map_err(|_e| {
+                .ok()
+                .and_then(|f| to_integer(f.trunc()))
+                .map(Value::Integer)
+                .ok_or_else(|| {
                     InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
-                })
-                .map(|f| (f as i64).into())?),
+                }),
             _ => Err(InternalError::FailedToApplyFilter(
                 filter.to_string(),
                 value.to_string(),
             )),
         },
         GrokFilter::Number | GrokFilter::NumberExt => match value {
-            Value::Bytes(v) => {
-                let v = Ok(Value::from_f64_or_zero(
-                    String::from_utf8_lossy(v).parse::<f64>().map_err(|_e| {
-                        InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
-                    })?,
-                ));
-                match v {
-                    Ok(Value::Float(v)) if (v.into_inner() as i64) as f64 == v.into_inner() => {
-                        Ok(Value::Integer(v.into_inner() as i64))
-                    }
-                    _ => v,
-                }
-            }
+            Value::Bytes(v) => String::from_utf8_lossy(v)
+                .parse::<f64>()
+                .ok()
+                .and_then(number_to_value)
+                .ok_or_else(|| {
+                    InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
+                }),
             _ => Err(InternalError::FailedToApplyFilter(
                 filter.to_string(),
                 value.to_string(),
@@ -167,29 +176,15 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, Interna
         GrokFilter::Scale(scale_factor) => {
             let scale_factor = scale_factor * 1000_f64 / 1000_f64;
             let v = match value {
-                Value::Integer(v) => Ok(Value::Float(
-                    NotNan::new((*v as f64) * scale_factor).expect("NaN"),
-                )),
-                Value::Float(v) => Ok(Value::Float(
-                    NotNan::new(v.into_inner() * scale_factor).expect("NaN"),
-                )),
-                Value::Bytes(v) => {
-                    let v = String::from_utf8_lossy(v).parse::<f64>().map_err(|_e| {
-                        InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
-                    })?;
-                    Ok(Value::Float(NotNan::new(v * scale_factor).expect("NaN")))
-                }
-                _ => Err(InternalError::FailedToApplyFilter(
-                    filter.to_string(),
-                    value.to_string(),
-                )),
+                Value::Integer(v) => Some(*v as f64),
+                Value::Float(v) => Some(v.into_inner()),
+                Value::Bytes(v) => String::from_utf8_lossy(v).parse::<f64>().ok(),
+                _ => None,
             };
-            match v {
-                Ok(Value::Float(v)) if (v.into_inner() as i64) as f64 == v.into_inner() => {
-                    Ok(Value::Integer(v.into_inner() as i64))
-                }
-                _ => v,
-            }
+            v.and_then(|v| number_to_value(v * scale_factor))
+                .ok_or_else(|| {
+                    InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
+                })
         }
         GrokFilter::Lowercase => {
             parse_value(value, filter, |b| String::from_utf8_lossy(b).to_lowercase())
@@ -225,19 +220,44 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, Interna
                 },
             )
         }),
-        GrokFilter::NullIf(null_value) => match value {
-            Value::Bytes(bytes) => {
-                if String::from_utf8_lossy(bytes) == *null_value {
-                    Ok(Value::Null)
-                } else {
-                    Ok(value.to_owned())
+        GrokFilter::NullIf(null_value) => {
+            let is_null = match value {
+                Value::Bytes(bytes) => String::from_utf8_lossy(bytes) == *null_value,
+                // values converted by a previous filter are compared by their text
+                Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => {
+                    value.to_string() == *null_value
                 }
+                _ => {
+                    return Err(InternalError::FailedToApplyFilter(
+                        filter.to_string(),
+                        value.to_string(),
+                    ))
+                }
+            };
+            Ok(if is_null {
+                Value::Null
+            } else {
+                value.to_owned()
+            })
+        }
+        GrokFilter::Url => match value {
+            Value::Bytes(bytes) => {
+                url::parse(String::from_utf8_lossy(bytes).as_ref()).ok_or_else(|| {
+                    InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
+                })
             }
             _ => Err(InternalError::FailedToApplyFilter(
                 filter.to_string(),
                 value.to_string(),
             )),
         },
+        GrokFilter::UserAgent(decode) => parse_value(value, filter, |b| {
+            if *decode {
+                useragent::parse(&percent_decode(b).decode_utf8_lossy())
+            } else {
+                useragent::parse(&String::from_utf8_lossy(b))
+            }
+        }),
         GrokFilter::Date(date_filter) => apply_date_filter(value, date_filter),
         GrokFilter::KeyValue(keyvalue_filter) => keyvalue_filter.apply_filter(value),
         GrokFilter::Array(brackets, delimiter, value_filter) => match value {
@@ -268,6 +288,24 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, Interna
     }
 }
 
+/// Converts a finite number to an integer if it has no fractional part and fits in an
+/// `i64`, and to a float otherwise.
+fn number_to_value(number: f64) -> Option<Value> {
+    match to_integer(number) {
+        Some(integer) => Some(Value::Integer(integer)),
+        None => NotNan::new(number)
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(Value::Float),
+    }
+}
+
+fn to_integer(number: f64) -> Option<i64> {
+    // `i64::MAX as f64` rounds up to 2^63, which is out of range
+    (number.fract() == 0.0 && number >= i64::MIN as f64 && number < i64::MAX as f64)
+        .then_some(number as i64)
+}
+
 fn parse_value<V: Into<Value>>(
     value: &Value,
     filter: &GrokFilter,
@@ -1292,6 +1292,144 @@ mod tests {
         )]);
     }
 
+    #[test]
+    fn supports_url_filter() {
+        test_grok_pattern(vec![
+            (
+                "%{data:field:url}",
+                "https://app.datadoghq.com:8080/logs/?query=status%3Aerror&live=true#top",
+                Ok(Value::from(btreemap! {
+                    "scheme" => "https",
+                    "host" => "app.datadoghq.com",
+                    "port" => 8080,
+                    "path" => "/logs/",
+                    "queryString" => btreemap! {
+                        "query" => "status:error",
+                        "live" => "true",
+                    },
+                })),
+            ),
+            (
+                "%{data:field:url}",
+                "http://www.example.com",
+                Ok(Value::from(btreemap! {
+                    "scheme" => "http",
+                    "host" => "www.example.com",
+                    "path" => "/",
+                })),
+            ),
+        ]);
+
+        test_full_grok_internal_errors(vec![(
+            "%{data:field:url}",
+            "/relative/path",
+            Ok(ParsedGrokObject {
+                parsed: Value::from(BTreeMap::new()),
+                internal_errors: vec![InternalError::FailedToApplyFilter(
+                    "Url".to_owned(),
+                    "\"/relative/path\"".to_owned(),
+                )],
+            }),
+        )]);
+    }
+
+    #[test]
+    fn supports_useragent_filter() {
+        let mac_chrome = Value::from(btreemap! {
+            "browser" => btreemap! {
+                "family" => "Chrome",
+                "major" => "65",
+                "minor" => "0",
+                "patch" => "3325",
+            },
+            "device" => btreemap! {
+                "family" => "Mac",
+                "category" => "Desktop",
+            },
+            "os" => btreemap! {
+                "family" => "Mac OS X",
+                "major" => "10",
+                "minor" => "13",
+                "patch" => "4",
+            },
+        });
+
+        test_grok_pattern(vec![
+            (
+                "%{data:field:useragent}",
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_13_4) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/65.0.3325.181 Safari/537.36",
+                Ok(mac_chrome.clone()),
+            ),
+            (
+                "%{data:field:useragent(true)}",
+                "Mozilla%2F5.0%20(Macintosh%3B%20Intel%20Mac%20OS%20X%2010_13_4)%20AppleWebKit%2F537.36%20(KHTML%2C%20like%20Gecko)%20Chrome%2F65.0.3325.181%20Safari%2F537.36",
+                Ok(mac_chrome),
+            ),
+            (
+                "%{data:field:useragent}",
+                "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+                Ok(Value::from(btreemap! {
+                    "browser" => btreemap! {
+                        "family" => "Googlebot",
+                        "major" => "2",
+                        "minor" => "1",
+                    },
+                    "device" => btreemap! {
+                        "family" => "Spider",
+                        "category" => "Bot",
+                    },
+                    "os" => btreemap! {
+                        "family" => "Other",
+                    },
+                })),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn number_filters_edge_cases() {
+        test_grok_pattern(vec![
+            ("%{data:field:number}", "-0", Ok(Value::from(0))),
+            ("%{data:field:number}", "1e3", Ok(Value::from(1000))),
+            (
+                "%{data:field:number}",
+                "9223372036854775808",
+                Ok(Value::from(9_223_372_036_854_775_808_f64)),
+            ),
+            ("%{data:field:integerExt}", "12.9", Ok(Value::from(12))),
+        ]);
+
+        for input in ["NaN", "inf", "-infinity", "1e400"] {
+            for filter in ["number", "integerExt"] {
+                let rules =
+                    parse_grok_rules(&[format!("%{{data:field:{filter}}}")], BTreeMap::new())
+                        .expect("couldn't parse rules");
+                let parsed = parse_grok(input, &rules).unwrap();
+
+                assert_eq!(
+                    parsed.parsed,
+                    Value::from(BTreeMap::new()),
+                    "{filter} of {input}"
+                );
+                assert_eq!(parsed.internal_errors.len(), 1, "{filter} of {input}");
+            }
+        }
+    }
+
+    #[test]
+    fn supports_nullif_on_converted_values() {
+        test_grok_pattern(vec![(
+            r#"%{integer:field:nullIf("0")}"#,
+            "1",
+            Ok(Value::from(1)),
+        )]);
+        test_full_grok(vec![(
+            r#"%{integer:field:nullIf("0")}"#,
+            "0",
+            Ok(Value::from(BTreeMap::new())),
+        )]);
+    }
+
     #[test]
     fn supports_xml_filter() {
         test_grok_pattern(vec![(
@@ -162,9 +162,10 @@ impl QueryVisitor {
         let mut field: Option<&str> = None;
         for item in clause.into_inner() {
             // As per the parser, a clause will only ever contain:
-            // matchall, field, value, query.
+            // matchall, cidr, field, value, query.
             match item.as_rule() {
                 Rule::matchall => return QueryNode::MatchAllDocs,
+                Rule::cidr => return Self::visit_cidr(item),
                 Rule::field => {
                     field = Some(Self::visit_field(item));
                 }
@@ -289,6 +290,15 @@ impl QueryVisitor {
         QueryNode::MatchAllDocs
     }
 
+    fn visit_cidr(token: Pair<Rule>) -> QueryNode {
+        let mut contents = token.into_inner();
+        // As per the parser, a cidr will always start with a field, followed by blocks.
+        let attr = unescape(contents.next().unwrap().as_str());
+        let cidrs = contents.map(|block| block.as_str().to_owned()).collect();
+
+        QueryNode::AttributeCidr { attr, cidrs }
+    }
+
     fn visit_operator(token: Pair<Rule>) -> Comparison {
         match token.as_rule() {
             Rule::GT => Comparison::Gt,
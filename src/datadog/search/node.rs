@@ -166,6 +166,8 @@ pub enum QueryNode {
     AttributePrefix { attr: String, prefix: String },
     /// Search for an attribute that matches a wildcard or glob string.
     AttributeWildcard { attr: String, wildcard: String },
+    /// Search for an attribute holding an IP address within any of the CIDR blocks.
+    AttributeCidr { attr: String, cidrs: Vec<String> },
     /// Container node denoting negation of the QueryNode within.
     NegatedNode { node: Box<QueryNode> },
     /// Container node for compound Boolean operations.
@@ -222,6 +224,9 @@ impl QueryNode {
             QueryNode::AttributeWildcard { attr, wildcard } => {
                 Self::is_default_attr(attr) + wildcard
             }
+            QueryNode::AttributeCidr { attr, cidrs } => {
+                format!("CIDR({},{})", attr, cidrs.join(","))
+            }
             QueryNode::NegatedNode { ref node } => {
                 if matches!(
                     **node,
@@ -1,14 +1,45 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
-use pest::Parser;
+use pest::{error::InputLocation, Parser};
 
 use super::{
-    grammar::{EventPlatformQuery, QueryVisitor, DEFAULT_FIELD},
+    grammar::{EventPlatformQuery, QueryVisitor, Rule, DEFAULT_FIELD},
     node::QueryNode,
 };
 
 pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
+/// A query that doesn't follow the search syntax. Parsing a `QueryNode` returns
+/// this as the source of its `Error`, which can be recovered by downcasting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// The byte offset within the query at which parsing failed.
+    pub position: usize,
+    /// What the parser expected to find at `position`.
+    pub message: String,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+impl From<pest::error::Error<Rule>> for SyntaxError {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        let position = match error.location {
+            InputLocation::Pos(position) | InputLocation::Span((position, _)) => position,
+        };
+
+        Self {
+            position,
+            message: error.variant.message().into_owned(),
+        }
+    }
+}
+
 /// Quick wrapper parse function to convert query strings into our AST
 impl FromStr for QueryNode {
     type Err = Error;
@@ -21,7 +52,8 @@ impl FromStr for QueryNode {
             Self::MatchAllDocs
         } else {
             // Otherwise parse and interpret the query
-            let mut ast = EventPlatformQuery::parse(super::grammar::Rule::queryroot, query)?;
+            let mut ast =
+                EventPlatformQuery::parse(Rule::queryroot, query).map_err(SyntaxError::from)?;
             let rootquery = ast.next().ok_or("Unable to find root query")?;
             QueryVisitor::visit_queryroot(rootquery, DEFAULT_FIELD)
         })
@@ -395,6 +427,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reports_syntax_error_position() {
+        let error = "foo:(bar".parse::<QueryNode>().unwrap_err();
+        let error = error
+            .downcast_ref::<SyntaxError>()
+            .expect("error should be a syntax error");
+
+        assert_eq!(error.position, 8);
+    }
+
+    #[test]
+    fn parses_cidr_query() {
+        let cases = [
+            "CIDR(@network.client.ip,10.0.0.0/8,2001:db8::/32)",
+            "cidr(@network.client.ip, 10.0.0.0/8, 2001:db8::/32)",
+        ];
+        for query in cases.iter() {
+            let res = parse(query);
+            assert!(
+                matches!(res,
+                QueryNode::AttributeCidr { ref attr, ref cidrs }
+                if attr == "@network.client.ip" && cidrs == &["10.0.0.0/8", "2001:db8::/32"]),
+                "Unable to properly parse '{:?}' - got {:?}",
+                query,
+                res
+            );
+            assert_eq!(
+                res.to_lucene(),
+                "CIDR(@network.client.ip,10.0.0.0/8,2001:db8::/32)"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_cidr_query_in_boolean() {
+        let res = parse("service:web CIDR(@ip,10.0.0.0/8)");
+        assert_eq!(res.to_lucene(), "service:web AND CIDR(@ip,10.0.0.0/8)");
+    }
+
     #[test]
     fn parses_attribute_exists_query() {
         let cases = ["_exists_:foo", "_exists_:\"foo\""];
@@ -0,0 +1,243 @@
+//! A C-compatible interface for embedding VRL in non-Rust programs.
+//!
+//! The API is intentionally small and stable: programs are compiled from source
+//! into an opaque handle, and events are passed in and out as JSON strings. See
+//! `vrl.h` in this directory for the matching C declarations.
+//!
+//! All strings returned by this module are owned by the caller and must be
+//! released with [`vrl_string_free`]. Programs must be released with
+//! [`vrl_program_free`].
+#![allow(clippy::missing_safety_doc)] // documented on each function and in `vrl.h`
+
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::compiler::runtime::Runtime;
+use crate::compiler::state::RuntimeState;
+use crate::compiler::{compile, Program, TargetValue, TimeZone};
+use crate::diagnostic::Formatter;
+use crate::value::{Secrets, Value};
+
+/// An opaque handle to a compiled VRL program.
+pub struct VrlProgram {
+    program: Program,
+}
+
+/// Writes `message` to `error`, if the caller provided a location for it.
+unsafe fn set_error(error: *mut *mut c_char, message: impl Into<String>) {
+    if !error.is_null() {
+        *error = into_c_string(message.into());
+    }
+}
+
+fn into_c_string(string: String) -> *mut c_char {
+    // Interior NUL bytes can't be represented, so they're replaced.
+    let string = string.replace('\0', "\u{fffd}");
+    CString::new(string).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err(format!("{name} must not be null"));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|err| format!("{name} is not valid UTF-8: {err}"))
+}
+
+/// Compiles a VRL program using the full standard library.
+///
+/// Returns a program handle, or null on failure, in which case `error` (if not
+/// null) is set to a human-readable description of the compilation diagnostics.
+///
+/// # Safety
+///
+/// `source` must be a valid NUL-terminated string. `error` must be null or point
+/// to writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn vrl_compile(
+    source: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut VrlProgram {
+    let result = catch_unwind(|| {
+        let source = read_str(source, "source")?;
+        compile(source, &crate::stdlib::all())
+            .map(|result| result.program)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).to_string())
+    });
+
+    match result {
+        Ok(Ok(program)) => Box::into_raw(Box::new(VrlProgram { program })),
+        Ok(Err(message)) => {
+            set_error(error, message);
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_error(error, "panic during compilation");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Runs a compiled program against an event given as a JSON document.
+///
+/// On success, returns a JSON object with two keys: `event`, holding the event
+/// after the program ran, and `result`, holding the value of the last expression
+/// of the program. On failure, returns null and sets `error` (if not null) to the
+/// runtime error message.
+///
+/// # Safety
+///
+/// `program` must be a handle returned by [`vrl_compile`] that hasn't been freed.
+/// `event` must be a valid NUL-terminated string. `error` must be null or point to
+/// writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn vrl_resolve(
+    program: *const VrlProgram,
+    event: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    if program.is_null() {
+        set_error(error, "program must not be null");
+        return ptr::null_mut();
+    }
+    let program = &(*program).program;
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let event = read_str(event, "event")?;
+        let value: Value =
+            serde_json::from_str(event).map_err(|err| format!("invalid event JSON: {err}"))?;
+
+        let mut target = TargetValue {
+            value,
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut runtime = Runtime::new(RuntimeState::default());
+        let result = runtime
+            .resolve(&mut target, program, &TimeZone::default())
+            .map_err(|err| err.to_string())?;
+
+        let output = Value::Object(BTreeMap::from([
+            ("event".into(), target.value),
+            ("result".into(), result),
+        ]));
+        serde_json::to_string(&output).map_err(|err| format!("unable to encode output: {err}"))
+    }));
+
+    match result {
+        Ok(Ok(output)) => into_c_string(output),
+        Ok(Err(message)) => {
+            set_error(error, message);
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_error(error, "panic during program execution");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a program returned by [`vrl_compile`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `program` must be null or a handle returned by [`vrl_compile`] that hasn't
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn vrl_program_free(program: *mut VrlProgram) {
+    if !program.is_null() {
+        drop(Box::from_raw(program));
+    }
+}
+
+/// Releases a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `string` must be null or a string returned by this library that hasn't been
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn vrl_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Returns the version of the VRL library as a static NUL-terminated string,
+/// which must not be freed.
+#[no_mangle]
+pub extern "C" fn vrl_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take_string(ptr: *mut c_char) -> String {
+        assert!(!ptr.is_null());
+        let string = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_owned();
+        unsafe { vrl_string_free(ptr) };
+        string
+    }
+
+    #[test]
+    fn compile_and_resolve() {
+        let source = CString::new(".b = .a * 2\n.b + 1").unwrap();
+        let event = CString::new(r#"{"a": 21}"#).unwrap();
+        let mut error = ptr::null_mut();
+
+        unsafe {
+            let program = vrl_compile(source.as_ptr(), &mut error);
+            assert!(error.is_null());
+
+            let output = take_string(vrl_resolve(program, event.as_ptr(), &mut error));
+            assert!(error.is_null());
+            assert_eq!(output, r#"{"event":{"a":21,"b":42},"result":43}"#);
+
+            vrl_program_free(program);
+        }
+    }
+
+    #[test]
+    fn compile_error() {
+        let source = CString::new("upcase(").unwrap();
+        let mut error = ptr::null_mut();
+
+        let program = unsafe { vrl_compile(source.as_ptr(), &mut error) };
+        assert!(program.is_null());
+        assert!(take_string(error).contains("syntax error"));
+    }
+
+    #[test]
+    fn runtime_error() {
+        let source = CString::new("parse_json!(.message)").unwrap();
+        let event = CString::new(r#"{"message": "{"}"#).unwrap();
+        let mut error = ptr::null_mut();
+
+        unsafe {
+            let program = vrl_compile(source.as_ptr(), &mut error);
+            assert!(!program.is_null());
+
+            let output = vrl_resolve(program, event.as_ptr(), &mut error);
+            assert!(output.is_null());
+            assert!(take_string(error).contains("unable to parse json"));
+
+            let invalid = CString::new("not json").unwrap();
+            let output = vrl_resolve(program, invalid.as_ptr(), &mut error);
+            assert!(output.is_null());
+            assert!(take_string(error).starts_with("invalid event JSON"));
+
+            vrl_program_free(program);
+        }
+    }
+
+    #[test]
+    fn version() {
+        let version = unsafe { CStr::from_ptr(vrl_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * C interface to the Vector Remap Language.
+ *
+ * Building
+ * --------
+ * The library is built from the `vrl` crate with the `ffi` feature, for example
+ * as a shared library with:
+ *
+ *     cargo rustc --lib --release --features ffi --crate-type cdylib
+ *
+ * or as a static library with `--crate-type staticlib`. The library is written
+ * to `target/release` (`libvrl.so`, `libvrl.dylib` or `vrl.dll`).
+ *
+ * Ownership
+ * ---------
+ * Every `char *` returned by the library, including the result of `vrl_resolve`
+ * and the messages stored in `*error`, is owned by the caller and must be
+ * released with `vrl_string_free`, never with `free`: the strings are allocated
+ * by Rust's allocator. Programs returned by `vrl_compile` must be released with
+ * `vrl_program_free`. Strings passed to the library are only borrowed for the
+ * duration of the call.
+ *
+ * Null pointers
+ * -------------
+ * Functions returning a pointer return NULL on failure. Passing NULL as
+ * `source`, `program` or `event` is a failure reported through `*error`, not
+ * undefined behavior. `error` may be NULL when the caller doesn't need the
+ * message; otherwise `*error` is only written on failure, so it should be set to
+ * NULL before the call. The `_free` functions accept NULL.
+ */
+
+#ifndef VRL_H
+#define VRL_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opaque handle to a compiled VRL program. */
+typedef struct VrlProgram VrlProgram;
+
+/*
+ * Compiles `source` using the full standard library. Returns NULL on failure
+ * and, if `error` is not NULL, stores the formatted diagnostics in `*error`.
+ */
+VrlProgram *vrl_compile(const char *source, char **error);
+
+/*
+ * Runs `program` against `event`, a JSON document. Returns a JSON object of the
+ * form `{"event": ..., "result": ...}`, or NULL on failure, in which case the
+ * runtime error is stored in `*error` if `error` is not NULL.
+ *
+ * A program may be shared between threads, but each call runs independently.
+ */
+char *vrl_resolve(const VrlProgram *program, const char *event, char **error);
+
+/* Releases a program. Passing NULL is a no-op. */
+void vrl_program_free(VrlProgram *program);
+
+/* Releases a string returned by the library. Passing NULL is a no-op. */
+void vrl_string_free(char *string);
+
+/* Returns the library version. The string is static and must not be freed. */
+const char *vrl_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VRL_H */
@@ -39,6 +39,12 @@ pub mod cli;
 #[cfg(feature = "test_framework")]
 pub mod test;
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "python")]
+pub mod python;
+
 #[cfg(feature = "parsing")]
 pub mod parsing;
 
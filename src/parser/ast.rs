@@ -187,6 +187,10 @@ impl IntoIterator for Program {
 pub enum RootExpr {
     Expr(Node<Expr>),
 
+    /// The program-level error handler, run when an uncaught runtime error
+    /// occurs.
+    OnError(Node<Block>),
+
     /// A special expression that is returned if a given expression could not be
     /// parsed. This allows the parser to continue on to the next expression.
     Error(Error),
@@ -194,10 +198,11 @@ pub enum RootExpr {
 
 impl fmt::Debug for RootExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RootExpr::{Error, Expr};
+        use RootExpr::{Error, Expr, OnError};
 
         let value = match self {
             Expr(v) => format!("{v:?}"),
+            OnError(v) => format!("OnError({v:?})"),
             Error(v) => format!("{v:?}"),
         };
 
@@ -207,10 +212,11 @@ impl fmt::Debug for RootExpr {
 
 impl fmt::Display for RootExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RootExpr::{Error, Expr};
+        use RootExpr::{Error, Expr, OnError};
 
         match self {
             Expr(v) => v.fmt(f),
+            OnError(v) => write!(f, "on_error {v}"),
             Error(v) => v.fmt(f),
         }
     }
@@ -234,13 +240,15 @@ pub enum Expr {
     Unary(Node<Unary>),
     Abort(Node<Abort>),
     Return(Node<Return>),
+    Loop(Node<Loop>),
+    Break(Node<Break>),
 }
 
 impl fmt::Debug for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Return,
-            Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Op,
+            Query, Return, Unary, Variable,
         };
 
         let value = match self {
@@ -255,6 +263,8 @@ impl fmt::Debug for Expr {
             Unary(v) => format!("{v:?}"),
             Abort(v) => format!("{v:?}"),
             Return(v) => format!("{v:?}"),
+            Loop(v) => format!("{v:?}"),
+            Break(v) => format!("{v:?}"),
         };
 
         write!(f, "Expr({value})")
@@ -264,8 +274,8 @@ impl fmt::Debug for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Return,
-            Unary, Variable,
+            Abort, Assignment, Break, Container, FunctionCall, IfStatement, Literal, Loop, Op,
+            Query, Return, Unary, Variable,
         };
 
         match self {
@@ -280,6 +290,8 @@ impl fmt::Display for Expr {
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
             Return(v) => v.fmt(f),
+            Loop(v) => v.fmt(f),
+            Break(v) => v.fmt(f),
         }
     }
 }
@@ -720,6 +732,10 @@ pub enum Opcode {
     Le,
     Lt,
     Merge,
+    BitAnd,
+    BitXor,
+    Shl,
+    Shr,
 }
 
 impl fmt::Display for Opcode {
@@ -731,7 +747,10 @@ impl fmt::Display for Opcode {
 impl Opcode {
     #[must_use]
     pub fn as_str(self) -> &'static str {
-        use Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Sub};
+        use Opcode::{
+            Add, And, BitAnd, BitXor, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Shl, Shr,
+            Sub,
+        };
 
         match self {
             Mul => "*",
@@ -740,6 +759,11 @@ impl Opcode {
             Sub => "-",
             Merge => "|",
 
+            BitAnd => "&",
+            BitXor => "^",
+            Shl => "<<",
+            Shr => ">>",
+
             Or => "||",
             And => "&&",
 
@@ -760,7 +784,10 @@ impl FromStr for Opcode {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, ()> {
-        use Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Sub};
+        use Opcode::{
+            Add, And, BitAnd, BitXor, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Shl, Shr,
+            Sub,
+        };
 
         let op = match s {
             "*" => Mul,
@@ -782,6 +809,11 @@ impl FromStr for Opcode {
             "<" => Lt,
             "|" => Merge,
 
+            "&" => BitAnd,
+            "^" => BitXor,
+            "<<" => Shl,
+            ">>" => Shr,
+
             _ => return std::result::Result::Err(()),
         };
 
@@ -1149,24 +1181,27 @@ impl fmt::Debug for FunctionClosure {
 #[derive(Clone, PartialEq)]
 pub enum Unary {
     Not(Node<Not>),
+    BitNot(Node<BitNot>),
 }
 
 impl fmt::Display for Unary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Unary::Not;
+        use Unary::{BitNot, Not};
 
         match self {
             Not(v) => v.fmt(f),
+            BitNot(v) => v.fmt(f),
         }
     }
 }
 
 impl fmt::Debug for Unary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Unary::Not;
+        use Unary::{BitNot, Not};
 
         let value = match self {
             Not(v) => format!("{v:?}"),
+            BitNot(v) => format!("{v:?}"),
         };
 
         write!(f, "Unary({value})")
@@ -1204,6 +1239,37 @@ impl fmt::Debug for Not {
     }
 }
 
+// -----------------------------------------------------------------------------
+// bitwise not
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+pub struct BitNot(pub(crate) Node<()>, pub(crate) Box<Node<Expr>>);
+
+impl BitNot {
+    #[must_use]
+    pub fn take(self) -> (Node<()>, Box<Node<Expr>>) {
+        (self.0, self.1)
+    }
+
+    #[must_use]
+    pub fn new(span: Span, expr: Node<Expr>) -> Self {
+        Self(Node::new(span, ()), Box::new(expr))
+    }
+}
+
+impl fmt::Display for BitNot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "~{}", self.1)
+    }
+}
+
+impl fmt::Debug for BitNot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BitNot({:?})", self.1)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // abort
 // -----------------------------------------------------------------------------
@@ -1250,3 +1316,55 @@ impl fmt::Debug for Return {
         write!(f, "Return({:?})", self.expr)
     }
 }
+
+// -----------------------------------------------------------------------------
+// loop
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+pub struct Loop {
+    /// The keyword of the iteration bound, which must be `max`.
+    pub keyword: Node<Ident>,
+    pub max: Box<Node<Expr>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "loop {}: {} {}", self.keyword, self.max, self.block)
+    }
+}
+
+impl fmt::Debug for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Loop({:?}: {:?}, {:?})",
+            self.keyword, self.max, self.block
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+// break
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+pub struct Break {
+    pub condition: Option<Node<Predicate>>,
+}
+
+impl fmt::Display for Break {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.condition {
+            Some(condition) => write!(f, "break if {condition}"),
+            None => f.write_str("break"),
+        }
+    }
+}
+
+impl fmt::Debug for Break {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Break({:?})", self.condition)
+    }
+}
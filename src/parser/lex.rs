@@ -1,6 +1,6 @@
 use std::{fmt, iter::Peekable, str::CharIndices};
 
-use crate::diagnostic::{DiagnosticMessage, Label, Span};
+use crate::diagnostic::{DiagnosticMessage, Label, Note, Span};
 use ordered_float::NotNan;
 
 use super::template_string::{StringSegment, TemplateString};
@@ -9,6 +9,9 @@ pub(crate) type Tok<'input> = Token<&'input str>;
 pub(crate) type SpannedResult<'input, Loc> = Result<Spanned<'input, Loc>, Error>;
 pub(crate) type Spanned<'input, Loc> = (Loc, Tok<'input>, Loc);
 
+/// The maximum number of `(`, `[` and `{` delimiters that can be open at once.
+const MAX_NESTING_DEPTH: usize = 256;
+
 #[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
 pub enum Error {
     #[error("syntax error")]
@@ -32,24 +35,30 @@ pub enum Error {
         end: usize,
     },
 
-    #[error("invalid string literal")]
+    #[error("unterminated string literal")]
     StringLiteral { start: usize },
 
-    #[error("invalid literal")]
-    Literal { start: usize },
+    #[error("unterminated {kind} literal")]
+    Literal { start: usize, kind: &'static str },
 
     #[error("invalid escape character: \\{}", .ch.unwrap_or_default())]
     EscapeChar { start: usize, ch: Option<char> },
 
     #[error("unexpected parse error")]
     UnexpectedParseError(String),
+
+    #[error("unclosed delimiter")]
+    UnclosedDelimiter { start: usize, delimiter: char },
+
+    #[error("nesting too deep")]
+    NestingDepth { start: usize, delimiter: char },
 }
 
 impl DiagnosticMessage for Error {
     fn code(&self) -> usize {
         use Error::{
-            EscapeChar, Literal, NumericLiteral, ParseError, ReservedKeyword, StringLiteral,
-            UnexpectedParseError,
+            EscapeChar, Literal, NestingDepth, NumericLiteral, ParseError, ReservedKeyword,
+            StringLiteral, UnclosedDelimiter, UnexpectedParseError,
         };
 
         match self {
@@ -66,13 +75,15 @@ impl DiagnosticMessage for Error {
             Literal { .. } => 208,
             EscapeChar { .. } => 209,
             UnexpectedParseError(..) => 210,
+            UnclosedDelimiter { .. } => 211,
+            NestingDepth { .. } => 212,
         }
     }
 
     fn labels(&self) -> Vec<Label> {
         use Error::{
-            EscapeChar, Literal, NumericLiteral, ParseError, ReservedKeyword, StringLiteral,
-            UnexpectedParseError,
+            EscapeChar, Literal, NestingDepth, NumericLiteral, ParseError, ReservedKeyword,
+            StringLiteral, UnclosedDelimiter, UnexpectedParseError,
         };
 
         fn update_expected(expected: Vec<String>) -> Vec<String> {
@@ -171,13 +182,13 @@ impl DiagnosticMessage for Error {
             )],
 
             StringLiteral { start } => vec![Label::primary(
-                "invalid string literal",
+                "this string literal is never closed",
                 Span::new(*start, *start + 1),
             )],
 
-            Literal { start } => vec![Label::primary(
-                "invalid literal",
-                Span::new(*start, *start + 1),
+            Literal { start, kind } => vec![Label::primary(
+                format!("this {kind} literal is never closed"),
+                Span::new(*start, *start + 2),
             )],
 
             EscapeChar { start, ch } => vec![Label::primary(
@@ -190,6 +201,106 @@ impl DiagnosticMessage for Error {
             )],
 
             UnexpectedParseError(string) => vec![Label::primary(string, Span::default())],
+
+            UnclosedDelimiter { start, delimiter } => vec![Label::primary(
+                format!("this `{delimiter}` is never closed"),
+                Span::new(*start, *start + 1),
+            )],
+
+            NestingDepth { start, delimiter } => vec![Label::primary(
+                format!("this `{delimiter}` is nested more than {MAX_NESTING_DEPTH} levels deep"),
+                Span::new(*start, *start + 1),
+            )],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::{Literal, NestingDepth, StringLiteral, UnclosedDelimiter};
+
+        match self {
+            StringLiteral { .. } => vec![Note::Hint(
+                r#"add a closing `"` to end the string literal"#.to_owned(),
+            )],
+            Literal { kind, .. } => vec![Note::Hint(format!(
+                "add a closing `'` to end the {kind} literal"
+            ))],
+            UnclosedDelimiter { delimiter, .. } => vec![Note::Hint(format!(
+                "add a closing `{}` to match it",
+                closing_delimiter(*delimiter)
+            ))],
+            NestingDepth { .. } => vec![Note::Hint(
+                "assign inner expressions to variables to reduce the nesting".to_owned(),
+            )],
+            _ => vec![],
+        }
+    }
+}
+
+impl Error {
+    /// Converts a syntax error the parser recovered from, spanning the dropped
+    /// input, into an error that can be reported.
+    pub(crate) fn from_recovery(
+        span: Span,
+        recovery: lalrpop_util::ErrorRecovery<usize, Tok<'_>, Error>,
+    ) -> Self {
+        let lalrpop_util::ErrorRecovery {
+            error: source,
+            dropped_tokens,
+        } = recovery;
+
+        let reserved_keyword =
+            dropped_tokens
+                .first()
+                .cloned()
+                .and_then(|(start, t, end)| match t {
+                    Token::ReservedIdentifier(s) => Some((start, s.to_owned(), end)),
+                    _ => None,
+                });
+
+        if let Some((start, keyword, end)) = reserved_keyword {
+            return Error::ReservedKeyword {
+                start,
+                keyword,
+                end,
+            };
+        }
+
+        let source = source
+            .map_token(|t| t.map(ToOwned::to_owned))
+            .map_error(|e| e.to_string());
+
+        let dropped_tokens = dropped_tokens
+            .into_iter()
+            .map(|(l, t, r)| (l, t.map(ToOwned::to_owned), r))
+            .collect();
+
+        Error::ParseError {
+            span,
+            source,
+            dropped_tokens,
+        }
+    }
+
+    /// Shifts the position of an error returned by a lexer that started
+    /// `offset` bytes into the input.
+    fn offset(self, offset: usize) -> Self {
+        match self {
+            Error::StringLiteral { start } => Error::StringLiteral {
+                start: start + offset,
+            },
+            Error::Literal { start, kind } => Error::Literal {
+                start: start + offset,
+                kind,
+            },
+            Error::EscapeChar { start, ch } => Error::EscapeChar {
+                start: start + offset,
+                ch,
+            },
+            Error::NestingDepth { start, delimiter } => Error::NestingDepth {
+                start: start + offset,
+                delimiter,
+            },
+            error => error,
         }
     }
 }
@@ -204,11 +315,12 @@ pub(crate) struct Lexer<'input> {
     chars: Peekable<CharIndices<'input>>,
 
     // state
-    open_brackets: usize,
-    open_braces: usize,
-    open_parens: usize,
     query_start: Option<usize>,
 
+    /// The start index and character of every `(`, `[` or `{` that hasn't been
+    /// closed yet, innermost last.
+    open_delimiters: Vec<(usize, char)>,
+
     /// Keep track of when the lexer is supposed to emit an `RQuery` token.
     ///
     /// For example:
@@ -230,9 +342,8 @@ pub(crate) struct Lexer<'input> {
 impl<'input> Lexer<'input> {
     fn next_token(&mut self) -> Option<SpannedResult<'input, usize>> {
         use Token::{
-            Ampersand, Arrow, Bang, Colon, Comma, Dot, Escape, InvalidToken, LBrace, LBracket,
-            LParen, LQuery, Newline, Percent, RBrace, RBracket, RParen, RQuery, SemiColon,
-            Underscore,
+            Arrow, Bang, Colon, Comma, Dot, Escape, InvalidToken, LBrace, LBracket, LParen, LQuery,
+            Newline, Percent, RBrace, RBracket, RParen, RQuery, SemiColon, Tilde, Underscore,
         };
 
         loop {
@@ -272,17 +383,15 @@ impl<'input> Lexer<'input> {
                     '\n' => Some(Ok(self.token(start, Newline))),
                     '\\' => Some(Ok(self.token(start, Escape))),
 
-                    '(' => Some(Ok(self.open(start, LParen))),
-                    '[' => Some(Ok(self.open(start, LBracket))),
-                    '{' => Some(Ok(self.open(start, LBrace))),
-                    '}' => Some(Ok(self.close(start, RBrace))),
-                    ']' => Some(Ok(self.close(start, RBracket))),
-                    ')' => Some(Ok(self.close(start, RParen))),
+                    '(' => Some(self.open(start, ch, LParen)),
+                    '[' => Some(self.open(start, ch, LBracket)),
+                    '{' => Some(self.open(start, ch, LBrace)),
+                    '}' => Some(Ok(self.close(start, ch, RBrace))),
+                    ']' => Some(Ok(self.close(start, ch, RBracket))),
+                    ')' => Some(Ok(self.close(start, ch, RParen))),
                     '.' => Some(Ok(self.token(start, Dot))),
                     '%' => Some(Ok(self.token(start, Percent))),
-                    '&' if !matches!(self.peek(), Some((_, '&'))) => {
-                        Some(Ok(self.token(start, Ampersand)))
-                    }
+                    '~' => Some(Ok(self.token(start, Tilde))),
                     ':' => Some(Ok(self.token(start, Colon))),
                     ',' => Some(Ok(self.token(start, Comma))),
 
@@ -330,6 +439,13 @@ impl<'input> Lexer<'input> {
                 return Some(Ok((end, RQuery, end + 1)));
             }
 
+            // Any delimiter still open at the end of the input is reported
+            // here, pointing at the delimiter itself, instead of as an
+            // unexpected end of the program.
+            if let Some((start, delimiter)) = self.open_delimiters.pop() {
+                return Some(Err(Error::UnclosedDelimiter { start, delimiter }));
+            }
+
             return None;
         }
     }
@@ -363,6 +479,9 @@ pub enum Token<S> {
     True,
     Abort,
     Return,
+    OnError,
+    Loop,
+    Break,
 
     // tokens
     Colon,
@@ -379,7 +498,7 @@ pub enum Token<S> {
     Underscore,
     Escape,
     Arrow,
-    Ampersand,
+    Tilde,
     Percent,
 
     Equals,
@@ -424,12 +543,12 @@ pub enum Token<S> {
 impl<S> Token<S> {
     pub(crate) fn map<R>(self, f: impl Fn(S) -> R) -> Token<R> {
         use self::Token::{
-            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
+            Abort, Arrow, Bang, Break, Colon, Comma, Dot, Else, Equals, Escape, False,
             FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral, True,
-            Underscore,
+            LBracket, LParen, LQuery, Loop, MergeEquals, Newline, Null, OnError, Operator,
+            PathField, Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral,
+            RegexLiteral, ReservedIdentifier, Return, SemiColon, StringLiteral, Tilde,
+            TimestampLiteral, True, Underscore,
         };
 
         match self {
@@ -459,6 +578,9 @@ impl<S> Token<S> {
             True => True,
             Abort => Abort,
             Return => Return,
+            OnError => OnError,
+            Loop => Loop,
+            Break => Break,
 
             // tokens
             Colon => Colon,
@@ -475,7 +597,7 @@ impl<S> Token<S> {
             Underscore => Underscore,
             Escape => Escape,
             Arrow => Arrow,
-            Ampersand => Ampersand,
+            Tilde => Tilde,
             Percent => Percent,
 
             Equals => Equals,
@@ -495,12 +617,12 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Token::{
-            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
+            Abort, Arrow, Bang, Break, Colon, Comma, Dot, Else, Equals, Escape, False,
             FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral, True,
-            Underscore,
+            LBracket, LParen, LQuery, Loop, MergeEquals, Newline, Null, OnError, Operator,
+            PathField, Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral,
+            RegexLiteral, ReservedIdentifier, Return, SemiColon, StringLiteral, Tilde,
+            TimestampLiteral, True, Underscore,
         };
 
         let s = match *self {
@@ -524,6 +646,9 @@ where
             True => "True",
             Abort => "Abort",
             Return => "Return",
+            OnError => "OnError",
+            Loop => "Loop",
+            Break => "Break",
 
             // tokens
             Colon => "Colon",
@@ -540,7 +665,7 @@ where
             Underscore => "Underscore",
             Escape => "Escape",
             Arrow => "Arrow",
-            Ampersand => "Ampersand",
+            Tilde => "Tilde",
             Percent => "Percent",
 
             Equals => "Equals",
@@ -560,7 +685,8 @@ impl<'input> Token<&'input str> {
     /// Returns either a literal, reserved, or generic identifier.
     fn ident(s: &'input str) -> Self {
         use Token::{
-            Abort, Else, False, Identifier, If, Null, PathField, ReservedIdentifier, Return, True,
+            Abort, Break, Else, False, Identifier, If, Loop, Null, OnError, PathField,
+            ReservedIdentifier, Return, True,
         };
 
         match s {
@@ -571,14 +697,15 @@ impl<'input> Token<&'input str> {
             "null" => Null,
             "abort" => Abort,
             "return" => Return,
+            "on_error" => OnError,
+            "loop" => Loop,
+            "break" => Break,
 
             // reserved identifiers
-            "array" | "bool" | "boolean" | "break" | "continue" | "do" | "emit" | "float"
-            | "for" | "forall" | "foreach" | "all" | "each" | "any" | "try" | "undefined"
-            | "int" | "integer" | "iter" | "object" | "regex" | "string" | "traverse"
-            | "timestamp" | "duration" | "unless" | "walk" | "while" | "loop" => {
-                ReservedIdentifier(s)
-            }
+            "array" | "bool" | "boolean" | "continue" | "do" | "emit" | "float" | "for"
+            | "forall" | "foreach" | "all" | "each" | "any" | "try" | "undefined" | "int"
+            | "integer" | "iter" | "object" | "regex" | "string" | "traverse" | "timestamp"
+            | "duration" | "unless" | "walk" | "while" => ReservedIdentifier(s),
 
             _ if s.contains('@') => PathField(s),
 
@@ -635,6 +762,15 @@ impl StringLiteralToken<&str> {
                     current.push_str("}}");
                     pos += 3;
                 }
+                '\\' if !template => {
+                    // Keep any other escape sequence together, so that an
+                    // escaped backslash doesn't escape the next character.
+                    current.push('\\');
+                    if let Some(&escaped) = chars.get(pos + 1) {
+                        current.push(escaped);
+                    }
+                    pos += 2;
+                }
                 '{' if !template && chars.get(pos + 1) == Some(&'{') => {
                     // Handle start of template.
                     if !current.is_empty() {
@@ -692,24 +828,35 @@ impl<'input> Iterator for Lexer<'input> {
 // -----------------------------------------------------------------------------
 
 impl<'input> Lexer<'input> {
-    fn open(&mut self, start: usize, token: Token<&'input str>) -> Spanned<'input, usize> {
-        match &token {
-            Token::LParen => self.open_parens += 1,
-            Token::LBracket => self.open_brackets += 1,
-            Token::LBrace => self.open_braces += 1,
-            _ => {}
-        };
+    fn open(
+        &mut self,
+        start: usize,
+        delimiter: char,
+        token: Token<&'input str>,
+    ) -> SpannedResult<'input, usize> {
+        // Nested expressions are compiled and resolved recursively, so the
+        // nesting is limited to keep deeply nested programs from overflowing
+        // the stack.
+        if self.open_delimiters.len() >= MAX_NESTING_DEPTH {
+            return Err(Error::NestingDepth { start, delimiter });
+        }
+        self.open_delimiters.push((start, delimiter));
 
-        self.token(start, token)
+        Ok(self.token(start, token))
     }
 
-    fn close(&mut self, start: usize, token: Token<&'input str>) -> Spanned<'input, usize> {
-        match &token {
-            Token::RParen => self.open_parens = self.open_parens.saturating_sub(1),
-            Token::RBracket => self.open_brackets = self.open_brackets.saturating_sub(1),
-            Token::RBrace => self.open_braces = self.open_braces.saturating_sub(1),
-            _ => {}
-        };
+    fn close(
+        &mut self,
+        start: usize,
+        delimiter: char,
+        token: Token<&'input str>,
+    ) -> Spanned<'input, usize> {
+        // A mismatched closing delimiter is left for the parser to report.
+        if let Some((_, open)) = self.open_delimiters.last() {
+            if closing_delimiter(*open) == delimiter {
+                self.open_delimiters.pop();
+            }
+        }
 
         self.token(start, token)
     }
@@ -877,20 +1024,19 @@ impl<'input> Lexer<'input> {
                     while let Some((pos, ch)) = chars.peek() {
                         let pos = *pos;
 
-                        let literal_check = |result: Spanned<'input, usize>, chars: &mut Peekable<CharIndices<'input>>| {
-                            let (_, _, new) = result;
-
-                            #[allow(clippy::while_let_on_iterator)]
-                            while let Some((i, _)) = chars.next() {
-                                if i == new + pos {
-                                    break;
+                        let literal_check =
+                            |quote: usize, chars: &mut Peekable<CharIndices<'input>>| {
+                                #[allow(clippy::while_let_on_iterator)]
+                                while let Some((i, _)) = chars.next() {
+                                    if i == quote {
+                                        break;
+                                    }
                                 }
-                            }
-                            match chars.peek().map(|(_, ch)| ch) {
-                                Some(ch) => Ok(*ch),
-                                None => Err(()),
-                            }
-                        };
+                                match chars.peek().map(|(_, ch)| ch) {
+                                    Some(ch) => Ok(*ch),
+                                    None => Err(()),
+                                }
+                            };
 
                         let ch = match &self.input[pos..] {
                             s if s.starts_with('#') => {
@@ -909,7 +1055,7 @@ impl<'input> Lexer<'input> {
                                 }
                             }
                             s if s.starts_with('"') => {
-                                let r = Lexer::new(&self.input[pos + 1..]).string_literal(0)?;
+                                let r = self.lookahead_literal(pos, Lexer::string_literal)?;
                                 match literal_check(r, &mut chars) {
                                     Ok(ch) => ch,
                                     Err(()) => {
@@ -923,7 +1069,7 @@ impl<'input> Lexer<'input> {
                                 }
                             }
                             s if s.starts_with("s'") => {
-                                let r = Lexer::new(&self.input[pos + 1..]).raw_string_literal(0)?;
+                                let r = self.lookahead_literal(pos, Lexer::raw_string_literal)?;
                                 match literal_check(r, &mut chars) {
                                     Ok(ch) => ch,
                                     Err(()) => {
@@ -935,7 +1081,7 @@ impl<'input> Lexer<'input> {
                                 }
                             }
                             s if s.starts_with("r'") => {
-                                let r = Lexer::new(&self.input[pos + 1..]).regex_literal(0)?;
+                                let r = self.lookahead_literal(pos, Lexer::regex_literal)?;
                                 match literal_check(r, &mut chars) {
                                     Ok(ch) => ch,
                                     Err(()) => {
@@ -947,7 +1093,7 @@ impl<'input> Lexer<'input> {
                                 }
                             }
                             s if s.starts_with("t'") => {
-                                let r = Lexer::new(&self.input[pos + 1..]).timestamp_literal(0)?;
+                                let r = self.lookahead_literal(pos, Lexer::timestamp_literal)?;
                                 match literal_check(r, &mut chars) {
                                     Ok(ch) => ch,
                                     Err(()) => {
@@ -1031,6 +1177,21 @@ impl<'input> Lexer<'input> {
         Ok(true)
     }
 
+    /// Lexes the literal starting at `pos` without advancing this lexer, and
+    /// returns the index of its closing quote.
+    fn lookahead_literal(
+        &self,
+        pos: usize,
+        literal: fn(&mut Lexer<'input>, usize) -> SpannedResult<'input, usize>,
+    ) -> Result<usize, Error> {
+        let mut lexer = Lexer::new(&self.input[pos..]);
+        lexer.bump();
+
+        literal(&mut lexer, 0)
+            .map(|(_, _, end)| pos + end - 1)
+            .map_err(|error| error.offset(pos))
+    }
+
     fn string_literal(&mut self, start: usize) -> SpannedResult<'input, usize> {
         let content_start = self.next_index();
 
@@ -1054,15 +1215,17 @@ impl<'input> Lexer<'input> {
     }
 
     fn regex_literal(&mut self, start: usize) -> SpannedResult<'input, usize> {
-        self.quoted_literal(start, Token::RegexLiteral)
+        self.quoted_literal(start, "regex", Token::RegexLiteral)
     }
 
     fn raw_string_literal(&mut self, start: usize) -> SpannedResult<'input, usize> {
-        self.quoted_literal(start, |c| Token::RawStringLiteral(RawStringLiteralToken(c)))
+        self.quoted_literal(start, "raw string", |c| {
+            Token::RawStringLiteral(RawStringLiteralToken(c))
+        })
     }
 
     fn timestamp_literal(&mut self, start: usize) -> SpannedResult<'input, usize> {
-        self.quoted_literal(start, Token::TimestampLiteral)
+        self.quoted_literal(start, "timestamp", Token::TimestampLiteral)
     }
 
     fn numeric_literal_or_identifier(&mut self, start: usize) -> SpannedResult<'input, usize> {
@@ -1103,7 +1266,21 @@ impl<'input> Lexer<'input> {
     }
 
     fn identifier_or_function_call(&mut self, start: usize) -> Spanned<'input, usize> {
-        let (end, ident) = self.take_while(start, is_ident_continue);
+        let (mut end, mut ident) = self.take_while(start, is_ident_continue);
+
+        // A namespaced function call, e.g. `myco::lookup(...)`.
+        let rest = &self.input[end..];
+        if rest.starts_with("::") && rest[2..].chars().next().map_or(false, is_ident_start) {
+            self.bump();
+            self.bump();
+            (end, ident) = self.take_while(start, is_ident_continue);
+
+            if self.test_peek(|ch| ch == '(' || ch == '!') {
+                return (start, Token::FunctionCall(ident), end);
+            }
+
+            return (start, Token::InvalidToken(':'), end);
+        }
 
         let token = if self.test_peek(|ch| ch == '(' || ch == '!') {
             Token::FunctionCall(ident)
@@ -1130,6 +1307,7 @@ impl<'input> Lexer<'input> {
     fn quoted_literal(
         &mut self,
         start: usize,
+        kind: &'static str,
         tok: impl Fn(&'input str) -> Tok<'input>,
     ) -> SpannedResult<'input, usize> {
         self.bump();
@@ -1152,7 +1330,7 @@ impl<'input> Lexer<'input> {
             };
         }
 
-        Err(Error::Literal { start })
+        Err(Error::Literal { start, kind })
     }
 }
 
@@ -1165,11 +1343,9 @@ impl<'input> Lexer<'input> {
         Self {
             input,
             chars: input.char_indices().peekable(),
-            open_braces: 0,
-            open_brackets: 0,
-            open_parens: 0,
             rquery_indices: vec![],
             query_start: None,
+            open_delimiters: vec![],
         }
     }
 
@@ -1237,6 +1413,15 @@ impl<'input> Lexer<'input> {
 // generic helpers
 // -----------------------------------------------------------------------------
 
+fn closing_delimiter(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        ch => ch,
+    }
+}
+
 fn is_ident_start(ch: char) -> bool {
     matches!(ch, '@' | '_' | 'a'..='z' | 'A'..='Z')
 }
@@ -1262,15 +1447,15 @@ fn is_digit(ch: char) -> bool {
 pub(crate) fn is_operator(ch: char) -> bool {
     matches!(
         ch,
-        '!' | '&' | '*' | '+' | '-' | '/' | '<' | '=' | '>' | '?' | '|'
+        '!' | '&' | '*' | '+' | '-' | '/' | '<' | '=' | '>' | '?' | '^' | '|'
     )
 }
 
 fn unescape_string_literal(mut s: &str) -> String {
     let mut string = String::with_capacity(s.len());
     while let Some(i) = s.bytes().position(|b| b == b'\\') {
-        let next = s.as_bytes()[i + 1];
-        if next == b'\n' {
+        let next = s.as_bytes().get(i + 1).copied();
+        if next == Some(b'\n') {
             // Remove the \n and any ensuing spaces or tabs
             string.push_str(&s[..i]);
             let remaining = &s[i + 2..];
@@ -1282,15 +1467,23 @@ fn unescape_string_literal(mut s: &str) -> String {
             s = &s[i + whitespace + 2..];
         } else {
             let c = match next {
-                b'\'' => '\'',
-                b'"' => '"',
-                b'\\' => '\\',
-                b'n' => '\n',
-                b'r' => '\r',
-                b't' => '\t',
-                b'0' => '\0',
-                b'{' => '{',
-                _ => unimplemented!("invalid escape"),
+                Some(b'\'') => '\'',
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'0') => '\0',
+                Some(b'{') => '{',
+                Some(b'}') => '}',
+                _ => {
+                    // The lexer rejects any other escape, so this is only
+                    // reached for strings that weren't lexed. Keep the
+                    // backslash as is.
+                    string.push_str(&s[..=i]);
+                    s = &s[i + 1..];
+                    continue;
+                }
             };
 
             string.push_str(&s[..i]);
@@ -1310,7 +1503,7 @@ mod test {
     use super::super::lex::Token::{
         Arrow, Bang, Colon, Comma, Dot, Else, Equals, FloatLiteral, FunctionCall, Identifier, If,
         IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator, PathField, Percent,
-        RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral,
+        RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral, Tilde,
         TimestampLiteral, True,
     };
     use super::*;
@@ -1441,7 +1634,13 @@ mod test {
     #[test]
     fn unterminated_literal_errors() {
         let mut lexer = Lexer::new("a(m, r')");
-        assert_eq!(Some(Err(Error::Literal { start: 0 })), lexer.next());
+        assert_eq!(
+            Some(Err(Error::Literal {
+                start: 5,
+                kind: "regex"
+            })),
+            lexer.next()
+        );
     }
 
     #[test]
@@ -1452,7 +1651,7 @@ mod test {
         );
         assert_eq!(
             Some(Err(Error::EscapeChar {
-                start: 55,
+                start: 112,
                 ch: Some('[')
             })),
             lexer.next()
@@ -1532,7 +1731,10 @@ mod test {
     fn regex_literal_unterminated() {
         assert_eq!(
             lexer("r'foo bar").last(),
-            Some(Err(Error::Literal { start: 0 }))
+            Some(Err(Error::Literal {
+                start: 0,
+                kind: "regex"
+            }))
         );
     }
 
@@ -1551,7 +1753,10 @@ mod test {
     fn timestamp_literal_unterminated() {
         assert_eq!(
             lexer("t'foo").last(),
-            Some(Err(Error::Literal { start: 0 }))
+            Some(Err(Error::Literal {
+                start: 0,
+                kind: "timestamp"
+            }))
         );
     }
 
@@ -1573,7 +1778,10 @@ mod test {
     fn raw_string_literal_unterminated() {
         assert_eq!(
             lexer("s'foo").last(),
-            Some(Err(Error::Literal { start: 0 }))
+            Some(Err(Error::Literal {
+                start: 0,
+                kind: "raw string"
+            }))
         );
     }
 
@@ -1637,6 +1845,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn namespaced_function_calls() {
+        test(
+            data("myco::lookup() a::b!()"),
+            vec![
+                ("~~~~~~~~~~~~          ", FunctionCall("myco::lookup")),
+                ("            ~         ", LParen),
+                ("             ~        ", RParen),
+                ("               ~~~~   ", FunctionCall("a::b")),
+                ("                   ~  ", Bang),
+                ("                    ~ ", LParen),
+                ("                     ~", RParen),
+            ],
+        );
+    }
+
     #[test]
     fn single_query() {
         test(
@@ -2146,6 +2370,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn unescape_closing_brace() {
+        assert_eq!(StringLiteralToken(r"a\}b").unescape(), "a}b");
+    }
+
+    #[test]
+    fn template_after_escaped_backslash() {
+        let string = StringLiteralToken(r"\\{{ zonk }}");
+        assert_eq!(
+            TemplateString(vec![
+                StringSegment::Literal("\\".to_string(), Span::new(1, 3)),
+                StringSegment::Template("zonk".to_string(), Span::new(3, 13)),
+            ]),
+            string.template(Span::new(0, 14))
+        );
+    }
+
+    #[test]
+    fn nesting_depth() {
+        let input = "(".repeat(MAX_NESTING_DEPTH + 1);
+        assert_eq!(
+            lexer(&input).find(Result::is_err),
+            Some(Err(Error::NestingDepth {
+                start: MAX_NESTING_DEPTH,
+                delimiter: '(',
+            }))
+        );
+    }
+
     #[test]
     fn function_closure_no_arg() {
         test(
@@ -2201,4 +2454,23 @@ mod test {
             ],
         );
     }
+
+    #[test]
+    fn bitwise_operators() {
+        test(
+            data("~1 & 2 ^ 3 << 4 >> 5"),
+            vec![
+                ("~                   ", Tilde),
+                (" ~                  ", IntegerLiteral(1)),
+                ("   ~                ", Operator("&")),
+                ("     ~              ", IntegerLiteral(2)),
+                ("       ~            ", Operator("^")),
+                ("         ~          ", IntegerLiteral(3)),
+                ("           ~~       ", Operator("<<")),
+                ("              ~     ", IntegerLiteral(4)),
+                ("                ~~  ", Operator(">>")),
+                ("                   ~", IntegerLiteral(5)),
+            ],
+        );
+    }
 }
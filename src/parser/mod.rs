@@ -45,26 +45,32 @@ pub fn parse(input: impl AsRef<str>) -> Result<Program, Error> {
     let lexer = lex::Lexer::new(input.as_ref());
 
     parser::ProgramParser::new()
-        .parse(input.as_ref(), lexer)
-        .map_err(|source| Error::ParseError {
-            span: Span::new(0, input.as_ref().len()),
-            source: source
-                .map_token(|t| t.map(ToOwned::to_owned))
-                .map_error(|err| err.to_string()),
-            dropped_tokens: vec![],
-        })
+        .parse(input.as_ref(), &mut vec![], lexer)
+        .map_err(|source| parse_error(input.as_ref(), source))
 }
 
 pub fn parse_literal(input: impl AsRef<str>) -> Result<Literal, Error> {
     let lexer = lex::Lexer::new(input.as_ref());
 
     parser::LiteralParser::new()
-        .parse(input.as_ref(), lexer)
-        .map_err(|source| Error::ParseError {
-            span: Span::new(0, input.as_ref().len()),
+        .parse(input.as_ref(), &mut vec![], lexer)
+        .map_err(|source| parse_error(input.as_ref(), source))
+}
+
+fn parse_error(input: &str, source: lalrpop_util::ParseError<usize, lex::Tok<'_>, Error>) -> Error {
+    match source {
+        // Errors from the lexer already point at the offending input.
+        lalrpop_util::ParseError::User { error }
+            if !matches!(error, Error::UnexpectedParseError(_)) =>
+        {
+            error
+        }
+        source => Error::ParseError {
+            span: Span::new(0, input.len()),
             source: source
                 .map_token(|t| t.map(ToOwned::to_owned))
                 .map_error(|err| err.to_string()),
             dropped_tokens: vec![],
-        })
+        },
+    }
 }
@@ -0,0 +1,436 @@
+use crate::compiler::prelude::*;
+use std::borrow::Cow;
+
+/// Inputs larger than this are rejected to guard against pathological documents.
+const MAX_HTML_SIZE: usize = 1024 * 1024;
+
+/// Elements whose content is never rendered as text.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "template", "noscript"];
+
+/// Elements that separate the words of their content from the surrounding text.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "title",
+    "tr",
+    "ul",
+];
+
+#[derive(Debug, PartialEq)]
+struct Tag {
+    /// Lowercase tag name.
+    name: String,
+    closing: bool,
+    /// Attributes with lowercase names and decoded values.
+    attributes: Vec<(String, String)>,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Tag(Tag),
+}
+
+/// A tolerant HTML tokenizer. Malformed markup never fails: a `<` that doesn't
+/// start a tag is treated as text, and unterminated constructs run to the end of
+/// the input.
+struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+    /// Set after an opening raw text element, until its closing tag.
+    raw_text: Option<String>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            raw_text: None,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_past(&mut self, delimiter: &str) {
+        self.pos = self
+            .rest()
+            .find(delimiter)
+            .map_or(self.input.len(), |i| self.pos + i + delimiter.len());
+    }
+
+    fn tag(&mut self) -> Option<Tag> {
+        let rest = self.rest();
+        let (closing, body) = match rest[1..].strip_prefix('/') {
+            Some(body) => (true, body),
+            None => (false, &rest[1..]),
+        };
+        if !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let name_len = body
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .unwrap_or(body.len());
+        let name = body[..name_len].to_ascii_lowercase();
+        let mut attrs = &body[name_len..];
+        let mut attributes = Vec::new();
+
+        loop {
+            attrs = attrs.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+            if attrs.is_empty() || attrs.starts_with('>') {
+                break;
+            }
+
+            let key_len = attrs
+                .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+                .unwrap_or(attrs.len())
+                .max(1);
+            let key = attrs[..key_len].to_ascii_lowercase();
+            attrs = attrs[key_len..].trim_start();
+
+            let value = if let Some(value) = attrs.strip_prefix('=') {
+                let value = value.trim_start();
+                let (raw, remaining) = match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = value[1..].find(quote).map_or(value.len(), |i| i + 1);
+                        (&value[1..end], value.get(end + 1..).unwrap_or_default())
+                    }
+                    _ => {
+                        let end = value
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(value.len());
+                        value.split_at(end)
+                    }
+                };
+                attrs = remaining;
+                decode_entities(raw).into_owned()
+            } else {
+                String::new()
+            };
+
+            attributes.push((key, value));
+        }
+
+        let consumed = rest.len() - attrs.len();
+        self.pos += consumed + usize::from(attrs.starts_with('>'));
+
+        Some(Tag {
+            name,
+            closing,
+            attributes,
+        })
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return None;
+            }
+
+            if let Some(element) = self.raw_text.take() {
+                let end = find_closing_tag(rest, &element).unwrap_or(rest.len());
+                self.pos += end;
+                if end > 0 {
+                    return Some(Token::Text(&rest[..end]));
+                }
+                continue;
+            }
+
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.pos += end;
+                return Some(Token::Text(&rest[..end]));
+            }
+
+            if rest.starts_with("<!--") {
+                self.skip_past("-->");
+                continue;
+            }
+
+            if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.skip_past(">");
+                continue;
+            }
+
+            match self.tag() {
+                Some(tag) => {
+                    if !tag.closing
+                        && (RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) || tag.name == "title")
+                    {
+                        self.raw_text = Some(tag.name.clone());
+                    }
+                    return Some(Token::Tag(tag));
+                }
+                None => {
+                    self.pos += 1;
+                    return Some(Token::Text("<"));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the position of the closing tag of `element`, ignoring ASCII case.
+fn find_closing_tag(input: &str, element: &str) -> Option<usize> {
+    let needle = format!("</{element}");
+    input
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Decodes named and numeric character references. Unknown references are kept as-is.
+fn decode_entities(input: &str) -> Cow<'_, str> {
+    if !input.contains('&') {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 32)
+            .and_then(|end| decode_entity(&rest[1..=end]).map(|c| (c, end + 2)));
+
+        match decoded {
+            Some((c, len)) => {
+                output.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+
+    Cow::Owned(output)
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "middot" => '·',
+        "bull" => '•',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "cent" => '¢',
+        "sect" => '§',
+        "deg" => '°',
+        _ => return None,
+    })
+}
+
+/// Collapses runs of whitespace into a single space and trims the ends.
+fn collapse_whitespace(input: &str) -> String {
+    input.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn check_size(input: &[u8]) -> Result<(), ExpressionError> {
+    if input.len() > MAX_HTML_SIZE {
+        return Err(format!("input exceeds maximum size of {MAX_HTML_SIZE} bytes").into());
+    }
+    Ok(())
+}
+
+/// Removes all tags, comments and non-rendered content (such as scripts and
+/// styles) from an HTML fragment, and decodes character references.
+pub(crate) fn strip_html(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    check_size(&bytes)?;
+    let input = String::from_utf8_lossy(&bytes);
+
+    let mut text = String::with_capacity(input.len());
+    let mut skip_until: Option<String> = None;
+
+    for token in Tokenizer::new(&input) {
+        match token {
+            Token::Tag(tag) => {
+                if tag.closing && skip_until.as_deref() == Some(tag.name.as_str()) {
+                    skip_until = None;
+                } else if !tag.closing && RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) {
+                    skip_until = Some(tag.name.clone());
+                }
+                if BLOCK_ELEMENTS.contains(&tag.name.as_str()) {
+                    text.push(' ');
+                }
+            }
+            Token::Text(_) if skip_until.is_some() => {}
+            Token::Text(t) => text.push_str(&decode_entities(t)),
+        }
+    }
+
+    Ok(collapse_whitespace(&text).into())
+}
+
+/// Extracts the document title and `<meta>` tags from an HTML document.
+///
+/// Meta tags are keyed by their `name`, `property`, `http-equiv` or `itemprop`
+/// attribute, with the `content` attribute as value. A `<meta charset>` tag is
+/// returned under the `charset` key.
+pub(crate) fn parse_html_meta(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    check_size(&bytes)?;
+    let input = String::from_utf8_lossy(&bytes);
+
+    let mut title: Option<String> = None;
+    let mut in_title = false;
+    let mut meta = ObjectMap::new();
+
+    for token in Tokenizer::new(&input) {
+        match token {
+            Token::Tag(tag) if tag.name == "title" => {
+                in_title = !tag.closing && title.is_none();
+            }
+            Token::Tag(tag) if tag.name == "meta" && !tag.closing => {
+                if let Some(charset) = tag.attribute("charset") {
+                    meta.insert("charset".into(), charset.into());
+                }
+
+                let key = ["name", "property", "http-equiv", "itemprop"]
+                    .iter()
+                    .find_map(|attr| tag.attribute(attr));
+                if let (Some(key), Some(content)) = (key, tag.attribute("content")) {
+                    meta.entry(key.to_lowercase().into())
+                        .or_insert_with(|| content.into());
+                }
+            }
+            Token::Text(text) if in_title => {
+                title = Some(collapse_whitespace(&decode_entities(text)));
+                in_title = false;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Value::Object(ObjectMap::from([
+        ("title".into(), title.map_or(Value::Null, Into::into)),
+        ("meta".into(), Value::Object(meta)),
+    ])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_tolerates_malformed_markup() {
+        let tokens: Vec<_> = Tokenizer::new("a < b <p class=x id='y' hidden>c</P").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("a "),
+                Token::Text("<"),
+                Token::Text(" b "),
+                Token::Tag(Tag {
+                    name: "p".to_owned(),
+                    closing: false,
+                    attributes: vec![
+                        ("class".to_owned(), "x".to_owned()),
+                        ("id".to_owned(), "y".to_owned()),
+                        ("hidden".to_owned(), String::new()),
+                    ],
+                }),
+                Token::Text("c"),
+                Token::Tag(Tag {
+                    name: "p".to_owned(),
+                    closing: true,
+                    attributes: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;amp; &#65;&#x42; &unknown; & done"),
+            "<a> &amp; AB &unknown; & done"
+        );
+    }
+
+    #[test]
+    fn size_limit() {
+        let input = "a".repeat(MAX_HTML_SIZE + 1);
+        assert!(strip_html(input.into()).is_err());
+    }
+}
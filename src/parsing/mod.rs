@@ -1,3 +1,4 @@
+pub mod html;
 pub mod query_string;
 pub mod ruby_hash;
 pub mod xml;
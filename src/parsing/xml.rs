@@ -1,7 +1,7 @@
 use crate::compiler::prelude::*;
 use once_cell::sync::Lazy;
 use regex::{Regex, RegexBuilder};
-use roxmltree::{Document, Node, NodeType};
+use roxmltree::{Attribute, Document, Node, NodeType};
 use rust_decimal::prelude::Zero;
 use std::{
     borrow::Cow,
@@ -19,6 +19,7 @@ pub(crate) struct ParseOptions {
     pub(crate) parse_bool: Option<Value>,
     pub(crate) parse_null: Option<Value>,
     pub(crate) parse_number: Option<Value>,
+    pub(crate) keep_namespace_prefix: Option<Value>,
 }
 
 struct ParseXmlConfig<'a> {
@@ -36,6 +37,9 @@ struct ParseXmlConfig<'a> {
     parse_null: bool,
     /// Parse numeric values as integers/floats. Default: true.
     parse_number: bool,
+    /// Keep namespace prefixes in element and attribute names, e.g. `<soap:Body>` -> `soap:Body`.
+    /// Default: false.
+    keep_namespace_prefix: bool,
 }
 
 static XML_RE: Lazy<Regex> = Lazy::new(|| {
@@ -45,49 +49,13 @@ static XML_RE: Lazy<Regex> = Lazy::new(|| {
         .expect("trim regex failed")
 });
 
-pub(crate) fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
+pub(crate) fn parse_xml(value: Value, mut options: ParseOptions) -> Resolved {
     let string = value.try_bytes_utf8_lossy()?;
-    let trim = match options.trim {
+    let trim = match options.trim.take() {
         Some(value) => value.try_boolean()?,
         None => true,
     };
-    let include_attr = match options.include_attr {
-        Some(value) => value.try_boolean()?,
-        None => true,
-    };
-    let attr_prefix = match options.attr_prefix {
-        Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
-        None => Cow::from("@"),
-    };
-    let text_key = match options.text_key {
-        Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
-        None => Cow::from("text"),
-    };
-    let always_use_text_key = match options.always_use_text_key {
-        Some(value) => value.try_boolean()?,
-        None => false,
-    };
-    let parse_bool = match options.parse_bool {
-        Some(value) => value.try_boolean()?,
-        None => true,
-    };
-    let parse_null = match options.parse_null {
-        Some(value) => value.try_boolean()?,
-        None => true,
-    };
-    let parse_number = match options.parse_number {
-        Some(value) => value.try_boolean()?,
-        None => true,
-    };
-    let config = ParseXmlConfig {
-        include_attr,
-        attr_prefix,
-        text_key,
-        always_use_text_key,
-        parse_bool,
-        parse_null,
-        parse_number,
-    };
+    let config = ParseXmlConfig::try_from(options)?;
     // Trim whitespace around XML elements, if applicable.
     let parse = if trim { trim_xml(&string) } else { string };
     let doc = Document::parse(&parse).map_err(|e| format!("unable to parse xml: {e}"))?;
@@ -95,6 +63,84 @@ pub(crate) fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
     Ok(value)
 }
 
+impl TryFrom<ParseOptions> for ParseXmlConfig<'_> {
+    type Error = ExpressionError;
+
+    fn try_from(options: ParseOptions) -> Result<Self, Self::Error> {
+        let include_attr = match options.include_attr {
+            Some(value) => value.try_boolean()?,
+            None => true,
+        };
+        let attr_prefix = match options.attr_prefix {
+            Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
+            None => Cow::from("@"),
+        };
+        let text_key = match options.text_key {
+            Some(value) => Cow::from(value.try_bytes_utf8_lossy()?.into_owned()),
+            None => Cow::from("text"),
+        };
+        let always_use_text_key = match options.always_use_text_key {
+            Some(value) => value.try_boolean()?,
+            None => false,
+        };
+        let parse_bool = match options.parse_bool {
+            Some(value) => value.try_boolean()?,
+            None => true,
+        };
+        let parse_null = match options.parse_null {
+            Some(value) => value.try_boolean()?,
+            None => true,
+        };
+        let parse_number = match options.parse_number {
+            Some(value) => value.try_boolean()?,
+            None => true,
+        };
+        let keep_namespace_prefix = match options.keep_namespace_prefix {
+            Some(value) => value.try_boolean()?,
+            None => false,
+        };
+
+        Ok(Self {
+            include_attr,
+            attr_prefix,
+            text_key,
+            always_use_text_key,
+            parse_bool,
+            parse_null,
+            parse_number,
+            keep_namespace_prefix,
+        })
+    }
+}
+
+/// Returns the name of an element, including its namespace prefix if configured.
+fn element_name(node: Node, config: &ParseXmlConfig) -> String {
+    let name = node.tag_name();
+    qualified_name(
+        node,
+        name.namespace(),
+        name.name(),
+        config.keep_namespace_prefix,
+    )
+}
+
+/// Returns the name of an attribute, including its namespace prefix if configured.
+fn attribute_name(node: Node, attr: &Attribute, config: &ParseXmlConfig) -> String {
+    qualified_name(
+        node,
+        attr.namespace(),
+        attr.name(),
+        config.keep_namespace_prefix,
+    )
+}
+
+fn qualified_name(node: Node, namespace: Option<&str>, name: &str, keep_prefix: bool) -> String {
+    match namespace.and_then(|uri| node.lookup_prefix(uri)) {
+        Some(prefix) if keep_prefix => format!("{prefix}:{name}"),
+        _ => name.to_owned(),
+    }
+}
+
 /// Process an XML node, and return a VRL `Value`.
 fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
     // Helper to recurse over a `Node`s children, and build an object.
@@ -105,7 +151,12 @@ fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
         if config.include_attr {
             for attr in node.attributes() {
                 map.insert(
-                    format!("{}{}", config.attr_prefix, attr.name()).into(),
+                    format!(
+                        "{}{}",
+                        config.attr_prefix,
+                        attribute_name(node, &attr, config)
+                    )
+                    .into(),
                     attr.value().into(),
                 );
             }
@@ -113,7 +164,7 @@ fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
 
         for n in node.children().filter(|n| n.is_element() || n.is_text()) {
             let name = match n.node_type() {
-                NodeType::Element => n.tag_name().name().to_string().into(),
+                NodeType::Element => element_name(n, config).into(),
                 NodeType::Text => config.text_key.to_string().into(),
                 _ => unreachable!("shouldn't be other XML nodes"),
             };
@@ -170,7 +221,7 @@ fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
                             let mut map = BTreeMap::new();
 
                             map.insert(
-                                node.tag_name().name().to_string().into(),
+                                element_name(node, config).into(),
                                 process_node(node, config),
                             );
 
@@ -218,7 +269,419 @@ fn process_text<'a>(text: &'a str, config: &ParseXmlConfig<'a>) -> Value {
     }
 }
 
+/// A compiled expression in the XPath subset supported by `query_xml`.
+///
+/// Supported are absolute and relative location paths using the child (`/`) and
+/// descendant (`//`) axes, name tests (optionally namespace-prefixed), `*`, `.`, `..`,
+/// and a trailing `@attr`, `@*` or `text()` step. Element steps accept positional
+/// (`[1]`, `[last()]`), attribute (`[@id]`, `[@id='1']`) and child (`[name='x']`)
+/// predicates.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct XPath {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    descendant: bool,
+    test: NodeTest,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NodeTest {
+    /// An element name, or any element if `None`.
+    Element(Option<String>),
+    /// An attribute name, or any attribute if `None`.
+    Attribute(Option<String>),
+    Text,
+    Current,
+    Parent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Position(usize),
+    Last,
+    Attribute { name: String, value: Option<String> },
+    Child { name: String, value: Option<String> },
+}
+
+impl std::str::FromStr for XPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err("empty expression".to_owned());
+        }
+
+        let mut steps = Vec::new();
+        let mut descendant = if let Some(r) = rest.strip_prefix("//") {
+            rest = r;
+            true
+        } else {
+            rest = rest.strip_prefix('/').unwrap_or(rest);
+            false
+        };
+
+        loop {
+            let (test, r) = parse_node_test(rest)?;
+            rest = r;
+
+            let mut predicates = Vec::new();
+            while let Some(r) = rest.strip_prefix('[') {
+                let end = predicate_end(r).ok_or("unterminated predicate")?;
+                predicates.push(parse_predicate(&r[..end])?);
+                rest = &r[end + 1..];
+            }
+
+            let terminal = matches!(test, NodeTest::Attribute(_) | NodeTest::Text);
+            if terminal && !predicates.is_empty() {
+                return Err("predicates are only supported on element steps".to_owned());
+            }
+
+            steps.push(Step {
+                descendant,
+                test,
+                predicates,
+            });
+
+            if rest.is_empty() {
+                break;
+            }
+            if terminal {
+                return Err("attribute and text() steps must be the last step".to_owned());
+            }
+
+            descendant = if let Some(r) = rest.strip_prefix("//") {
+                rest = r;
+                true
+            } else if let Some(r) = rest.strip_prefix('/') {
+                rest = r;
+                false
+            } else {
+                return Err(format!("unexpected input '{rest}'"));
+            };
+        }
+
+        Ok(Self { steps })
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')
+}
+
+/// Splits a name off the start of `s`.
+fn take_name(s: &str) -> (&str, &str) {
+    let end = s.find(|c| !is_name_char(c)).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn parse_node_test(s: &str) -> Result<(NodeTest, &str), String> {
+    if let Some(rest) = s.strip_prefix("text()") {
+        return Ok((NodeTest::Text, rest));
+    }
+    if let Some(rest) = s.strip_prefix("..") {
+        return Ok((NodeTest::Parent, rest));
+    }
+    if let Some(rest) = s.strip_prefix('*') {
+        return Ok((NodeTest::Element(None), rest));
+    }
+    if let Some(rest) = s.strip_prefix('@') {
+        if let Some(rest) = rest.strip_prefix('*') {
+            return Ok((NodeTest::Attribute(None), rest));
+        }
+        return match take_name(rest) {
+            ("", _) => Err("expected attribute name after '@'".to_owned()),
+            (name, rest) => Ok((NodeTest::Attribute(Some(name.to_owned())), rest)),
+        };
+    }
+    match take_name(s) {
+        (".", rest) => Ok((NodeTest::Current, rest)),
+        ("", _) => Err(format!("expected a step at '{s}'")),
+        (name, rest) => Ok((NodeTest::Element(Some(name.to_owned())), rest)),
+    }
+}
+
+/// Returns the position of the `]` closing a predicate, skipping over quoted literals.
+fn predicate_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, ']') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_predicate(s: &str) -> Result<Predicate, String> {
+    let s = s.trim();
+    if s == "last()" {
+        return Ok(Predicate::Last);
+    }
+    if let Ok(position) = s.parse::<usize>() {
+        return match position {
+            0 => Err("positions start at 1".to_owned()),
+            position => Ok(Predicate::Position(position)),
+        };
+    }
+
+    let (attribute, s) = match s.strip_prefix('@') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let (name, rest) = take_name(s);
+    if name.is_empty() {
+        return Err(format!("unsupported predicate '{s}'"));
+    }
+    let value = match rest.trim_start() {
+        "" => None,
+        rest => {
+            let literal = rest
+                .strip_prefix('=')
+                .map(str::trim)
+                .ok_or_else(|| format!("unsupported predicate '{s}'"))?;
+            let unquoted = literal
+                .strip_prefix('\'')
+                .and_then(|l| l.strip_suffix('\''))
+                .or_else(|| literal.strip_prefix('"').and_then(|l| l.strip_suffix('"')))
+                .ok_or_else(|| format!("expected a quoted literal, got '{literal}'"))?;
+            Some(unquoted.to_owned())
+        }
+    };
+
+    let name = name.to_owned();
+    Ok(if attribute {
+        Predicate::Attribute { name, value }
+    } else {
+        Predicate::Child { name, value }
+    })
+}
+
+/// Matches `name` against a node or attribute name. Prefixed names (`soap:Body`) only
+/// match if the prefix resolves to the same namespace in the document.
+fn name_matches(node: Node, namespace: Option<&str>, local: &str, name: &str) -> bool {
+    match name.split_once(':') {
+        Some((prefix, name)) => {
+            name == local && namespace.and_then(|uri| node.lookup_prefix(uri)) == Some(prefix)
+        }
+        None => name == local,
+    }
+}
+
+fn element_matches(node: Node, name: &str) -> bool {
+    let tag = node.tag_name();
+    name_matches(node, tag.namespace(), tag.name(), name)
+}
+
+fn predicate_matches(node: Node, predicate: &Predicate) -> bool {
+    match predicate {
+        Predicate::Attribute { name, value } => node.attributes().any(|attr| {
+            name_matches(node, attr.namespace(), attr.name(), name)
+                && value.as_ref().map_or(true, |v| attr.value() == v)
+        }),
+        Predicate::Child { name, value } => node
+            .children()
+            .filter(|child| child.is_element() && element_matches(*child, name))
+            .any(|child| {
+                value
+                    .as_ref()
+                    .map_or(true, |v| child.text().unwrap_or_default() == v)
+            }),
+        Predicate::Position(_) | Predicate::Last => true,
+    }
+}
+
+/// Applies a step's predicates, in order, to the candidates of a single context node.
+fn apply_predicates<'a, 'input>(
+    mut nodes: Vec<Node<'a, 'input>>,
+    predicates: &[Predicate],
+) -> Vec<Node<'a, 'input>> {
+    for predicate in predicates {
+        nodes = match predicate {
+            Predicate::Position(position) => {
+                nodes.into_iter().nth(position - 1).into_iter().collect()
+            }
+            Predicate::Last => nodes.pop().into_iter().collect(),
+            predicate => nodes
+                .into_iter()
+                .filter(|node| predicate_matches(*node, predicate))
+                .collect(),
+        };
+    }
+    nodes
+}
+
+/// Evaluates `xpath` against an XML document and returns all matches. Matched
+/// elements are converted as `parse_xml` would with its default options, while
+/// attributes and text nodes are returned as strings.
+pub(crate) fn query_xml(value: Value, xpath: &XPath) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let config = ParseXmlConfig::try_from(ParseOptions::default())?;
+    let parse = trim_xml(&string);
+    let doc = Document::parse(&parse).map_err(|e| format!("unable to parse xml: {e}"))?;
+
+    let mut context = vec![doc.root()];
+    for step in &xpath.steps {
+        match &step.test {
+            NodeTest::Attribute(name) => {
+                let values = context
+                    .iter()
+                    .flat_map(|node| {
+                        if step.descendant {
+                            node.descendants().collect()
+                        } else {
+                            vec![*node]
+                        }
+                    })
+                    .flat_map(|node| {
+                        node.attributes()
+                            .filter(move |attr| {
+                                name.as_ref().map_or(true, |name| {
+                                    name_matches(node, attr.namespace(), attr.name(), name)
+                                })
+                            })
+                            .map(|attr| Value::from(attr.value()))
+                    })
+                    .collect();
+                return Ok(Value::Array(values));
+            }
+            NodeTest::Text => {
+                let values = context
+                    .iter()
+                    .flat_map(|node| {
+                        if step.descendant {
+                            node.descendants().skip(1).collect::<Vec<_>>()
+                        } else {
+                            node.children().collect()
+                        }
+                    })
+                    .filter(Node::is_text)
+                    .filter_map(|node| node.text())
+                    .map(Value::from)
+                    .collect();
+                return Ok(Value::Array(values));
+            }
+            test => {
+                let mut next: Vec<Node> = Vec::new();
+                for node in &context {
+                    let candidates = match test {
+                        NodeTest::Current => vec![*node],
+                        NodeTest::Parent => node.parent().into_iter().collect(),
+                        NodeTest::Element(name) => {
+                            let matches = |n: &Node| {
+                                n.is_element()
+                                    && name.as_ref().map_or(true, |name| element_matches(*n, name))
+                            };
+                            if step.descendant {
+                                node.descendants().skip(1).filter(matches).collect()
+                            } else {
+                                node.children().filter(matches).collect()
+                            }
+                        }
+                        NodeTest::Attribute(_) | NodeTest::Text => unreachable!("handled above"),
+                    };
+                    for candidate in apply_predicates(candidates, &step.predicates) {
+                        if !next.contains(&candidate) {
+                            next.push(candidate);
+                        }
+                    }
+                }
+                context = next;
+            }
+        }
+    }
+
+    Ok(Value::Array(
+        context
+            .into_iter()
+            .map(|node| process_node(node, &config))
+            .collect(),
+    ))
+}
+
 #[inline]
 fn trim_xml(xml: &str) -> Cow<str> {
     XML_RE.replace_all(xml, "><")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn parse_xpath() {
+        let xpath: XPath = "//book[@lang='en'][2]/title/text()".parse().unwrap();
+        assert_eq!(
+            xpath.steps,
+            vec![
+                Step {
+                    descendant: true,
+                    test: NodeTest::Element(Some("book".to_owned())),
+                    predicates: vec![
+                        Predicate::Attribute {
+                            name: "lang".to_owned(),
+                            value: Some("en".to_owned()),
+                        },
+                        Predicate::Position(2),
+                    ],
+                },
+                Step {
+                    descendant: false,
+                    test: NodeTest::Element(Some("title".to_owned())),
+                    predicates: vec![],
+                },
+                Step {
+                    descendant: false,
+                    test: NodeTest::Text,
+                    predicates: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_xpath_errors() {
+        for (xpath, error) in [
+            ("", "empty expression"),
+            (
+                "/a/@id/b",
+                "attribute and text() steps must be the last step",
+            ),
+            ("/a[1", "unterminated predicate"),
+            ("/a[0]", "positions start at 1"),
+            ("/a[@id=1]", "expected a quoted literal, got '1'"),
+            (
+                "/a/text()[1]",
+                "predicates are only supported on element steps",
+            ),
+            ("/a/", "expected a step at ''"),
+        ] {
+            assert_eq!(xpath.parse::<XPath>().unwrap_err(), error, "{xpath}");
+        }
+    }
+
+    #[test]
+    fn keep_namespace_prefix() {
+        let xml = r#"<soap:Envelope xmlns:soap="urn:soap" xmlns="urn:default"><soap:Body a:id="1" xmlns:a="urn:a"><item>x</item></soap:Body></soap:Envelope>"#;
+        let options = ParseOptions {
+            keep_namespace_prefix: Some(true.into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse_xml(xml.into(), options).unwrap(),
+            value!({ "soap:Envelope": { "soap:Body": { "@a:id": "1", "item": "x" } } })
+        );
+        assert_eq!(
+            parse_xml(xml.into(), ParseOptions::default()).unwrap(),
+            value!({ "Envelope": { "Body": { "@id": "1", "item": "x" } } })
+        );
+    }
+}
@@ -103,6 +103,30 @@ impl OwnedValuePath {
     pub fn push(&mut self, segment: OwnedSegment) {
         self.segments.push(segment);
     }
+
+    /// Parses a value path, rejecting the lenient syntax that [`parse_value_path`] accepts for
+    /// backwards compatibility. The path is either empty or `.` for the root, or a list of
+    /// segments, optionally starting with a `.`:
+    ///
+    /// - A field is either unquoted, consisting of one or more of `A-Z`, `a-z`, `0-9`, `_` and
+    ///   `@`, or quoted with `"`, in which case it may contain any character, where `"` and `\`
+    ///   must be escaped with a `\`. Fields other than the first are preceded by a `.`.
+    /// - An index is an integer in square brackets, such as `[0]` or `[-1]`, that fits an
+    ///   `isize`. It isn't preceded by a `.`.
+    ///
+    /// The display output of any path is parsed back into the same path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path isn't valid strict path syntax.
+    pub fn parse_strict(src: &str) -> Result<Self, PathParseError> {
+        let segments = src.strip_prefix('.').unwrap_or(src);
+        parse_segments_strict(segments)
+            .map(Self::from)
+            .ok_or_else(|| PathParseError::InvalidPathSyntax {
+                path: src.to_owned(),
+            })
+    }
 }
 
 // OwnedValuePath values must have at least one segment.
@@ -180,15 +204,47 @@ impl OwnedTargetPath {
             path: new_path,
         }
     }
+
+    /// Parses a target path, which must start with either `.` or `%`, followed by a value path
+    /// in the syntax of [`OwnedValuePath::parse_strict`] without its optional leading `.`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path isn't valid strict path syntax.
+    pub fn parse_strict(src: &str) -> Result<Self, PathParseError> {
+        let mut chars = src.chars();
+        let prefix = match chars.next() {
+            Some('.') => Some(PathPrefix::Event),
+            Some('%') => Some(PathPrefix::Metadata),
+            _ => None,
+        };
+
+        prefix
+            .zip(parse_segments_strict(chars.as_str()))
+            .map(|(prefix, segments)| Self {
+                prefix,
+                path: segments.into(),
+            })
+            .ok_or_else(|| PathParseError::InvalidPathSyntax {
+                path: src.to_owned(),
+            })
+    }
+
+    /// Serializes the path such that [`OwnedTargetPath::parse_strict`] parses it back into the
+    /// same path, quoting and escaping fields as needed. This is also the display output of the
+    /// path.
+    pub fn to_string_lossless(&self) -> String {
+        let prefix = match self.prefix {
+            PathPrefix::Event => ".",
+            PathPrefix::Metadata => "%",
+        };
+        format!("{prefix}{}", String::from(&self.path))
+    }
 }
 
 impl Display for OwnedTargetPath {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.prefix {
-            PathPrefix::Event => write!(f, ".")?,
-            PathPrefix::Metadata => write!(f, "%")?,
-        }
-        Display::fmt(&self.path, f)
+        f.write_str(&self.to_string_lossless())
     }
 }
 
@@ -293,10 +349,7 @@ impl From<&OwnedValuePath> for String {
 
 fn serialize_field(string: &mut String, field: &str, separator: Option<&str>) {
     // These characters should match the ones from the parser, implemented in `JitLookup`
-    let needs_quotes = field.is_empty()
-        || field
-            .chars()
-            .any(|c| !matches!(c, 'A'..='Z' | 'a'..='z' | '_' | '0'..='9' | '@'));
+    let needs_quotes = field.is_empty() || !field.chars().all(is_unquoted_field_char);
 
     // Reserve enough to fit the field, a `.` and two `"` characters. This
     // should suffice for the majority of cases when no escape sequence is used.
@@ -319,6 +372,71 @@ fn serialize_field(string: &mut String, field: &str, separator: Option<&str>) {
     }
 }
 
+/// Parses the segments of a path in the syntax of [`OwnedValuePath::parse_strict`], without its
+/// optional leading `.`.
+fn parse_segments_strict(src: &str) -> Option<Vec<OwnedSegment>> {
+    let mut chars = src.chars().peekable();
+    let mut segments = vec![];
+
+    while let Some(c) = chars.next() {
+        let segment = match c {
+            '[' => {
+                let mut index = String::new();
+                loop {
+                    match chars.next()? {
+                        ']' => break,
+                        c => index.push(c),
+                    }
+                }
+                // `isize` accepts a leading `+`, which isn't valid path syntax.
+                if index.starts_with('+') {
+                    return None;
+                }
+                OwnedSegment::Index(index.parse().ok()?)
+            }
+            '.' if !segments.is_empty() => parse_field_strict(chars.next()?, &mut chars)?,
+            c if segments.is_empty() => parse_field_strict(c, &mut chars)?,
+            _ => return None,
+        };
+        segments.push(segment);
+    }
+
+    Some(segments)
+}
+
+fn parse_field_strict(
+    first: char,
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Option<OwnedSegment> {
+    let mut field = String::new();
+
+    if first == '"' {
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => match chars.next()? {
+                    c @ ('"' | '\\') => field.push(c),
+                    _ => return None,
+                },
+                c => field.push(c),
+            }
+        }
+    } else if is_unquoted_field_char(first) {
+        field.push(first);
+        while let Some(c) = chars.next_if(|c| is_unquoted_field_char(*c)) {
+            field.push(c);
+        }
+    } else {
+        return None;
+    }
+
+    Some(OwnedSegment::Field(field.into()))
+}
+
+fn is_unquoted_field_char(c: char) -> bool {
+    matches!(c, 'A'..='Z' | 'a'..='z' | '_' | '0'..='9' | '@')
+}
+
 impl From<Vec<OwnedSegment>> for OwnedValuePath {
     fn from(segments: Vec<OwnedSegment>) -> Self {
         Self { segments }
@@ -419,7 +537,14 @@ fn format_field(f: &mut Formatter<'_>, field: &str) -> fmt::Result {
     // should be quoted
     let needs_quotes = !VALID_FIELD.is_match(field);
     if needs_quotes {
-        write!(f, "\"{field}\"")
+        f.write_char('"')?;
+        for c in field.chars() {
+            if matches!(c, '"' | '\\') {
+                f.write_char('\\')?;
+            }
+            f.write_char(c)?;
+        }
+        f.write_char('"')
     } else {
         write!(f, "{field}")
     }
@@ -449,6 +574,7 @@ impl<'a> Iterator for OwnedSegmentSliceIter<'a> {
 mod test {
     use super::*;
     use crate::path::parse_value_path;
+    use crate::{owned_event_path, owned_value_path};
 
     #[test]
     fn owned_path_serialize() {
@@ -493,6 +619,55 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_strict() {
+        let valid = [
+            ("", owned_value_path!()),
+            (".", owned_value_path!()),
+            ("foo", owned_value_path!("foo")),
+            (".foo.bar", owned_value_path!("foo", "bar")),
+            ("[0].foo[-1]", owned_value_path!(0, "foo", -1)),
+            (r#"."a\"b"."c\\d""#, owned_value_path!("a\"b", "c\\d")),
+            (r#""""#, owned_value_path!("")),
+            ("[-9223372036854775808]", owned_value_path!(isize::MIN)),
+        ];
+        for (src, expected) in valid {
+            assert_eq!(OwnedValuePath::parse_strict(src), Ok(expected), "{src}");
+        }
+
+        for src in [
+            "..",
+            "foo-bar",
+            "foo.[0]",
+            "[0]foo",
+            "foo.",
+            "[1",
+            "[+1]",
+            "[99999999999999999999]",
+            r#""a\b""#,
+            r#""a"#,
+            "foo bar",
+        ] {
+            assert!(OwnedValuePath::parse_strict(src).is_err(), "{src}");
+        }
+
+        assert_eq!(
+            OwnedTargetPath::parse_strict("%"),
+            Ok(OwnedTargetPath::metadata_root())
+        );
+        assert_eq!(
+            OwnedTargetPath::parse_strict(".foo"),
+            Ok(owned_event_path!("foo"))
+        );
+        assert!(OwnedTargetPath::parse_strict("foo").is_err());
+        assert!(OwnedTargetPath::parse_strict("..foo").is_err());
+    }
+
+    #[test]
+    fn segment_display_escapes_quotes() {
+        assert_eq!(OwnedSegment::field("a\"b").to_string(), r#""a\"b""#);
+    }
+
     fn reparse_thing<T: std::fmt::Debug + std::fmt::Display + Eq + FromStr>(thing: T)
     where
         <T as FromStr>::Err: std::fmt::Debug,
@@ -512,5 +687,21 @@ mod test {
         fn reparses_valid_target_path(path: OwnedTargetPath) {
             reparse_thing(path);
         }
+
+        #[test]
+        fn strictly_reparses_value_path(path: OwnedValuePath) {
+            prop_assert_eq!(OwnedValuePath::parse_strict(&path.to_string()), Ok(path));
+        }
+
+        #[test]
+        fn strictly_reparses_target_path(path: OwnedTargetPath) {
+            prop_assert_eq!(OwnedTargetPath::parse_strict(&path.to_string_lossless()), Ok(path));
+        }
+
+        #[test]
+        fn parse_strict_never_panics(src in ".*") {
+            let _ = OwnedValuePath::parse_strict(&src);
+            let _ = OwnedTargetPath::parse_strict(&src);
+        }
     }
 }
@@ -0,0 +1,152 @@
+//! Python bindings, built with [pyo3](https://pyo3.rs).
+//!
+//! The module exposes `compile(source)`, which returns a `Program`, and
+//! `Program.resolve(event, metadata=None)`, which runs the program and returns a
+//! dict with the resulting `event` and the `result` of the last expression.
+//! Events are converted between Python objects and [`Value`]s, with `datetime`s
+//! mapping to timestamps and `bytes` to byte strings.
+//!
+//! To build an importable extension module, enable the `python` feature along
+//! with `pyo3/extension-module`, for example with
+//! `maturin build --features python,pyo3/extension-module`.
+
+use std::collections::BTreeMap;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use crate::compiler::runtime::Runtime;
+use crate::compiler::state::RuntimeState;
+use crate::compiler::{compile as compile_program, Program, TargetValue, TimeZone};
+use crate::diagnostic::Formatter;
+use crate::value::{Secrets, Value};
+
+create_exception!(
+    vrl,
+    CompileError,
+    PyException,
+    "A VRL program failed to compile."
+);
+create_exception!(
+    vrl,
+    ResolveError,
+    PyException,
+    "A VRL program failed at runtime."
+);
+
+/// A compiled VRL program.
+#[pyclass(name = "Program", module = "vrl", frozen)]
+pub struct PyProgram {
+    program: Program,
+}
+
+#[pymethods]
+impl PyProgram {
+    /// Runs the program against `event`, and optionally `metadata`, both dicts.
+    #[pyo3(signature = (event, metadata = None))]
+    fn resolve(&self, py: Python<'_>, event: Value, metadata: Option<Value>) -> PyResult<PyObject> {
+        let mut target = TargetValue {
+            value: event,
+            metadata: metadata.unwrap_or_else(|| Value::Object(BTreeMap::new())),
+            secrets: Secrets::new(),
+        };
+
+        let result = py
+            .allow_threads(|| {
+                Runtime::new(RuntimeState::default()).resolve(
+                    &mut target,
+                    &self.program,
+                    &TimeZone::default(),
+                )
+            })
+            .map_err(|err| ResolveError::new_err(err.to_string()))?;
+
+        let output = Value::Object(BTreeMap::from([
+            ("event".into(), target.value),
+            ("result".into(), result),
+        ]));
+        Ok(output.into_py(py))
+    }
+}
+
+/// Compiles a VRL program using the full standard library.
+#[pyfunction]
+fn compile(source: &str) -> PyResult<PyProgram> {
+    compile_program(source, &crate::stdlib::all())
+        .map(|result| PyProgram {
+            program: result.program,
+        })
+        .map_err(|diagnostics| {
+            CompileError::new_err(Formatter::new(source, diagnostics).to_string())
+        })
+}
+
+/// The `vrl` Python module.
+#[pymodule]
+fn vrl(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyProgram>()?;
+    m.add_function(wrap_pyfunction!(compile, m)?)?;
+    m.add("CompileError", m.py().get_type_bound::<CompileError>())?;
+    m.add("ResolveError", m.py().get_type_bound::<ResolveError>())?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::types::PyDict;
+
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn compile_and_resolve() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let program = compile(".b = .a * 2\n.b + 1").unwrap();
+            let output = program
+                .resolve(py, value!({"a": 21}), None)
+                .unwrap()
+                .extract::<Value>(py)
+                .unwrap();
+            assert_eq!(output, value!({"event": {"a": 21, "b": 42}, "result": 43}));
+        });
+    }
+
+    #[test]
+    fn metadata() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let program = compile("%source").unwrap();
+            let output = program
+                .resolve(py, value!({}), Some(value!({"source": "test"})))
+                .unwrap();
+            let output = output.downcast_bound::<PyDict>(py).unwrap();
+            let result: String = output
+                .get_item("result")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(result, "test");
+        });
+    }
+
+    #[test]
+    fn errors() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let err = compile("upcase(").err().unwrap();
+            assert!(err.is_instance_of::<CompileError>(py));
+            assert!(err.to_string().contains("syntax error"));
+
+            let program = compile("parse_json!(.message)").unwrap();
+            let err = program
+                .resolve(py, value!({"message": "{"}), None)
+                .unwrap_err();
+            assert!(err.is_instance_of::<ResolveError>(py));
+            assert!(err.to_string().contains("unable to parse json"));
+        });
+    }
+}
@@ -0,0 +1,381 @@
+use crate::compiler::prelude::*;
+use crate::path::{OwnedSegment, OwnedValuePath};
+
+/// The operations of a patch.
+pub(super) const OPERATIONS: [&str; 6] = ["add", "remove", "replace", "move", "copy", "test"];
+
+fn apply_patch(value: Value, patch: Value) -> Resolved {
+    apply_operations(value, patch, |_, path| {
+        OwnedValuePath::parse_strict(path).map_err(|_| format!(r#"invalid path "{path}""#))
+    })
+}
+
+/// Applies the operations of a patch in order, where `resolve_path` resolves the paths of the
+/// operations against the value as it is when the operation is applied.
+pub(super) fn apply_operations(
+    value: Value,
+    patch: Value,
+    resolve_path: impl Fn(&Value, &str) -> Result<OwnedValuePath, String>,
+) -> Resolved {
+    let mut value = value;
+    for (index, operation) in patch.try_array()?.into_iter().enumerate() {
+        apply(&mut value, operation, &resolve_path)
+            .map_err(|err| format!("patch operation {index}: {err}"))?;
+    }
+    Ok(value)
+}
+
+fn apply(
+    value: &mut Value,
+    operation: Value,
+    resolve_path: impl Fn(&Value, &str) -> Result<OwnedValuePath, String>,
+) -> Result<(), String> {
+    let Value::Object(mut operation) = operation else {
+        return Err("must be an object".to_owned());
+    };
+    let raw_path = string_field(&operation, "path")?;
+    let at = |reason: &str| format!("{raw_path} {reason}");
+
+    match string_field(&operation, "op")?.as_str() {
+        "add" => {
+            let new = required_field(&mut operation, "value")?;
+            let path = resolve_path(value, &raw_path)?;
+            add(value, &path, new).map_err(at)
+        }
+        "remove" => {
+            let path = resolve_path(value, &raw_path)?;
+            remove(value, &path).map(drop).map_err(at)
+        }
+        "replace" => {
+            let new = required_field(&mut operation, "value")?;
+            let path = resolve_path(value, &raw_path)?;
+            *get_mut(value, &path).map_err(at)? = new;
+            Ok(())
+        }
+        "move" => {
+            let raw_from = string_field(&operation, "from")?;
+            let from = resolve_path(value, &raw_from)?;
+            let path = resolve_path(value, &raw_path)?;
+            if from == path {
+                return Ok(());
+            }
+            if path.segments.starts_with(&from.segments) {
+                return Err(format!("cannot move {raw_from} into itself"));
+            }
+            let moved = remove(value, &from).map_err(|reason| format!("{raw_from} {reason}"))?;
+            // The path is resolved again once the value is removed, like the path of an "add".
+            let path = resolve_path(value, &raw_path)?;
+            add(value, &path, moved).map_err(at)
+        }
+        "copy" => {
+            let raw_from = string_field(&operation, "from")?;
+            let from = resolve_path(value, &raw_from)?;
+            let copied = get_mut(value, &from)
+                .map_err(|reason| format!("{raw_from} {reason}"))?
+                .clone();
+            let path = resolve_path(value, &raw_path)?;
+            add(value, &path, copied).map_err(at)
+        }
+        "test" => {
+            let expected = required_field(&mut operation, "value")?;
+            let path = resolve_path(value, &raw_path)?;
+            if *get_mut(value, &path).map_err(at)? == expected {
+                Ok(())
+            } else {
+                Err(format!(
+                    "value at {raw_path} doesn't match the tested value"
+                ))
+            }
+        }
+        op => Err(format!(r#"unknown operation "{op}""#)),
+    }
+}
+
+/// Rejects a patch at compile time if it's a constant with an invalid operation, where
+/// `is_valid_path` checks the syntax of the paths of the operations.
+pub(super) fn check_operations(
+    keyword: &'static str,
+    patch: &dyn Expression,
+    state: &TypeState,
+    is_valid_path: impl Fn(&str) -> bool,
+) -> Result<(), function::Error> {
+    let Some(Value::Array(operations)) = patch.resolve_constant(state) else {
+        return Ok(());
+    };
+
+    for operation in operations {
+        let error = match &operation {
+            Value::Object(object) => check_operation(object, &is_valid_path),
+            _ => Some("patch operations must be objects"),
+        };
+        if let Some(error) = error {
+            return Err(function::Error::InvalidArgument {
+                keyword,
+                value: operation,
+                error,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn check_operation(
+    operation: &ObjectMap,
+    is_valid_path: impl Fn(&str) -> bool,
+) -> Option<&'static str> {
+    let Ok(op) = string_field(operation, "op") else {
+        return Some("missing op in patch operation");
+    };
+    if !OPERATIONS.contains(&op.as_str()) {
+        return Some("unknown patch operation");
+    }
+
+    let mut paths = vec!["path"];
+    if matches!(op.as_str(), "move" | "copy") {
+        paths.push("from");
+    }
+    for field in paths {
+        match string_field(operation, field) {
+            Ok(path) if is_valid_path(&path) => {}
+            Ok(_) => return Some("invalid path in patch operation"),
+            Err(_) => return Some("missing path in patch operation"),
+        }
+    }
+
+    if matches!(op.as_str(), "add" | "replace" | "test") && !operation.contains_key("value") {
+        return Some("missing value in patch operation");
+    }
+
+    None
+}
+
+fn string_field(operation: &ObjectMap, field: &str) -> Result<String, String> {
+    match operation.get(field) {
+        Some(Value::Bytes(bytes)) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        Some(_) => Err(format!(r#""{field}" must be a string"#)),
+        None => Err(format!(r#"missing "{field}""#)),
+    }
+}
+
+fn required_field(operation: &mut ObjectMap, field: &str) -> Result<Value, String> {
+    operation
+        .remove(field)
+        .ok_or_else(|| format!(r#"missing "{field}""#))
+}
+
+fn get_mut<'a>(value: &'a mut Value, path: &OwnedValuePath) -> Result<&'a mut Value, &'static str> {
+    value.get_mut(path).ok_or("doesn't exist")
+}
+
+/// Splits the path into the parent that contains the last segment, and the last segment.
+fn parent_of<'a>(
+    value: &'a mut Value,
+    path: &OwnedValuePath,
+) -> Result<(&'a mut Value, OwnedSegment), &'static str> {
+    let mut parent = path.clone();
+    let last = parent
+        .segments
+        .pop()
+        .ok_or("is the root, which can't be removed")?;
+    let parent = value
+        .get_mut(&parent)
+        .ok_or("has a parent that doesn't exist")?;
+    Ok((parent, last))
+}
+
+/// Resolves an index, which counts from the end of the array if it's negative.
+fn array_index(array: &[Value], index: isize) -> Option<usize> {
+    if index >= 0 {
+        usize::try_from(index).ok()
+    } else {
+        array.len().checked_sub(index.unsigned_abs())
+    }
+}
+
+fn add(value: &mut Value, path: &OwnedValuePath, new: Value) -> Result<(), &'static str> {
+    if path.is_root() {
+        *value = new;
+        return Ok(());
+    }
+
+    match parent_of(value, path)? {
+        (Value::Object(object), OwnedSegment::Field(field)) => {
+            object.insert(field, new);
+            Ok(())
+        }
+        (Value::Array(array), OwnedSegment::Index(index)) => {
+            let index = array_index(array, index)
+                .filter(|index| *index <= array.len())
+                .ok_or("is out of bounds")?;
+            array.insert(index, new);
+            Ok(())
+        }
+        _ => Err("has a parent that isn't an object or array"),
+    }
+}
+
+fn remove(value: &mut Value, path: &OwnedValuePath) -> Result<Value, &'static str> {
+    let removed = match parent_of(value, path)? {
+        (Value::Object(object), OwnedSegment::Field(field)) => object.remove(&field),
+        (Value::Array(array), OwnedSegment::Index(index)) => array_index(array, index)
+            .filter(|index| *index < array.len())
+            .map(|index| array.remove(index)),
+        _ => None,
+    };
+
+    removed.ok_or("doesn't exist")
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ApplyPatch;
+
+impl Function for ApplyPatch {
+    fn identifier(&self) -> &'static str {
+        "apply_patch"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "patch",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "apply a diff",
+                source: r#"apply_patch!({"a": 1}, diff({"a": 1}, {"a": 2, "b": [3]}))"#,
+                result: Ok(r#"{"a": 2, "b": [3]}"#),
+            },
+            Example {
+                title: "move a field",
+                source: r#"apply_patch!({"a": {"b": 1}}, [{"op": "move", "from": ".a.b", "path": ".c"}])"#,
+                result: Ok(r#"{"a": {}, "c": 1}"#),
+            },
+            Example {
+                title: "failed test",
+                source: r#"apply_patch!({"a": 1}, [{"op": "test", "path": ".a", "value": 2}])"#,
+                result: Err(
+                    r#"function call error for "apply_patch" at (0:66): patch operation 0: value at .a doesn't match the tested value"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let patch = arguments.required("patch");
+
+        check_operations("patch", &*patch, state, |path| {
+            OwnedValuePath::parse_strict(path).is_ok()
+        })?;
+
+        Ok(ApplyPatchFn { value, patch }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ApplyPatchFn {
+    value: Box<dyn Expression>,
+    patch: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ApplyPatchFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let patch = self.patch.resolve(ctx)?;
+
+        apply_patch(value, patch)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        apply_patch => ApplyPatch;
+
+        add_and_replace {
+            args: func_args![
+                value: value!({"a": [1, 3]}),
+                patch: value!([
+                    {"op": "add", "path": ".a[1]", "value": 2},
+                    {"op": "replace", "path": ".a[-1]", "value": 4},
+                    {"op": "add", "path": ".b", "value": true},
+                ]),
+            ],
+            want: Ok(value!({"a": [1, 2, 4], "b": true})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        remove_and_copy {
+            args: func_args![
+                value: value!({"a": {"b": 1}, "c": [1, 2]}),
+                patch: value!([
+                    {"op": "copy", "from": ".a", "path": ".d"},
+                    {"op": "remove", "path": ".a.b"},
+                    {"op": "remove", "path": ".c[0]"},
+                ]),
+            ],
+            want: Ok(value!({"a": {}, "c": [2], "d": {"b": 1}})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        replace_root {
+            args: func_args![
+                value: value!({"a": 1}),
+                patch: value!([{"op": "replace", "path": ".", "value": "a"}]),
+            ],
+            want: Ok(value!("a")),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        missing_path {
+            args: func_args![
+                value: value!({}),
+                patch: value!([{"op": "remove", "path": ".a"}]),
+            ],
+            want: Err("patch operation 0: .a doesn't exist"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        move_into_itself {
+            args: func_args![
+                value: value!({"a": {}}),
+                patch: value!([{"op": "move", "from": ".a", "path": ".a.b"}]),
+            ],
+            want: Err("patch operation 0: cannot move .a into itself"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        unknown_operation {
+            args: func_args![
+                value: value!({}),
+                patch: value!([{"op": "merge", "path": "."}]),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,194 @@
+use crate::compiler::prelude::*;
+
+/// The longest array that `array_fill` pads to.
+const MAX_LENGTH: usize = 1_000_000;
+
+fn fill_length(length: i64) -> Result<usize, &'static str> {
+    match usize::try_from(length) {
+        Ok(length) if length <= MAX_LENGTH => Ok(length),
+        Ok(_) => Err(r#""length" must be at most 1000000"#),
+        Err(_) => Err(r#""length" must be at least 0"#),
+    }
+}
+
+fn array_fill(value: Value, length: Value, fill: Value) -> Resolved {
+    let mut array = value.try_array()?;
+    let length = length.try_integer()?;
+    let length = fill_length(length).map_err(|error| format!("{error}, got {length}"))?;
+
+    if array.len() < length {
+        array.resize(length, fill);
+    }
+
+    Ok(array.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ArrayFill;
+
+impl Function for ArrayFill {
+    fn identifier(&self) -> &'static str {
+        "array_fill"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "length",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "fill",
+                kind: kind::ANY,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "pad with null",
+                source: r"array_fill([1, 2], 4)",
+                result: Ok("[1, 2, null, null]"),
+            },
+            Example {
+                title: "pad with a value",
+                source: r#"array_fill(["a"], 3, fill: "b")"#,
+                result: Ok(r#"["a", "b", "b"]"#),
+            },
+            Example {
+                title: "longer array",
+                source: r"array_fill([1, 2, 3], 2)",
+                result: Ok("[1, 2, 3]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let length = arguments.required("length");
+        let fill = arguments.optional("fill").unwrap_or(expr!(null));
+
+        if let Some(literal) = length.resolve_constant(state) {
+            if let Some(Err(error)) = literal.as_integer().map(fill_length) {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "length",
+                    value: literal,
+                    error,
+                }
+                .into());
+            }
+        }
+
+        Ok(ArrayFillFn {
+            value,
+            length,
+            fill,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArrayFillFn {
+    value: Box<dyn Expression>,
+    length: Box<dyn Expression>,
+    fill: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ArrayFillFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let length = self.length.resolve(ctx)?;
+        let fill = self.fill.resolve(ctx)?;
+
+        array_fill(value, length, fill)
+    }
+
+    fn type_def(&self, state: &TypeState) -> TypeDef {
+        let fill = self.fill.type_def(state).kind().clone().upgrade_undefined();
+        let mut typedef = self.value.type_def(state).restrict_array();
+
+        let array = typedef.as_array_mut().expect("must be an array");
+        array.set_unknown(array.unknown_kind().union(fill));
+
+        let not_literal = self.length.resolve_constant(state).is_none();
+        typedef.maybe_fallible(not_literal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::btreemap;
+    use crate::value;
+
+    test_function![
+        array_fill => ArrayFill;
+
+        pads_with_null {
+            args: func_args![value: value!([1]), length: 3],
+            want: Ok(value!([1, null, null])),
+            tdef: TypeDef::array(Collection::from_parts(
+                btreemap! {
+                    Index::from(0) => Kind::integer(),
+                },
+                Kind::null(),
+            )),
+        }
+
+        pads_with_fill {
+            args: func_args![value: value!([]), length: 2, fill: "a"],
+            want: Ok(value!(["a", "a"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        keeps_longer_array {
+            args: func_args![value: value!([1, 2]), length: 1],
+            want: Ok(value!([1, 2])),
+            tdef: TypeDef::array(Collection::from_parts(
+                btreemap! {
+                    Index::from(0) => Kind::integer(),
+                    Index::from(1) => Kind::integer(),
+                },
+                Kind::null(),
+            )),
+        }
+
+        too_long {
+            args: func_args![value: value!([]), length: 1_000_001],
+            want: Err("invalid argument"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::null())),
+        }
+    ];
+
+    #[test]
+    fn limits_length_from_event() {
+        let resolve = |length: i64| {
+            let source = "array_fill([], int!(.length)) ?? []";
+            let program = crate::compiler::compile(source, &crate::stdlib::all())
+                .unwrap()
+                .program;
+            let mut target = value!({ "length": length });
+
+            crate::compiler::runtime::Runtime::default()
+                .resolve(&mut target, &program, &TimeZone::default())
+                .unwrap()
+        };
+
+        assert_eq!(resolve(2), value!([null, null]));
+        assert_eq!(resolve(9_223_372_036_854_775_807), value!([]));
+    }
+}
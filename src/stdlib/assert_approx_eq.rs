@@ -0,0 +1,161 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::expect_number;
+
+const DEFAULT_EPSILON: f64 = 1e-9;
+
+fn assert_approx_eq(left: Value, right: Value, epsilon: Value, message: Option<Value>) -> Resolved {
+    expect_number(&left)?;
+    expect_number(&right)?;
+    expect_number(&epsilon)?;
+
+    let difference = (left.try_into_f64()? - right.try_into_f64()?).abs();
+    if difference <= epsilon.try_into_f64()? {
+        Ok(true.into())
+    } else if let Some(message) = message {
+        let message = message.try_bytes_utf8_lossy()?.into_owned();
+        Err(ExpressionError::Error {
+            message: message.clone(),
+            labels: vec![],
+            notes: vec![Note::UserErrorMessage(message)],
+        })
+    } else {
+        Err(ExpressionError::from(format!(
+            "assertion failed: {left} ≈ {right} (difference {difference} exceeds {epsilon})"
+        )))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AssertApproxEq;
+
+impl Function for AssertApproxEq {
+    fn identifier(&self) -> &'static str {
+        "assert_approx_eq"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "left",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "right",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "epsilon",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: false,
+            },
+            Parameter {
+                keyword: "message",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "success",
+                source: "assert_approx_eq!(0.1 + 0.2, 0.3)",
+                result: Ok("true"),
+            },
+            Example {
+                title: "custom epsilon",
+                source: "assert_approx_eq!(99.5, 100, epsilon: 1)",
+                result: Ok("true"),
+            },
+            Example {
+                title: "failure",
+                source: "assert_approx_eq!(1.5, 1)",
+                result: Err(
+                    r#"function call error for "assert_approx_eq" at (0:25): assertion failed: 1.5 ≈ 1 (difference 0.5 exceeds 0.000000001)"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let left = arguments.required("left");
+        let right = arguments.required("right");
+        let epsilon = arguments
+            .optional("epsilon")
+            .unwrap_or_else(|| Value::from_f64_or_zero(DEFAULT_EPSILON).into_expression());
+        let message = arguments.optional("message");
+
+        Ok(AssertApproxEqFn {
+            left,
+            right,
+            epsilon,
+            message,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AssertApproxEqFn {
+    left: Box<dyn Expression>,
+    right: Box<dyn Expression>,
+    epsilon: Box<dyn Expression>,
+    message: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for AssertApproxEqFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let left = self.left.resolve(ctx)?;
+        let right = self.right.resolve(ctx)?;
+        let epsilon = self.epsilon.resolve(ctx)?;
+        let message = self.message.as_ref().map(|m| m.resolve(ctx)).transpose()?;
+
+        assert_approx_eq(left, right, epsilon, message)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        assert_approx_eq => AssertApproxEq;
+
+        pass {
+            args: func_args![left: 0.1 + 0.2, right: 0.3],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        pass_integer {
+            args: func_args![left: 10, right: 10.4, epsilon: 0.5],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        fail {
+            args: func_args![left: 1.5, right: 1],
+            want: Err("assertion failed: 1.5 ≈ 1 (difference 0.5 exceeds 0.000000001)"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        message {
+            args: func_args![left: 1.5, right: 1, message: "failure!"],
+            want: Err("failure!"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,182 @@
+use crate::compiler::prelude::*;
+
+/// Returns whether `expected` is contained in `value`.
+///
+/// Objects contain the fields of `expected` with values that contain the
+/// expected ones, arrays contain elements that contain each expected element,
+/// and any other value must be equal.
+fn contains(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::Object(value), Value::Object(expected)) => {
+            expected.iter().all(|(key, expected)| {
+                value
+                    .get(key)
+                    .is_some_and(|value| contains(value, expected))
+            })
+        }
+        (Value::Array(value), Value::Array(expected)) => expected
+            .iter()
+            .all(|expected| value.iter().any(|value| contains(value, expected))),
+        (Value::Array(value), expected) => value.iter().any(|value| contains(value, expected)),
+        (value, expected) => value == expected,
+    }
+}
+
+fn assert_contains(value: &Value, expected: &Value, message: Option<Value>) -> Resolved {
+    if contains(value, expected) {
+        Ok(true.into())
+    } else if let Some(message) = message {
+        let message = message.try_bytes_utf8_lossy()?.into_owned();
+        Err(ExpressionError::Error {
+            message: message.clone(),
+            labels: vec![],
+            notes: vec![Note::UserErrorMessage(message)],
+        })
+    } else {
+        Err(ExpressionError::from(format!(
+            "assertion failed: {value} contains {expected}"
+        )))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AssertContains;
+
+impl Function for AssertContains {
+    fn identifier(&self) -> &'static str {
+        "assert_contains"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "expected",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "message",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object subset",
+                source: r#"assert_contains!({"status": 200, "http": {"method": "GET", "path": "/"}}, {"http": {"method": "GET"}})"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "array element",
+                source: r#"assert_contains!(["web", "prod"], "prod")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "failure",
+                source: r#"assert_contains!({"status": 200}, {"status": 404})"#,
+                result: Err(
+                    r#"function call error for "assert_contains" at (0:50): assertion failed: { "status": 200 } contains { "status": 404 }"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let expected = arguments.required("expected");
+        let message = arguments.optional("message");
+
+        Ok(AssertContainsFn {
+            value,
+            expected,
+            message,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AssertContainsFn {
+    value: Box<dyn Expression>,
+    expected: Box<dyn Expression>,
+    message: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for AssertContainsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let expected = self.expected.resolve(ctx)?;
+        let message = self.message.as_ref().map(|m| m.resolve(ctx)).transpose()?;
+
+        assert_contains(&value, &expected, message)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        assert_contains => AssertContains;
+
+        object_subset {
+            args: func_args![
+                value: value!({"foo": {"bar": 1, "baz": [1, 2, 3]}, "qux": true}),
+                expected: value!({"foo": {"baz": [3, 1]}}),
+            ],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        array_of_objects {
+            args: func_args![
+                value: value!([{"name": "a", "value": 1}, {"name": "b", "value": 2}]),
+                expected: value!({"name": "b"}),
+            ],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        equal_scalars {
+            args: func_args![value: "foo", expected: "foo"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        missing_field {
+            args: func_args![value: value!({"foo": 1}), expected: value!({"bar": 1})],
+            want: Err(r#"assertion failed: { "foo": 1 } contains { "bar": 1 }"#),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        missing_element {
+            args: func_args![value: value!([1, 2]), expected: value!([3])],
+            want: Err("assertion failed: [1, 2] contains [3]"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        message {
+            args: func_args![value: "foo", expected: "bar", message: "failure!"],
+            want: Err("failure!"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,223 @@
+use crate::compiler::prelude::*;
+
+const TYPES: &[&str] = &[
+    "string",
+    "integer",
+    "float",
+    "boolean",
+    "timestamp",
+    "regex",
+    "null",
+    "array",
+    "object",
+];
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) => "string",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "float",
+        Value::Boolean(_) => "boolean",
+        Value::Timestamp(_) => "timestamp",
+        Value::Regex(_) => "regex",
+        Value::Null => "null",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Splits a type such as `"string | null"` into the names of its types.
+fn parse_types(types: &str) -> Result<Vec<&'static str>, &'static str> {
+    types
+        .split('|')
+        .map(|name| {
+            let name = name.trim();
+            if name == "any" {
+                Ok(TYPES)
+            } else {
+                TYPES
+                    .iter()
+                    .find(|&&ty| ty == name)
+                    .map(std::slice::from_ref)
+                    .ok_or("unknown type")
+            }
+        })
+        .try_fold(Vec::new(), |mut names, result| {
+            names.extend_from_slice(result?);
+            Ok(names)
+        })
+}
+
+fn assert_type(value: &Value, types: &[&str], message: Option<Value>) -> Resolved {
+    let actual = type_name(value);
+    if types.contains(&actual) {
+        Ok(true.into())
+    } else if let Some(message) = message {
+        let message = message.try_bytes_utf8_lossy()?.into_owned();
+        Err(ExpressionError::Error {
+            message: message.clone(),
+            labels: vec![],
+            notes: vec![Note::UserErrorMessage(message)],
+        })
+    } else {
+        Err(ExpressionError::from(format!(
+            "assertion failed: expected {}, got {actual}",
+            types.join(" | ")
+        )))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AssertType;
+
+impl Function for AssertType {
+    fn identifier(&self) -> &'static str {
+        "assert_type"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "type",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "message",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "success",
+                source: r#"assert_type!(42, "integer")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "one of several types",
+                source: r#"assert_type!(null, "string | null")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "failure",
+                source: r#"assert_type!("42", "integer")"#,
+                result: Err(
+                    r#"function call error for "assert_type" at (0:29): assertion failed: expected integer, got string"#,
+                ),
+            },
+            Example {
+                title: "custom message",
+                source: r#"assert_type!(.id, "integer", s'id must be an integer')"#,
+                result: Err(
+                    r#"function call error for "assert_type" at (0:54): id must be an integer"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let message = arguments.optional("message");
+
+        let types_expr = arguments.required_expr("type");
+        let types = types_expr
+            .resolve_constant(state)
+            .and_then(|types| types.try_bytes_utf8_lossy().ok().map(String::from))
+            .ok_or(function::Error::ExpectedStaticExpression {
+                keyword: "type",
+                expr: types_expr.clone(),
+            })?;
+        let types = parse_types(&types).map_err(|error| function::Error::InvalidArgument {
+            keyword: "type",
+            value: types.clone().into(),
+            error,
+        })?;
+
+        Ok(AssertTypeFn {
+            value,
+            types,
+            message,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AssertTypeFn {
+    value: Box<dyn Expression>,
+    types: Vec<&'static str>,
+    message: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for AssertTypeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let message = self.message.as_ref().map(|m| m.resolve(ctx)).transpose()?;
+
+        assert_type(&value, &self.types, message)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        assert_type => AssertType;
+
+        pass {
+            args: func_args![value: 42, type: "integer"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        pass_union {
+            args: func_args![value: value!({"foo": "bar"}), type: "array | object"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        pass_any {
+            args: func_args![value: value!(null), type: "any"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        fail {
+            args: func_args![value: "foo", type: "integer | float"],
+            want: Err("assertion failed: expected integer | float, got string"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        message {
+            args: func_args![value: "foo", type: "integer", message: "failure!"],
+            want: Err("failure!"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        unknown_type {
+            args: func_args![value: "foo", type: "int"],
+            want: Err("invalid argument"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
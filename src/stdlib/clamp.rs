@@ -0,0 +1,179 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, numeric_kind};
+
+fn check_bounds(min: &Value, max: &Value) -> Result<(), ExpressionError> {
+    expect_number(min)?;
+    expect_number(max)?;
+
+    let inverted = match (min, max) {
+        (Value::Integer(min), Value::Integer(max)) => min > max,
+        (min, max) => min.try_into_f64()? > max.try_into_f64()?,
+    };
+    if inverted {
+        return Err(format!("min {min} is greater than max {max}").into());
+    }
+    Ok(())
+}
+
+fn clamp(value: Value, min: Value, max: Value) -> Resolved {
+    expect_number(&value)?;
+    check_bounds(&min, &max)?;
+
+    match (value, min, max) {
+        (Value::Integer(value), Value::Integer(min), Value::Integer(max)) => {
+            Ok(value.clamp(min, max).into())
+        }
+        (value, min, max) => Ok(Value::from_f64_or_zero(
+            value
+                .try_into_f64()?
+                .clamp(min.try_into_f64()?, max.try_into_f64()?),
+        )),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Clamp;
+
+impl Function for Clamp {
+    fn identifier(&self) -> &'static str {
+        "clamp"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "min",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "max",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "clamp to max",
+                source: "clamp(150, 0, 100)",
+                result: Ok("100"),
+            },
+            Example {
+                title: "clamp float",
+                source: "clamp(-0.5, 0.0, 1.0)",
+                result: Ok("0.0"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let min = arguments.required("min");
+        let max = arguments.required("max");
+
+        // The call can only fail on invalid bounds if they aren't known at compile time.
+        let infallible = match (min.resolve_constant(state), max.resolve_constant(state)) {
+            (Some(min), Some(max)) => check_bounds(&min, &max).is_ok(),
+            _ => false,
+        };
+
+        Ok(ClampFn {
+            value,
+            min,
+            max,
+            infallible,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ClampFn {
+    value: Box<dyn Expression>,
+    min: Box<dyn Expression>,
+    max: Box<dyn Expression>,
+    infallible: bool,
+}
+
+impl FunctionExpression for ClampFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let min = self.min.resolve(ctx)?;
+        let max = self.max.resolve(ctx)?;
+
+        clamp(value, min, max)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let kind = numeric_kind([
+            self.value.type_def(state).kind(),
+            self.min.type_def(state).kind(),
+            self.max.type_def(state).kind(),
+        ]);
+
+        TypeDef::from(kind).maybe_fallible(!self.infallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        clamp => Clamp;
+
+        within {
+            args: func_args![value: 5, min: 0, max: 10],
+            want: Ok(5),
+            tdef: TypeDef::integer(),
+        }
+
+        below {
+            args: func_args![value: -5, min: 0, max: 10],
+            want: Ok(0),
+            tdef: TypeDef::integer(),
+        }
+
+        above {
+            args: func_args![value: 5.5, min: 0.0, max: 2.5],
+            want: Ok(2.5),
+            tdef: TypeDef::float(),
+        }
+
+        mixed {
+            args: func_args![value: 5, min: 0, max: 2.5],
+            want: Ok(2.5),
+            tdef: TypeDef::float(),
+        }
+
+        mixed_within {
+            args: func_args![value: 1, min: 0.5, max: 2],
+            want: Ok(1.0),
+            tdef: TypeDef::float(),
+        }
+
+        invalid_bounds {
+            args: func_args![value: 5, min: 10, max: 0],
+            want: Err("min 10 is greater than max 0"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -1,9 +1,123 @@
 use std::net::IpAddr;
 
-use community_id::calculate_community_id;
+use base64::Engine as _;
+use sha1::{Digest, Sha1};
 
 use crate::compiler::prelude::*;
 
+const ICMP: u8 = 1;
+const TCP: u8 = 6;
+const UDP: u8 = 17;
+const ICMPV6: u8 = 58;
+const SCTP: u8 = 132;
+
+/// The only version of the Community ID spec published so far.
+const VERSION: i64 = 1;
+
+/// Returns the message type answering the given ICMP or ICMPv6 message type, if
+/// the message is part of a request/response pair.
+fn icmp_counterpart(protocol: u8, message_type: u16) -> Option<u16> {
+    let counterpart = match (protocol, message_type) {
+        // Echo, router discovery, timestamp, information and address mask
+        (ICMP, 8) => 0,
+        (ICMP, 0) => 8,
+        (ICMP, 10) => 9,
+        (ICMP, 9) => 10,
+        (ICMP, 13) => 14,
+        (ICMP, 14) => 13,
+        (ICMP, 15) => 16,
+        (ICMP, 16) => 15,
+        (ICMP, 17) => 18,
+        (ICMP, 18) => 17,
+        // Echo, multicast listener, router and neighbor discovery, node
+        // information and home agent address discovery
+        (ICMPV6, 128) => 129,
+        (ICMPV6, 129) => 128,
+        (ICMPV6, 130) => 131,
+        (ICMPV6, 131) => 130,
+        (ICMPV6, 133) => 134,
+        (ICMPV6, 134) => 133,
+        (ICMPV6, 135) => 136,
+        (ICMPV6, 136) => 135,
+        (ICMPV6, 139) => 140,
+        (ICMPV6, 140) => 139,
+        (ICMPV6, 144) => 145,
+        (ICMPV6, 145) => 144,
+        _ => return None,
+    };
+
+    Some(counterpart)
+}
+
+fn ip_octets(ip: IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    }
+}
+
+/// Computes a version 1 Community ID, as described in
+/// <https://github.com/corelight/community-id-spec>.
+fn calculate_community_id(
+    seed: u16,
+    src_ip: IpAddr,
+    dst_ip: IpAddr,
+    src_port: Option<u16>,
+    dst_port: Option<u16>,
+    protocol: u8,
+) -> Result<String, &'static str> {
+    if src_ip.is_ipv4() != dst_ip.is_ipv4() {
+        return Err("source and destination IP addresses must be of the same family");
+    }
+
+    let mut ports = match (protocol, src_port, dst_port) {
+        (TCP | UDP | SCTP, Some(src_port), Some(dst_port)) => Some((src_port, dst_port)),
+        (TCP | UDP | SCTP, _, _) => {
+            return Err("src port and dst port should be set when protocol is tcp/udp/sctp")
+        }
+        (ICMP | ICMPV6, Some(src_port), Some(dst_port)) => Some((src_port, dst_port)),
+        _ => None,
+    };
+
+    // For ICMP the ports are the message type and code. Messages that are part of
+    // a request/response pair use the counterpart type in place of the code, so
+    // both directions hash the same. Any other message is one-way.
+    let mut one_way = false;
+    if let Some((message_type, code)) = &mut ports {
+        if matches!(protocol, ICMP | ICMPV6) {
+            match icmp_counterpart(protocol, *message_type) {
+                Some(counterpart) => *code = counterpart,
+                None => one_way = true,
+            }
+        }
+    }
+
+    let mut src = ip_octets(src_ip);
+    let mut dst = ip_octets(dst_ip);
+    let ordered = one_way
+        || src < dst
+        || (src == dst && ports.is_some_and(|(src_port, dst_port)| src_port < dst_port));
+    if !ordered {
+        std::mem::swap(&mut src, &mut dst);
+        ports = ports.map(|(src_port, dst_port)| (dst_port, src_port));
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(seed.to_be_bytes());
+    hasher.update(&src);
+    hasher.update(&dst);
+    hasher.update([protocol, 0]);
+    if let Some((src_port, dst_port)) = ports {
+        hasher.update(src_port.to_be_bytes());
+        hasher.update(dst_port.to_be_bytes());
+    }
+
+    Ok(format!(
+        "{VERSION}:{}",
+        base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+    ))
+}
+
 fn community_id(
     src_ip: Value,
     dst_ip: Value,
@@ -11,7 +125,16 @@ fn community_id(
     src_port: Option<Value>,
     dst_port: Option<Value>,
     seed: Option<Value>,
+    version: Option<Value>,
 ) -> Resolved {
+    let version = version
+        .map(VrlValueConvert::try_integer)
+        .transpose()?
+        .unwrap_or(VERSION);
+    if version != VERSION {
+        return Err(format!("unsupported community ID version: {version}").into());
+    }
+
     let src_ip: IpAddr = src_ip
         .try_bytes_utf8_lossy()?
         .parse()
@@ -49,12 +172,9 @@ fn community_id(
         .map_or(Ok(0), u16::try_from)
         .map_err(|err| format!("seed must be between 0 and 65535: {err}"))?;
 
-    let id = calculate_community_id(seed, src_ip, dst_ip, src_port, dst_port, protocol, false);
+    let id = calculate_community_id(seed, src_ip, dst_ip, src_port, dst_port, protocol)?;
 
-    match id {
-        Ok(id) => Ok(Value::Bytes(id.into())),
-        Err(err) => Err(ExpressionError::from(err.to_string())),
-    }
+    Ok(Value::Bytes(id.into()))
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -97,6 +217,11 @@ impl Function for CommunityID {
                 kind: kind::INTEGER,
                 required: false,
             },
+            Parameter {
+                keyword: "version",
+                kind: kind::INTEGER,
+                required: false,
+            },
         ]
     }
 
@@ -117,6 +242,16 @@ impl Function for CommunityID {
                 source: r#"community_id!(source_ip: "1.2.3.4", destination_ip: "5.6.7.8", source_port: 8, destination_port: 0, protocol: 1)"#,
                 result: Ok("1:crodRHL2FEsHjbv3UkRrfbs4bZ0="),
             },
+            Example {
+                title: "ICMPv6",
+                source: r#"community_id!(source_ip: "fe80::200:86ff:fe05:80da", destination_ip: "fe80::260:97ff:fe07:69ea", source_port: 135, destination_port: 0, protocol: 58)"#,
+                result: Ok("1:dGHyGvjMfljg6Bppwm3bg0LO8TY="),
+            },
+            Example {
+                title: "SCTP",
+                source: r#"community_id!(source_ip: "1.2.3.4", destination_ip: "5.6.7.8", source_port: 1122, destination_port: 3344, protocol: 132)"#,
+                result: Ok("1:EKt4MsxuyaE6mL+hmrEkQ9csDD8="),
+            },
             Example {
                 title: "RSVP",
                 source: r#"community_id!(source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 46)"#,
@@ -137,6 +272,7 @@ impl Function for CommunityID {
         let src_port = arguments.optional("source_port");
         let dst_port = arguments.optional("destination_port");
         let seed = arguments.optional("seed");
+        let version = arguments.optional("version");
 
         if let Some(protocol) = protocol.resolve_constant(state) {
             if let Some(protocol_literal) = protocol.as_integer() {
@@ -196,6 +332,22 @@ impl Function for CommunityID {
             }
         }
 
+        if let Some(version) = &version {
+            if let Some(version) = version.resolve_constant(state) {
+                if version
+                    .as_integer()
+                    .is_some_and(|version| version != VERSION)
+                {
+                    return Err(function::Error::InvalidArgument {
+                        keyword: "version",
+                        value: version,
+                        error: r#""version" must be 1"#,
+                    }
+                    .into());
+                }
+            }
+        }
+
         Ok(CommunityIDFn {
             src_ip,
             dst_ip,
@@ -203,6 +355,7 @@ impl Function for CommunityID {
             src_port,
             dst_port,
             seed,
+            version,
         }
         .as_expr())
     }
@@ -216,6 +369,7 @@ struct CommunityIDFn {
     src_port: Option<Box<dyn Expression>>,
     dst_port: Option<Box<dyn Expression>>,
     seed: Option<Box<dyn Expression>>,
+    version: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for CommunityIDFn {
@@ -242,7 +396,13 @@ impl FunctionExpression for CommunityIDFn {
             .map(|expr| expr.resolve(ctx))
             .transpose()?;
 
-        community_id(src_ip, dst_ip, protocol, src_port, dst_port, seed)
+        let version = self
+            .version
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        community_id(src_ip, dst_ip, protocol, src_port, dst_port, seed, version)
     }
 
     fn type_def(&self, _state: &state::TypeState) -> TypeDef {
@@ -329,6 +489,90 @@ mod tests {
             tdef: TypeDef::bytes().fallible(),
         }
 
+        icmp_echo_reply {
+            args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 1, source_port: 0, destination_port: 0],
+            want: Ok("1:crodRHL2FEsHjbv3UkRrfbs4bZ0="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_one_way {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 1, source_port: 3, destination_port: 1],
+            want: Ok("1:W4aFotGvd33My5d10oV5afM5O+E="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_one_way_reverse {
+            args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 1, source_port: 3, destination_port: 1],
+            want: Ok("1:8LPUHMfboj3XbJKoR1RD6x9ML9Y="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6_neighbor_solicitation {
+            args: func_args![source_ip: "fe80::200:86ff:fe05:80da", destination_ip: "fe80::260:97ff:fe07:69ea", protocol: 58, source_port: 135, destination_port: 0],
+            want: Ok("1:dGHyGvjMfljg6Bppwm3bg0LO8TY="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6_neighbor_advertisement {
+            args: func_args![source_ip: "fe80::260:97ff:fe07:69ea", destination_ip: "fe80::200:86ff:fe05:80da", protocol: 58, source_port: 136, destination_port: 0],
+            want: Ok("1:dGHyGvjMfljg6Bppwm3bg0LO8TY="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6_echo_request {
+            args: func_args![source_ip: "3ffe:507:0:1:200:86ff:fe05:80da", destination_ip: "3ffe:507:0:1:260:97ff:fe07:69ea", protocol: 58, source_port: 128, destination_port: 0],
+            want: Ok("1:GpbEQrKqfWtsfsFiqg8fufoZe5Y="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6_echo_reply {
+            args: func_args![source_ip: "3ffe:507:0:1:260:97ff:fe07:69ea", destination_ip: "3ffe:507:0:1:200:86ff:fe05:80da", protocol: 58, source_port: 129, destination_port: 0],
+            want: Ok("1:GpbEQrKqfWtsfsFiqg8fufoZe5Y="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6_one_way {
+            args: func_args![source_ip: "3ffe:507:0:1:200:86ff:fe05:80da", destination_ip: "3ffe:507:0:1:260:97ff:fe07:69ea", protocol: 58, source_port: 1, destination_port: 4],
+            want: Ok("1:VwoIG0r7uyeOsf+DOYL4alb3u4o="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        sctp_default_seed {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 132, source_port: 1122, destination_port: 3344],
+            want: Ok("1:EKt4MsxuyaE6mL+hmrEkQ9csDD8="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        sctp_reverse_default_seed {
+            args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 132, source_port: 3344, destination_port: 1122],
+            want: Ok("1:EKt4MsxuyaE6mL+hmrEkQ9csDD8="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        sctp_no_ports {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 132],
+            want: Err("src port and dst port should be set when protocol is tcp/udp/sctp"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mixed_address_families {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "::1", protocol: 46],
+            want: Err("source and destination IP addresses must be of the same family"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        explicit_version {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 1122, destination_port: 3344, version: 1],
+            want: Ok("1:wCb3OG7yAFWelaUydu0D+125CLM="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unsupported_version {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 1122, destination_port: 3344, version: 2],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
         protocol_too_large {
             args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: i64::MAX, source_port: 3344, destination_port: 1122],
             want: Err("invalid argument"),
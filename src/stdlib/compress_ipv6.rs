@@ -0,0 +1,111 @@
+use crate::compiler::prelude::*;
+use std::net::Ipv6Addr;
+
+fn compress_ipv6(value: Value) -> Resolved {
+    let ip: Ipv6Addr = value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse IPv6 address: {err}"))?;
+
+    // The standard library formats addresses in the canonical form of RFC 5952.
+    Ok(ip.to_string().into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CompressIpv6;
+
+impl Function for CompressIpv6 {
+    fn identifier(&self) -> &'static str {
+        "compress_ipv6"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "expanded address",
+                source: r#"compress_ipv6!("2001:0db8:0000:0000:0000:0000:0000:0001")"#,
+                result: Ok("2001:db8::1"),
+            },
+            Example {
+                title: "longest run of zeros",
+                source: r#"compress_ipv6!("2001:DB8:0:0:1:0:0:0")"#,
+                result: Ok("2001:db8:0:0:1::"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(CompressIpv6Fn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompressIpv6Fn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for CompressIpv6Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        compress_ipv6(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        compress_ipv6 => CompressIpv6;
+
+        expanded {
+            args: func_args![value: "2001:0DB8:0000:0000:0008:0800:200C:417A"],
+            want: Ok("2001:db8::8:800:200c:417a"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        first_of_equal_runs {
+            args: func_args![value: "2001:db8:0:0:1:0:0:1"],
+            want: Ok("2001:db8::1:0:0:1"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        single_zero_field {
+            args: func_args![value: "2001:db8:0:1:1:1:1:1"],
+            want: Ok("2001:db8:0:1:1:1:1:1"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ipv4_mapped {
+            args: func_args![value: "0:0:0:0:0:ffff:c000:0201"],
+            want: Ok("::ffff:192.0.2.1"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "2001:db8::1::1"],
+            want: Err("unable to parse IPv6 address: invalid IPv6 address syntax"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,244 @@
+use crate::compiler::prelude::*;
+
+use super::hash_util::{self, seed64};
+
+const INVALID_RATE: &str = r#""rate" must be between 0 and 1"#;
+
+fn rate(rate: &Value) -> Result<f64, &'static str> {
+    let rate = match rate {
+        Value::Integer(rate) => *rate as f64,
+        Value::Float(rate) => rate.into_inner(),
+        _ => return Err(INVALID_RATE),
+    };
+
+    if (0.0..=1.0).contains(&rate) {
+        Ok(rate)
+    } else {
+        Err(INVALID_RATE)
+    }
+}
+
+/// Hashes the value with XXH64, and samples it if the upper 53 bits of the
+/// hash, as a fraction of 2^53, are below the rate.
+#[allow(clippy::cast_precision_loss)] // the shifted hash has at most 53 bits
+fn consistent_sample(value: Value, rate: f64, seed: Value) -> Resolved {
+    let hash = match value {
+        Value::Bytes(bytes) => hash_util::xxhash64(&bytes, seed64(seed)?),
+        Value::Integer(integer) => {
+            hash_util::xxhash64(integer.to_string().as_bytes(), seed64(seed)?)
+        }
+        value => {
+            return Err(ValueError::Expected {
+                got: value.kind(),
+                expected: Kind::bytes() | Kind::integer(),
+            }
+            .into())
+        }
+    };
+
+    let fraction = (hash >> 11) as f64 / (1_u64 << 53) as f64;
+    Ok((fraction < rate).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConsistentSample;
+
+impl Function for ConsistentSample {
+    fn identifier(&self) -> &'static str {
+        "consistent_sample"
+    }
+
+    fn summary(&self) -> &'static str {
+        "sample events consistently by key"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns whether to keep an event, given a key of the event and the fraction of events
+            to keep. All events with the same key get the same result, so sampling on a trace id
+            keeps or drops whole traces.
+
+            The `value` is hashed with XXH64 and the given `seed`, integers being hashed as their
+            decimal representation. The event is kept if the upper 53 bits of the hash, as a
+            fraction of 2^53, are below `rate`. The result is stable across hosts and releases.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES | kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "rate",
+                kind: kind::FLOAT | kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "sample a trace",
+                source: r#"consistent_sample("4bf92f3577b34da6a3ce929d0e0e4736", 0.5)"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "keep all",
+                source: r#"consistent_sample("4bf92f3577b34da6a3ce929d0e0e4736", 1)"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "seed",
+                source: r#"consistent_sample("4bf92f3577b34da6a3ce929d0e0e4736", 0.5, seed: 7)"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+            .category("Random")
+            .returns(kind::BOOLEAN, false)
+            .return_rule("fails if a non-constant `rate` is not between 0 and 1")
+            .parameter_default("seed", "0")
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let rate = arguments.required("rate");
+        let seed = arguments.optional("seed").unwrap_or_else(|| expr!(0));
+
+        let constant_rate = match rate.resolve_constant(state) {
+            Some(value) => match self::rate(&value) {
+                Ok(rate) => Some(rate),
+                Err(error) => {
+                    return Err(function::Error::InvalidArgument {
+                        keyword: "rate",
+                        value,
+                        error,
+                    }
+                    .into())
+                }
+            },
+            None => None,
+        };
+
+        Ok(ConsistentSampleFn {
+            value,
+            rate,
+            seed,
+            constant_rate,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ConsistentSampleFn {
+    value: Box<dyn Expression>,
+    rate: Box<dyn Expression>,
+    seed: Box<dyn Expression>,
+    constant_rate: Option<f64>,
+}
+
+impl FunctionExpression for ConsistentSampleFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let rate = match self.constant_rate {
+            Some(rate) => rate,
+            None => rate(&self.rate.resolve(ctx)?)?,
+        };
+        let seed = self.seed.resolve(ctx)?;
+
+        consistent_sample(value, rate, seed)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().maybe_fallible(self.constant_rate.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        consistent_sample => ConsistentSample;
+
+        sampled {
+            args: func_args![value: "4bf92f3577b34da6a3ce929d0e0e4736", rate: 0.5],
+            want: Ok(true),
+            tdef: TypeDef::boolean(),
+        }
+
+        not_sampled_with_seed {
+            args: func_args![value: "4bf92f3577b34da6a3ce929d0e0e4736", rate: 0.5, seed: 7],
+            want: Ok(false),
+            tdef: TypeDef::boolean(),
+        }
+
+        keep_none {
+            args: func_args![value: "4bf92f3577b34da6a3ce929d0e0e4736", rate: 0],
+            want: Ok(false),
+            tdef: TypeDef::boolean(),
+        }
+
+        integer_value {
+            args: func_args![value: 1234, rate: 1.0],
+            want: Ok(true),
+            tdef: TypeDef::boolean(),
+        }
+
+        invalid_rate {
+            args: func_args![value: "foo", rate: 1.5],
+            want: Err("invalid argument"),
+            tdef: TypeDef::boolean(),
+        }
+    ];
+
+    #[test]
+    fn samples_consistently() {
+        let keys = (0..1000).map(|i| format!("trace-{i}")).collect::<Vec<_>>();
+        let sample = |rate: f64| {
+            keys.iter()
+                .filter(|key| {
+                    consistent_sample(Value::from(key.as_str()), rate, Value::from(0))
+                        .unwrap()
+                        .as_boolean()
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let tenth = sample(0.1);
+        let half = sample(0.5);
+
+        assert!((50..150).contains(&tenth.len()), "{}", tenth.len());
+        assert!((400..600).contains(&half.len()), "{}", half.len());
+
+        // Raising the rate only keeps additional keys.
+        assert!(tenth.iter().all(|key| half.contains(key)));
+        assert_eq!(
+            consistent_sample(Value::from(42), 0.5, Value::from(0)),
+            consistent_sample(Value::from("42"), 0.5, Value::from(0))
+        );
+    }
+}
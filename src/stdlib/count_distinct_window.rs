@@ -0,0 +1,203 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::compiler::prelude::*;
+use crate::compiler::StateStore;
+
+use super::util::window_duration;
+
+fn count_distinct_window(
+    store: &dyn StateStore,
+    key: Value,
+    value: &Value,
+    window: Duration,
+) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+
+    // Members are stored in their VRL representation, so that values of
+    // different types, such as `1` and `"1"`, are counted apart.
+    let count = store.insert(
+        &format!("count_distinct_window:{}:{key}", window.as_secs()),
+        &value.to_string(),
+        window,
+    );
+
+    Ok(Value::from(i64::try_from(count).unwrap_or(i64::MAX)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CountDistinctWindow;
+
+impl Function for CountDistinctWindow {
+    fn identifier(&self) -> &'static str {
+        "count_distinct_window"
+    }
+
+    fn summary(&self) -> &'static str {
+        "count the distinct values seen for a key"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Adds `value` to the distinct values seen for the given `key` within the last
+            `window_secs` seconds, and returns the number of distinct values.
+
+            The window starts with the first value of the key and lasts `window_secs` seconds,
+            at most one year, after which the count starts over. Values are kept in the state
+            store provided by the host, which decides whether they are shared by other
+            processes.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "window_secs",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "count the distinct users of a host",
+            source: r#"count_distinct_window("host-1", "alice", window_secs: 3600)"#,
+            result: Ok("1"),
+        }]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+            .returns(kind::INTEGER, false)
+            .return_rule("fails if a non-constant `window_secs` is invalid")
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let window_secs = arguments.required("window_secs");
+
+        let store = ctx
+            .state_store()
+            .cloned()
+            .ok_or(function::Error::MissingContext {
+                context: "state store",
+            })?;
+
+        let constant_window =
+            match window_secs.resolve_constant(state) {
+                Some(value) => Some(window_duration(&value).map_err(|error| {
+                    function::Error::InvalidArgument {
+                        keyword: "window_secs",
+                        value,
+                        error,
+                    }
+                })?),
+                None => None,
+            };
+
+        Ok(CountDistinctWindowFn {
+            key,
+            value,
+            window_secs,
+            constant_window,
+            store,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CountDistinctWindowFn {
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    window_secs: Box<dyn Expression>,
+    constant_window: Option<Duration>,
+    store: Arc<dyn StateStore>,
+}
+
+impl FunctionExpression for CountDistinctWindowFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let value = self.value.resolve(ctx)?;
+        let window = match self.constant_window {
+            Some(window) => window,
+            None => window_duration(&self.window_secs.resolve(ctx)?)?,
+        };
+
+        count_distinct_window(self.store.as_ref(), key, &value, window)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().maybe_fallible(self.constant_window.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile_with_external, CompileConfig, MemoryStateStore};
+    use crate::value;
+
+    fn resolve(source: &str, config: CompileConfig) -> Result<Value, String> {
+        let program = compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &state::ExternalEnv::default(),
+            config,
+        )
+        .map_err(|diagnostics| diagnostics[0].message.clone())?
+        .program;
+
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut state, &tz);
+        program.resolve(&mut ctx).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn counts_distinct_values_by_key() {
+        let mut config = CompileConfig::default();
+        config.set_state_store(MemoryStateStore::default());
+
+        let source = r#"
+            [
+                count_distinct_window("a", "alice", window_secs: 60),
+                count_distinct_window("a", "bob", window_secs: 60),
+                count_distinct_window("a", "alice", window_secs: 60),
+                count_distinct_window("a", 1, window_secs: 60),
+                count_distinct_window("a", "1", window_secs: 60),
+                count_distinct_window("b", "alice", window_secs: 60),
+            ]
+        "#;
+        assert_eq!(resolve(source, config), Ok(value!([1, 2, 2, 3, 4, 1])));
+    }
+
+    #[test]
+    fn requires_a_state_store() {
+        assert_eq!(
+            resolve(
+                r#"count_distinct_window("a", "alice", 60)"#,
+                CompileConfig::default()
+            ),
+            Err("function compilation error: error[E404] missing host context".to_owned())
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::compiler::prelude::*;
+
+use super::hash_util::{self, encodings, seed32, HashEncoding};
+
+fn crc32(value: Value, seed: Value, encoding: HashEncoding) -> Resolved {
+    let value = value.try_bytes()?;
+    let seed = seed32(seed)?;
+
+    Ok(encoding.encode32(hash_util::crc32(&value, seed)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32;
+
+impl Function for Crc32 {
+    fn identifier(&self) -> &'static str {
+        "crc32"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "encoding",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "crc32",
+                source: r#"crc32("foo")"#,
+                result: Ok("2356372769"),
+            },
+            Example {
+                title: "hex encoding",
+                source: r#"crc32("foo", encoding: "hex")"#,
+                result: Ok("8c736521"),
+            },
+            Example {
+                title: "incremental checksum",
+                source: r#"crc32("bar", seed: crc32("foo"))"#,
+                result: Ok("2666930069"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let seed = arguments.optional("seed").unwrap_or_else(|| expr!(0));
+        let encoding = arguments
+            .optional_enum("encoding", &encodings(), state)?
+            .map_or(HashEncoding::Integer, |encoding| {
+                HashEncoding::from_bytes(&encoding.try_bytes().expect("encoding not bytes"))
+            });
+
+        let valid_seed = seed
+            .resolve_constant(state)
+            .is_some_and(|seed| seed32(seed).is_ok());
+        Ok(Crc32Fn {
+            value,
+            seed,
+            encoding,
+            valid_seed,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Crc32Fn {
+    value: Box<dyn Expression>,
+    seed: Box<dyn Expression>,
+    encoding: HashEncoding,
+    valid_seed: bool,
+}
+
+impl FunctionExpression for Crc32Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let seed = self.seed.resolve(ctx)?;
+
+        crc32(value, seed, self.encoding)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        self.encoding.type_def().maybe_fallible(!self.valid_seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        crc32 => Crc32;
+
+        integer {
+            args: func_args![value: "foo"],
+            want: Ok(2_356_372_769_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        hex {
+            args: func_args![value: "foobar", encoding: "hex"],
+            want: Ok("9ef61f95"),
+            tdef: TypeDef::bytes(),
+        }
+
+        seed {
+            args: func_args![value: "foo", seed: 42],
+            want: Ok(3_114_903_063_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(0),
+            tdef: TypeDef::integer(),
+        }
+
+        invalid_seed {
+            args: func_args![value: "foo", seed: -1],
+            want: Err("seed must be between 0 and 4294967295, got -1"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
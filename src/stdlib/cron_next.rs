@@ -0,0 +1,239 @@
+use crate::compiler::prelude::*;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Utc};
+
+use super::parse_cron::Schedule;
+
+/// How far ahead the next fire time is searched, in days. Schedules that
+/// don't fire in this period, such as `0 0 30 2 *`, never do.
+const MAX_DAYS: i64 = 5 * 366;
+
+fn cron_next(value: Value, from: Option<Value>, timezone: &TimeZone) -> Resolved {
+    let expression = value.try_bytes_utf8_lossy()?;
+    let schedule = Schedule::parse(&expression)?;
+    let from = match from {
+        Some(from) => from.try_timestamp()?,
+        None => Utc::now(),
+    };
+
+    let next = match timezone {
+        TimeZone::Local => next_fire_time(&schedule, from, &chrono::Local),
+        TimeZone::Named(tz) => next_fire_time(&schedule, from, tz),
+    };
+
+    next.map(Value::from)
+        .ok_or_else(|| "the cron expression never fires".into())
+}
+
+/// The first time after `from` at which the schedule fires, with the fields of
+/// the schedule in the given timezone. Local times that don't exist, when
+/// clocks are set forward, are skipped.
+fn next_fire_time<Tz: chrono::TimeZone>(
+    schedule: &Schedule,
+    from: DateTime<Utc>,
+    timezone: &Tz,
+) -> Option<DateTime<Utc>> {
+    let start = from.with_timezone(timezone).naive_local();
+    let end = start + Duration::days(MAX_DAYS);
+
+    // The next whole minute.
+    let mut time = start.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+    while time < end {
+        let date = time.date();
+
+        if !schedule.months.contains(date.month()) {
+            time = first_of_next_month(date)?;
+            continue;
+        }
+        if !schedule.runs_on(date.day(), date.weekday().num_days_from_sunday()) {
+            time = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+            continue;
+        }
+        if !schedule.hours.contains(time.hour()) {
+            time = date.and_hms_opt(time.hour(), 0, 0)? + Duration::hours(1);
+            continue;
+        }
+        if !schedule.minutes.contains(time.minute()) {
+            time += Duration::minutes(1);
+            continue;
+        }
+
+        match timezone.from_local_datetime(&time).earliest() {
+            Some(fire_time) if fire_time.with_timezone(&Utc) > from => {
+                return Some(fire_time.with_timezone(&Utc));
+            }
+            _ => time += Duration::minutes(1),
+        }
+    }
+
+    None
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDateTime> {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CronNext;
+
+impl Function for CronNext {
+    fn identifier(&self) -> &'static str {
+        "cron_next"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "from",
+                kind: kind::TIMESTAMP,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "next weekday morning",
+            source: r#"cron_next!("30 9 * * mon-fri", from: t'2024-03-08T10:00:00Z')"#,
+            result: Ok("t'2024-03-11T09:30:00Z'"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let from = arguments.optional("from");
+
+        Ok(CronNextFn { value, from }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CronNextFn {
+    value: Box<dyn Expression>,
+    from: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for CronNextFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let from = self
+            .from
+            .as_ref()
+            .map(|from| from.resolve(ctx))
+            .transpose()?;
+
+        cron_next(value, from, ctx.timezone())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    test_function![
+        cron_next => CronNext;
+
+        next_minute {
+            args: func_args![value: "* * * * *",
+                             from: timestamp("2024-03-08T10:00:30Z")],
+            want: Ok(timestamp("2024-03-08T10:01:00Z")),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        excludes_from {
+            args: func_args![value: "0 * * * *",
+                             from: timestamp("2024-03-08T10:00:00Z")],
+            want: Ok(timestamp("2024-03-08T11:00:00Z")),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        next_year {
+            args: func_args![value: "@yearly",
+                             from: timestamp("2024-03-08T10:00:00Z")],
+            want: Ok(timestamp("2025-01-01T00:00:00Z")),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        leap_day {
+            args: func_args![value: "0 12 29 2 *",
+                             from: timestamp("2024-03-01T00:00:00Z")],
+            want: Ok(timestamp("2028-02-29T12:00:00Z")),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        day_of_month_or_week {
+            args: func_args![value: "0 0 20 * fri",
+                             from: timestamp("2024-03-09T00:00:00Z")],
+            want: Ok(timestamp("2024-03-15T00:00:00Z")),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        in_timezone {
+            args: func_args![value: "0 9 * * *",
+                             from: timestamp("2024-03-08T10:00:00Z")],
+            want: Ok(timestamp("2024-03-09T08:00:00Z")),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::Europe::Berlin),
+        }
+
+        never {
+            args: func_args![value: "0 0 30 2 *",
+                             from: timestamp("2024-03-08T10:00:00Z")],
+            want: Err("the cron expression never fires"),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        invalid {
+            args: func_args![value: "0 0 32 * *",
+                             from: timestamp("2024-03-08T10:00:00Z")],
+            want: Err("invalid day of month field: 32 is not in the range 1-31"),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+    ];
+
+    #[test]
+    fn skips_nonexistent_times() {
+        let schedule = Schedule::parse("30 2 * * *").unwrap();
+
+        // Clocks in Berlin skip from 2:00 to 3:00 on 2024-03-31.
+        assert_eq!(
+            next_fire_time(
+                &schedule,
+                timestamp("2024-03-30T12:00:00Z"),
+                &chrono_tz::Europe::Berlin
+            ),
+            Some(timestamp("2024-04-01T00:30:00Z"))
+        );
+    }
+}
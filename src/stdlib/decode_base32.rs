@@ -0,0 +1,139 @@
+use crate::compiler::prelude::*;
+
+use super::util::Base32Variant;
+
+fn decode_base32(value: Value, variant: Base32Variant) -> Resolved {
+    let value = value.try_bytes()?;
+    // Padding is optional, so it's removed and the input decoded as unpadded.
+    let value = match variant {
+        Base32Variant::Standard | Base32Variant::Hex => value
+            .iter()
+            .rposition(|&byte| byte != b'=')
+            .map_or(&value[..0], |end| &value[..=end]),
+        Base32Variant::Crockford => &value[..],
+    };
+
+    match variant.encoding(false).decode(value) {
+        Ok(bytes) => Ok(Value::from(Bytes::from(bytes))),
+        Err(_) => Err("unable to decode value from base32".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeBase32;
+
+impl Function for DecodeBase32 {
+    fn identifier(&self) -> &'static str {
+        "decode_base32"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variant = arguments
+            .optional_enum("variant", &Base32Variant::variants(), state)?
+            .map_or(Base32Variant::Standard, |variant| {
+                Base32Variant::from_bytes(&variant.try_bytes().expect("variant not bytes"))
+            });
+
+        Ok(DecodeBase32Fn { value, variant }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "demo string",
+                source: r#"decode_base32!("ONXW2ZJAON2HE2LOM4QHMYLMOVSQ====")"#,
+                result: Ok("some string value"),
+            },
+            Example {
+                title: "Crockford",
+                source: r#"decode_base32!("edqpts90-edt74tbe-cwg7crbc-enjg", variant: "crockford")"#,
+                result: Ok("some string value"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeBase32Fn {
+    value: Box<dyn Expression>,
+    variant: Base32Variant,
+}
+
+impl FunctionExpression for DecodeBase32Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_base32(value, self.variant)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        decode_base32 => DecodeBase32;
+
+        with_padding {
+            args: func_args![value: value!("MZXW6YTBOI======")],
+            want: Ok(value!("foobar")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        without_padding {
+            args: func_args![value: value!("MZXW6YTBOI")],
+            want: Ok(value!("foobar")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        hex {
+            args: func_args![value: value!("CPNMUOJ1E8======"), variant: value!("hex")],
+            want: Ok(value!("foobar")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        crockford_lenient {
+            args: func_args![value: value!("csqpyrk1e8"), variant: value!("crockford")],
+            want: Ok(value!("foobar")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        crockford_confusable_letters {
+            args: func_args![value: value!("ILO0"), variant: value!("crockford")],
+            want: Ok(value!(b"\x08@")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: value!("MZXW6YTBO!")],
+            want: Err("unable to decode value from base32"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,115 @@
+use crate::compiler::prelude::*;
+
+use super::util::Base58Variant;
+
+fn decode_base58(value: Value, variant: Base58Variant) -> Resolved {
+    let value = value.try_bytes()?;
+
+    match bs58::decode(value)
+        .with_alphabet(variant.alphabet())
+        .into_vec()
+    {
+        Ok(bytes) => Ok(Value::from(Bytes::from(bytes))),
+        Err(_) => Err("unable to decode value from base58".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeBase58;
+
+impl Function for DecodeBase58 {
+    fn identifier(&self) -> &'static str {
+        "decode_base58"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variant = arguments
+            .optional_enum("variant", &Base58Variant::variants(), state)?
+            .map_or(Base58Variant::Bitcoin, |variant| {
+                Base58Variant::from_bytes(&variant.try_bytes().expect("variant not bytes"))
+            });
+
+        Ok(DecodeBase58Fn { value, variant }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_base58!("25v8uHCxhqBqD5Kubh2Wu5hv")"#,
+            result: Ok("some string value"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeBase58Fn {
+    value: Box<dyn Expression>,
+    variant: Base58Variant,
+}
+
+impl FunctionExpression for DecodeBase58Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_base58(value, self.variant)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        decode_base58 => DecodeBase58;
+
+        bitcoin {
+            args: func_args![value: value!("StV1DL6CwTryKyV")],
+            want: Ok(value!("hello world")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        leading_zeros {
+            args: func_args![value: value!("11bQbp")],
+            want: Ok(value!(b"\x00\x00foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        flickr {
+            args: func_args![value: value!("rTu1dk6cWsRYjYu"), variant: value!("flickr")],
+            want: Ok(value!("hello world")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_character {
+            args: func_args![value: value!("StV1DL0CwTryKyV")],
+            want: Err("unable to decode value from base58"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
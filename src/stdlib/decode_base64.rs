@@ -2,25 +2,35 @@ use crate::compiler::prelude::*;
 use base64::Engine as _;
 use std::str::FromStr;
 
-use super::util::Base64Charset;
-
-fn decode_base64(charset: Option<Value>, value: Value) -> Resolved {
-    let charset = charset
-        .map(Value::try_bytes)
-        .transpose()?
-        .map(|c| Base64Charset::from_str(&String::from_utf8_lossy(&c)))
-        .transpose()?
-        .unwrap_or_default();
+use super::util::{check_base64_charset, Base64Charset};
+
+fn decode_base64(value: Value, charset: Value, strict: Value) -> Resolved {
+    let charset = Base64Charset::from_str(&charset.try_bytes_utf8_lossy()?)?;
+    let strict = strict.try_boolean()?;
     let alphabet = match charset {
         Base64Charset::Standard => base64::alphabet::STANDARD,
         Base64Charset::UrlSafe => base64::alphabet::URL_SAFE,
     };
     let value = value.try_bytes()?;
     let config = base64::engine::general_purpose::GeneralPurposeConfig::new()
-        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent);
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent)
+        .with_decode_allow_trailing_bits(!strict);
     let engine = base64::engine::GeneralPurpose::new(&alphabet, config);
 
-    match engine.decode(value) {
+    let decoded = if strict {
+        engine.decode(value)
+    } else {
+        // Whitespace, such as line breaks in wrapped payloads, is ignored, and
+        // so is any padding.
+        let value = value
+            .iter()
+            .copied()
+            .filter(|byte| !byte.is_ascii_whitespace() && *byte != b'=')
+            .collect::<Vec<_>>();
+        engine.decode(value)
+    };
+
+    match decoded {
         Ok(s) => Ok(Value::from(Bytes::from(s))),
         Err(_) => Err("unable to decode value to base64".into()),
     }
@@ -46,42 +56,69 @@ impl Function for DecodeBase64 {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "strict",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let charset = arguments.optional("charset");
-
-        Ok(DecodeBase64Fn { value, charset }.as_expr())
+        let charset = arguments
+            .optional("charset")
+            .unwrap_or_else(|| expr!("standard"));
+        let strict = arguments.optional("strict").unwrap_or_else(|| expr!(true));
+
+        check_base64_charset(&*charset, state)?;
+        Ok(DecodeBase64Fn {
+            value,
+            charset,
+            strict,
+        }
+        .as_expr())
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "demo string",
-            source: r#"decode_base64!("c29tZSBzdHJpbmcgdmFsdWU=")"#,
-            result: Ok("some string value"),
-        }]
+        &[
+            Example {
+                title: "demo string",
+                source: r#"decode_base64!("c29tZSBzdHJpbmcgdmFsdWU=")"#,
+                result: Ok("some string value"),
+            },
+            Example {
+                title: "wrapped payload",
+                source: r#"decode_base64!("c29tZSBzdHJp\nbmcgdmFsdWU", strict: false)"#,
+                result: Ok("some string value"),
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct DecodeBase64Fn {
     value: Box<dyn Expression>,
-    charset: Option<Box<dyn Expression>>,
+    charset: Box<dyn Expression>,
+    strict: Box<dyn Expression>,
 }
 
 impl FunctionExpression for DecodeBase64Fn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let charset = self.charset.as_ref().map(|c| c.resolve(ctx)).transpose()?;
+        let charset = self.charset.resolve(ctx)?;
+        let strict = self.strict.resolve(ctx)?;
 
-        decode_base64(charset, value)
+        decode_base64(value, charset, strict)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -135,5 +172,41 @@ mod test {
             want: Ok(value!(r#"{"schema":"iglu:com.snowplowanalytics.snowplow/unstruct_event/jsonschema/1-0-0","data":{"schema":"iglu:com.snowplowanalytics.snowplow/link_click/jsonschema/1-0-1","data":{"targetUrl":"https://idp-auth.gar.education.fr/domaineGar?idENT=SjA=&idSrc=YXJrOi80ODU2Ni9wdDE4MDAwMQ==","elementId":"","elementClasses":["link-button","tracked"],"elementTarget":"_blank"}}}"#)),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        strict_rejects_whitespace {
+            args: func_args![value: value!("c29tZSs9\nc3RyaW5nL3ZhbHVl")],
+            want: Err("unable to decode value to base64"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        lenient_whitespace_and_padding {
+            args: func_args![value: value!(" c29tZSs9\r\nc3RyaW5n\nL3ZhbHVlCg=\n"), strict: value!(false)],
+            want: Ok(value!("some+=string/value\n")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        lenient_trailing_bits {
+            args: func_args![value: value!("YR"), strict: value!(false)],
+            want: Ok(value!("a")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        strict_trailing_bits {
+            args: func_args![value: value!("YR")],
+            want: Err("unable to decode value to base64"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        lenient_invalid {
+            args: func_args![value: value!("c29t!ZQ"), strict: value!(false)],
+            want: Err("unable to decode value to base64"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_charset {
+            args: func_args![value: value!("c29tZQ"), charset: value!("foo")],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
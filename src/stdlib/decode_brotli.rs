@@ -0,0 +1,175 @@
+use crate::compiler::prelude::*;
+use brotli::Decompressor;
+use std::io::Read;
+
+const BUFFER_SIZE: usize = 4096;
+
+fn decode_brotli(value: Value, max_size: Option<Value>) -> Resolved {
+    let max_size = match max_size {
+        Some(max_size) => {
+            let max_size = max_size.try_integer()?;
+            Some(u64::try_from(max_size).map_err(|_| "max_size must not be negative")?)
+        }
+        None => None,
+    };
+
+    let value = value.try_bytes()?;
+    let mut decoder = Decompressor::new(&value[..], BUFFER_SIZE);
+    let mut decoded = Vec::new();
+    let result = match max_size {
+        // One byte more than the maximum is read to tell whether the decoded
+        // value is larger, without decoding all of it.
+        Some(max_size) => decoder.take(max_size + 1).read_to_end(&mut decoded),
+        None => decoder.read_to_end(&mut decoded),
+    };
+    if result.is_err() {
+        return Err("unable to decode value with Brotli decoder".into());
+    }
+
+    match max_size {
+        Some(max_size) if decoded.len() as u64 > max_size => {
+            Err(format!("decoded value is larger than the maximum size of {max_size} bytes").into())
+        }
+        _ => Ok(Value::Bytes(decoded.into())),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeBrotli;
+
+impl Function for DecodeBrotli {
+    fn identifier(&self) -> &'static str {
+        "decode_brotli"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "demo string",
+                source: r#"decode_brotli!(decode_base64!("sAIQVGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEzIGxhenkgZG9ncy4D"))"#,
+                result: Ok("The quick brown fox jumps over 13 lazy dogs."),
+            },
+            Example {
+                title: "maximum size",
+                source: r#"decode_brotli(decode_base64!("sAIQVGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEzIGxhenkgZG9ncy4D"), max_size: 16) ?? "too large""#,
+                result: Ok("too large"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
+
+        Ok(DecodeBrotliFn { value, max_size }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeBrotliFn {
+    value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecodeBrotliFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_brotli(value, max_size)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use base64::Engine;
+    use nom::AsBytes;
+
+    fn decode_base64(text: &str) -> Vec<u8> {
+        let engine = base64::engine::GeneralPurpose::new(
+            &base64::alphabet::STANDARD,
+            base64::engine::general_purpose::GeneralPurposeConfig::new(),
+        );
+
+        engine.decode(text).expect("Cannot decode from Base64")
+    }
+
+    fn get_encoded_bytes(text: &str) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        brotli::BrotliCompress(&mut text.as_bytes(), &mut encoded, &Default::default())
+            .expect("Cannot encode bytes with Brotli encoder");
+
+        encoded
+    }
+
+    test_function![
+        decode_brotli => DecodeBrotli;
+
+        right_brotli {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes())],
+            want: Ok(value!(b"sample")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        uncompressed_meta_block {
+            args: func_args![value: value!(decode_base64("sAIQVGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEzIGxhenkgZG9ncy4D").as_bytes())],
+            want: Ok(value!(b"The quick brown fox jumps over 13 lazy dogs.")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        wrong_brotli {
+            args: func_args![value: value!("some_bytes")],
+            want: Err("unable to decode value with Brotli decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        within_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: 6],
+            want: Ok(value!(b"sample")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        over_max_size {
+            args: func_args![value: value!(get_encoded_bytes(&"sample".repeat(1000)).as_bytes()), max_size: 100],
+            want: Err("decoded value is larger than the maximum size of 100 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: -1],
+            want: Err("max_size must not be negative"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
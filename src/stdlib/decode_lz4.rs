@@ -0,0 +1,169 @@
+use crate::compiler::prelude::*;
+use lz4_flex::frame::FrameDecoder;
+use std::io::Read;
+
+use super::util::Lz4Format;
+
+/// The maximum ratio between the decompressed and compressed size of a block,
+/// used to reject corrupt sizes before allocating the output.
+const MAX_BLOCK_RATIO: usize = 255;
+
+fn decode_block(value: &[u8]) -> Option<Vec<u8>> {
+    let (size, block) = value.split_at_checked(4)?;
+    let size = u32::from_le_bytes(size.try_into().ok()?) as usize;
+    if size > block.len().saturating_mul(MAX_BLOCK_RATIO) {
+        return None;
+    }
+
+    lz4_flex::block::decompress(block, size).ok()
+}
+
+fn decode_lz4(value: Value, format: Lz4Format) -> Resolved {
+    let value = value.try_bytes()?;
+    let result = match format {
+        Lz4Format::Frame => {
+            let mut buf = Vec::new();
+            FrameDecoder::new(value.as_ref())
+                .read_to_end(&mut buf)
+                .ok()
+                .map(|_| buf)
+        }
+        Lz4Format::Block => decode_block(&value),
+    };
+
+    match result {
+        Some(buf) => Ok(Value::Bytes(buf.into())),
+        None => Err("unable to decode value with LZ4 decoder".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeLz4;
+
+impl Function for DecodeLz4 {
+    fn identifier(&self) -> &'static str {
+        "decode_lz4"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "frame",
+                source: r#"decode_lz4!(decode_base64!("BCJNGGBAgiwAAIBUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLgAAAAA="))"#,
+                result: Ok("The quick brown fox jumps over 13 lazy dogs."),
+            },
+            Example {
+                title: "block",
+                source: r#"decode_lz4!(decode_base64!("GgAAAD5hYmMDAFB4eXp6eQ=="), format: "block")"#,
+                result: Ok("abcabcabcabcabcabcabcxyzzy"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let format = arguments
+            .optional_enum("format", &Lz4Format::variants(), state)?
+            .map_or(Lz4Format::Frame, |format| {
+                Lz4Format::from_bytes(&format.try_bytes().expect("format not bytes"))
+            });
+
+        Ok(DecodeLz4Fn { value, format }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeLz4Fn {
+    value: Box<dyn Expression>,
+    format: Lz4Format,
+}
+
+impl FunctionExpression for DecodeLz4Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_lz4(value, self.format)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use base64::Engine;
+    use nom::AsBytes;
+
+    fn decode_base64(text: &str) -> Vec<u8> {
+        let engine = base64::engine::GeneralPurpose::new(
+            &base64::alphabet::STANDARD,
+            base64::engine::general_purpose::GeneralPurposeConfig::new(),
+        );
+
+        engine.decode(text).expect("Cannot decode from Base64")
+    }
+
+    test_function![
+        decode_lz4 => DecodeLz4;
+
+        frame_uncompressed_block {
+            args: func_args![value: value!(decode_base64("BCJNGGBAgiwAAIBUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLgAAAAA=").as_bytes())],
+            want: Ok(value!(b"The quick brown fox jumps over 13 lazy dogs.")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        frame_compressed_block {
+            args: func_args![value: value!(decode_base64("BCJNGGBAgi4AAADwHVRoZSBxdWljayBicm93biBmb3gganVtcHMgb3ZlciAxMyBsYXp5IGRvZ3MuAAAAAA==").as_bytes())],
+            want: Ok(value!(b"The quick brown fox jumps over 13 lazy dogs.")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        block {
+            args: func_args![value: value!(decode_base64("GgAAAD5hYmMDAFB4eXp6eQ==").as_bytes()), format: "block"],
+            want: Ok(value!(b"abcabcabcabcabcabcabcxyzzy")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        block_as_frame {
+            args: func_args![value: value!(decode_base64("GgAAAD5hYmMDAFB4eXp6eQ==").as_bytes())],
+            want: Err("unable to decode value with LZ4 decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        block_with_corrupt_size {
+            args: func_args![value: value!(b"\xff\xff\xff\x7f\x50abcde"), format: "block"],
+            want: Err("unable to decode value with LZ4 decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        block_too_short {
+            args: func_args![value: value!(b"\x01\x00"), format: "block"],
+            want: Err("unable to decode value with LZ4 decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
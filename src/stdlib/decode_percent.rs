@@ -25,6 +25,10 @@ impl Function for DecodePercent {
         }]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -1,14 +1,26 @@
 use crate::compiler::prelude::*;
 use snap::raw::Decoder;
+use snap::read::FrameDecoder;
+use std::io::Read;
 
-fn decode_snappy(value: Value) -> Resolved {
+use super::util::SnappyFormat;
+
+fn decode_snappy(value: Value, format: SnappyFormat) -> Resolved {
     let value = value.try_bytes()?;
-    let mut decoder = Decoder::new();
-    let result = decoder.decompress_vec(&value);
+    let result = match format {
+        SnappyFormat::Raw => Decoder::new().decompress_vec(&value).ok(),
+        SnappyFormat::Framed => {
+            let mut buf = Vec::new();
+            FrameDecoder::new(value.as_ref())
+                .read_to_end(&mut buf)
+                .ok()
+                .map(|_| buf)
+        }
+    };
 
     match result {
-        Ok(buf) => Ok(Value::Bytes(buf.into())),
-        Err(_) => Err("unable to decode value with Snappy decoder".into()),
+        Some(buf) => Ok(Value::Bytes(buf.into())),
+        None => Err("unable to decode value with Snappy decoder".into()),
     }
 }
 
@@ -21,43 +33,63 @@ impl Function for DecodeSnappy {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "demo string",
-            source: r#"decode_snappy!(decode_base64!("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="))"#,
-            result: Ok("The quick brown fox jumps over 13 lazy dogs."),
-        }]
+        &[
+            Example {
+                title: "demo string",
+                source: r#"decode_snappy!(decode_base64!("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="))"#,
+                result: Ok("The quick brown fox jumps over 13 lazy dogs."),
+            },
+            Example {
+                title: "framed",
+                source: r#"decode_snappy!(decode_base64!("/wYAAHNOYVBwWQAyAAARi0RLLKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="), format: "framed")"#,
+                result: Ok("The quick brown fox jumps over 13 lazy dogs."),
+            },
+        ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let format = arguments
+            .optional_enum("format", &SnappyFormat::variants(), state)?
+            .map_or(SnappyFormat::Raw, |format| {
+                SnappyFormat::from_bytes(&format.try_bytes().expect("format not bytes"))
+            });
 
-        Ok(DecodeSnappyFn { value }.as_expr())
+        Ok(DecodeSnappyFn { value, format }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct DecodeSnappyFn {
     value: Box<dyn Expression>,
+    format: SnappyFormat,
 }
 
 impl FunctionExpression for DecodeSnappyFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
 
-        decode_snappy(value)
+        decode_snappy(value, self.format)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -96,5 +128,23 @@ mod tests {
             want: Err("unable to decode value with Snappy decoder"),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        framed_compressed_chunk {
+            args: func_args![value: value!(decode_base64("/wYAAHNOYVBwWQAyAAARi0RLLKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes()), format: "framed"],
+            want: Ok(value!(b"The quick brown fox jumps over 13 lazy dogs.")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        framed_uncompressed_chunk {
+            args: func_args![value: value!(decode_base64("/wYAAHNOYVBwWQEwAAARi0RLVGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEzIGxhenkgZG9ncy4=").as_bytes()), format: "framed"],
+            want: Ok(value!(b"The quick brown fox jumps over 13 lazy dogs.")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        raw_as_framed {
+            args: func_args![value: value!(decode_base64("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes()), format: "framed"],
+            want: Err("unable to decode value with Snappy decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
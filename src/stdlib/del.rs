@@ -115,9 +115,9 @@ impl Function for Del {
         let compact = arguments.optional("compact");
 
         if let Some(target_path) = query.external_path() {
-            if ctx.is_read_only_path(&target_path) {
+            if let Some(reason) = ctx.read_only_reason(&target_path) {
                 return Err(function::Error::ReadOnlyMutation {
-                    context: format!("{query} is read-only, and cannot be deleted"),
+                    context: format!("{query} cannot be deleted, because {reason}"),
                 }
                 .into());
             }
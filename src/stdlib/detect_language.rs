@@ -0,0 +1,226 @@
+use whatlang::Lang;
+
+use crate::compiler::prelude::*;
+use std::collections::BTreeMap;
+
+/// The ISO 639-1 code of a language supported by the detector.
+const fn iso_639_1(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Afr => "af",
+        Lang::Aka => "ak",
+        Lang::Amh => "am",
+        Lang::Ara => "ar",
+        Lang::Aze => "az",
+        Lang::Bel => "be",
+        Lang::Ben => "bn",
+        Lang::Bul => "bg",
+        Lang::Cat => "ca",
+        Lang::Ces => "cs",
+        Lang::Cmn => "zh",
+        Lang::Dan => "da",
+        Lang::Deu => "de",
+        Lang::Ell => "el",
+        Lang::Eng => "en",
+        Lang::Epo => "eo",
+        Lang::Est => "et",
+        Lang::Fin => "fi",
+        Lang::Fra => "fr",
+        Lang::Guj => "gu",
+        Lang::Heb => "he",
+        Lang::Hin => "hi",
+        Lang::Hrv => "hr",
+        Lang::Hun => "hu",
+        Lang::Hye => "hy",
+        Lang::Ind => "id",
+        Lang::Ita => "it",
+        Lang::Jav => "jv",
+        Lang::Jpn => "ja",
+        Lang::Kan => "kn",
+        Lang::Kat => "ka",
+        Lang::Khm => "km",
+        Lang::Kor => "ko",
+        Lang::Lat => "la",
+        Lang::Lav => "lv",
+        Lang::Lit => "lt",
+        Lang::Mal => "ml",
+        Lang::Mar => "mr",
+        Lang::Mkd => "mk",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Nld => "nl",
+        Lang::Nob => "nb",
+        Lang::Ori => "or",
+        Lang::Pan => "pa",
+        Lang::Pes => "fa",
+        Lang::Pol => "pl",
+        Lang::Por => "pt",
+        Lang::Ron => "ro",
+        Lang::Rus => "ru",
+        Lang::Sin => "si",
+        Lang::Slk => "sk",
+        Lang::Slv => "sl",
+        Lang::Sna => "sn",
+        Lang::Spa => "es",
+        Lang::Srp => "sr",
+        Lang::Swe => "sv",
+        Lang::Tam => "ta",
+        Lang::Tel => "te",
+        Lang::Tgl => "tl",
+        Lang::Tha => "th",
+        Lang::Tuk => "tk",
+        Lang::Tur => "tr",
+        Lang::Ukr => "uk",
+        Lang::Urd => "ur",
+        Lang::Uzb => "uz",
+        Lang::Vie => "vi",
+        Lang::Yid => "yi",
+        Lang::Zul => "zu",
+    }
+}
+
+fn detect_language(value: Value) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+
+    // Text without any letters, such as numbers or punctuation, has no language.
+    let Some(info) = whatlang::detect(&value) else {
+        return Ok(Value::Null);
+    };
+
+    let mut map = BTreeMap::<&str, Value>::new();
+
+    map.insert("language", iso_639_1(info.lang()).into());
+    map.insert("confidence", Value::from_f64_or_zero(info.confidence()));
+    map.insert("reliable", info.is_reliable().into());
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DetectLanguage;
+
+impl Function for DetectLanguage {
+    fn identifier(&self) -> &'static str {
+        "detect_language"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "detect language",
+                source: r#"detect_language("Καλημέρα κόσμε")"#,
+                result: Ok(indoc! {r#"
+                {
+                    "confidence": 1.0,
+                    "language": "el",
+                    "reliable": true
+                }
+            "#}),
+            },
+            Example {
+                title: "language code only",
+                source: r#"detect_language("There is no reason not to learn Esperanto.").language"#,
+                result: Ok("en"),
+            },
+            Example {
+                title: "no letters",
+                source: r#"detect_language("12:00 - 13:00")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(DetectLanguageFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DetectLanguageFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DetectLanguageFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        detect_language(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).or_null().infallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("language".into(), Kind::bytes()),
+        ("confidence".into(), Kind::float()),
+        ("reliable".into(), Kind::boolean()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        detect_language => DetectLanguage;
+
+        single_language_script {
+            args: func_args![value: "안녕하세요"],
+            want: Ok(value!({language: "ko", confidence: 1.0, reliable: true})),
+            tdef: TypeDef::object(inner_kind()).or_null().infallible(),
+        }
+
+        no_letters {
+            args: func_args![value: "1234 !?"],
+            want: Ok(value!(null)),
+            tdef: TypeDef::object(inner_kind()).or_null().infallible(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(value!(null)),
+            tdef: TypeDef::object(inner_kind()).or_null().infallible(),
+        }
+    ];
+
+    #[test]
+    fn latin_script() {
+        for (text, language) in [
+            ("There is no reason not to learn Esperanto.", "en"),
+            (
+                "Der schnelle braune Fuchs springt über den faulen Hund.",
+                "de",
+            ),
+            (
+                "El rápido zorro marrón salta sobre el perro perezoso.",
+                "es",
+            ),
+        ] {
+            let result = detect_language(text.into()).unwrap();
+            let result = result.as_object().unwrap();
+
+            assert_eq!(result["language"], Value::from(language), "{text}");
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+use crate::path::OwnedValuePath;
+
+fn diff(value1: &Value, value2: &Value) -> Value {
+    let mut operations = Vec::new();
+    diff_at(&mut OwnedValuePath::root(), value1, value2, &mut operations);
+    operations.into()
+}
+
+// The length of an array never exceeds `isize::MAX`.
+#[allow(clippy::cast_possible_wrap)]
+fn diff_at(path: &mut OwnedValuePath, old: &Value, new: &Value, operations: &mut Vec<Value>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old) in old {
+                path.push_field(key.as_str());
+                match new.get(key) {
+                    Some(new) => diff_at(path, old, new, operations),
+                    None => operations.push(remove(path, old)),
+                }
+                path.segments.pop();
+            }
+            for (key, new) in new {
+                if !old.contains_key(key) {
+                    path.push_field(key.as_str());
+                    operations.push(add(path, new));
+                    path.segments.pop();
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                path.push_index(index as isize);
+                diff_at(path, old, new, operations);
+                path.segments.pop();
+            }
+            // Elements are removed from the end, so the indices of the remaining elements don't
+            // change when the operations are applied in order.
+            for (index, old) in old.iter().enumerate().skip(new.len()).rev() {
+                path.push_index(index as isize);
+                operations.push(remove(path, old));
+                path.segments.pop();
+            }
+            for (index, new) in new.iter().enumerate().skip(old.len()) {
+                path.push_index(index as isize);
+                operations.push(add(path, new));
+                path.segments.pop();
+            }
+        }
+        (old, new) if old != new => operations.push(replace(path, old, new)),
+        _ => {}
+    }
+}
+
+/// Formats the path as a query on the value, such as `.foo[1]`.
+fn format_path(path: &OwnedValuePath) -> String {
+    format!(".{path}")
+}
+
+fn operation(op: &str, path: &OwnedValuePath) -> ObjectMap {
+    ObjectMap::from([
+        ("op".into(), op.into()),
+        ("path".into(), format_path(path).into()),
+    ])
+}
+
+fn add(path: &OwnedValuePath, value: &Value) -> Value {
+    let mut operation = operation("add", path);
+    operation.insert("value".into(), value.clone());
+    operation.into()
+}
+
+fn remove(path: &OwnedValuePath, old: &Value) -> Value {
+    let mut operation = operation("remove", path);
+    operation.insert("old_value".into(), old.clone());
+    operation.into()
+}
+
+fn replace(path: &OwnedValuePath, old: &Value, new: &Value) -> Value {
+    let mut operation = operation("replace", path);
+    operation.insert("old_value".into(), old.clone());
+    operation.insert("value".into(), new.clone());
+    operation.into()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Diff;
+
+impl Function for Diff {
+    fn identifier(&self) -> &'static str {
+        "diff"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value1",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "value2",
+                kind: kind::ANY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "changed fields",
+                source: r#"diff({"a": 1, "b": 2}, {"a": 1, "b": 3, "c": 4})"#,
+                result: Ok(
+                    r#"[{"op": "replace", "path": ".b", "old_value": 2, "value": 3}, {"op": "add", "path": ".c", "value": 4}]"#,
+                ),
+            },
+            Example {
+                title: "removed array element",
+                source: r"diff([1, 2], [1])",
+                result: Ok(r#"[{"op": "remove", "path": ".[1]", "old_value": 2}]"#),
+            },
+            Example {
+                title: "equal values",
+                source: r#"diff({"a": [1]}, {"a": [1]})"#,
+                result: Ok("[]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value1 = arguments.required("value1");
+        let value2 = arguments.required("value2");
+
+        Ok(DiffFn { value1, value2 }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DiffFn {
+    value1: Box<dyn Expression>,
+    value2: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DiffFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value1 = self.value1.resolve(ctx)?;
+        let value2 = self.value2.resolve(ctx)?;
+
+        Ok(diff(&value1, &value2))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::object(inner_kind()))).infallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("op".into(), Kind::bytes()),
+        ("path".into(), Kind::bytes()),
+        ("value".into(), Kind::any().or_undefined()),
+        ("old_value".into(), Kind::any().or_undefined()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        diff => Diff;
+
+        objects {
+            args: func_args![
+                value1: value!({"a": {"b": 1, "c": 2}, "d": true}),
+                value2: value!({"a": {"b": 1, "c": 3}, "e": null}),
+            ],
+            want: Ok(value!([
+                {"op": "replace", "path": ".a.c", "old_value": 2, "value": 3},
+                {"op": "remove", "path": ".d", "old_value": true},
+                {"op": "add", "path": ".e", "value": null},
+            ])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(inner_kind()))),
+        }
+
+        arrays {
+            args: func_args![value1: value!([1, 2, 3, 4]), value2: value!([0, 2])],
+            want: Ok(value!([
+                {"op": "replace", "path": ".[0]", "old_value": 1, "value": 0},
+                {"op": "remove", "path": ".[3]", "old_value": 4},
+                {"op": "remove", "path": ".[2]", "old_value": 3},
+            ])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(inner_kind()))),
+        }
+
+        quoted_fields {
+            args: func_args![value1: value!({}), value2: value!({"a b": 1})],
+            want: Ok(value!([{"op": "add", "path": r#"."a b""#, "value": 1}])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(inner_kind()))),
+        }
+
+        different_types {
+            args: func_args![value1: value!({"a": 1}), value2: value!("a")],
+            want: Ok(value!([{"op": "replace", "path": ".", "old_value": {"a": 1}, "value": "a"}])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(inner_kind()))),
+        }
+    ];
+}
@@ -20,6 +20,10 @@ impl Function for Downcase {
         }]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
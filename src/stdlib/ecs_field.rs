@@ -0,0 +1,114 @@
+use crate::compiler::prelude::*;
+
+use super::schema_util::{ecs_field_type, field_info, field_info_kind};
+
+fn ecs_field(name: Value) -> Resolved {
+    let name = name.try_bytes_utf8_lossy()?;
+    let kind = ecs_field_type(&name).ok_or_else(|| format!("unknown ECS field: {name}"))?;
+
+    Ok(field_info(&name, kind).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EcsField;
+
+impl Function for EcsField {
+    fn identifier(&self) -> &'static str {
+        "ecs_field"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "name",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "known field",
+                source: r#"ecs_field("source.ip")"#,
+                result: Ok(r#"{"name": "source.ip", "path": ["source", "ip"], "type": "ip"}"#),
+            },
+            Example {
+                title: "top-level field",
+                source: r#"ecs_field("@timestamp")"#,
+                result: Ok(r#"{"name": "@timestamp", "path": ["@timestamp"], "type": "date"}"#),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let name = arguments.required("name");
+        if let Some(value) = name.resolve_constant(state) {
+            if ecs_field(value.clone()).is_err() {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "name",
+                    value,
+                    error: "unknown ECS field",
+                }
+                .into());
+            }
+        }
+
+        Ok(EcsFieldFn { name }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EcsFieldFn {
+    name: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EcsFieldFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let name = self.name.resolve(ctx)?;
+
+        ecs_field(name)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // A constant name is checked when the call is compiled.
+        TypeDef::object(field_info_kind())
+            .maybe_fallible(self.name.resolve_constant(state).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        ecs_field => EcsField;
+
+        nested {
+            args: func_args![name: "source.ip"],
+            want: Ok(value!({"name": "source.ip", "path": ["source", "ip"], "type": "ip"})),
+            tdef: TypeDef::object(field_info_kind()),
+        }
+
+        top_level {
+            args: func_args![name: "@timestamp"],
+            want: Ok(value!({"name": "@timestamp", "path": ["@timestamp"], "type": "date"})),
+            tdef: TypeDef::object(field_info_kind()),
+        }
+
+        unknown {
+            args: func_args![name: "source.ipaddr"],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(field_info_kind()).fallible(),
+        }
+    ];
+}
@@ -21,6 +21,10 @@ impl Function for EncodeBase16 {
         }]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -0,0 +1,136 @@
+use crate::compiler::prelude::*;
+
+use super::util::Base32Variant;
+
+fn encode_base32(value: Value, padding: Value, variant: Base32Variant) -> Resolved {
+    let value = value.try_bytes()?;
+    let padding = padding.try_boolean()?;
+
+    Ok(variant.encoding(padding).encode(&value).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeBase32;
+
+impl Function for EncodeBase32 {
+    fn identifier(&self) -> &'static str {
+        "encode_base32"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "padding",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let padding = arguments.optional("padding").unwrap_or_else(|| expr!(true));
+        let variant = arguments
+            .optional_enum("variant", &Base32Variant::variants(), state)?
+            .map_or(Base32Variant::Standard, |variant| {
+                Base32Variant::from_bytes(&variant.try_bytes().expect("variant not bytes"))
+            });
+
+        Ok(EncodeBase32Fn {
+            value,
+            padding,
+            variant,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "demo string",
+                source: r#"encode_base32("some string value")"#,
+                result: Ok("ONXW2ZJAON2HE2LOM4QHMYLMOVSQ===="),
+            },
+            Example {
+                title: "Crockford",
+                source: r#"encode_base32("some string value", variant: "crockford")"#,
+                result: Ok("EDQPTS90EDT74TBECWG7CRBCENJG"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeBase32Fn {
+    value: Box<dyn Expression>,
+    padding: Box<dyn Expression>,
+    variant: Base32Variant,
+}
+
+impl FunctionExpression for EncodeBase32Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let padding = self.padding.resolve(ctx)?;
+
+        encode_base32(value, padding, self.variant)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_base32 => EncodeBase32;
+
+        with_defaults {
+            args: func_args![value: value!("foobar")],
+            want: Ok(value!("MZXW6YTBOI======")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        no_padding {
+            args: func_args![value: value!("foobar"), padding: value!(false)],
+            want: Ok(value!("MZXW6YTBOI")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hex {
+            args: func_args![value: value!("foobar"), variant: value!("hex")],
+            want: Ok(value!("CPNMUOJ1E8======")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        crockford {
+            args: func_args![value: value!("foobar"), variant: value!("crockford"), padding: value!(true)],
+            want: Ok(value!("CSQPYRK1E8")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        empty {
+            args: func_args![value: value!("")],
+            want: Ok(value!("")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,118 @@
+use crate::compiler::prelude::*;
+
+use super::util::Base58Variant;
+
+fn encode_base58(value: Value, variant: Base58Variant) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(bs58::encode(value)
+        .with_alphabet(variant.alphabet())
+        .into_string()
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeBase58;
+
+impl Function for EncodeBase58 {
+    fn identifier(&self) -> &'static str {
+        "encode_base58"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variant = arguments
+            .optional_enum("variant", &Base58Variant::variants(), state)?
+            .map_or(Base58Variant::Bitcoin, |variant| {
+                Base58Variant::from_bytes(&variant.try_bytes().expect("variant not bytes"))
+            });
+
+        Ok(EncodeBase58Fn { value, variant }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"encode_base58("some string value")"#,
+            result: Ok("25v8uHCxhqBqD5Kubh2Wu5hv"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeBase58Fn {
+    value: Box<dyn Expression>,
+    variant: Base58Variant,
+}
+
+impl FunctionExpression for EncodeBase58Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_base58(value, self.variant)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_base58 => EncodeBase58;
+
+        bitcoin {
+            args: func_args![value: value!("hello world")],
+            want: Ok(value!("StV1DL6CwTryKyV")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        leading_zeros {
+            args: func_args![value: value!(b"\x00\x00foo")],
+            want: Ok(value!("11bQbp")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        flickr {
+            args: func_args![value: value!("hello world"), variant: value!("flickr")],
+            want: Ok(value!("rTu1dk6cWsRYjYu")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        ripple {
+            args: func_args![value: value!("hello world"), variant: value!("ripple")],
+            want: Ok(value!("StVrDLaUATiyKyV")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        empty {
+            args: func_args![value: value!("")],
+            want: Ok(value!("")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -2,20 +2,12 @@ use crate::compiler::prelude::*;
 use base64::Engine as _;
 use std::str::FromStr;
 
-use super::util::Base64Charset;
+use super::util::{check_base64_charset, Base64Charset};
 
-fn encode_base64(value: Value, padding: Option<Value>, charset: Option<Value>) -> Resolved {
+fn encode_base64(value: Value, padding: Value, charset: Value) -> Resolved {
     let value = value.try_bytes()?;
-    let padding = padding
-        .map(VrlValueConvert::try_boolean)
-        .transpose()?
-        .unwrap_or(true);
-    let charset = charset
-        .map(VrlValueConvert::try_bytes)
-        .transpose()?
-        .map(|c| Base64Charset::from_str(&String::from_utf8_lossy(&c)))
-        .transpose()?
-        .unwrap_or_default();
+    let padding = padding.try_boolean()?;
+    let charset = Base64Charset::from_str(&charset.try_bytes_utf8_lossy()?)?;
 
     let engine = base64::engine::GeneralPurpose::new(
         &base64::alphabet::Alphabet::from(charset),
@@ -54,16 +46,23 @@ impl Function for EncodeBase64 {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let padding = arguments.optional("padding");
-        let charset = arguments.optional("charset");
+        let padding = arguments.optional("padding").unwrap_or_else(|| expr!(true));
+        let charset = arguments
+            .optional("charset")
+            .unwrap_or_else(|| expr!("standard"));
 
+        check_base64_charset(&*charset, state)?;
         Ok(EncodeBase64Fn {
             value,
             padding,
@@ -79,20 +78,29 @@ impl Function for EncodeBase64 {
             result: Ok("c29tZSBzdHJpbmcgdmFsdWU"),
         }]
     }
+
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+            .category("Codec")
+            .returns(kind::BYTES, false)
+            .parameter_default("padding", "true")
+            .parameter_default("charset", r#""standard""#)
+            .parameter_variants("charset", &["standard", "url_safe"])
+    }
 }
 
 #[derive(Clone, Debug)]
 struct EncodeBase64Fn {
     value: Box<dyn Expression>,
-    padding: Option<Box<dyn Expression>>,
-    charset: Option<Box<dyn Expression>>,
+    padding: Box<dyn Expression>,
+    charset: Box<dyn Expression>,
 }
 
 impl FunctionExpression for EncodeBase64Fn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let padding = self.padding.as_ref().map(|p| p.resolve(ctx)).transpose()?;
-        let charset = self.charset.as_ref().map(|c| c.resolve(ctx)).transpose()?;
+        let padding = self.padding.resolve(ctx)?;
+        let charset = self.charset.resolve(ctx)?;
 
         encode_base64(value, padding, charset)
     }
@@ -154,7 +162,7 @@ mod test {
 
         invalid_charset_error {
             args: func_args![value: value!("some string value"), padding: value!(false), charset: value!("foo")],
-            want: Err("unknown charset"),
+            want: Err(r#"invalid enum variant""#),
             tdef: TypeDef::bytes().infallible(),
         }
     ];
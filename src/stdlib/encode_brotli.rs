@@ -0,0 +1,175 @@
+use crate::compiler::prelude::*;
+use brotli::enc::BrotliEncoderParams;
+
+const DEFAULT_QUALITY: i64 = 11;
+const DEFAULT_WINDOW: i64 = 22;
+
+fn quality(quality: Value) -> Result<i32, ExpressionError> {
+    let quality = quality.try_integer()?;
+    if (0..=11).contains(&quality) {
+        Ok(quality as i32)
+    } else {
+        Err(format!("quality must be between 0 and 11, got {quality}").into())
+    }
+}
+
+fn window(window: Value) -> Result<i32, ExpressionError> {
+    let window = window.try_integer()?;
+    if (10..=24).contains(&window) {
+        Ok(window as i32)
+    } else {
+        Err(format!("window must be between 10 and 24, got {window}").into())
+    }
+}
+
+fn encode_brotli(value: Value, quality: Value, window: Value) -> Resolved {
+    let params = BrotliEncoderParams {
+        quality: self::quality(quality)?,
+        lgwin: self::window(window)?,
+        ..Default::default()
+    };
+
+    let value = value.try_bytes()?;
+    let mut encoded = Vec::new();
+    // Compression only fails if writing to the output fails, which can't
+    // happen for a vector.
+    brotli::BrotliCompress(&mut &value[..], &mut encoded, &params)
+        .expect("brotli compression failed, please report");
+
+    Ok(Value::Bytes(encoded.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeBrotli;
+
+impl Function for EncodeBrotli {
+    fn identifier(&self) -> &'static str {
+        "encode_brotli"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_brotli!(encode_brotli("encode_me", quality: 5, window: 16))"#,
+            result: Ok("encode_me"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let quality = arguments
+            .optional("quality")
+            .unwrap_or_else(|| expr!(DEFAULT_QUALITY));
+        let window = arguments
+            .optional("window")
+            .unwrap_or_else(|| expr!(DEFAULT_WINDOW));
+
+        let valid_options = quality
+            .resolve_constant(state)
+            .is_some_and(|quality| self::quality(quality).is_ok())
+            && window
+                .resolve_constant(state)
+                .is_some_and(|window| self::window(window).is_ok());
+
+        Ok(EncodeBrotliFn {
+            value,
+            quality,
+            window,
+            valid_options,
+        }
+        .as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "quality",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "window",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeBrotliFn {
+    value: Box<dyn Expression>,
+    quality: Box<dyn Expression>,
+    window: Box<dyn Expression>,
+    valid_options: bool,
+}
+
+impl FunctionExpression for EncodeBrotliFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let quality = self.quality.resolve(ctx)?;
+        let window = self.window.resolve(ctx)?;
+
+        encode_brotli(value, quality, window)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().maybe_fallible(!self.valid_options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn decode(bytes: &[u8]) -> String {
+        let mut decoded = String::new();
+        brotli::Decompressor::new(bytes, 4096)
+            .read_to_string(&mut decoded)
+            .expect("Cannot decode bytes with Brotli decoder");
+
+        decoded
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = "The quick brown fox jumps over 13 lazy dogs.".repeat(10);
+
+        for (quality, window) in [(0, 10), (5, 16), (11, 24)] {
+            let encoded = encode_brotli(value.clone().into(), quality.into(), window.into())
+                .unwrap()
+                .try_bytes()
+                .unwrap();
+
+            assert!(encoded.len() < value.len());
+            assert_eq!(decode(&encoded), value);
+        }
+    }
+
+    test_function![
+        encode_brotli => EncodeBrotli;
+
+        invalid_quality {
+            args: func_args![value: "encode_me", quality: 12],
+            want: Err("quality must be between 0 and 11, got 12"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_window {
+            args: func_args![value: "encode_me", window: 9],
+            want: Err("window must be between 10 and 24, got 9"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,144 @@
+use crate::compiler::prelude::*;
+use lz4_flex::frame::FrameEncoder;
+use std::io::Write;
+
+use super::util::Lz4Format;
+
+fn encode_lz4(value: Value, format: Lz4Format) -> Resolved {
+    let value = value.try_bytes()?;
+    let result = match format {
+        Lz4Format::Frame => {
+            let mut encoder = FrameEncoder::new(Vec::new());
+            encoder
+                .write_all(&value)
+                .ok()
+                .and_then(|()| encoder.finish().ok())
+        }
+        Lz4Format::Block => Some(lz4_flex::block::compress_prepend_size(&value)),
+    };
+
+    match result {
+        Some(buf) => Ok(Value::Bytes(buf.into())),
+        None => Err("unable to encode value with LZ4 encoder".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeLz4;
+
+impl Function for EncodeLz4 {
+    fn identifier(&self) -> &'static str {
+        "encode_lz4"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "frame",
+                source: r#"decode_lz4!(encode_lz4!("The quick brown fox jumps over 13 lazy dogs."))"#,
+                result: Ok("The quick brown fox jumps over 13 lazy dogs."),
+            },
+            Example {
+                title: "block",
+                source: r#"encode_base64(encode_lz4!("The quick brown fox jumps over 13 lazy dogs.", format: "block"))"#,
+                result: Ok("LAAAAPAdVGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEzIGxhenkgZG9ncy4="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let format = arguments
+            .optional_enum("format", &Lz4Format::variants(), state)?
+            .map_or(Lz4Format::Frame, |format| {
+                Lz4Format::from_bytes(&format.try_bytes().expect("format not bytes"))
+            });
+
+        Ok(EncodeLz4Fn { value, format }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeLz4Fn {
+    value: Box<dyn Expression>,
+    format: Lz4Format,
+}
+
+impl FunctionExpression for EncodeLz4Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_lz4(value, self.format)
+    }
+
+    fn type_def(&self, _state: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use base64::Engine;
+    use nom::AsBytes;
+    use std::io::Read;
+
+    fn decode_base64(text: &str) -> Vec<u8> {
+        let engine = base64::engine::GeneralPurpose::new(
+            &base64::alphabet::STANDARD,
+            base64::engine::general_purpose::GeneralPurposeConfig::new(),
+        );
+
+        engine.decode(text).expect("Cannot decode from Base64")
+    }
+
+    test_function![
+        encode_lz4 => EncodeLz4;
+
+        block {
+            args: func_args![value: value!("The quick brown fox jumps over 13 lazy dogs."), format: "block"],
+            want: Ok(value!(decode_base64("LAAAAPAdVGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyIDEzIGxhenkgZG9ncy4=").as_bytes())),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+
+    #[test]
+    fn frame() {
+        let value = "The quick brown fox jumps over 13 lazy dogs.".repeat(10);
+        let encoded = encode_lz4(value.clone().into(), Lz4Format::Frame)
+            .unwrap()
+            .try_bytes()
+            .unwrap();
+
+        // The magic number of LZ4 frames.
+        assert_eq!(&encoded[..4], b"\x04\x22\x4d\x18");
+        assert!(encoded.len() < value.len());
+
+        let mut decoded = String::new();
+        lz4_flex::frame::FrameDecoder::new(encoded.as_bytes())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, value);
+    }
+}
@@ -4,7 +4,15 @@ use percent_encoding::{utf8_percent_encode, AsciiSet};
 
 fn encode_percent(value: Value, ascii_set: &Bytes) -> Resolved {
     let string = value.try_bytes_utf8_lossy()?;
-    let ascii_set = match ascii_set.as_ref() {
+
+    Ok(utf8_percent_encode(&string, ascii_set_by_name(ascii_set))
+        .to_string()
+        .into())
+}
+
+/// Returns the [`AsciiSet`] for one of the names in [`ascii_sets`].
+pub(super) fn ascii_set_by_name(name: &[u8]) -> &'static AsciiSet {
+    match name {
         b"NON_ALPHANUMERIC" => percent_encoding::NON_ALPHANUMERIC,
         b"CONTROLS" => percent_encoding::CONTROLS,
         b"FRAGMENT" => FRAGMENT,
@@ -15,9 +23,7 @@ fn encode_percent(value: Value, ascii_set: &Bytes) -> Resolved {
         b"COMPONENT" => COMPONENT,
         b"WWW_FORM_URLENCODED" => WWW_FORM_URLENCODED,
         _ => unreachable!("enum invariant"),
-    };
-
-    Ok(utf8_percent_encode(&string, ascii_set).to_string().into())
+    }
 }
 
 /// https://url.spec.whatwg.org/#fragment-percent-encode-set
@@ -65,7 +71,7 @@ const WWW_FORM_URLENCODED: &AsciiSet =
 #[derive(Clone, Copy, Debug)]
 pub struct EncodePercent;
 
-fn ascii_sets() -> Vec<Value> {
+pub(super) fn ascii_sets() -> Vec<Value> {
     vec![
         value!("NON_ALPHANUMERIC"),
         value!("CONTROLS"),
@@ -99,6 +105,10 @@ impl Function for EncodePercent {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         state: &state::TypeState,
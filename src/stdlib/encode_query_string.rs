@@ -0,0 +1,277 @@
+use std::borrow::Cow;
+
+use crate::compiler::prelude::*;
+use crate::value;
+use chrono::SecondsFormat;
+use percent_encoding::{utf8_percent_encode, AsciiSet};
+
+use super::encode_percent::{ascii_set_by_name, ascii_sets};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArrayFormat {
+    /// `a=1&a=2`
+    Repeat,
+    /// `a[]=1&a[]=2`
+    Brackets,
+    /// `a=1,2`
+    Comma,
+}
+
+impl ArrayFormat {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes {
+            b"repeat" => Self::Repeat,
+            b"brackets" => Self::Brackets,
+            b"comma" => Self::Comma,
+            _ => unreachable!("enum invariant"),
+        }
+    }
+}
+
+fn array_formats() -> Vec<Value> {
+    vec![value!("repeat"), value!("brackets"), value!("comma")]
+}
+
+struct Encoder {
+    array_format: ArrayFormat,
+    ascii_set: &'static AsciiSet,
+    pairs: Vec<String>,
+}
+
+impl Encoder {
+    fn encode(&self, string: &str) -> String {
+        utf8_percent_encode(string, self.ascii_set).to_string()
+    }
+
+    /// Returns the string form of a scalar value, or `None` for null.
+    fn scalar(value: &Value) -> Option<Cow<'_, str>> {
+        match value {
+            Value::Null => None,
+            Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes)),
+            Value::Regex(regex) => Some(String::from_utf8_lossy(regex.as_bytes_slice())),
+            Value::Timestamp(ts) => Some(ts.to_rfc3339_opts(SecondsFormat::AutoSi, true).into()),
+            value => Some(value.to_string().into()),
+        }
+    }
+
+    fn push_scalar(&mut self, key: &str, value: &Value) {
+        let pair = match Self::scalar(value) {
+            Some(value) => format!("{key}={}", self.encode(&value)),
+            None => key.to_owned(),
+        };
+        self.pairs.push(pair);
+    }
+
+    /// Adds the pairs for `value` under `key`, which is already encoded.
+    fn push(&mut self, key: &str, value: Value) {
+        match value {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    self.push(&format!("{key}[{}]", self.encode(&k)), v);
+                }
+            }
+            // Arrays of containers can only be represented with explicit indices.
+            Value::Array(values)
+                if values
+                    .iter()
+                    .any(|v| matches!(v, Value::Object(_) | Value::Array(_))) =>
+            {
+                for (i, v) in values.into_iter().enumerate() {
+                    self.push(&format!("{key}[{i}]"), v);
+                }
+            }
+            Value::Array(values) => match self.array_format {
+                ArrayFormat::Repeat => values.iter().for_each(|v| self.push_scalar(key, v)),
+                ArrayFormat::Brackets => {
+                    let key = format!("{key}[]");
+                    values.iter().for_each(|v| self.push_scalar(&key, v));
+                }
+                ArrayFormat::Comma if values.is_empty() => {}
+                ArrayFormat::Comma => {
+                    let joined = values
+                        .iter()
+                        .map(|v| Self::scalar(v).map(|v| self.encode(&v)).unwrap_or_default())
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    self.pairs.push(format!("{key}={joined}"));
+                }
+            },
+            value => self.push_scalar(key, &value),
+        }
+    }
+}
+
+fn encode_query_string(
+    value: ObjectMap,
+    array_format: ArrayFormat,
+    ascii_set: &'static AsciiSet,
+) -> String {
+    let mut encoder = Encoder {
+        array_format,
+        ascii_set,
+        pairs: Vec::with_capacity(value.len()),
+    };
+    for (key, value) in value {
+        encoder.push(&encoder.encode(&key), value);
+    }
+    encoder.pairs.join("&")
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeQueryString;
+
+impl Function for EncodeQueryString {
+    fn identifier(&self) -> &'static str {
+        "encode_query_string"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "array_format",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "ascii_set",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode query string",
+                source: r#"encode_query_string({"foo": "a b", "bar": 2, "flag": null})"#,
+                result: Ok("bar=2&flag&foo=a%20b"),
+            },
+            Example {
+                title: "arrays with brackets",
+                source: r#"encode_query_string({"tags": ["x", "y"]}, array_format: "brackets")"#,
+                result: Ok("tags[]=x&tags[]=y"),
+            },
+            Example {
+                title: "nested objects",
+                source: r#"encode_query_string({"user": {"name": "ana", "ids": [1, 2]}}, array_format: "comma")"#,
+                result: Ok("user[ids]=1,2&user[name]=ana"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let array_format = arguments
+            .optional_enum("array_format", &array_formats(), state)?
+            .map_or(ArrayFormat::Repeat, |format| {
+                ArrayFormat::from_bytes(&format.try_bytes().expect("array_format not bytes"))
+            });
+        let ascii_set = ascii_set_by_name(
+            &arguments
+                .optional_enum("ascii_set", &ascii_sets(), state)?
+                .unwrap_or_else(|| value!("WWW_FORM_URLENCODED"))
+                .try_bytes()
+                .expect("ascii_set not bytes"),
+        );
+
+        Ok(EncodeQueryStringFn {
+            value,
+            array_format,
+            ascii_set,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeQueryStringFn {
+    value: Box<dyn Expression>,
+    array_format: ArrayFormat,
+    ascii_set: &'static AsciiSet,
+}
+
+impl FunctionExpression for EncodeQueryStringFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_object()?;
+
+        Ok(encode_query_string(value, self.array_format, self.ascii_set).into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_query_string => EncodeQueryString;
+
+        scalars {
+            args: func_args![value: value!({"s": "a b&c=d", "i": 1, "f": 1.5, "b": true, "n": null, "e": ""})],
+            want: Ok("b=true&e=&f=1.5&i=1&n&s=a%20b%26c%3Dd"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        empty {
+            args: func_args![value: value!({})],
+            want: Ok(""),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        repeat {
+            args: func_args![value: value!({"a": [1, "x y"], "b": []})],
+            want: Ok("a=1&a=x%20y"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        brackets {
+            args: func_args![value: value!({"a": [1, 2]}), array_format: "brackets"],
+            want: Ok("a[]=1&a[]=2"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        comma {
+            args: func_args![value: value!({"a": ["x,y", null, 3]}), array_format: "comma"],
+            want: Ok("a=x%2Cy,,3"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nested_objects {
+            args: func_args![value: value!({"user": {"name": "ana", "address": {"city": "São Paulo"}}})],
+            want: Ok("user[address][city]=S%C3%A3o%20Paulo&user[name]=ana"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        arrays_of_objects {
+            args: func_args![value: value!({"items": [{"id": 1}, {"id": 2}]}), array_format: "comma"],
+            want: Ok("items[0][id]=1&items[1][id]=2"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        encoded_keys {
+            args: func_args![value: value!({"a b": {"c&d": 1}})],
+            want: Ok("a%20b[c%26d]=1"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        ascii_set {
+            args: func_args![value: value!({"path": "/a b/c"}), ascii_set: "QUERY"],
+            want: Ok("path=/a%20b/c"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
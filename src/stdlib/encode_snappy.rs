@@ -1,14 +1,26 @@
 use crate::compiler::prelude::*;
 use snap::raw::Encoder;
+use snap::read::FrameEncoder;
+use std::io::Read;
 
-fn encode_snappy(value: Value) -> Resolved {
+use super::util::SnappyFormat;
+
+fn encode_snappy(value: Value, format: SnappyFormat) -> Resolved {
     let value = value.try_bytes()?;
-    let mut encoder = Encoder::new();
-    let result = encoder.compress_vec(&value);
+    let result = match format {
+        SnappyFormat::Raw => Encoder::new().compress_vec(&value).ok(),
+        SnappyFormat::Framed => {
+            let mut buf = Vec::new();
+            FrameEncoder::new(value.as_ref())
+                .read_to_end(&mut buf)
+                .ok()
+                .map(|_| buf)
+        }
+    };
 
     match result {
-        Ok(buf) => Ok(Value::Bytes(buf.into())),
-        Err(_) => Err("unable to encode value with Snappy encoder".into()),
+        Some(buf) => Ok(Value::Bytes(buf.into())),
+        None => Err("unable to encode value with Snappy encoder".into()),
     }
 }
 
@@ -21,43 +33,63 @@ impl Function for EncodeSnappy {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "demo string",
-            source: r#"encode_base64(encode_snappy!("The quick brown fox jumps over 13 lazy dogs."))"#,
-            result: Ok("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="),
-        }]
+        &[
+            Example {
+                title: "demo string",
+                source: r#"encode_base64(encode_snappy!("The quick brown fox jumps over 13 lazy dogs."))"#,
+                result: Ok("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="),
+            },
+            Example {
+                title: "framed",
+                source: r#"decode_snappy!(encode_snappy!("some bytes", format: "framed"), format: "framed")"#,
+                result: Ok("some bytes"),
+            },
+        ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let format = arguments
+            .optional_enum("format", &SnappyFormat::variants(), state)?
+            .map_or(SnappyFormat::Raw, |format| {
+                SnappyFormat::from_bytes(&format.try_bytes().expect("format not bytes"))
+            });
 
-        Ok(EncodeSnappyFn { value }.as_expr())
+        Ok(EncodeSnappyFn { value, format }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct EncodeSnappyFn {
     value: Box<dyn Expression>,
+    format: SnappyFormat,
 }
 
 impl FunctionExpression for EncodeSnappyFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
 
-        encode_snappy(value)
+        encode_snappy(value, self.format)
     }
 
     fn type_def(&self, _state: &state::TypeState) -> TypeDef {
@@ -90,5 +122,29 @@ mod test {
             want: Ok(value!(decode_base64("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes())),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        raw {
+            args: func_args![value: value!("The quick brown fox jumps over 13 lazy dogs."), format: "raw"],
+            want: Ok(value!(decode_base64("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes())),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
+
+    #[test]
+    fn framed() {
+        let value = "The quick brown fox jumps over 13 lazy dogs.".repeat(10);
+        let encoded = encode_snappy(value.clone().into(), SnappyFormat::Framed)
+            .unwrap()
+            .try_bytes()
+            .unwrap();
+
+        // The stream identifier chunk.
+        assert_eq!(&encoded[..10], b"\xff\x06\x00\x00sNaPpY");
+
+        let mut decoded = String::new();
+        snap::read::FrameDecoder::new(encoded.as_bytes())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, value);
+    }
 }
@@ -35,6 +35,10 @@ impl Function for EndsWith {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -0,0 +1,87 @@
+use crate::compiler::prelude::*;
+use crate::compiler::EnvVarPolicy;
+
+fn env_vars(policy: &EnvVarPolicy) -> Value {
+    std::env::vars_os()
+        .filter_map(|(name, value)| {
+            let name = name.to_string_lossy();
+            policy.is_allowed(&name).then(|| {
+                (
+                    KeyString::from(name.as_ref()),
+                    Value::from(value.to_string_lossy().as_ref()),
+                )
+            })
+        })
+        .collect::<ObjectMap>()
+        .into()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EnvVars;
+
+impl Function for EnvVars {
+    fn identifier(&self) -> &'static str {
+        "env_vars"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "home",
+            source: "env_vars().HOME != null",
+            result: Ok("true"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        _arguments: ArgumentList,
+    ) -> Compiled {
+        Ok(EnvVarsFn {
+            policy: ctx.env_var_policy().clone(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EnvVarsFn {
+    policy: EnvVarPolicy,
+}
+
+impl FunctionExpression for EnvVarsFn {
+    fn resolve(&self, _ctx: &mut Context) -> Resolved {
+        Ok(env_vars(&self.policy))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::from_unknown(Kind::bytes())).infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_allowed() {
+        std::env::set_var("ENV_VARS_ALLOWED", "yes");
+        std::env::set_var("ENV_VARS_DENIED", "no");
+
+        let policy = EnvVarPolicy::default().with_allowed(["ENV_VARS_*"]);
+        let vars = env_vars(&policy).try_object().unwrap();
+        assert_eq!(vars.get("ENV_VARS_ALLOWED"), Some(&Value::from("yes")));
+        assert!(vars.contains_key("ENV_VARS_DENIED"));
+        assert!(!vars.contains_key("HOME"));
+
+        let policy = policy.with_denied(["ENV_VARS_DENIED"]);
+        let vars = env_vars(&policy).try_object().unwrap();
+        assert_eq!(vars.get("ENV_VARS_ALLOWED"), Some(&Value::from("yes")));
+        assert!(!vars.contains_key("ENV_VARS_DENIED"));
+    }
+}
@@ -41,6 +41,15 @@ impl Function for Exists {
 
         Ok(ExistsFn { query }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        // Missing fields are queried as `null`, so their type isn't narrowed
+        // when they don't exist.
+        Some(TypeGuard {
+            kind: Kind::any().without_undefined(),
+            negatable: false,
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,99 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, float_result};
+
+fn exp(value: Value) -> Resolved {
+    expect_number(&value)?;
+    let value = value.try_into_f64()?;
+    float_result("exp", value.exp())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Exp;
+
+impl Function for Exp {
+    fn identifier(&self) -> &'static str {
+        "exp"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "exponential",
+                source: "exp!(0)",
+                result: Ok("1.0"),
+            },
+            Example {
+                title: "overflow",
+                source: "exp!(1000)",
+                result: Err(r#"function call error for "exp" at (0:10): exp result overflowed"#),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(ExpFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExpFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ExpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        exp(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        exp => Exp;
+
+        zero {
+            args: func_args![value: 0],
+            want: Ok(1.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        one {
+            args: func_args![value: 1],
+            want: Ok(std::f64::consts::E),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        overflow {
+            args: func_args![value: 710],
+            want: Err("exp result overflowed"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
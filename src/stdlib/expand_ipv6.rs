@@ -0,0 +1,110 @@
+use crate::compiler::prelude::*;
+use std::net::Ipv6Addr;
+
+fn expand_ipv6(value: Value) -> Resolved {
+    let ip: Ipv6Addr = value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse IPv6 address: {err}"))?;
+    let expanded = ip
+        .segments()
+        .iter()
+        .map(|segment| format!("{segment:04x}"))
+        .collect::<Vec<_>>()
+        .join(":");
+
+    Ok(expanded.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandIpv6;
+
+impl Function for ExpandIpv6 {
+    fn identifier(&self) -> &'static str {
+        "expand_ipv6"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "compressed address",
+                source: r#"expand_ipv6!("2001:db8::1")"#,
+                result: Ok("2001:0db8:0000:0000:0000:0000:0000:0001"),
+            },
+            Example {
+                title: "IPv4-mapped address",
+                source: r#"expand_ipv6!("::ffff:192.0.2.1")"#,
+                result: Ok("0000:0000:0000:0000:0000:ffff:c000:0201"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ExpandIpv6Fn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExpandIpv6Fn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ExpandIpv6Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        expand_ipv6(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        expand_ipv6 => ExpandIpv6;
+
+        compressed {
+            args: func_args![value: "2001:DB8::8:800:200C:417A"],
+            want: Ok("2001:0db8:0000:0000:0008:0800:200c:417a"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unspecified {
+            args: func_args![value: "::"],
+            want: Ok("0000:0000:0000:0000:0000:0000:0000:0000"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        already_expanded {
+            args: func_args![value: "fe80:0000:0000:0000:0202:b3ff:fe1e:8329"],
+            want: Ok("fe80:0000:0000:0000:0202:b3ff:fe1e:8329"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ipv4 {
+            args: func_args![value: "192.168.0.1"],
+            want: Err("unable to parse IPv6 address: invalid IPv6 address syntax"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,218 @@
+use crate::compiler::prelude::*;
+
+fn variable_to_string(name: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => Ok(value.to_string()),
+        other => Err(format!(
+            r#"variable "{name}" must be a string, number or boolean, got {}"#,
+            other.kind_str()
+        )),
+    }
+}
+
+/// Expands `${NAME}` and `${NAME:-default}` placeholders in `input` from
+/// `variables`. A literal `$` can be written as `$$`.
+///
+/// Undefined variables without a default are an error in strict mode, and are
+/// left untouched otherwise.
+fn expand_variables(input: &str, variables: &ObjectMap, strict: bool) -> Result<String, String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find('$') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            output.push('$');
+            rest = after;
+            continue;
+        }
+
+        let Some(body) = after.strip_prefix('{') else {
+            output.push('$');
+            rest = after;
+            continue;
+        };
+
+        let position = input.len() - rest.len() + start;
+        let end = body
+            .find('}')
+            .ok_or_else(|| format!("unterminated placeholder at position {position}"))?;
+        let placeholder = &rest[start..start + end + 3];
+        let (name, default) = match body[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&body[..end], None),
+        };
+        if name.is_empty() {
+            return Err(format!("empty variable name at position {position}"));
+        }
+
+        match (variables.get(name), default) {
+            (Some(value), _) => output.push_str(&variable_to_string(name, value)?),
+            (None, Some(default)) => output.push_str(default),
+            (None, None) if strict => return Err(format!(r#"undefined variable "{name}""#)),
+            (None, None) => output.push_str(placeholder),
+        }
+        rest = &body[end + 1..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandVariables;
+
+impl Function for ExpandVariables {
+    fn identifier(&self) -> &'static str {
+        "expand_variables"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variables",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "strict",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "expand variables",
+                source: r#"expand_variables!("logs-${service}-${env:-dev}", {"service": "api"})"#,
+                result: Ok("logs-api-dev"),
+            },
+            Example {
+                title: "lenient mode",
+                source: r#"expand_variables!("${known} ${unknown}", {"known": 1}, strict: false)"#,
+                result: Ok("1 ${unknown}"),
+            },
+            Example {
+                title: "strict mode",
+                source: r#"expand_variables!("${unknown}", {})"#,
+                result: Err(
+                    r#"function call error for "expand_variables" at (0:35): undefined variable "unknown""#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variables = arguments.required("variables");
+        let strict = arguments.optional("strict").unwrap_or_else(|| expr!(true));
+
+        Ok(ExpandVariablesFn {
+            value,
+            variables,
+            strict,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExpandVariablesFn {
+    value: Box<dyn Expression>,
+    variables: Box<dyn Expression>,
+    strict: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ExpandVariablesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let string = value.try_bytes_utf8_lossy()?;
+        let variables = self.variables.resolve(ctx)?.try_object()?;
+        let strict = self.strict.resolve(ctx)?.try_boolean()?;
+
+        Ok(expand_variables(&string, &variables, strict)?.into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        expand_variables => ExpandVariables;
+
+        expands {
+            args: func_args![value: "${a}/${b}/${c}/${d}", variables: value!({"a": "x", "b": 1, "c": 1.5, "d": true})],
+            want: Ok("x/1/1.5/true"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        defaults {
+            args: func_args![value: "${a:-one} ${b:-}!", variables: value!({"a": "set"})],
+            want: Ok("set !"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        escapes_and_bare_dollars {
+            args: func_args![value: "$$${a} costs $5 $", variables: value!({"a": "it"})],
+            want: Ok("$it costs $5 $"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        values_are_not_expanded_again {
+            args: func_args![value: "${a}", variables: value!({"a": "${b}", "b": "nope"})],
+            want: Ok("${b}"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        strict_undefined {
+            args: func_args![value: "host-${missing}", variables: value!({})],
+            want: Err(r#"undefined variable "missing""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        lenient_undefined {
+            args: func_args![value: "host-${missing}-${a}", variables: value!({"a": "x"}), strict: false],
+            want: Ok("host-${missing}-x"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unterminated {
+            args: func_args![value: "ok ${a", variables: value!({"a": "x"})],
+            want: Err("unterminated placeholder at position 3"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        empty_name {
+            args: func_args![value: "${}", variables: value!({}), strict: false],
+            want: Err("empty variable name at position 0"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_variable_type {
+            args: func_args![value: "${a}", variables: value!({"a": [1]})],
+            want: Err(r#"variable "a" must be a string, number or boolean, got array"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,243 @@
+use crate::compiler::prelude::*;
+use regex::{Regex, RegexSet};
+use std::collections::BTreeMap;
+
+use super::util;
+
+fn extract_all(value: Value, numeric_groups: Value, patterns: &Patterns) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+    let numeric_groups = numeric_groups.try_boolean()?;
+
+    let mut extracted: ObjectMap = patterns
+        .names
+        .iter()
+        .map(|name| (name.clone(), Value::Null))
+        .collect();
+
+    // The set finds every matching pattern in a single pass, so only those
+    // patterns have to run again to extract their captures.
+    for index in patterns.set.matches(&value) {
+        let regex = &patterns.regexes[index];
+        if let Some(capture) = regex.captures(&value) {
+            extracted.insert(
+                patterns.names[index].clone(),
+                util::capture_regex_to_map(regex, &capture, numeric_groups).into(),
+            );
+        }
+    }
+
+    Ok(extracted.into())
+}
+
+#[derive(Debug, Clone)]
+struct Patterns {
+    names: Vec<KeyString>,
+    regexes: Vec<Regex>,
+    set: RegexSet,
+}
+
+impl Patterns {
+    fn new(patterns: ObjectMap) -> Result<Self, &'static str> {
+        let mut names = Vec::with_capacity(patterns.len());
+        let mut regexes = Vec::with_capacity(patterns.len());
+        for (name, pattern) in patterns {
+            let Value::Regex(regex) = pattern else {
+                return Err("patterns must be regular expressions");
+            };
+            names.push(name);
+            regexes.push((*regex).clone());
+        }
+
+        let set = RegexSet::new(regexes.iter().map(Regex::as_str))
+            .map_err(|_| "patterns are too large to be matched together")?;
+
+        Ok(Self {
+            names,
+            regexes,
+            set,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ExtractAll;
+
+impl Function for ExtractAll {
+    fn identifier(&self) -> &'static str {
+        "extract_all"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "patterns",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "numeric_groups",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "multiple patterns",
+                source: r#"extract_all("user=zorp ip=8.7.6.5", {"user": r'user=(?P<name>\w+)', "ip": r'ip=(?P<address>[\d.]+)', "port": r'port=(?P<port>\d+)'})"#,
+                result: Ok(indoc! { r#"{
+                "ip": { "address": "8.7.6.5" },
+                "port": null,
+                "user": { "name": "zorp" }
+            }"# }),
+            },
+            Example {
+                title: "numeric groups",
+                source: r#"extract_all("status=200", {"status": r'status=(\d+)'}, numeric_groups: true)"#,
+                result: Ok(indoc! { r#"{
+                "status": { "0": "status=200", "1": "200" }
+            }"# }),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let numeric_groups = arguments
+            .optional("numeric_groups")
+            .unwrap_or_else(|| expr!(false));
+
+        let patterns_expr = arguments.required_expr("patterns");
+        let patterns = patterns_expr
+            .resolve_constant(state)
+            .and_then(|patterns| patterns.try_object().ok())
+            .ok_or(function::Error::ExpectedStaticExpression {
+                keyword: "patterns",
+                expr: patterns_expr.clone(),
+            })?;
+        let patterns =
+            Patterns::new(patterns).map_err(|error| function::Error::InvalidArgument {
+                keyword: "patterns",
+                value: format!("{patterns_expr:?}").into(),
+                error,
+            })?;
+
+        Ok(ExtractAllFn {
+            value,
+            patterns,
+            numeric_groups,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExtractAllFn {
+    value: Box<dyn Expression>,
+    patterns: Patterns,
+    numeric_groups: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ExtractAllFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let numeric_groups = self.numeric_groups.resolve(ctx)?;
+
+        extract_all(value, numeric_groups, &self.patterns)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind(&self.patterns)).infallible()
+    }
+}
+
+fn inner_kind(patterns: &Patterns) -> BTreeMap<Field, Kind> {
+    patterns
+        .names
+        .iter()
+        .zip(&patterns.regexes)
+        .map(|(name, regex)| {
+            (
+                name.clone().into(),
+                Kind::object(util::regex_kind(regex)).or_null(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn type_def(patterns: Value) -> TypeDef {
+        let patterns = Patterns::new(patterns.try_object().unwrap()).unwrap();
+        TypeDef::object(inner_kind(&patterns)).infallible()
+    }
+
+    test_function![
+        extract_all => ExtractAll;
+
+        all_match {
+            args: func_args![
+                value: "GET /index.html 200",
+                patterns: value!({
+                    "method": (Regex::new(r"^(?P<method>[A-Z]+) ").unwrap()),
+                    "status": (Regex::new(r" (?P<status>\d{3})$").unwrap()),
+                }),
+            ],
+            want: Ok(value!({
+                "method": { "method": "GET" },
+                "status": { "status": "200" },
+            })),
+            tdef: type_def(value!({
+                "method": (Regex::new(r"^(?P<method>[A-Z]+) ").unwrap()),
+                "status": (Regex::new(r" (?P<status>\d{3})$").unwrap()),
+            })),
+        }
+
+        no_match {
+            args: func_args![
+                value: "nothing to see",
+                patterns: value!({ "digits": (Regex::new(r"(?P<digits>\d+)").unwrap()) }),
+            ],
+            want: Ok(value!({ "digits": null })),
+            tdef: type_def(value!({
+                "digits": (Regex::new(r"(?P<digits>\d+)").unwrap()),
+            })),
+        }
+
+        first_match {
+            args: func_args![
+                value: "a1 b2",
+                patterns: value!({ "pair": (Regex::new(r"(?P<letter>[a-z])(?P<digit>\d)").unwrap()) }),
+                numeric_groups: true,
+            ],
+            want: Ok(value!({
+                "pair": { "0": "a1", "1": "a", "2": "1", "letter": "a", "digit": "1" },
+            })),
+            tdef: type_def(value!({
+                "pair": (Regex::new(r"(?P<letter>[a-z])(?P<digit>\d)").unwrap()),
+            })),
+        }
+
+        not_regex {
+            args: func_args![value: "foo", patterns: value!({ "foo": "foo" })],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(BTreeMap::new()).infallible(),
+        }
+    ];
+}
@@ -105,6 +105,7 @@ impl Function for Filter {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
@@ -1,10 +1,44 @@
-use std::collections::btree_map;
-
 use crate::compiler::prelude::*;
 
 static DEFAULT_SEPARATOR: &str = ".";
 
-fn flatten(value: Value, separator: Value) -> Resolved {
+/// How `flatten` encodes the indices of arrays nested in objects, and how
+/// `unflatten` reconstructs them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum ArrayIndices {
+    /// Arrays are kept as values.
+    #[default]
+    None,
+    /// Indices are separated like fields, as in `a.0.b`.
+    Separator,
+    /// Indices are enclosed in brackets, as in `a[0].b`.
+    Brackets,
+}
+
+impl ArrayIndices {
+    const ALL: [Self; 3] = [Self::None, Self::Separator, Self::Brackets];
+
+    pub(super) fn all_value() -> Vec<Value> {
+        Self::ALL.iter().map(|s| s.as_str().into()).collect()
+    }
+
+    pub(super) fn from_value(value: &Value) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|s| *value == Value::from(s.as_str()))
+            .expect("validated enum")
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Separator => "separator",
+            Self::Brackets => "brackets",
+        }
+    }
+}
+
+fn flatten(value: Value, separator: Value, array_indices: ArrayIndices) -> Resolved {
     let separator = separator.try_bytes_utf8_lossy()?;
 
     match value {
@@ -12,7 +46,7 @@ fn flatten(value: Value, separator: Value) -> Resolved {
             ArrayFlatten::new(arr.iter()).cloned().collect(),
         )),
         Value::Object(map) => Ok(Value::Object(
-            MapFlatten::new(map.iter(), &separator)
+            MapFlatten::new(map.iter(), &separator, array_indices)
                 .map(|(k, v)| (k, v.clone()))
                 .collect(),
         )),
@@ -44,6 +78,11 @@ impl Function for Flatten {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "array_indices",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -64,20 +103,34 @@ impl Function for Flatten {
                 source: "flatten([[true]])",
                 result: Ok("[true]"),
             },
+            Example {
+                title: "array indices in object",
+                source: r#"flatten({ "foo": [{ "bar": true }, 1] }, array_indices: "brackets")"#,
+                result: Ok(r#"{ "foo[0].bar": true, "foo[1]": 1 }"#),
+            },
         ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let separator = arguments
             .optional("separator")
             .unwrap_or_else(|| expr!(DEFAULT_SEPARATOR));
+        let array_indices = arguments
+            .optional_enum("array_indices", &ArrayIndices::all_value(), state)?
+            .map(|value| ArrayIndices::from_value(&value))
+            .unwrap_or_default();
         let value = arguments.required("value");
-        Ok(FlattenFn { value, separator }.as_expr())
+        Ok(FlattenFn {
+            value,
+            separator,
+            array_indices,
+        }
+        .as_expr())
     }
 }
 
@@ -85,6 +138,7 @@ impl Function for Flatten {
 struct FlattenFn {
     value: Box<dyn Expression>,
     separator: Box<dyn Expression>,
+    array_indices: ArrayIndices,
 }
 
 impl FunctionExpression for FlattenFn {
@@ -92,7 +146,7 @@ impl FunctionExpression for FlattenFn {
         let value = self.value.resolve(ctx)?;
         let separator = self.separator.resolve(ctx)?;
 
-        flatten(value, separator)
+        flatten(value, separator, self.array_indices)
     }
 
     fn type_def(&self, state: &state::TypeState) -> TypeDef {
@@ -106,43 +160,65 @@ impl FunctionExpression for FlattenFn {
     }
 }
 
+type Entries<'a> = Box<dyn Iterator<Item = (KeyString, &'a Value)> + 'a>;
+
 /// An iterator to walk over maps allowing us to flatten nested maps to a single level.
+///
+/// Unless `array_indices` is `None`, nested arrays are flattened as well, and
+/// empty objects and arrays are kept so that `unflatten` can reconstruct them.
 struct MapFlatten<'a> {
-    values: btree_map::Iter<'a, KeyString, Value>,
+    values: Entries<'a>,
     separator: &'a str,
+    array_indices: ArrayIndices,
     inner: Option<Box<MapFlatten<'a>>>,
-    parent: Option<KeyString>,
 }
 
 impl<'a> MapFlatten<'a> {
-    fn new(values: btree_map::Iter<'a, KeyString, Value>, separator: &'a str) -> Self {
-        Self {
-            values,
+    fn new(
+        values: impl Iterator<Item = (&'a KeyString, &'a Value)> + 'a,
+        separator: &'a str,
+        array_indices: ArrayIndices,
+    ) -> Self {
+        Self::with_keys(
+            Box::new(values.map(|(key, value)| (key.clone(), value))),
             separator,
-            inner: None,
-            parent: None,
-        }
+            array_indices,
+        )
     }
 
-    fn new_from_parent(
-        parent: KeyString,
-        values: btree_map::Iter<'a, KeyString, Value>,
-        separator: &'a str,
-    ) -> Self {
+    fn with_keys(values: Entries<'a>, separator: &'a str, array_indices: ArrayIndices) -> Self {
         Self {
             values,
             separator,
+            array_indices,
             inner: None,
-            parent: Some(parent),
         }
     }
 
-    /// Returns the key with the parent prepended.
-    fn new_key(&self, key: &str) -> KeyString {
-        match self.parent {
-            None => key.to_string().into(),
-            Some(ref parent) => format!("{parent}{}{key}", self.separator).into(),
-        }
+    /// Returns an iterator over the entries of the object, with the parent
+    /// prepended to the keys.
+    fn object(&self, parent: KeyString, object: &'a ObjectMap) -> Self {
+        let separator = self.separator;
+        let values = object
+            .iter()
+            .map(move |(key, value)| (format!("{parent}{separator}{key}").into(), value));
+        Self::with_keys(Box::new(values), separator, self.array_indices)
+    }
+
+    /// Returns an iterator over the elements of the array, with the parent
+    /// prepended to the indices.
+    fn array(&self, parent: KeyString, array: &'a [Value]) -> Self {
+        let separator = self.separator;
+        let brackets = self.array_indices == ArrayIndices::Brackets;
+        let values = array.iter().enumerate().map(move |(index, value)| {
+            let key = if brackets {
+                format!("{parent}[{index}]")
+            } else {
+                format!("{parent}{separator}{index}")
+            };
+            (key.into(), value)
+        });
+        Self::with_keys(Box::new(values), separator, self.array_indices)
     }
 }
 
@@ -158,18 +234,18 @@ impl<'a> std::iter::Iterator for MapFlatten<'a> {
             }
         }
 
+        let keep_empty = self.array_indices != ArrayIndices::None;
         let next = self.values.next();
         match next {
-            Some((key, Value::Object(value))) => {
-                self.inner = Some(Box::new(MapFlatten::new_from_parent(
-                    self.new_key(key),
-                    value.iter(),
-                    self.separator,
-                )));
+            Some((key, Value::Object(value))) if !(keep_empty && value.is_empty()) => {
+                self.inner = Some(Box::new(self.object(key, value)));
                 self.next()
             }
-            Some((key, value)) => Some((self.new_key(key), value)),
-            None => None,
+            Some((key, Value::Array(value))) if keep_empty && !value.is_empty() => {
+                self.inner = Some(Box::new(self.array(key, value)));
+                self.next()
+            }
+            next => next,
         }
     }
 }
@@ -359,5 +435,33 @@ mod test {
             })),
             tdef: TypeDef::object(Collection::any()),
         }
+
+        array_indices_separator {
+            args: func_args![value: value!({
+                parent: [{ child: 1 }, [2, 3]],
+                key: [],
+            }), array_indices: "separator"],
+            want: Ok(value!({
+                "parent.0.child": 1,
+                "parent.1.0": 2,
+                "parent.1.1": 3,
+                key: [],
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        array_indices_brackets {
+            args: func_args![value: value!({
+                parent: [{ child: 1 }, [2, 3]],
+                key: {},
+            }), separator: "_", array_indices: "brackets"],
+            want: Ok(value!({
+                "parent[0]_child": 1,
+                "parent[1][0]": 2,
+                "parent[1][1]": 3,
+                key: {},
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
     ];
 }
@@ -0,0 +1,113 @@
+use crate::compiler::prelude::*;
+
+use super::hash_util::{self, encodings, HashEncoding};
+
+fn fnv1a(value: Value, encoding: HashEncoding) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(encoding.encode64(hash_util::fnv1a(&value)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fnv1a;
+
+impl Function for Fnv1a {
+    fn identifier(&self) -> &'static str {
+        "fnv1a"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "encoding",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "fnv1a",
+                source: r#"fnv1a("foobar")"#,
+                result: Ok("-8821353812377114648"),
+            },
+            Example {
+                title: "hex encoding",
+                source: r#"fnv1a("foobar", encoding: "hex")"#,
+                result: Ok("85944171f73967e8"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let encoding = arguments
+            .optional_enum("encoding", &encodings(), state)?
+            .map_or(HashEncoding::Integer, |encoding| {
+                HashEncoding::from_bytes(&encoding.try_bytes().expect("encoding not bytes"))
+            });
+
+        Ok(Fnv1aFn { value, encoding }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Fnv1aFn {
+    value: Box<dyn Expression>,
+    encoding: HashEncoding,
+}
+
+impl FunctionExpression for Fnv1aFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        fnv1a(value, self.encoding)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        self.encoding.type_def()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        fnv1a => Fnv1a;
+
+        integer {
+            args: func_args![value: "foo"],
+            want: Ok(-2_543_842_089_295_555_209_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        hex {
+            args: func_args![value: "foo", encoding: "hex"],
+            want: Ok("dcb27518fed9d577"),
+            tdef: TypeDef::bytes(),
+        }
+
+        empty {
+            args: func_args![value: "", encoding: "hex"],
+            want: Ok("cbf29ce484222325"),
+            tdef: TypeDef::bytes(),
+        }
+    ];
+}
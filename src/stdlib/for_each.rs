@@ -81,6 +81,7 @@ impl Function for ForEach {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
@@ -93,7 +94,6 @@ struct ForEachFn {
 
 impl FunctionExpression for ForEachFn {
     fn resolve(&self, ctx: &mut Context) -> ExpressionResult<Value> {
-        let value = self.value.resolve(ctx)?;
         let FunctionClosure {
             variables,
             block,
@@ -101,6 +101,16 @@ impl FunctionExpression for ForEachFn {
         } = &self.closure;
         let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
 
+        // Iterate over ranges without materializing them as an array.
+        if let Some(range) = self.value.resolve_range(ctx) {
+            for (index, value) in range?.enumerate() {
+                runner.run_index_value(ctx, index, &value.into())?;
+            }
+
+            return Ok(Value::Null);
+        }
+
+        let value = self.value.resolve(ctx)?;
         for_each(value, ctx, runner)
     }
 
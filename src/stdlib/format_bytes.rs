@@ -0,0 +1,179 @@
+use crate::compiler::prelude::*;
+use crate::value;
+
+const BIN_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DEC_UNITS: &[&str] = &["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+fn format_bytes(value: Value, precision: Value, base: &Bytes) -> Resolved {
+    let (step, units) = match base.as_ref() {
+        b"2" => (1024.0, BIN_UNITS),
+        b"10" => (1000.0, DEC_UNITS),
+        _ => unreachable!("enum invariant"),
+    };
+    let value = value.try_integer()?;
+    let precision =
+        usize::try_from(precision.try_integer()?).map_err(|_| "precision must not be negative")?;
+
+    let sign = if value < 0 { "-" } else { "" };
+    let bytes = value.unsigned_abs();
+
+    // Whole bytes don't have a fractional part.
+    if (bytes as f64) < step {
+        return Ok(format!("{sign}{bytes} B").into());
+    }
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= step && unit < units.len() - 1 {
+        size /= step;
+        unit += 1;
+    }
+
+    Ok(format!("{sign}{size:.precision$} {}", units[unit]).into())
+}
+
+fn base_sets() -> Vec<Value> {
+    vec![value!("2"), value!("10")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FormatBytes;
+
+impl Function for FormatBytes {
+    fn identifier(&self) -> &'static str {
+        "format_bytes"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "precision",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "base",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "binary units",
+                source: "format_bytes!(1610612736)",
+                result: Ok("1.50 GiB"),
+            },
+            Example {
+                title: "decimal units",
+                source: r#"format_bytes!(200000000, precision: 0, base: "10")"#,
+                result: Ok("200 MB"),
+            },
+            Example {
+                title: "bytes",
+                source: "format_bytes!(512)",
+                result: Ok("512 B"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let precision = arguments.optional("precision").unwrap_or_else(|| expr!(2));
+        let base = arguments
+            .optional_enum("base", &base_sets(), state)?
+            .unwrap_or_else(|| value!("2"))
+            .try_bytes()
+            .expect("base not bytes");
+
+        Ok(FormatBytesFn {
+            value,
+            precision,
+            base,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FormatBytesFn {
+    value: Box<dyn Expression>,
+    precision: Box<dyn Expression>,
+    base: Bytes,
+}
+
+impl FunctionExpression for FormatBytesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let precision = self.precision.resolve(ctx)?;
+
+        format_bytes(value, precision, &self.base)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        format_bytes => FormatBytes;
+
+        bytes {
+            args: func_args![value: 1023],
+            want: Ok("1023 B"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        kibibytes {
+            args: func_args![value: 1536],
+            want: Ok("1.50 KiB"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        gibibytes {
+            args: func_args![value: 1_610_612_736, precision: 1],
+            want: Ok("1.5 GiB"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exbibytes {
+            args: func_args![value: i64::MAX, precision: 0],
+            want: Ok("8 EiB"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        decimal {
+            args: func_args![value: 1_500_000, base: "10"],
+            want: Ok("1.50 MB"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -2048, precision: 0],
+            want: Ok("-2 KiB"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_precision {
+            args: func_args![value: 2048, precision: -1],
+            want: Err("precision must not be negative"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -45,6 +45,10 @@ impl Function for FormatInt {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -0,0 +1,168 @@
+use crate::compiler::prelude::*;
+use crate::value;
+
+use super::mac_utils::{MacAddress, MacFormat};
+
+fn formats() -> Vec<Value> {
+    vec![
+        value!("colon"),
+        value!("dash"),
+        value!("dot"),
+        value!("bare"),
+    ]
+}
+
+fn format_from_bytes(bytes: &[u8]) -> MacFormat {
+    match bytes {
+        b"colon" => MacFormat::Colon,
+        b"dash" => MacFormat::Dash,
+        b"dot" => MacFormat::Dot,
+        b"bare" => MacFormat::Bare,
+        _ => unreachable!("enum invariant"),
+    }
+}
+
+fn format_mac(value: &Value, format: MacFormat, uppercase: Value) -> Resolved {
+    let address = MacAddress::parse(&value.try_bytes_utf8_lossy()?)?;
+    let uppercase = uppercase.try_boolean()?;
+
+    Ok(address.format(format, uppercase).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FormatMac;
+
+impl Function for FormatMac {
+    fn identifier(&self) -> &'static str {
+        "format_mac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "format",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "uppercase",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "normalize to colon notation",
+                source: r#"format_mac!("001A.2B3C.4D5E")"#,
+                result: Ok("00:1a:2b:3c:4d:5e"),
+            },
+            Example {
+                title: "dash notation in uppercase",
+                source: r#"format_mac!("00:1a:2b:3c:4d:5e", format: "dash", uppercase: true)"#,
+                result: Ok("00-1A-2B-3C-4D-5E"),
+            },
+            Example {
+                title: "dotted notation",
+                source: r#"format_mac!("00-1a-2b-3c-4d-5e", format: "dot")"#,
+                result: Ok("001a.2b3c.4d5e"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let format = arguments
+            .optional_enum("format", &formats(), state)?
+            .map_or(MacFormat::Colon, |format| {
+                format_from_bytes(&format.try_bytes().expect("format not bytes"))
+            });
+        let uppercase = arguments
+            .optional("uppercase")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(FormatMacFn {
+            value,
+            format,
+            uppercase,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FormatMacFn {
+    value: Box<dyn Expression>,
+    format: MacFormat,
+    uppercase: Box<dyn Expression>,
+}
+
+impl FunctionExpression for FormatMacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let uppercase = self.uppercase.resolve(ctx)?;
+
+        format_mac(&value, self.format, uppercase)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        format_mac => FormatMac;
+
+        default_format {
+            args: func_args![value: "001A2B3C4D5E"],
+            want: Ok("00:1a:2b:3c:4d:5e"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        dash_uppercase {
+            args: func_args![value: "00:1a:2b:3c:4d:5e", format: "dash", uppercase: true],
+            want: Ok("00-1A-2B-3C-4D-5E"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        dot {
+            args: func_args![value: "00:1a:2b:3c:4d:5e", format: "dot"],
+            want: Ok("001a.2b3c.4d5e"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        bare {
+            args: func_args![value: "00-1a-2b-3c-4d-5e", format: "bare"],
+            want: Ok("001a2b3c4d5e"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_address {
+            args: func_args![value: "00:1a:2b:3c:4d:zz"],
+            want: Err(r#"unable to parse MAC address "00:1a:2b:3c:4d:zz""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_format {
+            args: func_args![value: "00:1a:2b:3c:4d:5e", format: "cisco"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -6,6 +6,7 @@ fn format_number(
     scale: Option<Value>,
     grouping_separator: Option<Value>,
     decimal_separator: Option<Value>,
+    options: &Options,
 ) -> Resolved {
     let value: Decimal = match value {
         Value::Integer(v) => v.into(),
@@ -18,17 +19,20 @@ fn format_number(
             .into())
         }
     };
+    // The sign is added back after grouping the digits.
+    let negative = value.is_sign_negative() && !value.is_zero();
+    let value = value.abs();
     let scale = match scale {
         Some(expr) => Some(expr.try_integer()?),
-        None => None,
+        None => options.scale,
     };
     let grouping_separator = match grouping_separator {
-        Some(expr) => Some(expr.try_bytes()?),
-        None => None,
+        Some(expr) => Some(String::from_utf8_lossy(&expr.try_bytes()?).into_owned()),
+        None => options.grouping_separator.map(ToOwned::to_owned),
     };
     let decimal_separator = match decimal_separator {
-        Some(expr) => expr.try_bytes()?,
-        None => ".".into(),
+        Some(expr) => String::from_utf8_lossy(&expr.try_bytes()?).into_owned(),
+        None => options.decimal_separator.to_owned(),
     };
     // Split integral and fractional part of float.
     let mut parts = value
@@ -58,26 +62,140 @@ fn format_number(
         None => {}
     }
     // Manipulate integral part based on configuration.
-    if let Some(sep) = grouping_separator.as_deref() {
-        let sep = String::from_utf8_lossy(sep);
-        let start = parts[0].len() % 3;
+    if let Some(sep) = grouping_separator {
+        parts[0] = group(&parts[0], &sep, options);
+    }
+    // Join results, using configured decimal separator.
+    let mut number = parts.join(&decimal_separator);
+    if let Some(pattern) = &options.currency_pattern {
+        number = pattern.replace('#', &number);
+    }
+    if negative {
+        number.insert_str(0, options.minus_sign);
+    }
+
+    Ok(number.into())
+}
+
+/// Inserts the separator between the groups of digits of the integral part.
+fn group(digits: &str, separator: &str, options: &Options) -> String {
+    let len = digits.len();
+    if len < options.primary_group + options.min_grouping {
+        return digits.to_owned();
+    }
+
+    let mut end = len - options.primary_group;
+    let mut groups = vec![&digits[end..]];
+    while end > 0 {
+        let start = end.saturating_sub(options.secondary_group);
+        groups.push(&digits[start..end]);
+        end = start;
+    }
+    groups.reverse();
+
+    groups.join(separator)
+}
+
+/// The formatting defaults of a locale and currency, which apply to the
+/// arguments that aren't passed.
+#[derive(Clone, Debug)]
+struct Options {
+    decimal_separator: &'static str,
+    grouping_separator: Option<&'static str>,
+    primary_group: usize,
+    secondary_group: usize,
+    min_grouping: usize,
+    minus_sign: &'static str,
+    scale: Option<i64>,
 
-        let positions: Vec<usize> = parts[0]
-            .chars()
-            .skip(start)
-            .enumerate()
-            .map(|(i, _)| i)
-            .filter(|i| i % 3 == 0)
-            .collect();
+    /// Where the number (`#`) goes relative to the currency symbol.
+    currency_pattern: Option<String>,
+}
 
-        for (i, pos) in positions.iter().enumerate() {
-            parts[0].insert_str(pos + (i * sep.len()) + start, &sep);
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            decimal_separator: ".",
+            grouping_separator: None,
+            primary_group: 3,
+            secondary_group: 3,
+            min_grouping: 1,
+            minus_sign: "-",
+            scale: None,
+            currency_pattern: None,
         }
     }
-    // Join results, using configured decimal separator.
-    Ok(parts
-        .join(&String::from_utf8_lossy(&decimal_separator[..]))
-        .into())
+}
+
+#[cfg(feature = "format_number_locales")]
+fn options(arguments: &ArgumentList, state: &TypeState) -> Result<Options, function::Error> {
+    use super::number_locales::{self, CURRENCIES, LOCALES};
+    use crate::value;
+
+    let names = LOCALES
+        .iter()
+        .map(|locale| Value::from(locale.name))
+        .collect::<Vec<_>>();
+    let locale = arguments
+        .optional_enum("locale", &names, state)?
+        .and_then(|name| number_locales::locale(&name.as_str()?));
+
+    let codes = CURRENCIES
+        .iter()
+        .map(|currency| Value::from(currency.code))
+        .collect::<Vec<_>>();
+    let currency = arguments
+        .optional_enum("currency", &codes, state)?
+        .and_then(|code| number_locales::currency(&code.as_str()?));
+
+    let style = arguments
+        .optional_enum("style", &[value!("decimal"), value!("currency")], state)?
+        .unwrap_or_else(|| value!("decimal"));
+    let currency_style = style == value!("currency");
+
+    let mut options = Options::default();
+    if let Some(locale) = locale {
+        options.decimal_separator = locale.decimal_separator;
+        options.grouping_separator = Some(locale.grouping_separator);
+        options.primary_group = locale.primary_group;
+        options.secondary_group = locale.secondary_group;
+        options.min_grouping = locale.min_grouping;
+        options.minus_sign = locale.minus_sign;
+    }
+
+    match (currency_style, currency) {
+        (true, Some(currency)) => {
+            let (pattern, symbol) = match locale {
+                Some(locale) => (locale.currency_pattern, locale.currency_symbol(currency)),
+                None => ("¤#", currency.symbol),
+            };
+            options.currency_pattern = Some(pattern.replace('¤', symbol));
+            options.scale = Some(currency.digits);
+        }
+        (true, None) => {
+            return Err(function::Error::InvalidArgument {
+                keyword: "style",
+                value: style,
+                error: "the currency style requires a currency",
+            });
+        }
+        (false, Some(currency)) => {
+            return Err(function::Error::InvalidArgument {
+                keyword: "currency",
+                value: currency.code.into(),
+                error: "a currency requires the currency style",
+            });
+        }
+        (false, None) => {}
+    }
+
+    Ok(options)
+}
+
+#[cfg(not(feature = "format_number_locales"))]
+#[allow(clippy::unnecessary_wraps)]
+fn options(_: &ArgumentList, _: &TypeState) -> Result<Options, function::Error> {
+    Ok(Options::default())
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -110,15 +228,34 @@ impl Function for FormatNumber {
                 kind: kind::BYTES,
                 required: false,
             },
+            #[cfg(feature = "format_number_locales")]
+            Parameter {
+                keyword: "locale",
+                kind: kind::BYTES,
+                required: false,
+            },
+            #[cfg(feature = "format_number_locales")]
+            Parameter {
+                keyword: "style",
+                kind: kind::BYTES,
+                required: false,
+            },
+            #[cfg(feature = "format_number_locales")]
+            Parameter {
+                keyword: "currency",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
+        let options = options(&arguments, state)?;
         let value = arguments.required("value");
         let scale = arguments.optional("scale");
         let decimal_separator = arguments.optional("decimal_separator");
@@ -129,16 +266,31 @@ impl Function for FormatNumber {
             scale,
             decimal_separator,
             grouping_separator,
+            options,
         }
         .as_expr())
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "format number",
-            source: r#"format_number(4672.4, decimal_separator: ",", grouping_separator: "_")"#,
-            result: Ok("4_672,4"),
-        }]
+        &[
+            Example {
+                title: "format number",
+                source: r#"format_number(4672.4, decimal_separator: ",", grouping_separator: "_")"#,
+                result: Ok("4_672,4"),
+            },
+            #[cfg(feature = "format_number_locales")]
+            Example {
+                title: "locale",
+                source: r#"format_number(4672.4, scale: 2, locale: "de-DE")"#,
+                result: Ok("4.672,40"),
+            },
+            #[cfg(feature = "format_number_locales")]
+            Example {
+                title: "currency",
+                source: r#"format_number(4672.4, locale: "en-US", style: "currency", currency: "EUR")"#,
+                result: Ok("€4,672.40"),
+            },
+        ]
     }
 }
 
@@ -148,6 +300,7 @@ struct FormatNumberFn {
     scale: Option<Box<dyn Expression>>,
     decimal_separator: Option<Box<dyn Expression>>,
     grouping_separator: Option<Box<dyn Expression>>,
+    options: Options,
 }
 
 impl FunctionExpression for FormatNumberFn {
@@ -169,7 +322,13 @@ impl FunctionExpression for FormatNumberFn {
             .map(|expr| expr.resolve(ctx))
             .transpose()?;
 
-        format_number(value, scale, grouping_separator, decimal_separator)
+        format_number(
+            value,
+            scale,
+            grouping_separator,
+            decimal_separator,
+            &self.options,
+        )
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -251,5 +410,123 @@ mod tests {
             want: Ok(value!("12345.00")),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        negative_grouping {
+            args: func_args![value: -123_456,
+                             grouping_separator: ","],
+            want: Ok(value!("-123,456")),
+            tdef: TypeDef::bytes().infallible(),
+        }
     ];
+
+    #[cfg(feature = "format_number_locales")]
+    mod locales {
+        use super::*;
+
+        test_function![
+            format_number => FormatNumber;
+
+            locale {
+                args: func_args![value: 1_234_567.891,
+                                 scale: 2,
+                                 locale: "de-DE"],
+                want: Ok(value!("1.234.567,89")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            locale_narrow_space {
+                args: func_args![value: 1234.5,
+                                 locale: "fr-FR"],
+                want: Ok(value!("1\u{202f}234,5")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            locale_min_grouping {
+                args: func_args![value: 1234.5,
+                                 locale: "es-ES"],
+                want: Ok(value!("1234,5")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            locale_min_grouping_grouped {
+                args: func_args![value: 12345,
+                                 locale: "es-ES"],
+                want: Ok(value!("12.345")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            locale_indian_grouping {
+                args: func_args![value: 12_345_678,
+                                 locale: "en-IN"],
+                want: Ok(value!("1,23,45,678")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            locale_minus_sign {
+                args: func_args![value: -1234,
+                                 locale: "sv-SE"],
+                want: Ok(value!("\u{2212}1\u{a0}234")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            locale_override {
+                args: func_args![value: 1234.5,
+                                 locale: "de-DE",
+                                 grouping_separator: " "],
+                want: Ok(value!("1 234,5")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            currency {
+                args: func_args![value: 1234.5,
+                                 locale: "en-US",
+                                 style: "currency",
+                                 currency: "USD"],
+                want: Ok(value!("$1,234.50")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            currency_negative {
+                args: func_args![value: -1234.5,
+                                 locale: "en-US",
+                                 style: "currency",
+                                 currency: "USD"],
+                want: Ok(value!("-$1,234.50")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            currency_suffix {
+                args: func_args![value: 1234.5,
+                                 locale: "de-DE",
+                                 style: "currency",
+                                 currency: "EUR"],
+                want: Ok(value!("1.234,50\u{a0}€")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            currency_local_symbol {
+                args: func_args![value: 1234.5,
+                                 locale: "ja-JP",
+                                 style: "currency",
+                                 currency: "JPY"],
+                want: Ok(value!("￥1,234")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            currency_without_locale {
+                args: func_args![value: 1234.5,
+                                 style: "currency",
+                                 currency: "GBP"],
+                want: Ok(value!("£1234.50")),
+                tdef: TypeDef::bytes().infallible(),
+            }
+
+            currency_without_style {
+                args: func_args![value: 1234.5,
+                                 currency: "GBP"],
+                want: Err("invalid argument"),
+                tdef: TypeDef::bytes().infallible(),
+            }
+        ];
+    }
 }
@@ -1,7 +1,12 @@
 use crate::compiler::prelude::*;
+use crate::compiler::EnvVarPolicy;
 
-fn get_env_var(value: Value) -> Resolved {
+fn get_env_var(value: Value, policy: &EnvVarPolicy) -> Resolved {
     let name = value.try_bytes_utf8_lossy()?;
+    if !policy.is_allowed(&name) {
+        return Err(format!("environment variable {name} is not allowed").into());
+    }
+
     std::env::var(name.as_ref())
         .map(Into::into)
         .map_err(|e| e.to_string().into())
@@ -33,25 +38,40 @@ impl Function for GetEnvVar {
 
     fn compile(
         &self,
-        _state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let name = arguments.required("name");
+        let policy = ctx.env_var_policy().clone();
+
+        if let Some(value) = name.resolve_constant(state) {
+            if let Some(bytes) = value.as_bytes() {
+                if !policy.is_allowed(&String::from_utf8_lossy(bytes)) {
+                    return Err(function::Error::InvalidArgument {
+                        keyword: "name",
+                        value,
+                        error: "environment variable is not allowed",
+                    }
+                    .into());
+                }
+            }
+        }
 
-        Ok(GetEnvVarFn { name }.as_expr())
+        Ok(GetEnvVarFn { name, policy }.as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
 struct GetEnvVarFn {
     name: Box<dyn Expression>,
+    policy: EnvVarPolicy,
 }
 
 impl FunctionExpression for GetEnvVarFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.name.resolve(ctx)?;
-        get_env_var(value)
+        get_env_var(value, &self.policy)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -62,6 +82,7 @@ impl FunctionExpression for GetEnvVarFn {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::CompileConfig;
     use crate::value;
 
     test_function![
@@ -101,4 +122,28 @@ mod tests {
             tdef: TypeDef::bytes().fallible(),
         }
     ];
+
+    #[test]
+    fn disallowed() {
+        let mut config = CompileConfig::default();
+        config.set_env_var_policy(EnvVarPolicy::default().with_denied(["VAR3"]));
+
+        let result = crate::compiler::compile_with_state(
+            r#"get_env_var!("VAR3")"#,
+            &[Box::new(GetEnvVar) as _],
+            &state::TypeState::default(),
+            config,
+        );
+        assert!(result.is_err());
+
+        let error = get_env_var(
+            value!("VAR3"),
+            &EnvVarPolicy::default().with_denied(["VAR3"]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "environment variable VAR3 is not allowed"
+        );
+    }
 }
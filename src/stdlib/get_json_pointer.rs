@@ -0,0 +1,150 @@
+use crate::compiler::prelude::*;
+
+use super::json_patch::{parse_pointer, resolve_pointer};
+
+fn get_json_pointer(value: &Value, pointer: Value) -> Resolved {
+    let pointer = pointer.try_bytes_utf8_lossy()?;
+    parse_pointer(&pointer)?;
+
+    // Once the pointer is valid, a token that isn't an index of an array refers to a missing
+    // element, just like an index past the end of the array.
+    let value = resolve_pointer(value, &pointer)
+        .ok()
+        .and_then(|path| value.get(&path).cloned());
+    Ok(value.unwrap_or(Value::Null))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetJsonPointer;
+
+impl Function for GetJsonPointer {
+    fn identifier(&self) -> &'static str {
+        "get_json_pointer"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "pointer",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "nested field",
+                source: r#"get_json_pointer({"a": {"b": [1, 2]}}, "/a/b/1")"#,
+                result: Ok("2"),
+            },
+            Example {
+                title: "escaped field",
+                source: r#"get_json_pointer({"a/b": 1}, "/a~1b")"#,
+                result: Ok("1"),
+            },
+            Example {
+                title: "missing field",
+                source: r#"get_json_pointer({"a": 1}, "/b")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let pointer = arguments.required("pointer");
+
+        if let Some(literal) = pointer.resolve_constant(state) {
+            if let Some(bytes) = literal.as_bytes() {
+                if parse_pointer(&String::from_utf8_lossy(bytes)).is_err() {
+                    return Err(function::Error::InvalidArgument {
+                        keyword: "pointer",
+                        value: literal,
+                        error: "invalid JSON pointer",
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(GetJsonPointerFn { value, pointer }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetJsonPointerFn {
+    value: Box<dyn Expression>,
+    pointer: Box<dyn Expression>,
+}
+
+impl FunctionExpression for GetJsonPointerFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let pointer = self.pointer.resolve(ctx)?;
+
+        get_json_pointer(&value, pointer)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let not_literal = self.pointer.resolve_constant(state).is_none();
+        TypeDef::any().maybe_fallible(not_literal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        get_json_pointer => GetJsonPointer;
+
+        nested {
+            args: func_args![value: value!({"a": {"b": [1, {"c": 2}]}}), pointer: "/a/b/1/c"],
+            want: Ok(value!(2)),
+            tdef: TypeDef::any(),
+        }
+
+        root {
+            args: func_args![value: value!({"a": 1}), pointer: ""],
+            want: Ok(value!({"a": 1})),
+            tdef: TypeDef::any(),
+        }
+
+        escaped {
+            args: func_args![value: value!({"a/b": {"~": 1}}), pointer: "/a~1b/~0"],
+            want: Ok(value!(1)),
+            tdef: TypeDef::any(),
+        }
+
+        missing {
+            args: func_args![value: value!({"a": [1]}), pointer: "/a/1"],
+            want: Ok(value!(null)),
+            tdef: TypeDef::any(),
+        }
+
+        invalid_index {
+            args: func_args![value: value!({"a": [1]}), pointer: "/a/01"],
+            want: Ok(value!(null)),
+            tdef: TypeDef::any(),
+        }
+
+        invalid_pointer {
+            args: func_args![value: value!({}), pointer: "a"],
+            want: Err("invalid argument"),
+            tdef: TypeDef::any(),
+        }
+    ];
+}
@@ -0,0 +1,243 @@
+use crate::compiler::prelude::*;
+use crate::value;
+
+/// How the hash functions represent their result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum HashEncoding {
+    /// A signed integer. 64 bit hashes above `i64::MAX` wrap around.
+    Integer,
+    /// A zero-padded lowercase hex string.
+    Hex,
+}
+
+impl HashEncoding {
+    pub(super) fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes {
+            b"integer" => Self::Integer,
+            b"hex" => Self::Hex,
+            _ => unreachable!("enum invariant"),
+        }
+    }
+
+    pub(super) fn encode32(self, hash: u32) -> Value {
+        match self {
+            Self::Integer => i64::from(hash).into(),
+            Self::Hex => format!("{hash:08x}").into(),
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    pub(super) fn encode64(self, hash: u64) -> Value {
+        match self {
+            Self::Integer => (hash as i64).into(),
+            Self::Hex => format!("{hash:016x}").into(),
+        }
+    }
+
+    pub(super) fn type_def(self) -> TypeDef {
+        match self {
+            Self::Integer => TypeDef::integer(),
+            Self::Hex => TypeDef::bytes(),
+        }
+    }
+}
+
+pub(super) fn encodings() -> Vec<Value> {
+    vec![value!("integer"), value!("hex")]
+}
+
+/// Converts the seed of a 32 bit hash, failing if it doesn't fit in 32 bits.
+pub(super) fn seed32(seed: Value) -> Result<u32, ExpressionError> {
+    let seed = seed.try_integer()?;
+    u32::try_from(seed)
+        .map_err(|_| format!("seed must be between 0 and {}, got {seed}", u32::MAX).into())
+}
+
+/// Reinterprets the bits of the seed of a 64 bit hash.
+#[allow(clippy::cast_sign_loss)]
+pub(super) fn seed64(seed: Value) -> Result<u64, ExpressionError> {
+    Ok(seed.try_integer()? as u64)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 as used by zlib, gzip and PNG. The seed is the checksum of the
+/// preceding data, which allows computing the checksum incrementally.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn crc32(data: &[u8], seed: u32) -> u32 {
+    !data.iter().fold(!seed, |crc, byte| {
+        CRC32_TABLE[usize::from(crc as u8 ^ byte)] ^ (crc >> 8)
+    })
+}
+
+const XXH_PRIME64_1: u64 = 0x9e37_79b1_85eb_ca87;
+const XXH_PRIME64_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+const XXH_PRIME64_3: u64 = 0x1656_67b1_9e37_79f9;
+const XXH_PRIME64_4: u64 = 0x85eb_ca77_c2b2_ae63;
+const XXH_PRIME64_5: u64 = 0x27d4_eb2f_1656_67c5;
+
+fn xxh64_round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(XXH_PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(XXH_PRIME64_1)
+}
+
+fn xxh64_merge_round(acc: u64, value: u64) -> u64 {
+    (acc ^ xxh64_round(0, value))
+        .wrapping_mul(XXH_PRIME64_1)
+        .wrapping_add(XXH_PRIME64_4)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"))
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().expect("4 bytes"))
+}
+
+/// The 64 bit variant of xxHash (XXH64).
+pub(super) fn xxhash64(data: &[u8], seed: u64) -> u64 {
+    let mut stripes = data.chunks_exact(32);
+    let mut hash = if data.len() >= 32 {
+        let mut acc = [
+            seed.wrapping_add(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_2),
+            seed.wrapping_add(XXH_PRIME64_2),
+            seed,
+            seed.wrapping_sub(XXH_PRIME64_1),
+        ];
+        for stripe in stripes.by_ref() {
+            for (lane, acc) in stripe.chunks_exact(8).zip(&mut acc) {
+                *acc = xxh64_round(*acc, read_u64(lane));
+            }
+        }
+        let hash = acc[0]
+            .rotate_left(1)
+            .wrapping_add(acc[1].rotate_left(7))
+            .wrapping_add(acc[2].rotate_left(12))
+            .wrapping_add(acc[3].rotate_left(18));
+        acc.into_iter().fold(hash, xxh64_merge_round)
+    } else {
+        seed.wrapping_add(XXH_PRIME64_5)
+    };
+    hash = hash.wrapping_add(data.len() as u64);
+
+    let mut rest = stripes.remainder();
+    while rest.len() >= 8 {
+        hash = (hash ^ xxh64_round(0, read_u64(rest)))
+            .rotate_left(27)
+            .wrapping_mul(XXH_PRIME64_1)
+            .wrapping_add(XXH_PRIME64_4);
+        rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+        hash = (hash ^ u64::from(read_u32(rest)).wrapping_mul(XXH_PRIME64_1))
+            .rotate_left(23)
+            .wrapping_mul(XXH_PRIME64_2)
+            .wrapping_add(XXH_PRIME64_3);
+        rest = &rest[4..];
+    }
+    for byte in rest {
+        hash = (hash ^ u64::from(*byte).wrapping_mul(XXH_PRIME64_5))
+            .rotate_left(11)
+            .wrapping_mul(XXH_PRIME64_1);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(XXH_PRIME64_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(XXH_PRIME64_3);
+    hash ^ (hash >> 32)
+}
+
+fn murmur3_scramble(k: u32) -> u32 {
+    k.wrapping_mul(0xcc9e_2d51)
+        .rotate_left(15)
+        .wrapping_mul(0x1b87_3593)
+}
+
+/// The 32 bit x86 variant of MurmurHash3.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn murmur3(data: &[u8], seed: u32) -> u32 {
+    let mut blocks = data.chunks_exact(4);
+    let mut hash = seed;
+    for block in blocks.by_ref() {
+        hash = (hash ^ murmur3_scramble(read_u32(block)))
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    let tail = blocks.remainder();
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .rev()
+            .fold(0, |k, byte| (k << 8) | u32::from(*byte));
+        hash ^= murmur3_scramble(k);
+    }
+
+    // The length is mixed in modulo 2^32, as in the reference implementation.
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
+/// The 64 bit variant of FNV-1a.
+pub(super) fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_values() {
+        assert_eq!(crc32(b"", 0), 0);
+        assert_eq!(crc32(b"123456789", 0), 0xcbf4_3926);
+        assert_eq!(crc32(b"6789", crc32(b"12345", 0)), 0xcbf4_3926);
+
+        assert_eq!(xxhash64(b"", 0), 0xef46_db37_51d8_e999);
+        assert_eq!(xxhash64(b"abc", 0), 0x44bc_2cf5_ad77_0999);
+        assert_eq!(
+            xxhash64(b"The quick brown fox jumps over the lazy dog", 0),
+            0x0b24_2d36_1fda_71bc
+        );
+
+        assert_eq!(murmur3(b"", 0), 0);
+        assert_eq!(murmur3(b"hello", 0), 0x248b_fa47);
+        assert_eq!(
+            murmur3(b"The quick brown fox jumps over the lazy dog", 0),
+            0x2e4f_f723
+        );
+
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -2,30 +2,74 @@ use crate::compiler::prelude::*;
 use crate::value;
 use hmac::{Hmac as HmacHasher, Mac};
 use sha1::Sha1;
-use sha_2::{Sha224, Sha256, Sha384, Sha512};
+use sha_2::{Sha224, Sha256, Sha384, Sha512, Sha512_256};
+
+const VALID_ALGORITHMS: &[&str] = &[
+    "SHA1",
+    "SHA-224",
+    "SHA-256",
+    "SHA-384",
+    "SHA-512",
+    "SHA-512/256",
+    "BLAKE3",
+];
 
 macro_rules! hmac {
-    ($algorithm:ty, $key:expr, $val:expr) => {{
+    ($algorithm:ty, $key:expr, $chunks:expr) => {{
         let mut mac =
             <HmacHasher<$algorithm>>::new_from_slice($key.as_ref()).expect("key is bytes");
-        mac.update($val.as_ref());
+        for chunk in $chunks {
+            mac.update(chunk.as_ref());
+        }
         let result = mac.finalize();
         let code_bytes = result.into_bytes();
         code_bytes.to_vec()
     }};
 }
 
+fn blake3_key(key: &[u8]) -> Result<&[u8; blake3::KEY_LEN], String> {
+    key.try_into().map_err(|_| {
+        format!(
+            "BLAKE3 keys must be {} bytes, got {}",
+            blake3::KEY_LEN,
+            key.len()
+        )
+    })
+}
+
+fn blake3(key: &[u8], chunks: &[Bytes]) -> Result<Vec<u8>, String> {
+    let mut hasher = blake3::Hasher::new_keyed(blake3_key(key)?);
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    Ok(hasher.finalize().as_bytes().to_vec())
+}
+
+/// Returns the value to sign as a list of chunks. Arrays are signed as the
+/// concatenation of their elements, without building the concatenated string.
+fn chunks(value: Value) -> Result<Vec<Bytes>, ExpressionError> {
+    match value {
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| value.try_bytes().map_err(Into::into))
+            .collect(),
+        value => Ok(vec![value.try_bytes()?]),
+    }
+}
+
 fn hmac(value: Value, key: Value, algorithm: Value) -> Resolved {
-    let value = value.try_bytes()?;
+    let chunks = chunks(value)?;
     let key = key.try_bytes()?;
     let algorithm = algorithm.try_bytes_utf8_lossy()?.as_ref().to_uppercase();
 
     let code_bytes = match algorithm.as_str() {
-        "SHA1" => hmac!(Sha1, key, value),
-        "SHA-224" => hmac!(Sha224, key, value),
-        "SHA-256" => hmac!(Sha256, key, value),
-        "SHA-384" => hmac!(Sha384, key, value),
-        "SHA-512" => hmac!(Sha512, key, value),
+        "SHA1" => hmac!(Sha1, key, &chunks),
+        "SHA-224" => hmac!(Sha224, key, &chunks),
+        "SHA-256" => hmac!(Sha256, key, &chunks),
+        "SHA-384" => hmac!(Sha384, key, &chunks),
+        "SHA-512" => hmac!(Sha512, key, &chunks),
+        "SHA-512/256" => hmac!(Sha512_256, key, &chunks),
+        "BLAKE3" => blake3(&key, &chunks)?,
         _ => return Err(format!("Invalid algorithm: {algorithm}").into()),
     };
 
@@ -44,7 +88,7 @@ impl Function for Hmac {
         &[
             Parameter {
                 keyword: "value",
-                kind: kind::BYTES,
+                kind: kind::BYTES | kind::ARRAY,
                 required: true,
             },
             Parameter {
@@ -72,6 +116,16 @@ impl Function for Hmac {
                 source: r#"encode_base64(hmac("Hello there", "super-secret-key", algorithm: "SHA1"))"#,
                 result: Ok("MiyBIHO8Set9+6crALiwkS0yFPE="),
             },
+            Example {
+                title: "multiple parts",
+                source: r#"encode_base64(hmac(["Hello", " there"], "super-secret-key"))"#,
+                result: Ok("eLGE8YMviv85NPXgISRUZxstBNSU47JQdcXkUWcClmI="),
+            },
+            Example {
+                title: "BLAKE3",
+                source: r#"encode_base16(hmac("Hello there", "super-secret-key-that-is-32-byte", algorithm: "BLAKE3"))"#,
+                result: Ok("d0f9f8d87c1bac6b20aa4e84d29d69d7844cd9a32554dde50a2351177c44517f"),
+            },
         ]
     }
 
@@ -114,21 +168,37 @@ impl FunctionExpression for HmacFn {
     }
 
     fn type_def(&self, state: &state::TypeState) -> TypeDef {
-        let valid_algorithms = ["SHA1", "SHA-224", "SHA-256", "SHA-384", "SHA-512"];
-
-        let mut valid_static_algo = false;
-        if let Some(algorithm) = self.algorithm.as_ref() {
-            if let Some(algorithm) = algorithm.resolve_constant(state) {
-                if let Ok(algorithm) = algorithm.try_bytes_utf8_lossy() {
-                    let algorithm = algorithm.to_uppercase();
-                    valid_static_algo = valid_algorithms.contains(&algorithm.as_str());
-                }
-            }
-        } else {
-            valid_static_algo = true
-        }
+        let algorithm = match self.algorithm.as_ref() {
+            Some(algorithm) => algorithm.resolve_constant(state).and_then(|algorithm| {
+                algorithm
+                    .try_bytes_utf8_lossy()
+                    .ok()
+                    .map(|algorithm| algorithm.to_uppercase())
+            }),
+            None => Some("SHA-256".to_owned()),
+        };
 
-        if valid_static_algo {
+        let valid_static_algo = match algorithm.as_deref() {
+            // BLAKE3 only accepts keys of a fixed length.
+            Some("BLAKE3") => self
+                .key
+                .resolve_constant(state)
+                .and_then(|key| key.as_bytes().map(|key| blake3_key(key).is_ok()))
+                .unwrap_or(false),
+            Some(algorithm) => VALID_ALGORITHMS.contains(&algorithm),
+            None => false,
+        };
+
+        let chunks_are_bytes = self
+            .value
+            .type_def(state)
+            .kind()
+            .as_array()
+            .map_or(true, |array| {
+                Kind::bytes().is_superset(&array.reduced_kind()).is_ok()
+            });
+
+        if valid_static_algo && chunks_are_bytes {
             TypeDef::bytes().infallible()
         } else {
             TypeDef::bytes().fallible()
@@ -172,5 +242,41 @@ mod tests {
             want: Ok(value!(b" \xc9*\x07k\"\xf3C+\xfe\x91\x8d\xfeC\x14\xd0$<\x85\x08d:\xb1\xd7\xd7y\xa5e\x84\x81\xce/\xd4\x08!\x04@\x10\xe9x\xc16Q\x7fX\xff\xc8\xe6\xc1\xf2X0s\x88X0<\xf0\xa7\x10s\xc6\x0e\x96")),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        hmac_sha512_256 {
+            args: func_args![key: "super-secret-key", value: "Hello there", algorithm: "SHA-512/256"],
+            want: Ok(value!(b"\xda_\xf2\xd5t\xad\xd0=L\xce\xd3\x874\x7fNf\x1a\x84\x02}]\x7fl\xf9\xfd\x83t'%\xc6\xf8\xdd")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_blake3 {
+            args: func_args![key: "super-secret-key-that-is-32-byte", value: "Hello there", algorithm: "BLAKE3"],
+            want: Ok(value!(b"\xd0\xf9\xf8\xd8|\x1b\xack \xaaN\x84\xd2\x9di\xd7\x84L\xd9\xa3%T\xdd\xe5\x0a#Q\x17|DQ\x7f")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_blake3_invalid_key {
+            args: func_args![key: "super-secret-key", value: "Hello there", algorithm: "BLAKE3"],
+            want: Err("BLAKE3 keys must be 32 bytes, got 16"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        hmac_chunks {
+            args: func_args![key: "super-secret-key", value: value!(["Hello", " ", "there"])],
+            want: Ok(value!(b"x\xb1\x84\xf1\x83/\x8a\xff94\xf5\xe0!$Tg\x1b-\x04\xd4\x94\xe3\xb2Pu\xc5\xe4Qg\x02\x96b")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_blake3_chunks {
+            args: func_args![key: "super-secret-key-that-is-32-byte", value: value!(["Hello", " there"]), algorithm: "blake3"],
+            want: Ok(value!(b"\xd0\xf9\xf8\xd8|\x1b\xack \xaaN\x84\xd2\x9di\xd7\x84L\xd9\xa3%T\xdd\xe5\x0a#Q\x17|DQ\x7f")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_invalid_chunk {
+            args: func_args![key: "super-secret-key", value: value!(["Hello", 1])],
+            want: Err("expected string, got integer"),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
@@ -0,0 +1,135 @@
+use crate::compiler::prelude::*;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[allow(clippy::cast_sign_loss)]
+fn int_to_ip(value: Value) -> Resolved {
+    match value {
+        Value::Integer(int) => {
+            let int = u32::try_from(int)
+                .map_err(|_| format!("integer {int} is out of range for an IPv4 address"))?;
+
+            Ok(Ipv4Addr::from(int).to_string().into())
+        }
+        Value::Array(halves) => {
+            let [high, low] = halves.as_slice() else {
+                return Err("expected an array of two integers for an IPv6 address".into());
+            };
+            // The halves keep the bits of the address, as returned by `ip_to_int`.
+            let high = u128::from(high.clone().try_integer()? as u64);
+            let low = u128::from(low.clone().try_integer()? as u64);
+
+            Ok(Ipv6Addr::from((high << 64) | low).to_string().into())
+        }
+        value => Err(ValueError::Expected {
+            got: value.kind(),
+            expected: Kind::integer() | Kind::array(Collection::any()),
+        }
+        .into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IntToIp;
+
+impl Function for IntToIp {
+    fn identifier(&self) -> &'static str {
+        "int_to_ip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IPv4",
+                source: "int_to_ip!(3232235521)",
+                result: Ok("192.168.0.1"),
+            },
+            Example {
+                title: "IPv6",
+                source: "int_to_ip!([2306139568115548160, 1])",
+                result: Ok("2001:db8::1"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IntToIpFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IntToIpFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IntToIpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        int_to_ip(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        int_to_ip => IntToIp;
+
+        ipv4 {
+            args: func_args![value: 16_909_060],
+            want: Ok("1.2.3.4"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ipv4_out_of_range {
+            args: func_args![value: 4_294_967_296_i64],
+            want: Err("integer 4294967296 is out of range for an IPv4 address"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -1],
+            want: Err("integer -1 is out of range for an IPv4 address"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ipv6 {
+            args: func_args![value: value!([0, 281_470_698_652_420_i64])],
+            want: Ok("::ffff:1.2.3.4"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ipv6_high_bit {
+            args: func_args![value: Value::Array(vec![Value::from(-1), Value::from(-2)])],
+            want: Ok("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        wrong_length {
+            args: func_args![value: value!([1, 2, 3])],
+            want: Err("expected an array of two integers for an IPv6 address"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
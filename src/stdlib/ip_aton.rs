@@ -33,6 +33,10 @@ impl Function for IpAton {
         }]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -0,0 +1,184 @@
+use crate::compiler::prelude::*;
+
+use super::ip_utils::{parse_ip, CidrSet};
+
+fn parse_ranges(ranges: Value) -> Result<CidrSet, ExpressionError> {
+    let ranges = ranges
+        .try_array()?
+        .into_iter()
+        .map(|range| {
+            range
+                .try_bytes_utf8_lossy()
+                .map(std::borrow::Cow::into_owned)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CidrSet::from_strs(ranges)?)
+}
+
+/// The list of ranges, compiled at compile time when it is a literal.
+#[derive(Debug, Clone)]
+enum Ranges {
+    Compiled(CidrSet),
+    Expr(Box<dyn Expression>),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpInRangeList;
+
+impl Function for IpInRangeList {
+    fn identifier(&self) -> &'static str {
+        "ip_in_range_list"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "ranges",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "in range",
+                source: r#"ip_in_range_list!("192.168.10.32", ["10.0.0.0/8", "192.168.0.0/16"])"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "not in range",
+                source: r#"ip_in_range_list!("2001:db8::1", ["10.0.0.0/8", "fc00::/7"])"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let ranges = arguments.required("ranges");
+
+        let ranges = match ranges.resolve_constant(state) {
+            Some(literal) => match parse_ranges(literal.clone()) {
+                Ok(set) => Ranges::Compiled(set),
+                Err(_) => {
+                    return Err(function::Error::InvalidArgument {
+                        keyword: "ranges",
+                        value: literal,
+                        error: "must be a list of IP addresses and networks in CIDR notation",
+                    }
+                    .into())
+                }
+            },
+            None => Ranges::Expr(ranges),
+        };
+
+        Ok(IpInRangeListFn { value, ranges }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpInRangeListFn {
+    value: Box<dyn Expression>,
+    ranges: Ranges,
+}
+
+impl FunctionExpression for IpInRangeListFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let address = parse_ip(&value.try_bytes_utf8_lossy()?)?;
+
+        let contains = match &self.ranges {
+            Ranges::Compiled(set) => set.contains(&address),
+            Ranges::Expr(expr) => parse_ranges(expr.resolve(ctx)?)?.contains(&address),
+        };
+
+        Ok(contains.into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_in_range_list => IpInRangeList;
+
+        ipv4_yes {
+            args: func_args![value: "192.168.10.32", ranges: value!(["10.0.0.0/8", "192.168.0.0/16"])],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_no {
+            args: func_args![value: "192.169.0.1", ranges: value!(["10.0.0.0/8", "192.168.0.0/16"])],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_yes {
+            args: func_args![value: "2001:4f8:3:ba:2e0:81ff:fe22:d1f1", ranges: value!(["10.0.0.0/8", "2001:4f8:3:ba::/64"])],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        single_address {
+            args: func_args![value: "8.8.8.8", ranges: value!(["8.8.4.4", "8.8.8.8"])],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        empty_list {
+            args: func_args![value: "8.8.8.8", ranges: value!([])],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid_address {
+            args: func_args![value: "INVALID", ranges: value!(["10.0.0.0/8"])],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid_range {
+            args: func_args![value: "10.0.0.1", ranges: value!(["10.0.0.0/8", "INVALID"])],
+            want: Err("invalid argument"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+
+    #[test]
+    fn invalid_range_from_event() {
+        let source = r#"ip_in_range_list("10.0.0.1", .ranges)"#;
+        let program = crate::compiler::compile(source, &crate::stdlib::all())
+            .unwrap()
+            .program;
+        let mut target = value!({ "ranges": ["10.0.0.0/8", "INVALID"] });
+
+        let error = crate::compiler::runtime::Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(r#"unable to parse CIDR "INVALID""#),
+            "{error}"
+        );
+    }
+}
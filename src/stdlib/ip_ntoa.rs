@@ -34,6 +34,10 @@ impl Function for IpNtoa {
         }]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -48,6 +48,10 @@ impl Function for IpNtop {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
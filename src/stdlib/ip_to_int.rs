@@ -0,0 +1,120 @@
+use crate::compiler::prelude::*;
+use std::{collections::BTreeMap, net::IpAddr};
+
+use super::ip_utils::parse_ip;
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn ip_to_int(value: Value) -> Resolved {
+    match parse_ip(&value.try_bytes_utf8_lossy()?)? {
+        IpAddr::V4(ip) => Ok(i64::from(u32::from(ip)).into()),
+        IpAddr::V6(ip) => {
+            // VRL integers are 64 bits wide, so each half of the address keeps
+            // its bits, even when that makes it negative.
+            let ip = u128::from(ip);
+            let high = (ip >> 64) as u64 as i64;
+            let low = ip as u64 as i64;
+            Ok(Value::from(vec![Value::from(high), Value::from(low)]))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpToInt;
+
+impl Function for IpToInt {
+    fn identifier(&self) -> &'static str {
+        "ip_to_int"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IPv4",
+                source: r#"ip_to_int!("192.168.0.1")"#,
+                result: Ok("3232235521"),
+            },
+            Example {
+                title: "IPv6",
+                source: r#"ip_to_int!("2001:db8::1")"#,
+                result: Ok("[2306139568115548160, 1]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IpToIntFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpToIntFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IpToIntFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        ip_to_int(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().or_array(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Index, Kind> {
+    BTreeMap::from([
+        (Index::from(0), Kind::integer()),
+        (Index::from(1), Kind::integer()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        ip_to_int => IpToInt;
+
+        ipv4 {
+            args: func_args![value: "1.2.3.4"],
+            want: Ok(value!(16_909_060)),
+            tdef: TypeDef::integer().or_array(inner_kind()).fallible(),
+        }
+
+        ipv6 {
+            args: func_args![value: "::ffff:1.2.3.4"],
+            want: Ok(value!([0, 281_470_698_652_420_i64])),
+            tdef: TypeDef::integer().or_array(inner_kind()).fallible(),
+        }
+
+        ipv6_high_bit {
+            args: func_args![value: "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe"],
+            want: Ok(value!([-1, -2])),
+            tdef: TypeDef::integer().or_array(inner_kind()).fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "1.2.3"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::integer().or_array(inner_kind()).fallible(),
+        }
+    ];
+}
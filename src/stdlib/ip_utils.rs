@@ -0,0 +1,242 @@
+use cidr_utils::cidr::IpCidr;
+use once_cell::sync::Lazy;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// Private address ranges: RFC 1918 for IPv4 and unique local addresses for IPv6.
+const PRIVATE_RANGES: &[&str] = &["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16", "fc00::/7"];
+
+/// Ranges that aren't globally routable unicast addresses, from the IANA IPv4
+/// and IPv6 special-purpose address registries, plus multicast.
+const NON_GLOBAL_RANGES: &[&str] = &[
+    "0.0.0.0/8",
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.0.0.0/24",
+    "192.0.2.0/24",
+    "192.168.0.0/16",
+    "198.18.0.0/15",
+    "198.51.100.0/24",
+    "203.0.113.0/24",
+    "224.0.0.0/4",
+    "240.0.0.0/4",
+    "::/128",
+    "::1/128",
+    "64:ff9b:1::/48",
+    "100::/64",
+    "2001::/23",
+    "2001:db8::/32",
+    "3fff::/20",
+    "fc00::/7",
+    "fe80::/10",
+    "ff00::/8",
+];
+
+/// Ranges within [`NON_GLOBAL_RANGES`] that the IANA registries list as
+/// globally reachable.
+const GLOBAL_EXCEPTION_RANGES: &[&str] = &[
+    "192.0.0.9/32",
+    "192.0.0.10/32",
+    "2001:1::1/128",
+    "2001:1::2/128",
+    "2001:1::3/128",
+    "2001:3::/32",
+    "2001:4:112::/48",
+    "2001:20::/28",
+    "2001:30::/28",
+];
+
+pub(super) static PRIVATE: Lazy<CidrSet> =
+    Lazy::new(|| CidrSet::from_strs(PRIVATE_RANGES).expect("valid private ranges"));
+
+static NON_GLOBAL: Lazy<CidrSet> =
+    Lazy::new(|| CidrSet::from_strs(NON_GLOBAL_RANGES).expect("valid non-global ranges"));
+
+static GLOBAL_EXCEPTIONS: Lazy<CidrSet> = Lazy::new(|| {
+    CidrSet::from_strs(GLOBAL_EXCEPTION_RANGES).expect("valid global exception ranges")
+});
+
+/// Whether the address is a globally reachable unicast address.
+pub(super) fn is_global(address: &IpAddr) -> bool {
+    !NON_GLOBAL.contains(address) || GLOBAL_EXCEPTIONS.contains(address)
+}
+
+pub(super) fn parse_ip(value: &str) -> Result<IpAddr, String> {
+    IpAddr::from_str(value).map_err(|err| format!("unable to parse IP address: {err}"))
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    /// Indices of the child nodes for a 0 and 1 bit. The root is never a child,
+    /// so 0 means there is no child.
+    children: [u32; 2],
+    /// Whether a prefix ends at this node.
+    terminal: bool,
+}
+
+/// A binary trie of network prefixes. Lookups take at most one step per bit of
+/// the address, regardless of the number of prefixes.
+#[derive(Debug, Clone)]
+struct PrefixTrie {
+    width: u32,
+    nodes: Vec<Node>,
+}
+
+impl PrefixTrie {
+    fn new(width: u32) -> Self {
+        Self {
+            width,
+            nodes: vec![Node::default()],
+        }
+    }
+
+    fn bit(&self, address: u128, index: u32) -> usize {
+        usize::from((address >> (self.width - 1 - index)) & 1 == 1)
+    }
+
+    fn insert(&mut self, address: u128, length: u32) {
+        let mut node = 0;
+        for index in 0..length {
+            let bit = self.bit(address, index);
+            let child = self.nodes[node].children[bit];
+            node = if child == 0 {
+                self.nodes.push(Node::default());
+                let child = self.nodes.len() - 1;
+                self.nodes[node].children[bit] =
+                    u32::try_from(child).expect("trie has fewer than 2^32 nodes");
+                child
+            } else {
+                child as usize
+            };
+        }
+        self.nodes[node].terminal = true;
+    }
+
+    fn contains(&self, address: u128) -> bool {
+        let mut node = 0;
+        for index in 0..self.width {
+            if self.nodes[node].terminal {
+                return true;
+            }
+            match self.nodes[node].children[self.bit(address, index)] {
+                0 => return false,
+                child => node = child as usize,
+            }
+        }
+        self.nodes[node].terminal
+    }
+}
+
+/// A set of IPv4 and IPv6 networks, compiled for fast membership checks.
+#[derive(Debug, Clone)]
+pub(super) struct CidrSet {
+    v4: PrefixTrie,
+    v6: PrefixTrie,
+}
+
+impl Default for CidrSet {
+    fn default() -> Self {
+        Self {
+            v4: PrefixTrie::new(32),
+            v6: PrefixTrie::new(128),
+        }
+    }
+}
+
+impl CidrSet {
+    /// Parses a list of networks in CIDR notation. Plain addresses are treated as
+    /// single-address networks.
+    pub(super) fn from_strs<S: AsRef<str>>(
+        ranges: impl IntoIterator<Item = S>,
+    ) -> Result<Self, String> {
+        let mut set = Self::default();
+        for range in ranges {
+            let range = range.as_ref();
+            let cidr = IpCidr::from_str(range)
+                .map_err(|err| format!(r#"unable to parse CIDR "{range}": {err}"#))?;
+            let length = u32::from(cidr.network_length());
+            match cidr.first_address() {
+                IpAddr::V4(address) => set.v4.insert(u128::from(u32::from(address)), length),
+                IpAddr::V6(address) => set.v6.insert(u128::from(address), length),
+            }
+        }
+        Ok(set)
+    }
+
+    /// Checks whether `address` is in any of the networks. IPv4-mapped IPv6
+    /// addresses also match the IPv4 networks.
+    pub(super) fn contains(&self, address: &IpAddr) -> bool {
+        match address {
+            IpAddr::V4(address) => self.v4.contains(u128::from(u32::from(*address))),
+            IpAddr::V6(address) => {
+                self.v6.contains(u128::from(*address))
+                    || address
+                        .to_ipv4_mapped()
+                        .is_some_and(|v4| self.v4.contains(u128::from(u32::from(v4))))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains(set: &CidrSet, address: &str) -> bool {
+        set.contains(&parse_ip(address).unwrap())
+    }
+
+    #[test]
+    fn cidr_set() {
+        let set = CidrSet::from_strs(["10.0.0.0/8", "192.168.1.0/24", "1.2.3.4", "2001:db8::/32"])
+            .unwrap();
+
+        assert!(contains(&set, "10.255.0.1"));
+        assert!(contains(&set, "192.168.1.200"));
+        assert!(!contains(&set, "192.168.2.1"));
+        assert!(contains(&set, "1.2.3.4"));
+        assert!(!contains(&set, "1.2.3.5"));
+        assert!(contains(&set, "2001:db8::1"));
+        assert!(!contains(&set, "2001:db9::1"));
+        assert!(contains(&set, "::ffff:10.0.0.1"));
+    }
+
+    #[test]
+    fn match_all() {
+        let set = CidrSet::from_strs(["0.0.0.0/0"]).unwrap();
+        assert!(contains(&set, "8.8.8.8"));
+        assert!(!contains(&set, "2001:db8::1"));
+    }
+
+    #[test]
+    fn builtin_ranges() {
+        assert!(contains(&PRIVATE, "172.20.1.1"));
+        assert!(!contains(&PRIVATE, "172.32.0.1"));
+        assert!(contains(&NON_GLOBAL, "169.254.1.1"));
+        assert!(!contains(&NON_GLOBAL, "8.8.8.8"));
+        assert!(!contains(&NON_GLOBAL, "2606:4700:4700::1111"));
+    }
+
+    #[test]
+    fn global_exceptions() {
+        let is_global = |address| is_global(&parse_ip(address).unwrap());
+
+        assert!(!is_global("192.0.0.8"));
+        assert!(is_global("192.0.0.9"));
+        assert!(!is_global("2001::1"));
+        assert!(is_global("2001:1::1"));
+        assert!(!is_global("2001:1::4"));
+        assert!(is_global("2001:3::1"));
+        assert!(is_global("2001:20::1"));
+        assert!(is_global("2001:3f::1"));
+        assert!(!is_global("2001:40::1"));
+    }
+
+    #[test]
+    fn invalid_range() {
+        assert!(CidrSet::from_strs(["10.0.0.1/8"]).is_err());
+    }
+}
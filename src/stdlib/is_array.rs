@@ -47,6 +47,10 @@ impl Function for IsArray {
 
         Ok(IsArrayFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::array(Collection::any())))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,95 @@
+use crate::compiler::prelude::*;
+
+fn is_ascii(value: Value) -> Resolved {
+    Ok(value.try_bytes()?.is_ascii().into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsAscii;
+
+impl Function for IsAscii {
+    fn identifier(&self) -> &'static str {
+        "is_ascii"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "ascii",
+                source: r#"is_ascii("hello, world")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "non-ascii",
+                source: r#"is_ascii("héllo")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(IsAsciiFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsAsciiFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IsAsciiFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        is_ascii(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        is_ascii => IsAscii;
+
+        ascii {
+            args: func_args![value: "abc 123 ~"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        non_ascii {
+            args: func_args![value: "naïve"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
@@ -47,6 +47,10 @@ impl Function for IsBoolean {
 
         Ok(IsBooleanFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::boolean()))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -47,6 +47,10 @@ impl Function for IsFloat {
 
         Ok(IsFloatFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::float()))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,137 @@
+use crate::compiler::prelude::*;
+
+use super::ip_utils::{is_global, parse_ip};
+
+fn is_global_ip(value: Value) -> Resolved {
+    let address = parse_ip(&value.try_bytes_utf8_lossy()?)?;
+    Ok(is_global(&address).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsGlobalIp;
+
+impl Function for IsGlobalIp {
+    fn identifier(&self) -> &'static str {
+        "is_global_ip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "public address",
+                source: r#"is_global_ip!("8.8.8.8")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "link-local address",
+                source: r#"is_global_ip!("fe80::1")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IsGlobalIpFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsGlobalIpFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IsGlobalIpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        is_global_ip(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        is_global_ip => IsGlobalIp;
+
+        public_ipv4 {
+            args: func_args![value: "1.1.1.1"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        public_ipv6 {
+            args: func_args![value: "2606:4700:4700::1111"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        private {
+            args: func_args![value: "10.0.0.1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        shared_address_space {
+            args: func_args![value: "100.64.0.1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        loopback {
+            args: func_args![value: "::1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        documentation {
+            args: func_args![value: "2001:db8::1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        globally_reachable_exception {
+            args: func_args![value: "2001:1::1"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        multicast {
+            args: func_args![value: "239.255.255.250"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_mapped_private {
+            args: func_args![value: "::ffff:192.168.0.1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
@@ -47,6 +47,10 @@ impl Function for IsInteger {
 
         Ok(IsIntegerFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::integer()))
+    }
 }
 
 #[derive(Clone, Debug)]
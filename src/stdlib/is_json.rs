@@ -1,19 +1,17 @@
 use crate::compiler::prelude::*;
+use crate::stdlib::json_utils::backend;
 use crate::value;
 
 fn is_json(value: Value) -> Resolved {
     let bytes = value.try_bytes()?;
 
-    match serde_json::from_slice::<'_, serde::de::IgnoredAny>(&bytes) {
-        Ok(_) => Ok(value!(true)),
-        Err(_) => Ok(value!(false)),
-    }
+    Ok(value!(backend::is_valid(&bytes)))
 }
 
 fn is_json_with_variant(value: Value, variant: &Bytes) -> Resolved {
     let bytes = value.try_bytes()?;
 
-    if serde_json::from_slice::<'_, serde::de::IgnoredAny>(&bytes).is_ok() {
+    if backend::is_valid(&bytes) {
         for c in bytes {
             return match c {
                 // Search for the first non whitespace char
@@ -47,6 +47,11 @@ impl Function for IsNull {
 
         Ok(IsNullFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        // Querying a missing path returns `null`.
+        Some(TypeGuard::new(Kind::null().or_undefined()))
+    }
 }
 
 #[derive(Clone, Debug)]
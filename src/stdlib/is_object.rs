@@ -47,6 +47,10 @@ impl Function for IsObject {
 
         Ok(IsObjectFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::object(Collection::any())))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,118 @@
+use crate::compiler::prelude::*;
+
+use super::ip_utils::{parse_ip, PRIVATE};
+
+fn is_private_ip(value: Value) -> Resolved {
+    let address = parse_ip(&value.try_bytes_utf8_lossy()?)?;
+    Ok(PRIVATE.contains(&address).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsPrivateIp;
+
+impl Function for IsPrivateIp {
+    fn identifier(&self) -> &'static str {
+        "is_private_ip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "RFC 1918 address",
+                source: r#"is_private_ip!("192.168.1.10")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "IPv6 unique local address",
+                source: r#"is_private_ip!("fd12:3456:789a::1")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "public address",
+                source: r#"is_private_ip!("8.8.8.8")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IsPrivateIpFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsPrivateIpFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IsPrivateIpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        is_private_ip(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        is_private_ip => IsPrivateIp;
+
+        rfc1918 {
+            args: func_args![value: "172.31.255.255"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        outside_rfc1918 {
+            args: func_args![value: "172.32.0.1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        unique_local {
+            args: func_args![value: "fd00::1"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_mapped {
+            args: func_args![value: "::ffff:10.1.2.3"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        loopback {
+            args: func_args![value: "127.0.0.1"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "10.0.0"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
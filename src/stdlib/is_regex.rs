@@ -47,6 +47,10 @@ impl Function for IsRegex {
 
         Ok(IsRegexFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::regex()))
+    }
 }
 
 #[derive(Clone, Debug)]
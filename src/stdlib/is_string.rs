@@ -47,6 +47,10 @@ impl Function for IsString {
 
         Ok(IsStringFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::bytes()))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -47,6 +47,10 @@ impl Function for IsTimestamp {
 
         Ok(IsTimestampFn { value }.as_expr())
     }
+
+    fn type_guard(&self) -> Option<TypeGuard> {
+        Some(TypeGuard::new(Kind::timestamp()))
+    }
 }
 
 #[derive(Clone, Debug)]
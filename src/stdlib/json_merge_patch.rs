@@ -0,0 +1,142 @@
+use crate::compiler::prelude::*;
+
+/// Applies a JSON Merge Patch (RFC 7386): an object patch merges into the value recursively,
+/// where a `null` removes the field, and any other patch replaces the value.
+fn json_merge_patch(value: Value, patch: Value) -> Value {
+    let Value::Object(patch) = patch else {
+        return patch;
+    };
+    let mut object = match value {
+        Value::Object(object) => object,
+        _ => ObjectMap::new(),
+    };
+
+    for (key, patch) in patch {
+        if patch.is_null() {
+            object.remove(&key);
+        } else {
+            let value = object.remove(&key).unwrap_or(Value::Null);
+            object.insert(key, json_merge_patch(value, patch));
+        }
+    }
+
+    object.into()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct JsonMergePatch;
+
+impl Function for JsonMergePatch {
+    fn identifier(&self) -> &'static str {
+        "json_merge_patch"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "patch",
+                kind: kind::ANY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "merge and remove fields",
+                source: r#"json_merge_patch({"a": {"b": 1, "c": 2}, "d": 3}, {"a": {"c": null, "e": 4}, "d": null})"#,
+                result: Ok(r#"{"a": {"b": 1, "e": 4}}"#),
+            },
+            Example {
+                title: "replace arrays",
+                source: r#"json_merge_patch({"a": [1, 2]}, {"a": [3]})"#,
+                result: Ok(r#"{"a": [3]}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let patch = arguments.required("patch");
+
+        Ok(JsonMergePatchFn { value, patch }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JsonMergePatchFn {
+    value: Box<dyn Expression>,
+    patch: Box<dyn Expression>,
+}
+
+impl FunctionExpression for JsonMergePatchFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let patch = self.patch.resolve(ctx)?;
+
+        Ok(json_merge_patch(value, patch))
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let patch = self.patch.type_def(state);
+        if patch.is_object() {
+            TypeDef::object(Collection::any())
+        } else {
+            TypeDef::any()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        json_merge_patch => JsonMergePatch;
+
+        merges_objects {
+            args: func_args![
+                value: value!({"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"}, "tags": ["example", "sample"]}),
+                patch: value!({"title": "Hello!", "author": {"familyName": null}, "phoneNumber": "+01-123-456-7890", "tags": ["example"]}),
+            ],
+            want: Ok(value!({"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"], "phoneNumber": "+01-123-456-7890"})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        replaces_non_objects {
+            args: func_args![value: value!({"a": {"b": "c"}}), patch: value!({"a": {"b": "d", "c": null}})],
+            want: Ok(value!({"a": {"b": "d"}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        nested_nulls_of_new_fields {
+            args: func_args![value: value!({"a": "b"}), patch: value!({"a": {"bb": {"ccc": null}}})],
+            want: Ok(value!({"a": {"bb": {}}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        replaces_with_array {
+            args: func_args![value: value!({"a": "b"}), patch: value!(["c"])],
+            want: Ok(value!(["c"])),
+            tdef: TypeDef::any(),
+        }
+
+        replaces_with_null {
+            args: func_args![value: value!({"a": "b"}), patch: value!(null)],
+            want: Ok(value!(null)),
+            tdef: TypeDef::any(),
+        }
+    ];
+}
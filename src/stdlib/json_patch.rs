@@ -0,0 +1,262 @@
+use crate::compiler::prelude::*;
+use crate::path::OwnedValuePath;
+
+use super::apply_patch::{apply_operations, check_operations};
+
+/// Splits a JSON Pointer (RFC 6901) into its unescaped reference tokens. The empty pointer refers
+/// to the whole value.
+pub(super) fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(tokens) = pointer.strip_prefix('/') else {
+        return Err(format!(r#"JSON pointer "{pointer}" must start with "/""#));
+    };
+
+    tokens
+        .split('/')
+        .map(|token| {
+            let mut unescaped = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    unescaped.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => unescaped.push('~'),
+                    Some('1') => unescaped.push('/'),
+                    _ => {
+                        return Err(format!(
+                            r#"JSON pointer "{pointer}" has an invalid escape sequence"#
+                        ))
+                    }
+                }
+            }
+            Ok(unescaped)
+        })
+        .collect()
+}
+
+/// Resolves a JSON Pointer into a path on the value. A token refers to an array element if the
+/// value at that point is an array, where `-` refers to the position past the last element.
+// The length of an array never exceeds `isize::MAX`.
+#[allow(clippy::cast_possible_wrap)]
+pub(super) fn resolve_pointer(value: &Value, pointer: &str) -> Result<OwnedValuePath, String> {
+    let mut path = OwnedValuePath::root();
+    let mut current = Some(value);
+
+    for token in parse_pointer(pointer)? {
+        match current {
+            Some(Value::Array(array)) => {
+                let index = if token == "-" {
+                    array.len()
+                } else if token == "0" || (!token.starts_with('0') && !token.is_empty()) {
+                    token
+                        .parse::<usize>()
+                        .map_err(|_| format!(r#"invalid array index "{token}" in "{pointer}""#))?
+                } else {
+                    return Err(format!(r#"invalid array index "{token}" in "{pointer}""#));
+                };
+                current = array.get(index);
+                path.push_index(index as isize);
+            }
+            Some(Value::Object(object)) => {
+                current = object.get(token.as_str());
+                path.push_field(&token);
+            }
+            _ => {
+                current = None;
+                path.push_field(&token);
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+fn json_patch(value: Value, ops: Value) -> Resolved {
+    apply_operations(value, ops, resolve_pointer)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct JsonPatch;
+
+impl Function for JsonPatch {
+    fn identifier(&self) -> &'static str {
+        "json_patch"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "ops",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "add and remove",
+                source: r#"json_patch!({"a": [1, 3], "b": 2}, [{"op": "add", "path": "/a/1", "value": 2}, {"op": "remove", "path": "/b"}])"#,
+                result: Ok(r#"{"a": [1, 2, 3]}"#),
+            },
+            Example {
+                title: "append to an array",
+                source: r#"json_patch!([1], [{"op": "add", "path": "/-", "value": 2}])"#,
+                result: Ok("[1, 2]"),
+            },
+            Example {
+                title: "escaped pointer",
+                source: r#"json_patch!({"a/b": 1}, [{"op": "move", "from": "/a~1b", "path": "/c"}])"#,
+                result: Ok(r#"{"c": 1}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let ops = arguments.required("ops");
+
+        check_operations("ops", &*ops, state, |pointer| {
+            parse_pointer(pointer).is_ok()
+        })?;
+
+        Ok(JsonPatchFn { value, ops }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JsonPatchFn {
+    value: Box<dyn Expression>,
+    ops: Box<dyn Expression>,
+}
+
+impl FunctionExpression for JsonPatchFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let ops = self.ops.resolve(ctx)?;
+
+        json_patch(value, ops)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        json_patch => JsonPatch;
+
+        rfc_operations {
+            args: func_args![
+                value: value!({"foo": ["bar", "baz"], "qux": {"baz": 1}}),
+                ops: value!([
+                    {"op": "add", "path": "/foo/1", "value": "qux"},
+                    {"op": "replace", "path": "/qux/baz", "value": 2},
+                    {"op": "copy", "from": "/qux", "path": "/quux"},
+                    {"op": "remove", "path": "/foo/0"},
+                ]),
+            ],
+            want: Ok(value!({"foo": ["qux", "baz"], "qux": {"baz": 2}, "quux": {"baz": 2}})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        append_and_move {
+            args: func_args![
+                value: value!({"a": [1], "b": {"c~d": 2}}),
+                ops: value!([
+                    {"op": "add", "path": "/a/-", "value": 3},
+                    {"op": "move", "from": "/b/c~0d", "path": "/a/1"},
+                    {"op": "test", "path": "/a", "value": [1, 2, 3]},
+                ]),
+            ],
+            want: Ok(value!({"a": [1, 2, 3], "b": {}})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        numeric_object_keys {
+            args: func_args![
+                value: value!({"0": "a"}),
+                ops: value!([{"op": "replace", "path": "/0", "value": "b"}]),
+            ],
+            want: Ok(value!({"0": "b"})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        replace_root {
+            args: func_args![
+                value: value!({"a": 1}),
+                ops: value!([{"op": "replace", "path": "", "value": [1]}]),
+            ],
+            want: Ok(value!([1])),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        negative_index {
+            args: func_args![
+                value: value!([1]),
+                ops: value!([{"op": "remove", "path": "/-1"}]),
+            ],
+            want: Err(r#"patch operation 0: invalid array index "-1" in "/-1""#),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        failed_test {
+            args: func_args![
+                value: value!({"a": 1}),
+                ops: value!([{"op": "test", "path": "/a", "value": 2}]),
+            ],
+            want: Err("patch operation 0: value at /a doesn't match the tested value"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        invalid_pointer {
+            args: func_args![
+                value: value!({}),
+                ops: value!([{"op": "remove", "path": "a"}]),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+
+    #[test]
+    fn parses_pointers() {
+        assert_eq!(parse_pointer(""), Ok(vec![]));
+        assert_eq!(parse_pointer("/"), Ok(vec![String::new()]));
+        assert_eq!(
+            parse_pointer("/a~1b/~0/c"),
+            Ok(vec!["a/b".to_owned(), "~".to_owned(), "c".to_owned()])
+        );
+        assert!(parse_pointer("a").is_err());
+        assert!(parse_pointer("/a~2").is_err());
+        assert!(parse_pointer("/a~").is_err());
+    }
+
+    #[test]
+    fn resolves_array_indices() {
+        let value = value!({"a": [1, 2]});
+        assert_eq!(resolve_pointer(&value, "/a/-").unwrap().to_string(), "a[2]");
+        assert!(resolve_pointer(&value, "/a/01").is_err());
+        assert!(resolve_pointer(&value, "/a/b").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! The JSON parser of `parse_json` and `is_json`.
+//!
+//! With the `simd_json` feature, documents are parsed with simd-json. The
+//! documents that simd-json rejects, or that it might not parse as serde_json
+//! does, are parsed again with serde_json, so that both backends return the
+//! same values and errors. Invalid documents are therefore parsed twice, and
+//! rejecting them costs more than with serde_json alone.
+
+use crate::value::Value;
+
+/// Parses the document into a value.
+pub(crate) fn from_slice(bytes: &[u8]) -> Result<Value, serde_json::Error> {
+    #[cfg(feature = "simd_json")]
+    if let Some(value) = simd::from_slice(bytes) {
+        return Ok(value);
+    }
+
+    serde_json::from_slice(bytes)
+}
+
+/// Returns whether the document is valid JSON.
+pub(crate) fn is_valid(bytes: &[u8]) -> bool {
+    #[cfg(feature = "simd_json")]
+    if simd::is_valid(bytes) {
+        return true;
+    }
+
+    serde_json::from_slice::<serde::de::IgnoredAny>(bytes).is_ok()
+}
+
+#[cfg(feature = "simd_json")]
+mod simd {
+    use std::fmt;
+
+    use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+    use crate::value::Value;
+
+    /// serde_json fails for documents nesting this many arrays and objects.
+    const RECURSION_LIMIT: usize = 128;
+
+    /// Parses the document, or returns `None` if serde_json must parse it.
+    ///
+    /// simd-json parses a mutable buffer, so the document is copied first.
+    pub(super) fn from_slice(bytes: &[u8]) -> Option<Value> {
+        if needs_serde_json(bytes) {
+            return None;
+        }
+
+        // simd-json parses in place, and leaves the buffer unusable on errors.
+        let mut buffer = bytes.to_vec();
+        let value = simd_json::serde::from_slice::<Value>(&mut buffer).ok()?;
+
+        (check(&value, 1) == Some(())).then_some(value)
+    }
+
+    /// Returns whether the document is valid, or `false` if serde_json must
+    /// validate it. As with [`from_slice`], the document is copied first.
+    pub(super) fn is_valid(bytes: &[u8]) -> bool {
+        if needs_serde_json(bytes) {
+            return false;
+        }
+
+        let mut buffer = bytes.to_vec();
+        simd_json::serde::from_slice::<Validated>(&mut buffer).is_ok()
+    }
+
+    /// Returns whether the document may hold `-0`, which serde_json parses
+    /// as a float while simd-json parses it as an integer, or escaped UTF-16
+    /// surrogates, which the parsers validate differently.
+    fn needs_serde_json(bytes: &[u8]) -> bool {
+        bytes
+            .iter()
+            .enumerate()
+            .any(|(i, byte)| match (byte, &bytes[i + 1..]) {
+                (b'-', [b'0', rest @ ..]) => !matches!(rest.first(), Some(b'.' | b'e' | b'E')),
+                (b'\\', [b'u', b'd' | b'D', b'8'..=b'9' | b'a'..=b'f' | b'A'..=b'F', ..]) => true,
+                _ => false,
+            })
+    }
+
+    /// Checks that serde_json wouldn't reject the parsed value, for nesting
+    /// too deep or holding an out of range float.
+    fn check(value: &Value, depth: usize) -> Option<()> {
+        match value {
+            Value::Float(float) if !float.is_finite() => None,
+            Value::Array(_) | Value::Object(_) if depth >= RECURSION_LIMIT => None,
+            Value::Array(array) => array.iter().try_for_each(|value| check(value, depth + 1)),
+            Value::Object(object) => object
+                .values()
+                .try_for_each(|value| check(value, depth + 1)),
+            _ => Some(()),
+        }
+    }
+
+    /// A document skipped as with [`IgnoredAny`], which fails where
+    /// [`check`] does.
+    struct Validated;
+
+    impl<'de> Deserialize<'de> for Validated {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(Validator { depth: 1 })
+        }
+    }
+
+    struct Validator {
+        depth: usize,
+    }
+
+    impl Validator {
+        /// Returns the validator of the values of an array or object, failing
+        /// as serde_json for nesting too deep.
+        fn nested<E: serde::de::Error>(&self) -> Result<Self, E> {
+            if self.depth >= RECURSION_LIMIT {
+                return Err(E::custom("recursion limit exceeded"));
+            }
+
+            Ok(Self {
+                depth: self.depth + 1,
+            })
+        }
+    }
+
+    impl<'de> serde::de::DeserializeSeed<'de> for Validator {
+        type Value = Validated;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Validated, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Validator {
+        type Value = Validated;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("any valid JSON value")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Validated, A::Error> {
+            let depth = self.nested::<A::Error>()?.depth;
+            while map.next_key::<IgnoredAny>()?.is_some() {
+                map.next_value_seed(Validator { depth })?;
+            }
+            Ok(Validated)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Validated, A::Error> {
+            let depth = self.nested::<A::Error>()?.depth;
+            while seq.next_element_seed(Validator { depth })?.is_some() {}
+            Ok(Validated)
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, float: f64) -> Result<Validated, E> {
+            if float.is_finite() {
+                Ok(Validated)
+            } else {
+                Err(E::custom("number out of range"))
+            }
+        }
+
+        fn visit_bool<E>(self, _: bool) -> Result<Validated, E> {
+            Ok(Validated)
+        }
+
+        fn visit_i64<E>(self, _: i64) -> Result<Validated, E> {
+            Ok(Validated)
+        }
+
+        fn visit_u64<E>(self, _: u64) -> Result<Validated, E> {
+            Ok(Validated)
+        }
+
+        fn visit_str<E>(self, _: &str) -> Result<Validated, E> {
+            Ok(Validated)
+        }
+
+        fn visit_unit<E>(self) -> Result<Validated, E> {
+            Ok(Validated)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Documents covering the differences between JSON parsers, in addition
+    /// to the value fixtures.
+    const DOCUMENTS: &[&str] = &[
+        r#"{"key": "value", "nested": {"array": [1, -2, 3.5, true, null]}}"#,
+        r#"{"key": 1, "key": 2}"#,
+        r#""escaped \"quotes\", é and 😀""#,
+        r#""\ud800""#,
+        "-0",
+        "-0.0",
+        "[-0, 0, -0.5, -0e1]",
+        "9223372036854775807",
+        "9223372036854775808",
+        "18446744073709551616",
+        "-9223372036854775809",
+        "1.7976931348623157e308",
+        "1e400",
+        "-1e400",
+        "2.2250738585072014e-308",
+        "5e-324",
+        "0.1000000000000000055511151231257827",
+        "123456789012345678901234567890.123",
+        " \n\t[ ] ",
+        "{ INVALID }",
+        "[1, 2",
+        "[1,]",
+        "01",
+        "NaN",
+        "",
+        "\"unterminated",
+        "{} trailing",
+    ];
+
+    fn documents() -> Vec<Vec<u8>> {
+        let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+        let mut documents = DOCUMENTS
+            .iter()
+            .map(|document| document.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        documents.extend([126, 127, 128, 129].map(|depth| nested(depth).into_bytes()));
+        documents.push(b"\"invalid \xff utf-8\"".to_vec());
+
+        for type_dir in std::fs::read_dir("tests/data/fixtures/value").unwrap() {
+            for fixture in std::fs::read_dir(type_dir.unwrap().path()).unwrap() {
+                documents.push(std::fs::read(fixture.unwrap().path()).unwrap());
+            }
+        }
+
+        documents
+    }
+
+    #[test]
+    fn parses_as_serde_json() {
+        for document in documents() {
+            let expected = serde_json::from_slice::<Value>(&document).map_err(|e| e.to_string());
+            let parsed = from_slice(&document).map_err(|e| e.to_string());
+
+            assert_eq!(parsed, expected, "{}", String::from_utf8_lossy(&document));
+            if let (Ok(Value::Float(parsed)), Ok(Value::Float(expected))) = (parsed, expected) {
+                assert_eq!(
+                    parsed.is_sign_negative(),
+                    expected.is_sign_negative(),
+                    "{}",
+                    String::from_utf8_lossy(&document)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validates_as_serde_json() {
+        for document in documents() {
+            assert_eq!(
+                is_valid(&document),
+                serde_json::from_slice::<serde::de::IgnoredAny>(&document).is_ok(),
+                "{}",
+                String::from_utf8_lossy(&document)
+            );
+        }
+    }
+}
@@ -1 +1,2 @@
+pub(crate) mod backend;
 pub(crate) mod json_type_def;
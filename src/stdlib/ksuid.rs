@@ -0,0 +1,192 @@
+use crate::compiler::prelude::*;
+use chrono::Utc;
+use rand::{thread_rng, RngCore};
+
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const LENGTH: usize = 27;
+
+/// The timestamps of KSUIDs are in seconds since 2014-05-13T16:53:20Z.
+pub(crate) const EPOCH: i64 = 1_400_000_000;
+
+/// Encodes a KSUID from its timestamp, relative to [`EPOCH`], and its payload.
+pub(crate) fn encode(timestamp: u32, payload: [u8; 16]) -> String {
+    let mut bytes = [0; 20];
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    bytes[4..].copy_from_slice(&payload);
+
+    // The 160 bit number doesn't fit in an integer, so the digits are computed
+    // from the least significant one by long division of the bytes.
+    let mut encoded = [0; LENGTH];
+    for char in encoded.iter_mut().rev() {
+        let mut remainder = 0_u32;
+        for byte in &mut bytes {
+            let value = (remainder << 8) | u32::from(*byte);
+            *byte = (value / 62) as u8;
+            remainder = value % 62;
+        }
+        *char = ALPHABET[remainder as usize];
+    }
+
+    encoded.iter().copied().map(char::from).collect()
+}
+
+/// Decodes a KSUID into its timestamp, relative to [`EPOCH`], and its payload.
+pub(crate) fn decode(ksuid: &str) -> Result<(u32, [u8; 16]), String> {
+    if ksuid.len() != LENGTH {
+        return Err(format!(
+            "expected {LENGTH} characters, got {}",
+            ksuid.chars().count()
+        ));
+    }
+
+    let mut bytes = [0_u8; 20];
+    for (index, char) in ksuid.chars().enumerate() {
+        let digit = ALPHABET
+            .iter()
+            .position(|candidate| char::from(*candidate) == char)
+            .ok_or_else(|| format!("invalid character {char:?} at position {index}"))?;
+
+        let mut carry = digit as u32;
+        for byte in bytes.iter_mut().rev() {
+            let value = u32::from(*byte) * 62 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err("value is too large".to_owned());
+        }
+    }
+
+    let mut timestamp = [0; 4];
+    timestamp.copy_from_slice(&bytes[..4]);
+    let mut payload = [0; 16];
+    payload.copy_from_slice(&bytes[4..]);
+
+    Ok((u32::from_be_bytes(timestamp), payload))
+}
+
+fn ksuid(timestamp: Option<Value>) -> Resolved {
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp.try_timestamp()?,
+        None => Utc::now(),
+    };
+    let timestamp = u32::try_from(timestamp.timestamp() - EPOCH)
+        .map_err(|_| "timestamp is out of range for a KSUID")?;
+
+    let mut payload = [0; 16];
+    thread_rng().fill_bytes(&mut payload);
+
+    Ok(encode(timestamp, payload).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ksuid;
+
+impl Function for Ksuid {
+    fn identifier(&self) -> &'static str {
+        "ksuid"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "timestamp",
+            kind: kind::TIMESTAMP,
+            required: false,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "current time",
+                source: "length(ksuid())",
+                result: Ok("27"),
+            },
+            Example {
+                title: "explicit timestamp",
+                source: r#"parse_ksuid!(ksuid!(t'2024-03-08T10:00:00Z')).timestamp"#,
+                result: Ok("t'2024-03-08T10:00:00Z'"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let timestamp = arguments.optional("timestamp");
+
+        Ok(KsuidFn { timestamp }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KsuidFn {
+    timestamp: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for KsuidFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let timestamp = self
+            .timestamp
+            .as_ref()
+            .map(|timestamp| timestamp.resolve(ctx))
+            .transpose()?;
+
+        ksuid(timestamp)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().maybe_fallible(self.timestamp.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone as _};
+
+    #[test]
+    fn encode_decode() {
+        let payload = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let ksuid = encode(309_892_000, payload);
+
+        assert_eq!(ksuid, "2dOvaCAtXMnUFXBpGvr8ogdVRrM");
+        assert_eq!(decode(&ksuid), Ok((309_892_000, payload)));
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(encode(0, [0; 16]), "000000000000000000000000000");
+        assert_eq!(encode(u32::MAX, [0xff; 16]), "aWgEPTl1tmebfsQzFP4bxwgy80V");
+        assert_eq!(
+            decode("aWgEPTl1tmebfsQzFP4bxwgy80W"),
+            Err("value is too large".to_owned())
+        );
+    }
+
+    #[test]
+    fn generated() {
+        let timestamp = Utc.timestamp_opt(1_709_892_000, 0).unwrap();
+        let first = ksuid(Some(timestamp.into())).unwrap();
+        let second = ksuid(Some(timestamp.into())).unwrap();
+
+        assert_ne!(first, second);
+        let (seconds, _) = decode(&first.try_bytes_utf8_lossy().unwrap()).unwrap();
+        assert_eq!(i64::from(seconds) + EPOCH, 1_709_892_000);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let timestamp = DateTime::parse_from_rfc3339("2014-01-01T00:00:00Z").unwrap();
+
+        assert_eq!(
+            ksuid(Some(timestamp.with_timezone(&Utc).into()))
+                .unwrap_err()
+                .to_string(),
+            "timestamp is out of range for a KSUID"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::compiler::prelude::*;
+
+use super::string_utils::comparable_strings;
+
+pub(super) const DEFAULT_MAX_LENGTH: i64 = 1000;
+
+#[allow(clippy::cast_possible_wrap)]
+fn levenshtein_distance(
+    value1: Value,
+    value2: Value,
+    case_sensitive: Value,
+    max_length: Value,
+) -> Resolved {
+    let case_sensitive = case_sensitive.try_boolean()?;
+    let (value1, value2) = comparable_strings(value1, value2, case_sensitive, max_length)?;
+
+    // The distance is at most `max_length`, which is an integer.
+    Ok((strsim::levenshtein(&value1, &value2) as i64).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LevenshteinDistance;
+
+impl Function for LevenshteinDistance {
+    fn identifier(&self) -> &'static str {
+        "levenshtein_distance"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value1",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value2",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "case_sensitive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "max_length",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "edit distance",
+                source: r#"levenshtein_distance!("kitten", "sitting")"#,
+                result: Ok("3"),
+            },
+            Example {
+                title: "case insensitive",
+                source: r#"levenshtein_distance!("Hello", "hello", case_sensitive: false)"#,
+                result: Ok("0"),
+            },
+            Example {
+                title: "strings too long",
+                source: r#"levenshtein_distance!("abc", "abcd", max_length: 3)"#,
+                result: Err(
+                    r#"function call error for "levenshtein_distance" at (0:51): strings longer than 3 characters can't be compared"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value1 = arguments.required("value1");
+        let value2 = arguments.required("value2");
+        let case_sensitive = arguments.optional("case_sensitive").unwrap_or(expr!(true));
+        let max_length = arguments
+            .optional("max_length")
+            .unwrap_or(expr!(DEFAULT_MAX_LENGTH));
+
+        Ok(LevenshteinDistanceFn {
+            value1,
+            value2,
+            case_sensitive,
+            max_length,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LevenshteinDistanceFn {
+    value1: Box<dyn Expression>,
+    value2: Box<dyn Expression>,
+    case_sensitive: Box<dyn Expression>,
+    max_length: Box<dyn Expression>,
+}
+
+impl FunctionExpression for LevenshteinDistanceFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value1 = self.value1.resolve(ctx)?;
+        let value2 = self.value2.resolve(ctx)?;
+        let case_sensitive = self.case_sensitive.resolve(ctx)?;
+        let max_length = self.max_length.resolve(ctx)?;
+
+        levenshtein_distance(value1, value2, case_sensitive, max_length)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        levenshtein_distance => LevenshteinDistance;
+
+        distance {
+            args: func_args![value1: "kitten", value2: "sitting"],
+            want: Ok(3),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        empty {
+            args: func_args![value1: "", value2: "abc"],
+            want: Ok(3),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        unicode {
+            args: func_args![value1: "café", value2: "cafe"],
+            want: Ok(1),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        case_insensitive {
+            args: func_args![value1: "ABC", value2: "abd", case_sensitive: false],
+            want: Ok(1),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        too_long {
+            args: func_args![value1: "abcd", value2: "a", max_length: 3],
+            want: Err("strings longer than 3 characters can't be compared"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,96 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, float_result};
+
+fn log10(value: Value) -> Resolved {
+    expect_number(&value)?;
+    let value = value.try_into_f64()?;
+    if value <= 0.0 {
+        return Err(format!("can't calculate logarithm of non-positive number {value}").into());
+    }
+
+    float_result("log10", value.log10())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Log10;
+
+impl Function for Log10 {
+    fn identifier(&self) -> &'static str {
+        "log10"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "base 10 logarithm",
+            source: "log10!(1000)",
+            result: Ok("3.0"),
+        }]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(Log10Fn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Log10Fn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for Log10Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        log10(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        log10 => Log10;
+
+        integer {
+            args: func_args![value: 100],
+            want: Ok(2.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        fraction {
+            args: func_args![value: 0.001],
+            want: Ok(-3.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -10],
+            want: Err("can't calculate logarithm of non-positive number -10"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,117 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, float_result};
+
+fn log2(value: Value) -> Resolved {
+    expect_number(&value)?;
+    let value = value.try_into_f64()?;
+    if value <= 0.0 {
+        return Err(format!("can't calculate logarithm of non-positive number {value}").into());
+    }
+
+    float_result("log2", value.log2())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Log2;
+
+impl Function for Log2 {
+    fn identifier(&self) -> &'static str {
+        "log2"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "base 2 logarithm",
+                source: "log2!(1024)",
+                result: Ok("10.0"),
+            },
+            Example {
+                title: "zero",
+                source: "log2!(0)",
+                result: Err(
+                    r#"function call error for "log2" at (0:8): can't calculate logarithm of non-positive number 0"#,
+                ),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(Log2Fn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Log2Fn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for Log2Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        log2(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        log2 => Log2;
+
+        integer {
+            args: func_args![value: 8],
+            want: Ok(3.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        fraction {
+            args: func_args![value: 0.25],
+            want: Ok(-2.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        one {
+            args: func_args![value: 1],
+            want: Ok(0.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        zero {
+            args: func_args![value: 0],
+            want: Err("can't calculate logarithm of non-positive number 0"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -2.5],
+            want: Err("can't calculate logarithm of non-positive number -2.5"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
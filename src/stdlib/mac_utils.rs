@@ -0,0 +1,157 @@
+use std::fmt::Write as _;
+
+/// A 48-bit MAC address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct MacAddress(pub(super) [u8; 6]);
+
+/// The notations a MAC address can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MacFormat {
+    /// `00:1a:2b:3c:4d:5e`
+    Colon,
+    /// `00-1a-2b-3c-4d-5e`
+    Dash,
+    /// `001a.2b3c.4d5e`
+    Dot,
+    /// `001a2b3c4d5e`
+    Bare,
+}
+
+fn parse_groups(groups: &[&str], width: usize, bytes: &mut [u8; 6]) -> Option<()> {
+    if groups.len() * width != 12 {
+        return None;
+    }
+    let hex = groups
+        .iter()
+        .map(|group| (group.len() == width).then_some(*group))
+        .collect::<Option<String>>()?;
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let pair = hex.get(i * 2..i * 2 + 2)?;
+        if !pair.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(())
+}
+
+impl MacAddress {
+    /// Parses a MAC address in any of the [`MacFormat`] notations, in either case.
+    pub(super) fn parse(value: &str) -> Result<Self, String> {
+        let mut bytes = [0; 6];
+        let groups = value.split([':', '-', '.']).collect::<Vec<_>>();
+        let separators = value.matches([':', '-', '.']).collect::<Vec<_>>();
+        let consistent = separators.windows(2).all(|pair| pair[0] == pair[1]);
+
+        let parsed = match separators.first() {
+            Some(&".") if consistent => parse_groups(&groups, 4, &mut bytes),
+            Some(_) if consistent => parse_groups(&groups, 2, &mut bytes),
+            None => parse_groups(&groups, 12, &mut bytes),
+            Some(_) => None,
+        };
+
+        parsed
+            .map(|()| Self(bytes))
+            .ok_or_else(|| format!(r#"unable to parse MAC address "{value}""#))
+    }
+
+    /// The organizationally unique identifier, the first three bytes.
+    pub(super) fn oui(self) -> [u8; 3] {
+        [self.0[0], self.0[1], self.0[2]]
+    }
+
+    /// Whether the address is locally administered rather than universally
+    /// administered.
+    pub(super) fn is_local(self) -> bool {
+        self.0[0] & 0b10 != 0
+    }
+
+    /// Whether the address is a multicast (group) address rather than unicast.
+    pub(super) fn is_multicast(self) -> bool {
+        self.0[0] & 0b1 != 0
+    }
+
+    pub(super) fn format(self, format: MacFormat, uppercase: bool) -> String {
+        format_bytes(&self.0, format, uppercase)
+    }
+
+    /// Formats the OUI in lowercase colon notation, such as `00:1a:2b`.
+    pub(super) fn format_oui(self) -> String {
+        format_bytes(&self.oui(), MacFormat::Colon, false)
+    }
+}
+
+fn format_bytes(bytes: &[u8], format: MacFormat, uppercase: bool) -> String {
+    let mut output = String::with_capacity(bytes.len() * 3);
+    for (i, byte) in bytes.iter().enumerate() {
+        let separator = match format {
+            MacFormat::Colon => Some(':'),
+            MacFormat::Dash => Some('-'),
+            MacFormat::Dot if i % 2 == 0 => Some('.'),
+            MacFormat::Dot | MacFormat::Bare => None,
+        };
+        if let Some(separator) = separator.filter(|_| i > 0) {
+            output.push(separator);
+        }
+        if uppercase {
+            write!(output, "{byte:02X}").expect("writing to a string");
+        } else {
+            write!(output, "{byte:02x}").expect("writing to a string");
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: MacAddress = MacAddress([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+
+    #[test]
+    fn parse() {
+        for input in [
+            "00:1a:2b:3c:4d:5e",
+            "00-1A-2B-3C-4D-5E",
+            "001a.2b3c.4d5e",
+            "001A2B3C4D5E",
+        ] {
+            assert_eq!(MacAddress::parse(input), Ok(ADDRESS), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in [
+            "",
+            "00:1a:2b:3c:4d",
+            "00:1a:2b:3c:4d:5e:6f",
+            "00:1a-2b:3c:4d:5e",
+            "0:1a:2b:3c:4d:5e0",
+            "001a2b3c4d5g",
+            "+0:1a:2b:3c:4d:5e",
+            "001a:2b3c:4d5e",
+        ] {
+            assert!(MacAddress::parse(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(ADDRESS.format(MacFormat::Colon, false), "00:1a:2b:3c:4d:5e");
+        assert_eq!(ADDRESS.format(MacFormat::Dash, true), "00-1A-2B-3C-4D-5E");
+        assert_eq!(ADDRESS.format(MacFormat::Dot, false), "001a.2b3c.4d5e");
+        assert_eq!(ADDRESS.format(MacFormat::Bare, false), "001a2b3c4d5e");
+        assert_eq!(ADDRESS.format_oui(), "00:1a:2b");
+    }
+
+    #[test]
+    fn properties() {
+        assert!(!ADDRESS.is_local());
+        assert!(!ADDRESS.is_multicast());
+
+        let address = MacAddress::parse("03:00:00:00:00:01").unwrap();
+        assert!(address.is_local());
+        assert!(address.is_multicast());
+    }
+}
@@ -99,6 +99,7 @@ impl Function for MapKeys {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
@@ -0,0 +1,220 @@
+use crate::compiler::prelude::*;
+
+fn map_paths<T>(
+    value: Value,
+    path: &mut Vec<Value>,
+    ctx: &mut Context,
+    runner: &closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    match value {
+        Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| {
+                path.push(key.as_str().into());
+                let value = map_paths(value, path, ctx, runner);
+                path.pop();
+                Ok((key, value?))
+            })
+            .collect::<Result<ObjectMap, ExpressionError>>()
+            .map(Into::into),
+        Value::Array(array) => array
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                path.push(index.into());
+                let value = map_paths(value, path, ctx, runner);
+                path.pop();
+                value
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Into::into),
+        value => runner.run_values(ctx, &[&Value::Array(path.clone()), &value]),
+    }
+}
+
+/// The kind of the value once its leaves are replaced by the output of the closure.
+fn map_leaves(kind: &Kind, output: &Kind) -> Kind {
+    let mut mapped = if kind.without_undefined().contains_primitive() {
+        output.clone()
+    } else {
+        Kind::never()
+    };
+    if kind.contains_undefined() {
+        mapped.add_undefined();
+    }
+    if let Some(object) = kind.as_object() {
+        mapped = mapped.union(Kind::object(map_collection(object, output)));
+    }
+    if let Some(array) = kind.as_array() {
+        mapped = mapped.union(Kind::array(map_collection(array, output)));
+    }
+    mapped
+}
+
+fn map_collection<T: Ord + Clone>(collection: &Collection<T>, output: &Kind) -> Collection<T> {
+    let known = collection
+        .known()
+        .iter()
+        .map(|(key, kind)| (key.clone(), map_leaves(kind, output)))
+        .collect();
+
+    let unknown = collection.unknown_kind().without_undefined();
+    let unknown = if collection.is_unknown_exact() {
+        map_leaves(&unknown, output)
+    } else {
+        // The unknown kind repeats itself at every level of nesting, so the
+        // nested collections keep their unknown leaves.
+        let mut mapped = if unknown.contains_primitive() {
+            output.clone()
+        } else {
+            Kind::never()
+        };
+        if unknown.as_object().is_some() {
+            mapped = mapped.union(Kind::object(Collection::any()));
+        }
+        if unknown.as_array().is_some() {
+            mapped = mapped.union(Kind::array(Collection::any()));
+        }
+        mapped
+    };
+
+    if unknown.is_never() {
+        // The collection has no unknown elements.
+        Collection::from_parts(known, Kind::undefined())
+    } else {
+        Collection::from_parts(known, unknown)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapPaths;
+
+impl Function for MapPaths {
+    fn identifier(&self) -> &'static str {
+        "map_paths"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "redact fields by name",
+                source: r#"map_paths({ "user": { "name": "a", "password": "b" } }) -> |path, value| { if path[-1] == "password" { "***" } else { value } }"#,
+                result: Ok(r#"{ "user": { "name": "a", "password": "***" } }"#),
+            },
+            Example {
+                title: "paths of array elements",
+                source: r#"map_paths({ "a": [1, 2] }) -> |path, _value| { path }"#,
+                result: Ok(r#"{ "a": [["a", 0], ["a", 1]] }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(MapPathsFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        let leaf = Kind::bytes()
+            .or_integer()
+            .or_float()
+            .or_boolean()
+            .or_timestamp()
+            .or_regex()
+            .or_null();
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::Exact(Kind::array(Collection::from_unknown(
+                            Kind::bytes().or_integer(),
+                        ))),
+                    },
+                    Variable {
+                        kind: VariableKind::Exact(leaf),
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "clear top-level fields",
+                    source: r#"map_paths({ "a": { "b": 1 }, "c": 2 }) -> |path, value| { if length(path) > 1 { value } else { null } }"#,
+                    result: Ok(r#"{ "a": { "b": 1 }, "c": null }"#),
+                },
+            }],
+            is_iterator: true,
+            is_optional: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapPathsFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for MapPathsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        map_paths(value, &mut Vec::new(), ctx, &runner)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let value = self.value.type_def(state);
+        let output = self.closure.block_type_def.kind().clone();
+
+        TypeDef::from(map_leaves(value.kind(), &output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::btreemap;
+
+    #[test]
+    fn maps_leaves() {
+        let kind = Kind::object(btreemap! {
+            Field::from("a") => Kind::integer().or_array(Collection::from_unknown(Kind::bytes())),
+            Field::from("b") => Kind::object(Collection::empty()),
+        });
+
+        assert_eq!(
+            map_leaves(&kind, &Kind::boolean()),
+            Kind::object(btreemap! {
+                Field::from("a") => Kind::boolean().or_array(Collection::from_unknown(Kind::boolean())),
+                Field::from("b") => Kind::object(Collection::empty()),
+            })
+        );
+    }
+}
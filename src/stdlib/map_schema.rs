@@ -0,0 +1,289 @@
+use crate::compiler::prelude::*;
+use crate::path::{parse_value_path, OwnedValuePath};
+
+/// A single `target -> source` entry of a mapping table, validated at compile time.
+#[derive(Debug, Clone)]
+struct FieldMapping {
+    target: OwnedValuePath,
+    /// Paths tried in order, the first non-null value wins.
+    sources: Vec<OwnedValuePath>,
+    /// Used when none of the sources resolve to a value.
+    default: Option<Value>,
+}
+
+impl FieldMapping {
+    fn resolve(&self, value: &Value) -> Option<Value> {
+        self.sources
+            .iter()
+            .filter_map(|path| value.get(path))
+            .find(|value| !value.is_null())
+            .cloned()
+            .or_else(|| self.default.clone())
+    }
+}
+
+fn map_schema(value: &Value, mappings: &[FieldMapping]) -> Value {
+    let mut result = Value::Object(ObjectMap::new());
+
+    for mapping in mappings {
+        if let Some(field) = mapping.resolve(value) {
+            result.insert(&mapping.target, field);
+        }
+    }
+
+    result
+}
+
+fn invalid_mapping(value: Value, error: &'static str) -> function::Error {
+    function::Error::InvalidArgument {
+        keyword: "mapping",
+        value,
+        error,
+    }
+}
+
+fn parse_path(path: &Value, error: &'static str) -> Result<OwnedValuePath, function::Error> {
+    path.as_str()
+        .and_then(|path| parse_value_path(&path).ok())
+        .ok_or_else(|| invalid_mapping(path.clone(), error))
+}
+
+fn parse_sources(sources: &Value) -> Result<Vec<OwnedValuePath>, function::Error> {
+    match sources {
+        Value::Bytes(_) => Ok(vec![parse_path(sources, "invalid source path")?]),
+        Value::Array(paths) if !paths.is_empty() => paths
+            .iter()
+            .map(|path| parse_path(path, "invalid source path"))
+            .collect(),
+        _ => Err(invalid_mapping(
+            sources.clone(),
+            "source must be a path or a non-empty array of paths",
+        )),
+    }
+}
+
+fn parse_mapping(target: &str, source: &Value) -> Result<FieldMapping, function::Error> {
+    let target = parse_path(&Value::from(target), "invalid target path")?;
+
+    let Value::Object(options) = source else {
+        return Ok(FieldMapping {
+            target,
+            sources: parse_sources(source)?,
+            default: None,
+        });
+    };
+
+    if let Some(key) = options
+        .keys()
+        .find(|key| !matches!(key.as_str(), "path" | "default" | "value"))
+    {
+        return Err(invalid_mapping(
+            key.as_str().into(),
+            r#"unknown mapping option, expected "path", "default" or "value""#,
+        ));
+    }
+
+    match (
+        options.get("path"),
+        options.get("default"),
+        options.get("value"),
+    ) {
+        (None, None, Some(value)) => Ok(FieldMapping {
+            target,
+            sources: Vec::new(),
+            default: Some(value.clone()),
+        }),
+        (Some(sources), default, None) => Ok(FieldMapping {
+            target,
+            sources: parse_sources(sources)?,
+            default: default.cloned(),
+        }),
+        _ => Err(invalid_mapping(
+            source.clone(),
+            r#"mapping must set either "path" (with an optional "default") or "value""#,
+        )),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapSchema;
+
+impl Function for MapSchema {
+    fn identifier(&self) -> &'static str {
+        "map_schema"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "mapping",
+                kind: kind::OBJECT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "rename fields",
+                source: r#"map_schema({"src_ip": "10.0.0.1", "dest_port": 443}, {"source.ip": "src_ip", "destination.port": "dest_port"})"#,
+                result: Ok(r#"{"destination": {"port": 443}, "source": {"ip": "10.0.0.1"}}"#),
+            },
+            Example {
+                title: "fallbacks and defaults",
+                source: r#"map_schema({"proto": "TCP"}, {"network.transport": ["app_proto", "proto"], "event.kind": {"value": "alert"}, "event.severity": {"path": "alert.severity", "default": 3}})"#,
+                result: Ok(
+                    r#"{"event": {"kind": "alert", "severity": 3}, "network": {"transport": "TCP"}}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let mapping = arguments.required_literal("mapping", state)?;
+
+        let Value::Object(mapping) = mapping else {
+            return Err(invalid_mapping(mapping, "mapping must be an object").into());
+        };
+
+        let mappings = mapping
+            .iter()
+            .map(|(target, source)| parse_mapping(target.as_str(), source))
+            .collect::<Result<_, _>>()?;
+
+        Ok(MapSchemaFn { value, mappings }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapSchemaFn {
+    value: Box<dyn Expression>,
+    mappings: Vec<FieldMapping>,
+}
+
+impl FunctionExpression for MapSchemaFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(map_schema(&value, &self.mappings))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        map_schema => MapSchema;
+
+        rename {
+            args: func_args![
+                value: value!({"src_ip": "10.0.0.1", "dest_port": 443, "ignored": true}),
+                mapping: value!({"source.ip": "src_ip", "destination.port": "dest_port"}),
+            ],
+            want: Ok(value!({"source": {"ip": "10.0.0.1"}, "destination": {"port": 443}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        nested_sources {
+            args: func_args![
+                value: value!({"alert": {"signature_id": 2013028, "signature": "ET POLICY curl"}}),
+                mapping: value!({"rule.id": "alert.signature_id", "rule.name": "alert.signature"}),
+            ],
+            want: Ok(value!({"rule": {"id": 2013028, "name": "ET POLICY curl"}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        missing_sources_are_skipped {
+            args: func_args![
+                value: value!({"uid": "CHhAvVGS1DHFjwGM9", "proto": null}),
+                mapping: value!({"event.id": "uid", "network.transport": "proto", "source.ip": "id.orig_h"}),
+            ],
+            want: Ok(value!({"event": {"id": "CHhAvVGS1DHFjwGM9"}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        fallback_sources {
+            args: func_args![
+                value: value!({"proto": "TCP", "app_proto": null}),
+                mapping: value!({"network.protocol": ["app_proto", "proto"]}),
+            ],
+            want: Ok(value!({"network": {"protocol": "TCP"}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        defaults_and_constants {
+            args: func_args![
+                value: value!({"alert": {}}),
+                mapping: value!({
+                    "event.kind": {"value": "alert"},
+                    "event.severity": {"path": "alert.severity", "default": 3},
+                }),
+            ],
+            want: Ok(value!({"event": {"kind": "alert", "severity": 3}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        quoted_and_indexed_paths {
+            args: func_args![
+                value: value!({"answers": [{"rdata": "1.2.3.4"}], "query-name": "example.com"}),
+                mapping: value!({"dns.answers[0]": "answers[0].rdata", "dns.question.name": "\"query-name\""}),
+            ],
+            want: Ok(value!({"dns": {"answers": ["1.2.3.4"], "question": {"name": "example.com"}}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        invalid_target_path {
+            args: func_args![
+                value: value!({}),
+                mapping: value!({"source..ip": "src_ip"}),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        invalid_source {
+            args: func_args![
+                value: value!({}),
+                mapping: value!({"source.ip": 1}),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        unknown_option {
+            args: func_args![
+                value: value!({}),
+                mapping: value!({"source.ip": {"path": "src_ip", "fallback": "0.0.0.0"}}),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        path_and_value {
+            args: func_args![
+                value: value!({}),
+                mapping: value!({"source.ip": {"path": "src_ip", "value": "0.0.0.0"}}),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()),
+        }
+    ];
+}
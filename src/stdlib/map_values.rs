@@ -56,9 +56,14 @@ impl Function for MapValues {
             },
             Example {
                 title: "recursively map object values",
-                source: r#"map_values({ "a": 1, "b": [{ "c": 2 }, { "d": 3 }], "e": { "f": 4 } }, recursive: true) -> |value| { if is_integer(value) { int!(value) + 1 } else { value } }"#,
+                source: r#"map_values({ "a": 1, "b": [{ "c": 2 }, { "d": 3 }], "e": { "f": 4 } }, recursive: true) -> |value| { if is_integer(value) { value + 1 } else { value } }"#,
                 result: Ok(r#"{ "a": 2, "b": [{ "c": 3 }, { "d": 4 }], "e": { "f": 5 } }"#),
             },
+            Example {
+                title: "map range",
+                source: "map_values(range(0, 3)) -> |value| { value * 2 }",
+                result: Ok("[0, 2, 4]"),
+            },
         ]
     }
 
@@ -98,6 +103,7 @@ impl Function for MapValues {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
@@ -116,7 +122,6 @@ impl FunctionExpression for MapValuesFn {
             Some(expr) => expr.resolve(ctx)?.try_boolean()?,
         };
 
-        let value = self.value.resolve(ctx)?;
         let FunctionClosure {
             variables,
             block,
@@ -124,6 +129,18 @@ impl FunctionExpression for MapValuesFn {
         } = &self.closure;
         let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
 
+        // Map ranges without materializing them as an array first.
+        if let Some(range) = self.value.resolve_range(ctx) {
+            return range?
+                .map(|value| {
+                    let mut value = value.into();
+                    runner.map_value(ctx, &mut value).map(|()| value)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Into::into);
+        }
+
+        let value = self.value.resolve(ctx)?;
         map_values(value, recursive, ctx, runner)
     }
 
@@ -4,14 +4,74 @@ use crate::datadog_filter::{
     regex::{wildcard_regex, word_regex},
     Filter, Matcher, Resolver, Run,
 };
-use crate::datadog_search_syntax::{Comparison, ComparisonValue, Field, ParseError, QueryNode};
+use crate::datadog_search_syntax::{
+    Comparison, ComparisonValue, Field, ParseError, QueryNode, SyntaxError,
+};
+use crate::diagnostic::Label;
 use crate::owned_value_path;
 use crate::path::{parse_value_path, OwnedValuePath, PathParseError};
 use crate::prelude::function::Error::InvalidArgument;
+use cidr_utils::cidr::IpCidr;
 use std::borrow::Cow;
+use std::net::IpAddr;
+use std::str::FromStr;
 
 const QUERY_KEYWORD: &str = "query";
 
+/// A query literal that doesn't follow the Datadog search syntax.
+#[derive(Debug)]
+struct InvalidQueryError {
+    message: String,
+    span: Span,
+}
+
+impl InvalidQueryError {
+    /// Points at the character the query failed to parse at, when the literal is
+    /// a plain string whose characters map directly onto the source.
+    fn new(query: &str, error: &ParseError, argument_span: Option<Span>) -> Self {
+        let Some(error) = error.downcast_ref::<SyntaxError>() else {
+            return Self {
+                message: error.to_string(),
+                span: argument_span.unwrap_or_default(),
+            };
+        };
+
+        let span = argument_span.map_or_else(Span::default, |span| {
+            // `"query"` or `s'query'`, without any escape sequences
+            let prefix = match (span.end() - span.start()).checked_sub(query.len()) {
+                Some(2) => 1,
+                Some(3) => 2,
+                _ => return span,
+            };
+            let start = span.start() + prefix + error.position;
+            Span::new(start, start + 1)
+        });
+
+        Self {
+            message: error.message.clone(),
+            span,
+        }
+    }
+}
+
+impl fmt::Display for InvalidQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid Datadog search query: {}", self.message)
+    }
+}
+
+impl std::error::Error for InvalidQueryError {}
+
+impl DiagnosticMessage for InvalidQueryError {
+    fn code(&self) -> usize {
+        403
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![Label::primary(&self.message, self.span)]
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct MatchDatadogQuery;
 
@@ -61,7 +121,11 @@ impl Function for MatchDatadogQuery {
 
         // Compile the Datadog search query to AST.
         let node: QueryNode = query.parse().map_err(|e: ParseError| {
-            Box::new(ExpressionError::from(e.to_string())) as Box<dyn DiagnosticMessage>
+            Box::new(InvalidQueryError::new(
+                &query,
+                &e,
+                arguments.span(QUERY_KEYWORD),
+            )) as Box<dyn DiagnosticMessage>
         })?;
 
         // Build the matcher function that accepts a VRL event value. This will parse the `node`
@@ -301,6 +365,44 @@ impl Filter<Value> for VrlFilter {
         })
     }
 
+    fn cidr(
+        &self,
+        field: Field,
+        cidrs: &[String],
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        let buf = lookup_field(&field)?;
+
+        // Blocks that aren't valid CIDR notation can't contain any address.
+        let cidrs = cidrs
+            .iter()
+            .filter_map(|cidr| IpCidr::from_str(cidr).ok())
+            .collect::<Vec<_>>();
+        let contains = move |ip: &str| {
+            IpAddr::from_str(ip).is_ok_and(|ip| cidrs.iter().any(|cidr| cidr.contains(&ip)))
+        };
+
+        Ok(match field {
+            // Tag values need extracting by "key:value" to be matched.
+            Field::Tag(tag) => {
+                let starts_with = format!("{tag}:");
+
+                resolve_value(
+                    buf,
+                    Run::boxed(move |value| match value {
+                        Value::Array(v) => v.iter().any(|v| {
+                            string_value(v)
+                                .strip_prefix(&starts_with)
+                                .is_some_and(&contains)
+                        }),
+                        _ => false,
+                    }),
+                )
+            }
+            // Everything else is matched by its string value.
+            _ => resolve_value(buf, Run::boxed(move |value| contains(&string_value(value)))),
+        })
+    }
+
     fn compare(
         &self,
         field: Field,
@@ -2014,5 +2116,56 @@ mod test {
             want: Ok(true),
             tdef: type_def(),
         }
+
+        cidr_attribute_match {
+            args: func_args![value: value!({"network": {"client": {"ip": "10.1.2.3"}}}), query: "CIDR(@network.client.ip,10.0.0.0/8)"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        cidr_attribute_no_match {
+            args: func_args![value: value!({"network": {"client": {"ip": "11.1.2.3"}}}), query: "CIDR(@network.client.ip,10.0.0.0/8)"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        cidr_attribute_multiple_blocks {
+            args: func_args![value: value!({"ip": "2001:db8::1"}), query: "CIDR(@ip,10.0.0.0/8,2001:db8::/32)"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        cidr_attribute_not_an_ip {
+            args: func_args![value: value!({"ip": "localhost"}), query: "CIDR(@ip,10.0.0.0/8)"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        not_cidr_attribute {
+            args: func_args![value: value!({"ip": "192.168.0.1", "service": "web"}), query: "service:web -CIDR(@ip,192.168.0.0/16)"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        cidr_tag_match {
+            args: func_args![value: value!({"tags": ["host:a", "ip:192.168.1.1"]}), query: "CIDR(ip,192.168.0.0/16)"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
     ];
+
+    #[test]
+    fn invalid_query_points_into_literal() {
+        let source = r#"match_datadog_query({}, "foo:(bar")"#;
+        let diagnostics = crate::compiler::compile(source, &crate::stdlib::all())
+            .expect_err("invalid query should not compile");
+
+        let spans = diagnostics[0]
+            .labels
+            .iter()
+            .map(|label| label.span)
+            .collect::<Vec<_>>();
+        let start = source.find("foo").unwrap() + "foo:(bar".len();
+        assert!(spans.contains(&Span::new(start, start + 1)), "{spans:?}");
+    }
 }
@@ -0,0 +1,95 @@
+use crate::compiler::prelude::*;
+
+/// Converts the result of a floating point operation to a value, failing if it
+/// isn't a finite number.
+pub(super) fn float_result(function: &str, result: f64) -> Resolved {
+    if result.is_nan() {
+        Err(format!("{function} result is not a number").into())
+    } else if result.is_infinite() {
+        Err(format!("{function} result overflowed").into())
+    } else {
+        Ok(Value::from_f64_or_zero(result))
+    }
+}
+
+pub(super) fn expect_number(value: &Value) -> Result<(), ExpressionError> {
+    match value {
+        Value::Integer(_) | Value::Float(_) => Ok(()),
+        value => Err(ValueError::Expected {
+            got: value.kind(),
+            expected: Kind::float() | Kind::integer(),
+        }
+        .into()),
+    }
+}
+
+/// The kind of the result of an operation on numbers that returns an integer
+/// if all its operands are integers, and a float otherwise.
+pub(super) fn numeric_kind<'a>(kinds: impl IntoIterator<Item = &'a Kind>) -> Kind {
+    let mut all_integers = true;
+    let mut any_float = false;
+    for kind in kinds {
+        all_integers &= kind.is_integer();
+        any_float |= kind.is_float();
+    }
+
+    if all_integers {
+        Kind::integer()
+    } else if any_float {
+        Kind::float()
+    } else {
+        Kind::integer().or_float()
+    }
+}
+
+/// Returns the elements of an array of numbers, failing if any element isn't
+/// an integer or a float.
+pub(super) fn numbers(value: Value) -> Result<Vec<Value>, ExpressionError> {
+    let array = value.try_array()?;
+    if let Some((index, element)) = array
+        .iter()
+        .enumerate()
+        .find(|(_, element)| !matches!(element, Value::Integer(_) | Value::Float(_)))
+    {
+        return Err(format!(
+            "expected an array of numbers, found {} at index {index}",
+            element.kind()
+        )
+        .into());
+    }
+    Ok(array)
+}
+
+/// Returns the elements of an array of numbers as floats.
+pub(super) fn floats(value: Value) -> Result<Vec<f64>, ExpressionError> {
+    numbers(value)?
+        .iter()
+        .map(|number| number.try_into_f64().map_err(Into::into))
+        .collect()
+}
+
+/// The kind of the elements of an array of the given kind.
+pub(super) fn element_kind(kind: &Kind) -> Kind {
+    kind.as_array()
+        .map_or_else(Kind::any, Collection::reduced_kind)
+}
+
+/// Whether all elements of an array of the given kind are numbers.
+pub(super) fn numeric_elements(kind: &Kind) -> bool {
+    Kind::integer()
+        .or_float()
+        .is_superset(&element_kind(kind))
+        .is_ok()
+}
+
+/// Returns the value at `percentile` of `sorted`, linearly interpolating
+/// between the closest ranks.
+pub(super) fn interpolate_percentile(sorted: &[f64], percentile: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = percentile / 100.0 * last as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - rank.floor();
+
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+}
@@ -0,0 +1,109 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{floats, numeric_elements};
+
+fn mean(value: Value) -> Resolved {
+    let floats = floats(value)?;
+    if floats.is_empty() {
+        return Ok(Value::Null);
+    }
+
+    let mean = floats.iter().sum::<f64>() / floats.len() as f64;
+    Ok(Value::from_f64_or_zero(mean))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mean;
+
+impl Function for Mean {
+    fn identifier(&self) -> &'static str {
+        "mean"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "mean",
+                source: "mean([1, 2, 3, 4])",
+                result: Ok("2.5"),
+            },
+            Example {
+                title: "empty array",
+                source: "mean([])",
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(MeanFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MeanFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for MeanFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        mean(value)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let numeric = numeric_elements(self.value.type_def(state).kind());
+
+        TypeDef::float().or_null().maybe_fallible(!numeric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        mean => Mean;
+
+        integers {
+            args: func_args![value: value!([1, 2, 3, 4])],
+            want: Ok(2.5),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        mixed {
+            args: func_args![value: value!([1, 2.0, 6])],
+            want: Ok(3.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(Value::Null),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        non_numeric {
+            args: func_args![value: value!([1, null])],
+            want: Err("expected an array of numbers, found null at index 1"),
+            tdef: TypeDef::float().or_null().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,112 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{floats, interpolate_percentile, numeric_elements};
+
+fn median(value: Value) -> Resolved {
+    let mut floats = floats(value)?;
+    floats.sort_by(f64::total_cmp);
+
+    Ok(interpolate_percentile(&floats, 50.0).map_or(Value::Null, Value::from_f64_or_zero))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Median;
+
+impl Function for Median {
+    fn identifier(&self) -> &'static str {
+        "median"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "odd number of elements",
+                source: "median([3, 1, 2])",
+                result: Ok("2.0"),
+            },
+            Example {
+                title: "even number of elements",
+                source: "median([4, 1, 3, 2])",
+                result: Ok("2.5"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(MedianFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MedianFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for MedianFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        median(value)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let numeric = numeric_elements(self.value.type_def(state).kind());
+
+        TypeDef::float().or_null().maybe_fallible(!numeric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        median => Median;
+
+        odd {
+            args: func_args![value: value!([5, 1.5, 3])],
+            want: Ok(3.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        even {
+            args: func_args![value: value!([10, 1, 2, 3])],
+            want: Ok(2.5),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        single {
+            args: func_args![value: value!([7])],
+            want: Ok(7.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(Value::Null),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        non_numeric {
+            args: func_args![value: value!([1, true])],
+            want: Err("expected an array of numbers, found boolean at index 1"),
+            tdef: TypeDef::float().or_null().fallible(),
+        }
+    ];
+}
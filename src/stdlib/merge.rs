@@ -1,5 +1,47 @@
 use crate::compiler::prelude::*;
-use std::collections::BTreeMap;
+use crate::path::OwnedValuePath;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ArrayStrategy {
+    #[default]
+    Replace,
+    Concat,
+    Union,
+}
+
+impl ArrayStrategy {
+    const ALL: [Self; 3] = [Self::Replace, Self::Concat, Self::Union];
+
+    fn all_value() -> Vec<Value> {
+        Self::ALL.iter().map(|s| s.as_str().into()).collect()
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Concat => "concat",
+            Self::Union => "union",
+        }
+    }
+
+    fn merge(self, mut array1: Vec<Value>, array2: Vec<Value>) -> Vec<Value> {
+        match self {
+            Self::Replace => array2,
+            Self::Concat => {
+                array1.extend(array2);
+                array1
+            }
+            Self::Union => {
+                for value in array2 {
+                    if !array1.contains(&value) {
+                        array1.push(value);
+                    }
+                }
+                array1
+            }
+        }
+    }
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct Merge;
@@ -26,28 +68,92 @@ impl Function for Merge {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "array_strategy",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "merge objects",
-            source: r#"merge({ "a": 1, "b": 2 }, { "b": 3, "c": 4 })"#,
-            result: Ok(r#"{ "a": 1, "b": 3, "c": 4 }"#),
-        }]
+        &[
+            Example {
+                title: "merge objects",
+                source: r#"merge({ "a": 1, "b": 2 }, { "b": 3, "c": 4 })"#,
+                result: Ok(r#"{ "a": 1, "b": 3, "c": 4 }"#),
+            },
+            Example {
+                title: "concatenate arrays",
+                source: r#"merge({ "a": { "tags": ["x"] } }, { "a": { "tags": ["x", "y"] } }, deep: true, array_strategy: "concat")"#,
+                result: Ok(r#"{ "a": { "tags": ["x", "x", "y"] } }"#),
+            },
+            Example {
+                title: "union of arrays",
+                source: r#"merge({ "tags": ["x"] }, { "tags": ["x", "y"] }, array_strategy: "union")"#,
+                result: Ok(r#"{ "tags": ["x", "y"] }"#),
+            },
+        ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let to = arguments.required("to");
         let from = arguments.required("from");
         let deep = arguments.optional("deep").unwrap_or_else(|| expr!(false));
+        let array_strategy = arguments
+            .optional_enum("array_strategy", &ArrayStrategy::all_value(), state)?
+            .map(|value| {
+                ArrayStrategy::ALL
+                    .into_iter()
+                    .find(|strategy| value == Value::from(strategy.as_str()))
+                    .expect("validated enum")
+            })
+            .unwrap_or_default();
+        let closure = arguments.optional_closure().cloned();
+
+        Ok(MergeFn {
+            to,
+            from,
+            deep,
+            array_strategy,
+            closure,
+        }
+        .as_expr())
+    }
 
-        Ok(MergeFn { to, from, deep }.as_expr())
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "from",
+                kind: Kind::object(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::Exact(Kind::bytes()),
+                    },
+                    Variable {
+                        kind: VariableKind::Exact(Kind::any()),
+                    },
+                    Variable {
+                        kind: VariableKind::Exact(Kind::any()),
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "keep both values",
+                    source: r#"merge({ "a": 1, "b": 2 }, { "b": 3 }) -> |_path, left, right| { [left, right] }"#,
+                    result: Ok(r#"{ "a": 1, "b": [2, 3] }"#),
+                },
+            }],
+            is_iterator: false,
+            is_optional: true,
+        })
     }
 }
 
@@ -56,6 +162,8 @@ pub(crate) struct MergeFn {
     to: Box<dyn Expression>,
     from: Box<dyn Expression>,
     deep: Box<dyn Expression>,
+    array_strategy: ArrayStrategy,
+    closure: Option<FunctionClosure>,
 }
 
 impl FunctionExpression for MergeFn {
@@ -64,53 +172,132 @@ impl FunctionExpression for MergeFn {
         let from_value = self.from.resolve(ctx)?.try_object()?;
         let deep = self.deep.resolve(ctx)?.try_boolean()?;
 
-        merge_maps(&mut to_value, &from_value, deep);
+        let runner = self.closure.as_ref().map(|closure| {
+            let FunctionClosure {
+                variables, block, ..
+            } = closure;
+            closure::Runner::new(variables, |ctx| block.resolve(ctx))
+        });
+        let options = MergeOptions {
+            deep,
+            array_strategy: self.array_strategy,
+            on_conflict: runner.as_ref(),
+        };
+
+        merge_maps(
+            &mut OwnedValuePath::root(),
+            &mut to_value,
+            from_value,
+            &options,
+            ctx,
+        )?;
 
         Ok(to_value.into())
     }
 
     fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let to = self.to.type_def(state).restrict_object();
+        let from = self.from.type_def(state).restrict_object();
+
         // TODO: this has a known bug when deep is true
         // see: https://github.com/vectordotdev/vector/issues/13597
-        self.to
-            .type_def(state)
-            .restrict_object()
-            .merge_overwrite(self.from.type_def(state).restrict_object())
+        let mut merged = to.clone().merge_overwrite(from.clone());
+
+        // Fields in both objects can combine the arrays of both, or hold the
+        // result of the closure.
+        let conflict = self
+            .closure
+            .as_ref()
+            .map(|closure| closure.block_type_def.kind().clone());
+        if conflict.is_none() && self.array_strategy == ArrayStrategy::Replace {
+            return merged;
+        }
+
+        if let (Some(to), Some(from), Some(object)) =
+            (to.as_object(), from.as_object(), merged.as_object_mut())
+        {
+            for (field, kind) in object.known_mut() {
+                let (Some(to_kind), Some(from_kind)) =
+                    (to.known().get(field), from.known().get(field))
+                else {
+                    continue;
+                };
+
+                let mut combined = to_kind.union(from_kind.clone());
+                if let Some(array) = combined.as_array_mut() {
+                    *array = Collection::from_unknown(array.reduced_kind());
+                }
+                if let Some(conflict) = &conflict {
+                    combined = combined.union(conflict.clone());
+                }
+                *kind = combined;
+            }
+        }
+
+        merged
     }
 }
 
-/// Merges two `BTreeMaps` of Symbol’s value as variable is void: Values. The
-/// second map is merged into the first one.
+struct MergeOptions<'a, T> {
+    deep: bool,
+    array_strategy: ArrayStrategy,
+    on_conflict: Option<&'a closure::Runner<'a, T>>,
+}
+
+/// Merges the second map into the first one.
 ///
-/// If Symbol’s value as variable is void: deep is true, only the top level
-/// values are merged in. If both maps contain a field with the same name, the
-/// field from the first is overwritten with the field from the second.
+/// If both maps contain a field with the same name, the field from the second
+/// map overwrites the field from the first, unless:
 ///
-/// If Symbol’s value as variable is void: deep is false, should both maps
-/// contain a field with the same name, and both those fields are also maps, the
-/// function will recurse and will merge the child fields from the second into
-/// the child fields from the first.
+/// - `deep` is true and both fields are maps, in which case the function
+///   recurses and merges the child fields from the second into the child
+///   fields from the first,
+/// - both fields are arrays and the array strategy isn't "replace", in which
+///   case the arrays are combined,
+/// - a conflict closure is given, which returns the merged value.
 ///
 /// Note, this does recurse, so there is the theoretical possibility that it
 /// could blow up the stack. From quick tests on a sample project I was able to
 /// merge maps with a depth of 3,500 before encountering issues. So I think that
 /// is likely to be within acceptable limits. If it becomes a problem, we can
 /// unroll this function, but that will come at a cost of extra code complexity.
-fn merge_maps<K>(map1: &mut BTreeMap<K, Value>, map2: &BTreeMap<K, Value>, deep: bool)
+fn merge_maps<T>(
+    path: &mut OwnedValuePath,
+    map1: &mut ObjectMap,
+    map2: ObjectMap,
+    options: &MergeOptions<'_, T>,
+    ctx: &mut Context,
+) -> Result<(), ExpressionError>
 where
-    K: std::cmp::Ord + Clone,
+    T: Fn(&mut Context) -> Resolved,
 {
     for (key2, value2) in map2 {
-        match (deep, map1.get_mut(key2), value2) {
-            (true, Some(Value::Object(ref mut child1)), Value::Object(ref child2)) => {
+        path.push_field(key2.as_str());
+        let merged = match (map1.remove(&key2), value2) {
+            (None, value2) => value2,
+            (Some(Value::Object(mut child1)), Value::Object(child2)) if options.deep => {
                 // We are doing a deep merge and both fields are maps.
-                merge_maps(child1, child2, deep);
+                merge_maps(path, &mut child1, child2, options, ctx)?;
+                child1.into()
             }
-            _ => {
-                map1.insert(key2.clone(), value2.clone());
+            (Some(Value::Array(array1)), Value::Array(array2))
+                if options.array_strategy != ArrayStrategy::Replace =>
+            {
+                options.array_strategy.merge(array1, array2).into()
             }
-        }
+            (Some(value1), value2) => match options.on_conflict {
+                Some(runner) => {
+                    let path = Value::from(format!(".{path}"));
+                    runner.run_values(ctx, &[&path, &value1, &value2])?
+                }
+                None => value2,
+            },
+        };
+        map1.insert(key2, merged);
+        path.segments.pop();
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -187,5 +374,57 @@ mod tests {
             }),
 
         }
+
+        concat_arrays {
+            args: func_args![
+                to: value!({ tags: ["a", "b"], key: "val1" }),
+                from: value!({ tags: ["b", "c"], key: "val2" }),
+                array_strategy: "concat",
+            ],
+            want: Ok(value!({ tags: ["a", "b", "b", "c"], key: "val2" })),
+            tdef: TypeDef::object(btreemap! {
+                Field::from("tags") => Kind::array(Collection::from_unknown(Kind::bytes())),
+                Field::from("key") => Kind::bytes(),
+            }),
+        }
+
+        union_arrays {
+            args: func_args![
+                to: value!({ tags: ["a", "b"] }),
+                from: value!({ tags: ["b", "c", "c"] }),
+                array_strategy: "union",
+            ],
+            want: Ok(value!({ tags: ["a", "b", "c"] })),
+            tdef: TypeDef::object(btreemap! {
+                Field::from("tags") => Kind::array(Collection::from_unknown(Kind::bytes())),
+            }),
+        }
+
+        deep_concat_arrays {
+            args: func_args![
+                to: value!({ child: { tags: [1] } }),
+                from: value!({ child: { tags: [2] } }),
+                deep: true,
+                array_strategy: "concat",
+            ],
+            want: Ok(value!({ child: { tags: [1, 2] } })),
+            tdef: TypeDef::object(btreemap! {
+                Field::from("child") => Kind::object(btreemap! {
+                    Field::from("tags") => Kind::array(btreemap! {
+                        Index::from(0) => Kind::integer(),
+                    }),
+                }),
+            }),
+        }
+
+        invalid_array_strategy {
+            args: func_args![
+                to: value!({}),
+                from: value!({}),
+                array_strategy: "append",
+            ],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::object(Collection::any()),
+        }
     ];
 }
@@ -0,0 +1,120 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, numeric_kind};
+
+fn min(value: Value, other: Value) -> Resolved {
+    expect_number(&value)?;
+    expect_number(&other)?;
+
+    match (value, other) {
+        (Value::Integer(value), Value::Integer(other)) => Ok(value.min(other).into()),
+        (value, other) => Ok(Value::from_f64_or_zero(
+            value.try_into_f64()?.min(other.try_into_f64()?),
+        )),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Min;
+
+impl Function for Min {
+    fn identifier(&self) -> &'static str {
+        "min"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "integers",
+                source: "min(3, 7)",
+                result: Ok("3"),
+            },
+            Example {
+                title: "mixed",
+                source: "min(3, 2.5)",
+                result: Ok("2.5"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+        Ok(MinFn { value, other }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MinFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl FunctionExpression for MinFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+
+        min(value, other)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let kind = numeric_kind([
+            self.value.type_def(state).kind(),
+            self.other.type_def(state).kind(),
+        ]);
+
+        kind.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        min => Min;
+
+        integers {
+            args: func_args![value: 3, other: -7],
+            want: Ok(-7),
+            tdef: TypeDef::integer(),
+        }
+
+        floats {
+            args: func_args![value: 1.5, other: 2.5],
+            want: Ok(1.5),
+            tdef: TypeDef::float(),
+        }
+
+        mixed {
+            args: func_args![value: 1, other: 2.5],
+            want: Ok(1.0),
+            tdef: TypeDef::float(),
+        }
+    ];
+}
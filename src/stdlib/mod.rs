@@ -40,21 +40,36 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "stdlib")] {
         mod abs;
         mod append;
+        mod apply_patch;
         mod array;
+        mod array_fill;
         mod assert;
+        mod assert_approx_eq;
+        mod assert_contains;
         mod assert_eq;
+        mod assert_type;
         mod boolean;
         mod ceil;
         mod casing;
         mod chunks;
+        mod clamp;
         mod compact;
+        mod consistent_sample;
+        mod compress_ipv6;
         mod contains;
         mod contains_all;
+        mod count_distinct_window;
         mod crc;
+        mod crc32;
+        mod cron_next;
         mod decode_base16;
+        mod decode_base32;
+        mod decode_base58;
         mod decode_base64;
+        mod decode_brotli;
         mod decode_charset;
         mod decode_gzip;
+        mod decode_lz4;
         mod decode_mime_q;
         mod decode_percent;
         mod decode_punycode;
@@ -63,53 +78,77 @@ cfg_if::cfg_if! {
         mod decode_zstd;
         mod decrypt;
         mod del;
+        mod diff;
         mod dns_lookup;
         mod downcase;
+        mod ecs_field;
         mod encode_base16;
+        mod encode_base32;
+        mod encode_base58;
         mod encode_base64;
+        mod encode_brotli;
         mod encode_charset;
         mod encode_gzip;
         mod encode_json;
         mod encode_key_value;
         mod encode_logfmt;
+        mod encode_lz4;
         mod encode_percent;
         mod encode_proto;
         mod encode_punycode;
+        mod encode_query_string;
+        mod encode_url;
         mod encode_snappy;
         mod encode_zlib;
         mod encode_zstd;
         mod encrypt;
         mod ends_with;
+        mod env_vars;
         mod exists;
+        mod exp;
+        mod expand_ipv6;
+        mod expand_variables;
+        mod extract_all;
         mod filter;
         mod find;
         mod flatten;
         mod float;
         mod floor;
+        mod fnv1a;
         mod for_each;
+        mod format_bytes;
         mod format_int;
+        mod format_mac;
         mod format_number;
         mod format_timestamp;
         mod from_unix_timestamp;
         mod get;
         mod get_env_var;
         mod get_hostname;
+        mod get_json_pointer;
         mod get_timezone_name;
+        mod hash_util;
         mod hmac;
         mod includes;
+        mod int_to_ip;
         mod integer;
         mod ip_aton;
         mod ip_cidr_contains;
+        mod ip_in_range_list;
         mod ip_ntoa;
         mod ip_ntop;
         mod ip_pton;
         mod ip_subnet;
+        mod ip_to_int;
         mod ip_to_ipv6;
+        mod ip_utils;
         mod ipv6_to_ipv4;
         mod is_array;
+        mod is_ascii;
         mod is_boolean;
         mod is_empty;
         mod is_float;
+        mod is_global_ip;
         mod is_integer;
         mod is_ipv4;
         mod is_ipv6;
@@ -117,26 +156,46 @@ cfg_if::cfg_if! {
         mod is_null;
         mod is_nullish;
         mod is_object;
+        mod is_private_ip;
         mod is_regex;
         mod is_string;
         mod is_timestamp;
         mod join;
+        mod json_merge_patch;
+        mod json_patch;
         mod keys;
+        mod ksuid;
         mod length;
+        mod levenshtein_distance;
         mod log;
+        mod log10;
+        mod log2;
         mod log_util;
+        mod mac_utils;
+        mod math_util;
         mod map_keys;
+        mod map_paths;
+        mod map_schema;
         mod map_values;
         mod r#match;
         mod match_any;
         mod match_array;
         mod match_datadog_query;
+        mod max;
         mod md5;
+        mod mean;
+        mod median;
         mod merge;
+        mod min;
         mod mod_func;
+        mod murmur3;
+        mod normalize_unicode;
         mod now;
         mod object;
         mod object_from_array;
+        mod ocsf_field;
+        mod pad_left;
+        mod pad_right;
         mod parse_apache_log;
         mod parse_aws_alb_log;
         mod parse_aws_cloudwatch_log_subscription_message;
@@ -145,59 +204,90 @@ cfg_if::cfg_if! {
         mod parse_cef;
         mod parse_cbor;
         mod parse_common_log;
+        mod parse_cron;
         mod parse_csv;
         mod parse_duration;
         mod parse_etld;
         mod parse_float;
         mod parse_glog;
+        mod parse_graphql;
         mod parse_grok;
         mod parse_groks;
+        mod parse_html_meta;
         mod parse_influxdb;
         mod parse_int;
+        mod parse_journald_export;
         mod parse_json;
+        mod parse_jwt;
         mod parse_key_value;
         mod parse_klog;
+        mod parse_ksuid;
         mod parse_linux_authorization;
         mod parse_logfmt;
+        mod parse_mac;
+        mod parse_ndjson;
         mod parse_nginx_log;
         mod parse_proto;
         mod parse_query_string;
         mod parse_regex;
         mod parse_regex_all;
         mod parse_ruby_hash;
+        mod parse_semver;
         mod parse_syslog;
         mod parse_timestamp;
         mod parse_tokens;
+        mod parse_ulid;
         mod parse_url;
         mod parse_user_agent;
+        mod parse_uuid;
+        mod parse_windows_event;
         mod parse_xml;
+        mod parse_x509;
+        mod percentile;
+        mod pow;
         mod push;
+        mod query;
+        mod query_xml;
         mod random_bool;
         mod random_bytes;
         mod random_float;
         mod random_int;
+        mod range;
         mod redact;
         mod remove;
+        mod remove_diacritics;
+        mod repeat;
         mod replace;
         mod replace_with;
         mod reverse_dns;
         mod round;
+        mod schema_util;
         mod seahash;
+        mod semver_matches;
         mod set;
         mod sha1;
         mod sha2;
         mod sha3;
         mod sieve;
+        mod similarity;
         mod slice;
         mod split;
+        mod sqrt;
         mod starts_with;
+        mod stddev;
         mod string;
         mod strip_ansi_escape_codes;
+        mod strip_html;
         mod strip_whitespace;
         mod strlen;
+        mod substring;
+        mod sum;
+        mod summarize_object;
+        mod sum_window;
         mod tag_types_externally;
         mod tally;
         mod tally_value;
+        mod throttle;
         mod timestamp;
         mod to_bool;
         mod to_float;
@@ -210,15 +300,22 @@ cfg_if::cfg_if! {
         mod to_unix_timestamp;
         mod community_id;
         mod truncate;
+        mod ulid;
         mod unflatten;
         mod type_def;
         mod unique;
         mod unnest;
         mod upcase;
+        mod update_url;
         mod uuid_from_friendly_id;
+        mod uuid_v3;
         mod uuid_v4;
+        mod uuid_v5;
         mod uuid_v7;
         mod values;
+        mod verify_jwt;
+        mod walk;
+        mod xxhash64;
         mod zip;
 
         // -----------------------------------------------------------------------------
@@ -226,18 +323,33 @@ cfg_if::cfg_if! {
         pub use self::hmac::Hmac;
         pub use abs::Abs;
         pub use append::Append;
+        pub use apply_patch::ApplyPatch;
+        pub use array_fill::ArrayFill;
         pub use assert::Assert;
+        pub use assert_approx_eq::AssertApproxEq;
+        pub use assert_contains::AssertContains;
         pub use assert_eq::AssertEq;
+        pub use assert_type::AssertType;
         pub use boolean::Boolean;
         pub use ceil::Ceil;
         pub use chunks::Chunks;
+        pub use clamp::Clamp;
         pub use compact::Compact;
+        pub use consistent_sample::ConsistentSample;
+        pub use compress_ipv6::CompressIpv6;
         pub use contains::Contains;
         pub use contains_all::ContainsAll;
+        pub use count_distinct_window::CountDistinctWindow;
+        pub use crc32::Crc32;
+        pub use cron_next::CronNext;
         pub use decode_base16::DecodeBase16;
+        pub use decode_base32::DecodeBase32;
+        pub use decode_base58::DecodeBase58;
         pub use decode_base64::DecodeBase64;
+        pub use decode_brotli::DecodeBrotli;
         pub use decode_charset::DecodeCharset;
         pub use decode_gzip::DecodeGzip;
+        pub use decode_lz4::DecodeLz4;
         pub use decode_mime_q::DecodeMimeQ;
         pub use decode_percent::DecodePercent;
         pub use decode_punycode::DecodePunycode;
@@ -246,36 +358,52 @@ cfg_if::cfg_if! {
         pub use decode_zstd::DecodeZstd;
         pub use decrypt::Decrypt;
         pub use del::Del;
+        pub use diff::Diff;
         pub use dns_lookup::DnsLookup;
         pub use downcase::Downcase;
+        pub use ecs_field::EcsField;
         pub use casing::camelcase::Camelcase;
         pub use casing::pascalcase::Pascalcase;
         pub use casing::snakecase::Snakecase;
         pub use casing::screamingsnakecase::ScreamingSnakecase;
         pub use casing::kebabcase::Kebabcase;
         pub use encode_base16::EncodeBase16;
+        pub use encode_base32::EncodeBase32;
+        pub use encode_base58::EncodeBase58;
         pub use encode_base64::EncodeBase64;
+        pub use encode_brotli::EncodeBrotli;
         pub use encode_charset::EncodeCharset;
         pub use encode_gzip::EncodeGzip;
         pub use encode_json::EncodeJson;
         pub use encode_key_value::EncodeKeyValue;
         pub use encode_logfmt::EncodeLogfmt;
+        pub use encode_lz4::EncodeLz4;
         pub use encode_percent::EncodePercent;
         pub use encode_proto::EncodeProto;
         pub use encode_punycode::EncodePunycode;
+        pub use encode_query_string::EncodeQueryString;
+        pub use encode_url::EncodeUrl;
         pub use encode_snappy::EncodeSnappy;
         pub use encode_zlib::EncodeZlib;
         pub use encode_zstd::EncodeZstd;
         pub use encrypt::Encrypt;
         pub use ends_with::EndsWith;
+        pub use env_vars::EnvVars;
         pub use exists::Exists;
+        pub use exp::Exp;
+        pub use expand_ipv6::ExpandIpv6;
+        pub use expand_variables::ExpandVariables;
+        pub use extract_all::ExtractAll;
         pub use filter::Filter;
         pub use find::Find;
         pub use flatten::Flatten;
         pub use float::Float;
         pub use floor::Floor;
+        pub use fnv1a::Fnv1a;
         pub use for_each::ForEach;
+        pub use format_bytes::FormatBytes;
         pub use format_int::FormatInt;
+        pub use format_mac::FormatMac;
         pub use format_number::FormatNumber;
         pub use format_timestamp::FormatTimestamp;
         pub use from_unix_timestamp::FromUnixTimestamp;
@@ -283,22 +411,28 @@ cfg_if::cfg_if! {
         pub use get::Get;
         pub use get_env_var::GetEnvVar;
         pub use get_hostname::GetHostname;
+        pub use get_json_pointer::GetJsonPointer;
         pub use get_timezone_name::GetTimezoneName;
         pub use get_timezone_name::get_name_for_timezone;
         pub use includes::Includes;
+        pub use int_to_ip::IntToIp;
         pub use integer::Integer;
         pub use ip_aton::IpAton;
         pub use ip_cidr_contains::IpCidrContains;
+        pub use ip_in_range_list::IpInRangeList;
         pub use ip_ntoa::IpNtoa;
         pub use ip_ntop::IpNtop;
         pub use ip_pton::IpPton;
         pub use ip_subnet::IpSubnet;
+        pub use ip_to_int::IpToInt;
         pub use ip_to_ipv6::IpToIpv6;
         pub use ipv6_to_ipv4::Ipv6ToIpV4;
         pub use is_array::IsArray;
+        pub use is_ascii::IsAscii;
         pub use is_boolean::IsBoolean;
         pub use is_empty::IsEmpty;
         pub use is_float::IsFloat;
+        pub use is_global_ip::IsGlobalIp;
         pub use is_integer::IsInteger;
         pub use is_ipv4::IsIpv4;
         pub use is_ipv6::IsIpv6;
@@ -306,23 +440,41 @@ cfg_if::cfg_if! {
         pub use is_null::IsNull;
         pub use is_nullish::IsNullish;
         pub use is_object::IsObject;
+        pub use is_private_ip::IsPrivateIp;
         pub use is_regex::IsRegex;
         pub use is_string::IsString;
         pub use is_timestamp::IsTimestamp;
         pub use join::Join;
+        pub use json_merge_patch::JsonMergePatch;
+        pub use json_patch::JsonPatch;
         pub use keys::Keys;
+        pub use ksuid::Ksuid;
         pub use length::Length;
+        pub use levenshtein_distance::LevenshteinDistance;
         pub use log::Log;
+        pub use log10::Log10;
+        pub use log2::Log2;
         pub use map_keys::MapKeys;
+        pub use map_paths::MapPaths;
+        pub use map_schema::MapSchema;
         pub use map_values::MapValues;
         pub use match_any::MatchAny;
         pub use match_array::MatchArray;
         pub use match_datadog_query::MatchDatadogQuery;
+        pub use max::Max;
+        pub use mean::Mean;
+        pub use median::Median;
         pub use merge::Merge;
+        pub use min::Min;
         pub use mod_func::Mod;
+        pub use murmur3::Murmur3;
+        pub use normalize_unicode::NormalizeUnicode;
         pub use now::Now;
         pub use object::Object;
         pub use object_from_array::ObjectFromArray;
+        pub use ocsf_field::OcsfField;
+        pub use pad_left::PadLeft;
+        pub use pad_right::PadRight;
         pub use parse_apache_log::ParseApacheLog;
         pub use parse_aws_alb_log::ParseAwsAlbLog;
         pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
@@ -331,58 +483,88 @@ cfg_if::cfg_if! {
         pub use parse_cbor::ParseCbor;
         pub use parse_cef::ParseCef;
         pub use parse_common_log::ParseCommonLog;
+        pub use parse_cron::ParseCron;
         pub use parse_csv::ParseCsv;
         pub use parse_duration::ParseDuration;
         pub use parse_float::ParseFloat;
-        pub use parse_etld::ParseEtld;
+        pub use parse_etld::{ParseEtld, PublicSuffixList};
         pub use parse_glog::ParseGlog;
+        pub use parse_graphql::ParseGraphql;
         pub use parse_grok::ParseGrok;
-        pub use parse_groks::ParseGroks;
+        pub use parse_groks::{GrokPatternLibraries, ParseGroks};
+        pub use parse_html_meta::ParseHtmlMeta;
         pub use parse_influxdb::ParseInfluxDB;
         pub use parse_int::ParseInt;
+        pub use parse_journald_export::ParseJournaldExport;
         pub use parse_json::ParseJson;
+        pub use parse_jwt::ParseJwt;
         pub use parse_key_value::ParseKeyValue;
         pub use parse_klog::ParseKlog;
+        pub use parse_ksuid::ParseKsuid;
         pub use parse_linux_authorization::ParseLinuxAuthorization;
         pub use parse_logfmt::ParseLogFmt;
+        pub use parse_mac::{OuiLookup, OuiTable, ParseMac};
+        pub use parse_ndjson::ParseNdjson;
         pub use parse_nginx_log::ParseNginxLog;
         pub use parse_proto::ParseProto;
         pub use parse_query_string::ParseQueryString;
         pub use parse_regex::ParseRegex;
         pub use parse_regex_all::ParseRegexAll;
         pub use parse_ruby_hash::ParseRubyHash;
+        pub use parse_semver::ParseSemver;
         pub use parse_syslog::ParseSyslog;
         pub use parse_timestamp::ParseTimestamp;
         pub use parse_tokens::ParseTokens;
+        pub use parse_ulid::ParseUlid;
         pub use parse_url::ParseUrl;
-        pub use parse_user_agent::ParseUserAgent;
+        pub use parse_user_agent::{ParseUserAgent, UserAgentRegexes};
+        pub use parse_uuid::ParseUuid;
+        pub use parse_windows_event::ParseWindowsEvent;
         pub use parse_xml::ParseXml;
+        pub use parse_x509::ParseX509;
+        pub use percentile::Percentile;
+        pub use pow::Pow;
         pub use push::Push;
+        pub use query::Query;
+        pub use query_xml::QueryXml;
         pub use r#match::Match;
         pub use random_bool::RandomBool;
         pub use random_bytes::RandomBytes;
         pub use random_float::RandomFloat;
         pub use random_int::RandomInt;
+        pub use range::Range;
         pub use redact::Redact;
         pub use remove::Remove;
+        pub use remove_diacritics::RemoveDiacritics;
+        pub use repeat::Repeat;
         pub use replace::Replace;
         pub use replace_with::ReplaceWith;
         pub use reverse_dns::ReverseDns;
         pub use round::Round;
+        pub use semver_matches::SemverMatches;
         pub use set::Set;
         pub use sha2::Sha2;
         pub use sha3::Sha3;
         pub use sieve::Sieve;
+        pub use similarity::Similarity;
         pub use slice::Slice;
         pub use split::Split;
+        pub use sqrt::Sqrt;
         pub use starts_with::StartsWith;
+        pub use stddev::Stddev;
         pub use string::String;
         pub use strip_ansi_escape_codes::StripAnsiEscapeCodes;
+        pub use strip_html::StripHtml;
         pub use strip_whitespace::StripWhitespace;
         pub use strlen::Strlen;
+        pub use substring::Substring;
+        pub use sum::Sum;
+        pub use summarize_object::SummarizeObject;
+        pub use sum_window::SumWindow;
         pub use tag_types_externally::TagTypesExternally;
         pub use tally::Tally;
         pub use tally_value::TallyValue;
+        pub use throttle::Throttle;
         pub use timestamp::Timestamp;
         pub use to_bool::ToBool;
         pub use to_float::ToFloat;
@@ -395,14 +577,21 @@ cfg_if::cfg_if! {
         pub use to_unix_timestamp::ToUnixTimestamp;
         pub use truncate::Truncate;
         pub use type_def::TypeDef;
+        pub use ulid::Ulid;
         pub use unflatten::Unflatten;
         pub use unique::Unique;
         pub use unnest::Unnest;
         pub use upcase::Upcase;
+        pub use update_url::UpdateUrl;
         pub use uuid_from_friendly_id::UuidFromFriendlyId;
+        pub use uuid_v3::UuidV3;
         pub use uuid_v4::UuidV4;
+        pub use uuid_v5::UuidV5;
         pub use uuid_v7::UuidV7;
         pub use values::Values;
+        pub use verify_jwt::VerifyJwt;
+        pub use walk::Walk;
+        pub use xxhash64::Xxhash64;
         pub use zip::Zip;
         pub use self::array::Array;
         pub use self::md5::Md5;
@@ -412,27 +601,50 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(feature = "detect_language")]
+mod detect_language;
+#[cfg(feature = "detect_language")]
+pub use detect_language::DetectLanguage;
+
+#[cfg(feature = "format_number_locales")]
+mod number_locales;
+
 #[cfg(feature = "stdlib")]
 #[must_use]
 pub fn all() -> Vec<Box<dyn Function>> {
     vec![
         Box::new(Abs),
         Box::new(Append),
+        Box::new(ApplyPatch),
         Box::new(Array),
+        Box::new(ArrayFill),
         Box::new(Assert),
+        Box::new(AssertApproxEq),
+        Box::new(AssertContains),
         Box::new(AssertEq),
+        Box::new(AssertType),
         Box::new(Boolean),
         Box::new(Camelcase),
         Box::new(Ceil),
         Box::new(Chunks),
+        Box::new(Clamp),
         Box::new(Compact),
+        Box::new(ConsistentSample),
+        Box::new(CompressIpv6),
         Box::new(Contains),
         Box::new(ContainsAll),
+        Box::new(CountDistinctWindow),
         Box::new(Crc),
+        Box::new(Crc32),
+        Box::new(CronNext),
         Box::new(DecodeBase16),
+        Box::new(DecodeBase32),
+        Box::new(DecodeBase58),
         Box::new(DecodeBase64),
+        Box::new(DecodeBrotli),
         Box::new(DecodeCharset),
         Box::new(DecodeGzip),
+        Box::new(DecodeLz4),
         Box::new(DecodePercent),
         Box::new(DecodePunycode),
         Box::new(DecodeMimeQ),
@@ -441,53 +653,75 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(DecodeZstd),
         Box::new(Decrypt),
         Box::new(Del),
+        Box::new(Diff),
         Box::new(DnsLookup),
         Box::new(Downcase),
+        Box::new(EcsField),
         Box::new(EncodeBase16),
+        Box::new(EncodeBase32),
+        Box::new(EncodeBase58),
         Box::new(EncodeBase64),
+        Box::new(EncodeBrotli),
         Box::new(EncodeCharset),
         Box::new(EncodeGzip),
         Box::new(EncodeJson),
         Box::new(EncodeKeyValue),
         Box::new(EncodeLogfmt),
+        Box::new(EncodeLz4),
         Box::new(EncodePercent),
         Box::new(EncodeProto),
         Box::new(EncodePunycode),
+        Box::new(EncodeQueryString),
+        Box::new(EncodeUrl),
         Box::new(EncodeSnappy),
         Box::new(EncodeZlib),
         Box::new(EncodeZstd),
         Box::new(Encrypt),
         Box::new(EndsWith),
+        Box::new(EnvVars),
         Box::new(Exists),
+        Box::new(Exp),
+        Box::new(ExpandIpv6),
+        Box::new(ExpandVariables),
+        Box::new(ExtractAll),
         Box::new(Filter),
         Box::new(Find),
         Box::new(Flatten),
         Box::new(Float),
         Box::new(Floor),
+        Box::new(Fnv1a),
         Box::new(ForEach),
+        Box::new(FormatBytes),
         Box::new(FormatInt),
+        Box::new(FormatMac),
         Box::new(FormatNumber),
         Box::new(FormatTimestamp),
         Box::new(FromUnixTimestamp),
         Box::new(Get),
         Box::new(GetEnvVar),
         Box::new(GetHostname),
+        Box::new(GetJsonPointer),
         Box::new(GetTimezoneName),
         Box::new(Hmac),
         Box::new(Includes),
+        Box::new(IntToIp),
         Box::new(Integer),
         Box::new(IpAton),
         Box::new(IpCidrContains),
+        Box::new(IpInRangeList),
         Box::new(IpNtoa),
         Box::new(IpNtop),
         Box::new(IpPton),
         Box::new(IpSubnet),
+        Box::new(IpToInt),
         Box::new(IpToIpv6),
         Box::new(Ipv6ToIpV4),
         Box::new(IsArray),
+        Box::new(IsAscii),
         Box::new(IsBoolean),
         Box::new(IsEmpty),
         Box::new(IsFloat),
+        Box::new(IsGlobalIp),
         Box::new(IsInteger),
         Box::new(IsIpv4),
         Box::new(IsIpv6),
@@ -495,26 +729,44 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(IsNull),
         Box::new(IsNullish),
         Box::new(IsObject),
+        Box::new(IsPrivateIp),
         Box::new(IsRegex),
         Box::new(IsString),
         Box::new(IsTimestamp),
         Box::new(Join),
+        Box::new(JsonMergePatch),
+        Box::new(JsonPatch),
         Box::new(Kebabcase),
         Box::new(Keys),
+        Box::new(Ksuid),
         Box::new(Length),
+        Box::new(LevenshteinDistance),
         Box::new(Log),
+        Box::new(Log10),
+        Box::new(Log2),
         Box::new(MapKeys),
+        Box::new(MapPaths),
+        Box::new(MapSchema),
         Box::new(MapValues),
         Box::new(Match),
         Box::new(MatchAny),
         Box::new(MatchArray),
         Box::new(MatchDatadogQuery),
+        Box::new(Max),
         Box::new(Md5),
+        Box::new(Mean),
+        Box::new(Median),
         Box::new(Merge),
+        Box::new(Min),
         Box::new(Mod),
+        Box::new(Murmur3),
+        Box::new(NormalizeUnicode),
         Box::new(Now),
         Box::new(Object),
         Box::new(ObjectFromArray),
+        Box::new(OcsfField),
+        Box::new(PadLeft),
+        Box::new(PadRight),
         Box::new(ParseApacheLog),
         Box::new(ParseAwsAlbLog),
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
@@ -523,45 +775,66 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(ParseCbor),
         Box::new(ParseCef),
         Box::new(ParseCommonLog),
+        Box::new(ParseCron),
         Box::new(ParseCsv),
         Box::new(ParseDuration),
         Box::new(ParseFloat),
         Box::new(ParseEtld),
         Box::new(ParseGlog),
+        Box::new(ParseGraphql),
         Box::new(ParseGrok),
         Box::new(ParseGroks),
+        Box::new(ParseHtmlMeta),
         Box::new(ParseInfluxDB),
         Box::new(ParseInt),
+        Box::new(ParseJournaldExport),
         Box::new(ParseJson),
+        Box::new(ParseJwt),
         Box::new(ParseKeyValue),
         Box::new(ParseKlog),
+        Box::new(ParseKsuid),
         Box::new(ParseLinuxAuthorization),
         Box::new(ParseLogFmt),
+        Box::new(ParseMac),
+        Box::new(ParseNdjson),
         Box::new(ParseNginxLog),
         Box::new(ParseProto),
         Box::new(ParseQueryString),
         Box::new(ParseRegex),
         Box::new(ParseRegexAll),
         Box::new(ParseRubyHash),
+        Box::new(ParseSemver),
         Box::new(ParseSyslog),
         Box::new(ParseTimestamp),
         Box::new(ParseTokens),
+        Box::new(ParseUlid),
         Box::new(ParseUrl),
         Box::new(ParseUserAgent),
+        Box::new(ParseUuid),
+        Box::new(ParseWindowsEvent),
         Box::new(ParseXml),
+        Box::new(ParseX509),
+        Box::new(Percentile),
+        Box::new(Pow),
         Box::new(Pascalcase),
         Box::new(Push),
+        Box::new(Query),
+        Box::new(QueryXml),
         Box::new(RandomBool),
         Box::new(RandomBytes),
         Box::new(RandomFloat),
         Box::new(RandomInt),
+        Box::new(Range),
         Box::new(Redact),
         Box::new(Remove),
+        Box::new(RemoveDiacritics),
+        Box::new(Repeat),
         Box::new(Replace),
         Box::new(ReplaceWith),
         Box::new(ReverseDns),
         Box::new(Round),
         Box::new(Seahash),
+        Box::new(SemverMatches),
         Box::new(Set),
         Box::new(Sha1),
         Box::new(Sha2),
@@ -569,16 +842,25 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(Sieve),
         Box::new(ScreamingSnakecase),
         Box::new(Snakecase),
+        Box::new(Similarity),
         Box::new(Slice),
         Box::new(Split),
+        Box::new(Sqrt),
         Box::new(StartsWith),
+        Box::new(Stddev),
         Box::new(String),
         Box::new(StripAnsiEscapeCodes),
+        Box::new(StripHtml),
         Box::new(StripWhitespace),
         Box::new(Strlen),
+        Box::new(Substring),
+        Box::new(Sum),
+        Box::new(SummarizeObject),
+        Box::new(SumWindow),
         Box::new(Tally),
         Box::new(TallyValue),
         Box::new(TagTypesExternally),
+        Box::new(Throttle),
         Box::new(Timestamp),
         Box::new(ToBool),
         Box::new(ToFloat),
@@ -592,14 +874,37 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(CommunityID),
         Box::new(Truncate),
         Box::new(TypeDef),
+        Box::new(Ulid),
         Box::new(Unflatten),
         Box::new(Unique),
         Box::new(Unnest),
         Box::new(Upcase),
+        Box::new(UpdateUrl),
         Box::new(UuidFromFriendlyId),
+        Box::new(UuidV3),
         Box::new(UuidV4),
+        Box::new(UuidV5),
         Box::new(UuidV7),
         Box::new(Values),
+        Box::new(VerifyJwt),
+        Box::new(Walk),
+        Box::new(Xxhash64),
         Box::new(Zip),
+        #[cfg(feature = "detect_language")]
+        Box::new(DetectLanguage),
     ]
 }
+
+/// Returns the [signatures](crate::compiler::function::signature::Signature)
+/// of all stdlib functions as a JSON array, for use by documentation sites and
+/// editor integrations.
+#[cfg(feature = "stdlib")]
+#[must_use]
+pub fn signatures() -> serde_json::Value {
+    let signatures = all()
+        .iter()
+        .map(|function| function.signature())
+        .collect::<Vec<_>>();
+
+    serde_json::to_value(signatures).expect("signatures serialize to JSON")
+}
@@ -0,0 +1,149 @@
+use crate::compiler::prelude::*;
+
+use super::hash_util::{self, encodings, seed32, HashEncoding};
+
+fn murmur3(value: Value, seed: Value, encoding: HashEncoding) -> Resolved {
+    let value = value.try_bytes()?;
+    let seed = seed32(seed)?;
+
+    Ok(encoding.encode32(hash_util::murmur3(&value, seed)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Murmur3;
+
+impl Function for Murmur3 {
+    fn identifier(&self) -> &'static str {
+        "murmur3"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "encoding",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "murmur3",
+                source: r#"murmur3("foo")"#,
+                result: Ok("4138058784"),
+            },
+            Example {
+                title: "hex encoding",
+                source: r#"murmur3("foo", encoding: "hex")"#,
+                result: Ok("f6a5c420"),
+            },
+            Example {
+                title: "seed",
+                source: r#"murmur3("foo", seed: 42)"#,
+                result: Ok("2972666014"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let seed = arguments.optional("seed").unwrap_or_else(|| expr!(0));
+        let encoding = arguments
+            .optional_enum("encoding", &encodings(), state)?
+            .map_or(HashEncoding::Integer, |encoding| {
+                HashEncoding::from_bytes(&encoding.try_bytes().expect("encoding not bytes"))
+            });
+
+        let valid_seed = seed
+            .resolve_constant(state)
+            .is_some_and(|seed| seed32(seed).is_ok());
+        Ok(Murmur3Fn {
+            value,
+            seed,
+            encoding,
+            valid_seed,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Murmur3Fn {
+    value: Box<dyn Expression>,
+    seed: Box<dyn Expression>,
+    encoding: HashEncoding,
+    valid_seed: bool,
+}
+
+impl FunctionExpression for Murmur3Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let seed = self.seed.resolve(ctx)?;
+
+        murmur3(value, seed, self.encoding)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        self.encoding.type_def().maybe_fallible(!self.valid_seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        murmur3 => Murmur3;
+
+        integer {
+            args: func_args![value: "foo"],
+            want: Ok(4_138_058_784_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        hex {
+            args: func_args![value: "foobar", encoding: "hex"],
+            want: Ok("a4c4d4bd"),
+            tdef: TypeDef::bytes(),
+        }
+
+        seed {
+            args: func_args![value: "foo", seed: 42],
+            want: Ok(2_972_666_014_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(0),
+            tdef: TypeDef::integer(),
+        }
+
+        invalid_seed {
+            args: func_args![value: "foo", seed: -1],
+            want: Err("seed must be between 0 and 4294967295, got -1"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
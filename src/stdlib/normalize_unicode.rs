@@ -0,0 +1,158 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::compiler::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Form {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl Form {
+    const ALL: [Self; 4] = [Self::Nfc, Self::Nfd, Self::Nfkc, Self::Nfkd];
+
+    fn all_value() -> Vec<Value> {
+        Self::ALL.iter().map(|form| form.as_str().into()).collect()
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Nfc => "NFC",
+            Self::Nfd => "NFD",
+            Self::Nfkc => "NFKC",
+            Self::Nfkd => "NFKD",
+        }
+    }
+}
+
+fn normalize_unicode(value: Value, form: Form) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    let normalized: String = match form {
+        Form::Nfc => value.nfc().collect(),
+        Form::Nfd => value.nfd().collect(),
+        Form::Nfkc => value.nfkc().collect(),
+        Form::Nfkd => value.nfkd().collect(),
+    };
+
+    Ok(normalized.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeUnicode;
+
+impl Function for NormalizeUnicode {
+    fn identifier(&self) -> &'static str {
+        "normalize_unicode"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "form",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "compose",
+                source: r#"normalize_unicode("café") == "café""#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "compatibility",
+                source: r#"normalize_unicode("ﬁle①", form: "NFKC")"#,
+                result: Ok("file1"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let form = arguments
+            .optional_enum("form", &Form::all_value(), state)?
+            .map_or(Form::Nfc, |value| {
+                Form::ALL
+                    .into_iter()
+                    .find(|form| value == Value::from(form.as_str()))
+                    .expect("validated enum")
+            });
+        Ok(NormalizeUnicodeFn { value, form }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeUnicodeFn {
+    value: Box<dyn Expression>,
+    form: Form,
+}
+
+impl FunctionExpression for NormalizeUnicodeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        normalize_unicode(value, self.form)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        normalize_unicode => NormalizeUnicode;
+
+        nfc {
+            args: func_args![value: "e\u{301}"],
+            want: Ok("\u{e9}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfd {
+            args: func_args![value: "\u{e9}", form: "NFD"],
+            want: Ok("e\u{301}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfkc {
+            args: func_args![value: "\u{fb01}\u{2460}", form: "NFKC"],
+            want: Ok("fi1"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfkd {
+            args: func_args![value: "\u{1e9b}\u{323}", form: "NFKD"],
+            want: Ok("s\u{323}\u{307}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_form {
+            args: func_args![value: "a", form: "nfc"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,140 @@
+//! Number and currency formatting conventions of common locales, derived from
+//! the CLDR, used by `format_number`. The tables are embedded so that numbers
+//! are formatted the same on every platform.
+
+/// The number formatting conventions of a locale.
+#[derive(Debug)]
+pub(crate) struct NumberLocale {
+    pub(crate) name: &'static str,
+    pub(crate) decimal_separator: &'static str,
+    pub(crate) grouping_separator: &'static str,
+
+    /// The size of the group closest to the decimal separator.
+    pub(crate) primary_group: usize,
+
+    /// The size of the other groups, which differs in Indian locales.
+    pub(crate) secondary_group: usize,
+
+    /// The minimum number of digits in the leftmost group for the number to
+    /// be grouped at all, so that `1234` isn't grouped with a value of 2.
+    pub(crate) min_grouping: usize,
+
+    pub(crate) minus_sign: &'static str,
+
+    /// Where the currency symbol (`¤`) goes relative to the number (`#`).
+    pub(crate) currency_pattern: &'static str,
+
+    /// The code and symbol of the currency of the locale, which can differ from
+    /// its symbol in other locales.
+    pub(crate) local_currency: (&'static str, &'static str),
+}
+
+/// The symbol of a currency and the number of decimals of its amounts.
+#[derive(Debug)]
+pub(crate) struct Currency {
+    pub(crate) code: &'static str,
+    pub(crate) symbol: &'static str,
+    pub(crate) digits: i64,
+}
+
+const NBSP: &str = "\u{a0}";
+const NNBSP: &str = "\u{202f}";
+
+macro_rules! locale {
+    ($name:literal, $decimal:expr, $group:expr, $primary:literal/$secondary:literal, $min:literal, $minus:literal, $pattern:literal, $currency:literal => $symbol:literal) => {
+        NumberLocale {
+            name: $name,
+            decimal_separator: $decimal,
+            grouping_separator: $group,
+            primary_group: $primary,
+            secondary_group: $secondary,
+            min_grouping: $min,
+            minus_sign: $minus,
+            currency_pattern: $pattern,
+            local_currency: ($currency, $symbol),
+        }
+    };
+}
+
+pub(crate) const LOCALES: &[NumberLocale] = &[
+    locale!("da-DK", ",", ".", 3/3, 1, "-", "#\u{a0}¤", "DKK" => "kr."),
+    locale!("de-AT", ",", NBSP, 3/3, 1, "-", "¤\u{a0}#", "EUR" => "€"),
+    locale!("de-CH", ".", "’", 3/3, 1, "-", "¤\u{a0}#", "CHF" => "CHF"),
+    locale!("de-DE", ",", ".", 3/3, 1, "-", "#\u{a0}¤", "EUR" => "€"),
+    locale!("en-AU", ".", ",", 3/3, 1, "-", "¤#", "AUD" => "$"),
+    locale!("en-CA", ".", ",", 3/3, 1, "-", "¤#", "CAD" => "$"),
+    locale!("en-GB", ".", ",", 3/3, 1, "-", "¤#", "GBP" => "£"),
+    locale!("en-IN", ".", ",", 3/2, 1, "-", "¤#", "INR" => "₹"),
+    locale!("en-US", ".", ",", 3/3, 1, "-", "¤#", "USD" => "$"),
+    locale!("es-ES", ",", ".", 3/3, 2, "-", "#\u{a0}¤", "EUR" => "€"),
+    locale!("es-MX", ".", ",", 3/3, 1, "-", "¤#", "MXN" => "$"),
+    locale!("fi-FI", ",", NBSP, 3/3, 1, "\u{2212}", "#\u{a0}¤", "EUR" => "€"),
+    locale!("fr-CA", ",", NBSP, 3/3, 1, "-", "#\u{a0}¤", "CAD" => "$"),
+    locale!("fr-CH", ",", NNBSP, 3/3, 1, "-", "#\u{a0}¤", "CHF" => "CHF"),
+    locale!("fr-FR", ",", NNBSP, 3/3, 1, "-", "#\u{a0}¤", "EUR" => "€"),
+    locale!("hi-IN", ".", ",", 3/2, 1, "-", "¤#", "INR" => "₹"),
+    locale!("it-IT", ",", ".", 3/3, 1, "-", "#\u{a0}¤", "EUR" => "€"),
+    locale!("ja-JP", ".", ",", 3/3, 1, "-", "¤#", "JPY" => "￥"),
+    locale!("ko-KR", ".", ",", 3/3, 1, "-", "¤#", "KRW" => "₩"),
+    locale!("nb-NO", ",", NBSP, 3/3, 1, "\u{2212}", "#\u{a0}¤", "NOK" => "kr"),
+    locale!("nl-NL", ",", ".", 3/3, 1, "-", "¤\u{a0}#", "EUR" => "€"),
+    locale!("pl-PL", ",", NBSP, 3/3, 2, "-", "#\u{a0}¤", "PLN" => "zł"),
+    locale!("pt-BR", ",", ".", 3/3, 1, "-", "¤\u{a0}#", "BRL" => "R$"),
+    locale!("pt-PT", ",", NBSP, 3/3, 2, "-", "#\u{a0}¤", "EUR" => "€"),
+    locale!("ru-RU", ",", NBSP, 3/3, 1, "-", "#\u{a0}¤", "RUB" => "₽"),
+    locale!("sv-SE", ",", NBSP, 3/3, 1, "\u{2212}", "#\u{a0}¤", "SEK" => "kr"),
+    locale!("tr-TR", ",", ".", 3/3, 1, "-", "¤#", "TRY" => "₺"),
+    locale!("zh-CN", ".", ",", 3/3, 1, "-", "¤#", "CNY" => "¥"),
+];
+
+/// The currencies, with their symbols in English. Locales use their own symbol
+/// for their local currency.
+pub(crate) const CURRENCIES: &[Currency] = &[
+    Currency::new("AUD", "A$", 2),
+    Currency::new("BRL", "R$", 2),
+    Currency::new("CAD", "CA$", 2),
+    Currency::new("CHF", "CHF", 2),
+    Currency::new("CNY", "CN¥", 2),
+    Currency::new("DKK", "DKK", 2),
+    Currency::new("EUR", "€", 2),
+    Currency::new("GBP", "£", 2),
+    Currency::new("INR", "₹", 2),
+    Currency::new("JPY", "¥", 0),
+    Currency::new("KRW", "₩", 0),
+    Currency::new("MXN", "MX$", 2),
+    Currency::new("NOK", "NOK", 2),
+    Currency::new("PLN", "PLN", 2),
+    Currency::new("RUB", "RUB", 2),
+    Currency::new("SEK", "SEK", 2),
+    Currency::new("TRY", "TRY", 2),
+    Currency::new("USD", "$", 2),
+];
+
+pub(crate) fn locale(name: &str) -> Option<&'static NumberLocale> {
+    LOCALES.iter().find(|locale| locale.name == name)
+}
+
+pub(crate) fn currency(code: &str) -> Option<&'static Currency> {
+    CURRENCIES.iter().find(|currency| currency.code == code)
+}
+
+impl Currency {
+    const fn new(code: &'static str, symbol: &'static str, digits: i64) -> Self {
+        Self {
+            code,
+            symbol,
+            digits,
+        }
+    }
+}
+
+impl NumberLocale {
+    /// The symbol of a currency in this locale.
+    pub(crate) fn currency_symbol(&self, currency: &Currency) -> &'static str {
+        if self.local_currency.0 == currency.code {
+            self.local_currency.1
+        } else {
+            currency.symbol
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+
+use super::schema_util::{field_info, field_info_kind, ocsf_class_uid, ocsf_field_type};
+
+fn ocsf_field(class: Value, name: Value) -> Resolved {
+    let class = class.try_bytes_utf8_lossy()?;
+    let name = name.try_bytes_utf8_lossy()?;
+
+    let (class_uid, kind) =
+        ocsf_field_type(&class, &name).ok_or_else(|| format!("unknown OCSF class: {class}"))?;
+    let kind = kind.ok_or_else(|| format!("unknown attribute for OCSF class {class}: {name}"))?;
+
+    let mut info = field_info(&name, kind);
+    info.insert("class_uid".into(), class_uid.into());
+
+    Ok(info.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct OcsfField;
+
+impl Function for OcsfField {
+    fn identifier(&self) -> &'static str {
+        "ocsf_field"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "class",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "name",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "class attribute",
+                source: r#"ocsf_field("network_activity", "src_endpoint.ip")"#,
+                result: Ok(
+                    r#"{"class_uid": 4001, "name": "src_endpoint.ip", "path": ["src_endpoint", "ip"], "type": "ip_t"}"#,
+                ),
+            },
+            Example {
+                title: "shared attribute",
+                source: r#"ocsf_field("dns_activity", "severity_id")"#,
+                result: Ok(
+                    r#"{"class_uid": 4003, "name": "severity_id", "path": ["severity_id"], "type": "integer_t"}"#,
+                ),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let class = arguments.required("class");
+        let name = arguments.required("name");
+
+        if let Some(value) = class.resolve_constant(state) {
+            let known = value
+                .as_str()
+                .is_some_and(|class| ocsf_class_uid(&class).is_some());
+            if !known {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "class",
+                    value,
+                    error: "unknown OCSF class",
+                }
+                .into());
+            }
+        }
+
+        if let (Some(class), Some(value)) =
+            (class.resolve_constant(state), name.resolve_constant(state))
+        {
+            if ocsf_field(class, value.clone()).is_err() {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "name",
+                    value,
+                    error: "unknown OCSF attribute",
+                }
+                .into());
+            }
+        }
+
+        Ok(OcsfFieldFn { class, name }.as_expr())
+    }
+}
+
+fn ocsf_field_kind() -> BTreeMap<Field, Kind> {
+    let mut kind = field_info_kind();
+    kind.insert("class_uid".into(), Kind::integer());
+    kind
+}
+
+#[derive(Debug, Clone)]
+struct OcsfFieldFn {
+    class: Box<dyn Expression>,
+    name: Box<dyn Expression>,
+}
+
+impl FunctionExpression for OcsfFieldFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let class = self.class.resolve(ctx)?;
+        let name = self.name.resolve(ctx)?;
+
+        ocsf_field(class, name)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // Constant arguments are checked when the call is compiled.
+        let constant = self.class.resolve_constant(state).is_some()
+            && self.name.resolve_constant(state).is_some();
+
+        TypeDef::object(ocsf_field_kind()).maybe_fallible(!constant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        ocsf_field => OcsfField;
+
+        class_attribute {
+            args: func_args![class: "http_activity", name: "http_request.url.url_string"],
+            want: Ok(value!({
+                "class_uid": 4002,
+                "name": "http_request.url.url_string",
+                "path": ["http_request", "url", "url_string"],
+                "type": "url_t",
+            })),
+            tdef: TypeDef::object(ocsf_field_kind()),
+        }
+
+        shared_attribute {
+            args: func_args![class: "authentication", name: "metadata.product.name"],
+            want: Ok(value!({
+                "class_uid": 3002,
+                "name": "metadata.product.name",
+                "path": ["metadata", "product", "name"],
+                "type": "string_t",
+            })),
+            tdef: TypeDef::object(ocsf_field_kind()),
+        }
+
+        unknown_class {
+            args: func_args![class: "network_activities", name: "src_endpoint.ip"],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(field_info_kind()).fallible(),
+        }
+
+        attribute_of_other_class {
+            args: func_args![class: "dns_activity", name: "http_request.http_method"],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(field_info_kind()).fallible(),
+        }
+    ];
+}
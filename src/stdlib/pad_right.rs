@@ -0,0 +1,139 @@
+use crate::compiler::prelude::*;
+
+use super::string_utils::{pad_graphemes, pad_length};
+
+fn pad_right(value: Value, length: Value, pad: Value) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    let length = pad_length(length.try_integer()?)?;
+    let pad = pad.try_bytes_utf8_lossy()?;
+
+    Ok(pad_graphemes(&value, length, &pad, false).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PadRight;
+
+impl Function for PadRight {
+    fn identifier(&self) -> &'static str {
+        "pad_right"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "length",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "pad",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "pad with spaces",
+                source: r#"pad_right("42", 4)"#,
+                result: Ok("s'42  '"),
+            },
+            Example {
+                title: "pad with zeros",
+                source: r#"pad_right("42", 5, pad: "0")"#,
+                result: Ok("s'42000'"),
+            },
+            Example {
+                title: "pad with a longer string",
+                source: r#"pad_right("1", 6, pad: "ab")"#,
+                result: Ok("1ababa"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let length = arguments.required("length");
+        let pad = arguments.optional("pad").unwrap_or(expr!(" "));
+
+        if let Some(length) = length.resolve_constant(state).and_then(|v| v.as_integer()) {
+            if let Err(error) = pad_length(length) {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "length",
+                    value: length.into(),
+                    error,
+                }
+                .into());
+            }
+        }
+
+        Ok(PadRightFn { value, length, pad }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PadRightFn {
+    value: Box<dyn Expression>,
+    length: Box<dyn Expression>,
+    pad: Box<dyn Expression>,
+}
+
+impl FunctionExpression for PadRightFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let length = self.length.resolve(ctx)?;
+        let pad = self.pad.resolve(ctx)?;
+
+        pad_right(value, length, pad)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // Only a length known at compile time is known to be within the limit.
+        TypeDef::bytes().maybe_fallible(self.length.resolve_constant(state).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        pad_right => PadRight;
+
+        spaces {
+            args: func_args![value: "abc", length: 5],
+            want: Ok("abc  "),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        partial_pad {
+            args: func_args![value: "abc", length: 6, pad: "xy"],
+            want: Ok("abcxyx"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        graphemes {
+            args: func_args![value: "e\u{301}", length: 3, pad: "ñ"],
+            want: Ok("e\u{301}ññ"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        too_long {
+            args: func_args![value: "abc", length: 1_000_001],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
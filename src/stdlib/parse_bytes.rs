@@ -6,7 +6,7 @@ use parse_size::Config;
 use rust_decimal::{prelude::FromPrimitive, prelude::ToPrimitive, Decimal};
 use std::collections::HashMap;
 
-fn parse_bytes(bytes: Value, unit: Value, base: &Bytes) -> Resolved {
+fn parse_bytes(bytes: Value, unit: Option<Value>, base: &Bytes) -> Resolved {
     let (units, parse_config) = match base.as_ref() {
         b"2" => (&*BIN_UNITS, Config::new().with_binary()),
         b"10" => (&*DEC_UNITS, Config::new().with_decimal()),
@@ -15,6 +15,16 @@ fn parse_bytes(bytes: Value, unit: Value, base: &Bytes) -> Resolved {
     let bytes = bytes.try_bytes()?;
     let value = String::from_utf8_lossy(&bytes);
     let value: &str = value.as_ref();
+    let value = parse_config
+        .parse_size(value)
+        .map_err(|e| format!("unable to parse bytes: '{e}'"))?;
+
+    // Without a unit, the size is returned as a whole number of bytes.
+    let Some(unit) = unit else {
+        return i64::try_from(value)
+            .map(Value::from)
+            .map_err(|_| format!("unable to parse bytes: '{value}' is too large").into());
+    };
     let conversion_factor = {
         let bytes = unit.try_bytes()?;
         let string = String::from_utf8_lossy(&bytes);
@@ -23,9 +33,6 @@ fn parse_bytes(bytes: Value, unit: Value, base: &Bytes) -> Resolved {
             .get(string.as_ref())
             .ok_or(format!("unknown unit format: '{string}'"))?
     };
-    let value = parse_config
-        .parse_size(value)
-        .map_err(|e| format!("unable to parse bytes: '{e}'"))?;
     let value = Decimal::from_u64(value).ok_or(format!("unable to parse number: {value}"))?;
     let number = value / conversion_factor;
     let number = number
@@ -102,6 +109,11 @@ impl Function for ParseBytes {
                 source: r#"parse_bytes!("1GB", unit: "MB", base: "2")"#,
                 result: Ok("1024.0"),
             },
+            Example {
+                title: "number of bytes",
+                source: r#"parse_bytes!("1.5GiB")"#,
+                result: Ok("1610612736"),
+            },
         ]
     }
 
@@ -112,7 +124,7 @@ impl Function for ParseBytes {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let unit = arguments.required("unit");
+        let unit = arguments.optional("unit");
         let base = arguments
             .optional_enum("base", &base_sets(), state)?
             .unwrap_or_else(|| value!("2"))
@@ -132,7 +144,7 @@ impl Function for ParseBytes {
             Parameter {
                 keyword: "unit",
                 kind: kind::BYTES,
-                required: true,
+                required: false,
             },
             Parameter {
                 keyword: "base",
@@ -146,20 +158,27 @@ impl Function for ParseBytes {
 #[derive(Debug, Clone)]
 struct ParseBytesFn {
     value: Box<dyn Expression>,
-    unit: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
     base: Bytes,
 }
 
 impl FunctionExpression for ParseBytesFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let bytes = self.value.resolve(ctx)?;
-        let unit = self.unit.resolve(ctx)?;
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|unit| unit.resolve(ctx))
+            .transpose()?;
 
         parse_bytes(bytes, unit, &self.base)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::float().fallible()
+        match self.unit {
+            Some(_) => TypeDef::float().fallible(),
+            None => TypeDef::integer().fallible(),
+        }
     }
 }
 
@@ -282,6 +301,25 @@ mod tests {
             tdef: TypeDef::float().fallible(),
         }
 
+        no_unit {
+            args: func_args![value: "1.5GiB"],
+            want: Ok(1_610_612_736),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        no_unit_decimal {
+            args: func_args![value: "200MB",
+                             base: "10"],
+            want: Ok(200_000_000),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        no_unit_too_large {
+            args: func_args![value: "9EiB"],
+            want: Err("unable to parse bytes: '10376293541461622784' is too large"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
         error_format {
             args: func_args![value: "100KB",
                              unit: "ZB",
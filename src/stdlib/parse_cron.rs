@@ -0,0 +1,337 @@
+use crate::compiler::prelude::*;
+use std::collections::BTreeMap;
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAYS_OF_WEEK: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// The values of a field of a cron expression, as a bit set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CronField {
+    bits: u64,
+}
+
+impl CronField {
+    pub(crate) fn contains(self, value: u32) -> bool {
+        self.bits & (1 << value) != 0
+    }
+
+    fn values(self) -> impl Iterator<Item = u32> {
+        (0..64).filter(move |value| self.contains(*value))
+    }
+
+    /// Parses a comma separated list of values, ranges (`1-5`), wildcards
+    /// (`*`) and steps (`*/15`, `10-30/5`), which can use the given names.
+    fn parse(field: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<Self, String> {
+        let parse_value = |value: &str| -> Result<u32, String> {
+            let position = names
+                .iter()
+                .position(|candidate| candidate.eq_ignore_ascii_case(value));
+            let parsed = match position {
+                Some(position) => Ok(position as u32 + min),
+                None => value.parse::<u32>(),
+            };
+            match parsed {
+                Ok(parsed) if (min..=max).contains(&parsed) => Ok(parsed),
+                _ => Err(format!(
+                    "invalid {name} field: {value} is not in the range {min}-{max}"
+                )),
+            }
+        };
+
+        let mut bits = 0;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => match step.parse::<u32>() {
+                    Ok(step) if step > 0 => (range, step),
+                    _ => return Err(format!("invalid {name} field: invalid step {step}")),
+                },
+                None => (part, 1),
+            };
+
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (min, max),
+                // Days of the week have a name for all but their maximum,
+                // which is Sunday again, so a range such as `sat-sun` ends on it.
+                Some((start, end))
+                    if names.len() as u32 == max - min
+                        && names[0].eq_ignore_ascii_case(end) =>
+                {
+                    (parse_value(start)?, max)
+                }
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                // A single value with a step, such as `5/15`, runs until the maximum.
+                None if part.contains('/') => (parse_value(range)?, max),
+                None => {
+                    let value = parse_value(range)?;
+                    (value, value)
+                }
+            };
+            if start > end {
+                return Err(format!("invalid {name} field: {range} is an empty range"));
+            }
+
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+
+        Ok(Self { bits })
+    }
+}
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Schedule {
+    pub(crate) minutes: CronField,
+    pub(crate) hours: CronField,
+    pub(crate) days_of_month: CronField,
+    pub(crate) months: CronField,
+    /// From 0 for Sunday to 6 for Saturday.
+    pub(crate) days_of_week: CronField,
+
+    /// Whether the day of the month or of the week are restricted. If both are,
+    /// the schedule runs on the days matching either of them.
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+impl Schedule {
+    /// Parses a cron expression with five fields, for the minute, hour, day of
+    /// the month, month and day of the week, or a macro such as `@daily`.
+    pub(crate) fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields in the cron expression, got {}",
+                fields.len()
+            ));
+        };
+
+        // Sunday is both 0 and 7.
+        let mut days = CronField::parse(days_of_week, "day of week", 0, 7, DAYS_OF_WEEK)?;
+        if days.contains(7) {
+            days.bits = (days.bits | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: CronField::parse(minutes, "minute", 0, 59, &[])?,
+            hours: CronField::parse(hours, "hour", 0, 23, &[])?,
+            days_of_month: CronField::parse(days_of_month, "day of month", 1, 31, &[])?,
+            months: CronField::parse(months, "month", 1, 12, MONTHS)?,
+            days_of_week: days,
+            days_of_month_restricted: !days_of_month.starts_with('*'),
+            days_of_week_restricted: !days_of_week.starts_with('*'),
+        })
+    }
+
+    /// Returns whether the schedule runs on the given day, with the day of the
+    /// week from 0 for Sunday.
+    pub(crate) fn runs_on(&self, day_of_month: u32, day_of_week: u32) -> bool {
+        let day_of_month = self.days_of_month.contains(day_of_month);
+        let day_of_week = self.days_of_week.contains(day_of_week);
+
+        match (self.days_of_month_restricted, self.days_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        }
+    }
+}
+
+fn parse_cron(value: Value) -> Resolved {
+    let expression = value.try_bytes_utf8_lossy()?;
+
+    let mut map = BTreeMap::<&str, Value>::new();
+    match Schedule::parse(&expression) {
+        Ok(schedule) => {
+            let values = |field: CronField| field.values().map(Value::from).collect::<Vec<_>>();
+
+            map.insert("valid", true.into());
+            map.insert("minute", values(schedule.minutes).into());
+            map.insert("hour", values(schedule.hours).into());
+            map.insert("day_of_month", values(schedule.days_of_month).into());
+            map.insert("month", values(schedule.months).into());
+            map.insert("day_of_week", values(schedule.days_of_week).into());
+        }
+        Err(error) => {
+            map.insert("valid", false.into());
+            map.insert("error", error.into());
+        }
+    }
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCron;
+
+impl Function for ParseCron {
+    fn identifier(&self) -> &'static str {
+        "parse_cron"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid expression",
+                source: r#"parse_cron("30 9 * * mon-fri")"#,
+                result: Ok(indoc! {r#"{
+                    "day_of_month": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+                    "day_of_week": [1, 2, 3, 4, 5],
+                    "hour": [9],
+                    "minute": [30],
+                    "month": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+                    "valid": true
+                }"#}),
+            },
+            Example {
+                title: "invalid expression",
+                source: r#"parse_cron("61 * * * *")"#,
+                result: Ok(
+                    r#"{ "error": "invalid minute field: 61 is not in the range 0-59", "valid": false }"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseCronFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCronFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseCronFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_cron(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).infallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    let values = || Kind::array(Collection::from_unknown(Kind::integer())).or_undefined();
+
+    BTreeMap::from([
+        ("valid".into(), Kind::boolean()),
+        ("error".into(), Kind::bytes().or_undefined()),
+        ("minute".into(), values()),
+        ("hour".into(), values()),
+        ("day_of_month".into(), values()),
+        ("month".into(), values()),
+        ("day_of_week".into(), values()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_cron => ParseCron;
+
+        lists_and_steps {
+            args: func_args![value: "*/20 0,12 1-3 jan,JUL sat-sun"],
+            want: Ok(value!({
+                valid: true,
+                minute: [0, 20, 40],
+                hour: [0, 12],
+                day_of_month: [1, 2, 3],
+                month: [1, 7],
+                day_of_week: [0, 6],
+            })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+
+        sunday_as_seven {
+            args: func_args![value: "0 0 * * 5-7"],
+            want: Ok(value!({
+                valid: true,
+                minute: [0],
+                hour: [0],
+                day_of_month: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+                month: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+                day_of_week: [0, 5, 6],
+            })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+
+        too_few_fields {
+            args: func_args![value: "* * * *"],
+            want: Ok(value!({
+                valid: false,
+                error: "expected 5 fields in the cron expression, got 4",
+            })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+
+        invalid_step {
+            args: func_args![value: "*/0 * * * *"],
+            want: Ok(value!({
+                valid: false,
+                error: "invalid minute field: invalid step 0",
+            })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+
+        empty_range {
+            args: func_args![value: "* 5-2 * * *"],
+            want: Ok(value!({
+                valid: false,
+                error: "invalid hour field: 5-2 is an empty range",
+            })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+    ];
+
+    #[test]
+    fn value_with_step() {
+        let schedule = Schedule::parse("50/5 * * * *").unwrap();
+
+        assert_eq!(schedule.minutes.values().collect::<Vec<_>>(), [50, 55]);
+    }
+
+    #[test]
+    fn macros() {
+        assert_eq!(Schedule::parse("@daily"), Schedule::parse("0 0 * * *"));
+        assert_eq!(Schedule::parse("@annually"), Schedule::parse("0 0 1 1 *"));
+    }
+}
@@ -1,8 +1,83 @@
 use psl::Psl;
+use psl_types::{Suffix, Type};
 use publicsuffix::List;
 
 use crate::compiler::prelude::*;
-use std::{collections::BTreeMap, path::Path};
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+/// A public suffix list used by `parse_etld` instead of the bundled one, in the
+/// format of the `public_suffix_list.dat` file. Register it with
+/// [`CompileConfig::set_custom`], for example to add internal top-level domains.
+///
+/// Clones share the list, so replacing it with [`PublicSuffixList::replace`]
+/// updates programs that are already compiled, without compiling them again.
+///
+/// [`CompileConfig::set_custom`]: crate::compiler::CompileConfig::set_custom
+#[derive(Clone)]
+pub struct PublicSuffixList(Arc<RwLock<Arc<List>>>);
+
+impl PublicSuffixList {
+    /// Loads the list from the contents of a `public_suffix_list.dat` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list isn't valid UTF-8, or contains an invalid
+    /// rule.
+    pub fn from_bytes(list: &[u8]) -> io::Result<Self> {
+        let list = List::from_bytes(list)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+
+        Ok(Self(Arc::new(RwLock::new(Arc::new(list)))))
+    }
+
+    /// Loads the list from a reader, such as a `public_suffix_list.dat` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader fails, or the list isn't valid.
+    pub fn from_reader(mut reader: impl io::Read) -> io::Result<Self> {
+        let mut list = Vec::new();
+        reader.read_to_end(&mut list)?;
+        Self::from_bytes(&list)
+    }
+
+    /// Replaces the list with `other`, for every clone of this list.
+    pub fn replace(&self, other: &Self) {
+        let list = other.current();
+        *self.0.write().expect("lock poisoned") = list;
+    }
+
+    fn current(&self) -> Arc<List> {
+        Arc::clone(&self.0.read().expect("lock poisoned"))
+    }
+}
+
+impl fmt::Debug for PublicSuffixList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("PublicSuffixList")
+    }
+}
+
+/// Finds the suffix of `name`, skipping private suffixes, such as `github.io`,
+/// unless `include_private` is set.
+fn find_suffix<'a>(list: &impl Psl, name: &'a [u8], include_private: bool) -> Option<Suffix<'a>> {
+    let mut suffix = list.suffix(name)?;
+
+    // private suffixes are registered below a public one, so the public suffix
+    // is found by dropping their leftmost labels
+    while !include_private && suffix.typ() == Some(Type::Private) {
+        let bytes = suffix.as_bytes();
+        let dot = bytes.iter().position(|&byte| byte == b'.')?;
+        suffix = list.suffix(&bytes[dot + 1..])?;
+    }
+
+    Some(suffix)
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct ParseEtld;
@@ -29,6 +104,11 @@ impl Function for ParseEtld {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "include_private",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -67,17 +147,32 @@ impl Function for ParseEtld {
                 }
             "#}),
             },
+            Example {
+                title: "parse etld without private suffixes",
+                source: r#"parse_etld!("vector.github.io", plus_parts: 1, include_private: false)"#,
+                result: Ok(indoc! {r#"
+                {
+                    "etld": "io",
+                    "etld_plus": "github.io",
+                    "known_suffix": true
+                }
+            "#}),
+            },
         ]
     }
 
     fn compile(
         &self,
         state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
         let plus_parts = arguments.optional("plus_parts").unwrap_or_else(|| expr!(0));
+        let include_private = arguments
+            .optional("include_private")
+            .unwrap_or_else(|| expr!(true));
+        let custom_psl = ctx.get_external_context::<PublicSuffixList>().cloned();
 
         let psl_expr = arguments.optional_expr("psl");
         let mut psl: Option<List> = None;
@@ -117,7 +212,9 @@ impl Function for ParseEtld {
         Ok(ParseEtldFn {
             value,
             plus_parts,
+            include_private,
             psl,
+            custom_psl,
         }
         .as_expr())
     }
@@ -127,7 +224,9 @@ impl Function for ParseEtld {
 struct ParseEtldFn {
     value: Box<dyn Expression>,
     plus_parts: Box<dyn Expression>,
+    include_private: Box<dyn Expression>,
     psl: Option<List>,
+    custom_psl: Option<PublicSuffixList>,
 }
 
 impl FunctionExpression for ParseEtldFn {
@@ -140,10 +239,15 @@ impl FunctionExpression for ParseEtldFn {
             x => x as usize,
         };
 
+        let include_private = self.include_private.resolve(ctx)?.try_boolean()?;
+
+        // a list given as an argument takes precedence over one provided by the host
         let suffix_result = if let Some(list) = &self.psl {
-            list.suffix(string.as_bytes())
+            find_suffix(list, string.as_bytes(), include_private)
+        } else if let Some(list) = &self.custom_psl {
+            find_suffix(&*list.current(), string.as_bytes(), include_private)
         } else {
-            psl::suffix(string.as_bytes())
+            find_suffix(&psl::List, string.as_bytes(), include_private)
         };
         let etld = suffix_result.ok_or(format!("unable to determine eTLD for {string}"))?;
         let etld_string = core::str::from_utf8(etld.as_bytes())
@@ -189,6 +293,9 @@ fn inner_kind() -> BTreeMap<Field, Kind> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::{
+        compile_with_external, state::ExternalEnv, CompileConfig, Program, TimeZone,
+    };
     use crate::value;
 
     test_function![
@@ -295,5 +402,89 @@ mod tests {
             want: Err("invalid argument"),
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
+
+        private_suffix {
+            args: func_args![value: value!("vector.github.io"), plus_parts: 1],
+            want: Ok(value!({
+                etld: "github.io",
+                etld_plus: "vector.github.io",
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        exclude_private_suffix {
+            args: func_args![value: value!("vector.github.io"), plus_parts: 1, include_private: false],
+            want: Ok(value!({
+                etld: "io",
+                etld_plus: "github.io",
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
     ];
+
+    const CUSTOM_LIST: &str = indoc! {"
+        // ===BEGIN ICANN DOMAINS===
+        corp
+        // ===END ICANN DOMAINS===
+        // ===BEGIN PRIVATE DOMAINS===
+        apps.corp
+        // ===END PRIVATE DOMAINS===
+    "};
+
+    fn compile(source: &str, list: &PublicSuffixList) -> Program {
+        let mut config = CompileConfig::default();
+        config.set_custom(list.clone());
+        compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &ExternalEnv::default(),
+            config,
+        )
+        .expect("compiles")
+        .program
+    }
+
+    fn resolve(program: &Program) -> Value {
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let mut ctx = Context::new(&mut target, &mut state, &TimeZone::default());
+        program.resolve(&mut ctx).expect("resolves")
+    }
+
+    #[test]
+    fn custom_list() {
+        let list = PublicSuffixList::from_bytes(CUSTOM_LIST.as_bytes()).expect("valid list");
+        let program = compile(
+            r#"[
+                parse_etld!("team.apps.corp").etld,
+                parse_etld!("team.apps.corp", include_private: false).etld,
+                parse_etld!("vector.dev").known_suffix,
+            ]"#,
+            &list,
+        );
+
+        assert_eq!(resolve(&program), value!(["apps.corp", "corp", false]));
+    }
+
+    #[test]
+    fn replace_custom_list() {
+        let list = PublicSuffixList::from_bytes(CUSTOM_LIST.as_bytes()).expect("valid list");
+        let program = compile(r#"parse_etld!("team.apps.corp").etld"#, &list);
+        assert_eq!(resolve(&program), value!("apps.corp"));
+
+        list.replace(
+            &PublicSuffixList::from_reader(
+                "// ===BEGIN ICANN DOMAINS===\napps.corp\nteam.apps.corp\n".as_bytes(),
+            )
+            .expect("valid list"),
+        );
+        assert_eq!(resolve(&program), value!("team.apps.corp"));
+    }
+
+    #[test]
+    fn invalid_custom_list() {
+        assert!(PublicSuffixList::from_bytes(b"// no rules").is_err());
+    }
 }
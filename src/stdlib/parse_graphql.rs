@@ -0,0 +1,515 @@
+use crate::compiler::prelude::*;
+use sha_2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// A single lexical GraphQL token.
+///
+/// Insignificant tokens (whitespace, commas, comments and the unicode BOM) are
+/// dropped by the lexer.
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Punctuator(&'a str),
+    Name(&'a str),
+    Literal,
+}
+
+impl Token<'_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Token::Punctuator(s) | Token::Name(s) => s,
+            Token::Literal => "?",
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>, String> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        match c {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' => pos += 1,
+            b'#' => {
+                while pos < bytes.len() && bytes[pos] != b'\n' && bytes[pos] != b'\r' {
+                    pos += 1;
+                }
+            }
+            b'!' | b'$' | b'&' | b'(' | b')' | b':' | b'=' | b'@' | b'[' | b']' | b'{' | b'|'
+            | b'}' => {
+                tokens.push(Token::Punctuator(&input[pos..=pos]));
+                pos += 1;
+            }
+            b'.' => {
+                if !input[pos..].starts_with("...") {
+                    return Err(format!("unexpected character '.' at position {pos}"));
+                }
+                tokens.push(Token::Punctuator("..."));
+                pos += 3;
+            }
+            b'"' => {
+                pos = if input[pos..].starts_with(r#"""""#) {
+                    end_of_block_string(input, pos)?
+                } else {
+                    end_of_string(bytes, pos)?
+                };
+                tokens.push(Token::Literal);
+            }
+            b'-' | b'0'..=b'9' => {
+                let start = pos;
+                pos += 1;
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric()
+                        || matches!(bytes[pos], b'.' | b'+' | b'-'))
+                {
+                    pos += 1;
+                }
+                if input[start..pos].parse::<f64>().is_err() {
+                    return Err(format!(
+                        "invalid number '{}' at position {start}",
+                        &input[start..pos]
+                    ));
+                }
+                tokens.push(Token::Literal);
+            }
+            b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                let start = pos;
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                {
+                    pos += 1;
+                }
+                tokens.push(Token::Name(&input[start..pos]));
+            }
+            _ if input[pos..].starts_with('\u{feff}') => pos += '\u{feff}'.len_utf8(),
+            _ => {
+                let c = input[pos..].chars().next().unwrap_or_default();
+                return Err(format!("unexpected character '{c}' at position {pos}"));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn end_of_string(bytes: &[u8], start: usize) -> Result<usize, String> {
+    let mut pos = start + 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => pos += 2,
+            b'"' => return Ok(pos + 1),
+            b'\n' | b'\r' => break,
+            _ => pos += 1,
+        }
+    }
+    Err(format!("unterminated string at position {start}"))
+}
+
+fn end_of_block_string(input: &str, start: usize) -> Result<usize, String> {
+    let mut pos = start + 3;
+    while pos < input.len() {
+        if input[pos..].starts_with(r#"\""""#) {
+            pos += 4;
+        } else if input[pos..].starts_with(r#"""""#) {
+            return Ok(pos + 3);
+        } else {
+            pos += input[pos..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    Err(format!("unterminated block string at position {start}"))
+}
+
+/// Replaces boolean, null and enum values with a literal placeholder.
+///
+/// Names are only treated as values when they appear in argument position,
+/// which is inside parentheses (outside variable definitions) after a colon, or
+/// inside a list.
+fn strip_value_names(tokens: &mut [Token<'_>]) {
+    let mut brace_depth = 0usize;
+    let mut parens: Vec<bool> = Vec::new();
+    let mut brackets = 0usize;
+    let mut in_default_value = false;
+
+    for i in 0..tokens.len() {
+        let previous = i.checked_sub(1).map(|i| tokens[i].clone());
+        match tokens[i] {
+            Token::Punctuator("{") => brace_depth += 1,
+            Token::Punctuator("}") => brace_depth = brace_depth.saturating_sub(1),
+            Token::Punctuator("(") => {
+                // Parentheses at the top level of a definition hold variable
+                // definitions, where names after a colon are types.
+                parens.push(brace_depth == 0);
+            }
+            Token::Punctuator(")") => {
+                parens.pop();
+                in_default_value = false;
+            }
+            Token::Punctuator("[") => brackets += 1,
+            Token::Punctuator("]") => brackets = brackets.saturating_sub(1),
+            Token::Punctuator("=") => in_default_value = true,
+            Token::Punctuator("$") => in_default_value = false,
+            Token::Name(_) => {
+                let in_variables = parens.last().copied().unwrap_or(false);
+                let in_arguments = !parens.is_empty() && !in_variables;
+                let value_position = matches!(previous, Some(Token::Punctuator(":" | "=")))
+                    || (brackets > 0 && !matches!(previous, Some(Token::Punctuator("$"))));
+
+                if value_position && (in_arguments || in_default_value) {
+                    tokens[i] = Token::Literal;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn normalize(tokens: &[Token<'_>]) -> String {
+    let mut normalized = String::new();
+    let mut previous: Option<&str> = None;
+
+    for token in tokens {
+        let current = token.as_str();
+        if let Some(previous) = previous {
+            let tight = matches!(previous, "(" | "[" | "$" | "@")
+                || (previous == "..." && current != "on")
+                || matches!(current, ")" | "]" | ":" | "!" | "(");
+            if !tight {
+                normalized.push(' ');
+            }
+        }
+        normalized.push_str(current);
+        previous = Some(current);
+    }
+
+    normalized
+}
+
+/// Returns the index of the token after the selection set starting at `start`.
+fn skip_selection_set(tokens: &[Token<'_>], start: usize) -> Result<usize, String> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::Punctuator("{") => depth += 1,
+            Token::Punctuator("}") => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err("unbalanced braces in selection set".to_owned())
+}
+
+/// Collects the field names of the selection set starting at `start`.
+///
+/// Aliases are resolved to the underlying field name, and fragment spreads,
+/// inline fragments and directives are skipped.
+fn top_level_fields(tokens: &[Token<'_>], start: usize) -> Vec<Value> {
+    let mut fields: Vec<Value> = Vec::new();
+    let mut brace_depth = 0usize;
+    let mut paren_depth = 0usize;
+    let mut skip_name = false;
+
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::Punctuator("{") => brace_depth += 1,
+            Token::Punctuator("}") => {
+                brace_depth -= 1;
+                if brace_depth == 0 {
+                    break;
+                }
+            }
+            Token::Punctuator("(") => paren_depth += 1,
+            Token::Punctuator(")") => paren_depth = paren_depth.saturating_sub(1),
+            _ if brace_depth != 1 || paren_depth != 0 => {}
+            // Directive names and fragment spreads aren't fields.
+            Token::Punctuator("@" | "...") => skip_name = true,
+            // For inline fragments, skip the type condition instead.
+            Token::Name("on") if tokens[i - 1] == Token::Punctuator("...") => {}
+            Token::Name(name) => {
+                if skip_name {
+                    skip_name = false;
+                } else if !matches!(tokens.get(i + 1), Some(Token::Punctuator(":"))) {
+                    let name = Value::from(*name);
+                    if !fields.contains(&name) {
+                        fields.push(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fields
+}
+
+fn parse_graphql(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let input = String::from_utf8_lossy(&bytes);
+
+    let mut tokens = tokenize(&input).map_err(|err| format!("unable to parse graphql: {err}"))?;
+    strip_value_names(&mut tokens);
+
+    let mut operation = None;
+    let mut pos = 0;
+    while pos < tokens.len() {
+        match &tokens[pos] {
+            Token::Punctuator("{") => {
+                operation = Some(("query", None, pos));
+                break;
+            }
+            Token::Name(kind @ ("query" | "mutation" | "subscription")) => {
+                let name = match tokens.get(pos + 1) {
+                    Some(Token::Name(name)) => Some(*name),
+                    _ => None,
+                };
+                let selection = tokens[pos..]
+                    .iter()
+                    .position(|t| *t == Token::Punctuator("{"))
+                    .map(|offset| pos + offset)
+                    .ok_or("unable to parse graphql: operation has no selection set")?;
+                operation = Some((*kind, name, selection));
+                break;
+            }
+            Token::Name("fragment") => {
+                let selection = tokens[pos..]
+                    .iter()
+                    .position(|t| *t == Token::Punctuator("{"))
+                    .map(|offset| pos + offset)
+                    .ok_or("unable to parse graphql: fragment has no selection set")?;
+                pos = skip_selection_set(&tokens, selection)
+                    .map_err(|err| format!("unable to parse graphql: {err}"))?;
+            }
+            token => {
+                return Err(format!(
+                    "unable to parse graphql: unexpected token '{}'",
+                    token.as_str()
+                )
+                .into())
+            }
+        }
+    }
+
+    let (operation_type, operation_name, selection) =
+        operation.ok_or("unable to parse graphql: no operation found")?;
+    skip_selection_set(&tokens, selection)
+        .map_err(|err| format!("unable to parse graphql: {err}"))?;
+
+    let normalized = normalize(&tokens);
+    let hash = hex::encode(Sha256::digest(normalized.as_bytes()));
+
+    Ok(Value::Object(ObjectMap::from([
+        ("operation_type".into(), operation_type.into()),
+        (
+            "operation_name".into(),
+            operation_name.map_or(Value::Null, Into::into),
+        ),
+        (
+            "fields".into(),
+            Value::Array(top_level_fields(&tokens, selection)),
+        ),
+        ("normalized_query".into(), normalized.into()),
+        ("hash".into(), hash.into()),
+    ])))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseGraphql;
+
+impl Function for ParseGraphql {
+    fn identifier(&self) -> &'static str {
+        "parse_graphql"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "named query",
+                source: r#"parse_graphql!(s'query GetUser($id: ID!) { user(id: $id) { name } account(id: 5, active: true) { plan } }')"#,
+                result: Ok(indoc! {r#"{
+                    "operation_type": "query",
+                    "operation_name": "GetUser",
+                    "fields": ["user", "account"],
+                    "normalized_query": "query GetUser($id: ID!) { user(id: $id) { name } account(id: ? active: ?) { plan } }",
+                    "hash": "62db7dd8123bb793cc63e32f779963256e012edac20e490f69bdbbe92085b644"
+                }"#}),
+            },
+            Example {
+                title: "anonymous query",
+                source: r#"parse_graphql!(s'{ me { id } }').operation_name"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseGraphqlFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseGraphqlFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseGraphqlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_graphql(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("operation_type".into(), Kind::bytes()),
+        ("operation_name".into(), Kind::bytes().or_null()),
+        (
+            "fields".into(),
+            Kind::array(Collection::from_unknown(Kind::bytes())),
+        ),
+        ("normalized_query".into(), Kind::bytes()),
+        ("hash".into(), Kind::bytes()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn hash(normalized: &str) -> String {
+        hex::encode(Sha256::digest(normalized.as_bytes()))
+    }
+
+    test_function![
+        parse_graphql => ParseGraphql;
+
+        named_query {
+            args: func_args![value: "query GetUser($id: ID!) { user(id: $id) { name } }"],
+            want: Ok(value!({
+                operation_type: "query",
+                operation_name: "GetUser",
+                fields: ["user"],
+                normalized_query: "query GetUser($id: ID!) { user(id: $id) { name } }",
+                hash: (hash("query GetUser($id: ID!) { user(id: $id) { name } }")),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        shorthand_query {
+            args: func_args![value: "{ me { id } }"],
+            want: Ok(value!({
+                operation_type: "query",
+                operation_name: null,
+                fields: ["me"],
+                normalized_query: "{ me { id } }",
+                hash: (hash("{ me { id } }")),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        literals_stripped {
+            args: func_args![value: indoc! {r#"
+                mutation {
+                  # create a new user
+                  createUser(name: "Jane", age: 42, score: 1.5e3, role: ADMIN, tags: ["a", "b"], active: true) {
+                    id
+                  }
+                }
+            "#}],
+            want: Ok(value!({
+                operation_type: "mutation",
+                operation_name: null,
+                fields: ["createUser"],
+                normalized_query: "mutation { createUser(name: ? age: ? score: ? role: ? tags: [? ?] active: ?) { id } }",
+                hash: (hash("mutation { createUser(name: ? age: ? score: ? role: ? tags: [? ?] active: ?) { id } }")),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        same_shape_same_hash {
+            args: func_args![value: r#"{ user(id: "other") { name } }"#],
+            want: Ok(value!({
+                operation_type: "query",
+                operation_name: null,
+                fields: ["user"],
+                normalized_query: "{ user(id: ?) { name } }",
+                hash: (hash(r#"{ user(id: ?) { name } }"#)),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        aliases_fragments_and_directives {
+            args: func_args![value: indoc! {r"
+                fragment UserFields on User { id name }
+                subscription OnEvent {
+                  primary: user @include(if: true) { ...UserFields }
+                  ... on Query { hidden }
+                  ...Extra
+                  events
+                }
+            "}],
+            want: Ok(value!({
+                operation_type: "subscription",
+                operation_name: "OnEvent",
+                fields: ["user", "events"],
+                normalized_query: "fragment UserFields on User { id name } subscription OnEvent { primary: user @include(if: ?) { ...UserFields } ... on Query { hidden } ...Extra events }",
+                hash: (hash("fragment UserFields on User { id name } subscription OnEvent { primary: user @include(if: ?) { ...UserFields } ... on Query { hidden } ...Extra events }")),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        default_variable_value {
+            args: func_args![value: "query Q($limit: Int = 10, $sort: Order = ASC) { items(limit: $limit) { id } }"],
+            want: Ok(value!({
+                operation_type: "query",
+                operation_name: "Q",
+                fields: ["items"],
+                normalized_query: "query Q($limit: Int = ? $sort: Order = ?) { items(limit: $limit) { id } }",
+                hash: (hash("query Q($limit: Int = ? $sort: Order = ?) { items(limit: $limit) { id } }")),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unterminated_string {
+            args: func_args![value: r#"{ user(id: "abc) { name } }"#],
+            want: Err("unable to parse graphql: unterminated string at position 11"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unbalanced_braces {
+            args: func_args![value: "query { user { name }"],
+            want: Err("unable to parse graphql: unbalanced braces in selection set"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        no_operation {
+            args: func_args![value: "fragment F on User { id }"],
+            want: Err("unable to parse graphql: no operation found"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
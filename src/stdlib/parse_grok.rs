@@ -4,10 +4,20 @@ use crate::compiler::prelude::*;
 mod non_wasm {
     use crate::compiler::prelude::*;
     use crate::diagnostic::{Label, Span};
+    use crate::stdlib::util::BoundedCache;
     use crate::value::Value;
+    use once_cell::sync::Lazy;
     pub(super) use std::sync::Arc;
     use std::{collections::BTreeMap, fmt};
 
+    /// The number of compiled patterns kept in [`PATTERNS`].
+    const PATTERNS_CACHE_CAPACITY: usize = 256;
+
+    /// Compiled patterns by pattern text, so programs using the same pattern share
+    /// it instead of compiling their own.
+    pub(super) static PATTERNS: Lazy<BoundedCache<String, Arc<grok::Pattern>>> =
+        Lazy::new(|| BoundedCache::new(PATTERNS_CACHE_CAPACITY));
+
     fn parse_grok(value: Value, pattern: Arc<grok::Pattern>) -> Resolved {
         let bytes = value.try_bytes_utf8_lossy()?;
         match pattern.match_against(&bytes) {
@@ -139,11 +149,13 @@ impl Function for ParseGrok {
             .expect("grok pattern not bytes")
             .into_owned();
 
-        let mut grok = grok::Grok::with_default_patterns();
-        let pattern =
-            Arc::new(grok.compile(&pattern, true).map_err(|e| {
-                Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>
-            })?);
+        let pattern = PATTERNS
+            .get_or_try_insert_with(pattern.clone(), || {
+                grok::Grok::with_default_patterns()
+                    .compile(&pattern, true)
+                    .map(Arc::new)
+            })
+            .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
 
         Ok(ParseGrokFn { value, pattern }.as_expr())
     }
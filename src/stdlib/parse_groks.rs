@@ -5,7 +5,19 @@ mod non_wasm {
     use crate::compiler::prelude::*;
     use crate::datadog_grok::{parse_grok, parse_grok_rules::GrokRule};
     use crate::diagnostic::{Label, Span};
-    use std::fmt;
+    use crate::stdlib::util::BoundedCache;
+    use once_cell::sync::Lazy;
+    use std::{collections::BTreeMap, fmt, sync::Arc};
+
+    /// The number of compiled rule sets kept in [`GROK_RULES`].
+    const GROK_RULES_CACHE_CAPACITY: usize = 256;
+
+    type GrokRulesKey = (Vec<String>, BTreeMap<KeyString, String>);
+
+    /// Compiled rules by patterns and aliases, so programs using the same rules
+    /// share them instead of compiling their own.
+    pub(super) static GROK_RULES: Lazy<BoundedCache<GrokRulesKey, Arc<[GrokRule]>>> =
+        Lazy::new(|| BoundedCache::new(GROK_RULES_CACHE_CAPACITY));
 
     #[derive(Debug)]
     pub(crate) enum Error {
@@ -42,7 +54,7 @@ mod non_wasm {
     #[derive(Clone, Debug)]
     pub(super) struct ParseGroksFn {
         pub(super) value: Box<dyn Expression>,
-        pub(super) grok_rules: Vec<GrokRule>,
+        pub(super) grok_rules: Arc<[GrokRule]>,
     }
 
     impl FunctionExpression for ParseGroksFn {
@@ -66,8 +78,53 @@ mod non_wasm {
 #[allow(clippy::wildcard_imports)]
 #[cfg(not(target_arch = "wasm32"))]
 use non_wasm::*;
+use std::collections::BTreeMap;
 #[cfg(not(target_arch = "wasm32"))]
 use std::{fs::File, io::BufReader, path::Path};
+use std::{io, sync::Arc};
+
+/// Named grok pattern libraries that `parse_groks` calls can reference through the
+/// `libraries` argument. Register it with [`CompileConfig::set_custom`].
+///
+/// Each library is a map of alias names to grok patterns, in the same format as the
+/// files accepted by `alias_sources`. Libraries are shared between every program
+/// compiled with the config, so large pattern sets are only loaded once.
+///
+/// [`CompileConfig::set_custom`]: crate::compiler::CompileConfig::set_custom
+#[derive(Debug, Clone, Default)]
+pub struct GrokPatternLibraries(BTreeMap<String, Arc<BTreeMap<KeyString, String>>>);
+
+impl GrokPatternLibraries {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a library under `name`, replacing any library with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, aliases: BTreeMap<KeyString, String>) {
+        self.0.insert(name.into(), Arc::new(aliases));
+    }
+
+    /// Registers a library under `name` from a JSON object of alias names to patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader does not contain a JSON object of strings.
+    pub fn insert_from_reader(
+        &mut self,
+        name: impl Into<String>,
+        reader: impl io::Read,
+    ) -> io::Result<()> {
+        let aliases = serde_json::from_reader(reader)?;
+        self.insert(name, aliases);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&BTreeMap<KeyString, String>> {
+        self.0.get(name).map(AsRef::as_ref)
+    }
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct ParseGroks;
@@ -99,6 +156,11 @@ impl Function for ParseGroks {
                 kind: kind::ARRAY,
                 required: false,
             },
+            Parameter {
+                keyword: "libraries",
+                kind: kind::ARRAY,
+                required: false,
+            },
         ]
     }
 
@@ -134,11 +196,9 @@ impl Function for ParseGroks {
     fn compile(
         &self,
         state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
-        use std::collections::BTreeMap;
-
         let value = arguments.required("value");
 
         let patterns = arguments
@@ -227,11 +287,55 @@ impl Function for ParseGroks {
             aliases.append(&mut src_aliases);
         }
 
+        let libraries = arguments
+            .optional_array("libraries")?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|expr| {
+                let name = expr
+                    .clone()
+                    .resolve_constant(state)
+                    .ok_or(function::Error::ExpectedStaticExpression {
+                        keyword: "libraries",
+                        expr: expr.clone(),
+                    })?
+                    .try_bytes_utf8_lossy()
+                    .map_err(|_| function::Error::InvalidArgument {
+                        keyword: "libraries",
+                        value: format!("{expr:?}").into(),
+                        error: "library name should be a string",
+                    })?
+                    .into_owned();
+                Ok(name)
+            })
+            .collect::<std::result::Result<Vec<String>, function::Error>>()?;
+
+        // aliases given inline or through `alias_sources` take precedence over
+        // those of a library, and earlier libraries take precedence over later ones
+        for name in libraries {
+            let library = ctx
+                .get_external_context::<GrokPatternLibraries>()
+                .and_then(|libraries| libraries.get(&name))
+                .ok_or_else(|| function::Error::InvalidArgument {
+                    keyword: "libraries",
+                    value: name.clone().into(),
+                    error: "Unknown grok pattern library",
+                })?;
+
+            for (alias, pattern) in library {
+                aliases
+                    .entry(alias.clone())
+                    .or_insert_with(|| pattern.clone());
+            }
+        }
+
         // we use a datadog library here because it is a superset of grok
-        let grok_rules = crate::datadog_grok::parse_grok_rules::parse_grok_rules(
-            &patterns, aliases,
-        )
-        .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
+        let grok_rules = GROK_RULES
+            .get_or_try_insert_with((patterns.clone(), aliases.clone()), || {
+                crate::datadog_grok::parse_grok_rules::parse_grok_rules(&patterns, aliases)
+                    .map(Arc::from)
+            })
+            .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
 
         Ok(ParseGroksFn { value, grok_rules }.as_expr())
     }
@@ -254,6 +358,10 @@ impl Function for ParseGroks {
 #[cfg(test)]
 mod test {
     use crate::btreemap;
+    use crate::compiler::{
+        compile_with_external, state::ExternalEnv, CompileConfig, Program, TimeZone,
+    };
+    use crate::diagnostic::DiagnosticList;
     use crate::value;
     use crate::value::Value;
 
@@ -431,4 +539,67 @@ mod test {
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
     ];
+
+    fn compile_with_libraries(source: &str) -> Result<Program, DiagnosticList> {
+        let mut libraries = GrokPatternLibraries::new();
+        libraries.insert(
+            "common",
+            BTreeMap::from([
+                (
+                    "common_prefix".into(),
+                    "%{TIMESTAMP_ISO8601:timestamp} %{_loglevel}".into(),
+                ),
+                ("_loglevel".into(), "%{LOGLEVEL:level}".into()),
+            ]),
+        );
+        libraries
+            .insert_from_reader(
+                "message",
+                r#"{"_message": "%{GREEDYDATA:message}"}"#.as_bytes(),
+            )
+            .unwrap();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(libraries);
+        compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &ExternalEnv::default(),
+            config,
+        )
+        .map(|compilation| compilation.program)
+    }
+
+    #[test]
+    fn libraries() {
+        let program = compile_with_libraries(
+            r#"parse_groks!(
+                "2020-10-02T23:22:12.223222Z info hello world",
+                patterns: ["%{common_prefix} %{_message}"],
+                aliases: {"_loglevel": "%{WORD:severity}"},
+                libraries: ["common", "message"],
+            )"#,
+        )
+        .expect("compiles");
+
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let mut ctx = Context::new(&mut target, &mut state, &TimeZone::default());
+        assert_eq!(
+            program.resolve(&mut ctx),
+            Ok(value!({
+                "timestamp": "2020-10-02T23:22:12.223222Z",
+                "severity": "info",
+                "message": "hello world",
+            }))
+        );
+    }
+
+    #[test]
+    fn unknown_library() {
+        assert!(compile_with_libraries(
+            r#"parse_groks!("hello", patterns: ["%{_message}"], libraries: ["missing"])"#
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,124 @@
+use crate::compiler::prelude::*;
+use crate::parsing::html::parse_html_meta;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseHtmlMeta;
+
+impl Function for ParseHtmlMeta {
+    fn identifier(&self) -> &'static str {
+        "parse_html_meta"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "title and meta tags",
+            source: indoc! {r#"
+                parse_html_meta!(s'<html><head><meta charset="utf-8"><title>Vector &amp; VRL</title><meta name="description" content="Observability pipelines"><meta property="og:type" content="website"></head></html>')
+            "#},
+            result: Ok(indoc! {r#"{
+                "title": "Vector & VRL",
+                "meta": {
+                    "charset": "utf-8",
+                    "description": "Observability pipelines",
+                    "og:type": "website"
+                }
+            }"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseHtmlMetaFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseHtmlMetaFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseHtmlMetaFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_html_meta(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("title".into(), Kind::bytes().or_null()),
+        (
+            "meta".into(),
+            Kind::object(Collection::from_unknown(Kind::bytes())),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_html_meta => ParseHtmlMeta;
+
+        full_document {
+            args: func_args![value: indoc! {r#"
+                <!DOCTYPE html>
+                <html>
+                  <head>
+                    <META HTTP-EQUIV="refresh" CONTENT="30">
+                    <title>
+                      Login &mdash; Example
+                    </title>
+                    <meta name="Robots" content="noindex">
+                    <meta name="robots" content="ignored duplicate">
+                    <meta property="og:url" content='https://example.com/?a=1&amp;b=2'>
+                  </head>
+                  <body><title>not the title</title></body>
+                </html>
+            "#}],
+            want: Ok(value!({
+                title: "Login — Example",
+                meta: {
+                    refresh: "30",
+                    robots: "noindex",
+                    "og:url": "https://example.com/?a=1&b=2",
+                },
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        no_head {
+            args: func_args![value: "<p>just a fragment</p>"],
+            want: Ok(value!({ title: null, meta: {} })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        meta_without_content {
+            args: func_args![value: r#"<meta name="viewport"><title></title>"#],
+            want: Ok(value!({ title: null, meta: {} })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
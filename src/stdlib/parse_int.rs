@@ -74,6 +74,10 @@ impl Function for ParseInt {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
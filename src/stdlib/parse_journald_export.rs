@@ -0,0 +1,254 @@
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+
+use crate::compiler::prelude::*;
+
+const REALTIME_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
+
+/// Adds a field to an entry. Journald allows a field to appear more than once
+/// in an entry, in which case its values are collected into an array.
+fn insert_field(entry: &mut ObjectMap, name: KeyString, value: Value) {
+    match entry.get_mut(&name) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = std::mem::replace(existing, Value::Null);
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            entry.insert(name, value);
+        }
+    }
+}
+
+fn field_value(name: &str, data: &[u8]) -> Result<Value, String> {
+    if name != REALTIME_TIMESTAMP {
+        return Ok(Value::Bytes(Bytes::copy_from_slice(data)));
+    }
+
+    let micros = std::str::from_utf8(data)
+        .ok()
+        .and_then(|micros| micros.parse::<i64>().ok())
+        .ok_or_else(|| {
+            format!(
+                "invalid {REALTIME_TIMESTAMP} {:?}",
+                String::from_utf8_lossy(data)
+            )
+        })?;
+    let timestamp = Utc
+        .timestamp_micros(micros)
+        .single()
+        .ok_or_else(|| format!("{REALTIME_TIMESTAMP} out of range: {micros}"))?;
+
+    Ok(Value::from(timestamp))
+}
+
+/// Parses the journal export format, as described in
+/// <https://systemd.io/JOURNAL_EXPORT_FORMATS/>.
+fn parse_journald_export(bytes: &[u8]) -> Result<Vec<Value>, String> {
+    let mut entries = Vec::new();
+    let mut entry = ObjectMap::new();
+    let mut rest = bytes;
+
+    while !rest.is_empty() {
+        let line_end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        let line = &rest[..line_end];
+
+        // An empty line ends the current entry.
+        if line.is_empty() {
+            if !entry.is_empty() {
+                entries.push(std::mem::take(&mut entry).into());
+            }
+            rest = &rest[1..];
+            continue;
+        }
+
+        let (name, data, consumed) = match line.iter().position(|&b| b == b'=') {
+            Some(separator) => (&line[..separator], &line[separator + 1..], line_end + 1),
+            // Binary-safe field: the name on its own line, followed by the length of
+            // the data as a little-endian 64-bit integer, the data and a newline.
+            None => {
+                let data_start = line_end + 1 + 8;
+                let length = rest
+                    .get(line_end + 1..data_start)
+                    .map(|length| u64::from_le_bytes(length.try_into().expect("8 bytes")))
+                    .ok_or_else(|| {
+                        format!(
+                            "missing length of binary field {}",
+                            String::from_utf8_lossy(line)
+                        )
+                    })?;
+                let data_end = usize::try_from(length)
+                    .ok()
+                    .and_then(|length| data_start.checked_add(length))
+                    .filter(|&end| end < rest.len() && rest[end] == b'\n')
+                    .ok_or_else(|| {
+                        format!("truncated binary field {}", String::from_utf8_lossy(line))
+                    })?;
+
+                (line, &rest[data_start..data_end], data_end + 1)
+            }
+        };
+
+        let name = std::str::from_utf8(name)
+            .ok()
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| format!("invalid field name {:?}", String::from_utf8_lossy(name)))?;
+        let value = field_value(name, data)?;
+        insert_field(&mut entry, name.into(), value);
+
+        rest = rest.get(consumed..).unwrap_or_default();
+    }
+
+    if !entry.is_empty() {
+        entries.push(entry.into());
+    }
+
+    Ok(entries)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseJournaldExport;
+
+impl Function for ParseJournaldExport {
+    fn identifier(&self) -> &'static str {
+        "parse_journald_export"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse journal export",
+            source: r#"parse_journald_export!("__REALTIME_TIMESTAMP=1342540861416351\n_SYSTEMD_UNIT=sshd.service\nMESSAGE=Accepted publickey for root\n\n__REALTIME_TIMESTAMP=1342540861421465\nMESSAGE=Session opened\n")"#,
+            result: Ok(
+                r#"[{"MESSAGE": "Accepted publickey for root", "_SYSTEMD_UNIT": "sshd.service", "__REALTIME_TIMESTAMP": "2012-07-17T16:01:01.416351Z"}, {"MESSAGE": "Session opened", "__REALTIME_TIMESTAMP": "2012-07-17T16:01:01.421465Z"}]"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseJournaldExportFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseJournaldExportFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseJournaldExportFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let bytes = value.try_bytes()?;
+
+        Ok(parse_journald_export(&bytes)?.into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn binary_field(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut field = format!("{name}\n").into_bytes();
+        field.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        field.extend_from_slice(data);
+        field.push(b'\n');
+        field
+    }
+
+    fn type_def() -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible()
+    }
+
+    test_function![
+        parse_journald_export => ParseJournaldExport;
+
+        text_fields {
+            args: func_args![value: "__CURSOR=s=739ad463348b4ceca5a9e69c95a3c93f;i=4ece7\n__REALTIME_TIMESTAMP=1342540861416351\n__MONOTONIC_TIMESTAMP=16384426\n_SYSTEMD_UNIT=sshd.service\nMESSAGE=Accepted publickey for root from 192.168.1.1\n\n"],
+            want: Ok(vec![value!({
+                "__CURSOR": "s=739ad463348b4ceca5a9e69c95a3c93f;i=4ece7",
+                "__REALTIME_TIMESTAMP": (Utc.timestamp_micros(1_342_540_861_416_351).unwrap()),
+                "__MONOTONIC_TIMESTAMP": "16384426",
+                "_SYSTEMD_UNIT": "sshd.service",
+                "MESSAGE": "Accepted publickey for root from 192.168.1.1",
+            })]),
+            tdef: type_def(),
+        }
+
+        multiple_entries {
+            args: func_args![value: "MESSAGE=first\n\nMESSAGE=second\n\n\nMESSAGE=third"],
+            want: Ok(value!([{"MESSAGE": "first"}, {"MESSAGE": "second"}, {"MESSAGE": "third"}])),
+            tdef: type_def(),
+        }
+
+        empty_value_and_equals_in_value {
+            args: func_args![value: "CODE_FUNC=\nMESSAGE=a=b\n"],
+            want: Ok(value!([{"CODE_FUNC": "", "MESSAGE": "a=b"}])),
+            tdef: type_def(),
+        }
+
+        repeated_fields {
+            args: func_args![value: "TAG=a\nTAG=b\nTAG=c\n"],
+            want: Ok(value!([{"TAG": ["a", "b", "c"]}])),
+            tdef: type_def(),
+        }
+
+        binary_fields {
+            args: func_args![value: Bytes::from([
+                b"PRIORITY=6\n".to_vec(),
+                binary_field("MESSAGE", b"line one\nline two"),
+                binary_field("COREDUMP", &[0x00, 0xff, b'\n', 0x01]),
+                b"\n".to_vec(),
+            ].concat())],
+            want: Ok(vec![value!({
+                "PRIORITY": "6",
+                "MESSAGE": "line one\nline two",
+                "COREDUMP": (Bytes::from_static(&[0x00, 0xff, b'\n', 0x01])),
+            })]),
+            tdef: type_def(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(value!([])),
+            tdef: type_def(),
+        }
+
+        truncated_binary_field {
+            args: func_args![value: Bytes::from([b"MESSAGE\n".to_vec(), 100u64.to_le_bytes().to_vec(), b"short\n".to_vec()].concat())],
+            want: Err("truncated binary field MESSAGE"),
+            tdef: type_def(),
+        }
+
+        missing_binary_length {
+            args: func_args![value: "MESSAGE\nabc"],
+            want: Err("missing length of binary field MESSAGE"),
+            tdef: type_def(),
+        }
+
+        invalid_timestamp {
+            args: func_args![value: "__REALTIME_TIMESTAMP=yesterday\n"],
+            want: Err(r#"invalid __REALTIME_TIMESTAMP "yesterday""#),
+            tdef: type_def(),
+        }
+    ];
+}
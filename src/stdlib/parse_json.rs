@@ -6,7 +6,7 @@ use serde_json::{
 };
 
 use crate::compiler::prelude::*;
-use crate::stdlib::json_utils::json_type_def::json_type_def;
+use crate::stdlib::json_utils::{backend, json_type_def::json_type_def};
 
 fn parse_json(value: Value, lossy: Option<Value>) -> Resolved {
     let lossy = lossy.map(Value::try_boolean).transpose()?.unwrap_or(true);
@@ -15,8 +15,7 @@ fn parse_json(value: Value, lossy: Option<Value>) -> Resolved {
     } else {
         value.try_bytes()?
     };
-    let value = serde_json::from_slice::<'_, Value>(&bytes)
-        .map_err(|e| format!("unable to parse json: {e}"))?;
+    let value = backend::from_slice(&bytes).map_err(|e| format!("unable to parse json: {e}"))?;
     Ok(value)
 }
 
@@ -187,6 +186,17 @@ impl Function for ParseJson {
         ]
     }
 
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+            .category("Parse")
+            .returns(
+                kind::ANY ^ kind::UNDEFINED ^ kind::TIMESTAMP ^ kind::REGEX,
+                true,
+            )
+            .return_rule("fails if `value` is not valid JSON")
+            .parameter_default("lossy", "true")
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
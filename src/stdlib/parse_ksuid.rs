@@ -0,0 +1,128 @@
+use crate::compiler::prelude::*;
+use chrono::DateTime;
+use std::collections::BTreeMap;
+
+use super::ksuid::{decode, EPOCH};
+
+fn parse_ksuid(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let (timestamp, payload) =
+        decode(&string).map_err(|err| format!("unable to parse KSUID: {err}"))?;
+    let timestamp = DateTime::from_timestamp(EPOCH + i64::from(timestamp), 0)
+        .ok_or("unable to parse KSUID: timestamp is out of range")?;
+
+    let mut map = BTreeMap::<&str, Value>::new();
+    map.insert("timestamp", timestamp.into());
+    map.insert("payload", hex::encode(payload).into());
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseKsuid;
+
+impl Function for ParseKsuid {
+    fn identifier(&self) -> &'static str {
+        "parse_ksuid"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse KSUID",
+            source: r#"parse_ksuid!("0ujtsYcgvSTl8PAuAdqWYSMnLOv")"#,
+            result: Ok(
+                r#"{ "payload": "b5a1cd34b5f99d1154fb6853345c9735", "timestamp": "2017-10-10T04:00:47Z" }"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseKsuidFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseKsuidFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseKsuidFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_ksuid(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("timestamp".into(), Kind::timestamp()),
+        ("payload".into(), Kind::bytes()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use chrono::Utc;
+
+    fn timestamp(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    test_function![
+        parse_ksuid => ParseKsuid;
+
+        valid {
+            args: func_args![value: "0ujtsYcgvSTl8PAuAdqWYSMnLOv"],
+            want: Ok(value!({
+                timestamp: (timestamp("2017-10-10T04:00:47Z")),
+                payload: "b5a1cd34b5f99d1154fb6853345c9735",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_character {
+            args: func_args![value: "0ujtsYcgvSTl8PAuAdqWYSMnLO-"],
+            want: Err("unable to parse KSUID: invalid character '-' at position 26"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_length {
+            args: func_args![value: "0ujtsYcgvSTl8PAuAdqWYSMnLOvX"],
+            want: Err("unable to parse KSUID: expected 27 characters, got 28"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        too_large {
+            args: func_args![value: "zzzzzzzzzzzzzzzzzzzzzzzzzzz"],
+            want: Err("unable to parse KSUID: value is too large"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
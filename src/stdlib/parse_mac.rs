@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::compiler::prelude::*;
+
+use super::mac_utils::{MacAddress, MacFormat};
+
+/// A vendor lookup by organizationally unique identifier (OUI), provided by the
+/// host environment.
+pub trait OuiLookup: Send + Sync + fmt::Debug {
+    /// Returns the name of the vendor the OUI is assigned to, if known.
+    fn vendor(&self, oui: [u8; 3]) -> Option<String>;
+}
+
+impl OuiLookup for HashMap<[u8; 3], String> {
+    fn vendor(&self, oui: [u8; 3]) -> Option<String> {
+        self.get(&oui).cloned()
+    }
+}
+
+impl OuiLookup for BTreeMap<[u8; 3], String> {
+    fn vendor(&self, oui: [u8; 3]) -> Option<String> {
+        self.get(&oui).cloned()
+    }
+}
+
+/// The OUI table used by `parse_mac` to add the `vendor` field. Register it
+/// with [`CompileConfig::set_custom`].
+///
+/// [`CompileConfig::set_custom`]: crate::compiler::CompileConfig::set_custom
+#[derive(Debug, Clone)]
+pub struct OuiTable(Arc<dyn OuiLookup>);
+
+impl OuiTable {
+    #[must_use]
+    pub fn new(lookup: impl OuiLookup + 'static) -> Self {
+        Self(Arc::new(lookup))
+    }
+}
+
+fn parse_mac(value: &Value, table: Option<&OuiTable>) -> Resolved {
+    let address = MacAddress::parse(&value.try_bytes_utf8_lossy()?)?;
+
+    let mut map = ObjectMap::from([
+        (
+            "address".into(),
+            address.format(MacFormat::Colon, false).into(),
+        ),
+        ("oui".into(), address.format_oui().into()),
+        ("locally_administered".into(), address.is_local().into()),
+        ("multicast".into(), address.is_multicast().into()),
+    ]);
+    if let Some(OuiTable(lookup)) = table {
+        map.insert("vendor".into(), lookup.vendor(address.oui()).into());
+    }
+
+    Ok(map.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseMac;
+
+impl Function for ParseMac {
+    fn identifier(&self) -> &'static str {
+        "parse_mac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "colon notation",
+                source: r#"parse_mac!("00:1A:2B:3C:4D:5E")"#,
+                result: Ok(
+                    r#"{"address": "00:1a:2b:3c:4d:5e", "locally_administered": false, "multicast": false, "oui": "00:1a:2b"}"#,
+                ),
+            },
+            Example {
+                title: "dotted notation",
+                source: r#"parse_mac!("0300.5e00.0001")"#,
+                result: Ok(
+                    r#"{"address": "03:00:5e:00:00:01", "locally_administered": true, "multicast": true, "oui": "03:00:5e"}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let table = ctx.get_external_context::<OuiTable>().cloned();
+
+        Ok(ParseMacFn { value, table }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseMacFn {
+    value: Box<dyn Expression>,
+    table: Option<OuiTable>,
+}
+
+impl FunctionExpression for ParseMacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_mac(&value, self.table.as_ref())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        let mut kind = inner_kind();
+        if self.table.is_some() {
+            kind.insert("vendor".into(), Kind::bytes().or_null());
+        }
+
+        TypeDef::object(kind).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("address".into(), Kind::bytes()),
+        ("oui".into(), Kind::bytes()),
+        ("locally_administered".into(), Kind::boolean()),
+        ("multicast".into(), Kind::boolean()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use crate::compiler::{compile_with_external, state::ExternalEnv, CompileConfig, TimeZone};
+
+    test_function![
+        parse_mac => ParseMac;
+
+        colon {
+            args: func_args![value: "00:1A:2B:3C:4D:5E"],
+            want: Ok(value!({
+                "address": "00:1a:2b:3c:4d:5e",
+                "oui": "00:1a:2b",
+                "locally_administered": false,
+                "multicast": false,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        dash {
+            args: func_args![value: "02-00-00-00-00-01"],
+            want: Ok(value!({
+                "address": "02:00:00:00:00:01",
+                "oui": "02:00:00",
+                "locally_administered": true,
+                "multicast": false,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        broadcast {
+            args: func_args![value: "ffffffffffff"],
+            want: Ok(value!({
+                "address": "ff:ff:ff:ff:ff:ff",
+                "oui": "ff:ff:ff",
+                "locally_administered": true,
+                "multicast": true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "00:1a:2b:3c:4d"],
+            want: Err(r#"unable to parse MAC address "00:1a:2b:3c:4d""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+
+    #[test]
+    fn vendor_lookup() {
+        let mut config = CompileConfig::default();
+        config.set_custom(OuiTable::new(HashMap::from([(
+            [0x00, 0x1a, 0x2b],
+            "Acme".to_owned(),
+        )])));
+        let program = compile_with_external(
+            r#"[parse_mac!("00:1a:2b:00:00:01").vendor, parse_mac!("00:00:00:00:00:01").vendor]"#,
+            &crate::stdlib::all(),
+            &ExternalEnv::default(),
+            config,
+        )
+        .expect("compiles")
+        .program;
+
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut state, &tz);
+        assert_eq!(program.resolve(&mut ctx), Ok(value!(["Acme", null])));
+    }
+}
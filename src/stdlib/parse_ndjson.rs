@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+use crate::stdlib::json_utils::json_type_def::json_inner_kind;
+
+#[derive(Default)]
+struct Parsed {
+    values: Vec<Value>,
+    errors: Vec<Value>,
+}
+
+/// Parses each non-blank line of `value` as JSON. Invalid lines fail the whole
+/// call when `fail_fast` is set, and are collected as errors otherwise.
+fn parse_ndjson(value: &[u8], fail_fast: bool) -> Result<Parsed, ExpressionError> {
+    let mut parsed = Parsed::default();
+
+    for (index, line) in value.split(|&byte| byte == b'\n').enumerate() {
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_slice::<'_, Value>(line) {
+            Ok(value) => parsed.values.push(value),
+            Err(err) if fail_fast => {
+                return Err(format!("unable to parse line {}: {err}", index + 1).into());
+            }
+            Err(err) => parsed.errors.push(Value::from(ObjectMap::from([
+                ("line".into(), Value::from(index + 1)),
+                ("message".into(), Value::from(err.to_string())),
+            ]))),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn values_kind() -> Kind {
+    Kind::array(Collection::from_unknown(json_inner_kind()))
+}
+
+fn with_errors_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("parsed".into(), values_kind()),
+        (
+            "errors".into(),
+            Kind::array(Collection::from_unknown(Kind::object(BTreeMap::from([
+                ("line".into(), Kind::integer()),
+                ("message".into(), Kind::bytes()),
+            ])))),
+        ),
+    ])
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseNdjson;
+
+impl Function for ParseNdjson {
+    fn identifier(&self) -> &'static str {
+        "parse_ndjson"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "skip_invalid",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "with_errors",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse NDJSON",
+                source: r#"parse_ndjson!(s'{"a": 1}' + "\n" + s'{"a": 2}' + "\n")"#,
+                result: Ok(r#"[{"a": 1}, {"a": 2}]"#),
+            },
+            Example {
+                title: "skip invalid lines",
+                source: r#"parse_ndjson(s'{"a": 1}' + "\n" + "oops", skip_invalid: true)"#,
+                result: Ok(r#"[{"a": 1}]"#),
+            },
+            Example {
+                title: "with errors",
+                source: r#"parse_ndjson(s'{"a": 1}' + "\n" + "oops", with_errors: true)"#,
+                result: Ok(
+                    r#"{"errors": [{"line": 2, "message": "expected value at line 1 column 1"}], "parsed": [{"a": 1}]}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let skip_invalid = arguments
+            .optional("skip_invalid")
+            .unwrap_or_else(|| expr!(false));
+        let with_errors = arguments
+            .optional_literal("with_errors", state)?
+            .map(|with_errors| with_errors.try_boolean().expect("with_errors not boolean"))
+            .unwrap_or(false);
+
+        Ok(ParseNdjsonFn {
+            value,
+            skip_invalid,
+            with_errors,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseNdjsonFn {
+    value: Box<dyn Expression>,
+    skip_invalid: Box<dyn Expression>,
+    with_errors: bool,
+}
+
+impl FunctionExpression for ParseNdjsonFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+        let skip_invalid = self.skip_invalid.resolve(ctx)?.try_boolean()?;
+
+        let parsed = parse_ndjson(&value, !skip_invalid && !self.with_errors)?;
+
+        if self.with_errors {
+            Ok(Value::from(ObjectMap::from([
+                ("parsed".into(), Value::Array(parsed.values)),
+                ("errors".into(), Value::Array(parsed.errors)),
+            ])))
+        } else {
+            Ok(Value::Array(parsed.values))
+        }
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        if self.with_errors {
+            return TypeDef::object(with_errors_kind()).infallible();
+        }
+
+        let skip_invalid = self
+            .skip_invalid
+            .resolve_constant(state)
+            .and_then(|skip_invalid| skip_invalid.as_boolean())
+            .unwrap_or(false);
+
+        TypeDef::from(values_kind()).maybe_fallible(!skip_invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_ndjson => ParseNdjson;
+
+        lines {
+            args: func_args![value: "{\"a\": 1}\n[true]\n\"x\"\n"],
+            want: Ok(value!([{"a": 1}, [true], "x"])),
+            tdef: TypeDef::from(values_kind()).fallible(),
+        }
+
+        blank_lines_and_crlf {
+            args: func_args![value: "\r\n{\"a\": 1}\r\n  \n{\"a\": 2}"],
+            want: Ok(value!([{"a": 1}, {"a": 2}])),
+            tdef: TypeDef::from(values_kind()).fallible(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(value!([])),
+            tdef: TypeDef::from(values_kind()).fallible(),
+        }
+
+        invalid_line {
+            args: func_args![value: "{\"a\": 1}\n{oops}"],
+            want: Err("unable to parse line 2: key must be a string at line 1 column 2"),
+            tdef: TypeDef::from(values_kind()).fallible(),
+        }
+
+        skip_invalid {
+            args: func_args![value: "{oops}\n{\"a\": 1}", skip_invalid: true],
+            want: Ok(value!([{"a": 1}])),
+            tdef: TypeDef::from(values_kind()).infallible(),
+        }
+
+        with_errors {
+            args: func_args![value: "{\"a\": 1}\n{oops}\n2", with_errors: true],
+            want: Ok(value!({
+                "parsed": [{"a": 1}, 2],
+                "errors": [{"line": 2, "message": "key must be a string at line 1 column 2"}],
+            })),
+            tdef: TypeDef::object(with_errors_kind()).infallible(),
+        }
+    ];
+}
@@ -0,0 +1,167 @@
+use crate::compiler::prelude::*;
+use semver::Version;
+use std::collections::BTreeMap;
+
+/// Parses a semantic version, which can have a `v` prefix as in Git tags.
+pub(crate) fn parse_version(value: &str) -> Result<Version, String> {
+    let version = value.strip_prefix('v').unwrap_or(value);
+
+    Version::parse(version).map_err(|err| format!("unable to parse semantic version: {err}"))
+}
+
+fn version_number(number: u64) -> Result<Value, String> {
+    i64::try_from(number)
+        .map(Value::from)
+        .map_err(|_| format!("version number {number} is too large"))
+}
+
+fn parse_semver(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let version = parse_version(&string)?;
+
+    let optional = |identifier: &str| -> Value {
+        if identifier.is_empty() {
+            Value::Null
+        } else {
+            identifier.to_owned().into()
+        }
+    };
+
+    let mut map = BTreeMap::<&str, Value>::new();
+    map.insert("major", version_number(version.major)?);
+    map.insert("minor", version_number(version.minor)?);
+    map.insert("patch", version_number(version.patch)?);
+    map.insert("pre", optional(version.pre.as_str()));
+    map.insert("build", optional(version.build.as_str()));
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseSemver;
+
+impl Function for ParseSemver {
+    fn identifier(&self) -> &'static str {
+        "parse_semver"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "release",
+                source: r#"parse_semver!("1.72.0")"#,
+                result: Ok(
+                    r#"{ "build": null, "major": 1, "minor": 72, "patch": 0, "pre": null }"#,
+                ),
+            },
+            Example {
+                title: "pre-release with build metadata",
+                source: r#"parse_semver!("v2.0.0-rc.1+build.5")"#,
+                result: Ok(
+                    r#"{ "build": "build.5", "major": 2, "minor": 0, "patch": 0, "pre": "rc.1" }"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseSemverFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseSemverFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseSemverFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_semver(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("major".into(), Kind::integer()),
+        ("minor".into(), Kind::integer()),
+        ("patch".into(), Kind::integer()),
+        ("pre".into(), Kind::bytes().or_null()),
+        ("build".into(), Kind::bytes().or_null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_semver => ParseSemver;
+
+        release {
+            args: func_args![value: "1.2.3"],
+            want: Ok(value!({
+                major: 1,
+                minor: 2,
+                patch: 3,
+                pre: (),
+                build: (),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        pre_release_and_build {
+            args: func_args![value: "v1.0.0-alpha.1+sha.5114f85"],
+            want: Ok(value!({
+                major: 1,
+                minor: 0,
+                patch: 0,
+                pre: "alpha.1",
+                build: "sha.5114f85",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_patch {
+            args: func_args![value: "1.2"],
+            want: Err("unable to parse semantic version: unexpected end of input while parsing minor version number"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leading_zero {
+            args: func_args![value: "1.02.3"],
+            want: Err("unable to parse semantic version: invalid leading zero in minor version number"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        too_large {
+            args: func_args![value: "9223372036854775808.0.0"],
+            want: Err("version number 9223372036854775808 is too large"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,131 @@
+use crate::compiler::prelude::*;
+use chrono::DateTime;
+use std::collections::BTreeMap;
+
+use super::ulid::decode;
+
+fn parse_ulid(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let (timestamp, randomness) =
+        decode(&string).map_err(|err| format!("unable to parse ULID: {err}"))?;
+    let timestamp = DateTime::from_timestamp_millis(timestamp as i64)
+        .ok_or("unable to parse ULID: timestamp is out of range")?;
+
+    let mut map = BTreeMap::<&str, Value>::new();
+    map.insert("timestamp", timestamp.into());
+    map.insert("randomness", hex::encode(randomness).into());
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseUlid;
+
+impl Function for ParseUlid {
+    fn identifier(&self) -> &'static str {
+        "parse_ulid"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse ULID",
+            source: r#"parse_ulid!("01ARZ3NDEKTSV4RRFFQ69G5FAV")"#,
+            result: Ok(
+                r#"{ "randomness": "d6764c61efb99302bd5b", "timestamp": "2016-07-30T23:54:10.259Z" }"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseUlidFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseUlidFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseUlidFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_ulid(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("timestamp".into(), Kind::timestamp()),
+        ("randomness".into(), Kind::bytes()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use chrono::Utc;
+
+    fn timestamp(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    test_function![
+        parse_ulid => ParseUlid;
+
+        uppercase {
+            args: func_args![value: "01ARZ3NDEKTSV4RRFFQ69G5FAV"],
+            want: Ok(value!({
+                timestamp: (timestamp("2016-07-30T23:54:10.259Z")),
+                randomness: "d6764c61efb99302bd5b",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        lowercase {
+            args: func_args![value: "01arz3ndektsv4rrffq69g5fav"],
+            want: Ok(value!({
+                timestamp: (timestamp("2016-07-30T23:54:10.259Z")),
+                randomness: "d6764c61efb99302bd5b",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_character {
+            args: func_args![value: "01ARZ3NDEKTSV4RRFFQ69G5FAU"],
+            want: Err("unable to parse ULID: invalid character 'U' at position 25"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_length {
+            args: func_args![value: "01ARZ3NDEK"],
+            want: Err("unable to parse ULID: expected 26 characters, got 10"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
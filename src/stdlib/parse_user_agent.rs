@@ -3,7 +3,7 @@ use once_cell::sync::Lazy;
 use std::{
     borrow::{Borrow, Cow},
     collections::BTreeMap,
-    fmt,
+    fmt, io,
     str::FromStr,
     sync::Arc,
 };
@@ -15,6 +15,50 @@ static UA_PARSER: Lazy<UAParser> = Lazy::new(|| {
     UAParser::from_bytes(regexes).expect("Regex file is not valid.")
 });
 
+/// User agent definitions used by `parse_user_agent` in the `full` mode instead
+/// of the bundled ones, in the format of the `regexes.yaml` file of the
+/// [uap-core] project. Register them with [`CompileConfig::set_custom`], so
+/// that hosts can pick up new clients and bots without a release of this crate.
+///
+/// Programs keep the definitions they were compiled with, so to refresh them,
+/// register the new definitions and compile the programs again.
+///
+/// [uap-core]: https://github.com/ua-parser/uap-core
+/// [`CompileConfig::set_custom`]: crate::compiler::CompileConfig::set_custom
+#[derive(Clone)]
+pub struct UserAgentRegexes(Arc<UAParser>);
+
+impl UserAgentRegexes {
+    /// Loads the definitions from the contents of a `regexes.yaml` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the definitions aren't valid YAML, or contain an
+    /// invalid regex.
+    pub fn from_bytes(regexes: &[u8]) -> io::Result<Self> {
+        UAParser::from_bytes(regexes)
+            .map(|parser| Self(Arc::new(parser)))
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+    }
+
+    /// Loads the definitions from a reader, such as a `regexes.yaml` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader fails, or the definitions aren't valid.
+    pub fn from_reader(mut reader: impl io::Read) -> io::Result<Self> {
+        let mut regexes = Vec::new();
+        reader.read_to_end(&mut regexes)?;
+        Self::from_bytes(&regexes)
+    }
+}
+
+impl fmt::Debug for UserAgentRegexes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("UserAgentRegexes")
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ParseUserAgent;
 
@@ -33,6 +77,10 @@ impl Function for ParseUserAgent {
 
             Parses on the basis of best effort. Returned schema depends only on the configured `mode`,
             so if the function fails to parse a field it will set it to `null`.
+
+            The `full` mode returns the same fields as the `enriched` mode, plus `is_bot` for crawlers
+            and other automated clients. It uses the user agent definitions provided by the host if
+            there are any, and the bundled ones otherwise.
         "}
     }
 
@@ -81,13 +129,18 @@ impl Function for ParseUserAgent {
                     r#"{ "browser": { "family": "ESPN", "major": null, "minor": null, "patch": null, "version": "33.0.0.0" }, "device": { "brand": "HP", "category": "smartphone", "family": "HP Slate 17", "model": "Slate 17" }, "os": { "family": "Android", "major": "4", "minor": "4", "patch": "4", "patch_minor": null, "version": "4.4.4" } }"#,
                 ),
             },
+            Example {
+                title: "bot detection",
+                source: r#"parse_user_agent("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)", mode: "full").is_bot"#,
+                result: Ok("true"),
+            },
         ]
     }
 
     fn compile(
         &self,
         state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
@@ -131,6 +184,18 @@ impl Function for ParseUserAgent {
                         .full_schema()
                 }) as Arc<_>
             }
+            Mode::Full => {
+                let fast = WootheeParser::new();
+                let regexes = ctx.get_external_context::<UserAgentRegexes>().cloned();
+
+                Arc::new(move |s: &str| {
+                    let slow = regexes.as_ref().map_or(&*UA_PARSER, |regexes| &*regexes.0);
+
+                    slow.parse_user_agent(s)
+                        .or(fast.parse_user_agent(s))
+                        .bot_schema()
+                }) as Arc<_>
+            }
         };
 
         Ok(ParseUserAgentFn {
@@ -177,25 +242,27 @@ pub(crate) enum Mode {
     Fast,
     Reliable,
     Enriched,
+    Full,
 }
 
 impl Mode {
     fn all_value() -> Vec<Value> {
-        use Mode::{Enriched, Fast, Reliable};
+        use Mode::{Enriched, Fast, Full, Reliable};
 
-        vec![Fast, Reliable, Enriched]
+        vec![Fast, Reliable, Enriched, Full]
             .into_iter()
             .map(|u| u.as_str().into())
             .collect::<Vec<_>>()
     }
 
     const fn as_str(self) -> &'static str {
-        use Mode::{Enriched, Fast, Reliable};
+        use Mode::{Enriched, Fast, Full, Reliable};
 
         match self {
             Fast => "fast",
             Reliable => "reliable",
             Enriched => "enriched",
+            Full => "full",
         }
     }
 
@@ -256,6 +323,16 @@ impl Mode {
                     ])),
                 ),
             ])),
+            Mode::Full => {
+                let mut type_def = Mode::Enriched.type_def();
+                type_def
+                    .kind_mut()
+                    .as_object_mut()
+                    .expect("object")
+                    .known_mut()
+                    .insert("is_bot".into(), Kind::boolean());
+                type_def
+            }
         }
     }
 }
@@ -270,12 +347,13 @@ impl FromStr for Mode {
     type Err = &'static str;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        use Mode::{Enriched, Fast, Reliable};
+        use Mode::{Enriched, Fast, Full, Reliable};
 
         match s {
             "fast" => Ok(Fast),
             "reliable" => Ok(Reliable),
             "enriched" => Ok(Enriched),
+            "full" => Ok(Full),
             _ => Err("unknown mode variant"),
         }
     }
@@ -321,6 +399,22 @@ impl UserAgent {
         .collect()
     }
 
+    fn bot_schema(self) -> Value {
+        let is_bot = self.is_bot();
+        let mut value = self.full_schema();
+        value
+            .as_object_mut_unwrap()
+            .insert("is_bot".into(), is_bot.into());
+        value
+    }
+
+    /// Crawlers are reported as the "Spider" device by the user agent
+    /// definitions, and as the "crawler" category by woothee.
+    fn is_bot(&self) -> bool {
+        self.device.family.as_deref() == Some("Spider")
+            || self.device.category.as_deref() == Some("crawler")
+    }
+
     fn or(self, other: Self) -> Self {
         Self {
             browser: self.browser.or(other.browser),
@@ -556,6 +650,7 @@ impl Parser for UAParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::{compile_with_external, state::ExternalEnv, CompileConfig, TimeZone};
     use crate::value;
 
     test_function![
@@ -572,5 +667,82 @@ mod tests {
             want: Ok(value!({ browser: { family: null, major: null, minor: null, patch: null, version: null }, device: { brand: null, category: null, family: null, model: null }, os: { family: null, major: null, minor: null, patch: null, patch_minor: null, version: null } })),
             tdef: Mode::Enriched.type_def(),
         }
+
+        full_unknown_user_agent {
+            args: func_args![ value: "w3m/0.3", mode: "full"],
+            want: Ok(value!({ browser: { family: null, major: null, minor: null, patch: null, version: null }, device: { brand: null, category: null, family: null, model: null }, is_bot: false, os: { family: null, major: null, minor: null, patch: null, patch_minor: null, version: null } })),
+            tdef: Mode::Full.type_def(),
+        }
     ];
+
+    fn resolve_with_regexes(source: &str, regexes: Option<UserAgentRegexes>) -> Value {
+        let mut config = CompileConfig::default();
+        if let Some(regexes) = regexes {
+            config.set_custom(regexes);
+        }
+        let program = compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &ExternalEnv::default(),
+            config,
+        )
+        .expect("compiles")
+        .program;
+
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let mut ctx = Context::new(&mut target, &mut state, &TimeZone::default());
+        program.resolve(&mut ctx).expect("resolves")
+    }
+
+    #[test]
+    fn full_mode_detects_bots() {
+        let value = resolve_with_regexes(
+            r#"[
+                parse_user_agent("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)", mode: "full").is_bot,
+                parse_user_agent("Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)", mode: "full").is_bot,
+                parse_user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:109.0) Gecko/20100101 Firefox/115.0", mode: "full").is_bot,
+            ]"#,
+            None,
+        );
+
+        assert_eq!(value, value!([true, true, false]));
+    }
+
+    #[test]
+    fn full_mode_custom_regexes() {
+        let regexes = UserAgentRegexes::from_reader(
+            indoc! {r"
+                user_agent_parsers:
+                  - regex: '(Acmeview)/(\d+)\.(\d+)'
+                os_parsers: []
+                device_parsers:
+                  - regex: 'Acmeview'
+                    device_replacement: 'Spider'
+            "}
+            .as_bytes(),
+        )
+        .expect("valid regexes");
+        let source = r#"
+            ua = parse_user_agent("Acmeview/1.2", mode: "full")
+            [ua.browser.family, ua.browser.major, ua.browser.minor, ua.is_bot]
+        "#;
+
+        assert_eq!(
+            resolve_with_regexes(source, Some(regexes)),
+            value!(["Acmeview", "1", "2", true])
+        );
+        assert_eq!(
+            resolve_with_regexes(
+                r#"parse_user_agent("Acmeview/1.2", mode: "full").is_bot"#,
+                None
+            ),
+            value!(false)
+        );
+    }
+
+    #[test]
+    fn invalid_regexes() {
+        assert!(UserAgentRegexes::from_bytes(b"user_agent_parsers: [{ regex: '(' }]").is_err());
+    }
 }
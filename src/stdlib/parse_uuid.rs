@@ -0,0 +1,123 @@
+use crate::compiler::prelude::*;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::uuid_v5::encode;
+
+fn parse_uuid(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let uuid =
+        Uuid::parse_str(string.trim()).map_err(|err| format!("unable to parse UUID: {err}"))?;
+
+    let mut map = BTreeMap::<&str, Value>::new();
+    map.insert("uuid", encode(uuid));
+    map.insert("version", (uuid.get_version_num() as i64).into());
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseUuid;
+
+impl Function for ParseUuid {
+    fn identifier(&self) -> &'static str {
+        "parse_uuid"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "braced uppercase",
+            source: r#"parse_uuid!("{67E55044-10B1-426F-9247-BB680E5FE0C8}")"#,
+            result: Ok(r#"{ "uuid": "67e55044-10b1-426f-9247-bb680e5fe0c8", "version": 4 }"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseUuidFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseUuidFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseUuidFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_uuid(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("uuid".into(), Kind::bytes()),
+        ("version".into(), Kind::integer()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_uuid => ParseUuid;
+
+        hyphenated {
+            args: func_args![value: "886313e1-3b8a-5372-9b90-0c9aee199e5d"],
+            want: Ok(value!({ uuid: "886313e1-3b8a-5372-9b90-0c9aee199e5d", version: 5 })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        simple {
+            args: func_args![value: "6FA459EAEE8A3CA4894EDB77E160355E"],
+            want: Ok(value!({ uuid: "6fa459ea-ee8a-3ca4-894e-db77e160355e", version: 3 })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        urn {
+            args: func_args![value: "urn:uuid:0192e2e6-b7d2-7c2e-8a51-cd4c6d2e1f10"],
+            want: Ok(value!({ uuid: "0192e2e6-b7d2-7c2e-8a51-cd4c6d2e1f10", version: 7 })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        nil {
+            args: func_args![value: "00000000-0000-0000-0000-000000000000"],
+            want: Ok(value!({ uuid: "00000000-0000-0000-0000-000000000000", version: 0 })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+
+    #[test]
+    fn invalid() {
+        let error = parse_uuid("886313e1-3b8a-5372-9b90".into())
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.starts_with("unable to parse UUID: "), "{error}");
+    }
+}
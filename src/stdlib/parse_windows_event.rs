@@ -0,0 +1,563 @@
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+use crate::compiler::prelude::*;
+
+/// Standard keywords defined by `winmeta.xml`. The remaining bits are either
+/// provider-specific or reserved for the channel, and have no standard name.
+const STANDARD_KEYWORDS: [(u64, &str); 8] = [
+    (0x0001_0000_0000_0000, "Response Time"),
+    (0x0002_0000_0000_0000, "WDI Context"),
+    (0x0004_0000_0000_0000, "WDI Diag"),
+    (0x0008_0000_0000_0000, "SQM"),
+    (0x0010_0000_0000_0000, "Audit Failure"),
+    (0x0020_0000_0000_0000, "Audit Success"),
+    (0x0040_0000_0000_0000, "Correlation Hint"),
+    (0x0080_0000_0000_0000, "Classic"),
+];
+
+fn decode_keywords(mask: &str) -> Result<Vec<Value>, String> {
+    let digits = mask
+        .strip_prefix("0x")
+        .or_else(|| mask.strip_prefix("0X"))
+        .unwrap_or(mask);
+    let mask = u64::from_str_radix(digits, 16)
+        .map_err(|err| format!("invalid event keywords {mask:?}: {err}"))?;
+
+    Ok(STANDARD_KEYWORDS
+        .iter()
+        .filter(|(bit, _)| mask & bit != 0)
+        .map(|(_, name)| Value::from(*name))
+        .collect())
+}
+
+fn parse_integer(field: &str, value: &str) -> Result<Value, String> {
+    value
+        .trim()
+        .parse::<i64>()
+        .map(Value::from)
+        .map_err(|err| format!("invalid {field} {value:?}: {err}"))
+}
+
+fn parse_timestamp(value: &str) -> Result<Value, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| Value::from(timestamp.with_timezone(&Utc)))
+        .map_err(|err| format!("invalid event timestamp {value:?}: {err}"))
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|child| child.text())
+}
+
+/// Converts arbitrary XML, such as `UserData`, to an object keyed by element
+/// name, with leaf elements replaced by their text.
+fn element_value(node: Node) -> Value {
+    let mut children = node.children().filter(Node::is_element).peekable();
+    if children.peek().is_none() {
+        return node.text().unwrap_or_default().into();
+    }
+
+    children
+        .map(|child| (child.tag_name().name().into(), element_value(child)))
+        .collect::<ObjectMap>()
+        .into()
+}
+
+/// Flattens `EventData` to an object. Unnamed `Data` elements are keyed by their
+/// position (`param1`, `param2`, ...), the same way winlogbeat does.
+fn event_data(node: Node) -> ObjectMap {
+    let mut data = ObjectMap::new();
+
+    for (index, element) in node.children().filter(Node::is_element).enumerate() {
+        let key = match (element.tag_name().name(), element.attribute("Name")) {
+            ("Data", Some(name)) => name.to_owned(),
+            ("Data", None) => format!("param{}", index + 1),
+            (name, _) => name.to_owned(),
+        };
+        data.insert(key.into(), element.text().unwrap_or_default().into());
+    }
+
+    data
+}
+
+fn rendering_info(node: Node) -> ObjectMap {
+    let mut info = ObjectMap::new();
+
+    for (field, element) in [
+        ("message", "Message"),
+        ("level", "Level"),
+        ("task", "Task"),
+        ("opcode", "Opcode"),
+        ("channel", "Channel"),
+        ("provider", "Provider"),
+    ] {
+        if let Some(text) = child_text(node, element) {
+            info.insert(field.into(), text.into());
+        }
+    }
+    if let Some(keywords) = child(node, "Keywords") {
+        let keywords = keywords
+            .children()
+            .filter(|child| child.is_element() && child.tag_name().name() == "Keyword")
+            .filter_map(|child| child.text())
+            .map(Value::from)
+            .collect::<Vec<_>>();
+        info.insert("keywords".into(), keywords.into());
+    }
+
+    info
+}
+
+fn parse_event_xml(xml: &str) -> Result<ObjectMap, String> {
+    let document =
+        Document::parse(xml).map_err(|err| format!("unable to parse windows event xml: {err}"))?;
+    let event = document.root_element();
+    if event.tag_name().name() != "Event" {
+        return Err("windows event xml must have an Event root element".to_owned());
+    }
+    let system = child(event, "System").ok_or("windows event xml has no System element")?;
+
+    let mut result = ObjectMap::new();
+
+    if let Some(provider) = child(system, "Provider") {
+        let provider = [
+            ("name", "Name"),
+            ("guid", "Guid"),
+            ("event_source_name", "EventSourceName"),
+        ]
+        .into_iter()
+        .filter_map(|(field, attribute)| {
+            provider
+                .attribute(attribute)
+                .map(|value| (field.into(), value.into()))
+        })
+        .collect::<ObjectMap>();
+        result.insert("provider".into(), provider.into());
+    }
+
+    for (field, element) in [
+        ("event_id", "EventID"),
+        ("version", "Version"),
+        ("level", "Level"),
+        ("task", "Task"),
+        ("opcode", "Opcode"),
+        ("record_id", "EventRecordID"),
+    ] {
+        if let Some(text) = child_text(system, element) {
+            result.insert(field.into(), parse_integer(field, text)?);
+        }
+    }
+    for (field, element) in [("channel", "Channel"), ("computer", "Computer")] {
+        if let Some(text) = child_text(system, element) {
+            result.insert(field.into(), text.into());
+        }
+    }
+    if let Some(keywords) = child_text(system, "Keywords") {
+        result.insert("keywords".into(), decode_keywords(keywords)?.into());
+    }
+    if let Some(time) = child(system, "TimeCreated").and_then(|node| node.attribute("SystemTime")) {
+        result.insert("time_created".into(), parse_timestamp(time)?);
+    }
+    if let Some(correlation) = child(system, "Correlation") {
+        let correlation = [
+            ("activity_id", "ActivityID"),
+            ("related_activity_id", "RelatedActivityID"),
+        ]
+        .into_iter()
+        .filter_map(|(field, attribute)| {
+            correlation
+                .attribute(attribute)
+                .map(|value| (field.into(), value.into()))
+        })
+        .collect::<ObjectMap>();
+        if !correlation.is_empty() {
+            result.insert("correlation".into(), correlation.into());
+        }
+    }
+    if let Some(execution) = child(system, "Execution") {
+        for (field, attribute) in [("process_id", "ProcessID"), ("thread_id", "ThreadID")] {
+            if let Some(value) = execution.attribute(attribute) {
+                result.insert(field.into(), parse_integer(field, value)?);
+            }
+        }
+    }
+    if let Some(user_id) = child(system, "Security").and_then(|node| node.attribute("UserID")) {
+        result.insert("user_id".into(), user_id.into());
+    }
+
+    if let Some(data) = child(event, "EventData") {
+        result.insert("event_data".into(), event_data(data).into());
+    }
+    if let Some(data) = child(event, "UserData") {
+        result.insert("user_data".into(), element_value(data));
+    }
+    if let Some(info) = child(event, "RenderingInfo") {
+        result.insert("rendering_info".into(), rendering_info(info).into());
+    }
+
+    Ok(result)
+}
+
+fn get<'a>(object: &'a ObjectMap, path: &[&str]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    let value = object.get(*first)?;
+    match rest {
+        [] => Some(value),
+        _ => get(value.as_object()?, rest),
+    }
+}
+
+fn integer(field: &str, value: &Value) -> Result<Value, String> {
+    match value {
+        Value::Integer(_) => Ok(value.clone()),
+        Value::Bytes(bytes) => parse_integer(field, &String::from_utf8_lossy(bytes)),
+        _ => Err(format!(
+            "invalid {field}: expected integer, got {}",
+            value.kind()
+        )),
+    }
+}
+
+/// Normalizes an event in the shape produced by winlogbeat, either the whole
+/// event or only its `winlog` object.
+fn parse_event_winlogbeat(event: &ObjectMap) -> Result<ObjectMap, String> {
+    let winlog = match event.get("winlog") {
+        Some(Value::Object(winlog)) => winlog,
+        Some(value) => return Err(format!("winlog must be an object, got {}", value.kind())),
+        None => event,
+    };
+
+    let mut result = ObjectMap::new();
+
+    let provider = [("name", "provider_name"), ("guid", "provider_guid")]
+        .into_iter()
+        .filter_map(|(field, key)| winlog.get(key).map(|value| (field.into(), value.clone())))
+        .collect::<ObjectMap>();
+    if !provider.is_empty() {
+        result.insert("provider".into(), provider.into());
+    }
+
+    for (field, path) in [
+        ("event_id", &["event_id"][..]),
+        ("version", &["version"]),
+        ("record_id", &["record_id"]),
+        ("process_id", &["process", "pid"]),
+        ("thread_id", &["process", "thread", "id"]),
+    ] {
+        if let Some(value) = get(winlog, path) {
+            result.insert(field.into(), integer(field, value)?);
+        }
+    }
+    for (field, path) in [
+        ("channel", &["channel"][..]),
+        ("computer", &["computer_name"]),
+        ("keywords", &["keywords"]),
+        ("user_id", &["user", "identifier"]),
+        ("event_data", &["event_data"]),
+        ("user_data", &["user_data"]),
+    ] {
+        if let Some(value) = get(winlog, path) {
+            result.insert(field.into(), value.clone());
+        }
+    }
+    if let Some(timestamp) = event.get("@timestamp") {
+        let timestamp = match timestamp {
+            Value::Timestamp(_) => timestamp.clone(),
+            Value::Bytes(bytes) => parse_timestamp(&String::from_utf8_lossy(bytes))?,
+            _ => return Err(format!("invalid event timestamp: {}", timestamp.kind())),
+        };
+        result.insert("time_created".into(), timestamp);
+    }
+    let correlation = ["activity_id", "related_activity_id"]
+        .into_iter()
+        .filter_map(|key| winlog.get(key).map(|value| (key.into(), value.clone())))
+        .collect::<ObjectMap>();
+    if !correlation.is_empty() {
+        result.insert("correlation".into(), correlation.into());
+    }
+
+    // winlogbeat only keeps the rendered names of the level, task and opcode.
+    let rendering_info = [
+        ("message", &["message"][..]),
+        ("level", &["log", "level"]),
+        ("task", &["winlog", "task"]),
+        ("opcode", &["winlog", "opcode"]),
+    ]
+    .into_iter()
+    .filter_map(|(field, path)| {
+        get(event, path)
+            .or_else(|| get(winlog, &path[path.len() - 1..]))
+            .map(|value| (field.into(), value.clone()))
+    })
+    .collect::<ObjectMap>();
+    if !rendering_info.is_empty() {
+        result.insert("rendering_info".into(), rendering_info.into());
+    }
+
+    Ok(result)
+}
+
+fn parse_windows_event(value: Value) -> Resolved {
+    let event = match value {
+        Value::Bytes(bytes) => parse_event_xml(&String::from_utf8_lossy(&bytes))?,
+        Value::Object(object) => parse_event_winlogbeat(&object)?,
+        value => {
+            return Err(ValueError::Expected {
+                got: value.kind(),
+                expected: Kind::bytes() | Kind::object(Collection::any()),
+            }
+            .into())
+        }
+    };
+
+    Ok(event.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseWindowsEvent;
+
+impl Function for ParseWindowsEvent {
+    fn identifier(&self) -> &'static str {
+        "parse_windows_event"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES | kind::OBJECT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "event XML",
+                source: indoc! {r#"
+                    parse_windows_event!(s'<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Microsoft-Windows-Security-Auditing" /><EventID>4624</EventID><Level>0</Level><Keywords>0x8020000000000000</Keywords><Channel>Security</Channel></System><EventData><Data Name="TargetUserName">alice</Data></EventData></Event>')
+                "#},
+                result: Ok(
+                    r#"{"channel": "Security", "event_data": {"TargetUserName": "alice"}, "event_id": 4624, "keywords": ["Audit Success"], "level": 0, "provider": {"name": "Microsoft-Windows-Security-Auditing"}}"#,
+                ),
+            },
+            Example {
+                title: "winlogbeat event",
+                source: r#"parse_windows_event!({"winlog": {"provider_name": "Service Control Manager", "event_id": "7036", "channel": "System", "event_data": {"param1": "Windows Update", "param2": "running"}}, "log": {"level": "information"}})"#,
+                result: Ok(
+                    r#"{"channel": "System", "event_data": {"param1": "Windows Update", "param2": "running"}, "event_id": 7036, "provider": {"name": "Service Control Manager"}, "rendering_info": {"level": "information"}}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseWindowsEventFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseWindowsEventFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseWindowsEventFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_windows_event(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    const SECURITY_EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-A5BA-3E3B0328C30D}" />
+    <EventID>4624</EventID>
+    <Version>2</Version>
+    <Level>0</Level>
+    <Task>12544</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8020000000000000</Keywords>
+    <TimeCreated SystemTime="2015-09-10T19:31:51.1530930Z" />
+    <EventRecordID>1283</EventRecordID>
+    <Correlation ActivityID="{0D4C8B6A-1AD7-0001-E38C-4C0DD71AD101}" />
+    <Execution ProcessID="500" ThreadID="1428" />
+    <Channel>Security</Channel>
+    <Computer>WIN-GG82ULGC9GO</Computer>
+    <Security />
+  </System>
+  <EventData>
+    <Data Name="SubjectUserSid">S-1-5-18</Data>
+    <Data Name="TargetUserName">alice</Data>
+    <Data Name="LogonType">2</Data>
+    <Data Name="IpAddress">-</Data>
+  </EventData>
+  <RenderingInfo Culture="en-US">
+    <Message>An account was successfully logged on.</Message>
+    <Level>Information</Level>
+    <Task>Logon</Task>
+    <Opcode>Info</Opcode>
+    <Channel>Security</Channel>
+    <Provider>Microsoft Windows security auditing.</Provider>
+    <Keywords>
+      <Keyword>Audit Success</Keyword>
+    </Keywords>
+  </RenderingInfo>
+</Event>"#;
+
+    test_function![
+        parse_windows_event => ParseWindowsEvent;
+
+        security_xml {
+            args: func_args![value: SECURITY_EVENT],
+            want: Ok(value!({
+                "provider": {
+                    "name": "Microsoft-Windows-Security-Auditing",
+                    "guid": "{54849625-5478-4994-A5BA-3E3B0328C30D}",
+                },
+                "event_id": 4624,
+                "version": 2,
+                "level": 0,
+                "task": 12544,
+                "opcode": 0,
+                "keywords": ["Audit Success"],
+                "time_created": (DateTime::parse_from_rfc3339("2015-09-10T19:31:51.153093Z").unwrap().with_timezone(&Utc)),
+                "record_id": 1283,
+                "correlation": {"activity_id": "{0D4C8B6A-1AD7-0001-E38C-4C0DD71AD101}"},
+                "process_id": 500,
+                "thread_id": 1428,
+                "channel": "Security",
+                "computer": "WIN-GG82ULGC9GO",
+                "event_data": {
+                    "SubjectUserSid": "S-1-5-18",
+                    "TargetUserName": "alice",
+                    "LogonType": "2",
+                    "IpAddress": "-",
+                },
+                "rendering_info": {
+                    "message": "An account was successfully logged on.",
+                    "level": "Information",
+                    "task": "Logon",
+                    "opcode": "Info",
+                    "channel": "Security",
+                    "provider": "Microsoft Windows security auditing.",
+                    "keywords": ["Audit Success"],
+                },
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        classic_xml {
+            args: func_args![value: r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Application Error" EventSourceName="Application Error" /><EventID Qualifiers="0">1000</EventID><Level>2</Level><Keywords>0x80000000000000</Keywords><Security UserID="S-1-5-18" /></System><EventData><Data>app.exe</Data><Data>1.0.0.0</Data><Binary>00FF</Binary></EventData></Event>"#],
+            want: Ok(value!({
+                "provider": {"name": "Application Error", "event_source_name": "Application Error"},
+                "event_id": 1000,
+                "level": 2,
+                "keywords": ["Classic"],
+                "user_id": "S-1-5-18",
+                "event_data": {"param1": "app.exe", "param2": "1.0.0.0", "Binary": "00FF"},
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        user_data_xml {
+            args: func_args![value: r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><EventID>1102</EventID></System><UserData><LogFileCleared xmlns="http://manifests.microsoft.com/win/2004/08/windows/eventlog"><SubjectUserName>admin</SubjectUserName><SubjectDomainName>CORP</SubjectDomainName></LogFileCleared></UserData></Event>"#],
+            want: Ok(value!({
+                "event_id": 1102,
+                "user_data": {"LogFileCleared": {"SubjectUserName": "admin", "SubjectDomainName": "CORP"}},
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        winlogbeat {
+            args: func_args![value: value!({
+                "@timestamp": "2019-03-18T16:57:38.636Z",
+                "message": "The Windows Update service entered the running state.",
+                "log": {"level": "information"},
+                "winlog": {
+                    "provider_name": "Service Control Manager",
+                    "provider_guid": "{555908d1-a6d7-4695-8e1e-26931d2012f4}",
+                    "event_id": "7036",
+                    "channel": "System",
+                    "computer_name": "vagrant",
+                    "record_id": 22180,
+                    "keywords": ["Classic"],
+                    "task": "",
+                    "opcode": "Info",
+                    "process": {"pid": 620, "thread": {"id": 3388}},
+                    "event_data": {"param1": "Windows Update", "param2": "running"},
+                },
+            })],
+            want: Ok(value!({
+                "provider": {
+                    "name": "Service Control Manager",
+                    "guid": "{555908d1-a6d7-4695-8e1e-26931d2012f4}",
+                },
+                "event_id": 7036,
+                "record_id": 22180,
+                "process_id": 620,
+                "thread_id": 3388,
+                "channel": "System",
+                "computer": "vagrant",
+                "keywords": ["Classic"],
+                "time_created": (DateTime::parse_from_rfc3339("2019-03-18T16:57:38.636Z").unwrap().with_timezone(&Utc)),
+                "event_data": {"param1": "Windows Update", "param2": "running"},
+                "rendering_info": {
+                    "message": "The Windows Update service entered the running state.",
+                    "level": "information",
+                    "task": "",
+                    "opcode": "Info",
+                },
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        winlog_object {
+            args: func_args![value: value!({"provider_name": "Microsoft-Windows-Sysmon", "event_id": 1, "event_data": {"Image": "C:\\Windows\\System32\\cmd.exe"}})],
+            want: Ok(value!({
+                "provider": {"name": "Microsoft-Windows-Sysmon"},
+                "event_id": 1,
+                "event_data": {"Image": "C:\\Windows\\System32\\cmd.exe"},
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        not_an_event {
+            args: func_args![value: "<Events><Event /></Events>"],
+            want: Err("windows event xml must have an Event root element"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        missing_system {
+            args: func_args![value: "<Event />"],
+            want: Err("windows event xml has no System element"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        invalid_event_id {
+            args: func_args![value: "<Event><System><EventID>abc</EventID></System></Event>"],
+            want: Err(r#"invalid event_id "abc": invalid digit found in string"#),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
+use crate::compiler::prelude::*;
+use chrono::DateTime;
+use sha1::Sha1;
+use sha_2::{Digest, Sha256};
+use x509_parser::{
+    certificate::X509Certificate, extensions::GeneralName, pem::parse_x509_pem, time::ASN1Time,
+};
+
+fn timestamp(time: ASN1Time) -> Result<Value, String> {
+    DateTime::from_timestamp(time.timestamp(), 0)
+        .map(Value::Timestamp)
+        .ok_or_else(|| format!("certificate validity time {time} is out of range"))
+}
+
+fn ip_address(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => <[u8; 4]>::try_from(bytes).ok().map(IpAddr::from),
+        16 => <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from),
+        _ => None,
+    }
+}
+
+fn subject_alt_names(certificate: &X509Certificate) -> Result<ObjectMap, String> {
+    let mut dns = Vec::new();
+    let mut ip = Vec::new();
+    let mut email = Vec::new();
+    let mut uri = Vec::new();
+
+    let extension = certificate
+        .subject_alternative_name()
+        .map_err(|err| format!("invalid subject alternative name extension: {err}"))?;
+    for name in extension.iter().flat_map(|ext| &ext.value.general_names) {
+        match name {
+            GeneralName::DNSName(name) => dns.push(Value::from(*name)),
+            GeneralName::IPAddress(bytes) => {
+                if let Some(address) = ip_address(bytes) {
+                    ip.push(Value::from(address.to_string()));
+                }
+            }
+            GeneralName::RFC822Name(name) => email.push(Value::from(*name)),
+            GeneralName::URI(name) => uri.push(Value::from(*name)),
+            _ => {}
+        }
+    }
+
+    Ok(ObjectMap::from([
+        ("dns".into(), Value::Array(dns)),
+        ("ip".into(), Value::Array(ip)),
+        ("email".into(), Value::Array(email)),
+        ("uri".into(), Value::Array(uri)),
+    ]))
+}
+
+fn certificate_to_value(der: &[u8], certificate: &X509Certificate) -> Result<Value, String> {
+    let validity = certificate.validity();
+
+    Ok(Value::from(ObjectMap::from([
+        ("subject".into(), certificate.subject().to_string().into()),
+        ("issuer".into(), certificate.issuer().to_string().into()),
+        (
+            "subject_alt_names".into(),
+            subject_alt_names(certificate)?.into(),
+        ),
+        ("not_before".into(), timestamp(validity.not_before)?),
+        ("not_after".into(), timestamp(validity.not_after)?),
+        ("serial".into(), certificate.serial.to_str_radix(16).into()),
+        (
+            "fingerprints".into(),
+            Value::from(ObjectMap::from([
+                ("sha1".into(), hex::encode(Sha1::digest(der)).into()),
+                ("sha256".into(), hex::encode(Sha256::digest(der)).into()),
+            ])),
+        ),
+    ])))
+}
+
+fn parse_der(der: &[u8]) -> Result<Value, String> {
+    let (_, certificate) = x509_parser::parse_x509_certificate(der)
+        .map_err(|err| format!("unable to parse X.509 certificate: {err}"))?;
+
+    certificate_to_value(der, &certificate)
+}
+
+/// Parses a PEM or DER encoded X.509 certificate. Only the first certificate
+/// of a PEM bundle is parsed.
+fn parse_x509(bytes: &[u8]) -> Result<Value, String> {
+    if !bytes.trim_ascii_start().starts_with(b"-----BEGIN") {
+        return parse_der(bytes);
+    }
+
+    let (_, pem) =
+        parse_x509_pem(bytes).map_err(|err| format!("unable to parse PEM block: {err}"))?;
+    if pem.label != "CERTIFICATE" {
+        return Err(format!(
+            r#"expected a PEM encoded certificate, got "{}""#,
+            pem.label
+        ));
+    }
+
+    parse_der(&pem.contents)
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    let strings = Kind::array(Collection::from_unknown(Kind::bytes()));
+
+    BTreeMap::from([
+        ("subject".into(), Kind::bytes()),
+        ("issuer".into(), Kind::bytes()),
+        (
+            "subject_alt_names".into(),
+            Kind::object(BTreeMap::from([
+                ("dns".into(), strings.clone()),
+                ("ip".into(), strings.clone()),
+                ("email".into(), strings.clone()),
+                ("uri".into(), strings),
+            ])),
+        ),
+        ("not_before".into(), Kind::timestamp()),
+        ("not_after".into(), Kind::timestamp()),
+        ("serial".into(), Kind::bytes()),
+        (
+            "fingerprints".into(),
+            Kind::object(BTreeMap::from([
+                ("sha1".into(), Kind::bytes()),
+                ("sha256".into(), Kind::bytes()),
+            ])),
+        ),
+    ])
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseX509;
+
+impl Function for ParseX509 {
+    fn identifier(&self) -> &'static str {
+        "parse_x509"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse PEM certificate",
+            source: indoc! {r#"
+                cert = parse_x509!(s'-----BEGIN CERTIFICATE-----
+                MIIBuDCCAV6gAwIBAgIEGis8TTAKBggqhkjOPQQDAjAiMQswCQYDVQQGEwJVUzET
+                MBEGA1UECgwKRXhhbXBsZSBDQTAeFw0yNDAxMDEwMDAwMDBaFw0yNTAxMDEwMDAw
+                MDBaMDoxCzAJBgNVBAYTAlVTMRUwEwYDVQQKDAxFeGFtcGxlIENvcnAxFDASBgNV
+                BAMMC2V4YW1wbGUuY29tMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE+1A4jylJ
+                jQqTrSXsTDQDe508w8ykeH62/tq+KzAD6sifd2XKnWKI5v9zT1zQjzpZIc9Ushuz
+                mLUKwNJXf6B0cqNqMGgwZgYDVR0RBF8wXYILZXhhbXBsZS5jb22CDSouZXhhbXBs
+                ZS5jb22HBMAAAgGHECABDbgAAAAAAAAAAAAAAAGBEWFkbWluQGV4YW1wbGUuY29t
+                hhRodHRwczovL2V4YW1wbGUuY29tLzAKBggqhkjOPQQDAgNIADBFAiEAt2a2Wbf6
+                fUqEjGPs9HeQfh1+kUD2dPxjz/16TKz3MXcCIB1U4nzUJqmcUl/kUdLF5Qr/hrNS
+                FjoPZ021MogfLp4h
+                -----END CERTIFICATE-----')
+                [cert.subject, cert.subject_alt_names.dns, cert.serial]
+            "#},
+            result: Ok(
+                r#"["C=US, O=Example Corp, CN=example.com", ["example.com", "*.example.com"], "1a2b3c4d"]"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseX509Fn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseX509Fn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseX509Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?.try_bytes()?;
+
+        Ok(parse_x509(&bytes)?)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use base64::Engine as _;
+    use chrono::{TimeZone, Utc};
+
+    const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBuDCCAV6gAwIBAgIEGis8TTAKBggqhkjOPQQDAjAiMQswCQYDVQQGEwJVUzET
+MBEGA1UECgwKRXhhbXBsZSBDQTAeFw0yNDAxMDEwMDAwMDBaFw0yNTAxMDEwMDAw
+MDBaMDoxCzAJBgNVBAYTAlVTMRUwEwYDVQQKDAxFeGFtcGxlIENvcnAxFDASBgNV
+BAMMC2V4YW1wbGUuY29tMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE+1A4jylJ
+jQqTrSXsTDQDe508w8ykeH62/tq+KzAD6sifd2XKnWKI5v9zT1zQjzpZIc9Ushuz
+mLUKwNJXf6B0cqNqMGgwZgYDVR0RBF8wXYILZXhhbXBsZS5jb22CDSouZXhhbXBs
+ZS5jb22HBMAAAgGHECABDbgAAAAAAAAAAAAAAAGBEWFkbWluQGV4YW1wbGUuY29t
+hhRodHRwczovL2V4YW1wbGUuY29tLzAKBggqhkjOPQQDAgNIADBFAiEAt2a2Wbf6
+fUqEjGPs9HeQfh1+kUD2dPxjz/16TKz3MXcCIB1U4nzUJqmcUl/kUdLF5Qr/hrNS
+FjoPZ021MogfLp4h
+-----END CERTIFICATE-----
+";
+
+    fn der() -> Bytes {
+        let base64 = CERTIFICATE
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect::<String>();
+        base64::engine::general_purpose::STANDARD
+            .decode(base64)
+            .unwrap()
+            .into()
+    }
+
+    fn parsed() -> Value {
+        value!({
+            "subject": "C=US, O=Example Corp, CN=example.com",
+            "issuer": "C=US, O=Example CA",
+            "subject_alt_names": {
+                "dns": ["example.com", "*.example.com"],
+                "ip": ["192.0.2.1", "2001:db8::1"],
+                "email": ["admin@example.com"],
+                "uri": ["https://example.com/"],
+            },
+            "not_before": (Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+            "not_after": (Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+            "serial": "1a2b3c4d",
+            "fingerprints": {
+                "sha1": "2fe1625368e526bce2c1b50731171ff15c5d3214",
+                "sha256": "32e13dc63789b47484401f7b014c1d9b6e5f242dd94e2e0d27f371a9b0d97c6c",
+            },
+        })
+    }
+
+    test_function![
+        parse_x509 => ParseX509;
+
+        pem {
+            args: func_args![value: CERTIFICATE],
+            want: Ok(parsed()),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        der {
+            args: func_args![value: der()],
+            want: Ok(parsed()),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        wrong_pem_label {
+            args: func_args![value: "-----BEGIN PUBLIC KEY-----\nMAA=\n-----END PUBLIC KEY-----\n"],
+            want: Err(r#"expected a PEM encoded certificate, got "PUBLIC KEY""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
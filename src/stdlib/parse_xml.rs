@@ -39,6 +39,7 @@ impl Function for ParseXml {
         let parse_bool = arguments.optional("parse_bool");
         let parse_null = arguments.optional("parse_null");
         let parse_number = arguments.optional("parse_number");
+        let keep_namespace_prefix = arguments.optional("keep_namespace_prefix");
 
         Ok(ParseXmlFn {
             value,
@@ -50,6 +51,7 @@ impl Function for ParseXml {
             parse_bool,
             parse_null,
             parse_number,
+            keep_namespace_prefix,
         }
         .as_expr())
     }
@@ -101,6 +103,11 @@ impl Function for ParseXml {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "keep_namespace_prefix",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 }
@@ -117,6 +124,7 @@ struct ParseXmlFn {
     parse_bool: Option<Box<dyn Expression>>,
     parse_null: Option<Box<dyn Expression>>,
     parse_number: Option<Box<dyn Expression>>,
+    keep_namespace_prefix: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for ParseXmlFn {
@@ -171,6 +179,12 @@ impl FunctionExpression for ParseXmlFn {
                 .as_ref()
                 .map(|expr| expr.resolve(ctx))
                 .transpose()?,
+
+            keep_namespace_prefix: self
+                .keep_namespace_prefix
+                .as_ref()
+                .map(|expr| expr.resolve(ctx))
+                .transpose()?,
         };
 
         parse_xml(value, options)
@@ -474,6 +488,18 @@ mod tests {
             tdef: type_def(),
         }
 
+        strip_namespace_prefix {
+            args: func_args![ value: r#"<s:root xmlns:s="urn:s"><s:a>test</s:a></s:root>"# ],
+            want: Ok(value!({ "root": { "a": "test" } })),
+            tdef: type_def(),
+        }
+
+        keep_namespace_prefix {
+            args: func_args![ value: r#"<s:root xmlns:s="urn:s"><s:a s:id="1">test</s:a></s:root>"#, keep_namespace_prefix: true ],
+            want: Ok(value!({ "s:root": { "s:a": { "@s:id": "1", "text": "test" } } })),
+            tdef: type_def(),
+        }
+
         if_no_sibling2 {
             args: func_args![ value: "<root><a><a1>test</a1></a><b>test2</b></root>"],
             want: Ok(value!({ "root": { "a": { "a1": "test" }, "b" : "test2" } })),
@@ -495,6 +521,7 @@ mod tests {
             parse_bool: None,
             parse_null: None,
             parse_number: None,
+            keep_namespace_prefix: None,
         };
 
         let type_def = func.type_def(&state);
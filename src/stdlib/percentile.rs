@@ -0,0 +1,150 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, floats, interpolate_percentile, numeric_elements};
+
+fn check_percentile(percentile: &Value) -> Result<f64, ExpressionError> {
+    expect_number(percentile)?;
+    let percentile = percentile.try_into_f64()?;
+    if !(0.0..=100.0).contains(&percentile) {
+        return Err(format!("percentile must be between 0 and 100, got {percentile}").into());
+    }
+    Ok(percentile)
+}
+
+fn percentile(value: Value, percentile: &Value) -> Resolved {
+    let percentile = check_percentile(percentile)?;
+    let mut floats = floats(value)?;
+    floats.sort_by(f64::total_cmp);
+
+    Ok(interpolate_percentile(&floats, percentile).map_or(Value::Null, Value::from_f64_or_zero))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Percentile;
+
+impl Function for Percentile {
+    fn identifier(&self) -> &'static str {
+        "percentile"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "percentile",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "95th percentile",
+                source: "percentile([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], 95)",
+                result: Ok("10.5"),
+            },
+            Example {
+                title: "interpolated",
+                source: "percentile([10, 20], 25)",
+                result: Ok("12.5"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let percentile = arguments.required("percentile");
+        let valid_percentile = percentile
+            .resolve_constant(state)
+            .is_some_and(|percentile| check_percentile(&percentile).is_ok());
+
+        Ok(PercentileFn {
+            value,
+            percentile,
+            valid_percentile,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PercentileFn {
+    value: Box<dyn Expression>,
+    percentile: Box<dyn Expression>,
+    /// Whether the percentile is a constant in the valid range.
+    valid_percentile: bool,
+}
+
+impl FunctionExpression for PercentileFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let percentile_value = self.percentile.resolve(ctx)?;
+
+        percentile(value, &percentile_value)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let numeric = numeric_elements(self.value.type_def(state).kind());
+
+        TypeDef::float()
+            .or_null()
+            .maybe_fallible(!numeric || !self.valid_percentile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        percentile => Percentile;
+
+        median {
+            args: func_args![value: value!([3, 1, 2]), percentile: 50],
+            want: Ok(2.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        bounds {
+            args: func_args![value: value!([3, 1, 2]), percentile: 100],
+            want: Ok(3.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        interpolated {
+            args: func_args![value: value!([1, 2, 3, 4]), percentile: 90.0],
+            want: Ok(3.7),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        empty {
+            args: func_args![value: value!([]), percentile: 50],
+            want: Ok(Value::Null),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        out_of_range {
+            args: func_args![value: value!([1, 2]), percentile: 101],
+            want: Err("percentile must be between 0 and 100, got 101"),
+            tdef: TypeDef::float().or_null().fallible(),
+        }
+
+        non_numeric {
+            args: func_args![value: value!([1, "a"]), percentile: 50],
+            want: Err("expected an array of numbers, found string at index 1"),
+            tdef: TypeDef::float().or_null().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,172 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, float_result, numeric_kind};
+
+fn pow(value: Value, exponent: Value) -> Resolved {
+    expect_number(&value)?;
+    expect_number(&exponent)?;
+
+    match (value, exponent) {
+        (Value::Integer(base), Value::Integer(exponent)) => {
+            let exponent = u32::try_from(exponent).map_err(|_| {
+                if exponent < 0 {
+                    format!("can't raise integer {base} to negative power {exponent}")
+                } else {
+                    "integer overflow".to_owned()
+                }
+            })?;
+            base.checked_pow(exponent)
+                .map(Value::from)
+                .ok_or_else(|| "integer overflow".into())
+        }
+        (base, exponent) => {
+            float_result("pow", base.try_into_f64()?.powf(exponent.try_into_f64()?))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Pow;
+
+impl Function for Pow {
+    fn identifier(&self) -> &'static str {
+        "pow"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "exponent",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "integer power",
+                source: "pow!(2, 10)",
+                result: Ok("1024"),
+            },
+            Example {
+                title: "float power",
+                source: "pow!(9.0, 0.5)",
+                result: Ok("3.0"),
+            },
+            Example {
+                title: "integer overflow",
+                source: "pow!(10, 19)",
+                result: Err(r#"function call error for "pow" at (0:12): integer overflow"#),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let exponent = arguments.required("exponent");
+        Ok(PowFn { value, exponent }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PowFn {
+    value: Box<dyn Expression>,
+    exponent: Box<dyn Expression>,
+}
+
+impl FunctionExpression for PowFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let exponent = self.exponent.resolve(ctx)?;
+
+        pow(value, exponent)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let kind = numeric_kind([
+            self.value.type_def(state).kind(),
+            self.exponent.type_def(state).kind(),
+        ]);
+
+        TypeDef::from(kind).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        pow => Pow;
+
+        integers {
+            args: func_args![value: 3, exponent: 4],
+            want: Ok(81),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative_base {
+            args: func_args![value: -2, exponent: 3],
+            want: Ok(-8),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        zero_exponent {
+            args: func_args![value: 7, exponent: 0],
+            want: Ok(1),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        floats {
+            args: func_args![value: 2.0, exponent: -1],
+            want: Ok(0.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        mixed {
+            args: func_args![value: 4, exponent: 0.5],
+            want: Ok(2.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        integer_overflow {
+            args: func_args![value: 2, exponent: 63],
+            want: Err("integer overflow"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative_integer_exponent {
+            args: func_args![value: 2, exponent: -1],
+            want: Err("can't raise integer 2 to negative power -1"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        not_a_number {
+            args: func_args![value: -8.0, exponent: 0.5],
+            want: Err("pow result is not a number"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        float_overflow {
+            args: func_args![value: 10.0, exponent: 400],
+            want: Err("pow result overflowed"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
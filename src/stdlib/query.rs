@@ -0,0 +1,580 @@
+use crate::compiler::prelude::*;
+use std::cmp::Ordering;
+
+/// A parsed query: a pipeline of stages, each applied to every output of the previous one.
+#[derive(Debug, Clone, PartialEq)]
+struct Pipeline(Vec<Stage>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    Path(Vec<Segment>),
+    Select(Condition),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Field(String),
+    Index(i64),
+    /// Iterates over the elements of an array or the values of an object.
+    Iterate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Truthy(Vec<Segment>),
+    Compare(Vec<Segment>, Operator, Value),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(input: &'a str) -> Result<Pipeline, String> {
+        let mut parser = Self { input, pos: 0 };
+        let mut stages = vec![parser.stage()?];
+        while parser.eat("|") {
+            stages.push(parser.stage()?);
+        }
+        parser.skip_whitespace();
+        if parser.pos < input.len() {
+            return Err(parser.unexpected());
+        }
+        Ok(Pipeline(stages))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.pos = self.input.len() - self.rest().trim_start().len();
+    }
+
+    /// Consumes `token` after optional whitespace, if present.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the keyword `word` if it isn't followed by more identifier characters.
+    fn eat_keyword(&mut self, word: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let is_keyword = rest.starts_with(word)
+            && !rest[word.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        if is_keyword {
+            self.pos += word.len();
+        }
+        is_keyword
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected '{token}' at position {}", self.pos))
+        }
+    }
+
+    fn unexpected(&self) -> String {
+        match self.rest().chars().next() {
+            Some(c) => format!("unexpected '{c}' at position {}", self.pos),
+            None => "unexpected end of query".to_owned(),
+        }
+    }
+
+    fn stage(&mut self) -> Result<Stage, String> {
+        if self.eat_keyword("select") {
+            self.expect("(")?;
+            let condition = self.or()?;
+            self.expect(")")?;
+            return Ok(Stage::Select(condition));
+        }
+        self.path().map(Stage::Path)
+    }
+
+    fn path(&mut self) -> Result<Vec<Segment>, String> {
+        self.skip_whitespace();
+        if !self.rest().starts_with('.') {
+            return Err(self.unexpected());
+        }
+
+        let mut segments = Vec::new();
+        let mut first = true;
+        loop {
+            let rest = self.rest();
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                match after_dot.chars().next() {
+                    Some('[' | '"' | '*') => self.pos += 1,
+                    Some(c) if c.is_alphabetic() || c == '_' => self.pos += 1,
+                    // A lone `.` is only valid as the identity path.
+                    _ if first => {
+                        self.pos += 1;
+                        return Ok(segments);
+                    }
+                    _ => return Err(format!("expected field after '.' at position {}", self.pos)),
+                }
+            } else if !rest.starts_with('[') || first {
+                return Ok(segments);
+            }
+            first = false;
+
+            let rest = self.rest();
+            if rest.starts_with('[') {
+                self.pos += 1;
+                segments.push(self.bracket()?);
+            } else if rest.starts_with('*') {
+                self.pos += 1;
+                segments.push(Segment::Iterate);
+            } else if rest.starts_with('"') {
+                segments.push(Segment::Field(self.string()?));
+            } else {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                segments.push(Segment::Field(rest[..len].to_owned()));
+                self.pos += len;
+            }
+        }
+    }
+
+    /// Parses the inside of `[...]`, after the opening bracket.
+    fn bracket(&mut self) -> Result<Segment, String> {
+        self.skip_whitespace();
+        let segment = if self.eat("]") {
+            return Ok(Segment::Iterate);
+        } else if self.eat("*") {
+            Segment::Iterate
+        } else if self.rest().starts_with('"') {
+            Segment::Field(self.string()?)
+        } else {
+            match self.number()? {
+                Value::Integer(index) => Segment::Index(index),
+                _ => return Err(format!("expected integer index at position {}", self.pos)),
+            }
+        };
+        self.expect("]")?;
+        Ok(segment)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices().skip(1);
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(string);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some(c @ ('"' | '\\' | '/')) => string.push(c),
+                    _ => return Err(format!("invalid escape in string at position {start}")),
+                },
+                c => string.push(c),
+            }
+        }
+        Err(format!("unterminated string at position {start}"))
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && c == '-')))
+            .map_or(rest.len(), |(i, _)| i);
+        let literal = &rest[..len];
+        let value = if let Ok(integer) = literal.parse::<i64>() {
+            Value::Integer(integer)
+        } else if let Ok(float) = literal.parse::<f64>() {
+            Value::from_f64_or_zero(float)
+        } else {
+            return Err(self.unexpected());
+        };
+        self.pos += len;
+        Ok(value)
+    }
+
+    fn literal(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        if self.rest().starts_with('"') {
+            return self.string().map(Into::into);
+        }
+        for (keyword, value) in [
+            ("true", Value::Boolean(true)),
+            ("false", Value::Boolean(false)),
+            ("null", Value::Null),
+        ] {
+            if self.eat_keyword(keyword) {
+                return Ok(value);
+            }
+        }
+        self.number()
+    }
+
+    fn or(&mut self) -> Result<Condition, String> {
+        let mut condition = self.and()?;
+        while self.eat_keyword("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.unary()?;
+        while self.eat_keyword("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition, String> {
+        if self.eat_keyword("not") {
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let condition = self.or()?;
+            self.expect(")")?;
+            return Ok(condition);
+        }
+
+        let path = self.path()?;
+        for (token, operator) in [
+            ("==", Operator::Eq),
+            ("!=", Operator::Ne),
+            ("<=", Operator::Le),
+            (">=", Operator::Ge),
+            ("<", Operator::Lt),
+            (">", Operator::Gt),
+        ] {
+            if self.eat(token) {
+                return Ok(Condition::Compare(path, operator, self.literal()?));
+            }
+        }
+        Ok(Condition::Truthy(path))
+    }
+}
+
+fn eval_path(value: &Value, segments: &[Segment]) -> Result<Vec<Value>, String> {
+    let mut values = vec![value.clone()];
+    for segment in segments {
+        let mut next = Vec::with_capacity(values.len());
+        for value in values {
+            match (segment, value) {
+                (Segment::Field(_) | Segment::Index(_), Value::Null) => next.push(Value::Null),
+                (Segment::Field(field), Value::Object(mut map)) => {
+                    next.push(map.remove(field.as_str()).unwrap_or(Value::Null));
+                }
+                (Segment::Index(index), Value::Array(mut array)) => {
+                    let len = i64::try_from(array.len()).unwrap_or(i64::MAX);
+                    let index = if *index < 0 { len + index } else { *index };
+                    let element = usize::try_from(index)
+                        .ok()
+                        .filter(|&index| index < array.len())
+                        .map(|index| array.swap_remove(index));
+                    next.push(element.unwrap_or(Value::Null));
+                }
+                (Segment::Iterate, Value::Array(array)) => next.extend(array),
+                (Segment::Iterate, Value::Object(map)) => next.extend(map.into_values()),
+                (Segment::Field(field), value) => {
+                    return Err(format!(
+                        r#"cannot index {} with "{field}""#,
+                        value.kind_str()
+                    ))
+                }
+                (Segment::Index(_), value) => {
+                    return Err(format!("cannot index {} with number", value.kind_str()))
+                }
+                (Segment::Iterate, value) => {
+                    return Err(format!("cannot iterate over {}", value.kind_str()))
+                }
+            }
+        }
+        values = next;
+    }
+    Ok(values)
+}
+
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => Some(l.cmp(r)),
+        (Value::Integer(_) | Value::Float(_), Value::Integer(_) | Value::Float(_)) => {
+            let as_f64 = |v: &Value| match v {
+                Value::Integer(i) => *i as f64,
+                Value::Float(f) => f.into_inner(),
+                _ => unreachable!("numeric"),
+            };
+            as_f64(left).partial_cmp(&as_f64(right))
+        }
+        (Value::Bytes(l), Value::Bytes(r)) => Some(l.cmp(r)),
+        (l, r) if l == r => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+fn eval_condition(value: &Value, condition: &Condition) -> Result<bool, String> {
+    Ok(match condition {
+        Condition::Truthy(path) => eval_path(value, path)?
+            .iter()
+            .any(|v| !matches!(v, Value::Null | Value::Boolean(false))),
+        Condition::Compare(path, operator, literal) => eval_path(value, path)?.iter().any(|v| {
+            let ordering = compare(v, literal);
+            match operator {
+                Operator::Eq => ordering == Some(Ordering::Equal),
+                Operator::Ne => ordering != Some(Ordering::Equal),
+                Operator::Lt => ordering == Some(Ordering::Less),
+                Operator::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                Operator::Gt => ordering == Some(Ordering::Greater),
+                Operator::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            }
+        }),
+        Condition::Not(condition) => !eval_condition(value, condition)?,
+        Condition::And(left, right) => {
+            eval_condition(value, left)? && eval_condition(value, right)?
+        }
+        Condition::Or(left, right) => eval_condition(value, left)? || eval_condition(value, right)?,
+    })
+}
+
+impl Pipeline {
+    fn run(&self, value: Value) -> Result<Vec<Value>, String> {
+        let mut values = vec![value];
+        for stage in &self.0 {
+            let mut next = Vec::new();
+            for value in values {
+                match stage {
+                    Stage::Path(path) => next.extend(eval_path(&value, path)?),
+                    Stage::Select(condition) => {
+                        if eval_condition(&value, condition)? {
+                            next.push(value);
+                        }
+                    }
+                }
+            }
+            values = next;
+        }
+        Ok(values)
+    }
+}
+
+fn parse(expression: &Value) -> Result<Pipeline, String> {
+    let expression = expression
+        .as_str()
+        .ok_or_else(|| format!("expected string, got {}", expression.kind_str()))?;
+    Parser::parse(&expression).map_err(|err| format!("invalid query: {err}"))
+}
+
+/// The query argument, parsed at compile time when it is a literal.
+#[derive(Debug, Clone)]
+enum QueryExpr {
+    Parsed(Pipeline),
+    Expr(Box<dyn Expression>),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Query;
+
+impl Function for Query {
+    fn identifier(&self) -> &'static str {
+        "query"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "expression",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "field access",
+                source: r#"query!({"user": {"name": "ana"}}, ".user.name")"#,
+                result: Ok(r#"["ana"]"#),
+            },
+            Example {
+                title: "iterate and select",
+                source: r#"query!({"items": [{"id": 1, "ok": true}, {"id": 2, "ok": false}]}, ".items[] | select(.ok) | .id")"#,
+                result: Ok("[1]"),
+            },
+            Example {
+                title: "negative index",
+                source: r#"query!([1, 2, 3], ".[-1]")"#,
+                result: Ok("[3]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let expression = arguments.required("expression");
+
+        let expression = match expression.resolve_constant(state) {
+            Some(literal) => QueryExpr::Parsed(parse(&literal).map_err(|err| {
+                Box::new(ExpressionError::from(err)) as Box<dyn DiagnosticMessage>
+            })?),
+            None => QueryExpr::Expr(expression),
+        };
+
+        Ok(QueryFn { value, expression }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct QueryFn {
+    value: Box<dyn Expression>,
+    expression: QueryExpr,
+}
+
+impl FunctionExpression for QueryFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let results = match &self.expression {
+            QueryExpr::Parsed(pipeline) => pipeline.run(value),
+            QueryExpr::Expr(expr) => parse(&expr.resolve(ctx)?)?.run(value),
+        }?;
+
+        Ok(Value::Array(results))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn tdef() -> TypeDef {
+        TypeDef::array(Collection::any()).fallible()
+    }
+
+    test_function![
+        query => Query;
+
+        identity {
+            args: func_args![value: value!({"a": 1}), expression: "."],
+            want: Ok(value!([{"a": 1}])),
+            tdef: tdef(),
+        }
+
+        nested_fields {
+            args: func_args![value: value!({"a": {"b c": {"d": true}}}), expression: r#".a."b c".d"#],
+            want: Ok(value!([true])),
+            tdef: tdef(),
+        }
+
+        missing_field_is_null {
+            args: func_args![value: value!({"a": null}), expression: ".a.b.c"],
+            want: Ok(value!([null])),
+            tdef: tdef(),
+        }
+
+        indexing {
+            args: func_args![value: value!({"a": [1, {"x": 2}, 3]}), expression: r#".a[1]["x"]"#],
+            want: Ok(value!([2])),
+            tdef: tdef(),
+        }
+
+        index_out_of_bounds {
+            args: func_args![value: value!([1, 2, 3]), expression: ".[5]"],
+            want: Ok(value!([null])),
+            tdef: tdef(),
+        }
+
+        negative_index {
+            args: func_args![value: value!([1, 2, 3]), expression: ".[-1]"],
+            want: Ok(value!([3])),
+            tdef: tdef(),
+        }
+
+        wildcards {
+            args: func_args![value: value!({"a": {"x": {"n": 1}, "y": {"n": 2}}}), expression: ".a.*.n"],
+            want: Ok(value!([1, 2])),
+            tdef: tdef(),
+        }
+
+        iterate_arrays {
+            args: func_args![value: value!({"a": [{"n": 1}, {"n": 2}]}), expression: ".a[].n"],
+            want: Ok(value!([1, 2])),
+            tdef: tdef(),
+        }
+
+        select_comparisons {
+            args: func_args![
+                value: value!({"items": [
+                    {"name": "a", "size": 1, "tags": ["x"]},
+                    {"name": "b", "size": 2.5, "tags": ["y"]},
+                    {"name": "c", "size": 3, "tags": ["x", "z"]},
+                ]}),
+                expression: r#".items[] | select(.size >= 2 and (.tags[] == "x" or .name == "b")) | .name"#,
+            ],
+            want: Ok(value!(["b", "c"])),
+            tdef: tdef(),
+        }
+
+        select_not {
+            args: func_args![value: value!([{"ok": true}, {"ok": false}, {}]), expression: ".[] | select(not .ok)"],
+            want: Ok(value!([{"ok": false}, {}])),
+            tdef: tdef(),
+        }
+
+        index_non_object {
+            args: func_args![value: value!({"a": "text"}), expression: ".a.b"],
+            want: Err(r#"cannot index string with "b""#),
+            tdef: tdef(),
+        }
+
+        iterate_scalar {
+            args: func_args![value: value!(1), expression: ".[]"],
+            want: Err("cannot iterate over integer"),
+            tdef: tdef(),
+        }
+
+        invalid_expression {
+            args: func_args![value: value!({}), expression: "a.b"],
+            want: Err("invalid query: unexpected 'a' at position 0"),
+            tdef: tdef(),
+        }
+    ];
+}
@@ -0,0 +1,184 @@
+use crate::compiler::prelude::*;
+use crate::parsing::xml::{query_xml, XPath};
+
+#[derive(Clone, Copy, Debug)]
+pub struct QueryXml;
+
+impl Function for QueryXml {
+    fn identifier(&self) -> &'static str {
+        "query_xml"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "select element text",
+                source: indoc! {r#"
+                    value = s'<catalog><book id="1"><title>Dune</title></book><book id="2"><title>Emma</title></book></catalog>';
+
+                    query_xml!(value, "/catalog/book/title/text()")
+                "#},
+                result: Ok(r#"["Dune", "Emma"]"#),
+            },
+            Example {
+                title: "select with predicate",
+                source: indoc! {r#"
+                    value = s'<catalog><book id="1"><title>Dune</title></book><book id="2"><title>Emma</title></book></catalog>';
+
+                    query_xml!(value, "//book[@id='2']")
+                "#},
+                result: Ok(r#"[{ "@id": "2", "title": "Emma" }]"#),
+            },
+            Example {
+                title: "select attributes",
+                source: r#"query_xml!(s'<a><b x="1"/><b x="2"/></a>', "//b/@x")"#,
+                result: Ok(r#"["1", "2"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let xpath_value = arguments.required_literal("xpath", state)?;
+
+        let xpath = xpath_value
+            .try_bytes_utf8_lossy()
+            .expect("xpath should be a UTF8 string")
+            .parse::<XPath>()
+            .map_err(|err| {
+                Box::new(ExpressionError::from(format!("invalid xpath: {err}")))
+                    as Box<dyn DiagnosticMessage>
+            })?;
+
+        Ok(QueryXmlFn { value, xpath }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "xpath",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct QueryXmlFn {
+    value: Box<dyn Expression>,
+    xpath: XPath,
+}
+
+impl FunctionExpression for QueryXmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        query_xml(value, &self.xpath)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::array(Collection::any()).fallible()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    const CATALOG: &str = indoc! {r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <catalog xmlns:x="urn:extra">
+            <book id="1" lang="en">
+                <title>Dune</title>
+                <price>9.99</price>
+            </book>
+            <book id="2" lang="fr">
+                <title>Germinal</title>
+                <x:note>classic</x:note>
+            </book>
+            <book id="3" lang="en">
+                <title>Emma</title>
+            </book>
+        </catalog>
+    "#};
+
+    test_function![
+        query_xml => QueryXml;
+
+        child_path {
+            args: func_args![value: CATALOG, xpath: "/catalog/book/title/text()"],
+            want: Ok(value!(["Dune", "Germinal", "Emma"])),
+            tdef: type_def(),
+        }
+
+        relative_path {
+            args: func_args![value: CATALOG, xpath: "catalog/book/price"],
+            want: Ok(value!([9.99])),
+            tdef: type_def(),
+        }
+
+        descendant_with_attribute_predicate {
+            args: func_args![value: CATALOG, xpath: "//book[@lang='en']/title/text()"],
+            want: Ok(value!(["Dune", "Emma"])),
+            tdef: type_def(),
+        }
+
+        positional_predicates {
+            args: func_args![value: CATALOG, xpath: "//book[last()]/@id"],
+            want: Ok(value!(["3"])),
+            tdef: type_def(),
+        }
+
+        child_predicate_and_parent {
+            args: func_args![value: CATALOG, xpath: "//title/..[title='Germinal']/@id"],
+            want: Ok(value!(["2"])),
+            tdef: type_def(),
+        }
+
+        element_conversion {
+            args: func_args![value: CATALOG, xpath: "/catalog/book[1]"],
+            want: Ok(value!([{ "@id": "1", "@lang": "en", "title": "Dune", "price": 9.99 }])),
+            tdef: type_def(),
+        }
+
+        namespaced_name {
+            args: func_args![value: CATALOG, xpath: "//x:note/text()"],
+            want: Ok(value!(["classic"])),
+            tdef: type_def(),
+        }
+
+        wildcard_attributes {
+            args: func_args![value: CATALOG, xpath: "/catalog/*[2]/@*"],
+            want: Ok(value!(["2", "fr"])),
+            tdef: type_def(),
+        }
+
+        no_match {
+            args: func_args![value: CATALOG, xpath: "//magazine"],
+            want: Ok(value!([])),
+            tdef: type_def(),
+        }
+
+        invalid_xml {
+            args: func_args![value: "true", xpath: "/a"],
+            want: Err("unable to parse xml: unknown token at 1:1"),
+            tdef: type_def(),
+        }
+    ];
+}
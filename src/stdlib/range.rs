@@ -0,0 +1,199 @@
+use crate::compiler::prelude::*;
+
+fn range(start: Value, end: Value, step: Value) -> ExpressionResult<IntegerRange> {
+    let start = start.try_integer()?;
+    let end = end.try_integer()?;
+    let step = step.try_integer()?;
+
+    Ok(IntegerRange::new(start, end, step)?)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Range;
+
+impl Function for Range {
+    fn identifier(&self) -> &'static str {
+        "range"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "start",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "end",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "step",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "range",
+                source: "range(0, 5)",
+                result: Ok("[0, 1, 2, 3, 4]"),
+            },
+            Example {
+                title: "with step",
+                source: "range(10, 0, step: -3)",
+                result: Ok("[10, 7, 4, 1]"),
+            },
+            Example {
+                title: "iterate",
+                source:
+                    "sum = 0; for_each(range(1, 4)) -> |_index, value| { sum = sum + value }; sum",
+                result: Ok("6"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let start = arguments.required("start");
+        let end = arguments.required("end");
+        let step = arguments.optional("step").unwrap_or(expr!(1));
+
+        let constant = |expr: &dyn Expression| {
+            expr.resolve_constant(state)
+                .and_then(|value| value.as_integer())
+        };
+
+        if let (Some(start), Some(end), Some(step)) = (
+            constant(start.as_ref()),
+            constant(end.as_ref()),
+            constant(step.as_ref()),
+        ) {
+            if let Err(error) = IntegerRange::new(start, end, step) {
+                let (keyword, value) = if step == 0 {
+                    ("step", step)
+                } else {
+                    ("end", end)
+                };
+
+                return Err(function::Error::InvalidArgument {
+                    keyword,
+                    value: value.into(),
+                    error,
+                }
+                .into());
+            }
+        }
+
+        Ok(RangeFn { start, end, step }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RangeFn {
+    start: Box<dyn Expression>,
+    end: Box<dyn Expression>,
+    step: Box<dyn Expression>,
+}
+
+impl RangeFn {
+    fn range(&self, ctx: &mut Context) -> ExpressionResult<IntegerRange> {
+        let start = self.start.resolve(ctx)?;
+        let end = self.end.resolve(ctx)?;
+        let step = self.step.resolve(ctx)?;
+
+        range(start, end, step)
+    }
+}
+
+impl FunctionExpression for RangeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.range(ctx).map(IntegerRange::into_value)
+    }
+
+    fn resolve_range(&self, ctx: &mut Context) -> Option<ExpressionResult<IntegerRange>> {
+        Some(self.range(ctx))
+    }
+
+    fn type_def(&self, state: &TypeState) -> TypeDef {
+        let constant = self.start.resolve_constant(state).is_some()
+            && self.end.resolve_constant(state).is_some()
+            && self.step.resolve_constant(state).is_some();
+
+        TypeDef::array(Collection::from_unknown(Kind::integer())).maybe_fallible(!constant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        range => Range;
+
+        ascending {
+            args: func_args![start: 0, end: 3],
+            want: Ok(value!([0, 1, 2])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        descending_with_step {
+            args: func_args![start: 3, end: -3, step: -2],
+            want: Ok(Value::Array(vec![3.into(), 1.into(), (-1).into()])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        empty {
+            args: func_args![start: 3, end: 0],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        zero_step {
+            args: func_args![start: 0, end: 3, step: 0],
+            want: Err("invalid argument"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        too_long {
+            args: func_args![start: 0, end: 10_001],
+            want: Err("invalid argument"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+    ];
+
+    #[test]
+    fn iterates_lazily() {
+        let resolve = |end: i64| {
+            let source = "sum = 0; for_each(range!(0, int!(.end))) -> |_index, value| { sum = sum + value }; sum";
+            let program = crate::compiler::compile(source, &crate::stdlib::all())
+                .unwrap()
+                .program;
+            let mut target = value!({ "end": end });
+
+            crate::compiler::runtime::Runtime::default()
+                .resolve(&mut target, &program, &TimeZone::default())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(resolve(5), Ok(value!(10)));
+        assert_eq!(
+            resolve(10_001),
+            Err(concat!(
+                r#"function call error for "for_each" at (9:81): "#,
+                r#"function call error for "range" at (18:39): "#,
+                "range exceeds the maximum length of 10000 integers",
+            )
+            .to_owned())
+        );
+    }
+}
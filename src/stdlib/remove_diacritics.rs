@@ -0,0 +1,111 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::compiler::prelude::*;
+
+fn remove_diacritics(value: Value) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    // Decomposing separates the diacritics from the letters they're applied to.
+    let stripped: String = value
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .nfc()
+        .collect();
+
+    Ok(stripped.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RemoveDiacritics;
+
+impl Function for RemoveDiacritics {
+    fn identifier(&self) -> &'static str {
+        "remove_diacritics"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "accents",
+                source: r#"remove_diacritics("Crème brûlée")"#,
+                result: Ok("Creme brulee"),
+            },
+            Example {
+                title: "other scripts",
+                source: r#"remove_diacritics("Ελληνικά ñandú")"#,
+                result: Ok("Ελληνικα nandu"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(RemoveDiacriticsFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemoveDiacriticsFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for RemoveDiacriticsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        remove_diacritics(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        remove_diacritics => RemoveDiacritics;
+
+        composed {
+            args: func_args![value: "Ångström"],
+            want: Ok("Angstrom"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        decomposed {
+            args: func_args![value: "A\u{30a}ngstro\u{308}m"],
+            want: Ok("Angstrom"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        letters_without_decomposition {
+            args: func_args![value: "ø łódź"],
+            want: Ok("ø łodz"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        ascii {
+            args: func_args![value: "plain text"],
+            want: Ok("plain text"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
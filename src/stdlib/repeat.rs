@@ -0,0 +1,165 @@
+use crate::compiler::prelude::*;
+
+use super::string_utils::MAX_STRING_LENGTH;
+
+fn repeat(value: Value, count: Value) -> Resolved {
+    let value = value.try_bytes()?;
+    let count = repeat_count(value.len(), count.try_integer()?)?;
+
+    Ok(Bytes::from(value.repeat(count)).into())
+}
+
+/// Returns the number of times to repeat a string of `len` bytes, if the
+/// result is at most [`MAX_STRING_LENGTH`] bytes long.
+fn repeat_count(len: usize, count: i64) -> Result<usize, &'static str> {
+    let count = usize::try_from(count).unwrap_or_default();
+    match len.checked_mul(count) {
+        Some(length) if length <= MAX_STRING_LENGTH => Ok(count),
+        _ => Err("repeated string exceeds the maximum length of 1000000 bytes"),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Repeat;
+
+impl Function for Repeat {
+    fn identifier(&self) -> &'static str {
+        "repeat"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "count",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "repeat",
+                source: r#"repeat("ab", 3)"#,
+                result: Ok("ababab"),
+            },
+            Example {
+                title: "separator line",
+                source: r#"repeat("-", 5)"#,
+                result: Ok("-----"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let count = arguments.required("count");
+
+        if let (Some(Value::Bytes(bytes)), Some(Value::Integer(count))) =
+            (value.resolve_constant(state), count.resolve_constant(state))
+        {
+            if let Err(error) = repeat_count(bytes.len(), count) {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "count",
+                    value: count.into(),
+                    error,
+                }
+                .into());
+            }
+        }
+
+        Ok(RepeatFn { value, count }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RepeatFn {
+    value: Box<dyn Expression>,
+    count: Box<dyn Expression>,
+}
+
+impl FunctionExpression for RepeatFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let count = self.count.resolve(ctx)?;
+
+        repeat(value, count)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // Only constant arguments are known to repeat within the limit.
+        let constant = self.value.resolve_constant(state).is_some()
+            && self.count.resolve_constant(state).is_some();
+
+        TypeDef::bytes().maybe_fallible(!constant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        repeat => Repeat;
+
+        repeats {
+            args: func_args![value: "ab", count: 2],
+            want: Ok("abab"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        zero {
+            args: func_args![value: "ab", count: 0],
+            want: Ok(""),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        negative {
+            args: func_args![value: "ab", count: -3],
+            want: Ok(""),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        too_long {
+            args: func_args![value: "ab", count: 500_001],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        overflowing {
+            args: func_args![value: "ab", count: i64::MAX],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+
+    #[test]
+    fn limits_length_from_event() {
+        let resolve = |count: i64| {
+            let source = r#"repeat("ab", int!(.count)) ?? "too long""#;
+            let program = crate::compiler::compile(source, &crate::stdlib::all())
+                .unwrap()
+                .program;
+            let mut target = value!({ "count": count });
+
+            crate::compiler::runtime::Runtime::default()
+                .resolve(&mut target, &program, &TimeZone::default())
+                .unwrap()
+        };
+
+        assert_eq!(resolve(2), value!("abab"));
+        assert_eq!(resolve(500_001), value!("too long"));
+    }
+}
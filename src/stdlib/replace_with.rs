@@ -214,6 +214,7 @@ impl Function for ReplaceWith {
                 },
             }],
             is_iterator: false,
+            is_optional: false,
         })
     }
 }
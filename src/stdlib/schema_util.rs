@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+
+// Generated by `build.rs` from the tables in `data/schemas`.
+include!(concat!(env!("OUT_DIR"), "/schema_fields.rs"));
+
+fn find<'a, T>(table: &'a [T], name: &str, key: impl Fn(&T) -> &str) -> Option<&'a T> {
+    table
+        .binary_search_by(|entry| key(entry).cmp(name))
+        .ok()
+        .map(|index| &table[index])
+}
+
+/// Returns the type of an ECS field.
+pub(super) fn ecs_field_type(name: &str) -> Option<&'static str> {
+    find(ECS_FIELDS, name, |(name, _)| name).map(|(_, kind)| *kind)
+}
+
+/// Returns the `class_uid` of an OCSF event class.
+pub(super) fn ocsf_class_uid(class: &str) -> Option<u32> {
+    find(OCSF_CLASSES, class, |(class, _, _)| class).map(|(_, uid, _)| *uid)
+}
+
+/// Returns the `class_uid` of an OCSF event class, and the type of one of its
+/// attributes.
+pub(super) fn ocsf_field_type(class: &str, name: &str) -> Option<(u32, Option<&'static str>)> {
+    find(OCSF_CLASSES, class, |(class, _, _)| class).map(|(_, uid, fields)| {
+        let kind = find(fields, name, |(name, _)| name).map(|(_, kind)| *kind);
+        (*uid, kind)
+    })
+}
+
+/// Describes a schema field as an object with its dotted `name`, the `path`
+/// segments to reach it, and its `type`.
+pub(super) fn field_info(name: &str, kind: &str) -> ObjectMap {
+    let path = name.split('.').map(Value::from).collect::<Vec<_>>();
+
+    ObjectMap::from([
+        ("name".into(), name.into()),
+        ("path".into(), path.into()),
+        ("type".into(), kind.into()),
+    ])
+}
+
+pub(super) fn field_info_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("name".into(), Kind::bytes()),
+        (
+            "path".into(),
+            Kind::array(Collection::from_unknown(Kind::bytes())),
+        ),
+        ("type".into(), Kind::bytes()),
+    ])
+}
@@ -0,0 +1,134 @@
+use crate::compiler::prelude::*;
+use semver::VersionReq;
+
+use super::parse_semver::parse_version;
+
+fn semver_matches(value: Value, requirement: Value) -> Resolved {
+    let version = parse_version(&value.try_bytes_utf8_lossy()?)?;
+    let requirement = requirement.try_bytes_utf8_lossy()?;
+    let requirement = VersionReq::parse(&requirement)
+        .map_err(|err| format!("unable to parse version requirement: {err}"))?;
+
+    Ok(requirement.matches(&version).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SemverMatches;
+
+impl Function for SemverMatches {
+    fn identifier(&self) -> &'static str {
+        "semver_matches"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "requirement",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "caret requirement",
+                source: r#"semver_matches!("1.4.2", "^1.2")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "range",
+                source: r#"semver_matches!("2.1.0", ">=1.5, <2")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let requirement = arguments.required("requirement");
+
+        Ok(SemverMatchesFn { value, requirement }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SemverMatchesFn {
+    value: Box<dyn Expression>,
+    requirement: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SemverMatchesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let requirement = self.requirement.resolve(ctx)?;
+
+        semver_matches(value, requirement)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        semver_matches => SemverMatches;
+
+        caret {
+            args: func_args![value: "1.9.0", requirement: "^1.2"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        caret_next_major {
+            args: func_args![value: "2.0.0", requirement: "^1.2"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        greater_or_equal {
+            args: func_args![value: "v3.1.4", requirement: ">=2"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        multiple_comparators {
+            args: func_args![value: "1.5.0", requirement: ">=1.2.3, <1.5.0"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        pre_release_excluded {
+            args: func_args![value: "1.3.0-beta.1", requirement: ">=1.2"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid_version {
+            args: func_args![value: "latest", requirement: "^1"],
+            want: Err("unable to parse semantic version: unexpected character 'l' while parsing major version number"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid_requirement {
+            args: func_args![value: "1.0.0", requirement: "^a"],
+            want: Err("unable to parse version requirement: unexpected character 'a' while parsing major version number"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
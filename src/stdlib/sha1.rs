@@ -30,6 +30,10 @@ impl Function for Sha1 {
         }]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -65,6 +65,10 @@ impl Function for Sha2 {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         state: &state::TypeState,
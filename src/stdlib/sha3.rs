@@ -61,6 +61,10 @@ impl Function for Sha3 {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         state: &state::TypeState,
@@ -0,0 +1,151 @@
+use crate::compiler::prelude::*;
+
+use super::levenshtein_distance::DEFAULT_MAX_LENGTH;
+use super::string_utils::comparable_strings;
+
+fn similarity(value1: Value, value2: Value, case_sensitive: Value, max_length: Value) -> Resolved {
+    let case_sensitive = case_sensitive.try_boolean()?;
+    let (value1, value2) = comparable_strings(value1, value2, case_sensitive, max_length)?;
+
+    Ok(Value::from_f64_or_zero(strsim::jaro_winkler(
+        &value1, &value2,
+    )))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Similarity;
+
+impl Function for Similarity {
+    fn identifier(&self) -> &'static str {
+        "similarity"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value1",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value2",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "case_sensitive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "max_length",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "similar strings",
+                source: r#"floor(similarity!("martha", "marhta"), precision: 3)"#,
+                result: Ok("0.961"),
+            },
+            Example {
+                title: "equal strings",
+                source: r#"similarity!("Vector", "vector", case_sensitive: false)"#,
+                result: Ok("1.0"),
+            },
+            Example {
+                title: "different strings",
+                source: r#"similarity!("abc", "xyz")"#,
+                result: Ok("0.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value1 = arguments.required("value1");
+        let value2 = arguments.required("value2");
+        let case_sensitive = arguments.optional("case_sensitive").unwrap_or(expr!(true));
+        let max_length = arguments
+            .optional("max_length")
+            .unwrap_or(expr!(DEFAULT_MAX_LENGTH));
+
+        Ok(SimilarityFn {
+            value1,
+            value2,
+            case_sensitive,
+            max_length,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SimilarityFn {
+    value1: Box<dyn Expression>,
+    value2: Box<dyn Expression>,
+    case_sensitive: Box<dyn Expression>,
+    max_length: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SimilarityFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value1 = self.value1.resolve(ctx)?;
+        let value2 = self.value2.resolve(ctx)?;
+        let case_sensitive = self.case_sensitive.resolve(ctx)?;
+        let max_length = self.max_length.resolve(ctx)?;
+
+        similarity(value1, value2, case_sensitive, max_length)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        similarity => Similarity;
+
+        equal {
+            args: func_args![value1: "abc", value2: "abc"],
+            want: Ok(1.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        empty {
+            args: func_args![value1: "", value2: ""],
+            want: Ok(1.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        disjoint {
+            args: func_args![value1: "abc", value2: "xyz"],
+            want: Ok(0.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        case_insensitive {
+            args: func_args![value1: "ABC", value2: "abc", case_sensitive: false],
+            want: Ok(1.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        too_long {
+            args: func_args![value1: "a", value2: "abcd", max_length: 3],
+            want: Err("strings longer than 3 characters can't be compared"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
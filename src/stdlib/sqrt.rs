@@ -0,0 +1,111 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{expect_number, float_result};
+
+fn sqrt(value: Value) -> Resolved {
+    expect_number(&value)?;
+    let value = value.try_into_f64()?;
+    if value < 0.0 {
+        return Err(format!("can't calculate square root of negative number {value}").into());
+    }
+
+    float_result("sqrt", value.sqrt())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sqrt;
+
+impl Function for Sqrt {
+    fn identifier(&self) -> &'static str {
+        "sqrt"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "square root",
+                source: "sqrt!(16)",
+                result: Ok("4.0"),
+            },
+            Example {
+                title: "negative number",
+                source: "sqrt!(-1)",
+                result: Err(
+                    r#"function call error for "sqrt" at (0:9): can't calculate square root of negative number -1"#,
+                ),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(SqrtFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SqrtFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SqrtFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        sqrt(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        sqrt => Sqrt;
+
+        integer {
+            args: func_args![value: 16],
+            want: Ok(4.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        float {
+            args: func_args![value: 2.25],
+            want: Ok(1.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        zero {
+            args: func_args![value: 0],
+            want: Ok(0.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -4.0],
+            want: Err("can't calculate square root of negative number -4"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
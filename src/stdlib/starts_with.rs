@@ -114,6 +114,10 @@ impl Function for StartsWith {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
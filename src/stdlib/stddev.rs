@@ -0,0 +1,143 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{floats, numeric_elements};
+
+fn stddev(value: Value, sample: bool) -> Resolved {
+    let floats = floats(value)?;
+    let count = floats.len();
+    let divisor = if sample {
+        count.saturating_sub(1)
+    } else {
+        count
+    };
+    if divisor == 0 {
+        return Ok(Value::Null);
+    }
+
+    let mean = floats.iter().sum::<f64>() / count as f64;
+    let variance = floats
+        .iter()
+        .map(|float| (float - mean).powi(2))
+        .sum::<f64>()
+        / divisor as f64;
+
+    Ok(Value::from_f64_or_zero(variance.sqrt()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stddev;
+
+impl Function for Stddev {
+    fn identifier(&self) -> &'static str {
+        "stddev"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "sample",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "population standard deviation",
+                source: "stddev([2, 4, 4, 4, 5, 5, 7, 9])",
+                result: Ok("2.0"),
+            },
+            Example {
+                title: "sample standard deviation",
+                source: "stddev([1, 3], sample: true)",
+                result: Ok("1.4142135623730951"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let sample = arguments.optional("sample").unwrap_or_else(|| expr!(false));
+
+        Ok(StddevFn { value, sample }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StddevFn {
+    value: Box<dyn Expression>,
+    sample: Box<dyn Expression>,
+}
+
+impl FunctionExpression for StddevFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let sample = self.sample.resolve(ctx)?.try_boolean()?;
+
+        stddev(value, sample)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let numeric = numeric_elements(self.value.type_def(state).kind());
+
+        TypeDef::float().or_null().maybe_fallible(!numeric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        stddev => Stddev;
+
+        population {
+            args: func_args![value: value!([2, 4, 4, 4, 5, 5, 7, 9])],
+            want: Ok(2.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        sample {
+            args: func_args![value: value!([2, 4, 4, 4, 5, 5, 7, 9]), sample: true],
+            want: Ok(2.138_089_935_299_395),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        single_population {
+            args: func_args![value: value!([3.5])],
+            want: Ok(0.0),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        single_sample {
+            args: func_args![value: value!([3.5]), sample: true],
+            want: Ok(Value::Null),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(Value::Null),
+            tdef: TypeDef::float().or_null(),
+        }
+
+        non_numeric {
+            args: func_args![value: value!([1, null])],
+            want: Err("expected an array of numbers, found null at index 1"),
+            tdef: TypeDef::float().or_null().fallible(),
+        }
+    ];
+}
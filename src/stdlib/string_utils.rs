@@ -7,3 +7,61 @@ pub(crate) fn convert_to_string(value: Value, to_lowercase: bool) -> Result<Stri
         false => string.to_string(),
     })
 }
+
+/// The longest string `repeat` builds, in bytes, and `pad_left` and `pad_right`
+/// build, in grapheme clusters, so that event data can't make them allocate
+/// without bound.
+pub(crate) const MAX_STRING_LENGTH: usize = 1_000_000;
+
+/// Returns the length to pad a string to, if it's at most [`MAX_STRING_LENGTH`].
+pub(crate) fn pad_length(length: i64) -> Result<usize, &'static str> {
+    let length = usize::try_from(length).unwrap_or_default();
+    if length > MAX_STRING_LENGTH {
+        return Err("padded string exceeds the maximum length of 1000000 characters");
+    }
+
+    Ok(length)
+}
+
+/// Pads the string with repetitions of `pad` until it's `length` grapheme
+/// clusters long, where the last repetition is cut short if needed. The string
+/// is returned unchanged if it's at least that long, or if `pad` is empty.
+pub(crate) fn pad_graphemes(value: &str, length: usize, pad: &str, left: bool) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let missing = length.saturating_sub(value.graphemes(true).count());
+    if missing == 0 || pad.is_empty() {
+        return value.to_owned();
+    }
+
+    let padding: String = pad.graphemes(true).cycle().take(missing).collect();
+    if left {
+        padding + value
+    } else {
+        value.to_owned() + &padding
+    }
+}
+
+/// Prepares two strings for an edit distance or similarity, which takes time
+/// proportional to the product of their lengths, so longer strings than
+/// `max_length` characters are rejected.
+pub(crate) fn comparable_strings(
+    value1: Value,
+    value2: Value,
+    case_sensitive: bool,
+    max_length: Value,
+) -> Result<(String, String), crate::compiler::ExpressionError> {
+    let max_length = usize::try_from(max_length.try_integer()?).unwrap_or_default();
+    let value1 = convert_to_string(value1, !case_sensitive)?;
+    let value2 = convert_to_string(value2, !case_sensitive)?;
+
+    for value in [&value1, &value2] {
+        if value.chars().count() > max_length {
+            return Err(
+                format!("strings longer than {max_length} characters can't be compared").into(),
+            );
+        }
+    }
+
+    Ok((value1, value2))
+}
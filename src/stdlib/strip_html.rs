@@ -0,0 +1,107 @@
+use crate::compiler::prelude::*;
+use crate::parsing::html::strip_html;
+
+#[derive(Clone, Copy, Debug)]
+pub struct StripHtml;
+
+impl Function for StripHtml {
+    fn identifier(&self) -> &'static str {
+        "strip_html"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "strip tags",
+                source: r#"strip_html!(s'<p>Hello <b>world</b> &amp; friends</p><p>bye</p>')"#,
+                result: Ok("Hello world & friends bye"),
+            },
+            Example {
+                title: "drop scripts",
+                source: r#"strip_html!(s'<div>safe<script>alert("x")</script></div>')"#,
+                result: Ok("safe"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(StripHtmlFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StripHtmlFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for StripHtmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        strip_html(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        strip_html => StripHtml;
+
+        plain_text {
+            args: func_args![value: "no markup here"],
+            want: Ok("no markup here"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        tags_and_entities {
+            args: func_args![value: r#"<a href="https://vector.dev?a=1&amp;b=2">Vector</a> &lt;3 &#x1F600;"#],
+            want: Ok("Vector <3 😀"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        block_elements_separate_words {
+            args: func_args![value: "<ul><li>one</li><li>two</li></ul>line<br>break"],
+            want: Ok("one two line break"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        comments_scripts_and_styles {
+            args: func_args![value: "<!-- hidden --><style>p { color: red }</style>shown<SCRIPT>var a = '</p>';</SCRIPT>"],
+            want: Ok("shown"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        malformed {
+            args: func_args![value: "1 < 2 <b>bold <i>unterminated"],
+            want: Ok("1 < 2 bold unterminated"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        too_large {
+            args: func_args![value: "a".repeat(1024 * 1024 + 1)],
+            want: Err("input exceeds maximum size of 1048576 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,153 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::compiler::prelude::*;
+
+/// Resolves an index into a position between 0 and `len`, counting from the
+/// end if it's negative.
+fn position(index: i64, len: usize) -> usize {
+    let magnitude = usize::try_from(index.unsigned_abs()).unwrap_or(usize::MAX);
+    if index < 0 {
+        len.saturating_sub(magnitude)
+    } else {
+        magnitude.min(len)
+    }
+}
+
+fn substring(value: Value, start: Value, end: Value) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    let graphemes = value.graphemes(true).collect::<Vec<_>>();
+
+    let start = position(start.try_integer()?, graphemes.len());
+    let end = match end {
+        Value::Null => graphemes.len(),
+        end => position(end.try_integer()?, graphemes.len()),
+    };
+
+    Ok(graphemes
+        .get(start..end)
+        .unwrap_or_default()
+        .concat()
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Substring;
+
+impl Function for Substring {
+    fn identifier(&self) -> &'static str {
+        "substring"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "start",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "end",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "start..end",
+                source: r#"substring("foobar", 1, 4)"#,
+                result: Ok("oob"),
+            },
+            Example {
+                title: "from the end",
+                source: r#"substring("foobar", -3)"#,
+                result: Ok("bar"),
+            },
+            Example {
+                title: "grapheme clusters",
+                source: r#"substring("🇫🇷🇩🇪🇮🇹", 0, 2)"#,
+                result: Ok("🇫🇷🇩🇪"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let start = arguments.required("start");
+        let end = arguments.optional("end").unwrap_or(expr!(null));
+
+        Ok(SubstringFn { value, start, end }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SubstringFn {
+    value: Box<dyn Expression>,
+    start: Box<dyn Expression>,
+    end: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SubstringFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let start = self.start.resolve(ctx)?;
+        let end = self.end.resolve(ctx)?;
+
+        substring(value, start, end)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        substring => Substring;
+
+        start {
+            args: func_args![value: "foobar", start: 3],
+            want: Ok("bar"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        negative_end {
+            args: func_args![value: "foobar", start: 1, end: -1],
+            want: Ok("ooba"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        out_of_range {
+            args: func_args![value: "foo", start: -10, end: 10],
+            want: Ok("foo"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        end_before_start {
+            args: func_args![value: "foobar", start: 4, end: 2],
+            want: Ok(""),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        combining_characters {
+            args: func_args![value: "cafe\u{301}s", start: 3, end: 4],
+            want: Ok("e\u{301}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
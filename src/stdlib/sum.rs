@@ -0,0 +1,148 @@
+use crate::compiler::prelude::*;
+
+use super::math_util::{element_kind, numbers, numeric_elements, numeric_kind};
+
+fn sum(value: Value) -> Resolved {
+    let numbers = numbers(value)?;
+
+    // Like the `+` operator, integer sums wrap around on overflow.
+    if numbers
+        .iter()
+        .all(|number| matches!(number, Value::Integer(_)))
+    {
+        let sum = numbers
+            .iter()
+            .filter_map(Value::as_integer)
+            .fold(0_i64, i64::wrapping_add);
+        return Ok(sum.into());
+    }
+
+    let sum = numbers
+        .iter()
+        .map(|number| number.try_into_f64())
+        .sum::<Result<f64, _>>()?;
+    Ok(Value::from_f64_or_zero(sum))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sum;
+
+impl Function for Sum {
+    fn identifier(&self) -> &'static str {
+        "sum"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "sum integers",
+                source: "sum([1, 2, 3])",
+                result: Ok("6"),
+            },
+            Example {
+                title: "sum mixed numbers",
+                source: "sum([1, 2.5])",
+                result: Ok("3.5"),
+            },
+            Example {
+                title: "non-numeric element",
+                source: r#"sum!([1, "2"])"#,
+                result: Err(
+                    r#"function call error for "sum" at (0:14): expected an array of numbers, found string at index 1"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(SumFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SumFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SumFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        sum(value)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let kind = self.value.type_def(state).kind().clone();
+        let element = element_kind(&kind);
+
+        // An empty array sums to the integer zero.
+        let result = if element.is_never() {
+            Kind::integer()
+        } else {
+            numeric_kind([&element])
+        };
+
+        TypeDef::from(result).maybe_fallible(!numeric_elements(&kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        sum => Sum;
+
+        integers {
+            args: func_args![value: value!([1, 2, 3])],
+            want: Ok(6),
+            tdef: TypeDef::integer(),
+        }
+
+        floats {
+            args: func_args![value: value!([1.5, 2.25])],
+            want: Ok(3.75),
+            tdef: TypeDef::float(),
+        }
+
+        mixed {
+            args: func_args![value: value!([1, 2.5])],
+            want: Ok(3.5),
+            tdef: TypeDef::integer().or_float(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(0),
+            tdef: TypeDef::integer(),
+        }
+
+        wraps_on_overflow {
+            args: func_args![value: vec![Value::from(i64::MAX), Value::from(1)]],
+            want: Ok(i64::MIN),
+            tdef: TypeDef::integer(),
+        }
+
+        non_numeric {
+            args: func_args![value: value!([1, "2", 3])],
+            want: Err("expected an array of numbers, found string at index 1"),
+            tdef: TypeDef::integer().or_float().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::compiler::prelude::*;
+use crate::compiler::StateStore;
+
+use super::util::window_duration;
+
+fn sum_window(store: &dyn StateStore, key: Value, value: Value, window: Duration) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    let value = value.try_into_f64()?;
+
+    let sum = store.add(
+        &format!("sum_window:{}:{key}", window.as_secs()),
+        value,
+        window,
+    );
+
+    Ok(Value::from_f64_or_zero(sum))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SumWindow;
+
+impl Function for SumWindow {
+    fn identifier(&self) -> &'static str {
+        "sum_window"
+    }
+
+    fn summary(&self) -> &'static str {
+        "sum the values seen for a key"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Adds `value` to the sum of the values seen for the given `key` within the last
+            `window_secs` seconds, and returns the sum as a float.
+
+            The window starts with the first value of the key and lasts `window_secs` seconds,
+            at most one year, after which the sum starts over. Sums are kept in the state store
+            provided by the host, which decides whether they are shared by other processes.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "window_secs",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "sum the bytes sent by a host",
+                source: r#"sum_window("host-1", 512, window_secs: 60)"#,
+                result: Ok("512.0"),
+            },
+            Example {
+                title: "count the times an alert was seen",
+                source: r#"to_int(sum_window("alert-1", 1, window_secs: 86400))"#,
+                result: Ok("1"),
+            },
+        ]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+            .returns(kind::FLOAT, false)
+            .return_rule("fails if a non-constant `window_secs` is invalid")
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let window_secs = arguments.required("window_secs");
+
+        let store = ctx
+            .state_store()
+            .cloned()
+            .ok_or(function::Error::MissingContext {
+                context: "state store",
+            })?;
+
+        let constant_window =
+            match window_secs.resolve_constant(state) {
+                Some(value) => Some(window_duration(&value).map_err(|error| {
+                    function::Error::InvalidArgument {
+                        keyword: "window_secs",
+                        value,
+                        error,
+                    }
+                })?),
+                None => None,
+            };
+
+        Ok(SumWindowFn {
+            key,
+            value,
+            window_secs,
+            constant_window,
+            store,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SumWindowFn {
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    window_secs: Box<dyn Expression>,
+    constant_window: Option<Duration>,
+    store: Arc<dyn StateStore>,
+}
+
+impl FunctionExpression for SumWindowFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let value = self.value.resolve(ctx)?;
+        let window = match self.constant_window {
+            Some(window) => window,
+            None => window_duration(&self.window_secs.resolve(ctx)?)?,
+        };
+
+        sum_window(self.store.as_ref(), key, value, window)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().maybe_fallible(self.constant_window.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile_with_external, CompileConfig, MemoryStateStore};
+    use crate::value;
+
+    fn resolve(source: &str, config: CompileConfig) -> Result<Value, String> {
+        let program = compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &state::ExternalEnv::default(),
+            config,
+        )
+        .map_err(|diagnostics| diagnostics[0].message.clone())?
+        .program;
+
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut state, &tz);
+        program.resolve(&mut ctx).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn sums_values_by_key() {
+        let store = MemoryStateStore::default();
+        let mut config = CompileConfig::default();
+        config.set_state_store(store.clone());
+
+        let source = r#"
+            [
+                sum_window("a", 1, window_secs: 60),
+                sum_window("a", 2.5, window_secs: 60),
+                sum_window("b", 1, window_secs: 60),
+                sum_window("a", 1, window_secs: 30),
+            ]
+        "#;
+        assert_eq!(resolve(source, config), Ok(value!([1.0, 3.5, 1.0, 1.0])));
+    }
+
+    #[test]
+    fn rejects_invalid_windows() {
+        let mut config = CompileConfig::default();
+        config.set_state_store(MemoryStateStore::default());
+        assert_eq!(
+            resolve(r#"sum_window("a", 1, -60)"#, config),
+            Err("function compilation error: error[E403] invalid argument".to_owned())
+        );
+
+        let mut config = CompileConfig::default();
+        config.set_state_store(MemoryStateStore::default());
+        assert_eq!(
+            resolve(r#"sum_window("a", 1, 9223372036854775807)"#, config),
+            Err("function compilation error: error[E403] invalid argument".to_owned())
+        );
+    }
+}
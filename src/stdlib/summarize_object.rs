@@ -0,0 +1,282 @@
+use crate::compiler::prelude::*;
+
+const DEFAULT_MAX_KEYS: i64 = 20;
+const DEFAULT_MAX_SAMPLE_LENGTH: i64 = 32;
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) => "string",
+        Value::Regex(_) => "regex",
+        Value::Timestamp(_) => "timestamp",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "float",
+        Value::Boolean(_) => "boolean",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::Null => "null",
+    }
+}
+
+struct Summarizer {
+    max_keys: usize,
+    max_sample_length: usize,
+}
+
+impl Summarizer {
+    /// Returns at most `max_sample_length` characters of `string`, followed by
+    /// `...` when it was truncated.
+    fn sample(&self, string: &str) -> String {
+        match string.char_indices().nth(self.max_sample_length) {
+            Some((end, _)) => format!("{}...", &string[..end]),
+            None => string.to_owned(),
+        }
+    }
+
+    /// Summarizes a single value without descending into containers.
+    fn shallow(&self, value: &Value) -> ObjectMap {
+        let mut summary = ObjectMap::new();
+        summary.insert("type".into(), type_name(value).into());
+
+        match value {
+            Value::Bytes(bytes) => {
+                summary.insert("length".into(), bytes.len().into());
+                summary.insert(
+                    "sample".into(),
+                    self.sample(&String::from_utf8_lossy(bytes)).into(),
+                );
+            }
+            Value::Regex(regex) => {
+                summary.insert("sample".into(), self.sample(regex.as_str()).into());
+            }
+            Value::Object(map) => {
+                summary.insert("length".into(), map.len().into());
+            }
+            Value::Array(array) => {
+                summary.insert("length".into(), array.len().into());
+            }
+            Value::Null => {}
+            scalar => {
+                summary.insert("value".into(), scalar.clone());
+            }
+        }
+
+        summary
+    }
+
+    /// Summarizes a value, including a shallow summary of the first `max_keys`
+    /// fields or elements of a container.
+    fn summarize(&self, value: &Value) -> ObjectMap {
+        let mut summary = self.shallow(value);
+
+        let omitted = match value {
+            Value::Object(map) => {
+                let fields = map
+                    .iter()
+                    .take(self.max_keys)
+                    .map(|(key, value)| (key.clone(), self.shallow(value).into()))
+                    .collect::<ObjectMap>();
+                summary.insert("fields".into(), fields.into());
+                map.len().saturating_sub(self.max_keys)
+            }
+            Value::Array(array) => {
+                let items = array
+                    .iter()
+                    .take(self.max_keys)
+                    .map(|value| self.shallow(value).into())
+                    .collect::<Vec<Value>>();
+                summary.insert("items".into(), items.into());
+                array.len().saturating_sub(self.max_keys)
+            }
+            _ => 0,
+        };
+        if omitted > 0 {
+            summary.insert("omitted".into(), omitted.into());
+        }
+
+        summary
+    }
+}
+
+fn summarize_object(value: &Value, max_keys: Value, max_sample_length: Value) -> Resolved {
+    let max_keys = max_keys.try_integer()?;
+    let max_sample_length = max_sample_length.try_integer()?;
+    let summarizer = Summarizer {
+        max_keys: usize::try_from(max_keys).unwrap_or(0),
+        max_sample_length: usize::try_from(max_sample_length).unwrap_or(0),
+    };
+
+    Ok(summarizer.summarize(value).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SummarizeObject;
+
+impl Function for SummarizeObject {
+    fn identifier(&self) -> &'static str {
+        "summarize_object"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_keys",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "max_sample_length",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "summarize object",
+                source: r#"summarize_object({"message": "a very long message", "tags": ["a", "b"], "count": 3}, max_sample_length: 6)"#,
+                result: Ok(indoc! {r#"{
+                    "fields": {
+                        "count": {"type": "integer", "value": 3},
+                        "message": {"length": 19, "sample": "a very...", "type": "string"},
+                        "tags": {"length": 2, "type": "array"}
+                    },
+                    "length": 3,
+                    "type": "object"
+                }"#}),
+            },
+            Example {
+                title: "limit keys",
+                source: r#"summarize_object({"a": 1, "b": 2, "c": 3}, max_keys: 1)"#,
+                result: Ok(
+                    r#"{"fields": {"a": {"type": "integer", "value": 1}}, "length": 3, "omitted": 2, "type": "object"}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_keys = arguments
+            .optional("max_keys")
+            .unwrap_or_else(|| expr!(DEFAULT_MAX_KEYS));
+        let max_sample_length = arguments
+            .optional("max_sample_length")
+            .unwrap_or_else(|| expr!(DEFAULT_MAX_SAMPLE_LENGTH));
+
+        Ok(SummarizeObjectFn {
+            value,
+            max_keys,
+            max_sample_length,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SummarizeObjectFn {
+    value: Box<dyn Expression>,
+    max_keys: Box<dyn Expression>,
+    max_sample_length: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SummarizeObjectFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_keys = self.max_keys.resolve(ctx)?;
+        let max_sample_length = self.max_sample_length.resolve(ctx)?;
+
+        summarize_object(&value, max_keys, max_sample_length)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        summarize_object => SummarizeObject;
+
+        object {
+            args: func_args![value: value!({"s": "hello", "n": null, "f": 1.5, "b": true, "o": {"x": 1}})],
+            want: Ok(value!({
+                "type": "object",
+                "length": 5,
+                "fields": {
+                    "b": {"type": "boolean", "value": true},
+                    "f": {"type": "float", "value": 1.5},
+                    "n": {"type": "null"},
+                    "o": {"type": "object", "length": 1},
+                    "s": {"type": "string", "length": 5, "sample": "hello"},
+                },
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        truncates_samples {
+            args: func_args![value: value!({"s": "héllo world"}), max_sample_length: 2],
+            want: Ok(value!({
+                "type": "object",
+                "length": 1,
+                "fields": {"s": {"type": "string", "length": 12, "sample": "hé..."}},
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        limits_keys {
+            args: func_args![value: value!({"a": 1, "b": 2, "c": 3}), max_keys: 2],
+            want: Ok(value!({
+                "type": "object",
+                "length": 3,
+                "fields": {
+                    "a": {"type": "integer", "value": 1},
+                    "b": {"type": "integer", "value": 2},
+                },
+                "omitted": 1,
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        array {
+            args: func_args![value: value!([[1, 2], "x", 3]), max_keys: 2],
+            want: Ok(value!({
+                "type": "array",
+                "length": 3,
+                "items": [
+                    {"type": "array", "length": 2},
+                    {"type": "string", "length": 1, "sample": "x"},
+                ],
+                "omitted": 1,
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        scalar {
+            args: func_args![value: "a long string", max_sample_length: 4],
+            want: Ok(value!({"type": "string", "length": 13, "sample": "a lo..."})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        negative_limits {
+            args: func_args![value: value!({"a": "x"}), max_keys: -1, max_sample_length: -1],
+            want: Ok(value!({"type": "object", "length": 1, "fields": {}, "omitted": 1})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+    ];
+}
@@ -0,0 +1,244 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::compiler::prelude::*;
+use crate::compiler::StateStore;
+
+use super::util::window_duration;
+
+fn limit(limit: &Value) -> Result<i64, &'static str> {
+    match limit.as_integer() {
+        Some(limit) if limit >= 0 => Ok(limit),
+        _ => Err(r#""limit" must not be negative"#),
+    }
+}
+
+fn throttle(store: &dyn StateStore, key: Value, limit: i64, window: Duration) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+
+    // Counters are per window length, so that calls with different windows
+    // don't reset each other's counters.
+    let count = store.incr(&format!("throttle:{}:{key}", window.as_secs()), window);
+
+    Ok((count <= limit).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Throttle;
+
+impl Function for Throttle {
+    fn identifier(&self) -> &'static str {
+        "throttle"
+    }
+
+    fn summary(&self) -> &'static str {
+        "limit the rate of events by key"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns whether the event is within the budget of `limit` events per `window_secs`
+            seconds for the given `key`, counting the event.
+
+            The window starts with the first event of the key and lasts `window_secs` seconds,
+            at most one year, after which the count starts over. Events are counted in the state
+            store provided by the host, which decides whether the count is shared by other
+            processes.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "limit",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "window_secs",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "throttle by host",
+            source: r#"throttle("host-1", limit: 100, window_secs: 60)"#,
+            result: Ok("true"),
+        }]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::new(self)
+            .returns(kind::BOOLEAN, false)
+            .return_rule("fails if a non-constant `limit` or `window_secs` is invalid")
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let limit = arguments.required("limit");
+        let window_secs = arguments.required("window_secs");
+
+        let store = ctx
+            .state_store()
+            .cloned()
+            .ok_or(function::Error::MissingContext {
+                context: "state store",
+            })?;
+
+        let constant_limit = match limit.resolve_constant(state) {
+            Some(value) => {
+                Some(
+                    self::limit(&value).map_err(|error| function::Error::InvalidArgument {
+                        keyword: "limit",
+                        value,
+                        error,
+                    })?,
+                )
+            }
+            None => None,
+        };
+        let constant_window =
+            match window_secs.resolve_constant(state) {
+                Some(value) => Some(window_duration(&value).map_err(|error| {
+                    function::Error::InvalidArgument {
+                        keyword: "window_secs",
+                        value,
+                        error,
+                    }
+                })?),
+                None => None,
+            };
+
+        Ok(ThrottleFn {
+            key,
+            limit,
+            window_secs,
+            constant_limit,
+            constant_window,
+            store,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ThrottleFn {
+    key: Box<dyn Expression>,
+    limit: Box<dyn Expression>,
+    window_secs: Box<dyn Expression>,
+    constant_limit: Option<i64>,
+    constant_window: Option<Duration>,
+    store: Arc<dyn StateStore>,
+}
+
+impl FunctionExpression for ThrottleFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let limit = match self.constant_limit {
+            Some(limit) => limit,
+            None => limit(&self.limit.resolve(ctx)?)?,
+        };
+        let window = match self.constant_window {
+            Some(window) => window,
+            None => window_duration(&self.window_secs.resolve(ctx)?)?,
+        };
+
+        throttle(self.store.as_ref(), key, limit, window)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean()
+            .maybe_fallible(self.constant_limit.is_none() || self.constant_window.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile_with_external, CompileConfig, MemoryStateStore};
+    use crate::value;
+
+    fn resolve(source: &str, config: CompileConfig) -> Result<Value, String> {
+        let program = compile_with_external(
+            source,
+            &crate::stdlib::all(),
+            &state::ExternalEnv::default(),
+            config,
+        )
+        .map_err(|diagnostics| diagnostics[0].message.clone())?
+        .program;
+
+        let mut target = Value::Null;
+        let mut state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut state, &tz);
+        program.resolve(&mut ctx).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn throttles_by_key() {
+        let store = MemoryStateStore::default();
+        let mut config = CompileConfig::default();
+        config.set_state_store(store.clone());
+
+        let source = r#"
+            [
+                throttle("a", limit: 2, window_secs: 60),
+                throttle("a", limit: 2, window_secs: 60),
+                throttle("a", limit: 2, window_secs: 60),
+                throttle("b", limit: 2, window_secs: 60),
+                throttle("a", limit: 2, window_secs: 30),
+            ]
+        "#;
+        assert_eq!(
+            resolve(source, config),
+            Ok(value!([true, true, false, true, true]))
+        );
+        assert_eq!(store.get("throttle:60:a"), 3);
+    }
+
+    #[test]
+    fn requires_a_state_store() {
+        assert_eq!(
+            resolve(r#"throttle("a", 1, 60)"#, CompileConfig::default()),
+            Err("function compilation error: error[E404] missing host context".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let mut config = CompileConfig::default();
+        config.set_state_store(MemoryStateStore::default());
+        assert_eq!(
+            resolve(r#"throttle("a", -1, 60)"#, config),
+            Err("function compilation error: error[E403] invalid argument".to_owned())
+        );
+
+        let mut config = CompileConfig::default();
+        config.set_state_store(MemoryStateStore::default());
+        assert_eq!(
+            resolve(r#"throttle("a", 1, 0)"#, config),
+            Err("function compilation error: error[E403] invalid argument".to_owned())
+        );
+
+        let mut config = CompileConfig::default();
+        config.set_state_store(MemoryStateStore::default());
+        assert_eq!(
+            resolve(r#"throttle("a", 1, 9223372036854775807)"#, config),
+            Err("function compilation error: error[E403] invalid argument".to_owned())
+        );
+    }
+}
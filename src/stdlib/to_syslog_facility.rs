@@ -66,6 +66,10 @@ impl Function for ToSyslogFacility {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -50,6 +50,10 @@ impl Function for ToSyslogLevel {
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -1,11 +1,76 @@
+use unicode_segmentation::UnicodeSegmentation;
+
 use crate::compiler::prelude::*;
 
-fn truncate(value: Value, limit: Value, ellipsis: Value, suffix: Value) -> Resolved {
+/// The unit in which `truncate` counts the limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Unit {
+    Graphemes,
+    Codepoints,
+    Bytes,
+}
+
+impl Unit {
+    const ALL: [Self; 3] = [Self::Graphemes, Self::Codepoints, Self::Bytes];
+
+    fn all_value() -> Vec<Value> {
+        Self::ALL.iter().map(|unit| unit.as_str().into()).collect()
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Graphemes => "graphemes",
+            Self::Codepoints => "codepoints",
+            Self::Bytes => "bytes",
+        }
+    }
+
+    fn len(self, value: &str) -> usize {
+        match self {
+            Self::Graphemes => value.graphemes(true).count(),
+            Self::Codepoints => value.chars().count(),
+            Self::Bytes => value.len(),
+        }
+    }
+
+    /// The longest prefix of the value that's at most `limit` units long,
+    /// without splitting a grapheme cluster or a multibyte character.
+    fn prefix(self, value: &str, limit: usize) -> &str {
+        let end = match self {
+            Self::Graphemes => value.grapheme_indices(true).nth(limit).map(|(pos, _)| pos),
+            Self::Codepoints => value.char_indices().nth(limit).map(|(pos, _)| pos),
+            Self::Bytes => (0..=limit.min(value.len()))
+                .rev()
+                .find(|&pos| value.is_char_boundary(pos)),
+        };
+        &value[..end.unwrap_or(value.len())]
+    }
+}
+
+fn truncate(
+    value: Value,
+    limit: Value,
+    ellipsis: Value,
+    suffix: Value,
+    unit: Option<Unit>,
+) -> Resolved {
     let mut value = value.try_bytes_utf8_lossy()?.into_owned();
     let limit = limit.try_integer()?;
     let limit = if limit < 0 { 0 } else { limit as usize };
     let ellipsis = ellipsis.try_boolean()?;
     let suffix = suffix.try_bytes_utf8_lossy()?.to_string();
+    let suffix = if ellipsis { "...".to_owned() } else { suffix };
+
+    if let Some(unit) = unit {
+        // The suffix counts within the limit.
+        if unit.len(&value) <= limit {
+            return Ok(value.into());
+        }
+        let suffix = unit.prefix(&suffix, limit);
+        let kept = unit.prefix(&value, limit - unit.len(suffix));
+        return Ok(format!("{kept}{suffix}").into());
+    }
+
     let pos = if let Some((pos, chr)) = value.char_indices().take(limit).last() {
         // char_indices gives us the starting position of the character at limit,
         // we want the end position.
@@ -16,11 +81,7 @@ fn truncate(value: Value, limit: Value, ellipsis: Value, suffix: Value) -> Resol
     };
     if value.len() > pos {
         value.truncate(pos);
-        if ellipsis {
-            value.push_str("...");
-        } else if !suffix.is_empty() {
-            value.push_str(&suffix);
-        }
+        value.push_str(&suffix);
     }
     Ok(value.into())
 }
@@ -55,6 +116,11 @@ impl Function for Truncate {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -75,12 +141,26 @@ impl Function for Truncate {
                 source: r#"truncate("foo bar zoo", 4, suffix: "[TRUNCATED]")"#,
                 result: Ok("foo [TRUNCATED]"),
             },
+            Example {
+                title: "suffix within the limit",
+                source: r#"truncate("foo bar zoo", 7, suffix: "...", unit: "codepoints")"#,
+                result: Ok("foo ..."),
+            },
+            Example {
+                title: "grapheme clusters",
+                source: r#"truncate("👍🏽👍🏽👍🏽", 2, suffix: "…", unit: "graphemes")"#,
+                result: Ok("👍🏽…"),
+            },
         ]
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
@@ -88,12 +168,21 @@ impl Function for Truncate {
         let limit = arguments.required("limit");
         let ellipsis = arguments.optional("ellipsis").unwrap_or(expr!(false));
         let suffix = arguments.optional("suffix").unwrap_or(expr!(""));
+        let unit = arguments
+            .optional_enum("unit", &Unit::all_value(), state)?
+            .map(|value| {
+                Unit::ALL
+                    .into_iter()
+                    .find(|unit| value == Value::from(unit.as_str()))
+                    .expect("validated enum")
+            });
 
         Ok(TruncateFn {
             value,
             limit,
             ellipsis,
             suffix,
+            unit,
         }
         .as_expr())
     }
@@ -105,6 +194,7 @@ struct TruncateFn {
     limit: Box<dyn Expression>,
     ellipsis: Box<dyn Expression>,
     suffix: Box<dyn Expression>,
+    unit: Option<Unit>,
 }
 
 impl FunctionExpression for TruncateFn {
@@ -114,7 +204,7 @@ impl FunctionExpression for TruncateFn {
         let ellipsis = self.ellipsis.resolve(ctx)?;
         let suffix = self.suffix.resolve(ctx)?;
 
-        truncate(value, limit, ellipsis, suffix)
+        truncate(value, limit, ellipsis, suffix, self.unit)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -197,5 +287,64 @@ mod tests {
             want: Ok("S[TRUNCATED]"),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        suffix_within_limit {
+            args: func_args![value: "Supercalifragilisticexpialidocious",
+                             limit: 8,
+                             ellipsis: true,
+                             unit: "codepoints",
+            ],
+            want: Ok("Super..."),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        suffix_longer_than_limit {
+            args: func_args![value: "Super",
+                             limit: 2,
+                             suffix: "[TRUNCATED]",
+                             unit: "codepoints",
+            ],
+            want: Ok("[T"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        exact_with_unit {
+            args: func_args![value: "Super",
+                             limit: 5,
+                             suffix: "...",
+                             unit: "graphemes",
+            ],
+            want: Ok("Super"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        graphemes {
+            args: func_args![value: "e\u{301}e\u{301}e\u{301}",
+                             limit: 2,
+                             suffix: "~",
+                             unit: "graphemes",
+            ],
+            want: Ok("e\u{301}~"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        bytes_at_char_boundary {
+            args: func_args![value: "♔♕♖♗",
+                             limit: 8,
+                             suffix: ".",
+                             unit: "bytes",
+            ],
+            want: Ok("♔♕."),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_unit {
+            args: func_args![value: "Super",
+                             limit: 2,
+                             unit: "words",
+            ],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::bytes().infallible(),
+        }
     ];
 }
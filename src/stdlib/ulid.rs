@@ -0,0 +1,191 @@
+use crate::compiler::prelude::*;
+use chrono::Utc;
+use rand::{thread_rng, RngCore};
+
+/// Crockford's base 32 alphabet.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const LENGTH: usize = 26;
+
+/// Encodes a ULID from its timestamp in milliseconds, which must fit in 48
+/// bits, and 80 bits of randomness.
+pub(crate) fn encode(timestamp: u64, randomness: [u8; 10]) -> String {
+    let mut bytes = [0; 16];
+    bytes[..6].copy_from_slice(&timestamp.to_be_bytes()[2..]);
+    bytes[6..].copy_from_slice(&randomness);
+    let mut value = u128::from_be_bytes(bytes);
+
+    let mut encoded = [0; LENGTH];
+    for char in encoded.iter_mut().rev() {
+        *char = ALPHABET[(value & 31) as usize];
+        value >>= 5;
+    }
+
+    encoded.iter().copied().map(char::from).collect()
+}
+
+/// Decodes a ULID, case insensitively, into its timestamp in milliseconds and
+/// its randomness.
+pub(crate) fn decode(ulid: &str) -> Result<(u64, [u8; 10]), String> {
+    if ulid.len() != LENGTH {
+        return Err(format!(
+            "expected {LENGTH} characters, got {}",
+            ulid.chars().count()
+        ));
+    }
+
+    let mut value = 0_u128;
+    for (index, char) in ulid.chars().enumerate() {
+        let digit = ALPHABET
+            .iter()
+            .position(|candidate| char::from(*candidate).eq_ignore_ascii_case(&char))
+            .ok_or_else(|| format!("invalid character {char:?} at position {index}"))?;
+        // The 26 characters encode 130 bits, of which the first two must be 0.
+        if index == 0 && digit > 7 {
+            return Err("timestamp is too large".to_owned());
+        }
+        value = (value << 5) | digit as u128;
+    }
+
+    let bytes = value.to_be_bytes();
+    let mut timestamp = [0; 8];
+    timestamp[2..].copy_from_slice(&bytes[..6]);
+    let mut randomness = [0; 10];
+    randomness.copy_from_slice(&bytes[6..]);
+
+    Ok((u64::from_be_bytes(timestamp), randomness))
+}
+
+fn ulid(timestamp: Option<Value>) -> Resolved {
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp.try_timestamp()?,
+        None => Utc::now(),
+    };
+    let timestamp = u64::try_from(timestamp.timestamp_millis())
+        .ok()
+        .filter(|millis| millis >> 48 == 0)
+        .ok_or("timestamp is out of range for a ULID")?;
+
+    let mut randomness = [0; 10];
+    thread_rng().fill_bytes(&mut randomness);
+
+    Ok(encode(timestamp, randomness).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ulid;
+
+impl Function for Ulid {
+    fn identifier(&self) -> &'static str {
+        "ulid"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "timestamp",
+            kind: kind::TIMESTAMP,
+            required: false,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "current time",
+                source: "length(ulid())",
+                result: Ok("26"),
+            },
+            Example {
+                title: "explicit timestamp",
+                source: r#"slice!(ulid!(t'2024-03-08T10:00:00.123Z'), 0, 10)"#,
+                result: Ok("01HRER2PBV"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let timestamp = arguments.optional("timestamp");
+
+        Ok(UlidFn { timestamp }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UlidFn {
+    timestamp: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for UlidFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let timestamp = self
+            .timestamp
+            .as_ref()
+            .map(|timestamp| timestamp.resolve(ctx))
+            .transpose()?;
+
+        ulid(timestamp)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().maybe_fallible(self.timestamp.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone as _};
+
+    #[test]
+    fn encode_decode() {
+        let randomness = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let ulid = encode(1_709_892_000_123, randomness);
+
+        assert_eq!(ulid, "01HRER2PBV041061050R3GG28A");
+        assert_eq!(decode(&ulid), Ok((1_709_892_000_123, randomness)));
+        assert_eq!(
+            decode(&ulid.to_lowercase()),
+            Ok((1_709_892_000_123, randomness))
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(encode(0, [0; 10]), "00000000000000000000000000");
+        assert_eq!(
+            encode((1 << 48) - 1, [0xff; 10]),
+            "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+        );
+        assert_eq!(
+            decode("80000000000000000000000000"),
+            Err("timestamp is too large".to_owned())
+        );
+    }
+
+    #[test]
+    fn generated() {
+        let timestamp = Utc.timestamp_millis_opt(1_709_892_000_123).unwrap();
+        let first = ulid(Some(timestamp.into())).unwrap();
+        let second = ulid(Some(timestamp.into())).unwrap();
+
+        assert_ne!(first, second);
+        let (millis, _) = decode(&first.try_bytes_utf8_lossy().unwrap()).unwrap();
+        assert_eq!(millis, 1_709_892_000_123);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let timestamp = DateTime::parse_from_rfc3339("1969-12-31T23:59:59Z").unwrap();
+
+        assert_eq!(
+            ulid(Some(timestamp.with_timezone(&Utc).into()))
+                .unwrap_err()
+                .to_string(),
+            "timestamp is out of range for a ULID"
+        );
+    }
+}
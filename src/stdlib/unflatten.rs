@@ -1,35 +1,50 @@
 use itertools::Itertools;
 
+use super::flatten::ArrayIndices;
 use crate::compiler::prelude::*;
 
 static DEFAULT_SEPARATOR: &str = ".";
 
-fn unflatten(value: Value, separator: Value, recursive: Value) -> Resolved {
+#[derive(Clone, Copy)]
+struct Options<'a> {
+    separator: &'a str,
+    recursive: bool,
+    array_indices: ArrayIndices,
+}
+
+fn unflatten(
+    value: Value,
+    separator: Value,
+    recursive: Value,
+    array_indices: ArrayIndices,
+) -> Resolved {
     let separator = separator.try_bytes_utf8_lossy()?.into_owned();
     let recursive = recursive.try_boolean()?;
     let map = value.try_object()?;
-    Ok(do_unflatten(map.into(), &separator, recursive))
+    let options = Options {
+        separator: &separator,
+        recursive,
+        array_indices,
+    };
+    Ok(do_unflatten(map.into(), options))
 }
 
-fn do_unflatten(value: Value, separator: &str, recursive: bool) -> Value {
+fn do_unflatten(value: Value, options: Options<'_>) -> Value {
     match value {
-        Value::Object(map) => do_unflatten_entries(map, separator, recursive).into(),
+        Value::Object(map) => do_unflatten_entries(map, options).into(),
         // Note that objects inside arrays are not unflattened
         _ => value,
     }
 }
 
-fn do_unflatten_entries<I>(entries: I, separator: &str, recursive: bool) -> ObjectMap
+fn do_unflatten_entries<I>(entries: I, options: Options<'_>) -> ObjectMap
 where
     I: IntoIterator<Item = (KeyString, Value)>,
 {
     let grouped = entries
         .into_iter()
         .map(|(key, value)| {
-            let (head, rest) = match key.split_once(separator) {
-                Some((key, rest)) => (key.to_string().into(), Some(rest.to_string().into())),
-                None => (key.clone(), None),
-            };
+            let (head, rest) = split_key(&key, options);
             (head, rest, value)
         })
         .into_group_map_by(|(head, _, _)| head.clone());
@@ -40,15 +55,15 @@ where
             if values.len() == 1 {
                 match values.pop().expect("exactly one element") {
                     (_, None, value) => {
-                        let value = if recursive {
-                            do_unflatten(value, separator, recursive)
+                        let value = if options.recursive {
+                            do_unflatten(value, options)
                         } else {
                             value
                         };
                         return (key, value);
                     }
                     (_, Some(rest), value) => {
-                        let result = do_unflatten_entry((rest, value), separator, recursive);
+                        let result = do_unflatten_entry((rest, value), options);
                         return (key, result);
                     }
                 }
@@ -72,8 +87,8 @@ where
                     rest.map(|rest| (rest, value))
                 })
                 .collect::<Vec<_>>();
-            let result = do_unflatten_entries(new_entries, separator, recursive);
-            (key, result.into())
+            let result = do_unflatten_entries(new_entries, options);
+            (key, nested(result, options))
         })
         .collect()
 }
@@ -81,20 +96,117 @@ where
 // Optimization in the case we have to flatten objects like
 // { "a.b.c.d": 1 }
 // and avoid doing recursive calls to `do_unflatten_entries` with a single entry every time
-fn do_unflatten_entry(entry: (KeyString, Value), separator: &str, recursive: bool) -> Value {
-    let (key, value) = entry;
-    let keys = key.split(separator).map(Into::into).collect::<Vec<_>>();
-    let mut result = if recursive {
-        do_unflatten(value, separator, recursive)
+fn do_unflatten_entry(entry: (KeyString, Value), options: Options<'_>) -> Value {
+    let (mut key, value) = entry;
+    let mut keys = Vec::new();
+    loop {
+        let (head, rest) = split_key(&key, options);
+        keys.push(head);
+        match rest {
+            Some(rest) => key = rest,
+            None => break,
+        }
+    }
+
+    let mut result = if options.recursive {
+        do_unflatten(value, options)
     } else {
         value
     };
     for key in keys.into_iter().rev() {
-        result = Value::Object(ObjectMap::from_iter([(key, result)]));
+        result = nested(ObjectMap::from_iter([(key, result)]), options);
     }
     result
 }
 
+/// Splits the first segment off the key. With bracketed array indices, an
+/// index such as `[0]` is a segment of its own.
+fn split_key(key: &str, options: Options<'_>) -> (KeyString, Option<KeyString>) {
+    if options.array_indices == ArrayIndices::Brackets {
+        if let Some(end) = bracketed_index_len(key) {
+            let rest = &key[end..];
+            let rest = rest.strip_prefix(options.separator).unwrap_or(rest);
+            let rest = (!rest.is_empty()).then(|| rest.into());
+            return (key[..end].into(), rest);
+        }
+
+        let segment = key.split(options.separator).next().unwrap_or(key);
+        if let Some(start) = segment.find('[').filter(|&start| start > 0) {
+            if is_bracketed_indices(&segment[start..]) {
+                return (key[..start].into(), Some(key[start..].into()));
+            }
+        }
+    }
+
+    match key.split_once(options.separator) {
+        Some((key, rest)) if !options.separator.is_empty() => (key.into(), Some(rest.into())),
+        _ => (key.into(), None),
+    }
+}
+
+/// The length of the bracketed array index at the start of the key, if any.
+fn bracketed_index_len(key: &str) -> Option<usize> {
+    let digits = key.strip_prefix('[')?;
+    let end = digits.find(']')?;
+    parse_index(&digits[..end]).map(|_| end + 2)
+}
+
+fn is_bracketed_indices(mut segment: &str) -> bool {
+    while !segment.is_empty() {
+        match bracketed_index_len(segment) {
+            Some(len) => segment = &segment[len..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Parses an array index without leading zeros, as `flatten` encodes them.
+fn parse_index(index: &str) -> Option<usize> {
+    let canonical = index == "0" || !index.starts_with('0');
+    if canonical && !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()) {
+        index.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Reconstructs an array from an object created by unflattening, if its keys
+/// are exactly the indices of an array.
+fn nested(map: ObjectMap, options: Options<'_>) -> Value {
+    let index = |key: &str| match options.array_indices {
+        ArrayIndices::None => None,
+        ArrayIndices::Separator => parse_index(key),
+        ArrayIndices::Brackets => key
+            .strip_prefix('[')
+            .and_then(|key| key.strip_suffix(']'))
+            .and_then(parse_index),
+    };
+
+    let mut indices = Vec::with_capacity(map.len());
+    for key in map.keys() {
+        match index(key.as_str()) {
+            Some(index) => indices.push(index),
+            None => return map.into(),
+        }
+    }
+    indices.sort_unstable();
+    if indices
+        .iter()
+        .enumerate()
+        .any(|(position, index)| position != *index)
+    {
+        return map.into();
+    }
+
+    map.into_iter()
+        .map(|(key, value)| (index(key.as_str()).expect("checked index"), value))
+        .sorted_by_key(|(index, _)| *index)
+        .map(|(_, value)| value)
+        .collect::<Vec<_>>()
+        .into()
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Unflatten;
 
@@ -120,6 +232,11 @@ impl Function for Unflatten {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "array_indices",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -149,12 +266,17 @@ impl Function for Unflatten {
                 source: r#"unflatten({ "foo_bar": true }, "_")"#,
                 result: Ok(r#"{"foo": { "bar": true }}"#),
             },
+            Example {
+                title: "array indices",
+                source: r#"unflatten({ "foo[0].bar": true, "foo[1]": 1 }, array_indices: "brackets")"#,
+                result: Ok(r#"{ "foo": [{ "bar": true }, 1] }"#),
+            },
         ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
@@ -165,11 +287,16 @@ impl Function for Unflatten {
         let recursive = arguments
             .optional("recursive")
             .unwrap_or_else(|| expr!(true));
+        let array_indices = arguments
+            .optional_enum("array_indices", &ArrayIndices::all_value(), state)?
+            .map(|value| ArrayIndices::from_value(&value))
+            .unwrap_or_default();
 
         Ok(UnflattenFn {
             value,
             separator,
             recursive,
+            array_indices,
         }
         .as_expr())
     }
@@ -180,6 +307,7 @@ struct UnflattenFn {
     value: Box<dyn Expression>,
     separator: Box<dyn Expression>,
     recursive: Box<dyn Expression>,
+    array_indices: ArrayIndices,
 }
 
 impl FunctionExpression for UnflattenFn {
@@ -188,7 +316,7 @@ impl FunctionExpression for UnflattenFn {
         let separator = self.separator.resolve(ctx)?;
         let recursive = self.recursive.resolve(ctx)?;
 
-        unflatten(value, separator, recursive)
+        unflatten(value, separator, recursive, self.array_indices)
     }
 
     fn type_def(&self, _: &TypeState) -> TypeDef {
@@ -412,5 +540,43 @@ mod test {
             })),
             tdef: TypeDef::object(Collection::any()),
         }
+
+        array_indices_separator {
+            args: func_args![value: value!({
+                "parent.0.child": 1,
+                "parent.1.0": 2,
+                "parent.1.1": 3,
+                "sparse.1": 4,
+                "padded.00": 5,
+            }), array_indices: "separator"],
+            want: Ok(value!({
+                parent: [{ child: 1 }, [2, 3]],
+                sparse: { "1": 4 },
+                padded: { "00": 5 },
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        array_indices_brackets {
+            args: func_args![value: value!({
+                "parent[0]_child": 1,
+                "parent[1][0]": 2,
+                "parent[1][1]": 3,
+                "other_0": 4,
+                "name[x]": 5,
+            }), separator: "_", array_indices: "brackets"],
+            want: Ok(value!({
+                parent: [{ child: 1 }, [2, 3]],
+                other: { "0": 4 },
+                "name[x]": 5,
+            })),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        array_indices_not_reconstructed_by_default {
+            args: func_args![value: value!({ "parent.0": 1 })],
+            want: Ok(value!({ parent: { "0": 1 } })),
+            tdef: TypeDef::object(Collection::any()),
+        }
     ];
 }
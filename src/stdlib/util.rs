@@ -1,5 +1,9 @@
 use crate::compiler::{Context, Expression, Resolved, TypeState};
 use crate::value::{KeyString, ObjectMap, Value};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 
 /// Rounds the given number to the given precision.
 /// Takes a function parameter so the exact rounding function (ceil, floor or round)
@@ -117,6 +121,185 @@ impl std::str::FromStr for Base64Charset {
     }
 }
 
+pub(super) fn base64_charsets() -> Vec<Value> {
+    vec![Value::from("standard"), Value::from("url_safe")]
+}
+
+/// Rejects an unknown base64 charset at compile time, if it's a constant.
+pub(super) fn check_base64_charset(
+    charset: &dyn Expression,
+    state: &TypeState,
+) -> Result<(), crate::compiler::function::Error> {
+    match charset.resolve_constant(state) {
+        Some(value) if !base64_charsets().contains(&value) => {
+            Err(crate::compiler::function::Error::InvalidEnumVariant {
+                keyword: "charset",
+                value,
+                variants: base64_charsets(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The base32 alphabets supported by `encode_base32` and `decode_base32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Base32Variant {
+    /// The RFC 4648 alphabet.
+    Standard,
+    /// The RFC 4648 "extended hex" alphabet.
+    Hex,
+    /// Douglas Crockford's alphabet, which is never padded.
+    Crockford,
+}
+
+// Statics rather than the `data_encoding` constants, so `encoding` can hand out
+// `'static` references to them.
+static BASE32: data_encoding::Encoding = data_encoding::BASE32;
+static BASE32_NOPAD: data_encoding::Encoding = data_encoding::BASE32_NOPAD;
+static BASE32HEX: data_encoding::Encoding = data_encoding::BASE32HEX;
+static BASE32HEX_NOPAD: data_encoding::Encoding = data_encoding::BASE32HEX_NOPAD;
+
+static BASE32_CROCKFORD: once_cell::sync::Lazy<data_encoding::Encoding> =
+    once_cell::sync::Lazy::new(|| {
+        let mut spec = data_encoding::Specification::new();
+        spec.symbols.push_str("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+        // Decoding is case insensitive, maps easily confused letters to the
+        // digits they resemble and ignores hyphens.
+        spec.translate.from.push_str("abcdefghjkmnpqrstvwxyzILOilo");
+        spec.translate.to.push_str("ABCDEFGHJKMNPQRSTVWXYZ110110");
+        spec.ignore.push('-');
+        spec.encoding()
+            .expect("valid Crockford base32 specification")
+    });
+
+impl Base32Variant {
+    pub(super) fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes {
+            b"standard" => Self::Standard,
+            b"hex" => Self::Hex,
+            b"crockford" => Self::Crockford,
+            _ => unreachable!("enum invariant"),
+        }
+    }
+
+    pub(super) fn variants() -> Vec<Value> {
+        vec![
+            Value::from("standard"),
+            Value::from("hex"),
+            Value::from("crockford"),
+        ]
+    }
+
+    pub(super) fn encoding(self, padding: bool) -> &'static data_encoding::Encoding {
+        match (self, padding) {
+            (Self::Standard, true) => &BASE32,
+            (Self::Standard, false) => &BASE32_NOPAD,
+            (Self::Hex, true) => &BASE32HEX,
+            (Self::Hex, false) => &BASE32HEX_NOPAD,
+            (Self::Crockford, _) => &BASE32_CROCKFORD,
+        }
+    }
+}
+
+/// The base58 alphabets supported by `encode_base58` and `decode_base58`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Base58Variant {
+    /// The Bitcoin alphabet, also used by IPFS.
+    Bitcoin,
+    Flickr,
+    Ripple,
+}
+
+impl Base58Variant {
+    pub(super) fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes {
+            b"bitcoin" => Self::Bitcoin,
+            b"flickr" => Self::Flickr,
+            b"ripple" => Self::Ripple,
+            _ => unreachable!("enum invariant"),
+        }
+    }
+
+    pub(super) fn variants() -> Vec<Value> {
+        vec![
+            Value::from("bitcoin"),
+            Value::from("flickr"),
+            Value::from("ripple"),
+        ]
+    }
+
+    pub(super) fn alphabet(self) -> &'static bs58::Alphabet {
+        match self {
+            Self::Bitcoin => bs58::Alphabet::BITCOIN,
+            Self::Flickr => bs58::Alphabet::FLICKR,
+            Self::Ripple => bs58::Alphabet::RIPPLE,
+        }
+    }
+}
+
+/// The formats supported by `encode_snappy` and `decode_snappy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SnappyFormat {
+    /// A single compressed block, without a header or checksum.
+    Raw,
+    /// The framing format, a stream of checksummed chunks used by Kafka and
+    /// Hadoop among others.
+    Framed,
+}
+
+impl SnappyFormat {
+    pub(super) fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes {
+            b"raw" => Self::Raw,
+            b"framed" => Self::Framed,
+            _ => unreachable!("enum invariant"),
+        }
+    }
+
+    pub(super) fn variants() -> Vec<Value> {
+        vec![Value::from("raw"), Value::from("framed")]
+    }
+}
+
+/// The formats supported by `encode_lz4` and `decode_lz4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Lz4Format {
+    /// The frame format, as written by the `lz4` command line tool.
+    Frame,
+    /// A single block, prefixed with its uncompressed size as a little endian
+    /// 32-bit integer, since a block doesn't record it.
+    Block,
+}
+
+impl Lz4Format {
+    pub(super) fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes {
+            b"frame" => Self::Frame,
+            b"block" => Self::Block,
+            _ => unreachable!("enum invariant"),
+        }
+    }
+
+    pub(super) fn variants() -> Vec<Value> {
+        vec![Value::from("frame"), Value::from("block")]
+    }
+}
+
+/// The longest window of the functions keeping state across events, one year.
+const MAX_WINDOW_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// Converts the `window_secs` argument of the functions keeping state across
+/// events, such as `throttle`, into the time to live of their entries.
+pub(super) fn window_duration(window_secs: &Value) -> Result<Duration, &'static str> {
+    match window_secs.as_integer().map(u64::try_from) {
+        Some(Ok(window_secs)) if (1..=MAX_WINDOW_SECS).contains(&window_secs) => {
+            Ok(Duration::from_secs(window_secs))
+        }
+        _ => Err(r#""window_secs" must be positive and at most one year (31536000 seconds)"#),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(super) enum ConstOrExpr {
     Const(Value),
@@ -142,3 +325,96 @@ impl ConstOrExpr {
         }
     }
 }
+
+/// A map of compiled values shared between programs. Once `capacity` entries are
+/// stored, the oldest one is evicted to make room for a new one.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub(super) struct BoundedCache<K, V> {
+    capacity: usize,
+    entries: Mutex<CacheEntries<K, V>>,
+}
+
+struct CacheEntries<K, V> {
+    values: HashMap<K, V>,
+    order: VecDeque<K>,
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+impl<K, V> BoundedCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(CacheEntries {
+                values: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the cached value for `key`, or caches the one returned by `init`.
+    /// Errors are not cached, and the lock isn't held while `init` runs.
+    pub(super) fn get_or_try_insert_with<E>(
+        &self,
+        key: K,
+        init: impl FnOnce() -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.lock().values.get(&key) {
+            return Ok(value.clone());
+        }
+
+        let value = init()?;
+
+        let mut entries = self.lock();
+        if !entries.values.contains_key(&key) {
+            if entries.values.len() >= self.capacity {
+                if let Some(oldest) = entries.order.pop_front() {
+                    entries.values.remove(&oldest);
+                }
+            }
+            entries.order.push_back(key.clone());
+        }
+        entries.values.insert(key, value.clone());
+
+        Ok(value)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheEntries<K, V>> {
+        // a panic while holding the lock can't leave the entries inconsistent
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_cache_evicts_oldest() {
+        let cache = BoundedCache::new(2);
+        let insert = |key: u8| cache.get_or_try_insert_with(key, || Ok::<_, ()>(key.to_string()));
+
+        assert_eq!(insert(1), Ok("1".to_owned()));
+        assert_eq!(insert(2), Ok("2".to_owned()));
+        assert_eq!(insert(3), Ok("3".to_owned()));
+
+        let cached = |key: u8| cache.get_or_try_insert_with(key, || Err(()));
+        assert_eq!(cached(1), Err(()));
+        assert_eq!(cached(2), Ok("2".to_owned()));
+        assert_eq!(cached(3), Ok("3".to_owned()));
+    }
+
+    #[test]
+    fn bounded_cache_skips_errors() {
+        let cache = BoundedCache::new(2);
+
+        assert_eq!(
+            cache.get_or_try_insert_with(1, || Err("invalid")),
+            Err("invalid")
+        );
+        assert_eq!(cache.get_or_try_insert_with(1, || Ok::<_, &str>(1)), Ok(1));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::compiler::prelude::*;
+use uuid::Uuid;
+
+use super::uuid_v5::{encode, namespace};
+
+fn uuid_v3(namespace: Value, name: Value) -> Resolved {
+    let namespace = self::namespace(namespace)?;
+    let name = name.try_bytes()?;
+
+    Ok(encode(Uuid::new_v3(&namespace, &name)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UuidV3;
+
+impl Function for UuidV3 {
+    fn identifier(&self) -> &'static str {
+        "uuid_v3"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "namespace",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "name",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "standard namespace",
+            source: r#"uuid_v3!("dns", "python.org")"#,
+            result: Ok("6fa459ea-ee8a-3ca4-894e-db77e160355e"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let namespace = arguments.required("namespace");
+        let name = arguments.required("name");
+
+        Ok(UuidV3Fn { namespace, name }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UuidV3Fn {
+    namespace: Box<dyn Expression>,
+    name: Box<dyn Expression>,
+}
+
+impl FunctionExpression for UuidV3Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let namespace = self.namespace.resolve(ctx)?;
+        let name = self.name.resolve(ctx)?;
+
+        uuid_v3(namespace, name)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        uuid_v3 => UuidV3;
+
+        dns {
+            args: func_args![namespace: "dns", name: "python.org"],
+            want: Ok("6fa459ea-ee8a-3ca4-894e-db77e160355e"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        url {
+            args: func_args![namespace: "url", name: "https://vector.dev/docs"],
+            want: Ok("e1ce6821-94d9-3841-a739-1b47808a8fae"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        custom_namespace {
+            args: func_args![namespace: "2f6bf1a4-7d3c-4e8a-9b1f-5c0e2d4a6b8c", name: "web-01/var/log/app.log"],
+            want: Ok("7f8ec97a-95c1-307f-906e-e20b83a85292"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
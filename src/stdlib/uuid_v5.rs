@@ -0,0 +1,148 @@
+use crate::compiler::prelude::*;
+use bytes::Bytes;
+use uuid::Uuid;
+
+/// Resolves the namespace of a name-based UUID, which is either a UUID or the
+/// name of one of the standard namespaces of RFC 9562.
+pub(crate) fn namespace(value: Value) -> Result<Uuid, ExpressionError> {
+    let namespace = value.try_bytes_utf8_lossy()?;
+
+    match namespace.as_ref() {
+        "dns" => Ok(Uuid::NAMESPACE_DNS),
+        "url" => Ok(Uuid::NAMESPACE_URL),
+        "oid" => Ok(Uuid::NAMESPACE_OID),
+        "x500" => Ok(Uuid::NAMESPACE_X500),
+        namespace => {
+            Uuid::parse_str(namespace).map_err(|err| format!("invalid namespace: {err}").into())
+        }
+    }
+}
+
+pub(crate) fn encode(uuid: Uuid) -> Value {
+    let mut buffer = [0; 36];
+    let uuid = uuid.hyphenated().encode_lower(&mut buffer);
+    Bytes::copy_from_slice(uuid.as_bytes()).into()
+}
+
+fn uuid_v5(namespace: Value, name: Value) -> Resolved {
+    let namespace = self::namespace(namespace)?;
+    let name = name.try_bytes()?;
+
+    Ok(encode(Uuid::new_v5(&namespace, &name)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UuidV5;
+
+impl Function for UuidV5 {
+    fn identifier(&self) -> &'static str {
+        "uuid_v5"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "namespace",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "name",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "standard namespace",
+                source: r#"uuid_v5!("dns", "python.org")"#,
+                result: Ok("886313e1-3b8a-5372-9b90-0c9aee199e5d"),
+            },
+            Example {
+                title: "custom namespace",
+                source: r#"uuid_v5!("2f6bf1a4-7d3c-4e8a-9b1f-5c0e2d4a6b8c", "web-01/var/log/app.log")"#,
+                result: Ok("18100f4b-08e0-5405-bebb-b3f9dace3ea1"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let namespace = arguments.required("namespace");
+        let name = arguments.required("name");
+
+        Ok(UuidV5Fn { namespace, name }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UuidV5Fn {
+    namespace: Box<dyn Expression>,
+    name: Box<dyn Expression>,
+}
+
+impl FunctionExpression for UuidV5Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let namespace = self.namespace.resolve(ctx)?;
+        let name = self.name.resolve(ctx)?;
+
+        uuid_v5(namespace, name)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        uuid_v5 => UuidV5;
+
+        dns {
+            args: func_args![namespace: "dns", name: "python.org"],
+            want: Ok("886313e1-3b8a-5372-9b90-0c9aee199e5d"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        url {
+            args: func_args![namespace: "url", name: "https://vector.dev/docs"],
+            want: Ok("4a73ace3-8848-51ba-9007-a6875346454b"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        oid {
+            args: func_args![namespace: "oid", name: "1.3.6.1"],
+            want: Ok("1447fa61-5277-5fef-a9b3-fbc6e44f4af3"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        x500 {
+            args: func_args![namespace: "x500", name: "cn=vector"],
+            want: Ok("ceb1bdaf-5428-5104-8510-3c397c06631a"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        custom_namespace {
+            args: func_args![namespace: "2F6BF1A4-7D3C-4E8A-9B1F-5C0E2D4A6B8C", name: "web-01/var/log/app.log"],
+            want: Ok("18100f4b-08e0-5405-bebb-b3f9dace3ea1"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+
+    #[test]
+    fn invalid_namespace() {
+        let error = namespace("web".into()).unwrap_err().to_string();
+
+        assert!(error.starts_with("invalid namespace: "), "{error}");
+    }
+}
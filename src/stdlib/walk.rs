@@ -0,0 +1,193 @@
+use crate::compiler::prelude::*;
+
+fn walk<T>(
+    value: &Value,
+    max_depth: Value,
+    ctx: &mut Context,
+    runner: &closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let max_depth = match max_depth {
+        Value::Null => None,
+        max_depth => {
+            let max_depth = max_depth.try_integer()?;
+            let max_depth = usize::try_from(max_depth)
+                .map_err(|_| format!(r#""max_depth" must be at least 0, got {max_depth}"#))?;
+            Some(max_depth)
+        }
+    };
+
+    walk_children(value, &mut Vec::new(), max_depth, ctx, runner)?;
+
+    Ok(Value::Null)
+}
+
+/// Calls the closure with each child of the value, and walks the children of
+/// the child unless the closure returns `false` or the maximum depth is
+/// reached.
+fn walk_children<T>(
+    value: &Value,
+    path: &mut Vec<Value>,
+    max_depth: Option<usize>,
+    ctx: &mut Context,
+    runner: &closure::Runner<T>,
+) -> Result<(), ExpressionError>
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    if max_depth.is_some_and(|max_depth| path.len() >= max_depth) {
+        return Ok(());
+    }
+
+    let children: Box<dyn Iterator<Item = (Value, &Value)>> = match value {
+        Value::Object(object) => Box::new(
+            object
+                .iter()
+                .map(|(key, value)| (key.as_str().into(), value)),
+        ),
+        Value::Array(array) => Box::new(
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index.into(), value)),
+        ),
+        _ => return Ok(()),
+    };
+
+    for (segment, child) in children {
+        path.push(segment);
+        let descend = runner.run_values(ctx, &[&Value::Array(path.clone()), child])?;
+        if descend != Value::Boolean(false) {
+            walk_children(child, path, max_depth, ctx, runner)?;
+        }
+        path.pop();
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Walk;
+
+impl Function for Walk {
+    fn identifier(&self) -> &'static str {
+        "walk"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_depth",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "count nodes",
+                source: r#"count = 0; walk({ "a": { "b": [1, 2] } }) -> |_path, _value| { count = count + 1 }; count"#,
+                result: Ok("4"),
+            },
+            Example {
+                title: "limit depth",
+                source: r#"paths = []; walk({ "a": { "b": { "c": 1 } } }, max_depth: 2) -> |path, _value| { paths = push(paths, path) }; paths"#,
+                result: Ok(r#"[["a"], ["a", "b"]]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_depth = arguments.optional("max_depth").unwrap_or(expr!(null));
+        let closure = arguments.required_closure()?;
+
+        if let Some(literal) = max_depth.resolve_constant(state) {
+            if literal.as_integer().is_some_and(|max_depth| max_depth < 0) {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "max_depth",
+                    value: literal,
+                    error: r#""max_depth" must be at least 0"#,
+                }
+                .into());
+            }
+        }
+
+        Ok(WalkFn {
+            value,
+            max_depth,
+            closure,
+        }
+        .as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::Exact(Kind::array(Collection::from_unknown(
+                            Kind::bytes().or_integer(),
+                        ))),
+                    },
+                    Variable {
+                        kind: VariableKind::Exact(Kind::any()),
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "prune subtrees",
+                    source: r#"paths = []; walk({ "a": { "b": 1 }, "secret": { "c": 2 } }) -> |path, _value| { paths = push(paths, path); path != ["secret"] }; paths"#,
+                    result: Ok(r#"[["a"], ["a", "b"], ["secret"]]"#),
+                },
+            }],
+            is_iterator: true,
+            is_optional: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WalkFn {
+    value: Box<dyn Expression>,
+    max_depth: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for WalkFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_depth = self.max_depth.resolve(ctx)?;
+        let FunctionClosure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        walk(&value, max_depth, ctx, &runner)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let not_literal = self.max_depth.resolve_constant(state).is_none();
+        TypeDef::null().maybe_fallible(not_literal)
+    }
+}
@@ -0,0 +1,144 @@
+use crate::compiler::prelude::*;
+
+use super::hash_util::{self, encodings, seed64, HashEncoding};
+
+fn xxhash64(value: Value, seed: Value, encoding: HashEncoding) -> Resolved {
+    let value = value.try_bytes()?;
+    let seed = seed64(seed)?;
+
+    Ok(encoding.encode64(hash_util::xxhash64(&value, seed)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Xxhash64;
+
+impl Function for Xxhash64 {
+    fn identifier(&self) -> &'static str {
+        "xxhash64"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "encoding",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "xxhash64",
+                source: r#"xxhash64("foo")"#,
+                result: Ok("3728699739546630719"),
+            },
+            Example {
+                title: "hex encoding",
+                source: r#"xxhash64("foobar", encoding: "hex")"#,
+                result: Ok("a2aa05ed9085aaf9"),
+            },
+            Example {
+                title: "seed",
+                source: r#"xxhash64("foo", seed: 42)"#,
+                result: Ok("-3075308222547705278"),
+            },
+        ]
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let seed = arguments.optional("seed").unwrap_or_else(|| expr!(0));
+        let encoding = arguments
+            .optional_enum("encoding", &encodings(), state)?
+            .map_or(HashEncoding::Integer, |encoding| {
+                HashEncoding::from_bytes(&encoding.try_bytes().expect("encoding not bytes"))
+            });
+
+        Ok(Xxhash64Fn {
+            value,
+            seed,
+            encoding,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Xxhash64Fn {
+    value: Box<dyn Expression>,
+    seed: Box<dyn Expression>,
+    encoding: HashEncoding,
+}
+
+impl FunctionExpression for Xxhash64Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let seed = self.seed.resolve(ctx)?;
+
+        xxhash64(value, seed, self.encoding)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        self.encoding.type_def()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        xxhash64 => Xxhash64;
+
+        integer {
+            args: func_args![value: "foo"],
+            want: Ok(3_728_699_739_546_630_719_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        wraps_around {
+            args: func_args![value: "foobar"],
+            want: Ok(-6_725_556_575_634_347_271_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        hex {
+            args: func_args![value: "foobar", encoding: "hex"],
+            want: Ok("a2aa05ed9085aaf9"),
+            tdef: TypeDef::bytes(),
+        }
+
+        seed {
+            args: func_args![value: "foo", seed: 42],
+            want: Ok(-3_075_308_222_547_705_278_i64),
+            tdef: TypeDef::integer(),
+        }
+
+        long_input {
+            args: func_args![value: "The quick brown fox jumps over the lazy dog", encoding: "hex"],
+            want: Ok("0b242d361fda71bc"),
+            tdef: TypeDef::bytes(),
+        }
+    ];
+}
@@ -0,0 +1,135 @@
+//! Representative programs for measuring the performance of the compiler and
+//! the runtime. They are used by the `programs` criterion benchmarks of this
+//! crate, and can be used by hosts to benchmark them against their own
+//! functions in CI.
+
+use std::collections::BTreeMap;
+
+use crate::compiler::{
+    compile,
+    runtime::{Runtime, Terminate},
+    state::RuntimeState,
+    Function, Program, TargetValue, TimeZone,
+};
+use crate::diagnostic::Formatter;
+use crate::value::{Secrets, Value};
+
+/// A program along with the event it runs against.
+#[derive(Debug, Clone, Copy)]
+pub struct Benchmark {
+    pub name: &'static str,
+    pub source: &'static str,
+    /// The event, as JSON.
+    pub event: &'static str,
+}
+
+pub const BENCHMARKS: &[Benchmark] = &[
+    Benchmark {
+        name: "parse_json_and_move_fields",
+        source: r#"
+            . = parse_json!(.message)
+            .http.status_code = del(.status)
+            .http.method = del(.method)
+            .user_id = del(.user.id)
+            .tags = push(array!(.tags), "parsed")
+        "#,
+        event: r#"{
+            "message": "{\"status\":200,\"method\":\"GET\",\"user\":{\"id\":42,\"name\":\"zorp\"},\"tags\":[\"web\",\"prod\"]}"
+        }"#,
+    },
+    Benchmark {
+        name: "parse_grok",
+        source: r#"
+            . |= parse_grok!(.message, "%{IPORHOST:client} %{WORD:method} %{URIPATHPARAM:path} %{NUMBER:status} %{NUMBER:bytes}")
+        "#,
+        event: r#"{ "message": "10.0.0.1 GET /index.html?page=2 200 5324" }"#,
+    },
+    Benchmark {
+        name: "regex_replace",
+        source: r#"
+            .message = replace(string!(.message), r'\b\d{3}-\d{2}-\d{4}\b', "[REDACTED]")
+            .message = replace(.message, r'(?i)password=\S+', "password=[REDACTED]")
+        "#,
+        event: r#"{ "message": "user 123-45-6789 logged in with password=hunter2 from 10.0.0.1" }"#,
+    },
+    Benchmark {
+        name: "nested_path_inserts",
+        source: r#"
+            .kubernetes.pod.name = "api-7d4b9c"
+            .kubernetes.pod.labels.app = "api"
+            .kubernetes.pod.labels.tier = "backend"
+            .kubernetes.container.name = "server"
+            .kubernetes.container.image = "api:1.2.3"
+            .http.request.headers[0].name = "accept"
+            .http.request.headers[0].value = "*/*"
+            .http.request.headers[1].name = "user-agent"
+            .http.request.headers[1].value = "curl/8.0"
+            .http.response.status = 200
+            .metrics.latency.p50 = 1.5
+            .metrics.latency.p99 = 12.25
+        "#,
+        event: r#"{ "message": "GET /" }"#,
+    },
+];
+
+impl Benchmark {
+    /// Compiles the program with the given functions.
+    ///
+    /// # Errors
+    ///
+    /// Returns the formatted diagnostics if the program fails to compile.
+    pub fn compile(&self, functions: &[Box<dyn Function>]) -> Result<Program, String> {
+        compile(self.source, functions)
+            .map(|result| result.program)
+            .map_err(|diagnostics| Formatter::new(self.source, diagnostics).to_string())
+    }
+
+    /// The event the program runs against.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event isn't valid JSON.
+    #[must_use]
+    pub fn event(&self) -> Value {
+        serde_json::from_str(self.event).expect("valid event")
+    }
+
+    /// Runs a compiled program against an event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program fails or aborts.
+    pub fn run(program: &Program, event: Value) -> Result<Value, Terminate> {
+        let mut target = TargetValue {
+            value: event,
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut runtime = Runtime::new(RuntimeState::default());
+
+        runtime.resolve(&mut target, program, &TimeZone::default())?;
+        Ok(target.value)
+    }
+}
+
+#[cfg(all(test, feature = "stdlib"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn benchmarks_run() {
+        let functions = crate::stdlib::all();
+
+        for benchmark in BENCHMARKS {
+            let program = benchmark
+                .compile(&functions)
+                .unwrap_or_else(|error| panic!("{}: {error}", benchmark.name));
+
+            assert!(
+                Benchmark::run(&program, benchmark.event()).is_ok(),
+                "{} failed",
+                benchmark.name
+            );
+        }
+    }
+}
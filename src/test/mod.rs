@@ -2,8 +2,8 @@
 #![allow(clippy::print_stdout)] // tests
 #![allow(clippy::print_stderr)] // tests
 
-use std::path::{PathBuf, MAIN_SEPARATOR};
-use std::{collections::BTreeMap, env, str::FromStr, time::Instant};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::{collections::BTreeMap, env, fs, str::FromStr, time::Instant};
 
 use ansi_term::Colour;
 use chrono::{DateTime, SecondsFormat, Utc};
@@ -22,6 +22,7 @@ use crate::diagnostic::{DiagnosticList, Formatter};
 use crate::value::Secrets;
 use crate::value::Value;
 
+pub mod bench;
 #[allow(clippy::module_inception)]
 mod test;
 
@@ -40,6 +41,9 @@ pub struct TestConfig {
     pub verbose: bool,
     pub no_diff: bool,
     pub timings: bool,
+    /// Rewrite the snapshots of snapshot tests that don't match their result,
+    /// instead of failing them.
+    pub update_snapshots: bool,
     pub runtime: VrlRuntime,
     pub timezone: TimeZone,
 }
@@ -133,10 +137,13 @@ pub fn run_tests<T>(
         let failed = match result {
             Ok(CompilationResult {
                 program,
-                warnings,
+                mut warnings,
                 config: _,
             }) => {
                 warnings_count += warnings.len();
+                if !test.check_diagnostics {
+                    warnings.retain(|warning| !test.allowed_warnings.contains(&warning.code));
+                }
 
                 if test.check_diagnostics {
                     process_compilation_diagnostics(&test, cfg, warnings, compile_timing_fmt)
@@ -226,6 +233,11 @@ fn process_result(
 
             if got_value == want_value {
                 print!("{timings}{}", Colour::Green.bold().paint("OK"));
+            } else if let Some(snapshot) =
+                test.snapshot.as_ref().filter(|_| config.update_snapshots)
+            {
+                write_snapshot(snapshot, &got_value);
+                print!("{timings}{}", Colour::Yellow.bold().paint("UPDATED"));
             } else {
                 print!("{} (expectation)", Colour::Red.bold().paint("FAILED"));
 
@@ -267,6 +279,11 @@ fn process_result(
                 let got = vrl_value_to_json_value(test.object.clone());
                 if got == want {
                     println!("{}{}", Colour::Green.bold().paint("OK"), timings);
+                } else if let Some(snapshot) =
+                    test.snapshot.as_ref().filter(|_| config.update_snapshots)
+                {
+                    write_snapshot(snapshot, &got);
+                    println!("{}{}", Colour::Yellow.bold().paint("UPDATED"), timings);
                 } else {
                     println!("{} (abort)", Colour::Red.bold().paint("FAILED"));
 
@@ -344,6 +361,13 @@ fn process_compilation_diagnostics(
     failed
 }
 
+fn write_snapshot(path: &Path, value: &serde_json::Value) {
+    let mut snapshot = serde_json::to_string_pretty(value).expect("valid JSON");
+    snapshot.push('\n');
+    fs::write(path, snapshot)
+        .unwrap_or_else(|err| panic!("unable to write snapshot {}: {err}", path.display()));
+}
+
 fn print_result(total_count: usize, failed_count: usize, warnings_count: usize) {
     let code = i32::from(failed_count > 0);
 
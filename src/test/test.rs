@@ -1,5 +1,10 @@
-use std::{collections::BTreeMap, fs, path::Path};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
+use crate::compiler::codes::WARNING_FAILING_CONSTANT_CALL_CODE;
 use crate::compiler::function::Example;
 use crate::path::parse_value_path;
 use crate::path::OwnedTargetPath;
@@ -17,8 +22,14 @@ pub struct Test {
     pub result_approx: bool,
     pub skip: bool,
     pub check_diagnostics: bool,
+    /// The codes of the warnings the program can compile with, such as the
+    /// warning of a constant call in an example showing how a function fails.
+    pub allowed_warnings: Vec<usize>,
     // paths set to read-only
     pub read_only_paths: Vec<(OwnedTargetPath, bool)>,
+    /// The `.json` file next to the source that holds the expected result of
+    /// a snapshot test.
+    pub snapshot: Option<PathBuf>,
 }
 
 enum CaptureMode {
@@ -124,6 +135,15 @@ impl Test {
             })
         };
 
+        // The expected result of a snapshot test is read from its snapshot,
+        // which may not exist yet if the snapshots are about to be updated.
+        let snapshot = content
+            .starts_with("# SNAPSHOT")
+            .then(|| path.with_extension("json"));
+        if let Some(snapshot) = &snapshot {
+            result = fs::read_to_string(snapshot).unwrap_or_default();
+        }
+
         // See https://github.com/rust-lang/rust-clippy/pull/12756
         #[allow(clippy::assigning_clones)]
         {
@@ -140,15 +160,17 @@ impl Test {
             result_approx,
             skip: content.starts_with("# SKIP"),
             check_diagnostics: content.starts_with("# DIAGNOSTICS"),
+            allowed_warnings: vec![],
             read_only_paths,
+            snapshot,
         }
     }
 
     pub fn from_example(func: impl ToString, example: &Example) -> Self {
         let object = Value::Object(BTreeMap::default());
-        let result = match example.result {
-            Ok(string) => string.to_owned(),
-            Err(err) => err.to_string(),
+        let (result, allowed_warnings) = match example.result {
+            Ok(string) => (string.to_owned(), vec![]),
+            Err(err) => (err.to_string(), vec![WARNING_FAILING_CONSTANT_CALL_CODE]),
         };
 
         Self {
@@ -161,7 +183,9 @@ impl Test {
             result_approx: false,
             skip: false,
             check_diagnostics: false,
+            allowed_warnings,
             read_only_paths: vec![],
+            snapshot: None,
         }
     }
 }
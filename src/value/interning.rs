@@ -0,0 +1,244 @@
+//! Pools sharing the object keys and short strings that events repeat, enabled with the
+//! `interning` feature.
+//!
+//! With the feature, [`KeyString`](super::KeyString) can hold a shared `Arc<str>`, and keys
+//! created from the same string share one allocation while the key pool has room. The short
+//! strings of deserialized values, such as parsed JSON, are shared the same way by the value pool.
+//!
+//! Each thread keeps its own pools, so that looking up a string never waits on a lock. The pools
+//! don't evict strings: once a pool is full, or holds [`InterningLimits::max_bytes`] bytes of
+//! strings, strings it doesn't hold are allocated as without interning, and [`clear`] empties the
+//! pools of the current thread.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+static MAX_KEYS: AtomicUsize = AtomicUsize::new(InterningLimits::DEFAULT.max_keys);
+static MAX_KEY_LEN: AtomicUsize = AtomicUsize::new(InterningLimits::DEFAULT.max_key_len);
+static MAX_VALUES: AtomicUsize = AtomicUsize::new(InterningLimits::DEFAULT.max_values);
+static MAX_VALUE_LEN: AtomicUsize = AtomicUsize::new(InterningLimits::DEFAULT.max_value_len);
+static MAX_BYTES: AtomicUsize = AtomicUsize::new(InterningLimits::DEFAULT.max_bytes);
+
+thread_local! {
+    static KEYS: RefCell<HashSet<Arc<str>>> = RefCell::new(HashSet::new());
+    static VALUES: RefCell<HashSet<Bytes>> = RefCell::new(HashSet::new());
+
+    /// The bytes of the strings held by both pools.
+    static BYTES: Cell<usize> = const { Cell::new(0) };
+
+    /// The limits set by [`with_limits`], which take precedence over [`set_limits`].
+    static SCOPED_LIMITS: Cell<Option<InterningLimits>> = const { Cell::new(None) };
+}
+
+/// The sizes of the pools of each thread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterningLimits {
+    /// The number of distinct object keys pooled. Zero disables key interning.
+    pub max_keys: usize,
+
+    /// The length in bytes of the longest object key pooled.
+    pub max_key_len: usize,
+
+    /// The number of distinct strings pooled. Zero disables value interning.
+    pub max_values: usize,
+
+    /// The length in bytes of the longest string pooled.
+    pub max_value_len: usize,
+
+    /// The total length in bytes of the keys and strings pooled.
+    pub max_bytes: usize,
+}
+
+impl InterningLimits {
+    const DEFAULT: Self = Self {
+        max_keys: 10_000,
+        max_key_len: 64,
+        max_values: 10_000,
+        max_value_len: 32,
+        max_bytes: 1024 * 1024,
+    };
+
+    /// Limits disabling interning.
+    pub const DISABLED: Self = Self {
+        max_keys: 0,
+        max_key_len: 0,
+        max_values: 0,
+        max_value_len: 0,
+        max_bytes: 0,
+    };
+}
+
+impl Default for InterningLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Sets the sizes of the pools of every thread.
+///
+/// Pools holding more strings than the new limits keep them until [`clear`] is called.
+pub fn set_limits(limits: InterningLimits) {
+    MAX_KEYS.store(limits.max_keys, Ordering::Relaxed);
+    MAX_KEY_LEN.store(limits.max_key_len, Ordering::Relaxed);
+    MAX_VALUES.store(limits.max_values, Ordering::Relaxed);
+    MAX_VALUE_LEN.store(limits.max_value_len, Ordering::Relaxed);
+    MAX_BYTES.store(limits.max_bytes, Ordering::Relaxed);
+}
+
+/// Returns the sizes of the pools of the current thread.
+#[must_use]
+pub fn limits() -> InterningLimits {
+    SCOPED_LIMITS.get().unwrap_or_else(|| InterningLimits {
+        max_keys: MAX_KEYS.load(Ordering::Relaxed),
+        max_key_len: MAX_KEY_LEN.load(Ordering::Relaxed),
+        max_values: MAX_VALUES.load(Ordering::Relaxed),
+        max_value_len: MAX_VALUE_LEN.load(Ordering::Relaxed),
+        max_bytes: MAX_BYTES.load(Ordering::Relaxed),
+    })
+}
+
+/// Runs `f` with the given sizes for the pools of the current thread, leaving the other threads
+/// unaffected.
+pub fn with_limits<T>(limits: InterningLimits, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<InterningLimits>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_LIMITS.set(self.0);
+        }
+    }
+
+    let _restore = Restore(SCOPED_LIMITS.replace(Some(limits)));
+    f()
+}
+
+/// Empties the pools of the current thread, releasing the strings no value holds.
+pub fn clear() {
+    KEYS.with(|keys| keys.borrow_mut().clear());
+    VALUES.with(|values| values.borrow_mut().clear());
+    BYTES.set(0);
+}
+
+/// Reserves room for `len` more bytes in the pools, if they are within the budget.
+fn reserve(len: usize, limits: &InterningLimits) -> bool {
+    let bytes = BYTES.get() + len;
+    if bytes > limits.max_bytes {
+        return false;
+    }
+    BYTES.set(bytes);
+    true
+}
+
+/// Returns the pooled key equal to the string, pooling it if it is short enough and there is
+/// room, or `None` if it isn't pooled.
+pub(super) fn key(key: &str) -> Option<Arc<str>> {
+    let limits = limits();
+    if key.len() > limits.max_key_len {
+        return None;
+    }
+
+    KEYS.with(|keys| {
+        let mut keys = keys.borrow_mut();
+        if let Some(pooled) = keys.get(key) {
+            return Some(Arc::clone(pooled));
+        }
+        if keys.len() >= limits.max_keys || !reserve(key.len(), &limits) {
+            return None;
+        }
+
+        let key = Arc::<str>::from(key);
+        keys.insert(Arc::clone(&key));
+        Some(key)
+    })
+}
+
+/// Returns the pooled string equal to the bytes, pooling them if they are short enough and there
+/// is room, or `None` if they aren't pooled.
+pub(super) fn value(value: &[u8]) -> Option<Bytes> {
+    let limits = limits();
+    if value.len() > limits.max_value_len {
+        return None;
+    }
+
+    VALUES.with(|values| {
+        let mut values = values.borrow_mut();
+        if let Some(pooled) = values.get(value) {
+            return Some(pooled.clone());
+        }
+        if values.len() >= limits.max_values || !reserve(value.len(), &limits) {
+            return None;
+        }
+
+        let value = Bytes::copy_from_slice(value);
+        values.insert(value.clone());
+        Some(value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::{KeyString, Value};
+
+    fn shares_key(key: &str) -> bool {
+        let first = KeyString::from(key);
+        let second = KeyString::from(String::from(key));
+        assert_eq!(first, second);
+        first.as_str().as_ptr() == second.as_str().as_ptr()
+    }
+
+    fn shares_value(value: &str) -> bool {
+        let first: Value = serde_json::from_str(&format!("{value:?}")).unwrap();
+        let second: Value = serde_json::from_str(&format!("{value:?}")).unwrap();
+        assert_eq!(first, second);
+        first.as_bytes().unwrap().as_ptr() == second.as_bytes().unwrap().as_ptr()
+    }
+
+    // The pools and scoped limits belong to the thread running the test, so the tests don't
+    // affect each other.
+    #[test]
+    fn shares_pooled_strings() {
+        with_limits(InterningLimits::default(), || {
+            clear();
+            assert!(shares_key("message"));
+            assert!(!shares_key(&"k".repeat(65)));
+            assert!(shares_value("info"));
+            assert!(!shares_value(
+                "a string longer than the longest pooled string"
+            ));
+        });
+    }
+
+    #[test]
+    fn limits_pooled_bytes() {
+        let limits = InterningLimits {
+            max_bytes: 10,
+            ..InterningLimits::default()
+        };
+
+        with_limits(limits, || {
+            clear();
+            assert!(shares_key("message"));
+            assert!(!shares_key("timestamp"));
+            assert!(shares_value("abc"));
+            assert!(!shares_value("d"));
+
+            clear();
+            assert!(shares_key("timestamp"));
+        });
+    }
+
+    #[test]
+    fn disables_interning() {
+        with_limits(InterningLimits::DISABLED, || {
+            clear();
+            assert_eq!(limits(), InterningLimits::DISABLED);
+            assert!(!shares_key("message"));
+            assert_eq!(value(b"info"), None);
+        });
+    }
+}
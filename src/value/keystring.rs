@@ -1,21 +1,125 @@
 use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
 
-use serde::{Deserialize, Serialize};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+
+use self::repr::Inner;
+
+#[cfg(not(feature = "interning"))]
+mod repr {
+    pub(super) type Inner = String;
+
+    pub(super) fn from_str(s: &str) -> Inner {
+        s.into()
+    }
+
+    pub(super) fn from_string(s: String) -> Inner {
+        s
+    }
+
+    pub(super) fn into_string(inner: Inner) -> String {
+        inner
+    }
+}
+
+/// With the `interning` feature, keys are shared with the other keys created from the same string
+/// while the key pool has room, see [`interning`](super::interning). The keys the pool doesn't hold
+/// are owned strings, as without interning.
+#[cfg(feature = "interning")]
+mod repr {
+    use std::cmp::Ordering;
+    use std::fmt;
+    use std::hash::{Hash, Hasher};
+    use std::ops::Deref;
+    use std::sync::Arc;
+
+    use crate::value::interning;
+
+    #[derive(Clone)]
+    pub(super) enum Inner {
+        Pooled(Arc<str>),
+        Owned(String),
+    }
+
+    pub(super) fn from_str(s: &str) -> Inner {
+        interning::key(s).map_or_else(|| Inner::Owned(s.to_owned()), Inner::Pooled)
+    }
+
+    pub(super) fn from_string(s: String) -> Inner {
+        interning::key(&s).map_or(Inner::Owned(s), Inner::Pooled)
+    }
+
+    pub(super) fn into_string(inner: Inner) -> String {
+        match inner {
+            Inner::Pooled(s) => s.as_ref().into(),
+            Inner::Owned(s) => s,
+        }
+    }
+
+    // The traits compare the strings, as those of `String` do, whether the keys are pooled or not.
+
+    impl Deref for Inner {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            match self {
+                Self::Pooled(s) => s,
+                Self::Owned(s) => s,
+            }
+        }
+    }
+
+    impl PartialEq for Inner {
+        fn eq(&self, other: &Self) -> bool {
+            **self == **other
+        }
+    }
+
+    impl Eq for Inner {}
+
+    impl PartialOrd for Inner {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Inner {
+        fn cmp(&self, other: &Self) -> Ordering {
+            (**self).cmp(&**other)
+        }
+    }
+
+    impl Hash for Inner {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            (**self).hash(state);
+        }
+    }
+
+    impl fmt::Debug for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&**self, f)
+        }
+    }
+
+    impl fmt::Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&**self, f)
+        }
+    }
+}
 
 /// The key type value. This is a simple zero-overhead wrapper set up to make it explicit that
 /// object keys are read-only and their underlying type is opaque and may change for efficiency.
-#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
-#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
-#[serde(transparent)]
-pub struct KeyString(String);
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct KeyString(Inner);
 
 impl KeyString {
     /// Convert the key into a boxed slice of bytes (`u8`).
     #[inline]
     #[must_use]
     pub fn into_bytes(self) -> Box<[u8]> {
-        self.0.into_bytes().into()
+        repr::into_string(self.0).into_bytes().into()
     }
 
     /// Is this string empty?
@@ -73,25 +177,71 @@ impl PartialEq<str> for KeyString {
 
 impl From<&str> for KeyString {
     fn from(s: &str) -> Self {
-        Self(s.into())
+        Self(repr::from_str(s))
     }
 }
 
 impl From<String> for KeyString {
     fn from(s: String) -> Self {
-        Self(s)
+        Self(repr::from_string(s))
     }
 }
 
 impl From<Cow<'_, str>> for KeyString {
     fn from(s: Cow<'_, str>) -> Self {
-        Self(s.into())
+        match s {
+            Cow::Borrowed(s) => s.into(),
+            Cow::Owned(s) => s.into(),
+        }
     }
 }
 
 impl From<KeyString> for String {
     fn from(s: KeyString) -> Self {
-        s.0
+        repr::into_string(s.0)
+    }
+}
+
+impl Serialize for KeyString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = KeyString;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<KeyString, E> {
+                Ok(value.into())
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<KeyString, E> {
+                Ok(value.into())
+            }
+        }
+
+        deserializer.deserialize_string(KeyVisitor)
+    }
+}
+
+#[cfg(any(test, feature = "proptest"))]
+impl proptest::arbitrary::Arbitrary for KeyString {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        <String as proptest::arbitrary::Arbitrary>::Strategy,
+        fn(String) -> Self,
+    >;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        proptest::strategy::Strategy::prop_map(proptest::arbitrary::any::<String>(), Self::from)
     }
 }
 
@@ -122,7 +272,7 @@ mod lua {
 
     impl IntoLua for KeyString {
         fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
-            self.0.into_lua(lua)
+            String::from(self).into_lua(lua)
         }
     }
 }
@@ -6,6 +6,8 @@ mod comparison;
 mod conversion;
 mod crud;
 mod debug;
+#[cfg(any(test, feature = "proptest"))]
+mod strategy;
 
 pub mod merge;
 
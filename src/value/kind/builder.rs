@@ -480,4 +480,33 @@ impl Kind {
         kind.remove_object();
         kind
     }
+
+    /// Remove all states of `other` from the type, and return it.
+    ///
+    /// Collections are removed as a whole if `other` contains them, regardless
+    /// of their contents.
+    #[must_use]
+    pub fn without(&self, other: &Self) -> Self {
+        let mut kind = self.clone();
+        let states: [(bool, fn(&mut Self) -> bool); 10] = [
+            (other.contains_bytes(), Self::remove_bytes),
+            (other.contains_integer(), Self::remove_integer),
+            (other.contains_float(), Self::remove_float),
+            (other.contains_boolean(), Self::remove_boolean),
+            (other.contains_timestamp(), Self::remove_timestamp),
+            (other.contains_regex(), Self::remove_regex),
+            (other.contains_null(), Self::remove_null),
+            (other.contains_undefined(), Self::remove_undefined),
+            (other.contains_array(), Self::remove_array),
+            (other.contains_object(), Self::remove_object),
+        ];
+
+        for (contained, remove) in states {
+            if contained {
+                remove(&mut kind);
+            }
+        }
+
+        kind
+    }
 }
@@ -626,4 +626,16 @@ mod tests {
             assert_eq!(kind.is_exact(), want, "{title}");
         }
     }
+
+    proptest::proptest! {
+        #[test]
+        fn is_superset_is_reflexive(kind: Kind) {
+            proptest::prop_assert_eq!(kind.is_superset(&kind), Ok(()));
+        }
+
+        #[test]
+        fn any_is_superset_of_all(kind: Kind) {
+            proptest::prop_assert_eq!(Kind::any().is_superset(&kind), Ok(()));
+        }
+    }
 }
@@ -1,4 +1,7 @@
-use super::{super::ObjectMap, Kind, Value};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use super::{super::ObjectMap, Collection, Kind, Value};
 
 impl Kind {
     /// Returns a tree representation of `Kind`, in a more human readable format.
@@ -9,6 +12,176 @@ impl Kind {
         insert_kind(&mut output, self, true);
         output
     }
+
+    /// Returns an indented tree of the types this `Kind` can have, with one line
+    /// per known object field or array index.
+    ///
+    /// ```text
+    /// object
+    ///   .foo: integer or null
+    ///   .bar: array
+    ///     [0]: string
+    ///     [*]: boolean
+    /// ```
+    #[must_use]
+    pub fn pretty(&self) -> String {
+        let mut output = type_names(self);
+        write_children(&mut output, self, 1);
+        output
+    }
+
+    /// Describes where `other` diverges from `self`, with one line for each path
+    /// at which `other` has a type that `self` doesn't accept.
+    ///
+    /// Returns an empty list if `self` is a superset of `other`.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let mut differences = vec![];
+        diff_kinds(&mut differences, ".", self, other);
+        differences
+    }
+}
+
+/// The types a `Kind` can have, without the contents of its collections.
+fn type_names(kind: &Kind) -> String {
+    if kind.is_never() {
+        return "never".to_owned();
+    }
+    if kind.is_any() {
+        return "any".to_owned();
+    }
+
+    let names = [
+        (kind.contains_bytes(), "string"),
+        (kind.contains_integer(), "integer"),
+        (kind.contains_float(), "float"),
+        (kind.contains_boolean(), "boolean"),
+        (kind.contains_timestamp(), "timestamp"),
+        (kind.contains_regex(), "regex"),
+        (kind.contains_null(), "null"),
+        (kind.contains_array(), "array"),
+        (kind.contains_object(), "object"),
+        (kind.contains_undefined(), "undefined"),
+    ];
+
+    names
+        .into_iter()
+        .filter_map(|(contains, name)| contains.then_some(name))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+fn write_children(output: &mut String, kind: &Kind, depth: usize) {
+    if kind.is_any() {
+        return;
+    }
+
+    let mut write_child = |segment: String, kind: &Kind| {
+        let indent = "  ".repeat(depth);
+        write!(output, "\n{indent}{segment}: {}", type_names(kind)).expect("write to string");
+        write_children(output, kind, depth + 1);
+    };
+
+    if let Some(fields) = &kind.object {
+        for (field, kind) in fields.known() {
+            write_child(format!(".{field}"), kind);
+        }
+        if !fields.unknown_kind().is_undefined() {
+            write_child(".*".to_owned(), &fields.unknown_kind().without_undefined());
+        }
+    }
+    if let Some(indices) = &kind.array {
+        for (index, kind) in indices.known() {
+            write_child(format!("[{index}]"), kind);
+        }
+        if !indices.unknown_kind().is_undefined() {
+            write_child("[*]".to_owned(), &indices.unknown_kind().without_undefined());
+        }
+    }
+}
+
+fn child_path(path: &str, segment: &str) -> String {
+    match path {
+        "." if segment.starts_with('.') => segment.to_owned(),
+        _ => format!("{path}{segment}"),
+    }
+}
+
+/// The kind without the contents of its collections.
+fn top_level(kind: &Kind) -> Kind {
+    Kind {
+        array: kind.array.as_ref().map(|_| Collection::any()),
+        object: kind.object.as_ref().map(|_| Collection::any()),
+        ..kind.clone()
+    }
+}
+
+fn diff_kinds(differences: &mut Vec<String>, path: &str, expected: &Kind, found: &Kind) {
+    if expected.is_superset(found).is_ok() {
+        return;
+    }
+
+    if top_level(expected).is_superset(&top_level(found)).is_err() {
+        differences.push(format!(
+            "{path}: expected {}, found {}",
+            type_names(expected),
+            type_names(found)
+        ));
+    }
+
+    if let (Some(expected), Some(found)) = (&expected.object, &found.object) {
+        diff_collections(differences, path, expected, found, ".*", |field| {
+            format!(".{field}")
+        });
+    }
+    if let (Some(expected), Some(found)) = (&expected.array, &found.array) {
+        diff_collections(differences, path, expected, found, "[*]", |index| {
+            format!("[{index}]")
+        });
+    }
+}
+
+fn diff_collections<T: Ord + Clone>(
+    differences: &mut Vec<String>,
+    path: &str,
+    expected: &Collection<T>,
+    found: &Collection<T>,
+    unknown_segment: &str,
+    segment: impl Fn(&T) -> String,
+) {
+    let keys = expected
+        .known()
+        .keys()
+        .chain(found.known().keys())
+        .collect::<BTreeSet<_>>();
+
+    for key in keys {
+        let expected_kind = expected
+            .known()
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| expected.unknown_kind());
+        let found_kind = found
+            .known()
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| found.unknown_kind());
+        diff_kinds(
+            differences,
+            &child_path(path, &segment(key)),
+            &expected_kind,
+            &found_kind,
+        );
+    }
+
+    // Unknown fields or indices are always allowed to be missing, so only
+    // the types of the ones that exist are compared.
+    diff_kinds(
+        differences,
+        &child_path(path, unknown_segment),
+        &expected.unknown_kind().without_undefined(),
+        &found.unknown_kind().without_undefined(),
+    );
 }
 
 fn insert_kind(tree: &mut ObjectMap, kind: &Kind, show_unknown: bool) {
@@ -92,3 +265,69 @@ fn insert_if_true(tree: &mut ObjectMap, key: &str, value: bool) {
         tree.insert(key.to_owned().into(), Value::Boolean(true));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn pretty() {
+        let kind = Kind::object(BTreeMap::from([
+            ("foo".into(), Kind::integer().or_null()),
+            (
+                "bar".into(),
+                Kind::array(Collection::from_parts(
+                    BTreeMap::from([(0.into(), Kind::bytes())]),
+                    Kind::boolean(),
+                )),
+            ),
+        ]));
+
+        assert_eq!(
+            kind.pretty(),
+            "object\n  .bar: array\n    [0]: string\n    [*]: boolean\n  .foo: integer or null"
+        );
+        assert_eq!(Kind::any().pretty(), "any");
+        assert_eq!(Kind::bytes().or_timestamp().pretty(), "string or timestamp");
+    }
+
+    #[test]
+    fn diff() {
+        let expected = Kind::object(BTreeMap::from([
+            ("foo".into(), Kind::integer()),
+            (
+                "bar".into(),
+                Kind::object(BTreeMap::from([("baz".into(), Kind::bytes())])),
+            ),
+        ]));
+        let found = Kind::object(BTreeMap::from([
+            ("foo".into(), Kind::integer()),
+            (
+                "bar".into(),
+                Kind::object(BTreeMap::from([("baz".into(), Kind::integer())])),
+            ),
+            ("qux".into(), Kind::boolean()),
+        ]));
+
+        assert_eq!(
+            expected.diff(&found),
+            vec![
+                ".bar.baz: expected string, found integer",
+                ".qux: expected undefined, found boolean",
+            ]
+        );
+        assert_eq!(
+            Kind::bytes().diff(&Kind::bytes().or_integer()),
+            vec![".: expected string, found string or integer"]
+        );
+        assert_eq!(
+            Kind::array(Collection::from_unknown(Kind::integer()))
+                .diff(&Kind::array(Collection::from_unknown(Kind::float()))),
+            vec![".[*]: expected integer, found float"]
+        );
+        assert!(expected.diff(&expected).is_empty());
+        assert!(Kind::any().diff(&found).is_empty());
+    }
+}
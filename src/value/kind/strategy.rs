@@ -0,0 +1,83 @@
+use proptest::prelude::*;
+
+use super::{Collection, Field, Index, Kind};
+use crate::value::KeyString;
+
+const MAX_DEPTH: u32 = 3;
+const MAX_NODES: u32 = 32;
+const MAX_COLLECTION_SIZE: usize = 4;
+const MAX_INDEX: usize = 8;
+
+/// Any combination of the non-collection states, including none at all.
+fn primitives() -> impl Strategy<Value = Kind> {
+    const STATES: [fn(&mut Kind) -> bool; 8] = [
+        Kind::add_bytes,
+        Kind::add_integer,
+        Kind::add_float,
+        Kind::add_boolean,
+        Kind::add_timestamp,
+        Kind::add_regex,
+        Kind::add_null,
+        Kind::add_undefined,
+    ];
+
+    any::<u8>().prop_map(|bits| {
+        let mut kind = Kind::never();
+        for (i, add) in STATES.iter().enumerate() {
+            if bits & (1 << i) != 0 {
+                add(&mut kind);
+            }
+        }
+        kind
+    })
+}
+
+fn object(kind: BoxedStrategy<Kind>) -> impl Strategy<Value = Collection<Field>> {
+    (
+        prop::collection::btree_map(
+            any::<KeyString>().prop_map(Field::from),
+            kind.clone(),
+            0..MAX_COLLECTION_SIZE,
+        ),
+        kind,
+    )
+        .prop_map(|(known, unknown)| Collection::from_parts(known, unknown))
+}
+
+fn array(kind: BoxedStrategy<Kind>) -> impl Strategy<Value = Collection<Index>> {
+    (
+        prop::collection::btree_map(
+            (0..MAX_INDEX).prop_map(Index::from),
+            kind.clone(),
+            0..MAX_COLLECTION_SIZE,
+        ),
+        kind,
+    )
+        .prop_map(|(known, unknown)| Collection::from_parts(known, unknown))
+}
+
+impl Arbitrary for Kind {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        primitives()
+            .prop_recursive(MAX_DEPTH, MAX_NODES, MAX_COLLECTION_SIZE as u32, |inner| {
+                (
+                    primitives(),
+                    prop::option::of(object(inner.clone())),
+                    prop::option::of(array(inner)),
+                )
+                    .prop_map(|(mut kind, object, array)| {
+                        if let Some(object) = object {
+                            kind.add_object(object);
+                        }
+                        if let Some(array) = array {
+                            kind.add_array(array);
+                        }
+                        kind
+                    })
+            })
+            .boxed()
+    }
+}
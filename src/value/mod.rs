@@ -33,6 +33,8 @@
     unused_variables
 )]
 
+#[cfg(feature = "interning")]
+pub mod interning;
 pub mod kind;
 pub mod secrets;
 
@@ -45,7 +47,7 @@ mod keystring;
 pub use kind::Kind;
 
 pub use self::keystring::KeyString;
-pub use self::secrets::Secrets;
+pub use self::secrets::{Secret, SecretError, Secrets};
 pub use self::value::{ObjectMap, Value, ValueRegex};
 
 /// A macro to easily generate Values
@@ -1,13 +1,147 @@
 //! Contains the `Secrets` type.
 
+use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::fmt::{Debug, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::sync::Arc;
 
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+
+/// Unseals the value of a sealed secret, given the key and the sealed value
+/// of the secret.
+pub type Unsealer = dyn Fn(&str, &[u8]) -> Result<Vec<u8>, String> + Send + Sync;
+
+/// A secret of a [`Secrets`] container, with its metadata.
+#[derive(Clone, PartialEq, Eq, PartialOrd)]
+pub struct Secret {
+    value: SecretValue,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd)]
+enum SecretValue {
+    Text(Arc<str>),
+    Bytes(Bytes),
+    Sealed(Bytes),
+}
+
+impl Secret {
+    /// Creates a secret from its plain value, which doesn't need to be UTF-8.
+    #[must_use]
+    pub fn new(value: impl Into<Bytes>) -> Self {
+        let value: Bytes = value.into();
+        let value = std::str::from_utf8(&value).map_or_else(
+            |_| SecretValue::Bytes(value.clone()),
+            |text| SecretValue::Text(text.into()),
+        );
+
+        Self {
+            value,
+            expires_at: None,
+        }
+    }
+
+    /// Creates a secret from its sealed value, such as a value encrypted at
+    /// rest. The value is only unsealed when read, by the unsealer of the
+    /// container, see [`Secrets::set_unsealer`].
+    #[must_use]
+    pub fn sealed(value: impl Into<Bytes>) -> Self {
+        Self {
+            value: SecretValue::Sealed(value.into()),
+            expires_at: None,
+        }
+    }
+
+    /// Sets the time after which the secret can no longer be read.
+    #[must_use]
+    pub fn with_expiry(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Returns whether the secret is sealed.
+    #[must_use]
+    pub fn is_sealed(&self) -> bool {
+        matches!(self.value, SecretValue::Sealed(_))
+    }
+
+    /// Returns the time after which the secret can no longer be read, if any.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self {
+            value: SecretValue::Text(value.into()),
+            expires_at: None,
+        }
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self {
+            value: SecretValue::Text(value.into()),
+            expires_at: None,
+        }
+    }
+}
+
+/// The reason a secret can't be read, see [`Secrets::read`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretError {
+    /// The secret is sealed, and the container has no unsealer.
+    Sealed {
+        /// The key of the secret.
+        key: String,
+    },
+
+    /// The unsealer of the container failed to unseal the secret.
+    Unseal {
+        /// The key of the secret.
+        key: String,
+        /// The error returned by the unsealer.
+        error: String,
+    },
+
+    /// The secret has expired.
+    Expired {
+        /// The key of the secret.
+        key: String,
+    },
+}
+
+impl Display for SecretError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sealed { key } => {
+                write!(
+                    f,
+                    r#"secret "{key}" is sealed and was not unsealed by the host"#
+                )
+            }
+            Self::Unseal { key, error } => write!(f, r#"unable to unseal secret "{key}": {error}"#),
+            Self::Expired { key } => write!(f, r#"secret "{key}" has expired"#),
+        }
+    }
+}
+
+impl std::error::Error for SecretError {}
+
 /// A container that holds secrets accessible from Vector / VRL.
-#[derive(Clone, Default, PartialEq, Eq, PartialOrd)]
+#[derive(Clone, Default)]
 pub struct Secrets {
-    secrets: BTreeMap<String, Arc<str>>,
+    secrets: BTreeMap<String, Secret>,
+    unsealer: Option<Arc<Unsealer>>,
 }
 
 impl Debug for Secrets {
@@ -20,24 +154,102 @@ impl Debug for Secrets {
     }
 }
 
+impl PartialEq for Secrets {
+    fn eq(&self, other: &Self) -> bool {
+        self.secrets == other.secrets
+    }
+}
+
+impl Eq for Secrets {}
+
+impl PartialOrd for Secrets {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.secrets.partial_cmp(&other.secrets)
+    }
+}
+
 impl Secrets {
     /// Creates a new empty secrets container
     #[must_use]
     pub fn new() -> Self {
         Self {
             secrets: BTreeMap::new(),
+            unsealer: None,
         }
     }
 
     /// Gets a secret
+    ///
+    /// Secrets that are sealed, not valid UTF-8 or expired are not returned,
+    /// use [`Secrets::read`] to tell them apart from missing secrets.
     #[must_use]
     pub fn get(&self, key: &str) -> Option<&Arc<str>> {
+        match self.secrets.get(key) {
+            Some(
+                secret @ Secret {
+                    value: SecretValue::Text(value),
+                    ..
+                },
+            ) if !secret.is_expired() => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Gets a secret with its metadata, without unsealing it.
+    #[must_use]
+    pub fn secret(&self, key: &str) -> Option<&Secret> {
         self.secrets.get(key)
     }
 
+    /// Reads the value of a secret, unsealing it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret has expired, or if it is sealed and
+    /// can't be unsealed.
+    pub fn read(&self, key: &str) -> Result<Option<Cow<'_, [u8]>>, SecretError> {
+        let Some(secret) = self.secrets.get(key) else {
+            return Ok(None);
+        };
+
+        if secret.is_expired() {
+            return Err(SecretError::Expired {
+                key: key.to_owned(),
+            });
+        }
+
+        match &secret.value {
+            SecretValue::Text(value) => Ok(Some(Cow::Borrowed(value.as_bytes()))),
+            SecretValue::Bytes(value) => Ok(Some(Cow::Borrowed(value))),
+            SecretValue::Sealed(value) => {
+                let unsealer = self.unsealer.as_ref().ok_or_else(|| SecretError::Sealed {
+                    key: key.to_owned(),
+                })?;
+
+                unsealer(key, value)
+                    .map(|value| Some(Cow::Owned(value)))
+                    .map_err(|error| SecretError::Unseal {
+                        key: key.to_owned(),
+                        error,
+                    })
+            }
+        }
+    }
+
     /// Inserts a new secret into the container.
     pub fn insert(&mut self, key: impl Into<String>, value: impl Into<Arc<str>>) {
-        self.secrets.insert(key.into(), value.into());
+        self.set(
+            key,
+            Secret {
+                value: SecretValue::Text(value.into()),
+                expires_at: None,
+            },
+        );
+    }
+
+    /// Inserts a new secret with its metadata into the container.
+    pub fn set(&mut self, key: impl Into<String>, secret: Secret) {
+        self.secrets.insert(key.into(), secret);
     }
 
     /// Removes a secret
@@ -45,17 +257,31 @@ impl Secrets {
         self.secrets.remove(key);
     }
 
+    /// Sets the function unsealing the sealed secrets when they are read.
+    /// The unsealed values are not kept by the container.
+    pub fn set_unsealer(
+        &mut self,
+        unsealer: impl Fn(&str, &[u8]) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+    ) {
+        self.unsealer = Some(Arc::new(unsealer));
+    }
+
     /// Merged both together. If there are collisions, the value from `self` is kept.
+    /// The unsealer of `other` is only kept if `self` has none.
     pub fn merge(&mut self, other: Self) {
         for (key, value) in other.secrets {
             self.secrets.entry(key).or_insert(value);
         }
+        if self.unsealer.is_none() {
+            self.unsealer = other.unsealer;
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Secrets;
+    use super::{Secret, SecretError, Secrets};
+    use chrono::{Duration, Utc};
 
     #[test]
     fn test_merge() {
@@ -73,4 +299,60 @@ mod test {
         assert_eq!(a.get("key-b").unwrap().as_ref(), "value-b1");
         assert_eq!(a.get("key-c").unwrap().as_ref(), "value-c2");
     }
+
+    #[test]
+    fn test_read() {
+        let mut secrets = Secrets::new();
+        secrets.insert("text", "value");
+        secrets.set("bytes", Secret::new(&b"\xff\x00"[..]));
+        secrets.set("sealed", Secret::sealed("eulav"));
+        secrets.set(
+            "expired",
+            Secret::new("value").with_expiry(Utc::now() - Duration::seconds(1)),
+        );
+
+        assert_eq!(
+            secrets.read("text").unwrap().as_deref(),
+            Some(&b"value"[..])
+        );
+        assert_eq!(
+            secrets.read("bytes").unwrap().as_deref(),
+            Some(&b"\xff\x00"[..])
+        );
+        assert_eq!(secrets.read("missing"), Ok(None));
+        assert_eq!(
+            secrets.read("sealed"),
+            Err(SecretError::Sealed {
+                key: "sealed".to_owned()
+            })
+        );
+        assert_eq!(
+            secrets.read("expired"),
+            Err(SecretError::Expired {
+                key: "expired".to_owned()
+            })
+        );
+
+        // Only plain, unexpired UTF-8 secrets are returned by `get`.
+        assert_eq!(secrets.get("text").unwrap().as_ref(), "value");
+        assert!(secrets.get("bytes").is_none());
+        assert!(secrets.get("sealed").is_none());
+        assert!(secrets.get("expired").is_none());
+
+        secrets.set_unsealer(|key, value| match key {
+            "sealed" => Ok(value.iter().rev().copied().collect()),
+            _ => Err("unknown key".to_owned()),
+        });
+        secrets.set("unknown", Secret::sealed("value"));
+
+        assert_eq!(
+            secrets.read("sealed").unwrap().as_deref(),
+            Some(&b"value"[..])
+        );
+        assert_eq!(
+            secrets.read("unknown").unwrap_err().to_string(),
+            r#"unable to unseal secret "unknown": unknown key"#
+        );
+        assert!(secrets.secret("sealed").unwrap().is_sealed());
+    }
 }
@@ -9,7 +9,7 @@ pub use iter::{IterItem, ValueIter};
 
 pub use super::value::regex::ValueRegex;
 use super::KeyString;
-use crate::path::ValuePath;
+use crate::path::{BorrowedSegment, ValuePath};
 
 mod convert;
 mod crud;
@@ -22,7 +22,11 @@ mod regex;
 mod arbitrary;
 #[cfg(any(test, feature = "lua"))]
 mod lua;
+#[cfg(feature = "python")]
+mod python;
 mod serde;
+#[cfg(any(test, feature = "proptest"))]
+mod strategy;
 
 /// A boxed `std::error::Error`.
 pub type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -144,6 +148,40 @@ impl Value {
         crud::insert(self, (), path_iter, insert_value)
     }
 
+    /// Returns the largest number of `null` elements that inserting at the
+    /// given path pads an array with, to make room for an index past its end.
+    pub fn insert_padding<'a>(&self, path: impl ValuePath<'a>) -> usize {
+        let mut current = Some(self);
+        let mut padding = 0;
+
+        for segment in path.segment_iter() {
+            current = match segment {
+                BorrowedSegment::Field(field) => current
+                    .and_then(Self::as_object)
+                    .and_then(|object| object.get(&*field)),
+                BorrowedSegment::Index(index) => {
+                    let array = current.and_then(Self::as_array).unwrap_or_default();
+                    let required = if index >= 0 {
+                        index as usize + 1
+                    } else {
+                        index.unsigned_abs()
+                    };
+                    padding = padding.max(required.saturating_sub(array.len() + 1));
+
+                    let index = if index >= 0 {
+                        Some(index as usize)
+                    } else {
+                        array.len().checked_sub(index.unsigned_abs())
+                    };
+                    index.and_then(|index| array.get(index))
+                }
+                BorrowedSegment::Invalid => break,
+            };
+        }
+
+        padding
+    }
+
     /// Removes field value specified by the given path and return its value.
     ///
     /// A special case worth mentioning: if there is a nested array and an item is removed
@@ -199,10 +237,12 @@ pub fn timestamp_to_string(timestamp: &DateTime<Utc>) -> String {
 
 #[cfg(test)]
 mod test {
+    use proptest::prelude::*;
     use quickcheck::{QuickCheck, TestResult};
 
     use crate::path;
-    use crate::path::BorrowedSegment;
+    use crate::path::{BorrowedSegment, OwnedSegment, OwnedValuePath};
+    use crate::value::Kind;
 
     use super::*;
 
@@ -282,6 +322,42 @@ mod test {
             .quickcheck(inner as fn(Vec<BorrowedSegment<'static>>) -> TestResult);
     }
 
+    /// Paths below the `root` field, so that the top level stays an object.
+    fn path_below_root() -> impl Strategy<Value = OwnedValuePath> {
+        // Indexes are kept small, as inserting past the end of an array pads it
+        // with `null` values.
+        let segment = prop_oneof![
+            any::<KeyString>().prop_map(OwnedSegment::Field),
+            (0..8_isize).prop_map(OwnedSegment::Index),
+        ];
+
+        prop::collection::vec(segment, 0..6).prop_map(|rest| {
+            let mut segments = vec![OwnedSegment::field("root")];
+            segments.extend(rest);
+            OwnedValuePath::from(segments)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn insert_get_remove_round_trip(
+            root: Value,
+            path in path_below_root(),
+            marker: Value,
+        ) {
+            let mut value = Value::from(BTreeMap::from([("root".into(), root)]));
+
+            value.insert(&path, marker.clone());
+            prop_assert_eq!(value.get(&path), Some(&marker));
+            prop_assert_eq!(value.remove(&path, false), Some(marker));
+        }
+
+        #[test]
+        fn kind_of_value_is_exact(value: Value) {
+            prop_assert!(Kind::from(&value).is_exact());
+        }
+    }
+
     #[test]
     fn partial_ord_value() {
         assert_eq!(
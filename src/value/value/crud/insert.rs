@@ -86,4 +86,15 @@ mod test {
         assert_eq!(value.insert("[-1][1]", 3), None);
         assert_eq!(value, Value::from(json!([2, [null, 3]])));
     }
+
+    #[test]
+    fn test_insert_padding() {
+        let value = Value::from(json!({"a": [1, [2]]}));
+        assert_eq!(value.insert_padding("a[1]"), 0);
+        assert_eq!(value.insert_padding("a[2]"), 0);
+        assert_eq!(value.insert_padding("a[5]"), 3);
+        assert_eq!(value.insert_padding("a[-5]"), 2);
+        assert_eq!(value.insert_padding("a[1][3]"), 2);
+        assert_eq!(value.insert_padding("b[2].c[1]"), 2);
+    }
 }
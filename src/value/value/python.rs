@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use ordered_float::NotNan;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDateTime, PyDict, PyFloat, PyList, PyString, PyTuple};
+
+use crate::value::{KeyString, Value};
+
+impl IntoPy<PyObject> for Value {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            // Bytes that aren't valid UTF-8 can't be represented as `str`.
+            Self::Bytes(b) => match std::str::from_utf8(&b) {
+                Ok(s) => s.into_py(py),
+                Err(_) => PyBytes::new_bound(py, &b).into_py(py),
+            },
+            Self::Regex(regex) => String::from_utf8_lossy(regex.as_bytes_slice()).into_py(py),
+            Self::Integer(i) => i.into_py(py),
+            Self::Float(f) => f.into_inner().into_py(py),
+            Self::Boolean(b) => b.into_py(py),
+            Self::Timestamp(t) => t.into_py(py),
+            Self::Object(m) => m
+                .into_iter()
+                .map(|(k, v)| (String::from(k), v))
+                .collect::<BTreeMap<_, _>>()
+                .into_py(py),
+            Self::Array(a) => a.into_py(py),
+            Self::Null => py.None(),
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for Value {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if ob.is_none() {
+            return Ok(Self::Null);
+        }
+        // `bool` is a subclass of `int`, so it must be checked first.
+        if let Ok(b) = ob.downcast::<PyBool>() {
+            return Ok(Self::Boolean(b.is_true()));
+        }
+        if let Ok(s) = ob.downcast::<PyString>() {
+            return Ok(Self::Bytes(s.to_cow()?.into_owned().into()));
+        }
+        if let Ok(b) = ob.downcast::<PyBytes>() {
+            return Ok(Self::Bytes(b.as_bytes().to_vec().into()));
+        }
+        if let Ok(f) = ob.downcast::<PyFloat>() {
+            return NotNan::new(f.value())
+                .map(Self::Float)
+                .map_err(|_| PyValueError::new_err("NaN not supported"));
+        }
+        if let Ok(i) = ob.extract::<i64>() {
+            return Ok(Self::Integer(i));
+        }
+        if ob.is_instance_of::<PyDateTime>() {
+            // Naive datetimes are assumed to be in UTC.
+            let timestamp = match ob.extract::<DateTime<FixedOffset>>() {
+                Ok(t) => t.with_timezone(&Utc),
+                Err(_) => ob.extract::<NaiveDateTime>()?.and_utc(),
+            };
+            return Ok(Self::Timestamp(timestamp));
+        }
+        if let Ok(dict) = ob.downcast::<PyDict>() {
+            return dict
+                .iter()
+                .map(|(k, v)| {
+                    let key = k
+                        .extract::<String>()
+                        .map_err(|_| PyTypeError::new_err("dict keys must be strings"))?;
+                    Ok((KeyString::from(key), v.extract()?))
+                })
+                .collect::<PyResult<_>>()
+                .map(Self::Object);
+        }
+        if ob.is_instance_of::<PyList>() || ob.is_instance_of::<PyTuple>() {
+            return ob
+                .iter()?
+                .map(|item| item?.extract())
+                .collect::<PyResult<_>>()
+                .map(Self::Array);
+        }
+
+        Err(PyTypeError::new_err(format!(
+            "unsupported Python type: {}",
+            ob.get_type().name()?
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pyo3::types::IntoPyDict;
+
+    use super::*;
+    use crate::value;
+
+    fn round_trip(value: Value) -> Value {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| value.into_py(py).extract(py).unwrap())
+    }
+
+    #[test]
+    fn round_trips() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        for value in [
+            value!(null),
+            value!(true),
+            value!(-3),
+            value!(1.5),
+            value!("text"),
+            value!([1, "a", [null]]),
+            value!({"a": {"b": [false]}}),
+            Value::Timestamp(timestamp),
+        ] {
+            assert_eq!(round_trip(value.clone()), value);
+        }
+    }
+
+    #[test]
+    fn non_utf8_bytes_round_trip_as_bytes() {
+        let value = Value::Bytes(vec![0xff, 0xfe].into());
+        assert_eq!(round_trip(value.clone()), value);
+    }
+
+    #[test]
+    fn from_python() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let value: Value = py
+                .eval_bound(
+                    "{'t': (1, 2), 'd': __import__('datetime').datetime(2024, 1, 2)}",
+                    None,
+                    None,
+                )
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(
+                value,
+                value!({
+                    "t": [1, 2],
+                    "d": (Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap()),
+                })
+            );
+
+            let bad_key = [(1, 2)].into_py_dict_bound(py);
+            assert!(bad_key.extract::<Value>().is_err());
+
+            let nan = py.eval_bound("float('nan')", None, None).unwrap();
+            assert!(nan.extract::<Value>().is_err());
+        });
+    }
+}
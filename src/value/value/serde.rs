@@ -135,11 +135,21 @@ impl<'de> Deserialize<'de> for Value {
             where
                 E: serde::de::Error,
             {
+                #[cfg(feature = "interning")]
+                if let Some(value) = crate::value::interning::value(value.as_bytes()) {
+                    return Ok(Value::Bytes(value));
+                }
+
                 Ok(Value::Bytes(Bytes::copy_from_slice(value.as_bytes())))
             }
 
             #[inline]
             fn visit_string<E>(self, value: String) -> Result<Value, E> {
+                #[cfg(feature = "interning")]
+                if let Some(value) = crate::value::interning::value(value.as_bytes()) {
+                    return Ok(Value::Bytes(value));
+                }
+
                 Ok(Value::Bytes(value.into()))
             }
 
@@ -0,0 +1,53 @@
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use proptest::prelude::*;
+
+use super::Value;
+use crate::value::KeyString;
+
+const MAX_DEPTH: u32 = 3;
+const MAX_NODES: u32 = 32;
+const MAX_COLLECTION_SIZE: usize = 4;
+const MAX_F64_SIZE: f64 = 1_000_000.0;
+
+fn leaf() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        any::<Vec<u8>>().prop_map(|bytes| Value::Bytes(Bytes::from(bytes))),
+        any::<i64>().prop_map(Value::Integer),
+        (-MAX_F64_SIZE..MAX_F64_SIZE).prop_map(Value::from_f64_or_zero),
+        any::<bool>().prop_map(Value::Boolean),
+        // `chrono` doesn't document its valid ranges, so stick to timestamps
+        // that are always representable.
+        (0..32_000_i64, 0..1_000_000_000_u32).prop_map(|(secs, nanos)| {
+            Value::Timestamp(
+                Utc.timestamp_opt(secs, nanos)
+                    .single()
+                    .expect("valid timestamp"),
+            )
+        }),
+        Just(Value::Null),
+    ]
+}
+
+impl Arbitrary for Value {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        // Regexes are left out, as they can't be round-tripped through most
+        // encodings.
+        leaf()
+            .prop_recursive(MAX_DEPTH, MAX_NODES, MAX_COLLECTION_SIZE as u32, |inner| {
+                prop_oneof![
+                    prop::collection::btree_map(
+                        any::<KeyString>(),
+                        inner.clone(),
+                        0..MAX_COLLECTION_SIZE
+                    )
+                    .prop_map(Value::Object),
+                    prop::collection::vec(inner, 0..MAX_COLLECTION_SIZE).prop_map(Value::Array),
+                ]
+            })
+            .boxed()
+    }
+}